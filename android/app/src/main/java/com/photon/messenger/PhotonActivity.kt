@@ -176,6 +176,7 @@ class PhotonActivity : AppCompatActivity(), SurfaceHolder.Callback, Choreographe
     private external fun nativeSetAvatarFromFile(contextPtr: Long, fileBytes: ByteArray)  // Raw image file bytes (preserves ICC profile)
     private external fun nativeRestoreSessionFromVsf(vsfBytes: ByteArray): Int  // Restore session from sticky broadcast VSF capsule — call before nativeInitWithNetwork; returns 1 on success
     private external fun nativeDestroy(contextPtr: Long)
+    private external fun nativeOnLink(link: String)  // A photon://add/… contact link from a VIEW intent — queued in Rust, applied by the tick
 
     // Notification permission request (Android 13+)
     private val notificationPermissionLauncher = registerForActivityResult(
@@ -226,6 +227,21 @@ class PhotonActivity : AppCompatActivity(), SurfaceHolder.Callback, Choreographe
         }
     }
 
+    /** Forward a clicked photon:// contact link (launch or, with singleTask, a later click) down to Rust. */
+    private fun handleLinkIntent(i: android.content.Intent?) {
+        val i = i ?: return
+        val data = i.data ?: return
+        if (i.action == android.content.Intent.ACTION_VIEW && data.scheme.equals("photon", ignoreCase = true)) {
+            nativeOnLink(data.toString())
+        }
+    }
+
+    override fun onNewIntent(intent: android.content.Intent) {
+        super.onNewIntent(intent)
+        setIntent(intent)
+        handleLinkIntent(intent)
+    }
+
     private fun openImagePicker() {
         imagePickerLauncher.launch("image/*")
     }
@@ -237,6 +253,9 @@ class PhotonActivity : AppCompatActivity(), SurfaceHolder.Callback, Choreographe
         // screen can ask the radio for anything (docs/pairing-v2.md).
         PhotonBeacon.init(this)
         PhotonNfc.init(this)
+        PhotonQr.init(this)
+        PhotonVoice.init(this)
+        handleLinkIntent(intent)
 
         // Create custom SurfaceView with InputConnection for IME text input
         val container = FrameLayout(this)
@@ -0,0 +1,48 @@
+package com.photon.messenger
+
+import com.google.mlkit.vision.barcode.common.Barcode
+import com.google.mlkit.vision.codescanner.GmsBarcodeScannerOptions
+import com.google.mlkit.vision.codescanner.GmsBarcodeScanning
+
+/**
+ * Verification-QR scanning (the safety-number check's camera side).
+ *
+ * [startScan] opens the Play-services code scanner — it owns the camera UI and permission, so the app
+ * never holds CAMERA. The code's text (base64url of the card's VSF — text, because scanners hand back
+ * byte-mode payloads mangled thru a charset guess) goes down to Rust via [nativeOnQrScanned] as UTF-8;
+ * the Rust tick decodes and acts on it. Cancel / failure just logs — nothing to undo.
+ *
+ * Registered like PhotonNfc: [init] from PhotonActivity.onCreate caches the JNI bridge.
+ */
+object PhotonQr {
+    private var activity: PhotonActivity? = null
+
+    private external fun nativeInit()
+    private external fun nativeOnQrScanned(bytes: ByteArray)
+
+    /** Called once from PhotonActivity.onCreate (after loadLibrary). */
+    fun init(a: PhotonActivity) {
+        activity = a
+        nativeInit()
+    }
+
+    fun startScan() {
+        val a = activity ?: return
+        a.runOnUiThread {
+            val options = GmsBarcodeScannerOptions.Builder()
+                .setBarcodeFormats(Barcode.FORMAT_QR_CODE)
+                .build()
+            GmsBarcodeScanning.getClient(a, options).startScan()
+                .addOnSuccessListener { code ->
+                    val text = code.rawValue
+                    if (!text.isNullOrEmpty()) {
+                        nativeOnQrScanned(text.toByteArray(Charsets.UTF_8))
+                    } else {
+                        PhotonLog.i("Qr", "scanned code carried no text")
+                    }
+                }
+                .addOnCanceledListener { PhotonLog.i("Qr", "scan cancelled") }
+                .addOnFailureListener { e -> PhotonLog.w("Qr", "scan failed: ${e.message}") }
+        }
+    }
+}
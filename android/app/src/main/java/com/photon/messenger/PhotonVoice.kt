@@ -0,0 +1,158 @@
+package com.photon.messenger
+
+import android.Manifest
+import android.content.pm.PackageManager
+import android.media.MediaPlayer
+import android.media.MediaRecorder
+import android.os.Build
+import android.os.Handler
+import android.os.Looper
+import android.os.SystemClock
+import androidx.core.app.ActivityCompat
+import androidx.core.content.ContextCompat
+import java.io.File
+
+/**
+ * Voice memos (the platform half of Rust's VoiceMemo).
+ *
+ * [startRecording] / [stopRecording] run the system MediaRecorder straight to Ogg-Opus (API 29+ —
+ * older devices simply don't record), sampling the input level every [LEVEL_MS] for the waveform.
+ * The finished clip, its length and the level samples go down to Rust via [nativeOnRecorded]; the
+ * Rust tick sends it. [play] writes a clip to the cache dir, hands MediaPlayer an open descriptor
+ * and deletes the file at once — a played memo never lingers on disk.
+ *
+ * The first press asks for RECORD_AUDIO; the recording starts on the next press once granted.
+ * Registered like PhotonQr: [init] from PhotonActivity.onCreate caches the JNI bridge.
+ */
+object PhotonVoice {
+    private const val LEVEL_MS = 50L
+    private const val AUDIO_REQUEST = 0x70
+
+    private var activity: PhotonActivity? = null
+    private var recorder: MediaRecorder? = null
+    private var player: MediaPlayer? = null
+    private var startedAt = 0L
+    private val levels = java.io.ByteArrayOutputStream()
+    private val handler = Handler(Looper.getMainLooper())
+    private val sampler = object : Runnable {
+        override fun run() {
+            val r = recorder ?: return
+            // getMaxAmplitude is 0..32767 since the last call; keep the top 8 bits.
+            levels.write((r.maxAmplitude shr 7).coerceIn(0, 255))
+            handler.postDelayed(this, LEVEL_MS)
+        }
+    }
+
+    private external fun nativeInit()
+    private external fun nativeOnRecorded(opus: ByteArray, durationMs: Int, levels: ByteArray)
+
+    /** Called once from PhotonActivity.onCreate (after loadLibrary). */
+    fun init(a: PhotonActivity) {
+        activity = a
+        // Clips a crash (or an older build) left behind.
+        clipFile(a, "memo-play.ogg").delete()
+        clipFile(a, "memo-rec.ogg").delete()
+        nativeInit()
+    }
+
+    private fun clipFile(a: PhotonActivity, name: String) = File(a.cacheDir, name)
+
+    fun startRecording() {
+        val a = activity ?: return
+        a.runOnUiThread {
+            if (Build.VERSION.SDK_INT < Build.VERSION_CODES.Q) {
+                PhotonLog.i("Voice", "Opus recording needs Android 10+")
+                return@runOnUiThread
+            }
+            if (ContextCompat.checkSelfPermission(a, Manifest.permission.RECORD_AUDIO) != PackageManager.PERMISSION_GRANTED) {
+                ActivityCompat.requestPermissions(a, arrayOf(Manifest.permission.RECORD_AUDIO), AUDIO_REQUEST)
+                return@runOnUiThread
+            }
+            release()
+            try {
+                val r = MediaRecorder(a)
+                r.setAudioSource(MediaRecorder.AudioSource.MIC)
+                r.setOutputFormat(MediaRecorder.OutputFormat.OGG)
+                r.setAudioEncoder(MediaRecorder.AudioEncoder.OPUS)
+                r.setAudioEncodingBitRate(24_000)
+                r.setAudioSamplingRate(48_000)
+                r.setOutputFile(clipFile(a, "memo-rec.ogg").path)
+                r.prepare()
+                r.start()
+                recorder = r
+                startedAt = SystemClock.elapsedRealtime()
+                levels.reset()
+                handler.postDelayed(sampler, LEVEL_MS)
+            } catch (e: Exception) {
+                PhotonLog.w("Voice", "record start failed: ${e.message}")
+                release()
+            }
+        }
+    }
+
+    fun stopRecording() {
+        val a = activity ?: return
+        a.runOnUiThread {
+            val r = recorder ?: return@runOnUiThread
+            val durationMs = (SystemClock.elapsedRealtime() - startedAt).toInt()
+            val ok = try {
+                r.stop()
+                true
+            } catch (e: RuntimeException) {
+                // stop() throws when nothing was captured (a tap, not a hold).
+                PhotonLog.i("Voice", "recording too short")
+                false
+            }
+            release()
+            val file = clipFile(a, "memo-rec.ogg")
+            if (ok) {
+                nativeOnRecorded(file.readBytes(), durationMs, levels.toByteArray())
+            }
+            file.delete()
+        }
+    }
+
+    fun cancelRecording() {
+        val a = activity ?: return
+        a.runOnUiThread {
+            if (recorder == null) return@runOnUiThread
+            try {
+                recorder?.stop()
+            } catch (_: RuntimeException) {
+            }
+            release()
+            clipFile(a, "memo-rec.ogg").delete()
+        }
+    }
+
+    fun play(opus: ByteArray) {
+        val a = activity ?: return
+        a.runOnUiThread {
+            player?.release()
+            player = null
+            val file = clipFile(a, "memo-play.ogg")
+            try {
+                file.writeBytes(opus)
+                val p = MediaPlayer()
+                player = p
+                // MediaPlayer keeps its own reference to the descriptor, so the file can go as soon as it's set.
+                java.io.FileInputStream(file).use { p.setDataSource(it.fd) }
+                file.delete()
+                p.setOnCompletionListener { it.release(); if (player === it) player = null }
+                p.prepare()
+                p.start()
+            } catch (e: Exception) {
+                PhotonLog.w("Voice", "playback failed: ${e.message}")
+                player?.release()
+                player = null
+                file.delete()
+            }
+        }
+    }
+
+    private fun release() {
+        handler.removeCallbacks(sampler)
+        recorder?.release()
+        recorder = null
+    }
+}
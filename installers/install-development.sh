@@ -238,12 +238,13 @@ Version=1.0
 Type=Application
 Name=Photon Messenger
 Comment=Decentralized secure messaging with passless authentication (DEV)
-Exec=$INSTALL_DIR/$BINARY_NAME
+Exec=$INSTALL_DIR/$BINARY_NAME %u
 Icon=photon-messenger
 Terminal=false
 Categories=Network;InstantMessaging;
 Keywords=messenger;chat;encryption;p2p;
 StartupWMClass=photon-messenger
+MimeType=x-scheme-handler/photon;
 EOF
 
     chmod +x "$DESKTOP_FILE"
@@ -253,6 +254,11 @@ EOF
         update-desktop-database "$DESKTOP_DIR" 2>/dev/null || true
     fi
 
+    # Open photon://add/… contact links with Photon
+    if command -v xdg-mime >/dev/null 2>&1; then
+        xdg-mime default photon-messenger.desktop x-scheme-handler/photon 2>/dev/null || true
+    fi
+
     # Update icon cache
     if command -v gtk-update-icon-cache >/dev/null 2>&1; then
         gtk-update-icon-cache -f -t "$HOME/.local/share/icons/hicolor" 2>/dev/null || true
@@ -94,6 +94,14 @@ $Shortcut.WorkingDirectory = $installDir
 $Shortcut.Save()
 
 Write-Host "[OK] Start Menu shortcut created" -ForegroundColor Green
+
+# Register the photon:// scheme (per-user, no elevation) so clicking a photon://add/... contact link opens Photon
+$protoKey = "HKCU:\Software\Classes\photon"
+New-Item -Path "$protoKey\shell\open\command" -Force | Out-Null
+Set-ItemProperty -Path $protoKey -Name "(Default)" -Value "URL:Photon contact link"
+Set-ItemProperty -Path $protoKey -Name "URL Protocol" -Value ""
+Set-ItemProperty -Path "$protoKey\shell\open\command" -Name "(Default)" -Value "`"$binaryPath`" `"%1`""
+Write-Host "[OK] photon:// links registered" -ForegroundColor Green
 Write-Host ""
 
 # Clean up
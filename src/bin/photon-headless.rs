@@ -0,0 +1,73 @@
+//! photon-headless — drive this device's Photon vault without a window (CI, scripting).
+//!
+//! Opens the remembered session's vault the way the app does on launch (`HeadlessProfile::resume`), so it needs a device that has attested and isn't logged out. It refuses to run while the app holds the same data dir — two engines on one vault race each other; talk to a running app thru its rpc socket instead (`platform::rpc`).
+//!
+//! Nothing here touches the network. `send` queues the message in the contact's outbox; the app sends it the first tick it holds that contact's friendship chain, and `pending` lists what's still owed.
+//!
+//! Usage: `photon-headless COMMAND`
+//!   contacts               list friends: party id prefix and petname
+//!   messages HANDLE        the conversation with HANDLE, oldest first
+//!   pending HANDLE         messages queued to HANDLE the app hasn't sent yet
+//!   send HANDLE TEXT...    queue TEXT (the remaining args, space-joined) to HANDLE
+//!
+//! Honors `PHOTON_DATA_DIR` like the app. Exit status: 0 ok, 1 failure, 2 usage.
+
+use photon_messenger::headless::HeadlessProfile;
+
+const USAGE: &str = "usage: photon-headless contacts | messages HANDLE | pending HANDLE | send HANDLE TEXT...";
+
+fn fail(msg: impl std::fmt::Display) -> ! {
+    eprintln!("photon-headless: {}", msg);
+    std::process::exit(1);
+}
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    std::process::exit(2);
+}
+
+fn print_messages(messages: &[photon_messenger::types::ChatMessage]) {
+    for m in messages {
+        let dir = if m.is_outgoing { ">" } else { "<" };
+        let state = if !m.is_outgoing || m.delivered { "" } else { "  (undelivered)" };
+        println!("{}  {} {}{}", m.timestamp, dir, m.content, state);
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some(command) = args.first().map(String::as_str) else {
+        usage();
+    };
+    let handle = || args.get(1).map(String::as_str).unwrap_or_else(|| usage());
+
+    let data_dir = photon_messenger::storage::photon_config_dir().unwrap_or_else(|e| fail(format!("no data dir: {}", e)));
+    let Some(_lock) = photon_messenger::storage::acquire_single_instance(&data_dir) else {
+        fail("Photon is running on this data dir — use its rpc socket instead");
+    };
+    let mut profile = match HeadlessProfile::resume() {
+        Some(Ok(profile)) => profile,
+        Some(Err(e)) => fail(format!("can't open the vault: {}", e)),
+        None => fail("no remembered session on this device — attest in the app first"),
+    };
+
+    match command {
+        "contacts" => {
+            for c in profile.contacts().iter().filter(|c| !c.is_sibling) {
+                println!("{}  {}", hex::encode(&c.handle_hash[..8]), c.petname);
+            }
+        }
+        "messages" => print_messages(&profile.messages(handle()).unwrap_or_else(|e| fail(e))),
+        "pending" => print_messages(&profile.pending(handle()).unwrap_or_else(|e| fail(e))),
+        "send" => {
+            let handle = handle();
+            let text = args[2..].join(" ");
+            if text.is_empty() {
+                usage();
+            }
+            profile.queue_message(handle, &text, vsf::eagle_time_oscillations()).unwrap_or_else(|e| fail(e));
+            println!("queued — the app sends it once its chain with {} is ready", handle);
+        }
+        _ => usage(),
+    }
+}
@@ -301,7 +301,7 @@ pub fn verify_ack_proof(
     received_proof: &[u8; 32],
 ) -> bool {
     let expected = generate_ack_proof(eagle_time, plaintext_hash, chain);
-    constant_time_eq(&expected, received_proof)
+    crate::crypto::ct::eq(&expected, received_proof)
 }
 
 // ============================================================================
@@ -467,18 +467,6 @@ pub fn generate_scratch_at_offset(chain: &Chain, salt: &[u8; 32], offset: usize)
 
 // ============================================================================
 
-// // Utility Functions ============================================================================
-/// Constant-time equality comparison
-fn constant_time_eq(a: &[u8; 32], b: &[u8; 32]) -> bool {
-    let mut diff = 0u8;
-    for (x, y) in a.iter().zip(b.iter()) {
-        diff |= x ^ y;
-    }
-    diff == 0
-}
-
-// ============================================================================
-
 // // Tests ============================================================================
 #[cfg(test)]
 mod tests {
@@ -1474,10 +1474,8 @@ pub fn compute_clutch_proof(seed: &Seed) -> [u8; 32] {
 
 /// Verify the clutch completion proof matches our derived seed.
 pub fn verify_clutch_proof(seed: &Seed, proof: &[u8; 32]) -> bool {
-    use subtle::ConstantTimeEq;
     let expected = compute_clutch_proof(seed);
-    // Actually constant-time comparison (subtle crate)
-    expected.ct_eq(proof).into()
+    crate::crypto::ct::eq(&expected, proof)
 }
 
 /// Full clutch ceremony result
@@ -1667,9 +1665,8 @@ pub fn compute_eggs_proof(eggs: &ClutchEggs) -> [u8; 32] {
 
 /// Verify CLUTCH proof matches our eggs.
 pub fn verify_eggs_proof(eggs: &ClutchEggs, proof: &[u8; 32]) -> bool {
-    use subtle::ConstantTimeEq;
     let expected = compute_eggs_proof(eggs);
-    expected.ct_eq(proof).into()
+    crate::crypto::ct::eq(&expected, proof)
 }
 
 #[cfg(test)]
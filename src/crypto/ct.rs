@@ -0,0 +1,63 @@
+//! Constant-time equality for secrets and the values a peer hands us to match against ours — CLUTCH and ACK proofs, conversation tokens, provenance and data hashes.
+//!
+//! `==` on byte arrays stops at the first differing byte, so how long a mismatch takes tells a prober how much of its guess was right. [`eq`] reads every byte whatever it finds. Lengths aren't secret: different lengths are unequal straight away.
+
+use subtle::ConstantTimeEq;
+
+#[cfg(test)]
+thread_local! {
+    static CALLS: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+}
+
+/// `a == b`, in time that depends only on the lengths. Takes arrays, slices and references to either.
+pub fn eq(a: impl AsRef<[u8]>, b: impl AsRef<[u8]>) -> bool {
+    #[cfg(test)]
+    CALLS.with(|c| c.set(c.get() + 1));
+    a.as_ref().ct_eq(b.as_ref()).into()
+}
+
+/// Comparisons this thread has made thru [`eq`] — lets a test pin a check to it.
+#[cfg(test)]
+pub fn calls() -> u64 {
+    CALLS.with(|c| c.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eq_matches_plain_equality() {
+        let a = [0x5A; 32];
+        let mut first = a;
+        first[0] ^= 0x80;
+        let mut last = a;
+        last[31] ^= 0x01;
+        assert!(eq(&a, &[0x5A; 32]));
+        assert!(!eq(&a, &first));
+        assert!(!eq(&a, &last));
+        assert!(!eq(&a, &a[..31]));
+        assert!(eq(b"", b""));
+    }
+
+    #[test]
+    fn proof_and_transfer_checks_go_through_eq() {
+        use crate::crypto::clutch::{compute_eggs_proof, verify_eggs_proof, ClutchEggs};
+        use crate::network::pt::ReceiveBuffer;
+
+        let eggs = ClutchEggs { eggs: vec![[0x11; 32], [0x22; 32]] };
+        let proof = compute_eggs_proof(&eggs);
+        let before = calls();
+        assert!(verify_eggs_proof(&eggs, &proof));
+        assert!(!verify_eggs_proof(&eggs, &[0; 32]));
+        assert_eq!(calls() - before, 2, "CLUTCH proof check");
+
+        // A PT transfer's final hash, checked when its last packet lands.
+        let data = [0x33; 100];
+        let mut buf = ReceiveBuffer::new(1, 1024, 100, *blake3::hash(&data).as_bytes());
+        let before = calls();
+        assert!(buf.insert(0, &data));
+        assert!(buf.is_intact());
+        assert_eq!(calls() - before, 1, "transfer hash check");
+    }
+}
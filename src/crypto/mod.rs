@@ -1,7 +1,10 @@
 pub mod blind;
 pub mod chain;
 pub mod clutch;
+pub mod ct;
 pub mod handle_proof;
 pub mod keys;
+pub mod safety;
 pub mod self_verify;
+pub mod selftest;
 pub mod shards;
@@ -0,0 +1,66 @@
+//! Safety numbers — the out-of-band check that the friend we pinned is the friend we meant.
+//!
+//! A handle lookup hands us a pubkey; nothing in-band proves a MITM on that lookup didn't hand us theirs. Both ends derive the same short number from each side's pinned DEVICE key (the key CLUTCH offers are signed with and checked against — `Contact::public_identity` for them, our device key for us) and compare it in person or over a trusted channel: a match means each side pinned the other's real key. The party ids go in too, binding the number to the two identities, but they can't carry it alone — a party id derives from the handle string ([`crate::crypto::clutch::identity_party_id`]), so anyone who knows the handle computes it, and a lookup MITM that swaps only the device key would leave a party-id-only number unchanged.
+//!
+//! The number belongs to a device pair: compare it on the devices that met (the one each side pinned).
+
+/// Digit groups in a safety number — 12 × 5 decimal digits, read aloud in threes of four.
+pub const SAFETY_GROUPS: usize = 12;
+/// Hash bytes behind each group. 5 bytes (40 bits) reduced mod 10⁵ keeps the modulo bias under 1 part in 10⁷.
+const GROUP_BYTES: usize = 5;
+/// Decimal digits per group.
+const GROUP_MOD: u64 = 100_000;
+
+/// The safety number for a friendship, as 12 five-digit groups, from each side's `(party id, pinned device pubkey)`. Symmetric: the two sides are sorted before hashing, so each computes the identical number from its own view (ours, theirs).
+pub fn safety_number(a: (&[u8; 32], &[u8; 32]), b: (&[u8; 32], &[u8; 32])) -> [u32; SAFETY_GROUPS] {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(b"photon_safety_number_v2");
+    for (party, device) in [lo, hi] {
+        hasher.update(party);
+        hasher.update(device);
+    }
+    let mut out = [0u8; SAFETY_GROUPS * GROUP_BYTES];
+    hasher.finalize_xof().fill(&mut out);
+    let mut groups = [0u32; SAFETY_GROUPS];
+    for (g, chunk) in groups.iter_mut().zip(out.chunks_exact(GROUP_BYTES)) {
+        let v = chunk.iter().fold(0u64, |acc, &byte| (acc << 8) | byte as u64);
+        *g = (v % GROUP_MOD) as u32;
+    }
+    groups
+}
+
+/// The groups as display lines: three lines of four zero-padded groups ("01234 56789 ..."), the layout both screens draw so a side-by-side comparison lines up.
+pub fn safety_number_lines(groups: &[u32; SAFETY_GROUPS]) -> [String; 3] {
+    let line = |row: usize| {
+        groups[row * 4..row * 4 + 4]
+            .iter()
+            .map(|g| format!("{:05}", g))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    [line(0), line(1), line(2)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn both_sides_compute_the_same_number_regardless_of_order() {
+        let alice = (crate::crypto::clutch::identity_party_id(&[0x11; 32]), [0xA1; 32]);
+        let bob = (crate::crypto::clutch::identity_party_id(&[0x22; 32]), [0xB1; 32]);
+        let number = safety_number((&alice.0, &alice.1), (&bob.0, &bob.1));
+        assert_eq!(number, safety_number((&bob.0, &bob.1), (&alice.0, &alice.1)));
+        // The MITM case: the lookup hands Alice Mallory's device key under Bob's party id (which anyone knowing the handle derives). The number must move.
+        let mallory_device = [0x33; 32];
+        assert_ne!(number, safety_number((&alice.0, &alice.1), (&bob.0, &mallory_device)));
+        // And the same for a swap on Bob's view of Alice.
+        assert_ne!(number, safety_number((&alice.0, &mallory_device), (&bob.0, &bob.1)));
+        let lines = safety_number_lines(&number);
+        for l in &lines {
+            assert_eq!(l.len(), 4 * 5 + 3);
+            assert!(l.split(' ').all(|g| g.len() == 5 && g.bytes().all(|c| c.is_ascii_digit())));
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Runtime self-test of the crypto stack — the `selftest` CLI argument.
+//!
+//! `self_verify` proves the binary is the one we signed; this proves the primitives inside it actually compute. Exotic targets (a new libc, a soft-float ARM, a miscompiled SIMD path) can ship a binary that verifies fine but derives garbage — which surfaces as a CLUTCH that never completes or a chat that never decrypts, with nothing pointing at the cause. Each check here is independent and reported on its own line, so a user can paste the output into a bug report.
+//!
+//! Two kinds of check: published known-answer vectors where one exists for the exact primitive we call (BLAKE3, ChaCha20, X25519), and full round trips for the rest (every CLUTCH KEM/ECDH keygen → encapsulate → decapsulate, and a chain encrypt/decrypt).
+
+use crate::crypto::chain::{
+    decrypt_layers, derive_salt, encrypt_layers, generate_scratch, Chain, ACTIVE_LINKS,
+    CURRENT_KEY_INDEX, LINK_SIZE,
+};
+use crate::crypto::clutch::{
+    generate_all_ephemeral_keypairs, x25519_ecdh, ClutchKemResponsePayload,
+    ClutchKemSharedSecrets, ClutchOfferPayload,
+};
+
+/// One line of the report: which primitive, and whether it computed what it must.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Check {
+    pub name: &'static str,
+    pub pass: bool,
+}
+
+/// Run every check. Takes a second or so — the McEliece keygen dominates.
+pub fn run() -> Vec<Check> {
+    let mut checks = vec![
+        Check { name: "blake3 (known vector)", pass: blake3_vector() },
+        Check { name: "chacha20 (RFC 8439 vector)", pass: chacha20_vector() },
+        Check { name: "x25519 (RFC 7748 vector)", pass: x25519_vector() },
+    ];
+    checks.extend(clutch_round_trips());
+    checks.push(Check { name: "chain encrypt/decrypt", pass: chain_round_trip() });
+    checks
+}
+
+/// Print one PASS/FAIL line per check; `true` when everything passed.
+pub fn report(checks: &[Check]) -> bool {
+    for c in checks {
+        println!("{}  {}", if c.pass { "PASS" } else { "FAIL" }, c.name);
+    }
+    let failed = checks.iter().filter(|c| !c.pass).count();
+    if failed == 0 {
+        println!("selftest: all {} checks passed", checks.len());
+    } else {
+        println!("selftest: {} of {} checks FAILED", failed, checks.len());
+    }
+    failed == 0
+}
+
+/// BLAKE3 of the empty input, from the reference implementation's test vectors.
+fn blake3_vector() -> bool {
+    hex::encode(blake3::hash(b"").as_bytes())
+        == "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+}
+
+/// RFC 8439 §2.4.2: key 00..1f, nonce 00000000 0000004a 00000000, block counter 1, the "Ladies and Gentlemen" plaintext. The first 32 ciphertext bytes are enough to catch a broken keystream.
+fn chacha20_vector() -> bool {
+    use chacha20::cipher::{KeyIvInit, StreamCipher, StreamCipherSeek};
+    let key: [u8; 32] = std::array::from_fn(|i| i as u8);
+    let nonce = [0, 0, 0, 0, 0, 0, 0, 0x4a, 0, 0, 0, 0];
+    let mut buf = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.".to_vec();
+    let mut cipher = chacha20::ChaCha20::new(&key.into(), &nonce.into());
+    cipher.seek(64u32); // block counter 1
+    cipher.apply_keystream(&mut buf);
+    hex::encode(&buf[..32]) == "6e2e359a2568f98041ba0728dd0d6981e97e7aec1d4360c20a27afccfd9fae0b"
+}
+
+/// RFC 7748 §6.1: both directions of the Alice/Bob exchange must land on the published shared secret.
+fn x25519_vector() -> bool {
+    let key = |h: &str| -> [u8; 32] {
+        hex::decode(h).ok().and_then(|v| v.try_into().ok()).unwrap_or([0; 32])
+    };
+    let alice_secret = key("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+    let alice_public = key("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a");
+    let bob_secret = key("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb");
+    let bob_public = key("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f");
+    let shared = key("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+    x25519_ecdh(&alice_secret, &bob_public) == shared && x25519_ecdh(&bob_secret, &alice_public) == shared
+}
+
+/// The real CLUTCH path, both ends in-process: keygen all eight, offer, encapsulate to the offer, decapsulate with our secrets. Each algorithm is its own check — the two sides must agree on a non-empty secret.
+fn clutch_round_trips() -> Vec<Check> {
+    let mut keys = generate_all_ephemeral_keypairs();
+    let offer = ClutchOfferPayload::from_keypairs(&keys);
+    let (response, mut sent) = ClutchKemResponsePayload::encapsulate_to_peer(&offer);
+    let mut got = ClutchKemSharedSecrets::decapsulate_from_peer(&response, &keys);
+    let agree = |a: &[u8], b: &[u8]| !a.is_empty() && a == b && a.iter().any(|&x| x != 0);
+    let checks = vec![
+        Check { name: "clutch frodo976 keygen/encap/decap", pass: agree(&sent.frodo, &got.frodo) },
+        Check { name: "clutch ntru701 keygen/encap/decap", pass: agree(&sent.ntru, &got.ntru) },
+        Check { name: "clutch mceliece460896 keygen/encap/decap", pass: agree(&sent.mceliece, &got.mceliece) },
+        Check { name: "clutch hqc256 keygen/encap/decap", pass: agree(&sent.hqc, &got.hqc) },
+        Check { name: "clutch x25519 ecdh", pass: agree(&sent.x25519[..], &got.x25519[..]) },
+        Check { name: "clutch p384 ecdh", pass: agree(&sent.p384, &got.p384) },
+        Check { name: "clutch secp256k1 ecdh", pass: agree(&sent.secp256k1, &got.secp256k1) },
+        Check { name: "clutch p256 ecdh", pass: agree(&sent.p256, &got.p256) },
+    ];
+    keys.zeroize();
+    sent.zeroize();
+    got.zeroize();
+    checks
+}
+
+/// One message thru the chain's three layers and back, exactly as `prepare_send` and the receive path do it. The ciphertext must differ from the plaintext (the layers did something) and decrypt back byte-exact.
+fn chain_round_trip() -> bool {
+    let bytes: Vec<u8> = (0..ACTIVE_LINKS * LINK_SIZE).map(|i| (i * 7 % 251) as u8).collect();
+    let Some(chain) = Chain::from_bytes(&bytes) else {
+        return false;
+    };
+    let plaintext = b"photon selftest: the quick brown fox".to_vec();
+    let salt = derive_salt(b"previous message", &chain);
+    let scratch = generate_scratch(&chain, &salt);
+    let et = vsf::EagleTime::from_oscillations(1 << 40);
+    let ciphertext = encrypt_layers(&plaintext, &chain, &scratch, &et);
+    let decrypted = decrypt_layers(&ciphertext, &chain, CURRENT_KEY_INDEX, &scratch, &et);
+    ciphertext != plaintext && decrypted == plaintext
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selftest_passes_on_a_healthy_build() {
+        let checks = run();
+        let failed: Vec<&str> = checks.iter().filter(|c| !c.pass).map(|c| c.name).collect();
+        assert!(failed.is_empty(), "failed: {:?}", failed);
+        assert!(checks.len() >= 12);
+        assert!(report(&checks));
+    }
+}
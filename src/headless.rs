@@ -0,0 +1,284 @@
+//! Headless profile: a Photon identity's vault driven without a window — for CI, scripting and the control socket. It opens storage the same way `PhotonApp` does (finish any interrupted compaction, then `open_shared`), and manages contacts and conversations thru the same `storage::contacts` calls, so a profile touched headless is byte-for-byte the profile the GUI loads next.
+//!
+//! The network is two seams. [`Fgtw`] attests this device and resolves a friend's handle to their device ([`LiveFgtw`] is the real directory). [`Wire`] is where a send leaves — PT's message queue, i.e. the app's [`StatusChecker`]. A test stands in a stub for each.
+//!
+//! CLUTCH is NOT here: the ceremony is stepped by `PhotonApp::tick`. A message queued headless goes into the contact's outbox (`storage::contacts::save_outbox`), not the conversation. [`HeadlessProfile::flush`] sends it once the vault holds that contact's finished friendship chain — sealed on the chain exactly as the app's compose path does, the chain saved before the send, the conversation row written after. Until then the app drains the outbox the first tick it holds the chain. The `photon-headless` binary is the command-line front end.
+
+use crate::network::fgtw::{Keypair, PeerRecord};
+use crate::network::status::{MessageRequest, StatusChecker};
+use crate::storage::contacts::{load_all_contacts, load_conversation, load_outbox, save_contact, save_messages_page, save_outbox};
+use crate::storage::{FlatStorage, StorageError};
+use crate::types::{ChatMessage, ClutchState, Contact, DevicePubkey, Handle, HandleText};
+use std::sync::Arc;
+
+/// The party id a handle's contact is keyed by — the same derivation `Contact::new` does.
+fn party_id(handle: &str) -> [u8; 32] {
+    crate::crypto::clutch::identity_party_id(&crate::types::Handle::to_identity_seed(handle))
+}
+
+/// FGTW as a headless profile uses it: attest this device, and find the device a friend's handle is attested on.
+pub trait Fgtw {
+    /// Announce this device for `session` — the GUI's attest.
+    fn attest(&mut self, session: &tohu::SessionIdentity, device: &Keypair) -> Result<(), String>;
+
+    /// The device `handle_proof` is attested on; `None` when FGTW knows of none.
+    fn lookup(&mut self, handle_proof: &[u8; 32]) -> Result<Option<PeerRecord>, String>;
+}
+
+/// Where a headless send leaves: PT's message queue.
+pub trait Wire {
+    fn send(&mut self, request: MessageRequest);
+}
+
+impl Wire for StatusChecker {
+    fn send(&mut self, request: MessageRequest) {
+        self.send_message(request);
+    }
+}
+
+/// Photon's FGTW: the announce the GUI's attest makes (`bootstrap::load_bootstrap_peers`), with the peer list it answers as the lookup table — re-announced once for a handle it doesn't hold yet, like the app's search.
+pub struct LiveFgtw {
+    port: u16,
+    announced: Option<([u8; 32], [u8; 32], Keypair)>,
+    peers: Vec<PeerRecord>,
+}
+
+impl LiveFgtw {
+    /// Announce `port` as where this device listens.
+    pub fn new(port: u16) -> Self {
+        Self { port, announced: None, peers: Vec::new() }
+    }
+
+    fn announce(&mut self, handle_proof: [u8; 32], identity_seed: [u8; 32], device: &Keypair) -> Result<(), String> {
+        let result = crate::network::http::runtime().block_on(crate::network::fgtw::bootstrap::load_bootstrap_peers(device, handle_proof, self.port, &identity_seed));
+        if let Some(e) = result.error {
+            return Err(e);
+        }
+        self.peers = result.peers;
+        self.announced = Some((handle_proof, identity_seed, device.clone()));
+        Ok(())
+    }
+}
+
+impl Fgtw for LiveFgtw {
+    fn attest(&mut self, session: &tohu::SessionIdentity, device: &Keypair) -> Result<(), String> {
+        self.announce(session.handle_proof, session.identity_seed, device)
+    }
+
+    fn lookup(&mut self, handle_proof: &[u8; 32]) -> Result<Option<PeerRecord>, String> {
+        let find = |peers: &[PeerRecord]| peers.iter().find(|p| p.handle_proof == *handle_proof).cloned();
+        if let Some(peer) = find(&self.peers) {
+            return Ok(Some(peer));
+        }
+        let Some((hp, seed, device)) = self.announced.clone() else {
+            return Err("attest before looking anyone up".to_string());
+        };
+        self.announce(hp, seed, &device)?;
+        Ok(find(&self.peers))
+    }
+}
+
+pub struct HeadlessProfile {
+    storage: Arc<FlatStorage>,
+    contacts: Vec<Contact>,
+    /// Our identity seed, when the profile was opened for a session ([`Self::attest`] / [`Self::resume`]) — what a send is sealed as. A bare [`Self::open`] only queues.
+    identity_seed: Option<[u8; 32]>,
+}
+
+impl HeadlessProfile {
+    /// Open the vault for `vault_seed` on this device. Same open sequence as the GUI's resume path (`compact::open_shared`): an interrupted compaction is finished first — a vault whose journal can't be finished stays closed — then the shared engine (never `new` — a GUI instance or attest worker on the same vault must see one engine, not two racing ones).
+    pub fn open(vault_seed: [u8; 32], device_secret: [u8; 32]) -> Result<Self, StorageError> {
+        let storage = crate::storage::compact::open_shared(vault_seed, device_secret)?;
+        let contacts = load_all_contacts(&storage);
+        Ok(Self { storage, contacts, identity_seed: None })
+    }
+
+    /// Attest `handle` on this device thru `fgtw`, then open its vault — the GUI's first attest, minus remembering the session (nothing is written to tohu; [`Self::resume`] won't find it).
+    pub fn attest(handle: &str, device: &Keypair, fgtw: &mut impl Fgtw) -> Result<Self, String> {
+        let identity_seed = crate::storage::contacts::derive_identity_seed(handle);
+        let session = tohu::SessionIdentity { identity_seed, vault_seed: identity_seed, handle_proof: Handle::username_to_handle_proof(handle) };
+        fgtw.attest(&session, device)?;
+        let mut profile = Self::open(session.vault_seed, *device.secret.as_bytes()).map_err(|e| e.to_string())?;
+        profile.identity_seed = Some(identity_seed);
+        Ok(profile)
+    }
+
+    /// Open the remembered session's vault with this machine's device key — what the GUI would load on launch. `None` when nobody has attested on this device yet (or after logout).
+    #[cfg(not(target_os = "android"))]
+    pub fn resume() -> Option<Result<Self, StorageError>> {
+        let session = tohu::session()?;
+        let fingerprint = crate::network::fgtw::get_machine_fingerprint().ok()?;
+        let keypair = crate::network::fgtw::derive_device_keypair(&fingerprint);
+        Some(Self::open(session.vault_seed, *keypair.secret.as_bytes()).map(|mut profile| {
+            profile.identity_seed = Some(session.identity_seed);
+            profile
+        }))
+    }
+
+    pub fn storage(&self) -> &FlatStorage {
+        &self.storage
+    }
+
+    pub fn contacts(&self) -> &[Contact] {
+        &self.contacts
+    }
+
+    /// The contact whose handle is `handle` (matched by party id — contacts don't keep the handle string).
+    pub fn contact(&self, handle: &str) -> Option<&Contact> {
+        let pid = party_id(handle);
+        self.contacts.iter().find(|c| c.handle_hash == pid)
+    }
+
+    /// Add (or refresh) a friend from what a handle lookup returns: the handle, its attested proof and their device key. Persisted immediately; the CLUTCH ceremony starts when the GUI next ticks with this contact in its list.
+    pub fn add_contact(&mut self, handle: &str, handle_proof: [u8; 32], device: DevicePubkey) -> Result<&Contact, StorageError> {
+        self.put_contact(Contact::new(HandleText::new(handle), handle_proof, device))
+    }
+
+    /// Look `handle` up on `fgtw` and add them as a friend at the device and address they're attested with — the GUI's add-by-handle.
+    pub fn add_friend(&mut self, handle: &str, fgtw: &mut impl Fgtw) -> Result<&Contact, String> {
+        let handle_proof = Handle::username_to_handle_proof(handle);
+        let Some(peer) = fgtw.lookup(&handle_proof)? else {
+            return Err(format!("'{}' isn't attested on FGTW", handle));
+        };
+        let mut contact = Contact::new(HandleText::new(handle), handle_proof, peer.device_pubkey);
+        contact.ip = Some(peer.ip);
+        contact.local_ip = match peer.local_ip {
+            Some(std::net::IpAddr::V4(v4)) => Some(v4),
+            _ => None,
+        };
+        self.put_contact(contact).map_err(|e| e.to_string())
+    }
+
+    fn put_contact(&mut self, contact: Contact) -> Result<&Contact, StorageError> {
+        save_contact(&contact, &self.storage)?;
+        let handle_proof = contact.handle_proof;
+        let i = match self.contacts.iter().position(|c| c.handle_proof == handle_proof) {
+            Some(i) => {
+                self.contacts[i] = contact;
+                i
+            }
+            None => {
+                self.contacts.push(contact);
+                self.contacts.len() - 1
+            }
+        };
+        Ok(&self.contacts[i])
+    }
+
+    /// Queue `text` to `handle` at eagle time `now` in the contact's outbox, for the app to send on its next tick with that contact's chain. Returns the message as [`pending`](Self::pending) will list it. Errors if `handle` isn't a contact or `text` is empty.
+    pub fn queue_message(&mut self, handle: &str, text: &str, now: i64) -> Result<ChatMessage, StorageError> {
+        if text.is_empty() {
+            return Err(StorageError::Vault("empty message".to_string()));
+        }
+        let pid = party_id(handle);
+        if !self.contacts.iter().any(|c| c.handle_hash == pid) {
+            return Err(StorageError::Vault(format!("no contact for handle '{}'", handle)));
+        }
+        let mut queued = load_outbox(&pid, &self.storage)?;
+        queued.push((now, text.to_string()));
+        save_outbox(&pid, &queued, &self.storage)?;
+        Ok(ChatMessage::new_with_timestamp(text.to_string(), true, now))
+    }
+
+    /// Send what's queued to every contact whose CLUTCH has finished (its friendship chain is in the vault, it has an address) out thru `wire`, oldest first — what the app's outbox drain does. Each message is sealed on the chain, the chain saved BEFORE the send (disk is the commit point), and the conversation row written after, undelivered until the app sees its ACK. A send that fails keeps it and everything after it (order matters to the braid). Returns how many went out; `0` for a profile opened without a session.
+    pub fn flush(&mut self, wire: &mut impl Wire) -> Result<usize, StorageError> {
+        let Some(identity_seed) = self.identity_seed else {
+            return Ok(0);
+        };
+        let our_pid = crate::crypto::clutch::identity_party_id(&identity_seed);
+        let mut sent = 0;
+        for contact in self.contacts.iter().filter(|c| !c.is_sibling && c.clutch_state == ClutchState::Complete) {
+            let (Some(fid), Some((peer_addr, alt_addr))) = (contact.friendship_id, contact.race_addrs()) else {
+                continue;
+            };
+            let queued = load_outbox(&contact.handle_hash, &self.storage)?;
+            if queued.is_empty() {
+                continue;
+            }
+            let mut chains = crate::storage::friendship::load_friendship_chains(&fid, &self.storage)?;
+            let mut conversation = load_conversation(&contact.handle_hash, &self.storage)?;
+            let mut left = Vec::new();
+            for (queued_at, text) in queued {
+                let eagle_time = vsf::eagle_time_oscillations();
+                let sealed = if left.is_empty() {
+                    chains.seal_chat(&our_pid, &text, crate::types::pick_woven(&conversation), Vec::new(), eagle_time)
+                } else {
+                    None
+                };
+                let Some((ciphertext, prev_msg_hp)) = sealed else {
+                    left.push((queued_at, text));
+                    continue;
+                };
+                crate::storage::friendship::save_friendship_chains(&chains, &self.storage)?;
+                wire.send(MessageRequest {
+                    peer_addr,
+                    alt_addr,
+                    recipient_pubkey: contact.public_identity.key,
+                    conversation_token: chains.conversation_token,
+                    prev_msg_hp,
+                    ciphertext,
+                    eagle_time,
+                    relay_to: if contact.validated_path.is_none() { contact.relay_device_list() } else { Vec::new() },
+                });
+                let msg = ChatMessage::new_with_timestamp(text, true, eagle_time);
+                save_messages_page(&contact.handle_hash, std::slice::from_ref(&msg), &self.storage)?;
+                conversation.push(msg);
+                sent += 1;
+            }
+            save_outbox(&contact.handle_hash, &left, &self.storage)?;
+        }
+        Ok(sent)
+    }
+
+    /// The full conversation with `handle`, chronological, read back from the vault. Queued messages aren't in it until the app has sent them.
+    pub fn messages(&self, handle: &str) -> Result<Vec<ChatMessage>, StorageError> {
+        load_conversation(&party_id(handle), &self.storage)
+    }
+
+    /// Messages to `handle` not yet handed to the network: the outbox, oldest first, as outgoing undelivered messages.
+    pub fn pending(&self, handle: &str) -> Result<Vec<ChatMessage>, StorageError> {
+        Ok(load_outbox(&party_id(handle), &self.storage)?
+            .into_iter()
+            .map(|(queued_at, text)| ChatMessage::new_with_timestamp(text, true, queued_at))
+            .collect())
+    }
+}
+
+/// Scripting socket over a headless profile: sends are queued in the outbox, never dispatched (there's no chain driver here).
+#[cfg(not(target_os = "android"))]
+impl crate::platform::rpc::Target for HeadlessProfile {
+    fn contact_rows(&self) -> Vec<serde_json::Value> {
+        self.contacts.iter().filter(|c| !c.is_sibling).map(crate::platform::rpc::contact_row).collect()
+    }
+
+    fn send_text(&mut self, handle: &str, text: &str) -> Result<bool, String> {
+        self.queue_message(handle, text, vsf::eagle_time_oscillations()).map(|_| false).map_err(|e| e.to_string())
+    }
+
+    fn online(&self, handle: &str) -> Option<bool> {
+        self.contact(handle).map(|c| c.is_online)
+    }
+
+    fn export_backup(&self, passphrase: &str) -> Result<Vec<u8>, String> {
+        crate::storage::backup::export_all(&self.storage, passphrase).map_err(|e| e.to_string())
+    }
+
+    fn import_backup(&mut self, archive: &[u8], passphrase: &str) -> Result<crate::storage::backup::ImportReport, String> {
+        let report = crate::storage::backup::import_all(&self.storage, archive, passphrase).map_err(|e| e.to_string())?;
+        self.contacts = load_all_contacts(&self.storage);
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_resolve_to_the_party_id_contacts_are_keyed_by() {
+        // Contact::new keys a friend by party id, not by the raw identity seed; a lookup by seed would never find anyone.
+        let contact = Contact::new(HandleText::new("bob"), [7u8; 32], DevicePubkey::from_bytes([8u8; 32]));
+        assert_eq!(party_id("bob"), contact.handle_hash);
+        assert_ne!(crate::types::Handle::to_identity_seed("bob"), contact.handle_hash);
+    }
+}
@@ -1,63 +1,91 @@
 // PHOTON SOURCE MAP — one readable line per file. Keep updated when files or major pub items change.
 //
-// lib.rs   — constants (PHOTON_PORT=4383, PHOTON_PORT_FALLBACK=3546, MULTICAST_PORT=4384, OSC_PER_SEC, PEER_EXPIRY_OSC=7d, KBUCKET_STALE_OSC=1h), always-on VSF logging sink (16 MiB + jittered 24–48h caps, name-scrubbed), and helpers: init_logging/log/log_at/clear_log/snapshot_log_bytes/log_size_bytes/read_log_from/install_log_bridge, LogRecord + parse_log_records (shared record decode: photonlog bin + the in-app Diagnostics viewer), fp(public_id) (non-PII log label), dozenal helpers (DOZENAL_NAMES, dozenal_glyphs UI / dozenal_spell read-aloud / dozenal_words camelCase log form, deglyph_for_log), jitter/jitter_dur (anti-thundering-herd 50–100% pad), module re-exports.
+// lib.rs   — constants (PHOTON_PORT=4383, PHOTON_PORT_FALLBACK=3546, MULTICAST_PORT=4384, OSC_PER_SEC, PEER_EXPIRY_OSC=7d, KBUCKET_STALE_OSC=1h), always-on VSF log sink (16 MiB + 24–48h caps; Windows rolls to photon.log.N.vsf), LogLevel, redact_for_log, LogRecord, fp, dozenal helpers, jitter.
 // main.rs  — winit event loop, window creation, tokio async runtime.
+// headless.rs — HeadlessProfile: a vault driven without a window — attest/add_friend thru the Fgtw seam, queue_message, flush thru the Wire seam; CLUTCH stays in the app.
 //
 // crypto/
 //   blind.rs        — friend-blinded private identity secret S (RAM-only, never persisted): PrivateS{None,Provisional,Live}, derive_blind_pad (per-device+friend OTP pad), make/open_blind_blob ((S⊕pad)‖check, fail-closed), s_check/s_id (tamper commitment + 4-byte tag epoch), seal/open_sibling_s (kete-AEAD S-transfer to a sibling).
 //   chain.rs        — the braid: rolling-chain encryption (512-link, 16KB; see docs/braid.md). Chain, advance() (weaves ≤2 prior peer plaintexts), derive_salt, generate/verify_ack_proof, encrypt/decrypt_layers.
 //   clutch.rs       — 8-algorithm parallel key ceremony: smear_hash, derive_conversation_token, derive_ceremony_instance, spaghettify, sibling_party_id (device-derived fleet-weave party id).
+//   ct.rs           — constant-time equality (eq, via subtle) for every secret or peer-supplied match.
 //   handle_proof.rs — memory-hard handle attestation (~1s); re-exports ihi::handle_proof.
+//   safety.rs       — safety numbers: safety_number(a, b) (symmetric, 12 five-digit groups) + safety_number_lines, for the Verify page.
 //   self_verify.rs  — Ed25519 binary signature verification: AUTHOR_PUBKEY, SYSTEM_PUBKEYS, is_system_pubkey, verify_binary_hash, verify_file (update downloads — verify BEFORE exec).
+//   selftest.rs     — `selftest` CLI: known-answer vectors + CLUTCH and chain round trips, PASS/FAIL per primitive.
 //   shards.rs       — social recovery key sharding (TODO).
 //
 // network/
 //   fgtw/           — Fractal Gradient Trust Web (Kademlia DHT). blob.rs, bootstrap.rs (load_bootstrap_peers), fingerprint.rs (derive_device_keypair/get_machine_fingerprint; Keypair lives in the fgtw crate), node.rs (routing table/k-buckets), peer_store.rs (PeerStore).
 //     protocol.rs   — VSF FGTW+CLUTCH frames: FgtwMessage, PeerRecord (self-signed), hist_req/hist_page (friend-history), chain_reset (sibling fork repair), blind_put/ack/get/srv (friend-blinded S), av_req/av_resp (P2P avatar), reflect/reflect_resp (STUN reflection); all via canonical sign_file + read_verified.
-//     fleet.rs      — photon's binding to the fgtw crate (the pure logic lives there, shared by every app + the worker): PhotonTransport (pooled reqwest) + PhotonSealer (roster AEAD) injected into fgtw::client wrappers. Crate side: fgtw::fleet (MembershipBlob genesis/add/depart/fold — fold IS the auth rule: bilateral add via consent egg, self-signed departure only; BindRequest + bindreq_signing_bytes), fgtw::fanout (fleet-key seal/recover/rotate + fanout_needs_rotation, the §14.2 removal-rotates sentinel), fgtw::fstate (roster codec), fgtw::pair (masked device words). Photon wrappers: current_members[_with_ts|_verified], bind_device (consent-carrying), depart_device, bindreq_put/list/withdraw, rotate_fleet_key, push/pull_roster.
-//     relay.rs      — the relay SEND half: send_via_relay[_sync] signs a `relay` VSF (recipient kx + payload v'r') and POSTs it to fgtw.org, where the PipeHub DO forwards it live down the recipient's WebSocket (no R2, no mailbox, no polling). The RECEIVE half is a WebSocket the status task holds open to fgtw.org/pipe?dev=<our device>; each frame is injected into the receiver's select! tagged RELAY_ADDR so the whole data plane — CLUTCH, ping/pong presence, chat, acks — rides the real dispatch. See network/status.rs (pipe task + relay_reply).
+//     fleet.rs      — photon's binding to the fgtw crate's fleet logic: PhotonTransport + PhotonSealer, current_members, bind_device, depart_device (Departure seam), bindreq_*, rotate_fleet_key, push/pull_roster.
+//     relay.rs      — relay send (send_via_relay[_receipted]) + the pipe-ingress guards: RelayBudget (per-device byte window), RELAY_TTL envelope expiry, relay_rcpt receipts for PT fallback.
+//     metrics.rs    — (feature `metrics`) Prometheus text export on loopback :9464/metrics: blob/relay counters, PT + routing-table gauges.
+//   app_datagrams.rs — the embedder's end of PT app datagrams: on_received/on_delivered carriers drained with take_received/take_delivered.
 //   clock_check.rs  — one-shot wall-clock sanity check via nunc-time consensus (all platforms except Redox, warn-only): spawn_clock_check, ClockJumpDetector, ClockCheckResult.
 //   handle_query.rs — handle attestation + lookup: HandleQuery (query/query_resume/search + try_recv*), QueryRequest, QueryResult{Success(AttestationData),AlreadyAttested,Error}, AttestationData{handle_proof, identity_seed, contacts, friendships, avatar_pixels, peers}.
 //   history_pages.rs— key-agnostic history-backfill page codec (fleet phase reuses verbatim): seal/open_history_page (VSF + kete ChaCha20-Poly1305), HistoryRow, HistoryPagePlain, MAX_PAGE_ROWS=50, MAX_PAGE_BYTES=24KB.
 //   http.rs         — shared pooled HTTP for FGTW: runtime (one persistent tokio), async_client, blocking.
-//   inspect.rs      — network diagnostics + VSF disk I/O: vsf_write, vsf_read.
+//   inspect.rs      — diagnostics: vsf_write/vsf_read, dump_file (the `inspect` CLI), ThroughputSampler, peer_state → PeerState.
+//   lan.rs          — LAN discovery beacon: blinded pt_disc (only contacts can open it), MULTICAST_V4/V6, bind_listener_v4.
 //   pairing_beacon.rs — pairing v2 proximity beacon transport seam (docs/pairing-v2.md, shadow mode): announce_guard/start_scan/stop_scan/on_frame_heard/heard, HeardCandidate; couriers = bluer scan (Linux), PhotonBeacon JNI (Android), stubs elsewhere.
-//   peer_updates.rs — peer state change notifications: PeerUpdate, PeerUpdateClient.
-//   pt/             — Photon Transfer (large-message transport): buffer.rs (reassembly), packets.rs (PTSpec framing), state.rs (Direction/TransferState/OutboundTransfer), window.rs (PTManager sliding-window, send/send_with_pubkey, handle_spec/data/ack; SINGLE_PACKET_MAX=1024), RelayInfo, TickSend.
-//   status.rs       — P2P ping/pong + CLUTCH orchestration: StatusChecker, StatusUpdate (Online/ChatMessage/ChainResetReceived/MessageAck/Clutch*/Avatar*/History*/BlindFrameReceived/LanPeerDiscovered/ReflexiveLearned), request structs (Message/Ack/PTSend/History/ClutchOffer/Kem/Complete/LanBroadcast).
-//   tcp.rs          — TCP fallback for large payloads: send, recv.
-//   traverse/       — NAT traversal (reflexive discovery so far): reflexive.rs (ReflexiveState, quorum-adopted public addr from pong observed_addr + ReflectResponse).
-//   udp.rs          — UDP socket utilities: send/send_sync, canon_socketaddr (::ffff:→v4), get_local_ip, get_broadcast_addr.
+//   peer_updates.rs — peer state change notifications: PeerUpdate, PeerUpdateClient (held by the app), WS Keepalive + reconnect Backoff, Coalescer.
+//   pt/             — Photon Transfer (large-message transport): buffer.rs, packets.rs, state.rs, window.rs (PTManager), transport.rs (Transport, UdpTransport, Loopback), clock.rs, sim.rs, fuzz.rs; app datagrams, SPEC guards, auto-download holds.
+//   qr_scan.rs      — verification-QR scan seam: start_scan/can_scan (Android code scanner), on_scanned/take_scanned carrier.
+//   quality.rs      — link quality: RttEstimate (EWMA) → LinkQuality{Good,Fair,Poor} with hysteresis.
+//   replay.rs       — anti-replay: ReplayGuard (per-device, bounded) for pings and CLUTCH offers; admit_ping/admit_offer.
+//   status.rs       — P2P ping/pong + CLUTCH orchestration: StatusChecker, StatusUpdate, request structs, SigFailures (bad-signature blocking).
+//   tcp.rs          — TCP fallback for large payloads: send, recv, connect_race (happy-eyeballs), bind_listeners + accept_either.
+//   traverse/       — NAT traversal: reflexive.rs (ReflexiveState), gather.rs (CandidateSets, same-NAT LAN first), nat.rs (NatType, classify, NatProbe).
+//   udp.rs          — UDP socket utilities: bind_photon_udp → PhotonUdp (dual-stack or split), send/send_sync, canon_socketaddr, recv buffer sizing, get_local_ip, get_broadcast_addr.
 //
-// platform/  — mod.rs (platform detection), jni_android.rs (Android JNI bridge), autostart.rs (desktop login-item write/read/remove: HKCU Run / LaunchAgent plist / XDG autostart), control.rs (second-launch "show yourself" handoff channel for resident mode), desktop_notify.rs (generic "New message" system notification, hidden/unfocused-gated).
+// platform/
+//   mod.rs, jni_android.rs — platform detection; the Android JNI bridge (NFC, QR scanner, voice memos).
+//   autostart.rs    — desktop login-item write/read/remove (HKCU Run / LaunchAgent plist / XDG autostart).
+//   control.rs      — second-launch handoff for resident mode: "show" and token-gated "add <token> <link>".
+//   link.rs         — incoming photon://add/… links: on_link/take_link carrier, link_from_args.
+//   rpc.rs          — token-gated line-JSON scripting socket over rpc::Target: list_contacts, send_message, status, export/import_backup.
+//   desktop_notify.rs — generic "New message" system notification, hidden/unfocused-gated.
+//   voice.rs        — voice-memo seam: record/play (Android Ogg-Opus; desktop stubs), on_recorded/take_recorded carrier.
 //
 // storage/ — flat vault via the kete crate (FlatStorage, re-exported); conversation content in the rarangi crate. Every entry is addressed by a flat 32-byte key vault_key(domain, scope) = blake3_kdf("photon.storage.entry.v0", domain||scope), never a path — domain is a plain word ("avatar","state","chains",...), scope is the 32-byte identity the entry is about.
-//   mod.rs        — kete re-exports (FlatStorage, StorageError, encrypt/decrypt_bytes, App, APP, android_vault_dirs), vault_key, raw file helpers, photon_config_dir.
-//   cloud.rs      — FGTW cloud backup (contacts sync): CloudContact, CloudError, contacts_storage_key, contacts_encryption_key.
-//   contacts.rs   — contact + conversation storage. State keyed by contact.handle_hash (= party id: identity seed for friends, sibling pid for siblings). save/load_contact_list, save/load_contact_state, save/load_all_contacts, save/load_sibling_list + load_all_siblings + delete_sibling (fleet-sibling index), save/load_messages (rarangi rows keyed by eagle_time; carries content_hash/ack_hash/recovered), save_messages_page, load_message_page_before. contact_state persists the history cursor (hist_oldest/hist_complete), the roster LWW clock (roster_updated), blind deposits, and the folded fleet (fleet_member/fleet_folded_once/fleet_members_ts). CLUTCH keypairs/slots are memory-only no-ops.
-//   friendship.rs — per-friendship chain STATE (the ratchet, not content) at vault_key("chains", friendship_id); v6 adds history_key. save/load/delete_friendship_chains, load_all_friendships.
-//   settings.rs   — user-adjustable app settings, plain VSF (non-secret, NOT the vault): Settings{hex_head,hex_tail}, load_or_create, apply.
+//   mod.rs        — kete re-exports (FlatStorage, StorageError, encrypt/decrypt_bytes, App, APP, android_vault_dirs), vault_key, raw file helpers, photon_config_dir, set_data_dir (PHOTON_DATA_DIR).
+//   backup.rs     — whole-profile archive: export_all/import_all (Argon2id passphrase key, merging import); rotate_encryption (library-only).
+//   compact.rs    — vault compaction thru a sealed journal: compact, finish_interrupted, owe, open_shared (the app's only vault open).
+//   cloud.rs      — FGTW cloud contacts sync: CloudContact, sealed sync blob, merge_cloud_contacts + merge_fleet_roster, SyncBatch (debounced upload).
+//   contacts.rs   — contact + conversation storage keyed by party id: contact list/state, siblings, messages, outbox, retention, wipe_messages.
+//   friendship.rs — per-friendship chain STATE (the ratchet, not content) at vault_key("chains", friendship_id); save/load/delete_friendship_chains, load_all_friendships.
+//   migrate.rs    — schema versions for versioned vault stores: MIGRATIONS registry, upgrade, load_current.
+//   settings.rs   — user-adjustable app settings, plain VSF (non-secret, NOT the vault): Settings, load_or_create, apply.
+//   unflushed.rs  — entries memory is ahead of the vault on: what the app's exit flush owes.
 //   fleet_settings.rs — linked-settings layer (per-device maps + link-to-global, born linked; docs/global-vault.md): FleetSettings{global,devices,our_device}, effective/linked/set/set_link/merge_from, save/load_fleet_settings (vault "settings" entry via the fgtw::fstate codec).
 //
 // types/
-//   contact.rs    — Contact (id, handle*, public_identity, fleet_members + fleet_folded_once/fleet_members_ts, roster_updated LWW clock, clutch_* ceremony state, chain-weave flags, is_sibling, blind fields), plus ::new/new_sibling, knows_device/answerable_pubkeys (fold-respecting trust), init_clutch_slots, insert_message_sorted, clutch_status_detail. Also PartySlot, ChatMessage, HistoryRecovery, HandleText, ContactId, ClutchState, TrustLevel, CHAIN_PROBE_MARKER.
+//   attachment.rs — Attachment{name, bytes}: file messages — filename cleaning, wire_values/from_wire, save_into (never clobbers).
+//   voice.rs      — VoiceMemo{duration_ms, waveform, opus}: voice messages — from_recording, wire_values/from_wire.
+//   contact.rs    — Contact (identity, fleet, roster clock, last_seen, clutch_* state, verified), ChatMessage, HistoryRecovery, HandleText, ClutchState, TrustLevel.
+//   contact_card.rs — ContactCard: VSF/text/photon://add link forms, share_text, to_contact, matches.
 //   device.rs     — DevicePubkey, ed25519_secret_to_x25519.
-//   friendship.rs — CeremonyId (derive_base/derive), FriendshipId (derive/to_base64), FriendshipChains{friendship_id, conversation_token, chains, participants}.
+//   friendship.rs — CeremonyId, FriendshipId, FriendshipChains (seal_chat + pick_woven, pending-message retransmit backoff).
 //   handle.rs     — Handle{text,key}: new, to_handle_proof, username_to_handle_proof.
 //   peer.rs       — Peer, ConnectionState, DhtAnnouncement.
 //   seed.rs       — Seed([u8;32]).
 //   shard.rs      — KeyShard, ShardId, DecryptedShard, RecoveryRequest, RecoveryApproval, ShardDistribution.
 //
 // ui/
-//   photon_app.rs      — the whole app: PhotonApp state + the winit event/tick loop, all render arms, CLUTCH ceremony machinery, fleet reconcile, device add/remove, S/blind drivers, history recovery, settings pages. (The old app/compositing/drawing/text_* split was retired into fluor.)
-//   avatar.rs          — avatar encode/upload/download, AVATAR_SIZE.
+//   photon_app.rs      — the whole app: PhotonApp state + the winit event/tick loop, all render arms, CLUTCH ceremony machinery, fleet reconcile, device add/remove, S/blind drivers, history recovery, settings pages; send_message is the one programmatic send.
+//   avatar.rs          — avatar encode/upload/download/delete, AVATAR_SIZE; animated clips stay in this device's avatar_anim slot.
 //   colour.rs, colour_convert.rs, display_profile.rs, lms2006so.rs — colour + display-profile conversion (VSF RGB → BT.2020, ICC).
 //   chromatic_wave.rs  — the sine-modulated visible-spectrum bar (direct-pixel).
-//   state.rs           — AppState{Launch,Ready,Searching,Conversation,AddDevice,Settings(SettingsPage),Connected}, SettingsPage{You,Fleet,Security,Recovery,Appearance,Notifications,Updates,Diagnostics,About}.
+//   qr.rs              — qr_matrix (qrcode crate, EC level M) + draw_qr for the Verify page.
+//   chat_image.rs      — chat pictures and file drops: prepare_chat_image (EXIF-baked JPEG), prepare_drop, ChatThumb (worker-decoded), draw_image.
+//   attest_retry.rs    — launch-screen attestation retry: is_transient, RetryPolicy, AttestRetry → Step{Ready,Retry,Fail}.
+//   sweeps.rs          — SweepClocks (periodic network sweeps, force_reconnect), PingBackoff, ResumeDetector (sleep → reconnect).
+//   state.rs           — AppState{Launch,Ready,Searching,Conversation,AddDevice,Settings(SettingsPage),ContactPanel(ContactPage),Connected}, SettingsPage, ContactPage{About,Verify,Stats,Manage}.
 //   settings_widgets.rs, settings_layout.rs — Checkbox + SettingsLayout (nav-rail vs content split).
 //   keyboard.rs, mouse.rs — input handling.
 //
-// bin/  — photon-keygen.rs (signing-key gen), photon-signature-signer.rs (binary signing), test-device-key.rs (device-key diagnostic), photonlog.rs (VSF log reader).
+// bin/  — photon-keygen.rs (signing-key gen), photon-signature-signer.rs (binary signing), test-device-key.rs (device-key diagnostic), photonlog.rs (VSF log reader), photon-headless.rs (headless vault CLI).
 
 // Global debug flag - can be toggled at runtime with Ctrl+D
 use std::sync::atomic::AtomicBool;
@@ -102,7 +130,7 @@ macro_rules! debug_println {
 // - Other: stdout
 
 /// Severity of a structured log record. The discriminant IS the on-disk `lvl` value in the VSF log, so these numbers are wire-stable — append new levels at the end, never renumber.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum LogLevel {
     Trace = 0,
     Debug = 1,
@@ -111,6 +139,54 @@ pub enum LogLevel {
     Error = 4,
 }
 
+impl LogLevel {
+    /// Inverse of the discriminant. Anything past Error clamps to Error — the threshold atomic only ever holds values we stored, but a wild byte must read as "log less", never as a panic.
+    pub fn from_u8(n: u8) -> Self {
+        match n {
+            0 => LogLevel::Trace,
+            1 => LogLevel::Debug,
+            2 => LogLevel::Info,
+            3 => LogLevel::Warn,
+            _ => LogLevel::Error,
+        }
+    }
+
+    /// One step more verbose, wrapping Trace back round to Error — the `[]v` chord's cycle.
+    pub fn more_verbose(self) -> Self {
+        match self {
+            LogLevel::Error => LogLevel::Warn,
+            LogLevel::Warn => LogLevel::Info,
+            LogLevel::Info => LogLevel::Debug,
+            LogLevel::Debug => LogLevel::Trace,
+            LogLevel::Trace => LogLevel::Error,
+        }
+    }
+}
+
+/// Starting threshold. Development builds keep Debug (the per-packet PT trace is what a dev session is for); shipped builds start at Info so a submitted log carries handshakes, failures and security events without thousands of per-packet lines burying them.
+#[cfg(feature = "development")]
+const LOG_THRESHOLD_DEFAULT: LogLevel = LogLevel::Debug;
+#[cfg(not(feature = "development"))]
+const LOG_THRESHOLD_DEFAULT: LogLevel = LogLevel::Info;
+
+/// Runtime log threshold: records below it are dropped before anything is built. Process-global and lock-free — every `log`/`logf!` call reads it. Adjusted live with the `[]v` chord.
+static LOG_THRESHOLD: std::sync::atomic::AtomicU8 = std::sync::atomic::AtomicU8::new(LOG_THRESHOLD_DEFAULT as u8);
+
+/// The current runtime log threshold.
+pub fn log_threshold() -> LogLevel {
+    LogLevel::from_u8(LOG_THRESHOLD.load(std::sync::atomic::Ordering::Relaxed))
+}
+
+/// Set the runtime log threshold. Takes effect on the next record, from every thread.
+pub fn set_log_threshold(level: LogLevel) {
+    LOG_THRESHOLD.store(level as u8, std::sync::atomic::Ordering::Relaxed);
+}
+
+/// Would a record at `level` be kept under the current threshold? `logf!`/`logf_at!` check this before capturing their values, so a suppressed per-packet line costs one atomic load.
+pub fn log_enabled(level: LogLevel) -> bool {
+    level >= log_threshold()
+}
+
 /// Retained for the desktop/Windows `main()` call site. The VSF file sink now opens LAZILY on the first log after the platform data dir is known (Android sets it partway thru JNI startup), so this is a no-op — kept only so existing callers compile.
 pub fn init_logging() {}
 
@@ -178,6 +254,79 @@ pub fn fp(public_id: &[u8]) -> String {
     hex::encode(&public_id[..public_id.len().min(4)])
 }
 
+/// Shortest hex run treated as identity material: 32 hex chars = 16 bytes. Every key, token, provenance and full hash we print is at least this long; the deliberate short labels ([`fp`]'s 8 chars, the 16-char hash prefixes in PT lines) stay under it and pass untouched.
+const REDACT_MIN_HEX: usize = 32;
+/// Hex chars kept from a redacted run — the same 4 bytes [`fp`] logs, so a redacted value still correlates with fp-labelled lines.
+const REDACT_KEEP_HEX: usize = 8;
+
+/// Log-boundary redaction, applied to every record's text and captured strings before they hit the sink. Shipped builds cut any run of ≥32 hex digits (a pubkey, provenance, conversation token, full hash) to its first 8 and an ellipsis, so a log pasted into a bug report still correlates but carries no usable identity material. Development builds log in full — chasing a handshake needs the whole value.
+pub fn redact_for_log(msg: &str) -> std::borrow::Cow<'_, str> {
+    if cfg!(feature = "development") {
+        std::borrow::Cow::Borrowed(msg)
+    } else {
+        redact_hex_runs(msg)
+    }
+}
+
+// The pure cut behind [`redact_for_log`]. Hex digits are ASCII, so every slice index lands on a char boundary. Borrowed when nothing qualifies — the common line allocates nothing.
+fn redact_hex_runs(msg: &str) -> std::borrow::Cow<'_, str> {
+    let b = msg.as_bytes();
+    let mut out: Option<String> = None;
+    let mut copied = 0usize;
+    let mut i = 0usize;
+    while i < b.len() {
+        if !b[i].is_ascii_hexdigit() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < b.len() && b[i].is_ascii_hexdigit() {
+            i += 1;
+        }
+        if i - start >= REDACT_MIN_HEX {
+            let o = out.get_or_insert_with(|| String::with_capacity(msg.len()));
+            o.push_str(&msg[copied..start + REDACT_KEEP_HEX]);
+            o.push('\u{2026}');
+            copied = i;
+        }
+    }
+    match out {
+        None => std::borrow::Cow::Borrowed(msg),
+        Some(mut o) => {
+            o.push_str(&msg[copied..]);
+            std::borrow::Cow::Owned(o)
+        }
+    }
+}
+
+#[cfg(test)]
+mod log_redact_tests {
+    use super::*;
+
+    const TOKEN: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    #[test]
+    fn full_token_is_cut_to_a_prefix_in_shipped_builds_only() {
+        let line = format!("CLUTCH: token {} from peer", TOKEN);
+        let got = redact_for_log(&line);
+        if cfg!(feature = "development") {
+            assert_eq!(got, line);
+        } else {
+            assert_eq!(got, "CLUTCH: token 9f86d081\u{2026} from peer");
+            assert!(!got.contains(TOKEN));
+        }
+    }
+
+    #[test]
+    fn short_labels_pass_and_every_long_run_is_cut() {
+        // fp-sized and 8-byte hash prefixes are deliberate labels, not material.
+        let labels = "peer 9f86d081 hash 9f86d081884c7d65 port 4383";
+        assert!(matches!(redact_hex_runs(labels), std::borrow::Cow::Borrowed(_)));
+        let two = format!("{}:{}", TOKEN, &TOKEN[..32].to_uppercase());
+        assert_eq!(redact_hex_runs(&two), "9f86d081\u{2026}:9F86D081\u{2026}");
+    }
+}
+
 /// The log-submission encryption key: a ChaCha20-Poly1305 key derived from the identity seed ALONE — deliberately NOT folding in device_secret.
 /// The identity seed is deterministic from the handle, so anyone who knows the handle (the admin, handed one by a peer with a support request) can re-derive this key and open that peer's submitted log — while anyone who merely grabs the R2 ciphertext, not knowing whose it is, cannot. This is the whole "decryptable if you know the identity seed" property: the log is sealed on the client with this key before it ever leaves the device, so no plaintext hits the wire.
 pub fn log_encryption_key(identity_seed: &[u8; 32]) -> [u8; 32] {
@@ -216,6 +365,43 @@ mod log_seal_tests {
     }
 }
 
+#[cfg(test)]
+mod log_level_tests {
+    use super::*;
+
+    #[test]
+    fn threshold_suppresses_below_and_passes_at_or_above() {
+        let before = log_threshold();
+        set_log_threshold(LogLevel::Warn);
+        assert!(!log_enabled(LogLevel::Trace));
+        assert!(!log_enabled(LogLevel::Debug));
+        assert!(!log_enabled(LogLevel::Info));
+        assert!(log_enabled(LogLevel::Warn));
+        assert!(log_enabled(LogLevel::Error));
+        // The macros gate value capture on the same check: a suppressed record never evaluates its arguments.
+        let mut evaluated = false;
+        logf_at!(LogLevel::Debug, "per-packet {}", { evaluated = true; 1u32 });
+        assert!(!evaluated);
+        logf_at!(LogLevel::Error, "security {}", { evaluated = true; 2u32 });
+        assert!(evaluated);
+        set_log_threshold(LogLevel::Trace);
+        assert!(log_enabled(LogLevel::Trace));
+        set_log_threshold(before);
+    }
+
+    #[test]
+    fn verbosity_cycle_visits_every_level_and_wraps() {
+        let mut l = LogLevel::Error;
+        let mut seen = vec![];
+        for _ in 0..5 {
+            l = l.more_verbose();
+            seen.push(l);
+        }
+        assert_eq!(seen, [LogLevel::Warn, LogLevel::Info, LogLevel::Debug, LogLevel::Trace, LogLevel::Error]);
+        assert_eq!(LogLevel::from_u8(200), LogLevel::Error);
+    }
+}
+
 /// Stochastic pad for ANY periodic timer or age threshold: `base` scaled by a fresh random factor in [0.5, 1.0].
 /// Re-roll on every use. A fixed interval makes every client (and every subsystem) wake on the same tick — a routine timer becomes a synchronised network cascade (the thundering herd), e.g. everyone re-announcing exactly on the hour. Jittering each period spreads the load and makes accidental alignment vanishingly unlikely; the cost is a fuzzy deadline, which time-based housekeeping never needs exact.
 pub fn jitter(base: i64) -> i64 {
@@ -271,6 +457,10 @@ const LOG_PENDING_CAP: usize = 64 << 10;
 const LOG_CAP_BYTES: u64 = 16 << 20;
 #[cfg(feature = "logging")]
 const LOG_TRIM_TO_BYTES: u64 = 8 << 20;
+// Windows rolls instead of trimming, on BOTH caps: past the size cap or the age trigger the live file is renamed to `photon.log.1.vsf` (older rolls shift up, the oldest past LOG_ROTATE_KEEP is overwritten) and a fresh file opens. An in-place trim truncates then rewrites, and Windows has no advisory lock to fence that — a crash between the two loses the very log that would explain the crash. A rename never leaves a half-written file.
+// The age cap is therefore per FILE there: a rolled file is deleted once its last write is past the keep window, so a record can outlive the trigger by up to one more trigger period before its whole file goes.
+#[cfg(feature = "logging")]
+const LOG_ROTATE_KEEP: usize = 3;
 // Live byte count of the open log file, so the cap check is a cheap atomic load instead of a stat per line; seeded from the file's size when it's first opened.
 #[cfg(feature = "logging")]
 static LOG_BYTES: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
@@ -298,7 +488,7 @@ pub fn set_android_log_dir(dir: String) {
     }
 }
 
-/// Directory the VSF log file lives in. Android prefers the JNI-set external dir (pullable); everything else uses `photon_config_dir`.
+/// Directory the VSF log file lives in. Android prefers the JNI-set external dir (pullable); everything else uses `photon_config_dir`, so a `PHOTON_DATA_DIR` override moves the desktop/Windows log with the rest of the data.
 #[cfg(feature = "logging")]
 fn log_dir() -> Option<std::path::PathBuf> {
     #[cfg(target_os = "android")]
@@ -311,10 +501,14 @@ fn log_dir() -> Option<std::path::PathBuf> {
 #[cfg(feature = "logging")]
 fn append_log_record(level: LogLevel, msg: &str, vals: &[LogValue]) {
     use std::io::Write;
+    // The one gate every path funnels thru — `log`, `log_at`, `logf!` and the `log` crate bridge alike.
+    if !log_enabled(level) {
+        return;
+    }
     // Build first so a buffered record carries the stamp of when it was LOGGED, not when the sink finally opened. `msg` is the pure-text template; each captured value rides as its own TYPED `val` field, in slot order — a number never stringifies into the record (numbers-binary-at-rest).
     let mut section = vsf::VsfSection::new("log");
     section.add_field_multi("lvl", vec![vsf::VsfType::u(level as usize, false)]);
-    section.add_field_multi("msg", vec![vsf::VsfType::x(msg.to_string())]);
+    section.add_field_multi("msg", vec![vsf::VsfType::x(redact_for_log(msg).into_owned())]);
     for v in vals {
         let t = match v {
             LogValue::U(n) => vsf::VsfType::u(*n as usize, false),
@@ -322,7 +516,7 @@ fn append_log_record(level: LogLevel, msg: &str, vals: &[LogValue]) {
             LogValue::I(n) => vsf::VsfType::i6(*n as i64),
             LogValue::F(n) => vsf::VsfType::f6(*n),
             LogValue::B(b) => vsf::VsfType::u(*b as usize, false),
-            LogValue::T(s) => vsf::VsfType::x(s.clone()),
+            LogValue::T(s) => vsf::VsfType::x(redact_for_log(s).into_owned()),
             LogValue::Addr(a) => vsf::VsfType::v_u3(vsf::types::Vector {
                 data: {
                     let mut b = match a.ip() {
@@ -390,7 +584,25 @@ fn append_log_record(level: LogLevel, msg: &str, vals: &[LogValue]) {
         let oldest = LOG_OLDEST_OSC.load(std::sync::atomic::Ordering::Relaxed);
         let trigger = LOG_AGE_TRIGGER_OSC.load(std::sync::atomic::Ordering::Relaxed);
         let aged = oldest != i64::MAX && now.saturating_sub(oldest) > trigger;
-        if total > LOG_CAP_BYTES || aged {
+        let roll = log_size_action(total, LOG_CAP_BYTES) == LogSizeAction::Roll;
+        #[cfg(windows)]
+        if roll || aged {
+            // Drop our handle before the rename; the next line opens the fresh file if the roll failed half-way.
+            *guard = None;
+            let Some(dir) = log_dir() else { return };
+            if let Some(fresh) = rotate_log_files(&dir) {
+                *guard = Some(fresh);
+                LOG_BYTES.store(0, std::sync::atomic::Ordering::Relaxed);
+                LOG_OLDEST_OSC.store(i64::MAX, std::sync::atomic::Ordering::Relaxed);
+                LOG_AGE_TRIGGER_OSC.store(jitter(LOG_AGE_TRIGGER_BASE_OSC), std::sync::atomic::Ordering::Relaxed);
+            }
+            let keep = std::time::Duration::from_secs((jitter(LOG_AGE_KEEP_BASE_OSC) / OSC_PER_SEC) as u64);
+            if let Some(cutoff) = std::time::SystemTime::now().checked_sub(keep) {
+                prune_aged_rolls(&dir, cutoff);
+            }
+            return;
+        }
+        if roll || aged {
             // `file`'s borrow of `guard` ends above; reopen the handle on the trimmed file.
             if let Some((trimmed, new_size, new_oldest)) = trim_log_file(now) {
                 *guard = Some(trimmed);
@@ -406,6 +618,58 @@ fn append_log_record(level: LogLevel, msg: &str, vals: &[LogValue]) {
     }
 }
 
+/// What the size cap asks for after a write: keep appending, or roll (Windows: rotate to a numbered file — the age trigger does too; elsewhere: trim the oldest records in place).
+#[cfg(feature = "logging")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LogSizeAction {
+    Append,
+    Roll,
+}
+
+// Pure roll-vs-append decision: only a file strictly past the cap rolls, so a log sitting exactly at the cap keeps its last line.
+#[cfg(feature = "logging")]
+fn log_size_action(size: u64, cap: u64) -> LogSizeAction {
+    if size > cap {
+        LogSizeAction::Roll
+    } else {
+        LogSizeAction::Append
+    }
+}
+
+// Filename of the `n`th rolled log (1 = newest). Keeps the `.vsf` extension so photonlog/vsfinfo open a rolled file like the live one.
+#[cfg(feature = "logging")]
+fn rotated_log_name(n: usize) -> String {
+    format!("photon.log.{n}.vsf")
+}
+
+// Shift photon.log.{N-1}.vsf → .N ... photon.log.vsf → .1, then open a fresh live file for appending. The rename onto the oldest slot replaces it (std's rename overwrites on every platform), so at most LOG_ROTATE_KEEP rolls ever exist. Returns the fresh handle; None if the live file couldn't be moved aside (the caller retries on the next line rather than writing into a file it failed to roll).
+#[cfg(feature = "logging")]
+#[cfg_attr(not(windows), allow(dead_code))]
+fn rotate_log_files(dir: &std::path::Path) -> Option<std::fs::File> {
+    for n in (1..LOG_ROTATE_KEEP).rev() {
+        let from = dir.join(rotated_log_name(n));
+        if from.exists() {
+            let _ = std::fs::rename(&from, dir.join(rotated_log_name(n + 1)));
+        }
+    }
+    let live = dir.join("photon.log.vsf");
+    if live.exists() {
+        std::fs::rename(&live, dir.join(rotated_log_name(1))).ok()?;
+    }
+    std::fs::OpenOptions::new().create(true).append(true).open(&live).ok()
+}
+
+// The Windows age cap: delete every rolled log last written before `cutoff`. A roll's mtime is the time of its newest record, so a file goes only once everything in it is past the keep window. Returns how many were removed.
+#[cfg(feature = "logging")]
+#[cfg_attr(not(windows), allow(dead_code))]
+fn prune_aged_rolls(dir: &std::path::Path, cutoff: std::time::SystemTime) -> usize {
+    (1..=LOG_ROTATE_KEEP)
+        .map(|n| dir.join(rotated_log_name(n)))
+        .filter(|p| std::fs::metadata(p).and_then(|m| m.modified()).is_ok_and(|t| t < cutoff))
+        .filter(|p| std::fs::remove_file(p).is_ok())
+        .count()
+}
+
 // Trim the log by dropping the oldest whole records — enough to get under `LOG_TRIM_TO_BYTES` AND to drop anything older than 24h — then reopen it for appending.
 // The file is a stream of complete VSF records, so we cut only on record boundaries (never mid-record). Returns the reopened append handle, the kept byte count, and the new oldest-record time; None if the file couldn't be read/rewritten (the cap check just retries next line).
 #[cfg(feature = "logging")]
@@ -505,12 +769,15 @@ fn first_record_osc(path: &std::path::Path) -> Option<i64> {
 }
 
 /// Wipe the durable log (the `[]x` clean-relaunch chord, and any future privacy "clear logs" action).
-/// Removes `photon.log.vsf` and drops the open handle so the next write reopens a fresh, empty file.
+/// Removes `photon.log.vsf` plus any rolled `photon.log.N.vsf`, and drops the open handle so the next write reopens a fresh, empty file.
 #[cfg(feature = "logging")]
 pub fn clear_log() {
     if let Ok(mut guard) = LOG_FILE.lock() {
         if let Some(dir) = log_dir() {
             let _ = std::fs::remove_file(dir.join("photon.log.vsf"));
+            for n in 1..=LOG_ROTATE_KEEP {
+                let _ = std::fs::remove_file(dir.join(rotated_log_name(n)));
+            }
         }
         *guard = None;
         LOG_BYTES.store(0, std::sync::atomic::Ordering::Relaxed);
@@ -588,6 +855,55 @@ mod log_cap_tests {
         assert_eq!(keep, 0, "nothing dropped");
     }
 
+    #[test]
+    fn size_cap_rolls_only_past_the_cap() {
+        assert_eq!(log_size_action(0, 16 << 20), LogSizeAction::Append);
+        assert_eq!(log_size_action(16 << 20, 16 << 20), LogSizeAction::Append);
+        assert_eq!(log_size_action((16 << 20) + 1, 16 << 20), LogSizeAction::Roll);
+    }
+
+    #[test]
+    fn rotation_shifts_rolls_and_keeps_at_most_the_cap() {
+        let dir = std::env::temp_dir().join(format!("photon-logroll-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        // Roll more times than we keep; each live file is tagged with its generation.
+        for generation in 0..LOG_ROTATE_KEEP + 2 {
+            std::fs::write(dir.join("photon.log.vsf"), format!("gen {generation}")).unwrap();
+            assert!(rotate_log_files(&dir).is_some());
+            // The fresh live file is empty (appending, not carrying the rolled content).
+            assert_eq!(std::fs::metadata(dir.join("photon.log.vsf")).unwrap().len(), 0);
+        }
+        let newest = LOG_ROTATE_KEEP + 1;
+        for n in 1..=LOG_ROTATE_KEEP {
+            let got = std::fs::read_to_string(dir.join(rotated_log_name(n))).unwrap();
+            assert_eq!(got, format!("gen {}", newest + 1 - n), "roll {n} holds the wrong generation");
+        }
+        assert!(!dir.join(rotated_log_name(LOG_ROTATE_KEEP + 1)).exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn aged_rolls_are_pruned_whole() {
+        let dir = std::env::temp_dir().join(format!("photon-logprune-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let now = std::time::SystemTime::now();
+        let day = std::time::Duration::from_secs(24 * 60 * 60);
+        for (n, age) in [(1, 0), (2, 2), (3, 3)] {
+            let path = dir.join(rotated_log_name(n));
+            std::fs::write(&path, format!("roll {n}")).unwrap();
+            std::fs::File::options().write(true).open(&path).unwrap().set_modified(now - day * age).unwrap();
+        }
+        std::fs::write(dir.join("photon.log.vsf"), "live").unwrap();
+
+        assert_eq!(prune_aged_rolls(&dir, now - day), 2);
+        assert!(dir.join(rotated_log_name(1)).exists(), "a roll written inside the keep window stays");
+        assert!(!dir.join(rotated_log_name(2)).exists() && !dir.join(rotated_log_name(3)).exists());
+        assert!(dir.join("photon.log.vsf").exists(), "the live file is never pruned");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn age_cap_drops_records_older_than_cutoff() {
         // Ten "old" records at t=1000, then ten "new" at t=9000. Size is generous; only age should trim.
@@ -883,7 +1199,9 @@ macro_rules! logf {
     ($fmt:expr $(, $arg:expr)* $(,)?) => {{
         #[allow(unused_imports)]
         use $crate::CapDisplay as _;
-        $crate::log_structured($crate::LogLevel::Info, $fmt, vec![$($crate::Cap(&$arg).cap()),*]);
+        if $crate::log_enabled($crate::LogLevel::Info) {
+            $crate::log_structured($crate::LogLevel::Info, $fmt, vec![$($crate::Cap(&$arg).cap()),*]);
+        }
     }};
 }
 
@@ -893,7 +1211,10 @@ macro_rules! logf_at {
     ($level:expr, $fmt:expr $(, $arg:expr)* $(,)?) => {{
         #[allow(unused_imports)]
         use $crate::CapDisplay as _;
-        $crate::log_structured($level, $fmt, vec![$($crate::Cap(&$arg).cap()),*]);
+        let level: $crate::LogLevel = $level;
+        if $crate::log_enabled(level) {
+            $crate::log_structured(level, $fmt, vec![$($crate::Cap(&$arg).cap()),*]);
+        }
     }};
 }
 
@@ -942,6 +1263,7 @@ pub fn install_log_bridge() {
 pub fn install_log_bridge() {}
 
 pub mod crypto;
+pub mod headless;
 pub mod network;
 pub mod platform;
 pub mod storage;
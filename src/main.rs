@@ -44,6 +44,13 @@ fn main() {
         panic!("TEST PANIC - this should appear in the log");
     }
 
+    // `selftest`: run every crypto primitive against known vectors / round trips, one PASS/FAIL line each, and exit — before the signature check, so a dev or self-built binary on an exotic platform can still diagnose itself. Exit 1 = at least one primitive is broken.
+    if std::env::args().any(|arg| arg == "selftest") {
+        let checks = photon_messenger::crypto::selftest::run();
+        let ok = photon_messenger::crypto::selftest::report(&checks);
+        std::process::exit(if ok { 0 } else { 1 });
+    }
+
     // Verify binary signature matches fractaldecoder (Ed25519 cryptographic signature)
     let signature_hex = match self_verify::verify_binary_hash() {
         Ok(sig) => sig,
@@ -66,6 +73,27 @@ fn main() {
         std::process::exit(0);
     }
 
+    // `inspect <path>`: dump one stored VSF file (contact store, chains, blob) and exit — support/debug tool, no window, no vault, no single-instance lock (it only reads the one file). Exit 1 = unreadable, malformed or a bad signature.
+    if let Some(pos) = std::env::args().position(|arg| arg == "inspect") {
+        let Some(path) = std::env::args().nth(pos + 1) else {
+            eprintln!("usage: photon-messenger inspect <path>");
+            std::process::exit(2);
+        };
+        match photon_messenger::network::inspect::dump_file(std::path::Path::new(&path), true) {
+            Ok(dump) => {
+                println!("{}", dump.text);
+                std::process::exit(if dump.valid { 0 } else { 1 });
+            }
+            Err(e) => {
+                eprintln!("photon: inspect {}: {}", path, e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // A clicked `photon://add/…` link arrives as a launch argument (the scheme handler the installers register). Queued for the app below, or handed to the resident instance instead of a bare "show".
+    let contact_link = photon_messenger::platform::link::link_from_args(std::env::args());
+
     // Single-instance guard: a second instance on the SAME data dir would race the vault and corrupt the log.
     // Held for the whole process (OS frees it on exit). A second instance with its own PHOTON_DATA_DIR (+ PHOTON_FINGERPRINT for a distinct identity) hashes to a different lock port and is allowed — that's the supported way to run two parties on one machine.
     // Losing the lock is no longer an error by default: the resident-mode handoff — clicking the icon while a (possibly hidden) instance runs — asks that instance to surface itself and exits quietly. The old already-running error remains the fallback when nobody answers the control channel.
@@ -81,9 +109,18 @@ fn main() {
                 if let Some(l) = lock.control_listener() {
                     photon_messenger::platform::control::install_tcp_listener(l);
                 }
+                if let Some(link) = contact_link {
+                    photon_messenger::platform::link::on_link(link);
+                }
                 lock
             }
             None => {
+                if let Some(link) = contact_link.as_deref() {
+                    if photon_messenger::platform::control::request_link(&dir, link) {
+                        println!("photon: already running — handed the contact link to the resident instance.");
+                        std::process::exit(0);
+                    }
+                }
                 if photon_messenger::platform::control::request_show(&dir) {
                     println!("photon: already running — asked the resident instance to show itself.");
                     std::process::exit(0);
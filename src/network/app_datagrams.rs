@@ -0,0 +1,59 @@
+//! The embedding application's end of PT app datagrams ([`crate::network::pt::PTManager::send_app_datagram`]). Sending goes thru [`StatusChecker::send_app_datagram`](crate::network::StatusChecker::send_app_datagram); what arrives — verified datagrams from contacts and delivery acks for our own — the tick hands over here, and the embedder drains it with [`take_received`] / [`take_delivered`].
+//! Both carriers are bounded: an embedder that never drains costs [`INBOX_MAX`] datagrams of memory, not an unbounded queue a contact can fill.
+
+use crate::network::pt::{AppDatagram, AppDatagramId};
+use std::sync::Mutex;
+
+/// Most undrained entries each carrier holds; past it the oldest is dropped.
+pub const INBOX_MAX: usize = 256;
+
+static RECEIVED: Mutex<Vec<AppDatagram>> = Mutex::new(Vec::new());
+static DELIVERED: Mutex<Vec<AppDatagramId>> = Mutex::new(Vec::new());
+
+fn push_bounded<T>(carrier: &Mutex<Vec<T>>, item: T, what: &str) {
+    let mut q = carrier.lock().unwrap();
+    if q.len() >= INBOX_MAX {
+        q.remove(0);
+        crate::logf!("PT: app datagram {} carrier full ({}) — dropped the oldest", what, INBOX_MAX);
+    }
+    q.push(item);
+}
+
+/// A verified app datagram from a contact (tick, on `StatusUpdate::AppDatagramReceived`).
+pub fn on_received(datagram: AppDatagram) {
+    push_bounded(&RECEIVED, datagram, "inbox");
+}
+
+/// The peer acked one of our app datagrams (tick, on `StatusUpdate::AppDatagramDelivered`).
+pub fn on_delivered(id: AppDatagramId) {
+    push_bounded(&DELIVERED, id, "delivery");
+}
+
+/// Drain the received app datagrams, in arrival order.
+pub fn take_received() -> Vec<AppDatagram> {
+    std::mem::take(&mut *RECEIVED.lock().unwrap())
+}
+
+/// Drain the ids of our app datagrams acked since the last call.
+pub fn take_delivered() -> Vec<AppDatagramId> {
+    std::mem::take(&mut *DELIVERED.lock().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn carriers_hand_over_in_order_and_stay_bounded() {
+        let peer = std::net::SocketAddr::from(([127, 0, 0, 1], 1));
+        for i in 0..INBOX_MAX + 3 {
+            on_received(AppDatagram { from: [1; 32], peer_addr: peer, payload: vec![i as u8] });
+        }
+        on_delivered([9; 16]);
+        let received = take_received();
+        assert_eq!(received.len(), INBOX_MAX);
+        assert_eq!(received[0].payload, vec![3u8], "the oldest overflow is what's dropped");
+        assert!(take_received().is_empty());
+        assert_eq!(take_delivered(), vec![[9; 16]]);
+    }
+}
@@ -123,6 +123,8 @@ pub async fn put_blob(
         return Err(BlobError::ServerError(format!("transport {}", status)));
     }
     crate::logf!("FGTW: Uploaded blob ({} bytes)", data.len());
+    #[cfg(feature = "metrics")]
+    super::metrics::note_blob_stored();
     Ok(())
 }
 
@@ -267,6 +269,8 @@ pub fn put_blob_blocking(
         return Err(BlobError::ServerError(format!("transport {}", status)));
     }
     crate::logf!("FGTW: Uploaded blob ({} bytes)", data.len());
+    #[cfg(feature = "metrics")]
+    super::metrics::note_blob_stored();
     Ok(())
 }
 
@@ -106,11 +106,30 @@ pub fn bind_device(
     fgtw::client::bind_device(&PhotonTransport, member_key, handle_proof, req)
 }
 
-/// This device's own self-signed departure — the only chain remove that exists. Not yet wired to UI (self-retire arrives with the device-trust bundle).
+/// This device's own self-signed departure — the only chain remove that exists. Wired to Security → "Remove & shred" thru [`Departure`].
 pub fn depart_device(device_key: &Keypair, handle_proof: &[u8; 32]) -> Result<(), String> {
     fgtw::client::depart_device(&PhotonTransport, device_key, handle_proof)
 }
 
+/// The FGTW half of retiring this device (the member-count read behind the last-member gate, then the self-signed remove), behind a seam so the retire flow runs in tests without a network. [`LiveDeparture`] is the real one.
+pub trait Departure {
+    /// Current members of the fleet. The caller treats `Err` as "last" (fail toward refusal, never past it).
+    fn member_count(&mut self, handle_proof: &[u8; 32]) -> Result<usize, String>;
+    /// Publish this device's self-signed departure — FGTW stops announcing it as one of the identity's devices.
+    fn depart(&mut self, device_key: &Keypair, handle_proof: &[u8; 32]) -> Result<(), String>;
+}
+
+/// [`Departure`] against fgtw.org.
+pub struct LiveDeparture;
+impl Departure for LiveDeparture {
+    fn member_count(&mut self, handle_proof: &[u8; 32]) -> Result<usize, String> {
+        current_members(handle_proof).map(|m| m.len())
+    }
+    fn depart(&mut self, device_key: &Keypair, handle_proof: &[u8; 32]) -> Result<(), String> {
+        depart_device(device_key, handle_proof)
+    }
+}
+
 /// Devices the chain shows were once ours but are no longer current members — signed out, hardware brand still held (brands survive departure; identity never dies). The chain is the only truth source the client has: a brand the owner already released still lists here, and re-releasing it is an idempotent ack — so these rows are "retired" whether or not the registry claim is technically gone.
 pub fn retired_devices(handle_proof: &[u8; 32]) -> Result<Vec<[u8; 32]>, String> {
     let Some(blob) = fetch(handle_proof)? else {
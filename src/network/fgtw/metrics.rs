@@ -0,0 +1,159 @@
+//! Prometheus text-format export for a self-hosted FGTW node (feature `metrics`). Counters live here as process-wide atomics bumped where the event happens (blob put, relay frame admitted); gauges (live PT transfers, routing-table size) are published by the status task's PT tick via [`publish`]. [`serve`] answers `GET /metrics` on a loopback listener — never a public bind, the counts say who we talk to and how much.
+
+use std::io::{BufRead, BufReader, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Default scrape port — the one the Prometheus exporter registry reserves for "generic app", so a stock scrape config finds it. `PHOTON_METRICS_PORT` overrides.
+pub const DEFAULT_PORT: u16 = 9464;
+
+static BLOBS_STORED: AtomicU64 = AtomicU64::new(0);
+static RELAY_BYTES: AtomicU64 = AtomicU64::new(0);
+static ACTIVE_TRANSFERS: AtomicU64 = AtomicU64::new(0);
+static ROUTING_TABLE_SIZE: AtomicU64 = AtomicU64::new(0);
+
+/// One read of every exported value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NodeSnapshot {
+    /// Blobs this node has uploaded to FGTW since start (counter).
+    pub blobs_stored: u64,
+    /// Relay pipe bytes admitted past the per-sender budget since start (counter).
+    pub relay_bytes: u64,
+    /// Live PT transfers, both directions (gauge).
+    pub active_transfers: u64,
+    /// Peers in the routing table (gauge).
+    pub routing_table_size: u64,
+}
+
+/// A blob upload succeeded.
+pub fn note_blob_stored() {
+    BLOBS_STORED.fetch_add(1, Ordering::Relaxed);
+}
+
+/// A relay frame of `bytes` was admitted.
+pub fn note_relay_bytes(bytes: usize) {
+    RELAY_BYTES.fetch_add(bytes as u64, Ordering::Relaxed);
+}
+
+/// Publish the gauges (called from the PT tick, which already holds both numbers).
+pub fn publish(active_transfers: usize, routing_table_size: usize) {
+    ACTIVE_TRANSFERS.store(active_transfers as u64, Ordering::Relaxed);
+    ROUTING_TABLE_SIZE.store(routing_table_size as u64, Ordering::Relaxed);
+}
+
+/// Current values of every counter and gauge.
+pub fn snapshot() -> NodeSnapshot {
+    NodeSnapshot {
+        blobs_stored: BLOBS_STORED.load(Ordering::Relaxed),
+        relay_bytes: RELAY_BYTES.load(Ordering::Relaxed),
+        active_transfers: ACTIVE_TRANSFERS.load(Ordering::Relaxed),
+        routing_table_size: ROUTING_TABLE_SIZE.load(Ordering::Relaxed),
+    }
+}
+
+/// Render `snap` in the Prometheus text exposition format (version 0.0.4): a `# HELP` and `# TYPE` line per metric, then its sample. Counters carry the `_total` suffix the format expects.
+pub fn render(snap: &NodeSnapshot) -> String {
+    let metrics: [(&str, &str, &str, u64); 4] = [
+        ("photon_fgtw_blobs_stored_total", "counter", "Blobs uploaded to FGTW since start.", snap.blobs_stored),
+        ("photon_fgtw_relay_bytes_total", "counter", "Relay pipe bytes admitted since start.", snap.relay_bytes),
+        ("photon_pt_active_transfers", "gauge", "Live PT transfers, inbound and outbound.", snap.active_transfers),
+        ("photon_fgtw_routing_table_size", "gauge", "Peers in the routing table.", snap.routing_table_size),
+    ];
+    let mut out = String::new();
+    for (name, kind, help, value) in metrics {
+        out.push_str(&format!("# HELP {} {}\n# TYPE {} {}\n{} {}\n", name, help, name, kind, name, value));
+    }
+    out
+}
+
+/// Spawn the loopback scrape listener on `PHOTON_METRICS_PORT` (or [`DEFAULT_PORT`]). A bind failure is logged and the export stays off — metrics are never worth failing startup over.
+pub fn serve() {
+    let port = std::env::var("PHOTON_METRICS_PORT").ok().and_then(|p| p.parse().ok()).unwrap_or(DEFAULT_PORT);
+    let listener = match std::net::TcpListener::bind(("127.0.0.1", port)) {
+        Ok(l) => l,
+        Err(e) => {
+            crate::logf!("METRICS: bind 127.0.0.1:{} failed: {} (export disabled)", port, e);
+            return;
+        }
+    };
+    crate::logf!("METRICS: serving http://127.0.0.1:{}/metrics", port);
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(s) = stream else { continue };
+            let _ = s.set_read_timeout(Some(std::time::Duration::from_secs(5)));
+            respond(s);
+        }
+    });
+}
+
+/// Answer one scrape. Only the request line matters; headers are drained so the client sees a clean close.
+fn respond(mut stream: std::net::TcpStream) {
+    let mut reader = BufReader::new(match stream.try_clone() {
+        Ok(s) => s,
+        Err(_) => return,
+    });
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let mut header = String::new();
+    while reader.read_line(&mut header).is_ok_and(|n| n > 2) {
+        header.clear();
+    }
+    let path = request_line.split_whitespace().nth(1).unwrap_or("");
+    let (status, body) = if request_line.starts_with("GET ") && path == "/metrics" {
+        ("200 OK", render(&snapshot()))
+    } else {
+        ("404 Not Found", String::new())
+    };
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_emits_well_formed_lines() {
+        let snap = NodeSnapshot { blobs_stored: 12, relay_bytes: 1 << 20, active_transfers: 3, routing_table_size: 41 };
+        let text = render(&snap);
+        assert!(text.ends_with('\n'));
+
+        let mut samples = Vec::new();
+        let mut typed = std::collections::HashMap::new();
+        for line in text.lines() {
+            if let Some(rest) = line.strip_prefix("# HELP ") {
+                let (name, help) = rest.split_once(' ').unwrap();
+                assert!(!help.is_empty(), "{} has no help text", name);
+            } else if let Some(rest) = line.strip_prefix("# TYPE ") {
+                let (name, kind) = rest.split_once(' ').unwrap();
+                assert!(kind == "counter" || kind == "gauge");
+                if kind == "counter" {
+                    assert!(name.ends_with("_total"), "counter {} lacks _total", name);
+                }
+                typed.insert(name.to_string(), kind.to_string());
+            } else {
+                let (name, value) = line.split_once(' ').unwrap();
+                assert!(name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'), "bad metric name {}", name);
+                assert!(!name.starts_with(|c: char| c.is_ascii_digit()));
+                assert!(typed.contains_key(name), "{} sampled before its TYPE line", name);
+                samples.push((name.to_string(), value.parse::<u64>().unwrap()));
+            }
+        }
+
+        assert_eq!(
+            samples,
+            vec![
+                ("photon_fgtw_blobs_stored_total".to_string(), 12),
+                ("photon_fgtw_relay_bytes_total".to_string(), 1 << 20),
+                ("photon_pt_active_transfers".to_string(), 3),
+                ("photon_fgtw_routing_table_size".to_string(), 41),
+            ]
+        );
+    }
+}
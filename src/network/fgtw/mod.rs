@@ -2,6 +2,8 @@ pub mod blob;
 pub mod bootstrap;
 pub mod fingerprint;
 pub mod fleet;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod node;
 pub mod peer_store;
 pub mod protocol;
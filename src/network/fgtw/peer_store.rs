@@ -167,6 +167,22 @@ impl PeerStore {
         }
     }
 
+    /// A contact's device was heard beaconing on the LAN from `addr` ([`crate::network::lan`]). Inserts an unsigned record so the device is reachable by handle lookup even when FGTW isn't; returns `false` (no change) if the store already knows the device — a signed FGTW/gossip record is never rewritten, since editing its `local_ip` would void the signature that lets it be relayed.
+    pub fn note_lan_peer(&mut self, handle_proof: [u8; 32], device_pubkey: DevicePubkey, addr: std::net::SocketAddr) -> bool {
+        let pos = self.find_position(&handle_proof);
+        if self.peers[pos..]
+            .iter()
+            .take_while(|p| p.handle_proof == handle_proof)
+            .any(|p| p.device_pubkey.as_bytes() == device_pubkey.as_bytes())
+        {
+            return false;
+        }
+        let mut record = PeerRecord::new(handle_proof, device_pubkey, addr);
+        record.local_ip = Some(addr.ip());
+        self.add_peer(record);
+        true
+    }
+
     /// Remove stale peers (older than 7 days)
     pub fn cleanup_stale(&mut self) -> usize {
         let now = vsf::eagle_time_oscillations();
@@ -70,6 +70,8 @@ pub enum FgtwMessage {
         display_name: Option<String>,
         /// The responder's avatar PIN (64 = random AES key ‖ FGTW lookup): the friend-gated capability to fetch + decrypt the avatar blob. Rides the pong for the SAME reason as the name — only authenticated contacts get pongs, so only they receive the pin. Neither half is handle-derivable, so knowing the handle no longer yields the avatar. `None` = unset (no avatar / receiver keeps its stored pin).
         avatar_pin: Option<[u8; 64]>,
+        /// The responder has been idle past its away threshold (no keyboard/pointer input). Rides the pong like the name; only sent when true, so legacy pongs parse back as present.
+        away: bool,
     },
     // NOTE: ClutchOffer, ClutchInit, ClutchResponse, ClutchComplete REMOVED Full 8-primitive CLUTCH uses ClutchOffer and ClutchKemResponse which are handled via build_clutch_offer_vsf() and parse_clutch_offer_vsf() See docs/clutch.md Section 4.2 for the slot-based ceremony protocol.
     /// Encrypted chat message
@@ -354,6 +356,7 @@ impl FgtwMessage {
                 observed_addr,
                 display_name,
                 avatar_pin,
+                away,
             } => {
                 // Pong: one native multi-value `sync` row per conversation record — (hb token, e6 last_received). No counts, no numbered names.
                 let mut section = vsf::VsfSection::new("pong");
@@ -380,6 +383,10 @@ impl FgtwMessage {
                 if let Some(pin) = avatar_pin {
                     section.add_field_multi("apin", vec![VsfType::hR(pin.to_vec())]);
                 }
+                // Away presence — only when away (absent parses back to present).
+                if *away {
+                    section.add_field_multi("away", vec![VsfType::u3(1)]);
+                }
                 builder
                     .creation_time_oscillations(*timestamp)
                     .provenance_hash(*provenance_hash)
@@ -630,6 +637,7 @@ impl FgtwMessage {
                     }
                     _ => None,
                 });
+                let away = fields.iter().any(|(n, v)| n == "away" && matches!(v, VsfType::u3(a) if *a != 0));
 
                 return Ok(FgtwMessage::StatusPong {
                     timestamp,
@@ -640,6 +648,7 @@ impl FgtwMessage {
                     observed_addr,
                     display_name,
                     avatar_pin,
+                    away,
                 });
             }
         }
@@ -1807,6 +1816,14 @@ pub fn parse_clutch_offer_vsf_without_recipient_check(
     Ok((payload, sender_pubkey, offer_provenance, conversation_token))
 }
 
+/// The pinned send-time an offer was stamped with (its signed creation_time) — what the receiver's replay guard orders rounds by. Reads the header only; call after the offer has parsed and verified.
+pub fn clutch_offer_send_time(vsf_bytes: &[u8]) -> Result<i64, String> {
+    use vsf::file_format::VsfHeader;
+    let (header, _) =
+        VsfHeader::decode(vsf_bytes).map_err(|e| format!("Failed to parse header: {}", e))?;
+    extract_header_timestamp(&header)
+}
+
 /// Parse and verify a VSF ClutchKemResponse message WITHOUT recipient check.
 ///
 /// This variant is used by the TCP receiver which doesn't know our conversation_token. The caller (app.rs) is responsible for verifying the message is addressed to them.
@@ -8,15 +8,37 @@ use super::Keypair;
 
 const FGTW_URL: &str = "https://fgtw.org";
 
+/// How long a relayed payload stays deliverable after its sender signed it. `1 << 8` s (~4 min) sits just inside the 5-minute CLUTCH round window — a relayed offer/KEM older than that carries keys the round has already expired, and a stale ping or chat retransmit has long been superseded by a fresher copy. The worker drops undelivered envelopes past this age; `peel_relay_envelope` enforces the same bound on arrival so a frame held across a pipe reconnect can't be replayed into the dispatch late.
+pub const RELAY_TTL: std::time::Duration = std::time::Duration::from_secs(1 << 8);
+
+/// Clock-skew allowance on top of [`RELAY_TTL`] when aging an envelope on arrival. The creation stamp is the SENDER's clock and the age is measured on ours, so a sender running `RELAY_SKEW` behind would otherwise see its fresh frames dropped as expired. `1 << 7` s (~2 min) covers an unsynced phone or a laptop that slept thru an NTP correction.
+pub const RELAY_SKEW: std::time::Duration = std::time::Duration::from_secs(1 << 7);
+
+/// A peeled relay envelope: who signed it, the inner payload, and whether they asked for a delivery receipt.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RelayEnvelope {
+    pub sender: [u8; 32],
+    pub payload: Vec<u8>,
+    /// The sender's PT layer is waiting on this one (see [`send_via_relay_receipted`]) — answer with [`build_relay_receipt`].
+    pub wants_receipt: bool,
+}
+
 /// Peel a relay envelope received over the pipe: the whole signed `relay` VSF the SENDER built
 /// (`build_signed_vsf("relay", {recipient, payload})`, signed with their device key), which the worker now
-/// forwards intact instead of the unwrapped inner. Verifies the sender's whole-file signature, then returns
-/// `(sender_device_key, inner_payload)`. `None` on any structural/parse/verify failure — a malformed or
-/// unsigned frame off the pipe is dropped, never injected. This is the DOMAIN SEPARATOR for the pipe: a
-/// message is known-relayed because it arrived wrapped in this authenticated envelope, not because of a
-/// sentinel address. The inner payload is byte-identical to a direct message, so no inner parser changes.
-pub fn peel_relay_envelope(bytes: &[u8]) -> Option<([u8; 32], Vec<u8>)> {
+/// forwards intact instead of the unwrapped inner. Verifies the sender's whole-file signature and that the
+/// envelope is younger than [`RELAY_TTL`] (plus [`RELAY_SKEW`]), then returns the sender's device key + inner payload. `None` on any
+/// structural/parse/verify failure or expiry — a malformed, unsigned or stale frame off the pipe is dropped, never
+/// injected. This is the DOMAIN SEPARATOR for the pipe: a message is known-relayed because it arrived wrapped in
+/// this authenticated envelope, not because of a sentinel address. The inner payload is byte-identical to a direct
+/// message, so no inner parser changes.
+pub fn peel_relay_envelope(bytes: &[u8]) -> Option<RelayEnvelope> {
+    peel_relay_envelope_at(bytes, vsf::eagle_time_oscillations())
+}
+
+/// [`peel_relay_envelope`] against an explicit clock (`now_osc`, eagle time).
+pub fn peel_relay_envelope_at(bytes: &[u8], now_osc: i64) -> Option<RelayEnvelope> {
     use vsf::file_format::VsfHeader;
+    use vsf::types::EagleTime;
 
     // Verify the sender's whole-file signature with `verify_file_signature`, NOT `read_verified`.
     // `read_verified` additionally enforces `is_original` (the header `hp` must equal the content hash), but build_signed_vsf uses `signed_only(ke)` + `sign_file`, and the same waiver every CLUTCH/chat parser takes applies here: the signature covers the ENTIRE file (authorship + integrity are proven), only the content-hp self-attestation is not asserted. Using read_verified rejected every real envelope — the "not a valid signed relay envelope" drop that black-holed the whole pipe data plane.
@@ -26,6 +48,15 @@ pub fn peel_relay_envelope(bytes: &[u8]) -> Option<([u8; 32], Vec<u8>)> {
     }
     let (header, header_end) = VsfHeader::decode(bytes).ok()?;
 
+    // The creation stamp is covered by the signature, so the worker can't freshen it. No stamp = no way to age it = dropped. Age is measured against the sender's clock, so a sender running behind ours gets RELAY_SKEW of grace before its frames count as expired. Only age is bounded, not future skew: a sender whose clock runs ahead just gets a longer window, which the worker's own TTL still caps.
+    let created = match header.creation_time {
+        Some(VsfType::e(et)) => EagleTime::new(et).oscillations()?,
+        _ => return None,
+    };
+    if now_osc.saturating_sub(created) > (RELAY_TTL + RELAY_SKEW).as_secs() as i64 * crate::OSC_PER_SEC {
+        return None;
+    }
+
     // Signer device key from the header (the sender that built + signed this envelope).
     let sender_key: [u8; 32] = match &header.signer_pubkey {
         Some(VsfType::ke(k)) if k.len() == 32 => {
@@ -48,7 +79,100 @@ pub fn peel_relay_envelope(bytes: &[u8]) -> Option<([u8; 32], Vec<u8>)> {
     if payload.is_empty() {
         return None;
     }
-    Some((sender_key, payload))
+    let wants_receipt = matches!(section.get_field("rcpt").and_then(|f| f.values.first()), Some(VsfType::u0(true)));
+    Some(RelayEnvelope { sender: sender_key, payload, wants_receipt })
+}
+
+/// Inner payload of a delivery receipt: a `relay_rcpt` section carrying BLAKE3 of the payload that arrived. Unsigned — it only ever travels inside the recipient's own signed relay envelope, whose signer is the proof of who received it.
+pub fn build_relay_receipt(delivered: &[u8]) -> Vec<u8> {
+    vsf::VsfBuilder::new()
+        .creation_time_oscillations(vsf::eagle_time_oscillations())
+        .provenance_only()
+        .add_section(
+            "relay_rcpt",
+            vec![("hash".to_string(), VsfType::hb(blake3::hash(delivered).as_bytes().to_vec()))],
+        )
+        .build()
+        .unwrap_or_default()
+}
+
+/// The payload hash a delivery receipt acknowledges, or `None` if `inner` isn't one.
+pub fn parse_relay_receipt(inner: &[u8]) -> Option<[u8; 32]> {
+    use vsf::file_format::VsfHeader;
+
+    let (header, header_end) = VsfHeader::decode(inner).ok()?;
+    let section = header.primary_section(inner, header_end).ok()?;
+    if section.name != "relay_rcpt" {
+        return None;
+    }
+    match section.get_field("hash")?.values.first() {
+        Some(VsfType::hb(h)) => h.as_slice().try_into().ok(),
+        _ => None,
+    }
+}
+
+/// Window the relay byte budget is measured over.
+pub const RELAY_WINDOW: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Most contact devices [`RelayBudget`] tracks a window for at once; a contact device past it is charged to the shared stranger pool until a tracked one goes quiet.
+pub const RELAY_BUDGET_SENDERS: usize = 1 << 10;
+
+/// Byte accounting for relayed traffic, checked before a frame reaches the dispatch. Anyone who knows our device key can have fgtw.org forward frames down our pipe. By the time a frame is refused here its bytes have already crossed the link, so this can't save data; what it bounds is the verify/decrypt/dispatch work (and battery) a flood costs us. Accepted bytes are kept as a sliding window of `(when, bytes)` — usage decays smoothly as old frames age out, no cliff at a bucket edge.
+/// A signing key costs nothing to mint, so a window per signer would just hand a flooder a fresh budget per key and grow the table without end. Only devices we answer for (the contacts set) get a window of their own, keyed by the envelope's verified signer; every other signer shares ONE stranger pool, and the table never holds more than [`RELAY_BUDGET_SENDERS`] contact windows.
+#[derive(Debug)]
+pub struct RelayBudget {
+    /// Bytes per contact device (and for the stranger pool as a whole) per [`RELAY_WINDOW`]; 0 = unlimited.
+    cap: u64,
+    window: std::time::Duration,
+    /// `Some(device)` = a contact device's window, `None` = the stranger pool.
+    seen: std::collections::HashMap<Option<[u8; 32]>, std::collections::VecDeque<(std::time::Instant, u64)>>,
+}
+
+impl RelayBudget {
+    pub fn new(cap: u64, window: std::time::Duration) -> Self {
+        Self { cap, window, seen: Default::default() }
+    }
+
+    /// Built from `settings.vsf`'s `relay_cap_mib` (MiB per minute).
+    pub fn from_settings(s: &crate::storage::settings::Settings) -> Self {
+        Self::new(u64::from(s.relay_cap_mib) << 20, RELAY_WINDOW)
+    }
+
+    /// Account `bytes` from `sender` at `now`; `known` = the sender is in the contacts set, so it gets its own window rather than the stranger pool. `Ok` = within budget (and charged); `Err(retry_after)` = over the cap — refused and NOT charged, so a flooder that backs off recovers as its window drains rather than being locked out by its own refused traffic. A single frame bigger than the whole cap is always refused.
+    pub fn admit(&mut self, sender: &[u8; 32], known: bool, bytes: usize, now: std::time::Instant) -> Result<(), std::time::Duration> {
+        if self.cap == 0 {
+            return Ok(());
+        }
+        let window = self.window;
+        // Age out every sender, not just this one — otherwise a key that went quiet keeps its entry forever.
+        self.seen.retain(|_, log| {
+            while log.front().is_some_and(|(at, _)| now.saturating_duration_since(*at) >= window) {
+                log.pop_front();
+            }
+            !log.is_empty()
+        });
+        let tracked = self.seen.len() - usize::from(self.seen.contains_key(&None));
+        let key = if known && (tracked < RELAY_BUDGET_SENDERS || self.seen.contains_key(&Some(*sender))) { Some(*sender) } else { None };
+        let log = self.seen.entry(key).or_default();
+        let bytes = bytes as u64;
+        let used: u64 = log.iter().map(|(_, b)| b).sum();
+        if used + bytes <= self.cap {
+            log.push_back((now, bytes));
+            return Ok(());
+        }
+        if bytes > self.cap {
+            return Err(window);
+        }
+        // Earliest moment enough of the oldest frames have aged out to fit this one.
+        let mut freed = 0;
+        for (at, b) in log.iter() {
+            freed += b;
+            if used - freed + bytes <= self.cap {
+                return Err((*at + window).saturating_duration_since(now));
+            }
+        }
+        Err(window)
+    }
 }
 
 /// Build a signed VSF for conduit operations
@@ -80,6 +204,42 @@ pub async fn send_via_relay(
     keypair: &Keypair,
     recipient_pubkey: &[u8; 32],
     message_bytes: &[u8],
+) -> Result<(), String> {
+    post_relay(keypair, recipient_pubkey, message_bytes, false).await
+}
+
+/// [`send_via_relay`], asking the recipient to answer with a delivery receipt — the PT fallback path, whose transfer otherwise never hears a COMPLETE and times out as Failed even though the payload arrived. Receipts themselves go out un-receipted, so they can't echo.
+pub async fn send_via_relay_receipted(
+    keypair: &Keypair,
+    recipient_pubkey: &[u8; 32],
+    message_bytes: &[u8],
+) -> Result<(), String> {
+    post_relay(keypair, recipient_pubkey, message_bytes, true).await
+}
+
+/// The `relay` section fields for one envelope.
+fn relay_fields(recipient_pubkey: &[u8; 32], message_bytes: &[u8], receipt: bool) -> Vec<(String, VsfType)> {
+    let mut fields = vec![
+        (
+            "recipient".to_string(),
+            VsfType::kx(recipient_pubkey.to_vec()),
+        ),
+        (
+            "payload".to_string(),
+            VsfType::v(b'r', message_bytes.to_vec()),
+        ),
+    ];
+    if receipt {
+        fields.push(("rcpt".to_string(), VsfType::u0(true)));
+    }
+    fields
+}
+
+async fn post_relay(
+    keypair: &Keypair,
+    recipient_pubkey: &[u8; 32],
+    message_bytes: &[u8],
+    receipt: bool,
 ) -> Result<(), String> {
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(10))
@@ -87,20 +247,7 @@ pub async fn send_via_relay(
         .map_err(|e| format!("Failed to create client: {}", e))?;
 
     // Build relay VSF
-    let vsf_bytes = build_signed_vsf(
-        keypair,
-        "relay",
-        vec![
-            (
-                "recipient".to_string(),
-                VsfType::kx(recipient_pubkey.to_vec()),
-            ),
-            (
-                "payload".to_string(),
-                VsfType::v(b'r', message_bytes.to_vec()),
-            ),
-        ],
-    )?;
+    let vsf_bytes = build_signed_vsf(keypair, "relay", relay_fields(recipient_pubkey, message_bytes, receipt))?;
 
     let response = client
         .post(&format!("{}", FGTW_URL))
@@ -135,20 +282,7 @@ pub fn send_via_relay_sync(
         .map_err(|e| format!("Failed to create client: {}", e))?;
 
     // Build relay VSF
-    let vsf_bytes = build_signed_vsf(
-        keypair,
-        "relay",
-        vec![
-            (
-                "recipient".to_string(),
-                VsfType::kx(recipient_pubkey.to_vec()),
-            ),
-            (
-                "payload".to_string(),
-                VsfType::v(b'r', message_bytes.to_vec()),
-            ),
-        ],
-    )?;
+    let vsf_bytes = build_signed_vsf(keypair, "relay", relay_fields(recipient_pubkey, message_bytes, false))?;
 
     let response = client
         .post(&format!("{}", FGTW_URL))
@@ -187,9 +321,86 @@ mod peel_tests {
             ],
         )
         .expect("build envelope");
-        let (sender, payload) = peel_relay_envelope(&envelope).expect("peel must succeed");
-        assert_eq!(sender, kp.public.to_bytes(), "sender key must be the signer");
-        assert_eq!(payload, inner, "inner payload must round-trip byte-identical");
+        let peeled = peel_relay_envelope(&envelope).expect("peel must succeed");
+        assert_eq!(peeled.sender, kp.public.to_bytes(), "sender key must be the signer");
+        assert_eq!(peeled.payload, inner, "inner payload must round-trip byte-identical");
+        assert!(!peeled.wants_receipt);
+    }
+
+    /// An envelope the worker held past RELAY_TTL is dropped on arrival — but only once the skew allowance is spent too; a receipted one says so, and its receipt names the payload.
+    #[test]
+    fn expired_envelope_is_dropped_and_receipts_name_the_payload() {
+        let kp = crate::network::fgtw::Keypair::from_seed(&[3u8; 32]);
+        let inner = vec![5u8; 2048];
+        let signed_at = vsf::eagle_time_oscillations();
+        let envelope = build_signed_vsf(&kp, "relay", relay_fields(&[7u8; 32], &inner, true)).expect("build envelope");
+
+        let ttl_osc = RELAY_TTL.as_secs() as i64 * crate::OSC_PER_SEC;
+        let skew_osc = RELAY_SKEW.as_secs() as i64 * crate::OSC_PER_SEC;
+        let fresh = peel_relay_envelope_at(&envelope, signed_at + ttl_osc - crate::OSC_PER_SEC).expect("inside the TTL");
+        assert!(fresh.wants_receipt);
+        // A sender whose clock lags ours by up to RELAY_SKEW: its envelope reads older than it is, and still peels.
+        assert!(peel_relay_envelope_at(&envelope, signed_at + ttl_osc + skew_osc - crate::OSC_PER_SEC).is_some(), "inside the skew allowance");
+        assert_eq!(peel_relay_envelope_at(&envelope, signed_at + ttl_osc + skew_osc + crate::OSC_PER_SEC), None, "past TTL + skew");
+
+        let receipt = build_relay_receipt(&fresh.payload);
+        assert_eq!(parse_relay_receipt(&receipt), Some(*blake3::hash(&inner).as_bytes()));
+        assert_eq!(parse_relay_receipt(&inner), None);
     }
 }
 
+
+#[cfg(test)]
+mod budget_tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn over_cap_sender_is_refused_until_the_window_decays() {
+        let (flooder, friend) = ([0xF1u8; 32], [0x22u8; 32]);
+        let mut b = RelayBudget::new(1000, Duration::from_secs(60));
+        let t = Instant::now();
+        let secs = Duration::from_secs;
+
+        assert_eq!(b.admit(&flooder, true, 600, t), Ok(()));
+        assert_eq!(b.admit(&flooder, true, 300, t + secs(20)), Ok(()));
+        // 600 + 300 + 200 > 1000 — refused, and told when the 600 from t ages out.
+        assert_eq!(b.admit(&flooder, true, 200, t + secs(30)), Err(secs(30)));
+        // Refusals aren't charged, and other contacts have their own budget.
+        assert_eq!(b.admit(&flooder, true, 100, t + secs(30)), Ok(()));
+        assert_eq!(b.admit(&friend, true, 1000, t + secs(30)), Ok(()));
+
+        // Still refused just before the oldest frame leaves the window ...
+        assert!(b.admit(&flooder, true, 200, t + secs(59)).is_err());
+        // ... admitted once it has.
+        assert_eq!(b.admit(&flooder, true, 200, t + secs(60)), Ok(()));
+
+        // A frame bigger than the whole cap never fits; cap 0 = unlimited.
+        assert_eq!(b.admit(&friend, true, 1001, t + secs(500)), Err(secs(60)));
+        assert_eq!(RelayBudget::new(0, secs(60)).admit(&flooder, true, usize::MAX >> 1, t), Ok(()));
+    }
+
+    #[test]
+    fn minted_keys_share_one_stranger_pool_and_the_table_stays_bounded() {
+        let mut b = RelayBudget::new(1000, Duration::from_secs(60));
+        let t = Instant::now();
+
+        // A fresh key per frame buys a flooder nothing: every stranger draws on the same pool.
+        for i in 0..4u32 {
+            let mut minted = [0u8; 32];
+            minted[..4].copy_from_slice(&i.to_le_bytes());
+            assert_eq!(b.admit(&minted, false, 250, t), Ok(()));
+        }
+        assert!(b.admit(&[0xEE; 32], false, 1, t).is_err(), "the stranger pool is spent");
+        assert_eq!(b.admit(&[0x22; 32], true, 1000, t), Ok(()), "a contact's window is its own");
+
+        // Contact windows stop growing at the bound; the overflow is charged to the (spent) stranger pool.
+        for i in 0..RELAY_BUDGET_SENDERS as u32 {
+            let mut device = [0xC0u8; 32];
+            device[..4].copy_from_slice(&i.to_le_bytes());
+            let _ = b.admit(&device, true, 1, t);
+        }
+        assert!(b.seen.len() <= RELAY_BUDGET_SENDERS + 1);
+        assert!(b.admit(&[0xC1; 32], true, 1, t).is_err(), "an untracked contact past the bound falls into the stranger pool");
+    }
+}
@@ -6,6 +6,7 @@
 
 use crate::network::fgtw::Keypair;
 use crate::network::fgtw::{bootstrap::load_bootstrap_peers, PeerRecord, PeerStore};
+use crate::network::quality::{LinkQuality, RttEstimate};
 use crate::types::{Handle, HandleText};
 use crate::ui::state::{FoundPeer, SearchResult};
 use std::net::UdpSocket;
@@ -83,6 +84,8 @@ pub struct HandleQuery {
 
     // Connectivity channel
     online_receiver: Receiver<bool>,
+    /// Round-trip time of the connectivity worker's GET /status polls — the FGTW half of the link-quality indicator. Written by the worker, read by `fgtw_quality`.
+    fgtw_rtt: Arc<Mutex<RttEstimate>>,
 
     // Search channels
     search_sender: Sender<String>,
@@ -100,20 +103,44 @@ pub struct HandleQuery {
     port: Arc<Mutex<u16>>,
 }
 
-/// Bind UDP socket - tries ports in order: 4383 → 3546 → ephemeral Returns (socket, port) - must have both UDP and TCP free on chosen port
+/// Fold one GET /status poll into the FGTW RTT estimate. A failed poll times nothing (a 5s client timeout isn't a latency) and forgets the history, so the indicator comes back measured afresh.
+fn record_status_rtt(rtt: &Mutex<RttEstimate>, online: bool, started: std::time::Instant) {
+    let mut rtt = rtt.lock().unwrap();
+    if online {
+        rtt.record(started.elapsed());
+    } else {
+        rtt.reset();
+    }
+}
+
+/// Bind the photon UDP socket (see [`bind_free_port`]) and size its receive buffer from settings — PT's windows arrive in bursts this one socket has to hold. On a split binding (dual-stack refused) the IPv4 half is handed to `udp`, which sends IPv4 thru it and lets the status receiver read it.
 fn bind_photon_socket() -> (UdpSocket, u16) {
+    let (bound, port) = bind_free_port();
+    let kib = crate::storage::settings::Settings::load_or_create().udp_rcvbuf_kib;
+    for udp in std::iter::once(&bound.socket).chain(&bound.v4) {
+        // Enable broadcast receive (needed for LAN discovery)
+        if let Err(e) = udp.set_broadcast(true) {
+            crate::logf!("Network: Failed to enable broadcast: {}", e);
+        }
+        crate::network::udp::apply_recv_buffer(udp, kib);
+    }
+    if bound.v4.is_some() {
+        crate::logf!("Network: port {} bound per family (IPv6 + IPv4)", port);
+    }
+    crate::network::udp::set_split_v4(bound.v4);
+    (bound.socket, port)
+}
+
+/// Bind UDP socket - tries ports in order: 4383 → 3546 → ephemeral Returns (socket, port) - must have both UDP and TCP free on chosen port. Both families: dual-stack where the platform allows, one socket each where it doesn't (udp::bind_photon_udp / tcp::bind_listeners).
+fn bind_free_port() -> (crate::network::udp::PhotonUdp, u16) {
     let ports_to_try = [crate::PHOTON_PORT, crate::PHOTON_PORT_FALLBACK];
 
     for port in ports_to_try {
         // Try to bind UDP first
-        match UdpSocket::bind(format!("[::]:{}", port)) {
+        match crate::network::udp::bind_photon_udp(port) {
             Ok(udp) => {
-                // Enable broadcast receive (needed for LAN discovery)
-                if let Err(e) = udp.set_broadcast(true) {
-                    crate::logf!("Network: Failed to enable broadcast: {}", e);
-                }
                 // Check TCP is also free
-                match std::net::TcpListener::bind(format!("[::]:{}", port)) {
+                match crate::network::tcp::bind_listeners(port) {
                     Ok(_tcp) => {
                         // Both free! TCP listener dropped, status.rs will create its own
                         crate::logf!("Network: Bound to port {} (UDP+TCP)", port);
@@ -132,10 +159,9 @@ fn bind_photon_socket() -> (UdpSocket, u16) {
 
     // Fall back to ephemeral if all fixed ports failed
     crate::log("Network: All fixed ports busy - falling back to ephemeral");
-    let udp = UdpSocket::bind("[::]:0").expect("Failed to bind UDP socket");
-    // Enable broadcast receive for LAN discovery
-    let _ = udp.set_broadcast(true);
+    let udp = crate::network::udp::bind_photon_udp(0).expect("Failed to bind UDP socket");
     let port = udp
+        .socket
         .local_addr()
         .expect("Failed to get socket address")
         .port();
@@ -179,6 +205,7 @@ impl HandleQuery {
         crate::logf!("Network: Using port {} for all traffic", initial_port);
         let socket = Arc::new(Mutex::new(Arc::new(initial_socket)));
         let port = Arc::new(Mutex::new(initial_port));
+        let fgtw_rtt = Arc::new(Mutex::new(RttEstimate::default()));
 
         // Clone for workers
         let transport_query = transport.clone();
@@ -194,7 +221,7 @@ impl HandleQuery {
         let port_search = port.clone();
 
         // Spawn connectivity monitoring thread
-        Self::spawn_connectivity_worker(online_tx, event_proxy);
+        Self::spawn_connectivity_worker(online_tx, fgtw_rtt.clone(), event_proxy);
 
         // Spawn attestation worker
         Self::spawn_query_worker(
@@ -223,6 +250,7 @@ impl HandleQuery {
             query_sender: query_tx,
             query_receiver: query_rx,
             online_receiver: online_rx,
+            fgtw_rtt,
             search_sender: search_tx,
             search_receiver: search_rx,
             transport,
@@ -251,6 +279,7 @@ impl HandleQuery {
         crate::logf!("Network: Using port {} for all traffic", initial_port);
         let socket = Arc::new(Mutex::new(Arc::new(initial_socket)));
         let port = Arc::new(Mutex::new(initial_port));
+        let fgtw_rtt = Arc::new(Mutex::new(RttEstimate::default()));
 
         // Clone for workers
         let transport_query = transport.clone();
@@ -266,7 +295,7 @@ impl HandleQuery {
         let port_search = port.clone();
 
         // Spawn connectivity monitoring thread (simplified for Android)
-        Self::spawn_connectivity_worker_android(online_tx);
+        Self::spawn_connectivity_worker_android(online_tx, fgtw_rtt.clone());
 
         // Spawn attestation worker
         Self::spawn_query_worker(
@@ -295,6 +324,7 @@ impl HandleQuery {
             query_sender: query_tx,
             query_receiver: query_rx,
             online_receiver: online_rx,
+            fgtw_rtt,
             search_sender: search_tx,
             search_receiver: search_rx,
             transport,
@@ -309,6 +339,7 @@ impl HandleQuery {
     #[cfg(not(target_os = "android"))]
     fn spawn_connectivity_worker(
         online_tx: Sender<bool>,
+        fgtw_rtt: Arc<Mutex<RttEstimate>>,
         event_proxy: Option<Arc<dyn WakeSender<PhotonEvent>>>,
     ) {
         thread::spawn(move || {
@@ -352,11 +383,14 @@ impl HandleQuery {
             let mut first_check = true;
 
             let check_connectivity = |client: &Option<reqwest::blocking::Client>| -> bool {
-                client
+                let started = std::time::Instant::now();
+                let online = client
                     .as_ref()
                     .and_then(|c| c.get("https://fgtw.org/status").send().ok())
                     .map(|r| r.status().is_success())
-                    .unwrap_or(false)
+                    .unwrap_or(false);
+                record_status_rtt(&fgtw_rtt, online, started);
+                online
             };
 
             loop {
@@ -383,7 +417,7 @@ impl HandleQuery {
 
     /// Spawn connectivity monitoring thread (Android - simple polling)
     #[cfg(target_os = "android")]
-    fn spawn_connectivity_worker_android(online_tx: Sender<bool>) {
+    fn spawn_connectivity_worker_android(online_tx: Sender<bool>, fgtw_rtt: Arc<Mutex<RttEstimate>>) {
         thread::spawn(move || {
             let client = match reqwest::blocking::Client::builder()
                 .timeout(Duration::from_secs(5))
@@ -400,6 +434,7 @@ impl HandleQuery {
             let mut first_check = true;
 
             loop {
+                let started = std::time::Instant::now();
                 let online = match &client {
                     Some(c) => match c.get("https://fgtw.org/status").send() {
                         Ok(r) => {
@@ -418,6 +453,7 @@ impl HandleQuery {
                     },
                     None => false,
                 };
+                record_status_rtt(&fgtw_rtt, online, started);
 
                 if first_check || online != prev_online {
                     let _ = online_tx.send(online);
@@ -635,11 +671,7 @@ impl HandleQuery {
 
                         // Initialize FlatStorage for this session. A bare `return` here would silently strand the UI on the Attesting spinner because the result channel never gets a verdict — the worker has already proven FGTW says the handle is ours, but with no local vault we can't reach Ready. Surface the failure as a QueryResult::Error so the Launch screen flips to its error state and the user sees what happened.
                         // open_shared, NEVER new: on a resume the UI thread already holds this vault's engine and is writing to it (CLUTCH chains, avatars, presence state). A second independent engine here is two in-RAM states racing one file — the exact corruption that bricks a live vault ("seal verification failed" on every open after the stale engine's commit).
-                        let storage = match crate::storage::FlatStorage::open_shared(
-                            crate::storage::APP,
-                            vault_seed,
-                            device_secret_bytes,
-                        ) {
+                        let storage = match crate::storage::compact::open_shared(vault_seed, device_secret_bytes) {
                             Ok(s) => s,
                             Err(e) => {
                                 let msg = format!("storage init failed: {}", e);
@@ -716,17 +748,36 @@ impl HandleQuery {
 
                         // Cloud sync (download + merge)
                         crate::log("Network: Syncing with cloud...");
-                        if let Ok(Some(cloud_contacts)) =
-                            crate::storage::cloud::load_contacts_from_cloud(
-                                &identity_seed,
-                                &keypair,
-                            )
-                        {
-                            // Merge cloud contacts we don't have locally
-                            for cc in cloud_contacts {
-                                let exists =
-                                    contacts.iter().any(|c| c.handle_proof == cc.handle_proof);
-                                if !exists {
+                        let local: Vec<crate::storage::cloud::CloudContact> =
+                            contacts.iter().map(Into::into).collect();
+                        // Merge by version: contacts only the cloud has are added; one both sides have takes whichever side edited it last
+                        let mut merged = match crate::storage::cloud::load_contacts_from_cloud(&identity_seed, &keypair) {
+                            Ok(Some(cloud_contacts)) => crate::storage::cloud::merge_cloud_contacts(&local, &cloud_contacts),
+                            _ => local.clone(),
+                        };
+                        // Then the sibling devices' lists, as they pushed them to the fleet roster — needs the fleet key this device has cached (none before its first fleet join). A row the roster removed is left in `contacts`: the app's first roster pull carries the removal out (index, chains and all)
+                        let fleet_key = storage
+                            .read_addr(&crate::storage::vault_key("fleet_key", &vault_seed))
+                            .ok()
+                            .flatten()
+                            .and_then(|b| <[u8; 32]>::try_from(b.as_slice()).ok());
+                        if let Some(fleet_key) = fleet_key {
+                            match crate::network::fgtw::fleet::pull_roster(&handle_proof, &fleet_key) {
+                                Ok(Some(roster)) => merged = crate::storage::cloud::merge_fleet_roster(&merged, &roster),
+                                Ok(None) => {}
+                                Err(e) => crate::logf!("Network: Fleet roster pull failed: {}", e),
+                            }
+                        }
+                        if merged != local {
+                            for cc in merged {
+                                if let Some(c) =
+                                    contacts.iter_mut().find(|c| c.handle_proof == cc.handle_proof)
+                                {
+                                    if cc.apply_to(c) {
+                                        crate::logf!("Network: Cloud or a sibling has a newer edit of {} — applied", crate::fp(&c.handle_proof).as_str());
+                                        let _ = crate::storage::contacts::save_contact(c, &storage);
+                                    }
+                                } else {
                                     let mut contact = cc.to_contact();
                                     // Load CLUTCH state for cloud contact too
                                     if contact.clutch_state != crate::types::ClutchState::Complete {
@@ -749,7 +800,7 @@ impl HandleQuery {
                             }
                         }
 
-                        // Upload to cloud if we have more contacts locally
+                        // Upload the merged list, so the cloud carries both sides' edits
                         if !contacts.is_empty() {
                             let _ = crate::storage::cloud::sync_contacts_to_cloud(
                                 &contacts,
@@ -964,6 +1015,11 @@ impl HandleQuery {
         self.online_receiver.try_recv().ok()
     }
 
+    /// Link quality to FGTW from the /status poll round trips. `None` until a poll has succeeded (or while offline).
+    pub fn fgtw_quality(&self) -> Option<LinkQuality> {
+        self.fgtw_rtt.lock().unwrap().quality()
+    }
+
     /// Start a handle search (non-blocking)
     pub fn search(&self, handle: String) {
         let _ = self.search_sender.send(handle);
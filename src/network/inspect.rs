@@ -123,21 +123,7 @@ pub fn section_inspect(data: &[u8], transport: &str, direction: &str, label: &st
 fn strip_ansi_if_needed(s: &str) -> String {
     #[cfg(target_os = "android")]
     {
-        // Strip ANSI escape sequences on Android
-        let mut result = String::with_capacity(s.len());
-        let mut in_escape = false;
-        for c in s.chars() {
-            if c == '\x1b' {
-                in_escape = true;
-            } else if in_escape {
-                if c == 'm' {
-                    in_escape = false;
-                }
-            } else {
-                result.push(c);
-            }
-        }
-        result
+        strip_ansi(s)
     }
     #[cfg(not(target_os = "android"))]
     {
@@ -145,6 +131,68 @@ fn strip_ansi_if_needed(s: &str) -> String {
     }
 }
 
+/// Drop ANSI escape sequences (the inspector's colours) — for Android's log and for `inspect` output that isn't going to a terminal.
+fn strip_ansi(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut in_escape = false;
+    for c in s.chars() {
+        if c == '\x1b' {
+            in_escape = true;
+        } else if in_escape {
+            if c == 'm' {
+                in_escape = false;
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+// =============================================================================
+// `photon-messenger inspect <path>` — offline dump of one stored VSF file =============================================================================
+
+/// A dumped file: the human-readable structure, and whether it checked out (parsed, and any signature verified).
+pub struct FileDump {
+    pub text: String,
+    pub valid: bool,
+}
+
+/// Dump any Photon VSF file for support/debugging: a whole signed file (blob, signed store, log record) gets its signature checked and its header + sections printed; a bare section (a contact or sibling index as the vault holds it) gets its fields printed. `colour` keeps the inspector's ANSI colours (a terminal); off gives plain text. Vault ring files are sealed — they aren't VSF until decrypted with the profile's keys, so they come back as an error here rather than a hex wall.
+pub fn dump_file(path: &Path, colour: bool) -> Result<FileDump, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let mut text = format!("{} ({} bytes)\n", path.display(), bytes.len());
+    let mut valid = true;
+
+    let body = if bytes.starts_with("RÅ<".as_bytes()) {
+        let (header, _) = vsf::VsfHeader::decode(&bytes).map_err(|e| format!("VSF header: {:?}", e))?;
+        let signature = match &header.signer_pubkey {
+            Some(VsfType::ke(k)) => match vsf::verification::verify_file_signature(&bytes) {
+                Ok(true) => format!("valid (signer {})", hex::encode(&k[..k.len().min(8)])),
+                Ok(false) => {
+                    valid = false;
+                    format!("INVALID (claims signer {})", hex::encode(&k[..k.len().min(8)]))
+                }
+                Err(e) => {
+                    valid = false;
+                    format!("unverifiable: {}", e)
+                }
+            },
+            _ => "none (unsigned file)".to_string(),
+        };
+        text.push_str(&format!("signature: {}\n", signature));
+        vsf::inspect::inspect_vsf(&bytes).map_err(|e| format!("VSF structure: {}", e))?
+    } else if bytes.first() == Some(&b'[') {
+        text.push_str("signature: n/a (bare section)\n");
+        vsf::inspect::inspect_section(&bytes).map_err(|e| format!("VSF section: {}", e))?
+    } else {
+        return Err("not a VSF file or section (vault ring files are encrypted and can't be dumped without the profile's keys)".to_string());
+    };
+
+    text.push_str(&if colour { body } else { strip_ansi(&body) });
+    Ok(FileDump { text, valid })
+}
+
 // =============================================================================
 // Centralized VSF disk I/O with automatic dev-mode inspection =============================================================================
 
@@ -299,3 +347,246 @@ pub fn vsf_read_decrypted(decrypted: &[u8], label: &str) {
         crate::log(&msg);
     }
 }
+
+// =============================================================================
+// Transfer throughput — rolling per-peer samples for a graph =============================================================================
+
+/// Samples kept per peer. At one per completed transfer this spans a long chat session; the oldest drop off first.
+pub const THROUGHPUT_SAMPLES: usize = 1 << 8;
+
+/// How long a sample stays plottable. The tick prunes past this, so a peer gone quiet drops out of [`ThroughputSampler::peers`].
+pub const THROUGHPUT_HORIZON: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
+/// Rolling per-peer transfer throughput, fed by PT as transfers finish (`PTManager::throughput`). Each peer keeps a bounded ring of `(when, bytes)` samples; [`Self::rate`] and [`Self::series`] turn them into bytes/sec for a diagnostics overlay to plot.
+#[derive(Debug, Default)]
+pub struct ThroughputSampler {
+    peers: std::collections::HashMap<std::net::SocketAddr, std::collections::VecDeque<(std::time::Instant, u64)>>,
+}
+
+impl ThroughputSampler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `bytes` moved to/from `peer`, landing `at`.
+    pub fn record(&mut self, peer: std::net::SocketAddr, bytes: u64, at: std::time::Instant) {
+        let ring = self.peers.entry(peer).or_default();
+        if ring.len() == THROUGHPUT_SAMPLES {
+            ring.pop_front();
+        }
+        ring.push_back((at, bytes));
+    }
+
+    /// Drop samples older than [`THROUGHPUT_HORIZON`] at `now`, and peers left with none (PT tick).
+    pub fn prune(&mut self, now: std::time::Instant) {
+        for ring in self.peers.values_mut() {
+            while ring.front().is_some_and(|(at, _)| now.saturating_duration_since(*at) > THROUGHPUT_HORIZON) {
+                ring.pop_front();
+            }
+        }
+        self.peers.retain(|_, ring| !ring.is_empty());
+    }
+
+    /// Peers with samples on record.
+    pub fn peers(&self) -> impl Iterator<Item = &std::net::SocketAddr> {
+        self.peers.keys()
+    }
+
+    /// Mean bytes/sec to/from `peer` over the `window` ending at `now` (samples in `(now - window, now]`). 0 for an unknown peer or an empty window.
+    pub fn rate(&self, peer: &std::net::SocketAddr, window: std::time::Duration, now: std::time::Instant) -> f64 {
+        if window.is_zero() {
+            return 0.0;
+        }
+        let bytes: u64 = self.peers.get(peer).map_or(0, |ring| {
+            ring.iter().filter(|(at, _)| *at <= now && now.saturating_duration_since(*at) < window).map(|(_, b)| b).sum()
+        });
+        bytes as f64 / window.as_secs_f64()
+    }
+
+    /// `buckets` consecutive windows of `bucket` each, oldest first and ending at `now`, as bytes/sec — the time series a graph plots.
+    pub fn series(&self, peer: &std::net::SocketAddr, bucket: std::time::Duration, buckets: usize, now: std::time::Instant) -> Vec<f64> {
+        (0..buckets)
+            .rev()
+            .map(|back| match now.checked_sub(bucket * back as u32) {
+                Some(end) => self.rate(peer, bucket, end),
+                // Before the clock's origin: nothing could have landed there.
+                None => 0.0,
+            })
+            .collect()
+    }
+}
+
+// =============================================================================
+// Peer connection snapshot — one call for support =============================================================================
+
+/// How traffic to a peer is getting there right now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeerPath {
+    /// Nothing proven — no validated path, not online.
+    Unknown,
+    /// A direct UDP path: the punch-validated one, else the address the presence pings answer on.
+    Direct(std::net::SocketAddr),
+    /// A large transfer's SPEC went unanswered and the payload went over TCP.
+    Tcp,
+    /// Only the FGTW relay pipe reaches them.
+    Relay,
+}
+
+/// Everything known about one peer's connectivity, gathered from the contact's presence state ([`crate::types::Contact`]) and PT.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeerState {
+    pub online: bool,
+    /// Smoothed ping→pong round trip; `None` until a pong lands (reset on the offline edge).
+    pub rtt: Option<std::time::Duration>,
+    pub transfers: crate::network::pt::PeerTransfers,
+    pub path: PeerPath,
+    /// Eagle time last heard from (persisted, coarse).
+    pub last_seen: Option<i64>,
+    /// When signed traffic from them last reached us this session.
+    pub last_heard: Option<std::time::Instant>,
+}
+
+/// Snapshot the connection state of the contact owning device `pubkey` — `None` if no contact knows that device. Transfers are summed over every address the contact is reachable at (validated path, public, LAN), so a LAN/WAN race counts once per transfer on whichever path it runs.
+pub fn peer_state(pubkey: &[u8; 32], contacts: &[crate::types::Contact], pt: &crate::network::pt::PTManager) -> Option<PeerState> {
+    let contact = contacts.iter().find(|c| c.knows_device(pubkey))?;
+    let mut addrs: Vec<std::net::SocketAddr> = Vec::new();
+    let race = contact.race_addrs();
+    for a in [contact.validated_path.map(|(a, _)| a), contact.ip, race.map(|r| r.0), race.and_then(|r| r.1)].into_iter().flatten() {
+        if !addrs.contains(&a) {
+            addrs.push(a);
+        }
+    }
+    let mut transfers = crate::network::pt::PeerTransfers::default();
+    for a in &addrs {
+        let t = pt.peer_transfers(*a);
+        transfers.inbound += t.inbound;
+        transfers.tcp |= t.tcp;
+        transfers.relay |= t.relay;
+    }
+    // An outbound transfer racing LAN vs WAN matches both addresses; count it once.
+    transfers.outbound = addrs.iter().map(|a| pt.peer_transfers(*a).outbound).max().unwrap_or(0);
+    let path = if contact.reached_via_relay || transfers.relay {
+        PeerPath::Relay
+    } else if transfers.tcp {
+        PeerPath::Tcp
+    } else if let Some((a, _)) = contact.validated_path {
+        PeerPath::Direct(a)
+    } else if let (true, Some((a, _))) = (contact.is_online, race) {
+        PeerPath::Direct(a)
+    } else {
+        PeerPath::Unknown
+    };
+    Some(PeerState {
+        online: contact.is_online,
+        rtt: contact.rtt.srtt(),
+        transfers,
+        path,
+        last_seen: contact.last_seen,
+        last_heard: contact.last_heard,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn peer_state_gathers_presence_rtt_transfers_and_path() {
+        use crate::network::pt::{PTManager, PTSpec};
+        use crate::types::{Contact, DevicePubkey, HandleText};
+        let device = [0x22u8; 32];
+        let secret = SigningKey::from_bytes(&[0x42; 32]);
+        let mut pt = PTManager::new(crate::network::fgtw::Keypair { public: (&secret).into(), secret });
+        let mut bob = Contact::new(HandleText::new("bob"), [0x11; 32], DevicePubkey::from_bytes(device));
+        let addr: std::net::SocketAddr = "203.0.113.7:4383".parse().unwrap();
+        bob.ip = Some(addr);
+        let contacts = vec![bob];
+
+        // Known but silent: offline, nothing in flight, no path.
+        let s = peer_state(&device, &contacts, &pt).expect("bob");
+        assert_eq!((s.online, s.rtt, s.path, s.transfers.outbound), (false, None, PeerPath::Unknown, 0));
+        assert!(peer_state(&[0x99; 32], &contacts, &pt).is_none());
+
+        // A pong landed, a punch validated, and one transfer each way is live.
+        let mut contacts = contacts;
+        let now = std::time::Instant::now();
+        let bob = &mut contacts[0];
+        bob.is_online = true;
+        bob.rtt.record(std::time::Duration::from_millis(40));
+        bob.validated_path = Some((addr, now));
+        bob.last_seen = Some(1234);
+        bob.last_heard = Some(now);
+        pt.send(addr, vec![0xAA; 4096]);
+        pt.handle_spec(addr, [0xBB; 32], PTSpec::new(&[0xBB; 2048], b'q'));
+        let s = peer_state(&device, &contacts, &pt).unwrap();
+        assert!(s.online);
+        assert_eq!(s.rtt, Some(std::time::Duration::from_millis(40)));
+        assert_eq!((s.transfers.outbound, s.transfers.inbound), (1, 1));
+        assert_eq!(s.path, PeerPath::Direct(addr));
+        assert_eq!((s.last_seen, s.last_heard), (Some(1234), Some(now)));
+
+        // Relay-only wins over everything else.
+        contacts[0].reached_via_relay = true;
+        assert_eq!(peer_state(&device, &contacts, &pt).unwrap().path, PeerPath::Relay);
+    }
+
+    #[test]
+    fn throughput_is_bytes_over_the_window() {
+        let mut s = ThroughputSampler::new();
+        let (alice, bob): (std::net::SocketAddr, std::net::SocketAddr) = ("10.0.0.1:4383".parse().unwrap(), "10.0.0.2:4383".parse().unwrap());
+        let t0 = std::time::Instant::now();
+        let at = |secs: u64| t0 + std::time::Duration::from_secs(secs);
+        let secs = std::time::Duration::from_secs;
+
+        // Alice: 4 KB at t=1, 8 KB at t=3, 2 KB at t=6. Bob: 1 KB at t=5.
+        s.record(alice, 4096, at(1));
+        s.record(alice, 8192, at(3));
+        s.record(alice, 2048, at(6));
+        s.record(bob, 1024, at(5));
+
+        // The 4 s ending at t=6 holds t=3 and t=6 (t=1 is out): 10 KB / 4 s.
+        assert_eq!(s.rate(&alice, secs(4), at(6)), 10240.0 / 4.0);
+        // Before anything landed, and for a stranger, it's idle.
+        assert_eq!(s.rate(&alice, secs(4), at(0)), 0.0);
+        assert_eq!(s.rate(&"10.0.0.9:1".parse().unwrap(), secs(4), at(6)), 0.0);
+        // Peers don't mix.
+        assert_eq!(s.rate(&bob, secs(2), at(6)), 512.0);
+
+        // Three 2 s buckets ending at t=6: (0,2] has t=1, (2,4] has t=3, (4,6] has t=6.
+        assert_eq!(s.series(&alice, secs(2), 3, at(6)), vec![2048.0, 4096.0, 1024.0]);
+
+        // The ring is bounded: the oldest samples fall off.
+        for i in 0..THROUGHPUT_SAMPLES as u64 {
+            s.record(bob, 1, at(10 + i));
+        }
+        assert_eq!(s.peers.get(&bob).unwrap().len(), THROUGHPUT_SAMPLES);
+        assert_eq!(s.peers.get(&bob).unwrap().front().unwrap().0, at(10));
+
+        // Past the horizon everything is pruned, peers included.
+        s.prune(at(10 + THROUGHPUT_SAMPLES as u64) + THROUGHPUT_HORIZON + secs(1));
+        assert_eq!(s.peers().count(), 0);
+    }
+    use crate::storage::contacts::{encode_contact_list, ContactIdentity};
+
+    #[test]
+    fn dump_shows_a_contact_store_and_rejects_garbage() {
+        let dir = std::env::temp_dir().join(format!("photon-inspect-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let store = dir.join("contacts.vsf");
+        let bob = ContactIdentity { handle_proof: [1; 32], party_id: [2; 32], name: "Bob".to_string(), avatar_pin: [0; 64] };
+        std::fs::write(&store, encode_contact_list(&[bob]).unwrap()).unwrap();
+
+        let dump = dump_file(&store, false).unwrap();
+        assert!(dump.valid);
+        for needle in ["contact_list", "version", "contact", "Bob"] {
+            assert!(dump.text.contains(needle), "missing {:?} in:\n{}", needle, dump.text);
+        }
+        assert!(!dump.text.contains('\x1b'));
+
+        let junk = dir.join("junk");
+        std::fs::write(&junk, [0u8, 1, 2, 3]).unwrap();
+        assert!(dump_file(&junk, false).is_err());
+        assert!(dump_file(&dir.join("missing"), false).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,180 @@
+//! LAN discovery beacon — how two friends on the same network find each other's LAN address without FGTW (a NAT without hairpinning can't route their public addresses back in, and a dead uplink can't reach fgtw.org at all).
+//!
+//! Each ping sweep multicasts one `pt_disc` datagram on [`MULTICAST_V4`]/[`MULTICAST_V6`] port `MULTICAST_PORT` (plus a subnet broadcast). It carries our data-socket port and a **blinded** identity: `tag = keyed_hash(handle_proof, domain ‖ device_pubkey ‖ nonce)` with a fresh random nonce per beacon. Only someone who already holds our handle_proof — a contact — can recompute the tag, and the match names WHICH of our devices spoke; everyone else on the café wifi sees 32 random bytes that never repeat. (Same keyed-hash-is-the-filter shape as the pairing beacon, [`crate::network::pairing_beacon`].)
+//! Older builds beaconed the handle_proof + device key in clear; those are still parsed ([`BeaconId::Clear`]) so a mixed-version LAN keeps working, but nothing sends them anymore.
+
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+
+/// Photon's IPv4 group, administratively scoped (239/8, never leaves the site). Derived from random entropy: 0x68C790 → 239.104.199.144.
+pub const MULTICAST_V4: Ipv4Addr = Ipv4Addr::new(239, 104, 199, 144);
+/// Link-local IPv6 group with the same random bytes.
+pub const MULTICAST_V6: Ipv6Addr = Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0x68c7, 0x9014);
+
+/// Per-beacon nonce. 128 bits: a sender beaconing every sweep for a century never repeats one, so two beacons can't be linked by a reused tag.
+pub const NONCE_LEN: usize = 1 << 4;
+
+/// Domain separation — the handle_proof also keys the pairing beacon, and a LAN tag must never be replayable as one.
+const TAG_DOMAIN: &[u8] = b"PHOTON_LAN_BEACON_v1";
+
+/// The blinded identity for `device_pubkey` of `handle_proof`'s fleet under `nonce`.
+pub fn beacon_tag(handle_proof: &[u8; 32], device_pubkey: &[u8; 32], nonce: &[u8; NONCE_LEN]) -> [u8; 32] {
+    let mut h = blake3::Hasher::new_keyed(handle_proof);
+    h.update(TAG_DOMAIN);
+    h.update(device_pubkey);
+    h.update(nonce);
+    *h.finalize().as_bytes()
+}
+
+/// Who a beacon claims to be, as it came off the wire.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BeaconId {
+    /// Current format — only a contact can open it (see [`sender`](Self::sender)).
+    Blind { tag: [u8; 32], nonce: [u8; NONCE_LEN] },
+    /// Pre-blinding format: handle_proof + device key in clear. Beacons without the device key (older still) are dropped — they can't tell self from sibling.
+    Clear { handle_proof: [u8; 32], device: [u8; 32] },
+}
+
+impl BeaconId {
+    /// Which of `devices` (a contact's known fleet, under the contact's `handle_proof`) sent this beacon. `None` = not this contact.
+    pub fn sender(&self, handle_proof: &[u8; 32], devices: &[[u8; 32]]) -> Option<[u8; 32]> {
+        match self {
+            BeaconId::Blind { tag, nonce } => devices.iter().copied().find(|d| beacon_tag(handle_proof, d, nonce) == *tag),
+            BeaconId::Clear { handle_proof: hp, device } => (hp == handle_proof && devices.contains(device)).then_some(*device),
+        }
+    }
+}
+
+/// A parsed discovery beacon.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LanBeacon {
+    pub id: BeaconId,
+    /// The datagram's source — the sender's LAN address.
+    pub local_ip: Ipv4Addr,
+    /// The sender's Photon data-socket port (the beacon itself comes from a throwaway socket).
+    pub port: u16,
+}
+
+/// Build a beacon for our `device_pubkey` under `handle_proof`, advertising data port `port`. The tag rides in the header's provenance hash (where the clear format kept the handle_proof), the nonce in the section. One-shot, so no rolling hash.
+pub fn build(handle_proof: &[u8; 32], device_pubkey: &[u8; 32], port: u16) -> Vec<u8> {
+    use rand::RngCore;
+    use vsf::{VsfBuilder, VsfType};
+
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    VsfBuilder::new()
+        .creation_time_oscillations(vsf::eagle_time_oscillations())
+        .provenance_hash(beacon_tag(handle_proof, device_pubkey, &nonce))
+        .provenance_only()
+        .add_section(
+            "pt_disc",
+            vec![
+                ("port".to_string(), VsfType::u4(port)),
+                ("nc".to_string(), VsfType::hb(nonce.to_vec())),
+            ],
+        )
+        .build()
+        .unwrap_or_default()
+}
+
+/// Parse a beacon heard from `src_addr` (native IPv4 or v4-mapped). `None` for anything that isn't a well-formed `pt_disc`.
+pub fn parse(packet: &[u8], src_addr: SocketAddr) -> Option<LanBeacon> {
+    use vsf::file_format::{VsfHeader, VsfSection};
+    use vsf::VsfType;
+
+    // No is_original() check — the beacon is unsigned by design; a forged one can at worst make a contact try a LAN address, and the ping that follows is authenticated.
+    let (header, header_end) = VsfHeader::decode(packet).ok()?;
+    let hp: [u8; 32] = match header.provenance_hash {
+        VsfType::hp(bytes) => bytes.as_slice().try_into().ok()?,
+        _ => return None,
+    };
+
+    let mut ptr = header_end;
+    let section = VsfSection::parse(packet, &mut ptr).ok()?;
+    if section.name != "pt_disc" {
+        return None;
+    }
+    let port = match section.get_field("port")?.values.first() {
+        Some(VsfType::u4(p)) => *p,
+        _ => return None,
+    };
+
+    let nonce = section.get_field("nc").and_then(|f| match f.values.first() {
+        Some(VsfType::hb(bytes)) => <[u8; NONCE_LEN]>::try_from(bytes.as_slice()).ok(),
+        _ => None,
+    });
+    let id = match nonce {
+        Some(nonce) => BeaconId::Blind { tag: hp, nonce },
+        None => match section.get_field("ke")?.values.first() {
+            Some(VsfType::ke(bytes)) => BeaconId::Clear { handle_proof: hp, device: bytes.as_slice().try_into().ok()? },
+            _ => return None,
+        },
+    };
+
+    let local_ip = match src_addr.ip() {
+        std::net::IpAddr::V4(ip) => ip,
+        std::net::IpAddr::V6(ip6) => ip6.to_ipv4_mapped()?,
+    };
+
+    Some(LanBeacon { id, local_ip, port })
+}
+
+/// Bind the IPv4 listener on `port` (0 = ephemeral, for tests): broadcast receive on for the subnet-broadcast fallback, joined to [`MULTICAST_V4`] on the default interface. Blocking; the status task converts it to tokio.
+pub fn bind_listener_v4(port: u16) -> std::io::Result<UdpSocket> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, port))?;
+    let _ = socket.set_broadcast(true);
+    socket.join_multicast_v4(&MULTICAST_V4, &Ipv4Addr::UNSPECIFIED)?;
+    Ok(socket)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::IpAddr;
+    use std::time::Duration;
+
+    #[test]
+    fn loopback_multicast_beacon_is_heard_and_only_a_contact_can_match_it() {
+        // Ephemeral port so a running Photon on MULTICAST_PORT neither hears the test nor feeds it.
+        let listener = match bind_listener_v4(0) {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("no IPv4 multicast here ({e}) — skipping");
+                return;
+            }
+        };
+        listener.set_read_timeout(Some(Duration::from_secs(2))).unwrap();
+        let group = (MULTICAST_V4, listener.local_addr().unwrap().port());
+
+        let (hp, dev, sibling, stranger) = ([7u8; 32], [9u8; 32], [8u8; 32], [1u8; 32]);
+        let packet = build(&hp, &dev, 4383);
+        assert!(!packet.windows(32).any(|w| w == hp || w == dev), "neither identity goes out in clear");
+
+        let sender = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).unwrap();
+        sender.set_multicast_ttl_v4(1).unwrap();
+        sender.set_multicast_loop_v4(true).unwrap();
+        match sender.send_to(&packet, group) {
+            Err(e) if e.kind() == std::io::ErrorKind::NetworkUnreachable => {
+                eprintln!("no multicast route ({e}) — skipping");
+                return;
+            }
+            r => {
+                r.unwrap();
+            }
+        }
+
+        let mut buf = [0u8; 2048];
+        let (len, src) = listener.recv_from(&mut buf).expect("looped-back beacon");
+        let beacon = parse(&buf[..len], src).expect("beacon parses");
+        assert_eq!(beacon.port, 4383);
+        assert_eq!(IpAddr::V4(beacon.local_ip), src.ip());
+
+        // A contact holding our handle_proof picks out the device; the wrong handle or an unknown device matches nothing.
+        assert_eq!(beacon.id.sender(&hp, &[sibling, dev]), Some(dev));
+        assert_eq!(beacon.id.sender(&hp, &[sibling]), None);
+        assert_eq!(beacon.id.sender(&stranger, &[dev]), None);
+
+        // Fresh nonce every beacon — two sweeps can't be linked by their tags.
+        assert_ne!(parse(&build(&hp, &dev, 4383), src).unwrap().id, beacon.id);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod app_datagrams;
 pub mod clock_check;
 pub mod clutch_jobs;
 pub mod doorbell;
@@ -6,11 +7,15 @@ pub mod handle_query;
 pub mod history_pages;
 pub mod http;
 pub mod inspect;
+pub mod lan;
 pub mod pairing_beacon;
 pub mod pairing_nfc;
 #[cfg(not(target_os = "android"))]
 pub mod peer_updates;
 pub mod pt;
+pub mod qr_scan;
+pub mod quality;
+pub mod replay;
 pub mod updates;
 pub mod status;
 pub mod tcp;
@@ -24,4 +29,5 @@ pub use handle_query::{HandleQuery, QueryResult};
 #[cfg(not(target_os = "android"))]
 pub use peer_updates::{PeerUpdate, PeerUpdateClient};
 pub use pt::PTManager;
+pub use quality::{LinkQuality, RttEstimate};
 pub use status::{StatusChecker, StatusUpdate};
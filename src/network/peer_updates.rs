@@ -3,15 +3,21 @@
 //! Connects to wss://fgtw.org/ws and receives peer_update messages when any peer's IP changes. This eliminates the 25-second delay caused by stale IP caches.
 //!
 //! Desktop-only module (not available on Android - uses FCM instead)
+//!
+//! A connection nobody writes to is invisible to the NATs between us and FGTW: they drop the idle mapping and the socket never errors — updates just stop arriving. So an idle link is pinged every `ws_keepalive_s` (settings.vsf), and one that answers nothing within [`PONG_TIMEOUT`] is treated as dead. Reconnects back off exponentially ([`Backoff`]) rather than hammering FGTW at a fixed rate while it's down.
+//!
+//! Updates come in bursts — a peer roaming between networks announces each address it passes thru, and a reconnect replays a backlog — and every one used to wake the UI for its own redraw. [`Coalescer`] gathers a burst for [`COALESCE_WINDOW`] and hands on only the newest update per peer, with one wake for the lot.
 
 use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 #[cfg(not(target_os = "android"))]
 use crate::ui::PhotonEvent;
 #[cfg(not(target_os = "android"))]
-use winit::event_loop::EventLoopProxy;
+use fluor::host::WakeSender;
+#[cfg(not(target_os = "android"))]
+use std::sync::Arc;
 
 /// Parsed peer update from FGTW WebSocket
 #[derive(Debug, Clone)]
@@ -23,6 +29,167 @@ pub struct PeerUpdate {
     pub timestamp: i64,
 }
 
+impl PeerUpdate {
+    /// The update as an FGTW peer row, for the same address refresh a directory fetch feeds. `None` if `ip` doesn't parse. Unsigned (`signature` zeroed): the update arrives over FGTW's own TLS socket, the same trust as a directory fetch.
+    pub fn to_peer_record(&self) -> Option<crate::network::fgtw::PeerRecord> {
+        let ip: std::net::IpAddr = self.ip.parse().ok()?;
+        Some(crate::network::fgtw::PeerRecord {
+            handle_proof: self.handle_proof,
+            device_pubkey: crate::types::DevicePubkey::from_bytes(self.device_pubkey),
+            ip: std::net::SocketAddr::new(ip, self.port),
+            local_ip: None,
+            last_seen: self.timestamp,
+            signature: [0; 64],
+        })
+    }
+}
+
+/// Wait for a pong (or any frame) after a keepalive ping before giving up on the link.
+pub const PONG_TIMEOUT: Duration = Duration::from_secs(10);
+/// First reconnect wait; doubles per consecutive failure.
+pub const RECONNECT_BASE: Duration = Duration::from_secs(1 << 1);
+/// Longest reconnect wait — a minute still picks FGTW back up soon after an outage ends.
+pub const RECONNECT_CAP: Duration = Duration::from_secs(1 << 6);
+
+/// How long a burst of updates is gathered before it's handed on, counted from its first update — so a steady stream still flushes this often instead of being held back indefinitely.
+pub const COALESCE_WINDOW: Duration = Duration::from_millis(150);
+
+/// The peer-update link as the UI sees it (`PhotonEvent::PeerUpdateLink`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkState {
+    Connected,
+    /// Down; reconnect number `attempt` fires after `retry_in`.
+    Reconnecting { attempt: u32, retry_in: Duration },
+}
+
+/// Keepalive interval from settings (`ws_keepalive_s` 0 = off).
+pub fn keepalive_interval(s: &crate::storage::settings::Settings) -> Option<Duration> {
+    (s.ws_keepalive_s > 0).then(|| Duration::from_secs(u64::from(s.ws_keepalive_s)))
+}
+
+/// What the keepalive timer wants done when polled.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Tick {
+    /// Not due yet.
+    Wait,
+    /// Link idle for a whole interval — send a ping.
+    Ping,
+    /// Pinged and nothing came back within [`PONG_TIMEOUT`].
+    Dead,
+}
+
+/// Keepalive timer for one connection. Any frame heard restarts it; an interval of silence earns a ping, and silence for [`PONG_TIMEOUT`] after that ends the connection.
+pub struct Keepalive {
+    interval: Duration,
+    heard: Instant,
+    pinged: Option<Instant>,
+}
+
+impl Keepalive {
+    pub fn new(interval: Duration, now: Instant) -> Self {
+        Self { interval, heard: now, pinged: None }
+    }
+
+    /// A frame arrived.
+    pub fn heard(&mut self, now: Instant) {
+        self.heard = now;
+        self.pinged = None;
+    }
+
+    /// When [`Self::poll`] next has something to do.
+    pub fn deadline(&self) -> Instant {
+        match self.pinged {
+            Some(at) => at + PONG_TIMEOUT,
+            None => self.heard + self.interval,
+        }
+    }
+
+    pub fn poll(&mut self, now: Instant) -> Tick {
+        if now < self.deadline() {
+            Tick::Wait
+        } else if self.pinged.is_some() {
+            Tick::Dead
+        } else {
+            self.pinged = Some(now);
+            Tick::Ping
+        }
+    }
+}
+
+/// Reconnect schedule: `base`, doubling per consecutive failure up to `cap`. Reset once a connection has carried a frame — a socket that connects and dies at once keeps backing off.
+pub struct Backoff {
+    base: Duration,
+    cap: Duration,
+    attempt: u32,
+}
+
+impl Backoff {
+    pub fn new(base: Duration, cap: Duration) -> Self {
+        Self { base, cap, attempt: 0 }
+    }
+
+    /// Wait before the next reconnect, counting it as an attempt.
+    pub fn next(&mut self) -> Duration {
+        let doublings = self.attempt.min(16);
+        self.attempt = self.attempt.saturating_add(1);
+        self.base.saturating_mul(1 << doublings).min(self.cap)
+    }
+
+    /// Reconnects since the link last carried anything.
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+}
+
+/// Pending updates for the burst in progress: newest per peer (by signed timestamp; a tie goes to the later arrival).
+#[derive(Default)]
+pub struct Coalescer {
+    pending: Vec<PeerUpdate>,
+    since: Option<Instant>,
+}
+
+impl Coalescer {
+    pub fn push(&mut self, update: PeerUpdate, now: Instant) {
+        match self.pending.iter_mut().find(|p| p.device_pubkey == update.device_pubkey) {
+            Some(p) if update.timestamp >= p.timestamp => *p = update,
+            Some(_) => {} // an older announcement overtaken in flight
+            None => self.pending.push(update),
+        }
+        self.since.get_or_insert(now);
+    }
+
+    /// When the burst in progress is due to be handed on; `None` with nothing pending.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.since.map(|at| at + COALESCE_WINDOW)
+    }
+
+    /// Hand everything pending to `sink` and `wake` once. Returns whether there was anything.
+    pub fn flush(&mut self, sink: &Sender<PeerUpdate>, wake: impl FnOnce()) -> bool {
+        self.since = None;
+        if self.pending.is_empty() {
+            return false;
+        }
+        for update in self.pending.drain(..) {
+            let _ = sink.send(update);
+        }
+        wake();
+        true
+    }
+}
+
+/// Sleep until `deadline`, or forever without one (keepalive off).
+#[cfg(not(target_os = "android"))]
+async fn sleep_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(at) => tokio::time::sleep_until(at.into()).await,
+        None => std::future::pending().await,
+    }
+}
+
 /// WebSocket client for receiving peer updates
 pub struct PeerUpdateClient {
     /// Channel to receive parsed peer updates
@@ -34,32 +201,27 @@ pub struct PeerUpdateClient {
 impl PeerUpdateClient {
     /// Create and start a new peer update WebSocket client
     ///
-    /// Spawns a background thread that maintains a WebSocket connection to fgtw.org/ws and receives peer updates.
+    /// Spawns a background thread that maintains a WebSocket connection to fgtw.org/ws and receives peer updates. Link state changes arrive on the proxy as `PhotonEvent::PeerUpdateLink`.
     #[cfg(not(target_os = "android"))]
-    pub fn new(event_proxy: EventLoopProxy<PhotonEvent>) -> Self {
-        let (update_tx, update_rx) = channel::<PeerUpdate>();
-        let (shutdown_tx, shutdown_rx) = channel::<()>();
-
-        // Spawn WebSocket client thread
-        thread::spawn(move || {
-            Self::websocket_loop(update_tx, shutdown_rx, event_proxy);
-        });
-
-        Self {
-            update_receiver: update_rx,
-            shutdown_sender: Some(shutdown_tx),
-        }
+    pub fn new(event_proxy: Arc<dyn WakeSender<PhotonEvent>>) -> Self {
+        Self::spawn(Some(event_proxy))
     }
 
     /// Create client without event proxy (for simpler use cases)
     #[cfg(not(target_os = "android"))]
     pub fn new_simple() -> Self {
+        Self::spawn(None)
+    }
+
+    #[cfg(not(target_os = "android"))]
+    fn spawn(event_proxy: Option<Arc<dyn WakeSender<PhotonEvent>>>) -> Self {
         let (update_tx, update_rx) = channel::<PeerUpdate>();
         let (shutdown_tx, shutdown_rx) = channel::<()>();
+        let keepalive = keepalive_interval(&crate::storage::settings::Settings::load_or_create());
 
-        // Spawn WebSocket client thread without event proxy
+        // Spawn WebSocket client thread
         thread::spawn(move || {
-            Self::websocket_loop_simple(update_tx, shutdown_rx);
+            Self::websocket_loop(update_tx, shutdown_rx, event_proxy, keepalive);
         });
 
         Self {
@@ -77,14 +239,15 @@ impl PeerUpdateClient {
         }
     }
 
-    /// WebSocket event loop with event proxy
+    /// WebSocket event loop: connect, read updates (coalesced per burst), ping an idle link every `keepalive`, and reconnect on the [`Backoff`] schedule whenever the link fails or goes quiet.
     #[cfg(not(target_os = "android"))]
     fn websocket_loop(
         update_tx: Sender<PeerUpdate>,
         shutdown_rx: Receiver<()>,
-        event_proxy: EventLoopProxy<PhotonEvent>,
+        event_proxy: Option<Arc<dyn WakeSender<PhotonEvent>>>,
+        keepalive: Option<Duration>,
     ) {
-        use futures::StreamExt;
+        use futures::{SinkExt, StreamExt};
         use tokio_tungstenite::tungstenite::Message;
 
         let rt = tokio::runtime::Builder::new_current_thread()
@@ -96,6 +259,13 @@ impl PeerUpdateClient {
             return;
         };
 
+        let notify = |event: PhotonEvent| {
+            if let Some(ref proxy) = event_proxy {
+                let _ = proxy.send(event);
+            }
+        };
+        let mut backoff = Backoff::new(RECONNECT_BASE, RECONNECT_CAP);
+
         rt.block_on(async {
             loop {
                 // Check for shutdown
@@ -111,24 +281,63 @@ impl PeerUpdateClient {
                 match ws_result {
                     Ok((ws_stream, _response)) => {
                         crate::log("PeerUpdate: Connected to FGTW WebSocket");
+                        notify(PhotonEvent::PeerUpdateLink(LinkState::Connected));
+
+                        let (mut write, mut read) = ws_stream.split();
+                        let mut alive = keepalive.map(|interval| Keepalive::new(interval, Instant::now()));
+                        let mut burst = Coalescer::default();
+
+                        // Read messages until connection closes or the keepalive gives up on it
+                        loop {
+                            let deadline = alive.as_ref().map(Keepalive::deadline);
+                            let msg_result = tokio::select! {
+                                m = read.next() => match m {
+                                    Some(m) => m,
+                                    None => break,
+                                },
+                                _ = sleep_until(deadline) => {
+                                    if shutdown_rx.try_recv().is_ok() {
+                                        crate::log("PeerUpdate: Shutdown while idle");
+                                        return;
+                                    }
+                                    match alive.as_mut().map(|k| k.poll(Instant::now())) {
+                                        Some(Tick::Ping) => {
+                                            if let Err(e) = write.send(Message::Ping(Default::default())).await {
+                                                crate::logf!("PeerUpdate: Keepalive ping failed: {}", e);
+                                                break;
+                                            }
+                                        }
+                                        Some(Tick::Dead) => {
+                                            crate::logf!("PeerUpdate: No pong within {}s — link is dead, reconnecting", PONG_TIMEOUT.as_secs());
+                                            break;
+                                        }
+                                        _ => {}
+                                    }
+                                    continue;
+                                }
+                                _ = sleep_until(burst.deadline()) => {
+                                    // Wake up the event loop — once for the whole burst
+                                    burst.flush(&update_tx, || notify(PhotonEvent::NetworkUpdate));
+                                    continue;
+                                }
+                            };
 
-                        let (_, mut read) = ws_stream.split();
-
-                        // Read messages until connection closes
-                        while let Some(msg_result) = read.next().await {
                             // Check for shutdown
                             if shutdown_rx.try_recv().is_ok() {
                                 crate::log("PeerUpdate: Shutdown during read");
                                 return;
                             }
+                            // Anything off the wire — an update, the server's ping, our pong — proves the path is open end to end.
+                            if let Some(k) = alive.as_mut() {
+                                k.heard(Instant::now());
+                            }
+                            backoff.reset();
 
                             match msg_result {
                                 Ok(Message::Binary(data)) => {
                                     // Parse VSF peer_update message
                                     if let Some(update) = Self::parse_peer_update(&data) {
-                                        let _ = update_tx.send(update);
-                                        // Wake up the event loop
-                                        let _ = event_proxy.send_event(PhotonEvent::NetworkUpdate);
+                                        burst.push(update, Instant::now());
                                     }
                                 }
                                 Ok(Message::Ping(_)) => {
@@ -147,6 +356,8 @@ impl PeerUpdateClient {
                                 }
                             }
                         }
+                        // Whatever the dead link delivered is still news.
+                        burst.flush(&update_tx, || notify(PhotonEvent::NetworkUpdate));
                     }
                     Err(e) => {
                         crate::logf!("PeerUpdate: Connection failed: {}", e);
@@ -154,51 +365,10 @@ impl PeerUpdateClient {
                 }
 
                 // Wait before reconnecting
-                crate::log("PeerUpdate: Reconnecting in 5 seconds...");
-                tokio::time::sleep(Duration::from_secs(5)).await;
-            }
-        });
-    }
-
-    /// Simpler WebSocket loop without event proxy
-    #[cfg(not(target_os = "android"))]
-    fn websocket_loop_simple(update_tx: Sender<PeerUpdate>, shutdown_rx: Receiver<()>) {
-        use futures::StreamExt;
-        use tokio_tungstenite::tungstenite::Message;
-
-        let rt = tokio::runtime::Builder::new_current_thread()
-            .enable_all()
-            .build();
-
-        let Ok(rt) = rt else {
-            return;
-        };
-
-        rt.block_on(async {
-            loop {
-                if shutdown_rx.try_recv().is_ok() {
-                    break;
-                }
-
-                let ws_result = tokio_tungstenite::connect_async("wss://fgtw.org/ws").await;
-
-                if let Ok((ws_stream, _)) = ws_result {
-                    let (_, mut read) = ws_stream.split();
-
-                    while let Some(msg_result) = read.next().await {
-                        if shutdown_rx.try_recv().is_ok() {
-                            return;
-                        }
-
-                        if let Ok(Message::Binary(data)) = msg_result {
-                            if let Some(update) = Self::parse_peer_update(&data) {
-                                let _ = update_tx.send(update);
-                            }
-                        }
-                    }
-                }
-
-                tokio::time::sleep(Duration::from_secs(5)).await;
+                let retry_in = backoff.next();
+                notify(PhotonEvent::PeerUpdateLink(LinkState::Reconnecting { attempt: backoff.attempt(), retry_in }));
+                crate::logf!("PeerUpdate: Reconnecting in {}s (attempt {})...", retry_in.as_secs(), backoff.attempt());
+                tokio::time::sleep(retry_in).await;
             }
         });
     }
@@ -291,3 +461,93 @@ impl Drop for PeerUpdateClient {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(ip: &str, timestamp: i64) -> PeerUpdate {
+        PeerUpdate { handle_proof: [1; 32], device_pubkey: [2; 32], ip: ip.to_string(), port: 4000, timestamp }
+    }
+
+    #[test]
+    fn burst_for_one_peer_applies_only_the_final_ip_with_one_wake() {
+        let t0 = Instant::now();
+        let ms = |n: u64| t0 + Duration::from_millis(n);
+        let mut burst = Coalescer::default();
+        assert_eq!(burst.deadline(), None);
+        for (i, ip) in ["10.0.0.1", "10.0.0.2", "192.0.2.7", "198.51.100.4", "203.0.113.9"].iter().enumerate() {
+            burst.push(update(ip, 100 + i as i64), ms(i as u64 * 10));
+        }
+        // Late-arriving older announcement doesn't roll the address back.
+        burst.push(update("10.0.0.1", 100), ms(60));
+        // Counted from the first update, not pushed out by the rest.
+        assert_eq!(burst.deadline(), Some(t0 + COALESCE_WINDOW));
+
+        let (tx, rx) = channel();
+        let mut wakes = 0;
+        assert!(burst.flush(&tx, || wakes += 1));
+        let applied: Vec<_> = rx.try_iter().collect();
+        assert_eq!(applied.len(), 1);
+        assert_eq!(applied[0].ip, "203.0.113.9");
+        assert_eq!(wakes, 1);
+
+        // Nothing pending: no second wake, no timer.
+        assert!(!burst.flush(&tx, || wakes += 1));
+        assert_eq!(wakes, 1);
+        assert_eq!(burst.deadline(), None);
+    }
+
+    #[test]
+    fn reconnect_backoff_doubles_to_the_cap_and_resets() {
+        let mut backoff = Backoff::new(RECONNECT_BASE, RECONNECT_CAP);
+        let waits: Vec<u64> = (0..8).map(|_| backoff.next().as_secs()).collect();
+        assert_eq!(waits, [2, 4, 8, 16, 32, 64, 64, 64]);
+        assert_eq!(backoff.attempt(), 8);
+        // Hundreds of failures later, still the cap — no overflow.
+        for _ in 0..100 {
+            backoff.next();
+        }
+        assert_eq!(backoff.next(), RECONNECT_CAP);
+        // A link that carried a frame starts the schedule over.
+        backoff.reset();
+        assert_eq!(backoff.next(), RECONNECT_BASE);
+    }
+
+    #[test]
+    fn keepalive_pings_an_idle_link_then_gives_up_on_silence() {
+        let t0 = Instant::now();
+        let s = |n: u64| t0 + Duration::from_secs(n);
+        let mut k = Keepalive::new(Duration::from_secs(30), t0);
+        assert_eq!(k.poll(s(29)), Tick::Wait);
+        // Traffic pushes the ping back.
+        k.heard(s(20));
+        assert_eq!(k.poll(s(30)), Tick::Wait);
+        assert_eq!(k.deadline(), s(50));
+        assert_eq!(k.poll(s(50)), Tick::Ping);
+        // One ping per silence, not one per poll.
+        assert_eq!(k.deadline(), s(50) + PONG_TIMEOUT);
+        assert_eq!(k.poll(s(55)), Tick::Wait);
+        // The pong clears it...
+        k.heard(s(51));
+        assert_eq!(k.poll(s(70)), Tick::Wait);
+        assert_eq!(k.poll(s(81)), Tick::Ping);
+        // ...and without one the link is dead.
+        assert_eq!(k.poll(s(81) + PONG_TIMEOUT), Tick::Dead);
+
+        let mut settings = crate::storage::settings::Settings::default();
+        assert_eq!(keepalive_interval(&settings), Some(Duration::from_secs(32)));
+        settings.ws_keepalive_s = 0;
+        assert_eq!(keepalive_interval(&settings), None);
+    }
+
+    #[test]
+    fn update_becomes_a_peer_row_or_nothing() {
+        let mut u = update("2001:db8::7", 9);
+        let rec = u.to_peer_record().expect("a v6 literal parses");
+        assert_eq!(rec.ip, "[2001:db8::7]:4000".parse().unwrap());
+        assert_eq!((rec.handle_proof, *rec.device_pubkey.as_bytes(), rec.last_seen), ([1; 32], [2; 32], 9));
+        u.ip = "not an address".into();
+        assert!(u.to_peer_record().is_none());
+    }
+}
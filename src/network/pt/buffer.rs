@@ -24,6 +24,8 @@ pub struct ReceiveBuffer {
     expected_hash: [u8; 32],
     /// Count of received packets
     received_count: u32,
+    /// Whether the data matched `expected_hash` the last time a write left every packet received — hashed once on completion and again after each repair, not per query.
+    intact: bool,
 }
 
 impl ReceiveBuffer {
@@ -48,33 +50,56 @@ impl ReceiveBuffer {
             total_size,
             expected_hash,
             received_count: 0,
+            intact: false,
         }
     }
 
-    /// Insert received packet, returns true if new (not duplicate)
-    pub fn insert(&mut self, sequence: u32, payload: &[u8]) -> bool {
+    /// Payload length DATA `sequence` must carry: a full `packet_size`, except the last packet, which carries the remainder. `None` outside the SPEC's `total_packets`.
+    pub fn expected_len(&self, sequence: u32) -> Option<usize> {
         if sequence >= self.total_packets {
-            return false; // Out of range
+            return None;
         }
+        let offset = sequence as usize * self.packet_size as usize;
+        Some(
+            (self.total_size as usize)
+                .saturating_sub(offset)
+                .min(self.packet_size as usize),
+        )
+    }
 
-        let idx = sequence as usize;
-        if self.received[idx] {
-            return false; // Duplicate
+    /// Whether a DATA packet fits this transfer: a sequence inside the SPEC with exactly the payload length that slot takes. Anything else is forged or corrupt — refused before it's stored or ACK'd, so a short payload can't leave a hole that hashes wrong and a long one can't spill into its neighbour's slot.
+    pub fn accepts(&self, sequence: u32, len: usize) -> bool {
+        self.expected_len(sequence) == Some(len)
+    }
+
+    /// Insert received packet, returns true if new (not duplicate). A packet the buffer doesn't [`accept`](Self::accepts) is also refused — check that first to tell the two apart.
+    ///
+    /// A duplicate carrying different bytes still returns false, and replaces the stored copy only while every packet is in but the data fails the SPEC's hash — the one state a repair can fix. Before that it's dropped (the copy we hold may be the good one), and once intact nothing is rewritten, so a stream of differing duplicates can't buy a full-buffer hash each.
+    pub fn insert(&mut self, sequence: u32, payload: &[u8]) -> bool {
+        if !self.accepts(sequence, payload.len()) {
+            return false; // Out of range or wrong length
         }
 
-        // Calculate offset in data buffer
+        // In bounds: accepts() held the payload to its slot, which ends at or before total_size
+        let idx = sequence as usize;
         let offset = idx * self.packet_size as usize;
-        let end = (offset + payload.len()).min(self.total_size as usize);
+        let slot = &mut self.data[offset..offset + payload.len()];
 
-        // Copy payload to buffer
-        if offset < self.data.len() {
-            let copy_len = end - offset;
-            self.data[offset..offset + copy_len].copy_from_slice(&payload[..copy_len]);
+        if self.received[idx] {
+            if self.is_complete() && !self.intact && slot != payload {
+                slot.copy_from_slice(payload);
+                self.intact = self.verify();
+            }
+            return false; // Duplicate
         }
+        slot.copy_from_slice(payload);
 
         // Mark as received
         self.received.set(idx, true);
         self.received_count += 1;
+        if self.is_complete() {
+            self.intact = self.verify();
+        }
 
         true
     }
@@ -84,6 +109,20 @@ impl ReceiveBuffer {
         self.received_count == self.total_packets
     }
 
+    /// The payload stored for `sequence`, if it has been received.
+    pub fn stored(&self, sequence: u32) -> Option<&[u8]> {
+        if !self.received.get(sequence as usize).is_some_and(|b| *b) {
+            return None;
+        }
+        let offset = sequence as usize * self.packet_size as usize;
+        Some(&self.data[offset..offset + self.expected_len(sequence)?])
+    }
+
+    /// Every packet received and the data matches the SPEC's hash. Cached — cheap to ask per packet.
+    pub fn is_intact(&self) -> bool {
+        self.intact
+    }
+
     /// Get list of missing sequence numbers
     pub fn missing_sequences(&self) -> Vec<u32> {
         self.received
@@ -106,7 +145,7 @@ impl ReceiveBuffer {
         }
 
         let actual_hash = blake3::hash(&self.data[..self.total_size as usize]);
-        actual_hash.as_bytes() == &self.expected_hash
+        crate::crypto::ct::eq(actual_hash.as_bytes(), self.expected_hash)
     }
 
     /// Get final data (only valid if complete and verified)
@@ -221,6 +260,22 @@ impl SendBuffer {
         true
     }
 
+    /// Take back an ACK, returns true if the packet was ACK'd
+    pub fn unmark_acked(&mut self, sequence: u32) -> bool {
+        if !self.is_acked(sequence) {
+            return false;
+        }
+
+        self.acked.set(sequence as usize, false);
+        self.acked_count -= 1;
+        true
+    }
+
+    /// Check if packet has been ACK'd
+    pub fn is_acked(&self, sequence: u32) -> bool {
+        self.acked.get(sequence as usize).is_some_and(|b| *b)
+    }
+
     /// Check if all packets have been ACK'd
     pub fn is_complete(&self) -> bool {
         self.acked_count == self.total_packets
@@ -295,6 +350,46 @@ mod tests {
         assert_eq!(received, data);
     }
 
+    #[test]
+    fn test_receive_buffer_repairs_corrupt_packet() {
+        let data: Vec<u8> = (0..3000u32).map(|i| i as u8).collect();
+        let mut buf = ReceiveBuffer::new(3, 1000, 3000, *blake3::hash(&data).as_bytes());
+        let mut corrupt = data[1000..2000].to_vec();
+        corrupt[17] ^= 0x01;
+
+        assert!(buf.insert(0, &data[..1000]));
+        assert!(buf.insert(1, &corrupt));
+        assert!(buf.insert(2, &data[2000..]));
+        assert!(buf.is_complete() && !buf.is_intact());
+
+        // The sender's re-send overwrites the bad copy; it's still a duplicate by count.
+        assert!(!buf.insert(1, &data[1000..2000]));
+        assert!(buf.is_intact());
+        assert_eq!(buf.take_data(), data);
+    }
+
+    #[test]
+    fn test_receive_buffer_only_repairs_once_complete() {
+        let data: Vec<u8> = (0..3000u32).map(|i| i as u8).collect();
+        let mut buf = ReceiveBuffer::new(3, 1000, 3000, *blake3::hash(&data).as_bytes());
+        let mut other = data[..1000].to_vec();
+        other[3] ^= 0x80;
+
+        // Mid-transfer a differing duplicate is dropped: the copy already held stays.
+        assert!(buf.insert(0, &data[..1000]));
+        assert!(!buf.insert(0, &other));
+        assert_eq!(buf.stored(0), Some(&data[..1000]));
+        assert_eq!(buf.stored(1), None);
+
+        // Intact is final: nothing rewrites it.
+        assert!(buf.insert(1, &data[1000..2000]));
+        assert!(buf.insert(2, &data[2000..]));
+        assert!(buf.is_intact());
+        assert!(!buf.insert(2, &other[..1000]));
+        assert!(buf.is_intact());
+        assert_eq!(buf.take_data(), data);
+    }
+
     #[test]
     fn test_receive_buffer_out_of_order() {
         let data = vec![0xCD; 5000];
@@ -316,6 +411,26 @@ mod tests {
         assert!(buf.verify());
     }
 
+    #[test]
+    fn test_receive_buffer_refuses_misfit_packets() {
+        let data = vec![0x5E; 2500]; // 1000 + 1000 + 500
+        let mut buf = ReceiveBuffer::new(3, 1000, 2500, *blake3::hash(&data).as_bytes());
+
+        // Past total_packets, however large.
+        assert!(!buf.accepts(3, 1000));
+        assert!(!buf.insert(u32::MAX, &data[..1000]));
+        // Truncated, overlong, and a last packet claiming a full slot.
+        assert!(!buf.insert(0, &data[..999]));
+        assert!(!buf.insert(1, &data[..1001]));
+        assert!(!buf.insert(2, &data[..1000]));
+        assert_eq!(buf.progress(), (0, 3), "nothing stored");
+
+        assert!(buf.insert(0, &data[..1000]));
+        assert!(buf.insert(1, &data[1000..2000]));
+        assert!(buf.insert(2, &data[2000..]));
+        assert!(buf.verify());
+    }
+
     #[test]
     fn test_send_buffer() {
         let data = vec![0xEF; 2500]; // 3 packets
@@ -0,0 +1,40 @@
+//! PT's "now". Every retransmit timer, backoff and stale sweep reads [`now`] rather than `Instant::now()`, so the network simulator (`pt::sim`, tests only) can push a transfer thru minutes of virtual time in a blink and get the same run from the same seed. Outside tests this is `Instant::now()` and the jittered backoff of [`crate::jitter_dur`], nothing more.
+
+use std::time::{Duration, Instant};
+
+#[cfg(not(test))]
+#[inline(always)]
+pub fn now() -> Instant {
+    Instant::now()
+}
+
+#[cfg(test)]
+thread_local! {
+    static VIRTUAL: std::cell::Cell<Option<Instant>> = const { std::cell::Cell::new(None) };
+}
+
+/// The virtual time when a simulator on this thread has set one, else the real clock.
+#[cfg(test)]
+pub fn now() -> Instant {
+    VIRTUAL.with(|v| v.get()).unwrap_or_else(Instant::now)
+}
+
+/// Pin (or with `None`, release) this thread's clock.
+#[cfg(test)]
+pub fn set_virtual(at: Option<Instant>) {
+    VIRTUAL.with(|v| v.set(at));
+}
+
+/// Time since `t` — `t.elapsed()` on PT's clock.
+pub fn since(t: Instant) -> Duration {
+    now().saturating_duration_since(t)
+}
+
+/// A backoff delay jittered to 50–100% ([`crate::jitter_dur`]). Under a virtual clock the jitter is off: the run must depend on the simulator's seed alone.
+pub fn jitter(base: Duration) -> Duration {
+    #[cfg(test)]
+    if VIRTUAL.with(|v| v.get()).is_some() {
+        return base;
+    }
+    crate::jitter_dur(base)
+}
@@ -0,0 +1,111 @@
+//! Fuzz entry points for PT's parsers — everything here reads bytes straight off the network from anyone. Compiled only with `--cfg fuzz`; a cargo-fuzz target is one line, e.g. `fuzz_target!(|b: &[u8]| photon_messenger::network::pt::fuzz::packet(b))`, built with `RUSTFLAGS="--cfg fuzz"`.
+//!
+//! Each function must return on any input. A panic — an out-of-range slice, an overflow, a failed round-trip check — is the finding.
+
+use super::*;
+use vsf::VsfType;
+
+/// A DATA packet. Whatever parses must re-serialise to a packet that parses back the same.
+pub fn data(bytes: &[u8]) {
+    if let Some(d) = PTData::from_bytes(bytes) {
+        let again = PTData::from_bytes(&d.to_bytes()).expect("re-encoded DATA parses");
+        assert_eq!((again.stream_id, again.sequence, again.payload), (d.stream_id, d.sequence, d.payload));
+    }
+}
+
+/// Any datagram thru the VSF control-packet path, then into whichever typed parser its name selects — the receive loop's own order.
+pub fn packet(bytes: &[u8]) {
+    match parse_pt_packet(bytes) {
+        Some(ParsedPtPacket::HeaderOnly { name, provenance_hash, values }) => match name.as_str() {
+            "pt_ack" => {
+                let _ = PTAck::from_vsf_header(provenance_hash, &values);
+            }
+            "pt_nak" => {
+                let _ = PTNak::from_vsf_header(&values);
+            }
+            "pt_ctrl" => {
+                let _ = PTControl::from_vsf_header(provenance_hash, &values);
+            }
+            "pt_done" => {
+                let _ = PTComplete::from_vsf_header(provenance_hash, &values);
+            }
+            _ => {}
+        },
+        Some(ParsedPtPacket::Section { fields, .. }) => {
+            let _ = PTSpec::from_vsf_fields(&fields);
+        }
+        None => {}
+    }
+}
+
+/// The typed header parsers fed values built from `bytes`, skipping VSF decoding — reaches value shapes a well-formed header would rarely carry.
+pub fn header_values(bytes: &[u8]) {
+    let values = values_from(bytes);
+    let hash = *blake3::hash(bytes).as_bytes();
+    let _ = PTAck::from_vsf_header(hash, &values);
+    let _ = PTNak::from_vsf_header(&values);
+    let _ = PTControl::from_vsf_header(hash, &values);
+    let _ = PTComplete::from_vsf_header(hash, &values);
+}
+
+/// [`PTSpec::from_vsf_fields`] over fields named from SPEC's own field names, values built from `bytes`.
+pub fn spec_fields(bytes: &[u8]) {
+    const NAMES: [&str; 6] = ["sid", "count", "psize", "total", "hash", "other"];
+    let fields: Vec<(String, VsfType)> = values_from(bytes)
+        .into_iter()
+        .enumerate()
+        .map(|(i, v)| (NAMES[bytes.get(i).copied().unwrap_or(0) as usize % NAMES.len()].to_string(), v))
+        .collect();
+    let _ = PTSpec::from_vsf_fields(&fields);
+}
+
+/// An app datagram: split, signature check, and the full receive (which must never ack what it didn't deliver).
+pub fn app_datagram(bytes: &[u8]) {
+    let _ = app_datagram_sender(bytes);
+    let mut pt = PTManager::new(fuzz_keypair());
+    let opened = open_app_datagram(bytes, pt.keypair().public.as_bytes()).is_some();
+    let peer = SocketAddr::from(([127, 0, 0, 1], 1));
+    // Delivered ⇒ opened (an opened one may still be refused as stale); never an ack without a delivery.
+    let acked = pt.receive_app_datagram(peer, bytes).is_some();
+    assert!(!acked || opened);
+    assert_eq!(pt.take_app_datagrams().len(), acked as usize);
+}
+
+/// A value list from raw bytes: each tag byte picks a VSF type, the bytes after it fill it.
+fn values_from(bytes: &[u8]) -> Vec<VsfType> {
+    let mut out = Vec::new();
+    let mut rest = bytes;
+    while let Some((&tag, tail)) = rest.split_first() {
+        let take = |n: usize| {
+            let mut v = [0u8; 8];
+            let n = n.min(tail.len());
+            v[..n].copy_from_slice(&tail[..n]);
+            (u64::from_le_bytes(v), n)
+        };
+        let (value, used) = match tag % 7 {
+            0 => take(1),
+            1 => take(8),
+            2 => take(2),
+            3 => take(4),
+            4 => take(8),
+            _ => (0, (tag as usize / 7).min(tail.len())),
+        };
+        out.push(match tag % 7 {
+            0 => VsfType::u3(value as u8),
+            1 => VsfType::u(value as usize, tag & 0x80 != 0),
+            2 => VsfType::u4(value as u16),
+            3 => VsfType::u5(value as u32),
+            4 => VsfType::u6(value),
+            5 => VsfType::hb(tail[..used].to_vec()),
+            _ => VsfType::hp(tail[..used].to_vec()),
+        });
+        rest = &tail[used..];
+    }
+    out
+}
+
+fn fuzz_keypair() -> Keypair {
+    let secret = ed25519_dalek::SigningKey::from_bytes(&[0x5A; 32]);
+    let public = (&secret).into();
+    Keypair { secret, public }
+}
@@ -18,17 +18,33 @@
 //! - VSF-encoded control packets, minimal DATA headers
 //! - Bidirectional transfers (both parties can send simultaneously)
 //! - Multiple concurrent transfers per peer (keyed by stream_id)
+//!
+//! **First-byte / stream_id reservation** — the receiver routes every datagram on its first byte, so these never overlap:
+//! - `0x00` ([`PTManager::PACKET_ACK_STREAM_ID`]): small-packet delivery ack (inside a `pt_ack`)
+//! - `'a'`–`'z'`: sharded transfer streams — chat, CLUTCH, avatars, history
+//! - `'A'` ([`PTManager::APP_DATAGRAM_STREAM_ID`]): app datagrams — an embedding application's own signed payloads ([`PTManager::send_app_datagram`])
+//! - `'R'`: VSF frames (`RÅ<`) — PT control packets and photon's small messages
+//!
+//! Nothing photon sends starts with any other byte; new uses take an unused uppercase letter here.
 
 pub mod buffer;
+pub mod clock;
+#[cfg(fuzz)]
+pub mod fuzz;
 pub mod packets;
+#[cfg(test)]
+pub(crate) mod sim;
 pub mod state;
+pub mod transport;
 pub mod window;
 
 pub use buffer::{ReceiveBuffer, SendBuffer};
 pub use packets::*;
 pub use state::*;
+pub use transport::*;
 pub use window::*;
 
+use crate::crypto::ct;
 use crate::network::fgtw::Keypair;
 use std::net::SocketAddr;
 use std::time::{Duration, Instant};
@@ -63,10 +79,59 @@ pub struct TickSend {
     pub wire_bytes: Vec<u8>,
     /// When `Some`, also send this WHOLE VSF payload over a TCP connection (the reliable fallback).
     /// UDP is preferred and carries `wire_bytes` (a PT shard); TCP is tried in parallel only after the UDP SPEC has gone ~1s without an ACK, and carries the entire pre-sharded VSF once — no PT stream framing, since TCP is ordered/reliable and the VSF `l` field self-frames the length.
-    pub tcp_payload: Option<Vec<u8>>,
+    pub tcp_fallback: Option<TcpFallback>,
     pub relay: Option<RelayInfo>,
 }
 
+/// A [`TickSend`]'s whole-payload TCP send: the VSF, and every address the peer may be reached at — `peer_addr` first, then the alternate (LAN vs WAN) — so the connect can race them (`tcp::connect_race`) instead of waiting out a dead primary.
+#[derive(Debug)]
+pub struct TcpFallback {
+    pub payload: Vec<u8>,
+    pub addrs: Vec<SocketAddr>,
+}
+
+/// An inbound SPEC held for the user's accept/decline (its size is over the auto-download limit). Nothing is allocated and no DATA is requested until [`PTManager::accept_spec`]; the sender is told to hold meanwhile (`Pause`), so it doesn't fall back to pushing the whole payload over TCP or relay.
+#[derive(Debug, Clone)]
+pub struct PendingSpec {
+    pub peer_addr: SocketAddr,
+    /// Device pubkey that signed the SPEC — what the flood limits count against.
+    pub sender: [u8; 32],
+    pub spec: PTSpec,
+    /// When the SPEC was first held — a pending SPEC nobody decides on expires after [`PTManager::HOLD_TIMEOUT`].
+    pub since: Instant,
+}
+
+/// [`PTManager::handle_datagram`]: what one PT datagram did.
+#[derive(Debug, Default)]
+pub struct Handled {
+    /// Owed back to the datagram's source, in order.
+    pub replies: Vec<Vec<u8>>,
+    /// The payload of the inbound transfer this datagram finished, with that transfer's [`PTManager::inbound_stats`].
+    pub received: Option<(Vec<u8>, Option<(u32, u32, u32, u64)>)>,
+    /// A SPEC this datagram newly held for the user's go-ahead (over the auto-download limit): its stream and size.
+    pub held: Option<(u8, u32)>,
+}
+
+/// [`PTManager::peer_transfers`]: what PT has in flight with one peer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PeerTransfers {
+    pub outbound: usize,
+    pub inbound: usize,
+    /// An outbound SPEC went unanswered long enough that the whole payload went over TCP.
+    pub tcp: bool,
+    /// ... and past that, handed to the relay.
+    pub relay: bool,
+}
+
+/// A verified app datagram off the wire, waiting in [`PTManager::take_app_datagrams`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppDatagram {
+    /// Device pubkey that signed it — the embedder decides whether that's a contact it talks to.
+    pub from: [u8; 32],
+    pub peer_addr: SocketAddr,
+    pub payload: Vec<u8>,
+}
+
 /// PT Manager - coordinates transfers for all peers
 pub struct PTManager {
     /// Outbound transfers (we're sending) - multiple per peer allowed
@@ -84,8 +149,33 @@ pub struct PTManager {
     next_stream_id: u8,
     /// Monotonic transfer ID counter for external tracking
     next_transfer_id: usize,
+    /// Auto-download policy: inbound transfers larger than this many bytes wait in `pending_specs` for the user. `None` = accept everything (the default).
+    auto_accept_limit: Option<u32>,
+    /// Hard ceiling on an inbound transfer's declared size — a SPEC over it is aborted before its receive buffer exists, held or not.
+    max_transfer_size: u32,
+    /// Ceiling on the receive buffers of all open inbound transfers together, every peer counted.
+    inbound_budget: u64,
+    /// Inbound SPECs held by the auto-download policy, awaiting accept/decline.
+    pending_specs: Vec<PendingSpec>,
+    /// When each sender's recent new SPECs arrived (by signing pubkey), for the [`Self::SPEC_RATE_MAX`] limit. Pruned to [`Self::SPEC_RATE_WINDOW`] on every SPEC.
+    spec_arrivals: Vec<([u8; 32], Instant)>,
+    /// Per-peer throughput samples — one per finished transfer, either direction (see [`Self::throughput`]).
+    throughput: crate::network::inspect::ThroughputSampler,
+    /// (packet hash, datagram id) of our app datagrams still in `outbound_packets`.
+    app_pending: Vec<([u8; 32], AppDatagramId)>,
+    /// Ids of app datagrams the peer acked, for [`Self::take_app_deliveries`].
+    app_delivered: Vec<AppDatagramId>,
+    /// Verified inbound app datagrams, for [`Self::take_app_datagrams`].
+    app_inbox: Vec<AppDatagram>,
+    /// (signer, nonce, signed time) of inbound app datagrams still inside [`Self::APP_DATAGRAM_MAX_AGE_OSC`] — a retransmit whose ack was lost is re-acked, not delivered twice, and a replay is neither. Capped at [`Self::APP_SEEN_MAX`]; evicting raises `app_seen_floor`.
+    app_seen: Vec<([u8; 32], AppDatagramId, i64)>,
+    /// Nothing signed at or before this time is delivered — the newest evicted `app_seen` entry, so an evicted nonce stays refused.
+    app_seen_floor: i64,
 }
 
+/// An app datagram's id: its 16-byte signed nonce, drawn fresh per datagram by the sender and reported back by [`PTManager::take_app_deliveries`].
+pub type AppDatagramId = [u8; 16];
+
 impl PTManager {
     /// Create new PT manager
     pub fn new(keypair: Keypair) -> Self {
@@ -97,9 +187,25 @@ impl PTManager {
             stale_timeout: Duration::from_secs(30),
             next_stream_id: b'a',
             next_transfer_id: 0,
+            auto_accept_limit: None,
+            max_transfer_size: Self::DEFAULT_MAX_TRANSFER_SIZE,
+            inbound_budget: Self::DEFAULT_INBOUND_BUDGET,
+            pending_specs: Vec::new(),
+            spec_arrivals: Vec::new(),
+            throughput: crate::network::inspect::ThroughputSampler::new(),
+            app_pending: Vec::new(),
+            app_delivered: Vec::new(),
+            app_inbox: Vec::new(),
+            app_seen: Vec::new(),
+            app_seen_floor: i64::MIN,
         }
     }
 
+    /// Rolling per-peer throughput for diagnostics: bytes of every completed transfer, sent (on its verified COMPLETE) or received (on drain), sampled when it finished.
+    pub fn throughput(&self) -> &crate::network::inspect::ThroughputSampler {
+        &self.throughput
+    }
+
     /// Get reference to keypair (for relay fallback)
     pub fn keypair(&self) -> &Keypair {
         &self.keypair
@@ -119,6 +225,24 @@ impl PTManager {
         id
     }
 
+    /// How long a held transfer waits for a decision — the receiver's pending SPEC and the paused sender both give up after this. Long enough for the user to notice the prompt; short enough that a forgotten one doesn't pin the sender's payload in memory all day.
+    pub const HOLD_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+    /// Most SPECs held for a decision at once, every sender counted. The per-sender cap bounds one contact; this bounds the prompt queue when many hold at once — a SPEC past it is refused for its stream, not held.
+    pub const MAX_PENDING_SPECS: usize = 16;
+
+    /// Default [`Self::set_max_transfer_size`]: twice the largest attachment ([`crate::types::MAX_ATTACHMENT_BYTES`]), headroom for the message envelope around it.
+    pub const DEFAULT_MAX_TRANSFER_SIZE: u32 = 1 << 24;
+
+    /// Default [`Self::set_inbound_budget`]: sixteen maximum-size transfers open at once.
+    pub const DEFAULT_INBOUND_BUDGET: u64 = 1 << 28;
+
+    /// Most inbound transfers one sender may have open or held at once — one per stream letter, more than an honest sender ever has in flight. Counted by the pubkey that signed the SPECs, so a sender rotating source ports or addresses doesn't get a fresh allowance per address.
+    pub const MAX_INBOUND_PER_PEER: usize = 26;
+    /// Most new SPECs one sender (by pubkey) may start per [`Self::SPEC_RATE_WINDOW`]. A re-sent SPEC (same stream, same hash) isn't new; a SPEC replacing a stream with different data is.
+    pub const SPEC_RATE_MAX: usize = 64;
+    pub const SPEC_RATE_WINDOW: Duration = Duration::from_secs(10);
+
     /// Max VSF size for single UDP packet (no sharding needed) 1KB threshold - VSF this size or smaller sent directly Larger VSF gets sharded into [lowercase letter][packet number][1KB DATA] packets
     pub const SINGLE_PACKET_MAX: usize = 1024;
     /// Retry cap for a reliable small packet before the stop-and-wait head is dropped and the per-peer FIFO advances. With 1→2→…→60s backoff, ~5 retries ≈ 30-60s of trying — long enough to ride out a brief blip, short enough that an undeliverable head (dead avatar request) can't blackhole the chat queued behind it. The higher layer re-queues (chat retransmit / avatar→FGTW), so a drop is a deferral, not a loss.
@@ -203,18 +327,134 @@ impl PTManager {
         spec_bytes
     }
 
-    /// Handle received SPEC (start receiving)
-    pub fn handle_spec(&mut self, peer_addr: SocketAddr, spec: PTSpec) -> Vec<u8> {
+    /// Set the auto-download policy: inbound transfers over `limit` bytes are held for [`Self::accept_spec`] / [`Self::decline_spec`] instead of starting. `None` accepts everything.
+    pub fn set_auto_accept_limit(&mut self, limit: Option<u32>) {
+        self.auto_accept_limit = limit;
+    }
+
+    /// Set the largest inbound transfer this peer will take, in bytes. A SPEC over it (or one whose packet count doesn't match its size) is answered with a `Refuse` for that stream — the receive buffer a SPEC sizes is allocated up front, so its declared size is the peer's to pick and ours to refuse. Never an `Abort`: that drops everything the sender has going to us, not just the transfer we won't take.
+    pub fn set_max_transfer_size(&mut self, bytes: u32) {
+        self.max_transfer_size = bytes;
+    }
+
+    /// Set the most bytes of receive buffer all open inbound transfers may hold together. A SPEC that would start past it is refused for its stream — the per-sender caps bound one contact, this bounds all of them at once.
+    pub fn set_inbound_budget(&mut self, bytes: u64) {
+        self.inbound_budget = bytes;
+    }
+
+    /// Inbound SPECs currently held for a decision.
+    pub fn pending_specs(&self) -> &[PendingSpec] {
+        &self.pending_specs
+    }
+
+    /// Whether the SPEC for `(peer_addr, stream_id)` is held for a decision.
+    pub fn is_spec_pending(&self, peer_addr: SocketAddr, stream_id: u8) -> bool {
+        self.pending_specs.iter().any(|p| same_addr(p.peer_addr, peer_addr) && p.spec.stream_id == stream_id)
+    }
+
+    /// Handle received SPEC (start receiving). Over the auto-download limit the SPEC is held instead (see [`PendingSpec`]) and the reply is a `Pause` — a re-sent SPEC for a held transfer just gets the `Pause` again, so a lost one heals on the sender's next SPEC retry.
+    /// `sender` is the device pubkey that signed the SPEC; the flood limits are counted against it.
+    pub fn handle_spec(&mut self, peer_addr: SocketAddr, sender: [u8; 32], spec: PTSpec) -> Vec<u8> {
         crate::logf!("PT: Received SPEC from {} - stream '{}', {} packets, {} bytes, hash {}", peer_addr, spec.stream_id as char, spec.total_packets, spec.total_size, hex::encode(&spec.data_hash[..4]));
 
+        if !spec.is_consistent() || spec.total_size > self.max_transfer_size {
+            crate::logf!("PT: refusing SPEC from {} (stream '{}'): {} packets of {} for {} bytes, cap {}", peer_addr, spec.stream_id as char, spec.total_packets, spec.packet_size, spec.total_size, self.max_transfer_size);
+            return PTControl::refuse(spec.stream_id, spec.data_hash).to_vsf_bytes(&self.keypair);
+        }
+
+        if let Some(why) = self.spec_flood(sender, &spec) {
+            crate::logf!("PT: refusing SPEC from {} (stream '{}'): {}", peer_addr, spec.stream_id as char, why);
+            return PTControl::refuse(spec.stream_id, spec.data_hash).to_vsf_bytes(&self.keypair);
+        }
+
+        if self.auto_accept_limit.is_some_and(|limit| spec.total_size > limit) {
+            let held = self.pending_specs.iter().position(|p| same_addr(p.peer_addr, peer_addr) && p.spec.stream_id == spec.stream_id);
+            match held {
+                // Same transfer re-sent: keep the original hold time.
+                Some(i) if ct::eq(self.pending_specs[i].spec.data_hash, spec.data_hash) => {}
+                // A new SPEC on the stream replaces the abandoned one.
+                Some(i) => self.pending_specs[i] = PendingSpec { peer_addr, sender, spec, since: clock::now() },
+                None if self.pending_specs.len() >= Self::MAX_PENDING_SPECS => {
+                    crate::logf!("PT: refusing SPEC from {} (stream '{}'): {} transfers already held for acceptance", peer_addr, spec.stream_id as char, Self::MAX_PENDING_SPECS);
+                    return PTControl::refuse(spec.stream_id, spec.data_hash).to_vsf_bytes(&self.keypair);
+                }
+                None => {
+                    crate::logf!("PT: holding {}-byte transfer from {} for acceptance (stream '{}')", spec.total_size, peer_addr, spec.stream_id as char);
+                    self.pending_specs.push(PendingSpec { peer_addr, sender, spec, since: clock::now() });
+                }
+            }
+            return PTControl::new(ControlCommand::Pause).to_vsf_bytes(&self.keypair);
+        }
+        if !self.within_inbound_budget(sender, &spec) {
+            crate::logf!("PT: refusing SPEC from {} (stream '{}'): {} bytes would pass the inbound budget of {}", peer_addr, spec.stream_id as char, spec.total_size, self.inbound_budget);
+            return PTControl::refuse(spec.stream_id, spec.data_hash).to_vsf_bytes(&self.keypair);
+        }
+        self.start_inbound(peer_addr, sender, spec)
+    }
+
+    /// Whether starting `spec` keeps the open inbound receive buffers within [`Self::set_inbound_budget`]. An open transfer the SPEC would replace (same sender, same stream) doesn't count — its buffer goes.
+    fn within_inbound_budget(&self, sender: [u8; 32], spec: &PTSpec) -> bool {
+        let open: u64 = self.inbound.iter()
+            .filter(|t| !t.is_complete() && !(t.sender == sender && t.stream_id == spec.stream_id))
+            .map(|t| t.receive_buffer.total_size() as u64)
+            .sum();
+        open + spec.total_size as u64 <= self.inbound_budget
+    }
+
+    /// Flood limits for a SPEC signed by `sender`: why it's refused, or `None` to go ahead (recorded against the rate limit if it's new). Only that sender's budget is spent — everyone else's transfers, and its own already open, carry on.
+    fn spec_flood(&mut self, sender: [u8; 32], spec: &PTSpec) -> Option<&'static str> {
+        let ours = |from: [u8; 32], stream_id: u8| from == sender && stream_id != spec.stream_id;
+        let resent = self.inbound.iter().any(|t| t.sender == sender && t.stream_id == spec.stream_id && ct::eq(t.receive_buffer.expected_hash(), spec.data_hash))
+            || self.pending_specs.iter().any(|p| p.sender == sender && p.spec.stream_id == spec.stream_id && ct::eq(p.spec.data_hash, spec.data_hash));
+        if resent {
+            return None;
+        }
+
+        // A SPEC on a stream already in use replaces that transfer, so it doesn't count toward the concurrent cap.
+        let open = self.inbound.iter().filter(|t| ours(t.sender, t.stream_id) && !t.is_complete()).count()
+            + self.pending_specs.iter().filter(|p| ours(p.sender, p.spec.stream_id)).count();
+        if open >= Self::MAX_INBOUND_PER_PEER {
+            return Some("too many transfers open");
+        }
+
+        self.spec_arrivals.retain(|(_, at)| clock::since(*at) < Self::SPEC_RATE_WINDOW);
+        if self.spec_arrivals.iter().filter(|(from, _)| *from == sender).count() >= Self::SPEC_RATE_MAX {
+            return Some("too many new SPECs");
+        }
+        self.spec_arrivals.push((sender, clock::now()));
+        None
+    }
+
+    /// Accept a held transfer: start receiving it and return the SPEC ACK to send, which resumes the paused sender — or, if starting it would pass the inbound budget, the `Refuse` that drops it. `None` if nothing is held for `(peer_addr, stream_id)` (declined, expired, or never held).
+    pub fn accept_spec(&mut self, peer_addr: SocketAddr, stream_id: u8) -> Option<Vec<u8>> {
+        let i = self.pending_specs.iter().position(|p| same_addr(p.peer_addr, peer_addr) && p.spec.stream_id == stream_id)?;
+        let held = self.pending_specs.remove(i);
+        if !self.within_inbound_budget(held.sender, &held.spec) {
+            crate::logf!("PT: can't take held transfer from {} (stream '{}'): {} bytes would pass the inbound budget of {}", held.peer_addr, stream_id as char, held.spec.total_size, self.inbound_budget);
+            return Some(PTControl::refuse(stream_id, held.spec.data_hash).to_vsf_bytes(&self.keypair));
+        }
+        crate::logf!("PT: accepted held transfer from {} (stream '{}', {} bytes)", held.peer_addr, stream_id as char, held.spec.total_size);
+        Some(self.start_inbound(held.peer_addr, held.sender, held.spec))
+    }
+
+    /// Decline a held transfer: forget it and return the `Refuse` to send, which drops it (and only it) on the sender. `None` if nothing is held for `(peer_addr, stream_id)`.
+    pub fn decline_spec(&mut self, peer_addr: SocketAddr, stream_id: u8) -> Option<Vec<u8>> {
+        let i = self.pending_specs.iter().position(|p| same_addr(p.peer_addr, peer_addr) && p.spec.stream_id == stream_id)?;
+        let held = self.pending_specs.remove(i);
+        crate::logf!("PT: declined held transfer from {} (stream '{}', {} bytes)", held.peer_addr, stream_id as char, held.spec.total_size);
+        Some(PTControl::refuse(stream_id, held.spec.data_hash).to_vsf_bytes(&self.keypair))
+    }
+
+    /// Open the inbound transfer for `spec` and build its SPEC ACK.
+    fn start_inbound(&mut self, peer_addr: SocketAddr, sender: [u8; 32], spec: PTSpec) -> Vec<u8> {
         let stream_id = spec.stream_id;
 
         // Remove any existing incomplete transfer for this (peer, stream_id) A new SPEC means peer has abandoned the old transfer
         self.inbound.retain(|t| {
-            !(same_addr(t.peer_addr, peer_addr) && t.stream_id == stream_id && !t.is_complete())
+            !((same_addr(t.peer_addr, peer_addr) || t.sender == sender) && t.stream_id == stream_id && !t.is_complete())
         });
 
-        let transfer = InboundTransfer::new(peer_addr, &spec);
+        let transfer = InboundTransfer::new(peer_addr, sender, &spec);
         self.inbound.push(transfer);
 
         // Send SPEC ACK (ACK with seq=MAX as special marker)
@@ -245,8 +485,9 @@ impl PTManager {
             }
             transfer.alt_addr = None;
             transfer.spec_acked = true;
+            transfer.held = false;
             transfer.state = TransferState::Transferring;
-            transfer.last_activity = Instant::now();
+            transfer.last_activity = clock::now();
             // Fresh stale budget for the just-proven path: whatever was burned before the lock (SPEC rounds against a dead primary can run 10+ seconds) must not bill the DATA phase.
             transfer.retries = 0;
 
@@ -284,13 +525,17 @@ impl PTManager {
         let Some(pos) = self
             .outbound_packets
             .iter()
-            .position(|p| p.packet_hash == packet_hash)
+            .position(|p| ct::eq(p.packet_hash, packet_hash))
         else {
             return Vec::new(); // no match (already acked / unknown)
         };
         // Remember which peer this packet was for, so we promote that peer's next queued packet.
         let peer_addr = self.outbound_packets[pos].peer_addr;
         self.outbound_packets.remove(pos);
+        if let Some(i) = self.app_pending.iter().position(|(h, _)| ct::eq(h, packet_hash)) {
+            let (_, id) = self.app_pending.swap_remove(i);
+            self.app_delivered.push(id);
+        }
 
         // If nothing else is in flight to this peer, promote the next queued packet for it.
         let peer_busy = self
@@ -311,6 +556,87 @@ impl PTManager {
         Vec::new()
     }
 
+    // =========================================================================
+    // App Datagrams ('A') =========================================================================
+
+    /// First byte of an app datagram: `[b'A'][sender pubkey:32][ed25519 sig:64][nonce:16][eagle time:8 LE][payload]`. Uppercase, so never a transfer stream ('a'-'z'), the packet-ack sentinel (0) or a VSF frame ('R'). The signature covers the RECIPIENT's pubkey, the nonce and the time as well as the payload, so a captured datagram can't be re-aimed at another device or replayed later.
+    pub const APP_DATAGRAM_STREAM_ID: u8 = b'A';
+    /// Bytes ahead of the payload.
+    const APP_DATAGRAM_HEADER: usize = 1 + 32 + 64 + 16 + 8;
+    /// Largest app payload: what's left of a single reliable packet after the header.
+    pub const APP_DATAGRAM_MAX: usize = Self::SINGLE_PACKET_MAX - Self::APP_DATAGRAM_HEADER;
+    /// Oldest signed time an inbound app datagram may carry. It's stamped when queued, and the stop-and-wait queue can hold it behind a stuck head before its own retries and the relay's store-and-forward — the same 10 min the CLUTCH offer window allows.
+    pub const APP_DATAGRAM_MAX_AGE_OSC: i64 = crate::network::replay::OFFER_MAX_AGE_OSC;
+    /// Inbound app datagram nonces remembered for duplicate and replay suppression.
+    const APP_SEEN_MAX: usize = 1024;
+
+    /// Send an application's own payload to the device `recipient_pubkey` at `peer_addr` as a signed datagram on the reliable small-packet path — same stop-and-wait queue, backoff and relay fallback as photon's chat, so it's ordered with everything else to that peer. `nonce` is the datagram's id ([`Self::take_app_deliveries`] reports it once the peer acks) and must be fresh per datagram. Returns the bytes to send now (empty while an earlier packet to this peer is in flight). Errors past [`Self::APP_DATAGRAM_MAX`].
+    pub fn send_app_datagram(
+        &mut self,
+        peer_addr: SocketAddr,
+        recipient_pubkey: [u8; 32],
+        payload: &[u8],
+        nonce: AppDatagramId,
+    ) -> Result<Vec<u8>, String> {
+        if payload.len() > Self::APP_DATAGRAM_MAX {
+            return Err(format!("App datagram is {} bytes, max {}", payload.len(), Self::APP_DATAGRAM_MAX));
+        }
+        let time = vsf::eagle_time_oscillations();
+        let sig = self.keypair.sign(&app_datagram_signed_bytes(&recipient_pubkey, &nonce, time, payload));
+        let mut wire = Vec::with_capacity(Self::APP_DATAGRAM_HEADER + payload.len());
+        wire.push(Self::APP_DATAGRAM_STREAM_ID);
+        wire.extend_from_slice(self.keypair.public.as_bytes());
+        wire.extend_from_slice(&sig.to_bytes());
+        wire.extend_from_slice(&nonce);
+        wire.extend_from_slice(&time.to_le_bytes());
+        wire.extend_from_slice(payload);
+        self.app_pending.push((*blake3::hash(&wire).as_bytes(), nonce));
+        Ok(self.send_with_pubkey(peer_addr, wire, Some(recipient_pubkey)))
+    }
+
+    /// Receiver side: an app datagram arrived. Verifies the signature (which must name US as the recipient) and the signed time, queues the payload for [`Self::take_app_datagrams`] (once — a retransmit is only re-acked) and returns the delivery-ack bytes to send back. `None` for a malformed, forged, misaddressed, stale or replayed datagram: no ack, so nothing is confirmed that wasn't really sent to us.
+    pub fn receive_app_datagram(&mut self, peer_addr: SocketAddr, bytes: &[u8]) -> Option<Vec<u8>> {
+        self.receive_app_datagram_at(peer_addr, bytes, vsf::eagle_time_oscillations())
+    }
+
+    fn receive_app_datagram_at(&mut self, peer_addr: SocketAddr, bytes: &[u8], now: i64) -> Option<Vec<u8>> {
+        let opened = open_app_datagram(bytes, self.keypair.public.as_bytes())?;
+        if now.saturating_sub(opened.time) > Self::APP_DATAGRAM_MAX_AGE_OSC || opened.time.saturating_sub(now) > crate::network::replay::PING_WINDOW_OSC {
+            crate::logf!("PT: app datagram from {} refused: signed time outside the window", crate::fp(&opened.from));
+            return None;
+        }
+        self.app_seen.retain(|&(_, _, t)| now.saturating_sub(t) <= Self::APP_DATAGRAM_MAX_AGE_OSC);
+        let seen = self.app_seen.iter().any(|(f, n, _)| ct::eq(f, opened.from) && ct::eq(n, opened.nonce));
+        if !seen {
+            if opened.time <= self.app_seen_floor {
+                return None;
+            }
+            if self.app_seen.len() >= Self::APP_SEEN_MAX {
+                if let Some(oldest) = (0..self.app_seen.len()).min_by_key(|&i| self.app_seen[i].2) {
+                    self.app_seen_floor = self.app_seen_floor.max(self.app_seen.swap_remove(oldest).2);
+                }
+            }
+            self.app_seen.push((opened.from, opened.nonce, opened.time));
+            self.app_inbox.push(AppDatagram { from: opened.from, peer_addr, payload: opened.payload.to_vec() });
+        }
+        Some(self.build_packet_ack(bytes))
+    }
+
+    /// Drain the app datagrams received since the last call, in arrival order.
+    pub fn take_app_datagrams(&mut self) -> Vec<AppDatagram> {
+        std::mem::take(&mut self.app_inbox)
+    }
+
+    /// Drain the ids of our app datagrams the peer has acked since the last call. An id that never shows up was given up on after [`Self::MAX_PACKET_RETRIES`] — see [`Self::is_app_datagram_pending`].
+    pub fn take_app_deliveries(&mut self) -> Vec<AppDatagramId> {
+        std::mem::take(&mut self.app_delivered)
+    }
+
+    /// True while an app datagram is still queued or retransmitting, awaiting its ack.
+    pub fn is_app_datagram_pending(&self, id: &AppDatagramId) -> bool {
+        self.app_pending.iter().any(|(_, n)| n == id)
+    }
+
     /// Handle received DATA packet Routes by (peer_addr, stream_id) to support concurrent transfers
     pub fn handle_data(&mut self, peer_addr: SocketAddr, data: PTData) -> Option<Vec<u8>> {
         // Find inbound transfer by peer AND stream_id
@@ -323,7 +649,7 @@ impl PTManager {
                 let (recv, total) = transfer.progress();
                 // Log at milestones: every 50 packets (but not 0) or completion
                 if recv == total || (recv > 0 && recv % 50 == 0) {
-                    crate::logf!("PT: Received {}/{} from {} stream '{}'", recv, total, peer_addr, data.stream_id as char);
+                    crate::logf_at!(crate::LogLevel::Debug, "PT: Received {}/{} from {} stream '{}'", recv, total, peer_addr, data.stream_id as char);
                 }
 
                 return Some(ack.to_vsf_bytes(&self.keypair));
@@ -356,14 +682,15 @@ impl PTManager {
                 && t.stream_id == ack.stream_id
                 && t.state == TransferState::Transferring
         }) {
-            transfer.handle_ack(&ack);
+            // A chunk hash that doesn't match what we sent: the repair goes out ahead of anything new.
+            packets.extend(transfer.handle_ack(&ack).map(|data| data.to_bytes()));
 
             // Only log progress at milestones (every 100 packets or completion) Avoids spamming logs with per-ACK updates
             let (acked, total) = transfer.send_buffer.progress();
             if acked == total {
-                crate::logf!("PT: All {}/{} ACK'd to {} stream '{}'", acked, total, peer_addr, ack.stream_id as char);
+                crate::logf_at!(crate::LogLevel::Debug, "PT: All {}/{} ACK'd to {} stream '{}'", acked, total, peer_addr, ack.stream_id as char);
             } else if acked > 0 && acked % 100 == 0 {
-                crate::logf!("PT: Progress {}/{} to {} stream '{}'", acked, total, peer_addr, ack.stream_id as char);
+                crate::logf_at!(crate::LogLevel::Debug, "PT: Progress {}/{} to {} stream '{}'", acked, total, peer_addr, ack.stream_id as char);
             }
 
             // Send more packets (pipelining phase sends packets_per_ack new packets)
@@ -402,9 +729,26 @@ impl PTManager {
                 self.outbound.retain(|t| !same_addr(t.peer_addr, peer_addr));
                 self.inbound.retain(|t| !same_addr(t.peer_addr, peer_addr));
             }
+            ControlCommand::Refuse => {
+                let Some((stream_id, data_hash)) = control.stream else {
+                    return;
+                };
+                crate::logf!("PT: Peer {} refused stream '{}'", peer_addr, stream_id as char);
+                self.outbound.retain(|t| {
+                    let to_peer = same_addr(t.peer_addr, peer_addr) || t.alt_addr.is_some_and(|a| same_addr(a, peer_addr));
+                    !(to_peer && t.stream_id == stream_id && ct::eq(t.send_buffer.data_hash(), data_hash))
+                });
+            }
             ControlCommand::Pause => {
-                // Could pause sending, but for now just log
+                // The receiver is holding our SPEC for its user (auto-download policy). Hold every not-yet-started transfer to it: SPEC keepalives only, no TCP/relay push, and the longer hold budget. A SPEC ACK resumes; a Refuse (or Abort) drops. Pause carries no stream id, so this is per peer.
                 crate::logf!("PT: Peer {} requested pause", peer_addr);
+                for t in self.outbound.iter_mut().filter(|t| {
+                    t.state == TransferState::AwaitingSpec
+                        && (same_addr(t.peer_addr, peer_addr) || t.alt_addr.is_some_and(|a| same_addr(a, peer_addr)))
+                }) {
+                    t.held = true;
+                    t.last_activity = clock::now();
+                }
             }
             ControlCommand::Resume => {
                 crate::logf!("PT: Peer {} requested resume", peer_addr);
@@ -428,7 +772,7 @@ impl PTManager {
         if let Some(transfer) = self
             .outbound
             .iter_mut()
-            .find(|t| same_addr(t.peer_addr, peer_addr) && t.send_buffer.data_hash() == complete.final_hash)
+            .find(|t| same_addr(t.peer_addr, peer_addr) && ct::eq(t.send_buffer.data_hash(), complete.final_hash))
         {
             let (packets, bytes, retransmits, duration_ms, max_window, rtt_ms, packet_size) =
                 transfer.stats();
@@ -454,12 +798,29 @@ impl PTManager {
                 };
 
                 crate::logf!("PT: → {} OK | {} | {:.1}s | {}B pkt | win {} | RTT {}ms | {:.0}% util ({} retx)", peer_addr, thruput_str, duration_ms as f64 / 1000.0, packet_size, max_window, rtt_ms, utilization, retransmits);
+                self.throughput.record(canon_addr(peer_addr), bytes as u64, clock::now());
             } else {
                 crate::logf!("PT: → {} FAILED verification ({} packets, {} bytes)", peer_addr, packets, bytes);
             }
         }
     }
 
+    /// A delivery receipt came back over the relay pipe from `from` for the payload hashing to `hash`. The relayed copy is the one that landed, so the matching relayed transfer is done even though no COMPLETE will ever arrive over UDP — promote it (a transfer the stale sweep already gave up on included) so the caller's `is_outbound_complete_by_id` stops waiting. The receipt's envelope signer must be the transfer's recipient. Returns whether a transfer matched.
+    pub fn handle_relay_receipt(&mut self, from: [u8; 32], hash: [u8; 32]) -> bool {
+        let Some(transfer) = self.outbound.iter_mut().find(|t| {
+            t.relay_sent
+                && t.recipient_pubkey == Some(from)
+                && t.state != TransferState::Complete
+                && t.original_payload.as_ref().is_some_and(|p| ct::eq(blake3::hash(p).as_bytes(), hash))
+        }) else {
+            return false;
+        };
+        transfer.state = TransferState::Complete;
+        transfer.complete_received = true;
+        crate::logf!("PT: → {} delivered via relay (stream '{}')", transfer.peer_addr, transfer.stream_id as char);
+        true
+    }
+
     /// Check if a SPECIFIC inbound transfer (peer + stream) is complete, return its COMPLETE packet.
     /// Stream-scoped: a peer can have several concurrent transfers (e.g. a CLUTCH offer AND a KEM response in flight at once), and they must not be confused — matching by address alone grabs whichever happens to be first in the vec, which silently drops the other.
     pub fn check_inbound_complete(&mut self, peer_addr: SocketAddr, stream_id: u8) -> Option<Vec<u8>> {
@@ -486,11 +847,12 @@ impl PTManager {
             same_addr(t.peer_addr, peer_addr)
                 && t.stream_id == stream_id
                 && t.is_complete()
-                && t.receive_buffer.verify()
         })?;
 
         let transfer = self.inbound.remove(idx);
-        Some(transfer.take_data())
+        let data = transfer.take_data();
+        self.throughput.record(canon_addr(peer_addr), data.len() as u64, clock::now());
+        Some(data)
     }
 
     /// Check if outbound transfer is complete (by peer address - any transfer)
@@ -531,7 +893,7 @@ impl PTManager {
 
     /// Periodic tick - check timeouts, send retransmits Returns TickSend structs with:
     /// - peer_addr, wire_bytes: UDP packet to send (the preferred path)
-    /// - tcp_payload: if Some, also send this whole VSF over TCP (reliable fallback, once per transfer)
+    /// - tcp_fallback: if Some, also send this whole VSF over TCP (reliable fallback, once per transfer), racing every candidate address
     /// - relay: if Some, UDP+TCP failed, relay via /conduit with this info
     pub fn tick(&mut self) -> Vec<TickSend> {
         let mut to_send = Vec::new();
@@ -545,6 +907,22 @@ impl PTManager {
             ) {
                 continue;
             }
+            // Held by the receiver's auto-download policy: keep the SPEC alive at the normal backoff (the receiver re-answers Pause, healing a lost one) but never push the payload over TCP or relay — that would download it anyway.
+            if transfer.held {
+                if clock::since(transfer.last_activity) > Self::HOLD_TIMEOUT {
+                    crate::logf!("PT: Held transfer to {} was never accepted - giving up", transfer.peer_addr);
+                    transfer.state = TransferState::Failed;
+                } else if transfer.spec_needs_retry() {
+                    transfer.mark_spec_sent();
+                    to_send.push(TickSend {
+                        peer_addr: transfer.peer_addr,
+                        wire_bytes: transfer.build_spec().to_vsf_bytes(&self.keypair),
+                        tcp_fallback: None,
+                        relay: None,
+                    });
+                }
+                continue;
+            }
             if transfer.is_stale(self.stale_timeout) {
                 crate::logf!("PT: Outbound transfer to {} timed out", transfer.peer_addr);
                 transfer.state = TransferState::Failed;
@@ -555,11 +933,14 @@ impl PTManager {
             if transfer.spec_needs_retry() {
                 // After 1s, also try TCP in parallel — but send the WHOLE VSF over TCP exactly once (not the SPEC shard, and not every retry). TCP is the reliable fallback; UDP sharding stays preferred and keeps going in parallel until one path ACKs.
                 let tcp_eligible = transfer.tcp_eligible();
-                let tcp_payload = if tcp_eligible && !transfer.tcp_sent {
+                let tcp_fallback = if tcp_eligible && !transfer.tcp_sent {
                     transfer.set_spec_tcp_fallback();
                     transfer.tcp_sent = true;
                     crate::logf!("PT: SPEC for stream '{}' to {} - sending whole payload over TCP (fallback, once)", transfer.stream_id as char, transfer.peer_addr);
-                    transfer.original_payload.clone()
+                    transfer.original_payload.clone().map(|payload| TcpFallback {
+                        payload,
+                        addrs: std::iter::once(transfer.peer_addr).chain(transfer.alt_addr).collect(),
+                    })
                 } else {
                     None
                 };
@@ -600,7 +981,7 @@ impl PTManager {
                 to_send.push(TickSend {
                     peer_addr: transfer.peer_addr,
                     wire_bytes: spec_bytes.clone(),
-                    tcp_payload,
+                    tcp_fallback,
                     relay,
                 });
 
@@ -609,7 +990,7 @@ impl PTManager {
                     to_send.push(TickSend {
                         peer_addr: alt,
                         wire_bytes: spec_bytes,
-                        tcp_payload: None,
+                        tcp_fallback: None,
                         relay: None,
                     });
                 }
@@ -621,7 +1002,7 @@ impl PTManager {
                     to_send.push(TickSend {
                         peer_addr: transfer.peer_addr,
                         wire_bytes: data.to_bytes(),
-                        tcp_payload: None,
+                        tcp_fallback: None,
                         relay: None, // DATA packets don't use relay
                     });
                 }
@@ -630,10 +1011,12 @@ impl PTManager {
 
         // Give-up-and-advance: an in-flight head that has retried past the cap is undeliverable (dead address, peer gone) and must NOT hold the per-peer FIFO forever — otherwise one stuck packet (e.g. an AVATAR_REQUEST to a peer that won't ack) head-of-line-blocks every chat message queued behind it, which is exactly the "messages stick after CLUTCH" bug. Dropping is safe: small packets carry their own higher-layer retransmit (chat re-queues via the CHAT retransmit sweep; avatar falls back to FGTW), so a dropped PT packet just re-enters later — but the queue keeps flowing meanwhile.
         let mut advanced_peers: Vec<SocketAddr> = Vec::new();
+        let app_pending = &mut self.app_pending;
         self.outbound_packets.retain(|pkt| {
             if pkt.in_flight && pkt.retry_count >= Self::MAX_PACKET_RETRIES {
                 crate::logf!("PT: giving up on undeliverable packet to {} after {} retries — advancing the queue", pkt.peer_addr, pkt.retry_count);
                 advanced_peers.push(pkt.peer_addr);
+                app_pending.retain(|(h, _)| *h != pkt.packet_hash);
                 false
             } else {
                 true
@@ -655,9 +1038,9 @@ impl PTManager {
             {
                 next.mark_sent();
                 let (paddr, payload, alt) = (next.peer_addr, next.payload.clone(), next.alt_addr);
-                to_send.push(TickSend { peer_addr: paddr, wire_bytes: payload.clone(), tcp_payload: None, relay: None });
+                to_send.push(TickSend { peer_addr: paddr, wire_bytes: payload.clone(), tcp_fallback: None, relay: None });
                 if let Some(alt) = alt {
-                    to_send.push(TickSend { peer_addr: alt, wire_bytes: payload, tcp_payload: None, relay: None });
+                    to_send.push(TickSend { peer_addr: alt, wire_bytes: payload, tcp_fallback: None, relay: None });
                 }
             }
         }
@@ -666,18 +1049,18 @@ impl PTManager {
         for pkt in self.outbound_packets.iter_mut() {
             if pkt.in_flight && pkt.needs_retransmit() {
                 pkt.mark_retransmit();
-                crate::logf!("PT: Retransmitting packet to {} (attempt {}, next backoff {}s)", pkt.peer_addr, pkt.retry_count, pkt.next_delay.as_secs());
+                crate::logf_at!(crate::LogLevel::Debug, "PT: Retransmitting packet to {} (attempt {}, next backoff {}s)", pkt.peer_addr, pkt.retry_count, pkt.next_delay.as_secs());
                 to_send.push(TickSend {
                     peer_addr: pkt.peer_addr,
                     wire_bytes: pkt.payload.clone(),
-                    tcp_payload: None,
+                    tcp_fallback: None,
                     relay: None,
                 });
                 if let Some(alt) = pkt.alt_addr {
                     to_send.push(TickSend {
                         peer_addr: alt,
                         wire_bytes: pkt.payload.clone(),
-                        tcp_payload: None,
+                        tcp_fallback: None,
                         relay: None,
                     });
                 }
@@ -692,6 +1075,11 @@ impl PTManager {
             }
         }
 
+        self.throughput.prune(clock::now());
+
+        // Held SPECs nobody decided on: the sender gives up on the same budget.
+        self.pending_specs.retain(|p| clock::since(p.since) <= Self::HOLD_TIMEOUT);
+
         // Remove failed transfers
         self.outbound.retain(|t| t.state != TransferState::Failed);
         self.inbound.retain(|t| t.state != TransferState::Failed);
@@ -699,6 +1087,114 @@ impl PTManager {
         to_send
     }
 
+    /// Live transfers with `peer` (any representation of the address; an outbound one still racing its alternate counts on either path), and whether any outbound one has fallen back to TCP or the relay.
+    pub fn peer_transfers(&self, peer: SocketAddr) -> PeerTransfers {
+        let live = |s: TransferState| !matches!(s, TransferState::Complete | TransferState::Failed);
+        let outbound: Vec<&OutboundTransfer> = self
+            .outbound
+            .iter()
+            .filter(|t| live(t.state) && (same_addr(t.peer_addr, peer) || t.alt_addr.is_some_and(|a| same_addr(a, peer))))
+            .collect();
+        PeerTransfers {
+            outbound: outbound.len(),
+            inbound: self.inbound.iter().filter(|t| live(t.state) && same_addr(t.peer_addr, peer)).count(),
+            tcp: outbound.iter().any(|t| t.tcp_sent && !t.spec_acked),
+            relay: outbound.iter().any(|t| t.relay_sent && !t.spec_acked),
+        }
+    }
+
+    /// Live transfers with every peer, both directions.
+    pub fn active_transfers(&self) -> usize {
+        let live = |s: TransferState| !matches!(s, TransferState::Complete | TransferState::Failed);
+        self.outbound.iter().filter(|t| live(t.state)).count() + self.inbound.iter().filter(|t| live(t.state)).count()
+    }
+
+    // =========================================================================
+    // Driving a Transport =========================================================================
+
+    /// Handle one PT datagram from `src`: DATA, a VSF control packet (SPEC/ACK/NAK/CONTROL/COMPLETE) or an app datagram. This is THE dispatch — photon's receive loop (`network::status`) and [`Self::drive`] both go thru it, and only react to the [`Handled`] it returns. `None` if the bytes aren't PT's — the caller's own message, to parse (and packet-ack) itself.
+    /// `trusted` gates the two things a stranger could use to make us allocate or deliver: a SPEC and an app datagram, by signer. Photon passes its contact list. A delivered app datagram or an acked one waits in [`Self::take_app_datagrams`] / [`Self::take_app_deliveries`], as ever.
+    pub fn handle_datagram(&mut self, src: SocketAddr, bytes: &[u8], trusted: &dyn Fn(&[u8; 32]) -> bool) -> Option<Handled> {
+        let mut handled = Handled::default();
+        if is_pt_data(bytes) {
+            let data = PTData::from_bytes(bytes)?;
+            // Completion + drain are stream-scoped, so capture the stream before `data` moves: concurrent transfers from one peer (CLUTCH offer + KEM response) must not cross-wire.
+            let stream_id = data.stream_id;
+            handled.replies.extend(self.handle_data(src, data));
+            if let Some(complete) = self.check_inbound_complete(src, stream_id) {
+                handled.replies.push(complete);
+                let stats = self.inbound_stats(&src);
+                handled.received = self.take_inbound_data(src, stream_id).map(|data| (data, stats));
+            }
+            return Some(handled);
+        }
+        if is_app_datagram(bytes) {
+            if app_datagram_sender(bytes).is_some_and(|pk| trusted(&pk)) {
+                handled.replies.extend(self.receive_app_datagram(src, bytes));
+            }
+            return Some(handled);
+        }
+        match parse_pt_packet(bytes)? {
+            ParsedPtPacket::HeaderOnly { name, provenance_hash, values } => match name.as_str() {
+                "pt_ack" => handled.replies = self.handle_ack(src, PTAck::from_vsf_header(provenance_hash, &values)?),
+                "pt_nak" => handled.replies = self.handle_nak(src, PTNak::from_vsf_header(&values)?),
+                "pt_ctrl" => self.handle_control(src, PTControl::from_vsf_header(provenance_hash, &values)?),
+                "pt_done" => {
+                    let complete = PTComplete::from_vsf_header(provenance_hash, &values)?;
+                    if !complete.success {
+                        crate::logf!("PT: Transfer FAILED from {}", src);
+                    }
+                    self.handle_complete(src, complete);
+                }
+                _ => return None,
+            },
+            ParsedPtPacket::Section { name, fields, sender_pubkey } if name == "pt_spec" => {
+                let spec = PTSpec::from_vsf_fields(&fields)?;
+                // Only a known signer may make us allocate — an unsigned or stranger's SPEC is dropped without a reply.
+                let Some(sender) = sender_pubkey.filter(|pk| trusted(pk)) else {
+                    crate::logf!("PT: SPEC REJECTED from {} - untrusted sender (pubkey: {})", src, sender_pubkey.map_or_else(|| "none".to_string(), |p| hex::encode(&p[..8])));
+                    return Some(handled);
+                };
+                let (stream_id, total_size) = (spec.stream_id, spec.total_size);
+                let was_held = self.is_spec_pending(src, stream_id);
+                handled.replies.push(self.handle_spec(src, sender, spec));
+                if !was_held && self.is_spec_pending(src, stream_id) {
+                    handled.held = Some((stream_id, total_size));
+                }
+            }
+            ParsedPtPacket::Section { .. } => return None,
+        }
+        Some(handled)
+    }
+
+    /// One turn of the loop over `transport`: handle everything that has arrived ([`Self::handle_datagram`]), then send whatever [`Self::tick`] says is due. Returns, in arrival order, each finished inbound transfer's payload and every datagram that wasn't PT's, both with their source. A relay hand-off in a tick is left out — the relay is reached thru FGTW, which is the app's business. Synchronous, and blocking whenever a tick falls back to [`Transport::send_stream`]: not for a tokio worker.
+    pub fn drive(
+        &mut self,
+        transport: &mut dyn Transport,
+        trusted: &dyn Fn(&[u8; 32]) -> bool,
+    ) -> Vec<(SocketAddr, Vec<u8>)> {
+        let mut out = Vec::new();
+        while let Some((src, bytes)) = transport.recv() {
+            match self.handle_datagram(src, &bytes, trusted) {
+                Some(handled) => {
+                    for reply in handled.replies {
+                        transport.send(src, &reply);
+                    }
+                    out.extend(handled.received.map(|(data, _)| (src, data)));
+                }
+                None => out.push((src, bytes)),
+            }
+        }
+        for send in self.tick() {
+            transport.send(send.peer_addr, &send.wire_bytes);
+            // A blocking carrier can't race: candidates in order until one takes it.
+            if let Some(fallback) = send.tcp_fallback {
+                let _ = fallback.addrs.iter().any(|&to| transport.send_stream(to, &fallback.payload));
+            }
+        }
+        out
+    }
+
     /// Check if we have an active transfer with peer
     pub fn has_transfer(&self, peer_addr: &SocketAddr) -> bool {
         self.outbound.iter().any(|t| t.peer_addr == *peer_addr)
@@ -727,6 +1223,52 @@ pub fn is_pt_data(bytes: &[u8]) -> bool {
     !bytes.is_empty() && (b'a'..=b'z').contains(&bytes[0])
 }
 
+/// Check if bytes are an app datagram (start with [`PTManager::APP_DATAGRAM_STREAM_ID`])
+pub fn is_app_datagram(bytes: &[u8]) -> bool {
+    bytes.first() == Some(&PTManager::APP_DATAGRAM_STREAM_ID)
+}
+
+/// The sender pubkey an app datagram claims, unverified — cheap enough to gate on before [`PTManager::receive_app_datagram`] checks the signature.
+pub fn app_datagram_sender(bytes: &[u8]) -> Option<[u8; 32]> {
+    if !is_app_datagram(bytes) || bytes.len() < 1 + 32 + 64 {
+        return None;
+    }
+    bytes[1..33].try_into().ok()
+}
+
+/// What an app datagram's signature covers: a context label, so the signature can't be lifted onto any other photon message, then the recipient device, nonce and signed time, then the payload.
+fn app_datagram_signed_bytes(recipient: &[u8; 32], nonce: &AppDatagramId, time: i64, payload: &[u8]) -> Vec<u8> {
+    let mut out = b"photon app datagram v1".to_vec();
+    out.extend_from_slice(recipient);
+    out.extend_from_slice(nonce);
+    out.extend_from_slice(&time.to_le_bytes());
+    out.extend_from_slice(payload);
+    out
+}
+
+/// A split, signature-checked app datagram (freshness is the receiver's to judge).
+struct OpenedAppDatagram<'a> {
+    from: [u8; 32],
+    nonce: AppDatagramId,
+    time: i64,
+    payload: &'a [u8],
+}
+
+/// Split and verify an app datagram addressed to `recipient`: `None` if malformed, or if the signature doesn't check out over that recipient.
+fn open_app_datagram<'a>(bytes: &'a [u8], recipient: &[u8; 32]) -> Option<OpenedAppDatagram<'a>> {
+    use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+    if bytes.len() < PTManager::APP_DATAGRAM_HEADER {
+        return None;
+    }
+    let from = app_datagram_sender(bytes)?;
+    let sig = Signature::from_bytes(bytes[33..97].try_into().ok()?);
+    let nonce: AppDatagramId = bytes[97..113].try_into().ok()?;
+    let time = i64::from_le_bytes(bytes[113..121].try_into().ok()?);
+    let payload = &bytes[PTManager::APP_DATAGRAM_HEADER..];
+    VerifyingKey::from_bytes(&from).ok()?.verify(&app_datagram_signed_bytes(recipient, &nonce, time, payload), &sig).ok()?;
+    Some(OpenedAppDatagram { from, nonce, time, payload })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -739,84 +1281,81 @@ mod tests {
     }
 
     #[test]
-    fn test_full_transfer_simulation() {
-        let sender_keypair = test_keypair();
-        let receiver_keypair = test_keypair();
-
-        let mut sender = PTManager::new(sender_keypair);
-        let mut receiver = PTManager::new(receiver_keypair);
+    fn relay_receipt_completes_the_relayed_transfer() {
+        let mut sender = PTManager::new(test_keypair());
+        let peer_addr: SocketAddr = "127.0.0.1:12345".parse().unwrap();
+        let (recipient, stranger) = ([0x5Au8; 32], [0x66u8; 32]);
+        let data = vec![0xCD; 4096];
+        sender.send_with_pubkey(peer_addr, data.clone(), Some(recipient));
+        let id = sender.outbound[0].transfer_id;
+
+        // UDP + TCP never got thru; the tick handed the payload to the relay, then gave up waiting for a COMPLETE.
+        sender.outbound[0].relay_sent = true;
+        sender.outbound[0].state = TransferState::Failed;
+
+        // The recipient peels it off its pipe and answers; the receipt comes back down ours.
+        let receipt = crate::network::fgtw::relay::build_relay_receipt(&data);
+        let hash = crate::network::fgtw::relay::parse_relay_receipt(&receipt).expect("receipt parses");
+
+        assert!(!sender.handle_relay_receipt(stranger, hash), "only the recipient can confirm delivery");
+        assert!(!sender.handle_relay_receipt(recipient, [0u8; 32]), "a receipt for other bytes matches nothing");
+        assert!(!sender.is_outbound_complete_by_id(id));
+
+        assert!(sender.handle_relay_receipt(recipient, hash));
+        assert!(sender.is_outbound_complete_by_id(id));
+        assert!(!sender.handle_relay_receipt(recipient, hash), "a duplicate receipt is a no-op");
+    }
 
+    #[test]
+    fn test_full_transfer_simulation() {
+        let sender_addr: SocketAddr = "127.0.0.1:54321".parse().unwrap();
         let peer_addr: SocketAddr = "127.0.0.1:12345".parse().unwrap();
+        // Both ends talk thru the trait, the way a real carrier is driven.
+        let (mut link_s, mut link_r) = Loopback::pair(sender_addr, peer_addr);
+        let (ts, tr): (&mut dyn Transport, &mut dyn Transport) = (&mut link_s, &mut link_r);
+        let trusted = |_: &[u8; 32]| true;
+
+        let mut sender = PTManager::new(test_keypair());
+        let mut receiver = PTManager::new(test_keypair());
         let data = vec![0xAB; 3000]; // 3 packets
 
         // Sender initiates
         let spec_bytes = sender.send(peer_addr, data.clone());
         assert!(!spec_bytes.is_empty());
+        ts.send(peer_addr, &spec_bytes);
 
-        // Parse SPEC and feed to receiver
-        let spec_fields = parse_vsf_section_fields(&spec_bytes);
-        let spec = PTSpec::from_vsf_fields(&spec_fields).expect("Failed to parse SPEC");
-        let spec_ack = receiver.handle_spec(peer_addr, spec.clone());
-        assert!(!spec_ack.is_empty());
-
-        // Parse SPEC ACK - it's now header-only format
+        // The receiver answers the SPEC with a SPEC ACK - header-only format, seq = MAX marker
+        assert!(receiver.drive(tr, &trusted).is_empty());
+        let (from, spec_ack) = ts.recv().expect("SPEC ACK over the link");
+        assert_eq!(from, peer_addr);
         let (provenance, values) =
             parse_pt_header_field(&spec_ack).expect("Failed to parse SPEC ACK header");
         let ack = PTAck::from_vsf_header(provenance, &values).expect("Failed to parse SPEC ACK");
         assert_eq!(ack.sequence, u32::MAX); // SPEC ACK marker
 
-        let mut data_packets = sender.handle_spec_ack(peer_addr, spec.stream_id, spec.data_hash);
+        // Its answer is the first DATA window
+        let data_packets = sender.handle_datagram(peer_addr, &spec_ack, &trusted).expect("PT packet").replies;
         assert!(!data_packets.is_empty(), "Should have data packets to send");
+        for packet in &data_packets {
+            ts.send(peer_addr, packet);
+        }
 
-        // Process DATA packets - window starts at 1 so we need multiple rounds
-        loop {
-            let mut new_packets = Vec::new();
-
-            for data_bytes in &data_packets {
-                let data_pkt = PTData::from_bytes(data_bytes).expect("Failed to parse DATA packet");
-                let ack_bytes = receiver
-                    .handle_data(peer_addr, data_pkt)
-                    .expect("Should get ACK for DATA");
-
-                // Parse ACK - header-only format
-                let (provenance, values) =
-                    parse_pt_header_field(&ack_bytes).expect("Failed to parse DATA ACK header");
-                let ack =
-                    PTAck::from_vsf_header(provenance, &values).expect("Failed to parse DATA ACK");
-
-                // handle_ack may return more packets to send as window grows
-                new_packets.extend(sender.handle_ack(peer_addr, ack));
-            }
-
-            // Check if we're done
-            if sender.outbound_state(&peer_addr) == Some(TransferState::AwaitingComplete) {
+        // DATA, ACKs (the window grows as they land) and COMPLETE all ride the link from here
+        let mut received = Vec::new();
+        for _ in 0..100 {
+            received.extend(receiver.drive(tr, &trusted));
+            sender.drive(ts, &trusted);
+            if sender.is_outbound_complete(&peer_addr) {
                 break;
             }
-
-            if new_packets.is_empty() {
-                break; // No more packets to send
-            }
-            data_packets = new_packets;
         }
+        assert_eq!(received, vec![(sender_addr, data.clone())]);
+        assert!(sender.is_outbound_complete(&peer_addr), "verified COMPLETE made it back");
 
-        // Check completion - header-only format
-        let complete_bytes = receiver
-            .check_inbound_complete(peer_addr, b'a')
-            .expect("Should have COMPLETE");
-        let (provenance, values) =
-            parse_pt_header_field(&complete_bytes).expect("Failed to parse COMPLETE header");
-        let complete =
-            PTComplete::from_vsf_header(provenance, &values).expect("Failed to parse COMPLETE");
-        assert!(complete.success);
-
-        sender.handle_complete(peer_addr, complete);
-        assert!(sender.is_outbound_complete(&peer_addr));
-
-        // Get received data
-        let received = receiver
-            .take_inbound_data(peer_addr, b'a')
-            .expect("Should have received data");
-        assert_eq!(received, data);
+        // Both ends sampled the finished transfer for the throughput graph.
+        let window = Duration::from_secs(60);
+        assert_eq!(sender.throughput().rate(&peer_addr, window, Instant::now()), data.len() as f64 / 60.0);
+        assert_eq!(receiver.throughput().rate(&sender_addr, window, Instant::now()), data.len() as f64 / 60.0);
     }
 
     #[test]
@@ -871,8 +1410,8 @@ mod tests {
             total_size: d.len() as u32,
             data_hash: *blake3::hash(d).as_bytes(),
         };
-        mgr.handle_spec(peer, spec(b'a', &data_a));
-        mgr.handle_spec(peer, spec(b'b', &data_b));
+        mgr.handle_spec(peer, [1; 32], spec(b'a', &data_a));
+        mgr.handle_spec(peer, [1; 32], spec(b'b', &data_b));
 
         // Deliver both final packets (order intentionally b-then-a to prove drain isn't positional).
         mgr.handle_data(
@@ -891,6 +1430,333 @@ mod tests {
         assert_eq!(mgr.take_inbound_data(peer, b'b'), Some(data_b));
     }
 
+    #[test]
+    fn transfer_over_the_auto_download_limit_waits_for_acceptance() {
+        let mut sender = PTManager::new(test_keypair());
+        let mut receiver = PTManager::new(test_keypair());
+        receiver.set_auto_accept_limit(Some(2048));
+        let peer: SocketAddr = "127.0.0.1:12345".parse().unwrap();
+        let control = |bytes: &[u8]| {
+            let (prov, values) = parse_pt_header_field(bytes).expect("control header");
+            PTControl::from_vsf_header(prov, &values).expect("control").command
+        };
+
+        // Under the limit: accepted straight away, as before.
+        let small = PTSpec::new(&[0x11; 1500], b'z');
+        let ack = receiver.handle_spec(peer, [1; 32], small);
+        let (prov, values) = parse_pt_header_field(&ack).unwrap();
+        assert_eq!(PTAck::from_vsf_header(prov, &values).unwrap().sequence, u32::MAX);
+        assert!(receiver.pending_specs().is_empty());
+
+        // Over it: held, answered with Pause, no inbound transfer opened.
+        let data = vec![0xAB; 3000];
+        let spec_bytes = sender.send(peer, data.clone());
+        let spec = PTSpec::from_vsf_fields(&parse_vsf_section_fields(&spec_bytes)).unwrap();
+        let reply = receiver.handle_spec(peer, [1; 32], spec.clone());
+        assert_eq!(control(&reply), ControlCommand::Pause);
+        assert!(receiver.is_spec_pending(peer, spec.stream_id));
+        assert!(receiver.check_inbound_complete(peer, spec.stream_id).is_none());
+        assert_eq!(receiver.inbound.len(), 1, "only the small transfer is open");
+        // The sender's SPEC retry is re-answered with Pause and doesn't double up the hold.
+        assert_eq!(control(&receiver.handle_spec(peer, [1; 32], spec.clone())), ControlCommand::Pause);
+        assert_eq!(receiver.pending_specs().len(), 1);
+
+        // The paused sender never pushes the payload over TCP or relay while it waits.
+        sender.handle_control(peer, PTControl::new(ControlCommand::Pause));
+        sender.outbound[0].spec_next_delay = Duration::ZERO;
+        sender.outbound[0].created_at -= Duration::from_secs(60);
+        let sends = sender.tick();
+        assert_eq!(sends.len(), 1, "a SPEC keepalive only");
+        assert!(sends[0].tcp_fallback.is_none() && sends[0].relay.is_none());
+
+        // Accept: the SPEC ACK goes back and DATA flows to completion.
+        let ack = receiver.accept_spec(peer, spec.stream_id).expect("held");
+        assert!(!receiver.is_spec_pending(peer, spec.stream_id));
+        let (prov, values) = parse_pt_header_field(&ack).unwrap();
+        let ack = PTAck::from_vsf_header(prov, &values).unwrap();
+        let mut packets = sender.handle_spec_ack(peer, ack.stream_id, ack.chunk_hash);
+        while !packets.is_empty() {
+            let mut next = Vec::new();
+            for bytes in &packets {
+                let ack_bytes = receiver.handle_data(peer, PTData::from_bytes(bytes).unwrap()).unwrap();
+                let (prov, values) = parse_pt_header_field(&ack_bytes).unwrap();
+                next.extend(sender.handle_ack(peer, PTAck::from_vsf_header(prov, &values).unwrap()));
+            }
+            packets = next;
+        }
+        assert!(receiver.check_inbound_complete(peer, spec.stream_id).is_some());
+        assert_eq!(receiver.take_inbound_data(peer, spec.stream_id), Some(data));
+
+        // Decline: a Refuse for that stream, and the hold is gone.
+        let other = PTSpec::new(&[0x22; 4000], b'c');
+        receiver.handle_spec(peer, [1; 32], other);
+        assert_eq!(control(&receiver.decline_spec(peer, b'c').expect("held")), ControlCommand::Refuse);
+        assert!(receiver.pending_specs().is_empty());
+        assert!(receiver.accept_spec(peer, b'c').is_none());
+    }
+
+    #[test]
+    fn held_specs_stop_at_the_cap_across_senders() {
+        let mut receiver = PTManager::new(test_keypair());
+        receiver.set_auto_accept_limit(Some(2048));
+        let control = |bytes: &[u8]| {
+            let (prov, values) = parse_pt_header_field(bytes).expect("control header");
+            PTControl::from_vsf_header(prov, &values).expect("control").command
+        };
+        // One held SPEC from each of many senders — each well inside its own per-sender cap.
+        for i in 0..PTManager::MAX_PENDING_SPECS as u8 {
+            let peer = SocketAddr::from(([10, 0, 0, i], 4383));
+            assert_eq!(control(&receiver.handle_spec(peer, [i; 32], PTSpec::new(&[i; 3000], b'h'))), ControlCommand::Pause);
+        }
+        let late = SocketAddr::from(([10, 0, 1, 1], 4383));
+        assert_eq!(control(&receiver.handle_spec(late, [0xEE; 32], PTSpec::new(&[0xEE; 3000], b'h'))), ControlCommand::Refuse);
+        assert_eq!(receiver.pending_specs().len(), PTManager::MAX_PENDING_SPECS);
+        // A held one's retry still gets its Pause.
+        let first = SocketAddr::from(([10, 0, 0, 0], 4383));
+        assert_eq!(control(&receiver.handle_spec(first, [0; 32], PTSpec::new(&[0; 3000], b'h'))), ControlCommand::Pause);
+    }
+
+    #[test]
+    fn oversized_or_misshapen_spec_is_refused_without_allocating() {
+        let mut receiver = PTManager::new(test_keypair());
+        receiver.set_auto_accept_limit(Some(2048));
+        let peer: SocketAddr = "127.0.0.1:12345".parse().unwrap();
+        let control = |bytes: &[u8]| {
+            let (prov, values) = parse_pt_header_field(bytes).expect("control header");
+            PTControl::from_vsf_header(prov, &values).expect("control").command
+        };
+        let spec = |total_packets, packet_size, total_size| PTSpec { stream_id: b'q', total_packets, packet_size, total_size, data_hash: [0x77; 32] };
+
+        // A 4 GiB claim would be a 4 GiB zeroed buffer: refused, nothing opened or held.
+        assert_eq!(control(&receiver.handle_spec(peer, [1; 32], spec(u32::MAX / 1024 + 1, 1024, u32::MAX))), ControlCommand::Refuse);
+        // Shapes that don't add up, at any size.
+        assert_eq!(control(&receiver.handle_spec(peer, [1; 32], spec(1, 1024, 3000))), ControlCommand::Refuse);
+        assert_eq!(control(&receiver.handle_spec(peer, [1; 32], spec(u32::MAX, 1024, 3000))), ControlCommand::Refuse);
+        assert_eq!(control(&receiver.handle_spec(peer, [1; 32], spec(0, 0, 0))), ControlCommand::Refuse);
+        assert!(receiver.inbound.is_empty() && receiver.pending_specs().is_empty());
+
+        // The cap is the embedder's: lowered, an ordinary transfer over it is refused; under it, accepted.
+        receiver.set_max_transfer_size(1500);
+        assert_eq!(control(&receiver.handle_spec(peer, [1; 32], PTSpec::new(&[0x11; 1600], b'r'))), ControlCommand::Refuse);
+        let (prov, values) = parse_pt_header_field(&receiver.handle_spec(peer, [1; 32], PTSpec::new(&[0x11; 1400], b's'))).unwrap();
+        assert_eq!(PTAck::from_vsf_header(prov, &values).unwrap().sequence, u32::MAX);
+        assert_eq!(receiver.inbound.len(), 1);
+    }
+
+    #[test]
+    fn a_refused_spec_drops_only_that_transfer_on_the_sender() {
+        let mut sender = PTManager::new(test_keypair());
+        let mut receiver = PTManager::new(test_keypair());
+        receiver.set_max_transfer_size(2048);
+        let peer: SocketAddr = "127.0.0.1:12345".parse().unwrap();
+        let spec_of = |bytes: &[u8]| PTSpec::from_vsf_fields(&parse_vsf_section_fields(bytes)).unwrap();
+
+        let small = spec_of(&sender.send(peer, vec![0x11; 1500]));
+        let big = spec_of(&sender.send(peer, vec![0x22; 3000]));
+        assert_eq!(sender.outbound.len(), 2);
+
+        // The receiver takes the small one and refuses the big one: the sender forgets the big one and keeps going with the small.
+        receiver.handle_spec(peer, [1; 32], small.clone());
+        let (prov, values) = parse_pt_header_field(&receiver.handle_spec(peer, [1; 32], big.clone())).expect("refusal");
+        let refusal = PTControl::from_vsf_header(prov, &values).expect("control");
+        assert_eq!(refusal.stream, Some((big.stream_id, big.data_hash)));
+        sender.handle_control(peer, refusal);
+        assert_eq!(sender.outbound.len(), 1);
+        assert_eq!(sender.outbound[0].stream_id, small.stream_id);
+
+        // A refusal naming other data on the stream (a stale one for a replaced transfer) drops nothing.
+        sender.handle_control(peer, PTControl::refuse(small.stream_id, [0x99; 32]));
+        assert_eq!(sender.outbound.len(), 1);
+    }
+
+    #[test]
+    fn corrupt_data_packet_is_resent_on_its_ack() {
+        let mut sender = PTManager::new(test_keypair());
+        let mut receiver = PTManager::new(test_keypair());
+        let peer: SocketAddr = "127.0.0.1:12345".parse().unwrap();
+        let header = |bytes: &[u8]| parse_pt_header_field(bytes).expect("header");
+
+        let data: Vec<u8> = (0..3000u32).map(|i| (i % 251) as u8).collect();
+        let spec_bytes = sender.send(peer, data.clone());
+        let spec = PTSpec::from_vsf_fields(&parse_vsf_section_fields(&spec_bytes)).unwrap();
+        let stream_id = spec.stream_id;
+        let (prov, values) = header(&receiver.handle_spec(peer, [1; 32], spec));
+        let ack = PTAck::from_vsf_header(prov, &values).unwrap();
+        let mut blast = sender.handle_spec_ack(peer, ack.stream_id, ack.chunk_hash);
+        assert_eq!(blast.len(), 3);
+
+        // One bit flipped in flight in the middle packet: every slot fills, but nothing completes on bad data.
+        *blast[1].last_mut().unwrap() ^= 0x01;
+        let acks: Vec<Vec<u8>> = blast.iter().map(|bytes| receiver.handle_data(peer, PTData::from_bytes(bytes).unwrap()).expect("ack")).collect();
+        assert!(receiver.check_inbound_complete(peer, stream_id).is_none());
+
+        // Its ACK's chunk hash gives it away: the sender re-sends that packet on the spot, not after a failed COMPLETE.
+        let mut repairs = Vec::new();
+        for bytes in &acks {
+            let (prov, values) = header(bytes);
+            repairs.extend(sender.handle_ack(peer, PTAck::from_vsf_header(prov, &values).unwrap()));
+        }
+        let repairs: Vec<PTData> = repairs.iter().filter_map(|bytes| PTData::from_bytes(bytes)).collect();
+        assert_eq!(repairs.len(), 1);
+        assert_eq!((repairs[0].sequence, &repairs[0].payload[..]), (1, &data[1024..2048]));
+
+        // The repair overwrites the bad copy, the transfer verifies, and the sender hears a good COMPLETE.
+        let (prov, values) = header(&receiver.handle_data(peer, repairs[0].clone()).expect("ack"));
+        assert!(sender.handle_ack(peer, PTAck::from_vsf_header(prov, &values).unwrap()).is_empty());
+        let (prov, values) = header(&receiver.check_inbound_complete(peer, stream_id).expect("complete"));
+        sender.handle_complete(peer, PTComplete::from_vsf_header(prov, &values).unwrap());
+        assert!(sender.is_outbound_complete(&peer));
+        assert_eq!(receiver.take_inbound_data(peer, stream_id), Some(data));
+    }
+
+    #[test]
+    fn spec_flood_from_one_sender_is_capped() {
+        let mut receiver = PTManager::new(test_keypair());
+        let (flooder, friend, churner) = ([0x66u8; 32], [0xF0u8; 32], [0x77u8; 32]);
+        let friend_addr: SocketAddr = "127.0.0.1:12345".parse().unwrap();
+        // The flooder rotates source ports; its allowance follows the key that signs, not the address.
+        let flooder_addr = |n: u8| SocketAddr::from(([127, 0, 0, 1], 6000 + n as u16));
+        let spec_acked = |bytes: &[u8]| {
+            let (prov, values) = parse_pt_header_field(bytes).expect("reply header");
+            PTAck::from_vsf_header(prov, &values).is_some_and(|a| a.sequence == u32::MAX)
+        };
+        let base = Instant::now();
+        clock::set_virtual(Some(base));
+
+        // A friend's transfer is already open when the flood starts.
+        assert!(spec_acked(&receiver.handle_spec(friend_addr, friend, PTSpec::new(&[0xF0; 3000], b'a'))));
+
+        // 100 SPECs on 100 streams from 100 ports: only MAX_INBOUND_PER_PEER open, the rest are refused.
+        let accepted = (100..200u8).filter(|&sid| spec_acked(&receiver.handle_spec(flooder_addr(sid), flooder, PTSpec::new(&[sid; 2000], sid)))).count();
+        assert_eq!(accepted, PTManager::MAX_INBOUND_PER_PEER);
+        assert_eq!(receiver.inbound.iter().filter(|t| t.sender == flooder).count(), PTManager::MAX_INBOUND_PER_PEER);
+        // A re-sent SPEC for one it holds is still answered, and the friend is untouched.
+        assert!(spec_acked(&receiver.handle_spec(flooder_addr(100), flooder, PTSpec::new(&[100; 2000], 100))));
+        assert_eq!(receiver.peer_transfers(friend_addr).inbound, 1);
+        assert!(spec_acked(&receiver.handle_spec(friend_addr, friend, PTSpec::new(&[0xF1; 3000], b'b'))));
+
+        // Churning one stream with fresh data stays under the cap but runs into the rate limit, whatever port each SPEC comes from...
+        let churn = |receiver: &mut PTManager, n: u32| spec_acked(&receiver.handle_spec(SocketAddr::from(([127, 0, 0, 1], 7000 + n as u16)), churner, PTSpec::new(&n.to_le_bytes().repeat(500), b'c')));
+        let accepted = (0..100).filter(|&n| churn(&mut receiver, n)).count();
+        assert_eq!(accepted, PTManager::SPEC_RATE_MAX);
+        assert_eq!(receiver.inbound.iter().filter(|t| t.sender == churner).count(), 1, "each new SPEC on the stream replaces the last");
+        // ...which refills once the window has passed.
+        clock::set_virtual(Some(base + PTManager::SPEC_RATE_WINDOW));
+        assert!(churn(&mut receiver, 100));
+        clock::set_virtual(None);
+    }
+
+    #[test]
+    fn inbound_budget_caps_all_senders_together() {
+        let mut receiver = PTManager::new(test_keypair());
+        receiver.set_inbound_budget(10_000);
+        let (a, b): (SocketAddr, SocketAddr) = ("127.0.0.1:1001".parse().unwrap(), "127.0.0.1:1002".parse().unwrap());
+        let spec_acked = |bytes: &[u8]| {
+            let (prov, values) = parse_pt_header_field(bytes).expect("reply header");
+            PTAck::from_vsf_header(prov, &values).is_some_and(|a| a.sequence == u32::MAX)
+        };
+        let control = |bytes: &[u8]| {
+            let (prov, values) = parse_pt_header_field(bytes).expect("reply header");
+            PTControl::from_vsf_header(prov, &values).map(|c| c.command)
+        };
+
+        // Two senders, each well inside its own caps, share one budget.
+        assert!(spec_acked(&receiver.handle_spec(a, [0xA0; 32], PTSpec::new(&[1; 6000], b'a'))));
+        assert!(spec_acked(&receiver.handle_spec(b, [0xB0; 32], PTSpec::new(&[2; 4000], b'a'))));
+        assert_eq!(control(&receiver.handle_spec(b, [0xB0; 32], PTSpec::new(&[3; 1], b'b'))), Some(ControlCommand::Refuse));
+        assert_eq!(receiver.inbound.len(), 2);
+        // A SPEC replacing a stream frees that stream's buffer first.
+        assert!(spec_acked(&receiver.handle_spec(a, [0xA0; 32], PTSpec::new(&[4; 5000], b'a'))));
+        assert_eq!(receiver.inbound.len(), 2);
+
+        // A held transfer accepted past the budget is refused rather than opened.
+        receiver.set_auto_accept_limit(Some(100));
+        assert_eq!(control(&receiver.handle_spec(b, [0xB0; 32], PTSpec::new(&[5; 2000], b'c'))), Some(ControlCommand::Pause));
+        assert_eq!(control(&receiver.accept_spec(b, b'c').expect("held")), Some(ControlCommand::Refuse));
+        assert_eq!(receiver.inbound.len(), 2);
+    }
+
+    #[test]
+    fn app_datagram_is_delivered_and_acked() {
+        let mut sender = PTManager::new(test_keypair());
+        let mut receiver = PTManager::new(test_keypair());
+        let peer: SocketAddr = "127.0.0.1:12345".parse().unwrap();
+        let from = *sender.keypair().public.as_bytes();
+        let to = *receiver.keypair().public.as_bytes();
+
+        let id = [7u8; 16];
+        let wire = sender.send_app_datagram(peer, to, b"ping from the game", id).expect("fits");
+        assert!(is_app_datagram(&wire) && !is_pt_data(&wire));
+        assert!(sender.is_app_datagram_pending(&id));
+
+        // A forged copy (payload swapped under the same signature) is neither delivered nor acked.
+        let mut forged = wire.clone();
+        *forged.last_mut().unwrap() ^= 1;
+        assert!(receiver.receive_app_datagram(peer, &forged).is_none());
+        // Nor is the genuine datagram at any device but the one it was signed for.
+        let mut bystander = PTManager::new(test_keypair());
+        assert!(bystander.receive_app_datagram(peer, &wire).is_none());
+        // Nor once its signed time has aged out of the window.
+        let now = vsf::eagle_time_oscillations();
+        assert!(receiver.receive_app_datagram_at(peer, &wire, now + PTManager::APP_DATAGRAM_MAX_AGE_OSC + crate::OSC_PER_SEC).is_none());
+
+        // The real one is delivered once; its retransmit is re-acked but not delivered again.
+        let ack_bytes = receiver.receive_app_datagram(peer, &wire).expect("verified");
+        assert_eq!(receiver.receive_app_datagram(peer, &wire), Some(ack_bytes.clone()));
+        assert_eq!(
+            receiver.take_app_datagrams(),
+            vec![AppDatagram { from, peer_addr: peer, payload: b"ping from the game".to_vec() }]
+        );
+        assert!(receiver.take_app_datagrams().is_empty());
+
+        // The ack goes back down the normal PT path and confirms delivery.
+        let (prov, values) = parse_pt_header_field(&ack_bytes).expect("ack header");
+        let ack = PTAck::from_vsf_header(prov, &values).expect("ack");
+        assert_eq!(ack.stream_id, PTManager::PACKET_ACK_STREAM_ID);
+        sender.handle_ack(peer, ack);
+        assert!(!sender.is_app_datagram_pending(&id));
+        assert_eq!(sender.take_app_deliveries(), vec![id]);
+        assert!(sender.take_app_deliveries().is_empty());
+
+        assert!(sender.send_app_datagram(peer, to, &[0; PTManager::APP_DATAGRAM_MAX + 1], [8u8; 16]).is_err());
+    }
+
+    #[test]
+    fn full_transfer_over_an_in_memory_transport() {
+        let (a, b): (SocketAddr, SocketAddr) = ("10.0.0.1:4000".parse().unwrap(), "10.0.0.2:4000".parse().unwrap());
+        let (mut link_a, mut link_b) = Loopback::pair(a, b);
+        let (ta, tb): (&mut dyn Transport, &mut dyn Transport) = (&mut link_a, &mut link_b);
+        let mut sender = PTManager::new(test_keypair());
+        let mut receiver = PTManager::new(test_keypair());
+        let anyone = |_: &[u8; 32]| true;
+
+        let data: Vec<u8> = (0..20_000u32).map(|i| (i % 251) as u8).collect();
+        let spec = sender.send(b, data.clone());
+        ta.send(b, &spec);
+
+        let mut received = Vec::new();
+        for _ in 0..1000 {
+            received.extend(receiver.drive(tb, &anyone));
+            sender.drive(ta, &anyone);
+            if sender.is_outbound_complete(&b) {
+                break;
+            }
+        }
+        assert_eq!(received, vec![(a, data)]);
+        assert!(sender.is_outbound_complete(&b), "COMPLETE made it back over the link");
+
+        // A small packet isn't PT's to interpret — it comes out of drive for the app, which acks it itself.
+        let small = "RÅ< a small message".as_bytes().to_vec();
+        ta.send(b, &sender.send(b, small.clone()));
+        assert_eq!(receiver.drive(tb, &anyone), vec![(a, small)]);
+
+        // An untrusted SPEC opens nothing and gets no answer.
+        ta.send(b, &sender.send(b, vec![0x11; 4096]));
+        assert!(receiver.drive(tb, &|_: &[u8; 32]| false).is_empty());
+        assert_eq!(receiver.active_transfers(), 0);
+        assert!(ta.recv().is_none());
+    }
+
     // Helper to parse VSF section fields (for legacy format like pt_spec)
     fn parse_vsf_section_fields(bytes: &[u8]) -> Vec<(String, vsf::VsfType)> {
         use vsf::file_format::VsfHeader;
@@ -50,6 +50,11 @@ impl PTSpec {
         }
     }
 
+    /// Whether the declared shape adds up: a nonzero packet size, and exactly as many packets as `total_size` needs at that size. What [`Self::new`] always builds; anything else is a SPEC nobody honest sent.
+    pub fn is_consistent(&self) -> bool {
+        self.packet_size != 0 && self.total_packets as u64 == (self.total_size as u64).div_ceil(self.packet_size as u64)
+    }
+
     /// Serialize to VSF bytes with signature
     pub fn to_vsf_bytes(&self, keypair: &Keypair) -> Vec<u8> {
         use vsf::{VsfBuilder, VsfType};
@@ -212,12 +217,14 @@ impl PTData {
             return None;
         }
 
+        // Five varint bytes carry 35 bits — a sequence past u32 is malformed, not something to truncate onto a real one.
         let (sequence, seq_len) = decode_vsf_uint(&bytes[1..])?;
+        let sequence = u32::try_from(sequence).ok()?;
         let payload = bytes[1 + seq_len..].to_vec();
 
         Some(Self {
             stream_id,
-            sequence: sequence as u32,
+            sequence,
             payload,
         })
     }
@@ -384,6 +391,8 @@ pub enum ControlCommand {
     Resume = 1,
     SlowDown = 2,
     Abort = 3,
+    /// Refuse ONE transfer: its stream id and data hash ride along, so the sender drops just that one and keeps the rest of what it has going to us.
+    Refuse = 4,
 }
 
 impl ControlCommand {
@@ -393,6 +402,7 @@ impl ControlCommand {
             1 => Some(Self::Resume),
             2 => Some(Self::SlowDown),
             3 => Some(Self::Abort),
+            4 => Some(Self::Refuse),
             _ => None,
         }
     }
@@ -401,44 +411,62 @@ impl ControlCommand {
 /// CONTROL packet - flow control signals
 ///
 /// Header-only VSF format:
-/// - provenance_hash = hash of command (integrity proof)
-/// - inline field: (pt_ctrl:u#{cmd})
+/// - provenance_hash = hash of command (integrity proof); for `Refuse`, the refused transfer's data hash
+/// - inline field: (pt_ctrl:u#{cmd}), or (pt_ctrl:u#{cmd},u#{stream_id}) for `Refuse`
 /// - No signature needed - provenance hash provides integrity
 #[derive(Clone, Debug)]
 pub struct PTControl {
     pub command: ControlCommand,
+    /// The one transfer a `Refuse` is about: (stream_id, data_hash). `None` for every other command.
+    pub stream: Option<(u8, [u8; 32])>,
 }
 
 impl PTControl {
+    /// A peer-wide command (`Pause`, `Resume`, `SlowDown`, `Abort`).
+    pub fn new(command: ControlCommand) -> Self {
+        Self { command, stream: None }
+    }
+
+    /// Refuse the transfer on `stream_id` carrying `data_hash`, and nothing else.
+    pub fn refuse(stream_id: u8, data_hash: [u8; 32]) -> Self {
+        Self { command: ControlCommand::Refuse, stream: Some((stream_id, data_hash)) }
+    }
+
     /// Serialize to VSF bytes (header-only, ~45 bytes vs 180+ before)
-    #[allow(dead_code)]
     pub fn to_vsf_bytes(&self, _keypair: &Keypair) -> Vec<u8> {
         use vsf::{VsfBuilder, VsfType};
 
-        let provenance = self.compute_provenance();
+        let (provenance, values) = match self.stream {
+            Some((stream_id, data_hash)) => (data_hash, vec![VsfType::u3(self.command as u8), VsfType::u3(stream_id)]),
+            None => (self.compute_provenance(), vec![VsfType::u3(self.command as u8)]),
+        };
 
         VsfBuilder::new()
             .creation_time_oscillations(vsf::eagle_time_oscillations())
             .provenance_hash(provenance)
             .provenance_only() // No signature - provenance hash provides integrity
-            .add_inline_field("pt_ctrl", vec![VsfType::u3(self.command as u8)])
+            .add_inline_field("pt_ctrl", values)
             .build()
             .unwrap_or_default()
     }
 
     /// Parse from VSF header (inline field format)
     ///
-    /// Expects header with provenance_hash and inline field: (pt_ctrl:u#{cmd})
-    pub fn from_vsf_header(field_values: &[vsf::VsfType]) -> Option<Self> {
+    /// Expects header with provenance_hash and inline field: (pt_ctrl:u#{cmd}). A `Refuse` must also carry its stream id, and its provenance hash is the refused transfer's data hash.
+    pub fn from_vsf_header(provenance_hash: [u8; 32], field_values: &[vsf::VsfType]) -> Option<Self> {
         use vsf::VsfType;
 
-        let cmd = field_values.first().and_then(|v| match v {
-            VsfType::u3(n) => ControlCommand::from_u8(*n),
-            VsfType::u(n, _) => ControlCommand::from_u8(*n as u8),
+        let small = |v: &VsfType| match v {
+            VsfType::u3(n) => Some(*n),
+            VsfType::u(n, _) => u8::try_from(*n).ok(),
             _ => None,
-        })?;
-
-        Some(Self { command: cmd })
+        };
+        let cmd = field_values.first().and_then(small).and_then(ControlCommand::from_u8)?;
+        if cmd != ControlCommand::Refuse {
+            return Some(Self::new(cmd));
+        }
+        let stream_id = field_values.get(1).and_then(small)?;
+        Some(Self::refuse(stream_id, provenance_hash))
     }
 
     fn compute_provenance(&self) -> [u8; 32] {
@@ -507,6 +535,97 @@ impl PTComplete {
     }
 }
 
+// ============================================================================
+// Wire Parsing ============================================================================
+
+/// Parsed PT packet info - either from header inline field or section body
+pub enum ParsedPtPacket {
+    /// Header-only format: (pt_name:value1,value2,...) with provenance hash
+    HeaderOnly {
+        name: String,
+        provenance_hash: [u8; 32],
+        values: Vec<vsf::VsfType>,
+    },
+    /// Section format: [pt_name (field:value)...] with optional sender pubkey from signature
+    Section {
+        name: String,
+        fields: Vec<(String, vsf::VsfType)>,
+        /// Sender's Ed25519 public key from header signature (for authentication)
+        sender_pubkey: Option<[u8; 32]>,
+    },
+}
+
+/// Parse VSF PT packet - supports both header-only and section formats
+pub fn parse_pt_packet(bytes: &[u8]) -> Option<ParsedPtPacket> {
+    use vsf::file_format::VsfHeader;
+
+    let (header, header_end) = VsfHeader::decode(bytes).ok()?;
+
+    // Extract provenance hash from header
+    let provenance_hash = match &header.provenance_hash {
+        vsf::VsfType::hp(hash) if hash.len() == 32 => {
+            let mut arr = [0u8; 32];
+            arr.copy_from_slice(hash);
+            arr
+        }
+        _ => return None,
+    };
+
+    // Check for header-only format first (inline fields like pt_ack, pt_nak, pt_ctrl, pt_done) These have fields with values directly in the header, no section body
+    for field in &header.fields {
+        if field.name.starts_with("pt_") && field.offset_bytes == 0 && field.size_bytes == 0 {
+            // This is a header-only field with inline values We need to re-parse to get the actual values
+            if let Some(values) = parse_header_inline_values(bytes, &field.name) {
+                return Some(ParsedPtPacket::HeaderOnly {
+                    name: field.name.clone(),
+                    provenance_hash,
+                    values,
+                });
+            }
+        }
+    }
+
+    // Extract sender pubkey from header signature (if present) This is the Ed25519 public key used to sign the packet
+    let sender_pubkey = match &header.signer_pubkey {
+        Some(vsf::VsfType::ke(key)) if key.len() == 32 => {
+            let mut arr = [0u8; 32];
+            arr.copy_from_slice(key);
+            Some(arr)
+        }
+        _ => None,
+    };
+
+    // Fall back to section body parsing — primary_section resolves the near-form name from the header TOC (the knowledge lives in the vsf crate now).
+    let section = header.primary_section(bytes, header_end).ok()?;
+    let section_name = section.name.clone();
+
+    let fields: Vec<(String, vsf::VsfType)> = section
+        .fields
+        .iter()
+        .filter_map(|f| f.values.first().map(|v| (f.name.clone(), v.clone())))
+        .collect();
+
+    Some(ParsedPtPacket::Section {
+        name: section_name,
+        fields,
+        sender_pubkey,
+    })
+}
+
+/// Parse inline values from a header field by name Returns the values for (name:val1,val2,...) format
+fn parse_header_inline_values(bytes: &[u8], target_name: &str) -> Option<Vec<vsf::VsfType>> {
+    use vsf::file_format::VsfHeader;
+
+    let (header, _) = VsfHeader::decode(bytes).ok()?;
+
+    // Find the target field in the header and return its inline values
+    header
+        .fields
+        .iter()
+        .find(|f| f.name == target_name)
+        .map(|f| f.inline_values.clone())
+}
+
 // ============================================================================
 // VSF Variable-Length Uint Encoding (matching VSF spec) ============================================================================
 
@@ -654,4 +773,52 @@ mod tests {
         };
         assert_eq!(spec.seq_bytes(), 2);
     }
+
+    #[test]
+    fn malformed_data_packets_are_rejected() {
+        // Empty, a stream_id outside 'a'-'z', a sequence varint cut off mid-way, one running past 32 bits.
+        let cases: [&[u8]; 7] = [&[], &[b'a'], &[b'A', 0, 1], &[b'{', 0], &[0, 0, 1], &[b'a', 0x80], &[b'a', 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]];
+        for bytes in cases {
+            assert!(PTData::from_bytes(bytes).is_none(), "{bytes:?}");
+        }
+        // The largest sequence a varint can hold past u32 is refused, not wrapped; u32::MAX itself parses.
+        assert!(PTData::from_bytes(&[b'a', 0xFF, 0xFF, 0xFF, 0xFF, 0x1F, 0xAB]).is_none());
+        assert_eq!(PTData::from_bytes(&[b'a', 0xFF, 0xFF, 0xFF, 0xFF, 0x0F, 0xAB]).map(|d| d.sequence), Some(u32::MAX));
+    }
+
+    #[test]
+    fn malformed_headers_are_rejected() {
+        use vsf::VsfType;
+        let hash = [0u8; 32];
+        // Inline values missing or of the wrong type.
+        assert!(PTAck::from_vsf_header(hash, &[]).is_none());
+        assert!(PTAck::from_vsf_header(hash, &[VsfType::u3(b'a')]).is_none());
+        assert!(PTAck::from_vsf_header(hash, &[VsfType::u3(b'a'), VsfType::hb(vec![1])]).is_none());
+        assert!(PTNak::from_vsf_header(&[VsfType::hb(Vec::new())]).is_none());
+        assert!(PTControl::from_vsf_header(hash, &[]).is_none());
+        assert!(PTControl::from_vsf_header(hash, &[VsfType::u3(9)]).is_none());
+        // A Refuse that doesn't name its stream.
+        assert!(PTControl::from_vsf_header(hash, &[VsfType::u3(ControlCommand::Refuse as u8)]).is_none());
+
+        // A SPEC without its data hash, or with a hash one byte short.
+        let mut fields = vec![
+            ("sid".to_string(), VsfType::u3(b'a')),
+            ("count".to_string(), VsfType::u(3, false)),
+            ("psize".to_string(), VsfType::u(1024, false)),
+            ("total".to_string(), VsfType::u(3000, false)),
+        ];
+        assert!(PTSpec::from_vsf_fields(&fields).is_none());
+        fields.push(("hash".to_string(), VsfType::hb(vec![0; 31])));
+        assert!(PTSpec::from_vsf_fields(&fields).is_none());
+
+        // Every truncation of a real ACK: too short to hold its 32-byte provenance hash is a clean None, and no cut anywhere panics.
+        let secret = ed25519_dalek::SigningKey::from_bytes(&[0x42; 32]);
+        let keypair = Keypair { public: (&secret).into(), secret };
+        let ack = PTAck::new(b'a', 7, b"chunk").to_vsf_bytes(&keypair);
+        for n in 0..ack.len() {
+            let parsed = parse_pt_packet(&ack[..n]);
+            assert!(n >= 35 || parsed.is_none(), "{n}-byte prefix parsed");
+        }
+        assert!(matches!(parse_pt_packet(&ack), Some(ParsedPtPacket::HeaderOnly { .. })));
+    }
 }
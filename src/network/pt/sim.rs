@@ -0,0 +1,261 @@
+//! In-process network simulator for PT tests. [`Sim`] joins [`PTManager`]s over a simulated link — latency, loss, reordering and duplication, all drawn from one seeded RNG — and runs them on PT's virtual clock ([`super::clock`]). A transfer that would spend a minute in retransmit backoff runs in milliseconds, and the same seed replays the same run datagram for datagram.
+
+use super::{clock, PTManager, Transport};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// What the simulated link does to every datagram, both directions.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Link {
+    /// One-way delay every datagram gets.
+    pub latency: Duration,
+    /// Extra delay drawn uniformly from `0..spread` per datagram. Wider than the gap between two sends, and they can arrive swapped.
+    pub spread: Duration,
+    /// Chance a datagram is lost.
+    pub loss: f64,
+    /// Chance a datagram that survives arrives twice (the copy draws its own delay).
+    pub duplicate: f64,
+}
+
+/// What the link did over a run — to check it was actually hostile, and to compare two runs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub sent: u64,
+    pub dropped: u64,
+    pub duplicated: u64,
+    /// Datagrams that arrived behind one sent after them.
+    pub reordered: u64,
+    pub delivered: u64,
+}
+
+struct Datagram {
+    at: Duration,
+    seq: u64,
+    from: SocketAddr,
+    to: SocketAddr,
+    bytes: Vec<u8>,
+}
+
+/// The medium every [`Port`] sends into.
+struct Wire {
+    rng: ChaCha8Rng,
+    link: Link,
+    now: Duration,
+    next_seq: u64,
+    in_flight: Vec<Datagram>,
+    stats: Stats,
+    /// Highest send sequence delivered to each address so far, for counting reordering.
+    newest: Vec<(SocketAddr, u64)>,
+}
+
+impl Wire {
+    fn send(&mut self, from: SocketAddr, to: SocketAddr, bytes: &[u8]) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.stats.sent += 1;
+        if self.rng.gen_bool(self.link.loss) {
+            self.stats.dropped += 1;
+            return;
+        }
+        let copies = if self.rng.gen_bool(self.link.duplicate) {
+            self.stats.duplicated += 1;
+            2
+        } else {
+            1
+        };
+        for _ in 0..copies {
+            let spread = self.link.spread.mul_f64(self.rng.gen::<f64>());
+            self.in_flight.push(Datagram { at: self.now + self.link.latency + spread, seq, from, to, bytes: bytes.to_vec() });
+        }
+    }
+
+    /// Everything due by now, in arrival order.
+    fn due(&mut self) -> Vec<Datagram> {
+        let now = self.now;
+        let (mut due, later): (Vec<_>, Vec<_>) = std::mem::take(&mut self.in_flight).into_iter().partition(|d| d.at <= now);
+        self.in_flight = later;
+        due.sort_by_key(|d| (d.at, d.seq));
+        for d in &due {
+            match self.newest.iter_mut().find(|(a, _)| *a == d.to) {
+                Some((_, newest)) if *newest > d.seq => self.stats.reordered += 1,
+                Some((_, newest)) => *newest = d.seq,
+                None => self.newest.push((d.to, d.seq)),
+            }
+        }
+        due
+    }
+}
+
+/// A peer's attachment to the wire.
+struct Port {
+    addr: SocketAddr,
+    wire: Rc<RefCell<Wire>>,
+    inbox: VecDeque<(SocketAddr, Vec<u8>)>,
+}
+
+impl Transport for Port {
+    fn send(&mut self, to: SocketAddr, bytes: &[u8]) {
+        self.wire.borrow_mut().send(self.addr, to, bytes);
+    }
+
+    fn recv(&mut self) -> Option<(SocketAddr, Vec<u8>)> {
+        self.inbox.pop_front()
+    }
+}
+
+/// One simulated peer.
+pub struct Node {
+    pub pt: PTManager,
+    port: Port,
+    /// What [`PTManager::drive`] handed up so far: finished transfers and datagrams that weren't PT's.
+    pub received: Vec<(SocketAddr, Vec<u8>)>,
+}
+
+/// Peers on a simulated link, stepped on PT's virtual clock. Owns this thread's clock while it lives.
+pub struct Sim {
+    wire: Rc<RefCell<Wire>>,
+    nodes: Vec<Node>,
+    base: Instant,
+}
+
+impl Sim {
+    /// Virtual time per [`Self::step`].
+    pub const STEP: Duration = Duration::from_millis(1);
+
+    pub fn new(seed: u64, link: Link) -> Self {
+        let base = Instant::now();
+        clock::set_virtual(Some(base));
+        let wire = Wire {
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            link,
+            now: Duration::ZERO,
+            next_seq: 0,
+            in_flight: Vec::new(),
+            stats: Stats::default(),
+            newest: Vec::new(),
+        };
+        Self { wire: Rc::new(RefCell::new(wire)), nodes: Vec::new(), base }
+    }
+
+    /// Attach a peer; it's reachable at the returned address (10.0.0.n:4000, n counting from 1).
+    pub fn join(&mut self, pt: PTManager) -> SocketAddr {
+        let addr = SocketAddr::from(([10, 0, 0, self.nodes.len() as u8 + 1], 4000));
+        let port = Port { addr, wire: self.wire.clone(), inbox: VecDeque::new() };
+        self.nodes.push(Node { pt, port, received: Vec::new() });
+        addr
+    }
+
+    pub fn node(&self, addr: SocketAddr) -> &Node {
+        self.nodes.iter().find(|n| n.port.addr == addr).expect("joined")
+    }
+
+    pub fn node_mut(&mut self, addr: SocketAddr) -> &mut Node {
+        self.nodes.iter_mut().find(|n| n.port.addr == addr).expect("joined")
+    }
+
+    /// `from` hands `data` to PT for `to` and puts the first bytes on the wire — what the app does with [`PTManager::send`]'s return.
+    pub fn send(&mut self, from: SocketAddr, to: SocketAddr, data: Vec<u8>) {
+        let node = self.node_mut(from);
+        let bytes = node.pt.send(to, data);
+        if !bytes.is_empty() {
+            node.port.send(to, &bytes);
+        }
+    }
+
+    /// Advance the clock by [`Self::STEP`], deliver what's due, and drive every peer once.
+    pub fn step(&mut self) {
+        let due = {
+            let mut wire = self.wire.borrow_mut();
+            wire.now += Self::STEP;
+            clock::set_virtual(Some(self.base + wire.now));
+            wire.due()
+        };
+        for d in due {
+            if let Some(node) = self.nodes.iter_mut().find(|n| n.port.addr == d.to) {
+                node.port.inbox.push_back((d.from, d.bytes));
+                self.wire.borrow_mut().stats.delivered += 1;
+            }
+        }
+        for node in &mut self.nodes {
+            let received = node.pt.drive(&mut node.port, &|_: &[u8; 32]| true);
+            node.received.extend(received);
+        }
+    }
+
+    /// Step until `done` holds or `limit` of virtual time has passed. Returns whether `done` was reached.
+    pub fn run_until(&mut self, limit: Duration, mut done: impl FnMut(&Self) -> bool) -> bool {
+        while self.elapsed() < limit {
+            if done(self) {
+                return true;
+            }
+            self.step();
+        }
+        done(self)
+    }
+
+    /// Virtual time since the simulation started.
+    pub fn elapsed(&self) -> Duration {
+        self.wire.borrow().now
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.wire.borrow().stats
+    }
+}
+
+impl Drop for Sim {
+    fn drop(&mut self) {
+        clock::set_virtual(None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::fgtw::Keypair;
+    use ed25519_dalek::SigningKey;
+
+    fn keypair() -> Keypair {
+        let secret = SigningKey::from_bytes(&[0x42; 32]);
+        let public = (&secret).into();
+        Keypair { secret, public }
+    }
+
+    /// One transfer from a to b over `link`: what b received, what the link did, and how long it took.
+    fn transfer(seed: u64, link: Link, data: &[u8]) -> (Vec<(SocketAddr, Vec<u8>)>, Stats, Duration) {
+        let mut sim = Sim::new(seed, link);
+        let a = sim.join(PTManager::new(keypair()));
+        let b = sim.join(PTManager::new(keypair()));
+        sim.send(a, b, data.to_vec());
+        sim.run_until(Duration::from_secs(120), |sim| !sim.node(b).received.is_empty());
+        (sim.node(b).received.clone(), sim.stats(), sim.elapsed())
+    }
+
+    #[test]
+    fn transfer_survives_20_percent_loss_and_replays_from_its_seed() {
+        let data: Vec<u8> = (0..24_000u32).map(|i| (i * 7 % 251) as u8).collect();
+        let link = Link { latency: Duration::from_millis(20), loss: 0.2, ..Link::default() };
+        let (received, stats, took) = transfer(7, link, &data);
+        assert_eq!(received, vec![(SocketAddr::from(([10, 0, 0, 1], 4000)), data.clone())]);
+        assert!(stats.dropped > 0, "the link lost nothing: {stats:?}");
+
+        // Same seed, same run — every drop, every retransmit, the same virtual finish time.
+        assert_eq!(transfer(7, link, &data), (received, stats, took));
+    }
+
+    #[test]
+    fn transfer_survives_heavy_reordering() {
+        let data: Vec<u8> = (0..24_000u32).map(|i| (i * 13 % 251) as u8).collect();
+        // Up to 150 ms on top of 10 ms: a blast of DATA sent in the same millisecond lands in any order.
+        let link = Link { latency: Duration::from_millis(10), spread: Duration::from_millis(150), ..Link::default() };
+        let (received, stats, _) = transfer(11, link, &data);
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].1, data);
+        assert!(stats.reordered > 0, "nothing arrived out of order: {stats:?}");
+    }
+}
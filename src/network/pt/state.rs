@@ -3,8 +3,10 @@
 //! Manages the lifecycle of a single transfer (send or receive).
 
 use super::buffer::{ReceiveBuffer, SendBuffer};
+use super::clock;
 use super::packets::*;
 use super::window::{FlightTracker, RTTEstimator, WindowController};
+use crate::crypto::ct;
 use std::net::SocketAddr;
 use std::time::{Duration, Instant};
 
@@ -80,6 +82,8 @@ pub struct OutboundTransfer {
     pub recipient_pubkey: Option<[u8; 32]>,
     /// Original payload for relay fallback (the full VSF before sharding)
     pub original_payload: Option<Vec<u8>>,
+    /// The receiver paused this transfer to ask its user (auto-download policy): SPEC keepalives only until its SPEC ACK or Abort.
+    pub held: bool,
 }
 
 impl OutboundTransfer {
@@ -105,9 +109,9 @@ impl OutboundTransfer {
             complete_received: false,
             retries: 0,
             retransmits: 0,
-            last_activity: Instant::now(),
-            created_at: Instant::now(),
-            spec_last_sent: Instant::now(),
+            last_activity: clock::now(),
+            created_at: clock::now(),
+            spec_last_sent: clock::now(),
             spec_retry_count: 0,
             spec_next_delay: Duration::from_secs(1),
             spec_tcp_fallback: false,
@@ -115,6 +119,7 @@ impl OutboundTransfer {
             relay_sent: false,
             recipient_pubkey: None,
             original_payload,
+            held: false,
         }
     }
 
@@ -125,18 +130,18 @@ impl OutboundTransfer {
 
     /// Check if SPEC needs retry (exponential backoff)
     pub fn spec_needs_retry(&self) -> bool {
-        !self.spec_acked && self.spec_sent && self.spec_last_sent.elapsed() >= self.spec_next_delay
+        !self.spec_acked && self.spec_sent && clock::since(self.spec_last_sent) >= self.spec_next_delay
     }
 
     /// Mark SPEC as sent and update backoff
     pub fn mark_spec_sent(&mut self) {
         self.spec_sent = true;
-        self.spec_last_sent = Instant::now();
+        self.spec_last_sent = clock::now();
         self.spec_retry_count += 1;
 
         // Exponential backoff: 1s → 2s → 4s → 8s → 16s → 32s (capped), JITTERED to 50–100% so peers that
         // retransmit after the same shared outage don't sync up into a retransmit storm (decorrelated backoff).
-        self.spec_next_delay = crate::jitter_dur(std::cmp::min(
+        self.spec_next_delay = clock::jitter(std::cmp::min(
             Duration::from_secs(1 << self.spec_retry_count.min(5)),
             Duration::from_secs(32),
         ));
@@ -144,7 +149,7 @@ impl OutboundTransfer {
 
     /// Check if TCP should be used in parallel (after 1s) Returns true when transfer is old enough that TCP should be tried alongside UDP
     pub fn tcp_eligible(&self) -> bool {
-        self.created_at.elapsed() >= Duration::from_secs(1)
+        clock::since(self.created_at) >= Duration::from_secs(1)
     }
 
     /// Check if we should fall back to relay (UDP + TCP tried, no ACK). Trigger at SPEC_MAX_RETRIES (~31s with 1/2/4/8/16s jittered backoff), NOT 2× that: the old ~90s / 10-retry threshold was never reached because a re-firing CLUTCH ceremony supersedes the transfer first (field logs topped out at attempt 7), so relay NEVER engaged for the peers that needed it most (asymmetric reachability, no direct path). The relayed copy is redundant if a direct path ACKs in the meantime, so an earlier trigger only costs one best-effort store on fgtw.org.
@@ -229,8 +234,22 @@ impl OutboundTransfer {
         packets
     }
 
-    /// Handle ACK received Note: chunk_hash verification is done in PTManager::handle_ack() during transfer matching
-    pub fn handle_ack(&mut self, ack: &PTAck) -> bool {
+    /// Handle ACK received. Returns the packet to re-send at once when the ACK's chunk hash shows it arrived corrupted.
+    pub fn handle_ack(&mut self, ack: &PTAck) -> Option<PTData> {
+        // The ACK carries BLAKE3 of the payload the receiver stored. Not what we sent: that copy is corrupt, so it goes straight back out instead of surfacing as a failed final hash after the whole transfer. That holds even for a sequence already ACK'd — the receiver only ever ACKs what it holds — so the ACK is taken back and the transfer reopens until the repair lands.
+        let payload = self.send_buffer.get_packet(ack.sequence)?;
+        if !ct::eq(blake3::hash(payload).as_bytes(), ack.chunk_hash) {
+            crate::logf!("PT: chunk hash mismatch on stream '{}' seq {} - retransmitting", self.stream_id as char, ack.sequence);
+            if self.send_buffer.unmark_acked(ack.sequence) && self.state == TransferState::AwaitingComplete {
+                self.state = TransferState::Transferring;
+            }
+            let data = PTData { stream_id: self.stream_id, sequence: ack.sequence, payload: payload.to_vec() };
+            self.flight.sent(ack.sequence);
+            self.retransmits += 1;
+            self.last_activity = clock::now();
+            return Some(data);
+        }
+
         // Update RTT if we were tracking this packet
         if let Some(rtt_sample) = self.flight.acked(ack.sequence) {
             self.rtt.update(rtt_sample);
@@ -239,7 +258,7 @@ impl OutboundTransfer {
         // Mark as ACK'd
         if self.send_buffer.mark_acked(ack.sequence) {
             self.window.on_ack();
-            self.last_activity = Instant::now();
+            self.last_activity = clock::now();
             // `retries` counts CONSECUTIVE no-progress timeout rounds, not lifetime losses — so any real progress refunds the whole stale budget. Without this, a blast into a path whose RTT hovers near the RTO (cellular: every tick finds SOME packet older than the ACK-recomputed RTO) bumps `retries` past the `is_stale` cap in under a second and kills a transfer that is actively ACKing (observed: both sides of a multi-hundred-packet offer exchange self-killed about a second after locking a working path).
             self.retries = 0;
         }
@@ -249,13 +268,13 @@ impl OutboundTransfer {
             self.state = TransferState::AwaitingComplete;
         }
 
-        true
+        None
     }
 
     /// Handle NAK received - queue retransmits
     pub fn handle_nak(&mut self, nak: &PTNak) -> Vec<PTData> {
         self.window.on_loss();
-        self.last_activity = Instant::now();
+        self.last_activity = clock::now();
 
         let mut packets = Vec::new();
         for &seq in &nak.missing_sequences {
@@ -274,9 +293,9 @@ impl OutboundTransfer {
 
     /// Handle COMPLETE received
     pub fn handle_complete(&mut self, complete: &PTComplete) -> bool {
-        self.last_activity = Instant::now();
+        self.last_activity = clock::now();
 
-        if complete.success && complete.final_hash == self.send_buffer.data_hash() {
+        if complete.success && ct::eq(complete.final_hash, self.send_buffer.data_hash()) {
             self.state = TransferState::Complete;
             self.complete_received = true;
             true
@@ -289,7 +308,7 @@ impl OutboundTransfer {
 
     /// Get transfer statistics Returns: (total_packets, bytes, retransmits, duration_ms, send_ratio_x100, rtt_ms, packet_size)
     pub fn stats(&self) -> (u32, u32, u32, u64, u32, u64, u16) {
-        let duration_ms = self.created_at.elapsed().as_millis() as u64;
+        let duration_ms = clock::since(self.created_at).as_millis() as u64;
         let rtt_ms = self.rtt.srtt().as_millis() as u64;
         // Report send_ratio * 100 as integer (e.g., 2.0 -> 200, 1.5 -> 150)
         let send_ratio_x100 = (self.window.send_ratio() * 100.0) as u32;
@@ -330,7 +349,7 @@ impl OutboundTransfer {
 
     /// Check if transfer has totally timed out
     pub fn is_stale(&self, timeout: Duration) -> bool {
-        self.last_activity.elapsed() > timeout || self.retries > 10
+        clock::since(self.last_activity) > timeout || self.retries > 10
     }
 }
 
@@ -384,25 +403,25 @@ impl OutboundPacket {
     /// Record the initial transmission of this packet (becomes the in-flight head). The first retransmit then waits `next_delay` = 1s; each retransmit doubles it via `mark_retransmit`.
     pub fn mark_sent(&mut self) {
         self.in_flight = true;
-        self.last_sent = Some(Instant::now());
+        self.last_sent = Some(clock::now());
     }
 
     /// Record a retransmit and back off toward the 60s cap (2 → 4 → … → 60s), JITTERED to 50–100%.
     /// The exponential is recomputed from `retry_count` (not the previous jittered delay) so the randomness never compounds; jitter decorrelates peers retransmitting after a shared outage.
     pub fn mark_retransmit(&mut self) {
-        self.last_sent = Some(Instant::now());
+        self.last_sent = Some(clock::now());
         self.retry_count += 1;
         let base = std::cmp::min(
             Duration::from_secs(1 << self.retry_count.min(6)),
             Self::MAX_BACKOFF,
         );
-        self.next_delay = crate::jitter_dur(base);
+        self.next_delay = clock::jitter(base);
     }
 
     /// True when the in-flight head's backoff has elapsed and it should be retransmitted.
     pub fn needs_retransmit(&self) -> bool {
         match self.last_sent {
-            Some(t) => self.in_flight && t.elapsed() >= self.next_delay,
+            Some(t) => self.in_flight && clock::since(t) >= self.next_delay,
             None => false,
         }
     }
@@ -411,6 +430,7 @@ impl OutboundPacket {
 /// Inbound transfer (we're receiving)
 pub struct InboundTransfer {
     pub peer_addr: SocketAddr,
+    pub sender: [u8; 32], // Device pubkey that signed the SPEC
     pub stream_id: u8, // 'a'-'z' for concurrent transfer routing
     pub state: TransferState,
     pub receive_buffer: ReceiveBuffer,
@@ -421,9 +441,10 @@ pub struct InboundTransfer {
 
 impl InboundTransfer {
     /// Create from received SPEC
-    pub fn new(peer_addr: SocketAddr, spec: &PTSpec) -> Self {
+    pub fn new(peer_addr: SocketAddr, sender: [u8; 32], spec: &PTSpec) -> Self {
         Self {
             peer_addr,
+            sender,
             stream_id: spec.stream_id,
             state: TransferState::Transferring,
             receive_buffer: ReceiveBuffer::new(
@@ -433,28 +454,33 @@ impl InboundTransfer {
                 spec.data_hash,
             ),
             duplicates: 0,
-            last_activity: Instant::now(),
-            created_at: Instant::now(),
+            last_activity: clock::now(),
+            created_at: clock::now(),
         }
     }
 
     /// Handle DATA packet received, returns ACK to send
     pub fn handle_data(&mut self, data: &PTData) -> Option<PTAck> {
-        self.last_activity = Instant::now();
+        // A sequence outside the SPEC or a payload of the wrong length: never stored, never ACK'd, and it doesn't keep the transfer alive.
+        if !self.receive_buffer.accepts(data.sequence, data.payload.len()) {
+            return None;
+        }
+        self.last_activity = clock::now();
 
         if self.receive_buffer.insert(data.sequence, &data.payload) {
             // New packet - send ACK with stream_id for routing
             Some(PTAck::new(self.stream_id, data.sequence, &data.payload))
         } else {
-            // Duplicate - track and still ACK to prevent sender retransmit
+            // Duplicate - track and still ACK to prevent sender retransmit. The ACK names what we hold: a differing copy the buffer didn't take gets none, and the sender's timer re-sends it until the buffer will (see `ReceiveBuffer::insert`).
             self.duplicates += 1;
-            Some(PTAck::new(self.stream_id, data.sequence, &data.payload))
+            let stored = self.receive_buffer.stored(data.sequence)?;
+            (stored == &data.payload[..]).then(|| PTAck::new(self.stream_id, data.sequence, stored))
         }
     }
 
-    /// Check if transfer is complete
+    /// Check if transfer is complete: every packet in, and the data matches the SPEC's hash. All in but corrupt stays open for the sender's repair (see [`OutboundTransfer::handle_ack`]) until it lands or the transfer goes stale.
     pub fn is_complete(&self) -> bool {
-        self.receive_buffer.is_complete()
+        self.receive_buffer.is_intact()
     }
 
     /// Verify and build COMPLETE packet
@@ -486,7 +512,7 @@ impl InboundTransfer {
 
     /// Check if transfer has stalled
     pub fn is_stale(&self, timeout: Duration) -> bool {
-        self.last_activity.elapsed() > timeout
+        clock::since(self.last_activity) > timeout
     }
 
     /// Get progress
@@ -496,7 +522,7 @@ impl InboundTransfer {
 
     /// Get transfer statistics Returns: (total_packets, total_bytes, duplicates, duration_ms)
     pub fn stats(&self) -> (u32, u32, u32, u64) {
-        let duration_ms = self.created_at.elapsed().as_millis() as u64;
+        let duration_ms = clock::since(self.created_at).as_millis() as u64;
         (
             self.receive_buffer.total_packets(),
             self.receive_buffer.total_size(),
@@ -529,6 +555,25 @@ mod tests {
         assert_eq!(spec.total_size, 3072);
     }
 
+    #[test]
+    fn test_outbound_transfer_reopens_on_a_late_mismatched_ack() {
+        let data: Vec<u8> = (0..3072u32).map(|i| i as u8).collect();
+        let mut transfer = OutboundTransfer::new("127.0.0.1:12345".parse().unwrap(), data.clone(), b'a', 0);
+        for (sequence, range) in [(0, 0..1024), (1, 1024..2048), (2, 2048..3072)] {
+            assert!(transfer.handle_ack(&PTAck::new(b'a', sequence, &data[range])).is_none());
+        }
+        assert_eq!(transfer.state, TransferState::AwaitingComplete);
+
+        // The receiver says it holds other bytes for a sequence we counted as delivered: the ACK is taken back and the packet goes out again.
+        let resend = transfer.handle_ack(&PTAck::new(b'a', 1, &[0u8; 1024])).expect("resend");
+        assert_eq!((resend.sequence, &resend.payload[..]), (1, &data[1024..2048]));
+        assert!(!transfer.send_buffer.is_acked(1));
+        assert_eq!(transfer.state, TransferState::Transferring);
+
+        assert!(transfer.handle_ack(&PTAck::new(b'a', 1, &data[1024..2048])).is_none());
+        assert_eq!(transfer.state, TransferState::AwaitingComplete);
+    }
+
     #[test]
     fn test_inbound_transfer_basic() {
         let data = vec![0xCD; 2560]; // 3 packets (1024+1024+512)
@@ -543,7 +588,7 @@ mod tests {
             data_hash: hash,
         };
 
-        let mut transfer = InboundTransfer::new(peer, &spec);
+        let mut transfer = InboundTransfer::new(peer, [1; 32], &spec);
 
         assert_eq!(transfer.state, TransferState::Transferring);
         assert_eq!(transfer.stream_id, b'b');
@@ -580,4 +625,33 @@ mod tests {
         assert!(complete.success);
         assert_eq!(complete.final_hash, hash);
     }
+
+    #[test]
+    fn test_inbound_transfer_refuses_misfit_data() {
+        let data = vec![0xEF; 2560];
+        let spec = PTSpec {
+            stream_id: b'c',
+            total_packets: 3,
+            packet_size: 1024,
+            total_size: 2560,
+            data_hash: *blake3::hash(&data).as_bytes(),
+        };
+        let mut transfer = InboundTransfer::new("127.0.0.1:12345".parse().unwrap(), [1; 32], &spec);
+
+        // A sequence past the SPEC's total_packets, and one far past it.
+        for sequence in [3, u32::MAX] {
+            let ack = transfer.handle_data(&PTData { stream_id: b'c', sequence, payload: data[..1024].to_vec() });
+            assert!(ack.is_none(), "sequence {sequence} ACK'd");
+        }
+        // A truncated middle packet, and a last packet padded to a full slot.
+        assert!(transfer.handle_data(&PTData { stream_id: b'c', sequence: 1, payload: data[..1000].to_vec() }).is_none());
+        assert!(transfer.handle_data(&PTData { stream_id: b'c', sequence: 2, payload: data[..1024].to_vec() }).is_none());
+        assert_eq!((transfer.progress(), transfer.duplicates), ((0, 3), 0));
+
+        // The real packets still land.
+        for (sequence, range) in [(0, 0..1024), (1, 1024..2048), (2, 2048..2560)] {
+            assert!(transfer.handle_data(&PTData { stream_id: b'c', sequence, payload: data[range].to_vec() }).is_some());
+        }
+        assert!(transfer.is_complete());
+    }
 }
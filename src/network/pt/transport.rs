@@ -0,0 +1,109 @@
+//! What PT rides on. [`PTManager`](super::PTManager) only ever produces and consumes bytes; a [`Transport`] is the carrier those bytes go over, and [`PTManager::drive`](super::PTManager::drive) is the loop that moves them: drain what arrived, answer it, send what the retry timers say is due.
+//!
+//! [`UdpTransport`] is the real thing — one UDP socket, with a TCP connect for the whole-payload fallback. [`Loopback`] is an in-memory pair, for tests and for anything that wants PT's reliability over a carrier of its own. Photon's own async receive loop (`network::status`) doesn't run on either: it hands each arrival to [`PTManager::handle_datagram`](super::PTManager::handle_datagram) — the same dispatch `drive` uses — and sends the replies itself, awaited on its tokio socket. `drive` is synchronous and may block (see [`Transport::send_stream`]); keep it off tokio workers.
+
+use std::collections::VecDeque;
+use std::io::Write;
+use std::net::{SocketAddr, TcpStream, UdpSocket};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// A datagram carrier for PT.
+pub trait Transport {
+    /// Send one datagram to `to`. Best effort — PT's ACKs and retries supply the reliability.
+    fn send(&mut self, to: SocketAddr, bytes: &[u8]);
+
+    /// The next datagram waiting, with its source. Never blocks: `None` when nothing has arrived.
+    fn recv(&mut self) -> Option<(SocketAddr, Vec<u8>)>;
+
+    /// Push a whole payload down an ordered stream to `to` — PT's fallback once a SPEC goes unanswered over datagrams. `false` when the carrier has none (the default); PT then keeps to datagram retries.
+    ///
+    /// May block: [`UdpTransport`]'s connects and writes synchronously, up to [`UdpTransport::TCP_CONNECT_TIMEOUT`] on the connect alone. Never call it — or [`PTManager::drive`](super::PTManager::drive), which does — on a tokio worker thread; run that loop on its own thread or under `spawn_blocking`.
+    fn send_stream(&mut self, _to: SocketAddr, _bytes: &[u8]) -> bool {
+        false
+    }
+}
+
+/// PT over a UDP socket, with TCP for the stream fallback.
+pub struct UdpTransport {
+    socket: UdpSocket,
+    buf: Vec<u8>,
+}
+
+impl UdpTransport {
+    /// How long the TCP fallback waits to connect before giving up on that attempt (the SPEC retries carry on regardless).
+    pub const TCP_CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+    /// Wrap a bound socket. It's switched to non-blocking so [`Transport::recv`] can poll.
+    pub fn new(socket: UdpSocket) -> std::io::Result<Self> {
+        socket.set_nonblocking(true)?;
+        Ok(Self { socket, buf: vec![0u8; 65536] })
+    }
+
+    pub fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+}
+
+impl Transport for UdpTransport {
+    fn send(&mut self, to: SocketAddr, bytes: &[u8]) {
+        if let Err(e) = self.socket.send_to(bytes, to) {
+            crate::logf_at!(crate::LogLevel::Debug, "PT: UDP send to {} failed: {}", to, e);
+        }
+    }
+
+    fn recv(&mut self) -> Option<(SocketAddr, Vec<u8>)> {
+        match self.socket.recv_from(&mut self.buf) {
+            Ok((n, src)) => Some((src, self.buf[..n].to_vec())),
+            Err(_) => None,
+        }
+    }
+
+    /// Blocking — see [`Transport::send_stream`].
+    fn send_stream(&mut self, to: SocketAddr, bytes: &[u8]) -> bool {
+        // The VSF `l` field self-frames the payload — no length prefix, same as photon's TCP listener expects.
+        match TcpStream::connect_timeout(&to, Self::TCP_CONNECT_TIMEOUT).and_then(|mut s| s.write_all(bytes)) {
+            Ok(()) => true,
+            Err(e) => {
+                crate::logf!("PT: TCP fallback to {} failed: {}", to, e);
+                false
+            }
+        }
+    }
+}
+
+/// One end of an in-memory link: what one end sends, the other receives, tagged with the sender's address. No loss, no reordering, no stream fallback.
+pub struct Loopback {
+    addr: SocketAddr,
+    inbox: Arc<Mutex<VecDeque<(SocketAddr, Vec<u8>)>>>,
+    /// The far end's address and inbox.
+    peer: (SocketAddr, Arc<Mutex<VecDeque<(SocketAddr, Vec<u8>)>>>),
+}
+
+impl Loopback {
+    /// Two connected ends, at addresses `a` and `b`.
+    pub fn pair(a: SocketAddr, b: SocketAddr) -> (Self, Self) {
+        let (inbox_a, inbox_b) = (Arc::new(Mutex::new(VecDeque::new())), Arc::new(Mutex::new(VecDeque::new())));
+        (
+            Self { addr: a, inbox: inbox_a.clone(), peer: (b, inbox_b.clone()) },
+            Self { addr: b, inbox: inbox_b, peer: (a, inbox_a) },
+        )
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl Transport for Loopback {
+    fn send(&mut self, to: SocketAddr, bytes: &[u8]) {
+        // Anything not addressed to the far end falls on the floor, like a datagram to nowhere.
+        if to == self.peer.0 {
+            self.peer.1.lock().unwrap().push_back((self.addr, bytes.to_vec()));
+        }
+    }
+
+    fn recv(&mut self) -> Option<(SocketAddr, Vec<u8>)> {
+        self.inbox.lock().unwrap().pop_front()
+    }
+}
@@ -8,6 +8,7 @@
 //!
 //! This is NOT TCP - we intentionally overshoot to saturate the link, then clean up gaps in sweep cycles after all data is sent.
 
+use super::clock;
 use std::time::{Duration, Instant};
 
 /// RTT Estimator using TCP-style exponential moving average
@@ -235,14 +236,14 @@ impl FlightTracker {
 
     /// Record packet sent
     pub fn sent(&mut self, sequence: u32) {
-        self.in_flight.push((sequence, Instant::now()));
+        self.in_flight.push((sequence, clock::now()));
     }
 
     /// Record ACK received, returns RTT sample if found
     pub fn acked(&mut self, sequence: u32) -> Option<Duration> {
         if let Some(pos) = self.in_flight.iter().position(|(s, _)| *s == sequence) {
             let (_, send_time) = self.in_flight.remove(pos);
-            Some(send_time.elapsed())
+            Some(clock::since(send_time))
         } else {
             None
         }
@@ -250,7 +251,7 @@ impl FlightTracker {
 
     /// Get sequences that have timed out
     pub fn timed_out(&mut self, timeout: Duration) -> Vec<u32> {
-        let now = Instant::now();
+        let now = clock::now();
         let mut timed_out = Vec::new();
 
         self.in_flight.retain(|(seq, send_time)| {
@@ -0,0 +1,41 @@
+//! Verification-QR scanning — the camera side of the safety-number check (Android only).
+//!
+//! The Verify page shows our [`crate::types::ContactCard`] as a QR; a friend's phone scans it. Kotlin runs the system code scanner (no camera permission — the Play-services scanner owns the camera UI) and hands the code's text down; the tick drains them, decodes the card, and either marks the matching contact verified or adds the identity as a new, already-verified contact. Scanning in person IS the out-of-band channel, so a match needs no second confirmation.
+
+use std::sync::Mutex;
+
+/// Text of the last scanned code (UTF-8 bytes, decoded by [`crate::types::ContactCard::from_text`]), awaiting the tick drain. One deep — a second scan before the drain overwrites.
+static SCANNED: Mutex<Option<Vec<u8>>> = Mutex::new(None);
+
+/// Kotlin scanner result → here (via JNI). The tick drains with [`take_scanned`].
+pub fn on_scanned(bytes: Vec<u8>) {
+    *SCANNED.lock().unwrap() = Some(bytes);
+}
+
+/// Drain the last scanned payload (UI tick).
+pub fn take_scanned() -> Option<Vec<u8>> {
+    SCANNED.lock().unwrap().take()
+}
+
+/// Open the platform scanner. The result arrives asynchronously thru [`on_scanned`]; a cancelled scan simply never arrives.
+pub fn start_scan() {
+    imp::start_scan();
+}
+
+/// Whether this platform can scan at all — the Verify page offers the Scan pill only where it does something.
+pub fn can_scan() -> bool {
+    cfg!(target_os = "android")
+}
+
+#[cfg(target_os = "android")]
+mod imp {
+    pub(super) fn start_scan() {
+        crate::platform::jni_android::qr_call("startScan");
+    }
+}
+
+/// Every other platform displays the QR but has no camera path wired — desktop generates, mobile scans.
+#[cfg(not(target_os = "android"))]
+mod imp {
+    pub(super) fn start_scan() {}
+}
@@ -0,0 +1,146 @@
+//! Link quality: a smoothed round-trip time and the signal-strength-style bucket the UI draws from it. Fed by ping→pong round trips per contact (`StatusUpdate::Online::rtt`) and by the FGTW `/status` poll (`HandleQuery::fgtw_quality`).
+
+use std::time::Duration;
+
+/// Below this smoothed RTT a link is Good — a chat message lands before the sender's eye leaves the send button.
+const GOOD_MS: f32 = 150.;
+/// Below this it's Fair; above, Poor (a relay detour or a congested uplink — typing indicators and acks start to feel late).
+const FAIR_MS: f32 = 400.;
+/// Hysteresis: leaving a bucket takes crossing its edge by this fraction, so an RTT sitting right on a threshold doesn't flicker the indicator every ping.
+const HYSTERESIS: f32 = 1. / 8.;
+/// EWMA gain for each new sample — TCP's SRTT weight (RFC 6298's α = 1/8), slow enough that one delayed pong doesn't repaint the bars.
+const GAIN: f32 = 1. / 8.;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinkQuality {
+    Good,
+    Fair,
+    Poor,
+}
+
+impl LinkQuality {
+    /// The bucket for `ms` with no history.
+    fn classify(ms: f32) -> Self {
+        if ms < GOOD_MS {
+            LinkQuality::Good
+        } else if ms < FAIR_MS {
+            LinkQuality::Fair
+        } else {
+            LinkQuality::Poor
+        }
+    }
+
+    /// Bars lit in a three-bar indicator.
+    pub fn bars(self) -> u8 {
+        match self {
+            LinkQuality::Good => 3,
+            LinkQuality::Fair => 2,
+            LinkQuality::Poor => 1,
+        }
+    }
+}
+
+/// Rolling RTT average + current bucket for one link. Runtime only — a fresh session measures afresh.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RttEstimate {
+    srtt_ms: Option<f32>,
+    quality: Option<LinkQuality>,
+}
+
+impl RttEstimate {
+    /// Fold in one measured round trip.
+    pub fn record(&mut self, rtt: Duration) {
+        let sample = rtt.as_secs_f32() * 1000.;
+        let srtt = match self.srtt_ms {
+            Some(s) => s + GAIN * (sample - s),
+            None => sample,
+        };
+        self.srtt_ms = Some(srtt);
+        self.quality = Some(match self.quality {
+            None => LinkQuality::classify(srtt),
+            Some(q) => Self::step(q, srtt),
+        });
+    }
+
+    /// Move from bucket `q` only once `srtt` is clearly past its edges.
+    fn step(q: LinkQuality, srtt: f32) -> LinkQuality {
+        let (lo, hi) = match q {
+            LinkQuality::Good => (0., GOOD_MS),
+            LinkQuality::Fair => (GOOD_MS, FAIR_MS),
+            LinkQuality::Poor => (FAIR_MS, f32::INFINITY),
+        };
+        if srtt < lo * (1. - HYSTERESIS) || srtt > hi * (1. + HYSTERESIS) {
+            LinkQuality::classify(srtt)
+        } else {
+            q
+        }
+    }
+
+    /// Smoothed RTT, `None` before the first sample.
+    pub fn srtt(&self) -> Option<Duration> {
+        self.srtt_ms.map(|ms| Duration::from_micros((ms * 1000.).round() as u64))
+    }
+
+    pub fn quality(&self) -> Option<LinkQuality> {
+        self.quality
+    }
+
+    /// Forget the history — the link went down, and whatever comes back (maybe a relay path instead of direct) is measured from scratch.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn buckets_follow_the_smoothed_rtt_with_hysteresis() {
+        let mut e = RttEstimate::default();
+        assert_eq!(e.quality(), None);
+
+        // First pong sets the estimate outright.
+        e.record(ms(40));
+        assert_eq!(e.quality(), Some(LinkQuality::Good));
+        assert_eq!(e.srtt(), Some(ms(40)));
+
+        // One slow pong nudges the average, it doesn't repaint the bars.
+        e.record(ms(900));
+        assert_eq!(e.quality(), Some(LinkQuality::Good));
+
+        // A sustained relay detour walks it down thru Fair to Poor.
+        let mut seen = vec![e.quality().unwrap()];
+        for _ in 0..40 {
+            e.record(ms(600));
+            if seen.last() != e.quality().as_ref() {
+                seen.push(e.quality().unwrap());
+            }
+        }
+        assert_eq!(seen, [LinkQuality::Good, LinkQuality::Fair, LinkQuality::Poor]);
+
+        // Hovering just under the Fair/Poor edge stays Poor (within hysteresis) ...
+        let mut edge = RttEstimate::default();
+        edge.record(ms(420));
+        for _ in 0..40 {
+            edge.record(ms(380));
+        }
+        assert_eq!(edge.quality(), Some(LinkQuality::Poor));
+        // ... and clearly under it drops back to Fair, then Good on a direct path.
+        for _ in 0..40 {
+            edge.record(ms(300));
+        }
+        assert_eq!(edge.quality(), Some(LinkQuality::Fair));
+        for _ in 0..60 {
+            edge.record(ms(30));
+        }
+        assert_eq!(edge.quality(), Some(LinkQuality::Good));
+
+        edge.reset();
+        assert_eq!((edge.srtt(), edge.quality()), (None, None));
+    }
+}
@@ -0,0 +1,159 @@
+//! Anti-replay for signed presence pings and CLUTCH offers. A signature proves who built a message, not that it's fresh — a captured ping re-sent an hour later verifies just as well, and answering it tells whoever holds it that we're online and where. [`ReplayGuard`] remembers, per sender device, what it has already let thru and refuses the same thing twice or anything too old to judge.
+//!
+//! Pings carry a fresh signed timestamp each cycle, so a ping is one-shot: its provenance (which binds the timestamp) is the nonce, and one seen before on the same path is a replay. A relay-carried copy of a direct ping is the sender's doing, not a replay — each path gets its own answer. Offers are the opposite: every re-send is byte-identical by design (pinned send-time, see `build_clutch_offer_vsf`), so they're never nonce-deduped; instead the signed send-time must be recent and must not go backwards — an offer from a round older than the newest one we've seen from that device is a replay of a dead round.
+//!
+//! Memory is bounded: at most [`SEEN_PER_PEER`] nonces per device (evicting one raises that device's floor to its timestamp, so an evicted ping stays refused) and [`MAX_PEERS`] devices (least recently heard dropped first). Runtime only — a restart forgets, and the age window covers what it forgot.
+
+use crate::OSC_PER_SEC;
+
+/// How far a ping's signed timestamp may sit from our clock, either way. Wide enough for a relay hop and ordinary clock drift; a ping older than this is no use as presence anyway.
+pub const PING_WINDOW_OSC: i64 = 120 * OSC_PER_SEC;
+/// Oldest an offer's pinned send-time may be. A round re-keys after 5 min and the relay holds a frame ~4 min more, so 10 min covers the last legitimate re-send (the app's zombie-round bound).
+pub const OFFER_MAX_AGE_OSC: i64 = 600 * OSC_PER_SEC;
+/// Nonces kept per device — a ping cycle's worth of paths, many times over.
+pub const SEEN_PER_PEER: usize = 32;
+/// Devices tracked. Every caller checks the sender against the contacts set first (`admit_offer`, the ping handler), so only contacts reach the guard and this is a ceiling, not a working size.
+pub const MAX_PEERS: usize = 1024;
+
+struct Peer {
+    device: [u8; 32],
+    /// Nothing at or below this timestamp is accepted: raised as nonces are evicted.
+    floor: i64,
+    /// (timestamp, nonce, arrived via relay) for pings accepted and not yet evicted.
+    seen: Vec<(i64, [u8; 32], bool)>,
+    /// Newest offer send-time accepted.
+    round: Option<i64>,
+    /// Our clock when this device last passed a check — the eviction order.
+    heard: i64,
+}
+
+/// Per-device anti-replay state, shared by every receive path in the status task.
+#[derive(Default)]
+pub struct ReplayGuard {
+    peers: Vec<Peer>,
+}
+
+impl ReplayGuard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Admit a signed ping from `device` stamped `timestamp`, identified by `nonce` (its provenance), seen at `now`. `Err` names why it's refused; nothing is recorded then.
+    pub fn check_ping(
+        &mut self,
+        device: &[u8; 32],
+        timestamp: i64,
+        nonce: &[u8; 32],
+        via_relay: bool,
+        now: i64,
+    ) -> Result<(), &'static str> {
+        if now.saturating_sub(timestamp) > PING_WINDOW_OSC {
+            return Err("stale timestamp");
+        }
+        if timestamp.saturating_sub(now) > PING_WINDOW_OSC {
+            return Err("timestamp in the future");
+        }
+        let peer = self.peer(device, now);
+        if timestamp <= peer.floor {
+            return Err("older than replay floor");
+        }
+        if peer.seen.iter().any(|(_, n, r)| *r == via_relay && crate::crypto::ct::eq(n, nonce)) {
+            return Err("replayed");
+        }
+        peer.seen.push((timestamp, *nonce, via_relay));
+        if peer.seen.len() > SEEN_PER_PEER {
+            let oldest = (0..peer.seen.len()).min_by_key(|&i| peer.seen[i].0).unwrap_or(0);
+            let (ts, _, _) = peer.seen.swap_remove(oldest);
+            peer.floor = peer.floor.max(ts);
+        }
+        peer.heard = now;
+        Ok(())
+    }
+
+    /// Admit a CLUTCH offer from `device` whose signed pinned send-time is `send_time`. A re-send of the newest round passes; an older round, or one older than [`OFFER_MAX_AGE_OSC`], doesn't.
+    pub fn check_offer(&mut self, device: &[u8; 32], send_time: i64, now: i64) -> Result<(), &'static str> {
+        if now.saturating_sub(send_time) > OFFER_MAX_AGE_OSC {
+            return Err("stale send-time");
+        }
+        if send_time.saturating_sub(now) > PING_WINDOW_OSC {
+            return Err("send-time in the future");
+        }
+        let peer = self.peer(device, now);
+        if peer.round.is_some_and(|r| send_time < r) {
+            return Err("superseded round");
+        }
+        peer.round = Some(send_time);
+        peer.heard = now;
+        Ok(())
+    }
+
+    /// This device's entry, made (evicting the least recently heard past [`MAX_PEERS`]) if new.
+    fn peer(&mut self, device: &[u8; 32], now: i64) -> &mut Peer {
+        let i = match self.peers.iter().position(|p| p.device == *device) {
+            Some(i) => i,
+            None => {
+                if self.peers.len() >= MAX_PEERS {
+                    let lru = (0..self.peers.len()).min_by_key(|&i| self.peers[i].heard).unwrap_or(0);
+                    self.peers.swap_remove(lru);
+                }
+                self.peers.push(Peer { device: *device, floor: i64::MIN, seen: Vec::new(), round: None, heard: now });
+                self.peers.len() - 1
+            }
+        };
+        &mut self.peers[i]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_000_000 * OSC_PER_SEC;
+
+    #[test]
+    fn ping_nonce_cache_is_bounded_and_keeps_refusing() {
+        let mut guard = ReplayGuard::new();
+        let dev = [7; 32];
+        let nonce = |i: u8| [i; 32];
+        // One per path: the relay copy of a direct ping is answered too.
+        assert_eq!(guard.check_ping(&dev, NOW, &nonce(0), false, NOW), Ok(()));
+        assert_eq!(guard.check_ping(&dev, NOW, &nonce(0), true, NOW), Ok(()));
+        assert_eq!(guard.check_ping(&dev, NOW, &nonce(0), false, NOW), Err("replayed"));
+
+        for i in 1..=SEEN_PER_PEER as u8 {
+            assert_eq!(guard.check_ping(&dev, NOW + i as i64, &nonce(i), false, NOW), Ok(()));
+        }
+        assert_eq!(guard.peers[0].seen.len(), SEEN_PER_PEER);
+        // Evicted from the cache, still refused: the floor rose past it.
+        assert_eq!(guard.check_ping(&dev, NOW, &nonce(0), false, NOW), Err("older than replay floor"));
+
+        assert_eq!(guard.check_ping(&dev, NOW - PING_WINDOW_OSC - 1, &nonce(99), false, NOW), Err("stale timestamp"));
+        assert_eq!(guard.check_ping(&dev, NOW + PING_WINDOW_OSC + 1, &nonce(99), false, NOW), Err("timestamp in the future"));
+    }
+
+    #[test]
+    fn offer_resends_pass_but_old_rounds_do_not() {
+        let mut guard = ReplayGuard::new();
+        let dev = [9; 32];
+        let round = NOW - 60 * OSC_PER_SEC;
+        assert_eq!(guard.check_offer(&dev, round, NOW), Ok(()));
+        assert_eq!(guard.check_offer(&dev, round, NOW + OSC_PER_SEC), Ok(()));
+        let next = NOW + 300 * OSC_PER_SEC;
+        assert_eq!(guard.check_offer(&dev, next, next), Ok(()));
+        assert_eq!(guard.check_offer(&dev, round, next), Err("superseded round"));
+        assert_eq!(guard.check_offer(&[1; 32], NOW - OFFER_MAX_AGE_OSC - 1, NOW), Err("stale send-time"));
+    }
+
+    #[test]
+    fn peer_table_is_bounded() {
+        let mut guard = ReplayGuard::new();
+        for i in 0..=MAX_PEERS {
+            let dev = blake3::hash(&i.to_le_bytes()).into();
+            assert_eq!(guard.check_ping(&dev, NOW + i as i64, &[0; 32], false, NOW + i as i64), Ok(()));
+        }
+        assert_eq!(guard.peers.len(), MAX_PEERS);
+        // The first device, least recently heard, made room.
+        let first: [u8; 32] = blake3::hash(&0usize.to_le_bytes()).into();
+        assert!(!guard.peers.iter().any(|p| p.device == first));
+    }
+}
@@ -11,12 +11,12 @@ use super::udp;
 use crate::network::fgtw::protocol::SyncRecord;
 use crate::network::fgtw::FgtwMessage;
 use crate::network::fgtw::Keypair;
-use crate::network::pt::{
-    is_pt_data, PTAck, PTComplete, PTControl, PTData, PTManager, PTNak, PTSpec,
-};
+use crate::network::pt::{is_pt_data, parse_pt_packet, PTManager, ParsedPtPacket};
+use crate::network::replay::ReplayGuard;
 use crate::types::DevicePubkey;
 use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
 use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
@@ -76,6 +76,18 @@ fn avatar_pin() -> Option<[u8; 64]> {
     AVATAR_PIN.lock().ok().and_then(|p| if *p == [0u8; 64] { None } else { Some(*p) })
 }
 
+/// Whether we're away (input idle past `settings.vsf` `away_after_min`), as sent in pongs. Written by the UI thread when its `AwayClock` flips; read by the status thread per pong.
+static AWAY: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Publish our away presence for outgoing pongs.
+pub fn set_away(away: bool) {
+    AWAY.store(away, std::sync::atomic::Ordering::Relaxed);
+}
+
+fn away() -> bool {
+    AWAY.load(std::sync::atomic::Ordering::Relaxed)
+}
+
 /// Request to ping a contact
 #[derive(Clone)]
 pub struct PingRequest {
@@ -218,6 +230,26 @@ pub struct ClearPtSendsRequest {
     pub peer_addr: SocketAddr,
 }
 
+/// Auto-download policy for inbound PT transfers: the size limit, and the user's answer for a transfer it held (`StatusUpdate::TransferHeld`).
+#[derive(Clone, Debug)]
+pub enum TransferPolicyRequest {
+    /// Transfers over this many bytes wait for the user; `None` accepts everything.
+    Limit(Option<u32>),
+    /// Transfers over this many bytes are refused outright (`PTManager::set_max_transfer_size`).
+    MaxSize(u32),
+    Accept { peer_addr: SocketAddr, stream_id: u8 },
+    Decline { peer_addr: SocketAddr, stream_id: u8 },
+}
+
+/// An embedding application's datagram for [`StatusChecker::send_app_datagram`]: the run loop signs and queues it on PT's reliable small-packet path.
+#[derive(Clone, Debug)]
+pub struct AppDatagramRequest {
+    pub peer_addr: SocketAddr,
+    pub recipient_pubkey: [u8; 32],
+    pub payload: Vec<u8>,
+    pub id: crate::network::pt::AppDatagramId,
+}
+
 // Use global PHOTON_PORT for all network communication
 use crate::PHOTON_PORT;
 
@@ -235,6 +267,10 @@ pub enum StatusUpdate {
         display_name: Option<String>,
         /// The peer's avatar pin from the pong (friend-gated key ‖ lookup). None on pings/timeouts/legacy pongs.
         avatar_pin: Option<[u8; 64]>,
+        /// Away presence: `Some(away)` off a pong (the flag it carries; a legacy pong reads present), `Some(false)` off a chat (they just typed), `None` off a ping or timeout — an automatic ping says nothing about the person, so the receiver keeps its last verdict.
+        away: Option<bool>,
+        /// Ping→pong round trip, measured against the matched pending ping's `sent_at`. Only a verified pong carries one — an inbound ping or chat proves liveness but times nothing. Feeds the contact's `RttEstimate`.
+        rtt: Option<std::time::Duration>,
     },
     // NOTE: ClutchOffer, ClutchInit, ClutchResponse, ClutchComplete REMOVED Full 8-primitive CLUTCH uses ClutchOfferReceived and ClutchKemResponseReceived See docs/clutch.md Section 4.2 for the slot-based ceremony protocol.
     /// Encrypted chat message received (CHAIN format)
@@ -317,6 +353,14 @@ pub enum StatusUpdate {
     },
     /// PT outbound transfer completed successfully
     PTSendComplete { peer_addr: SocketAddr },
+    /// An app datagram from a contact (signature verified by PT) — see [`PTManager::send_app_datagram`].
+    AppDatagramReceived {
+        sender_pubkey: [u8; 32],
+        payload: Vec<u8>,
+        sender_addr: SocketAddr,
+    },
+    /// The peer acked one of our app datagrams; `id` is what `send_app_datagram` returned.
+    AppDatagramDelivered { id: crate::network::pt::AppDatagramId },
     /// Full CLUTCH offer received (~548KB with all 8 pubkeys) Payload is already verified and parsed from VSF format.
     ClutchOfferReceived {
         conversation_token: [u8; 32], // Privacy-preserving smear_hash of sorted participant seeds
@@ -341,14 +385,22 @@ pub enum StatusUpdate {
         payload: crate::crypto::clutch::ClutchCompletePayload,
         sender_addr: SocketAddr,
     },
-    /// LAN peer discovered via broadcast (NAT hairpinning workaround)
+    /// LAN peer discovered via multicast/broadcast (NAT hairpinning workaround). `id` is blinded — the app opens it against each contact's handle_proof + fleet (see [`crate::network::lan`]).
     LanPeerDiscovered {
-        handle_proof: [u8; 32],
+        id: crate::network::lan::BeaconId,
         local_ip: Ipv4Addr,
         port: u16,
     },
     /// Our own reflexive (public) address, learned+adopted from peer-echoed reflection (pong `observed_addr` or a `ReflectResponse`). The app stores it as `PhotonApp.our_reflexive`, feeding candidate gathering and the FGTW announce (so our published address is the one seen on the live UDP data socket, not fgtw.org's cone-only TLS view).
     ReflexiveLearned { addr: SocketAddr },
+    /// A NAT-type probe ([`StatusChecker::probe_nat`]) finished: both reflectors answered. See [`crate::network::traverse::nat`].
+    NatClassified { nat: crate::network::traverse::nat::NatType },
+    /// An inbound transfer over the auto-download limit is waiting for the user: answer with [`StatusChecker::accept_transfer`] / [`StatusChecker::decline_transfer`]. Sent once per held transfer.
+    TransferHeld {
+        peer_addr: SocketAddr,
+        stream_id: u8,
+        total_size: u32,
+    },
     /// A hole-punch to `peer_pubkey` round-tripped: `remote` is a validated direct path. The app records it on the matching contact's `validated_path`, so `race_addrs` prefers it. `peer_pubkey` may be any device in the friend's fleet (match via `Contact::knows_device`).
     PathValidated {
         peer_pubkey: DevicePubkey,
@@ -363,6 +415,91 @@ struct PendingPing {
     sent_at: Instant,
 }
 
+/// Signature failures from one source before its traffic is dropped — a stray corrupt packet or two is line noise, a run of them is someone forging.
+const SIG_FAIL_THRESHOLD: u32 = 8;
+/// Failures count within this window of the first; a quiet spell resets the tally.
+const SIG_FAIL_WINDOW: Duration = Duration::from_secs(60);
+/// How long a source over the threshold is ignored.
+const SIG_BLOCK: Duration = Duration::from_secs(300);
+/// Sources tracked at once; past this the one whose tally is oldest is forgotten.
+const SIG_FAIL_MAX_TRACKED: usize = 256;
+
+/// Per-source tally of messages that failed signature verification, and the sources temporarily dropped for too many. A source is the pair (IP, claimed pubkey): not the port (a forger picks any port), and never the IP alone, since a NAT or the relay pipe (`RELAY_ADDR`) puts many honest peers behind one address. So relayed forgeries count like any others, and a block drops only what that IP sends under that pubkey — everyone else sharing the address is untouched.
+struct SigFailures {
+    /// (source IP, claimed pubkey, tally window start, failures in window, dropped until)
+    sources: Vec<(std::net::IpAddr, [u8; 32], Instant, u32, Option<Instant>)>,
+}
+
+impl SigFailures {
+    fn new() -> Self {
+        Self { sources: Vec::new() }
+    }
+
+    /// Count a failed verification from `src` claiming `claimed`. Returns true when this one tipped the pair into a block.
+    fn record(&mut self, src: SocketAddr, claimed: &DevicePubkey, now: Instant) -> bool {
+        let ip = udp::canon_socketaddr(src).ip();
+        let key = *claimed.as_bytes();
+        let i = match self.sources.iter().position(|(s, k, ..)| *s == ip && *k == key) {
+            Some(i) => i,
+            None => {
+                if self.sources.len() >= SIG_FAIL_MAX_TRACKED {
+                    if let Some(oldest) = (0..self.sources.len())
+                        .filter(|&i| !self.sources[i].4.is_some_and(|until| until > now))
+                        .min_by_key(|&i| self.sources[i].2)
+                    {
+                        self.sources.swap_remove(oldest);
+                    } else {
+                        return false;
+                    }
+                }
+                self.sources.push((ip, key, now, 0, None));
+                self.sources.len() - 1
+            }
+        };
+        let (_, _, start, count, until) = &mut self.sources[i];
+        if now.duration_since(*start) > SIG_FAIL_WINDOW {
+            *start = now;
+            *count = 0;
+        }
+        *count += 1;
+        if *count >= SIG_FAIL_THRESHOLD && !until.is_some_and(|u| u > now) {
+            *until = Some(now + SIG_BLOCK);
+            *count = 0;
+            return true;
+        }
+        false
+    }
+
+    /// Whether what `src` sends claiming `claimed` is being dropped right now.
+    fn blocked(&self, src: SocketAddr, claimed: &DevicePubkey, now: Instant) -> bool {
+        let ip = udp::canon_socketaddr(src).ip();
+        self.sources
+            .iter()
+            .any(|(s, k, _, _, until)| *s == ip && k == claimed.as_bytes() && until.is_some_and(|u| u > now))
+    }
+}
+
+/// [`verify_provenance_signature`] for a message that arrived from `src`: a blocked (source, claimed pubkey) pair is refused without the verify, a failure is tallied against the pair, and the one that crosses the threshold is logged as a security event.
+fn verify_from(
+    failures: &Mutex<SigFailures>,
+    src: SocketAddr,
+    provenance_hash: &[u8; 32],
+    signer_pubkey: &DevicePubkey,
+    signature: &[u8; 64],
+) -> bool {
+    let now = Instant::now();
+    if failures.lock().unwrap().blocked(src, signer_pubkey, now) {
+        return false;
+    }
+    if verify_provenance_signature(provenance_hash, signer_pubkey, signature) {
+        return true;
+    }
+    if failures.lock().unwrap().record(src, signer_pubkey, now) {
+        crate::logf_at!(crate::LogLevel::Warn, "SECURITY: {} sent {} messages with bad signatures claiming pubkey {} - dropping them for {}s", src, SIG_FAIL_THRESHOLD, hex::encode(&signer_pubkey.as_bytes()[..8]), SIG_BLOCK.as_secs());
+    }
+    false
+}
+
 /// Contact status checker
 ///
 /// Spawns a background thread to handle async UDP ping/pong and CLUTCH messages. Uses the shared UDP socket from HandleQuery. For large CLUTCH payloads, uses TCP fallback (raw254 not yet implemented).
@@ -380,12 +517,20 @@ pub struct StatusChecker {
     complete_proof_sender: Sender<ClutchCompleteRequest>,
     lan_broadcast_sender: Sender<LanBroadcastRequest>,
     clear_pt_sender: Sender<ClearPtSendsRequest>,
+    transfer_policy_sender: Sender<TransferPolicyRequest>,
+    app_datagram_sender: Sender<AppDatagramRequest>,
     status_receiver: Receiver<StatusUpdate>,
     /// Fire a phonebook-gossip request at a reachable peer (its address). The peer replies with
     /// the self-signed peer records it holds, so a device whose own fgtw is unreachable can still
     /// learn a friend's address from a friend it CAN reach. Not a relay — only routing records
     /// (each independently verifiable) travel, never payload.
     phonebook_req_sender: Sender<SocketAddr>,
+    /// Kick a NAT-type probe: `Reflect` to two FGTW directory nodes at different IPs, compared when both answer (`StatusUpdate::NatClassified`).
+    nat_probe_sender: Sender<()>,
+    /// Bounce the relay pipe WebSocket: drop the current connection (or cut short the reconnect backoff) and dial again now.
+    pipe_kick: Arc<tokio::sync::Notify>,
+    /// Set at shutdown: the pipe task drops its WebSocket on the next kick and stops redialling.
+    pipe_closed: Arc<AtomicBool>,
 }
 
 impl StatusChecker {
@@ -413,8 +558,15 @@ impl StatusChecker {
         let (complete_proof_tx, complete_proof_rx) = channel::<ClutchCompleteRequest>();
         let (lan_broadcast_tx, lan_broadcast_rx) = channel::<LanBroadcastRequest>();
         let (clear_pt_tx, clear_pt_rx) = channel::<ClearPtSendsRequest>();
+        let (transfer_policy_tx, transfer_policy_rx) = channel::<TransferPolicyRequest>();
+        let (app_datagram_tx, app_datagram_rx) = channel::<AppDatagramRequest>();
         let (status_tx, status_rx) = channel::<StatusUpdate>();
         let (phonebook_req_tx, phonebook_req_rx) = channel::<SocketAddr>();
+        let (nat_probe_tx, nat_probe_rx) = channel::<()>();
+        let pipe_kick = Arc::new(tokio::sync::Notify::new());
+        let pipe_kick_task = pipe_kick.clone();
+        let pipe_closed = Arc::new(AtomicBool::new(false));
+        let pipe_closed_task = pipe_closed.clone();
 
         let our_pubkey = DevicePubkey::from_bytes(keypair.public.to_bytes());
 
@@ -456,11 +608,16 @@ impl StatusChecker {
                     complete_proof_rx,
                     lan_broadcast_rx,
                     clear_pt_rx,
+                    transfer_policy_rx,
+                    app_datagram_rx,
                     status_tx,
                     contacts,
                     sync_records,
                     Some(event_proxy),
                     phonebook_req_rx,
+                    nat_probe_rx,
+                    pipe_kick_task,
+                    pipe_closed_task,
                     peer_store,
                 )
                 .await;
@@ -496,8 +653,13 @@ impl StatusChecker {
             complete_proof_sender: complete_proof_tx,
             lan_broadcast_sender: lan_broadcast_tx,
             clear_pt_sender: clear_pt_tx,
+            transfer_policy_sender: transfer_policy_tx,
+            app_datagram_sender: app_datagram_tx,
             status_receiver: status_rx,
             phonebook_req_sender: phonebook_req_tx,
+            nat_probe_sender: nat_probe_tx,
+            pipe_kick,
+            pipe_closed,
         })
     }
 
@@ -522,8 +684,15 @@ impl StatusChecker {
         let (complete_proof_tx, complete_proof_rx) = channel::<ClutchCompleteRequest>();
         let (lan_broadcast_tx, lan_broadcast_rx) = channel::<LanBroadcastRequest>();
         let (clear_pt_tx, clear_pt_rx) = channel::<ClearPtSendsRequest>();
+        let (transfer_policy_tx, transfer_policy_rx) = channel::<TransferPolicyRequest>();
+        let (app_datagram_tx, app_datagram_rx) = channel::<AppDatagramRequest>();
         let (status_tx, status_rx) = channel::<StatusUpdate>();
         let (phonebook_req_tx, phonebook_req_rx) = channel::<SocketAddr>();
+        let (nat_probe_tx, nat_probe_rx) = channel::<()>();
+        let pipe_kick = Arc::new(tokio::sync::Notify::new());
+        let pipe_kick_task = pipe_kick.clone();
+        let pipe_closed = Arc::new(AtomicBool::new(false));
+        let pipe_closed_task = pipe_closed.clone();
 
         let our_pubkey = DevicePubkey::from_bytes(keypair.public.to_bytes());
 
@@ -565,11 +734,16 @@ impl StatusChecker {
                     complete_proof_rx,
                     lan_broadcast_rx,
                     clear_pt_rx,
+                    transfer_policy_rx,
+                    app_datagram_rx,
                     status_tx,
                     contacts,
                     sync_records,
                     None,
                     phonebook_req_rx,
+                    nat_probe_rx,
+                    pipe_kick_task,
+                    pipe_closed_task,
                     peer_store,
                 )
                 .await;
@@ -605,8 +779,13 @@ impl StatusChecker {
             complete_proof_sender: complete_proof_tx,
             lan_broadcast_sender: lan_broadcast_tx,
             clear_pt_sender: clear_pt_tx,
+            transfer_policy_sender: transfer_policy_tx,
+            app_datagram_sender: app_datagram_tx,
             status_receiver: status_rx,
             phonebook_req_sender: phonebook_req_tx,
+            nat_probe_sender: nat_probe_tx,
+            pipe_kick,
+            pipe_closed,
         })
     }
 
@@ -629,6 +808,42 @@ impl StatusChecker {
 
     // NOTE: send_clutch() removed - legacy v1 CLUTCH no longer used
 
+    /// A checker with no network thread behind it: every request channel is live but unread, except outgoing messages, whose receiver is handed back so a test can see exactly what reached PT.
+    #[cfg(test)]
+    pub(crate) fn detached() -> (Self, Receiver<MessageRequest>) {
+        let (checker, message_rx, _status_tx) = Self::detached_with_status();
+        (checker, message_rx)
+    }
+
+    /// [`Self::detached`], also handing back its status channel's sender — a test feeds the UI the updates the network thread would.
+    #[cfg(test)]
+    pub(crate) fn detached_with_status() -> (Self, Receiver<MessageRequest>, Sender<StatusUpdate>) {
+        let (message_tx, message_rx) = channel::<MessageRequest>();
+        let (status_tx, status_rx) = channel::<StatusUpdate>();
+        let checker = Self {
+            ping_sender: channel().0,
+            message_sender: message_tx,
+            ack_sender: channel().0,
+            avatar_request_sender: channel().0,
+            avatar_response_sender: channel().0,
+            history_sender: channel().0,
+            pt_sender: channel().0,
+            offer_sender: channel().0,
+            kem_response_sender: channel().0,
+            complete_proof_sender: channel().0,
+            lan_broadcast_sender: channel().0,
+            clear_pt_sender: channel().0,
+            transfer_policy_sender: channel().0,
+            app_datagram_sender: channel().0,
+            status_receiver: status_rx,
+            phonebook_req_sender: channel().0,
+            nat_probe_sender: channel().0,
+            pipe_kick: Arc::new(tokio::sync::Notify::new()),
+            pipe_closed: Arc::new(AtomicBool::new(false)),
+        };
+        (checker, message_rx, status_tx)
+    }
+
     /// Send an encrypted message (non-blocking)
     pub fn send_message(&self, request: MessageRequest) {
         let _ = self.message_sender.send(request);
@@ -662,6 +877,22 @@ impl StatusChecker {
         let _ = self.phonebook_req_sender.send(addr);
     }
 
+    /// Diagnose our NAT (non-blocking): the result arrives as `StatusUpdate::NatClassified`, or never if the peer store doesn't yet hold two reflectors at distinct IPs.
+    pub fn probe_nat(&self) {
+        let _ = self.nat_probe_sender.send(());
+    }
+
+    /// Reconnect the relay pipe now (non-blocking) — see `PhotonApp::force_reconnect`.
+    pub fn reconnect_pipe(&self) {
+        self.pipe_kick.notify_one();
+    }
+
+    /// Close the relay pipe for good (shutdown): the socket is dropped now and never redialled.
+    pub fn close_pipe(&self) {
+        self.pipe_closed.store(true, Ordering::SeqCst);
+        self.pipe_kick.notify_one();
+    }
+
     pub fn send_pt(&self, peer_addr: SocketAddr, data: Vec<u8>) {
         let _ = self.pt_sender.send(PTSendRequest { peer_addr, data });
     }
@@ -703,6 +934,38 @@ impl StatusChecker {
         let _ = self.clear_pt_sender.send(ClearPtSendsRequest { peer_addr });
     }
 
+    /// Set the auto-download limit (non-blocking): inbound transfers over `limit` bytes are held and surface as `StatusUpdate::TransferHeld`. `None` accepts everything. Seeded from the `auto_download_mib` setting when the checker starts.
+    pub fn set_auto_download_limit(&self, limit: Option<u32>) {
+        let _ = self.transfer_policy_sender.send(TransferPolicyRequest::Limit(limit));
+    }
+
+    /// Set the largest inbound transfer we take (non-blocking): a SPEC over `bytes` is refused for its stream. Seeded from the `max_transfer_mib` setting when the checker starts.
+    pub fn set_max_transfer_size(&self, bytes: u32) {
+        let _ = self.transfer_policy_sender.send(TransferPolicyRequest::MaxSize(bytes));
+    }
+
+    /// Download a held transfer (non-blocking).
+    pub fn accept_transfer(&self, peer_addr: SocketAddr, stream_id: u8) {
+        let _ = self.transfer_policy_sender.send(TransferPolicyRequest::Accept { peer_addr, stream_id });
+    }
+
+    /// Refuse a held transfer (non-blocking) — the sender drops it.
+    pub fn decline_transfer(&self, peer_addr: SocketAddr, stream_id: u8) {
+        let _ = self.transfer_policy_sender.send(TransferPolicyRequest::Decline { peer_addr, stream_id });
+    }
+
+    /// Send an embedding application's own payload to the device `recipient_pubkey` at `peer_addr` (non-blocking): signed for that device, ordered with our chat on PT's reliable small-packet path. Returns the datagram's id — `StatusUpdate::AppDatagramDelivered` carries it once the peer acks. Errors past [`PTManager::APP_DATAGRAM_MAX`] or if the network thread is gone.
+    pub fn send_app_datagram(&self, peer_addr: SocketAddr, recipient_pubkey: [u8; 32], payload: &[u8]) -> Result<crate::network::pt::AppDatagramId, String> {
+        if payload.len() > PTManager::APP_DATAGRAM_MAX {
+            return Err(format!("App datagram is {} bytes, max {}", payload.len(), PTManager::APP_DATAGRAM_MAX));
+        }
+        let id = rand::random::<crate::network::pt::AppDatagramId>();
+        self.app_datagram_sender
+            .send(AppDatagramRequest { peer_addr, recipient_pubkey, payload: payload.to_vec(), id })
+            .map_err(|_| "network thread gone".to_string())?;
+        Ok(id)
+    }
+
     /// Check for status updates (non-blocking)
     pub fn try_recv(&self) -> Option<StatusUpdate> {
         self.status_receiver.try_recv().ok()
@@ -785,11 +1048,16 @@ async fn run_checker(
     complete_proof_rx: Receiver<ClutchCompleteRequest>,
     lan_broadcast_rx: Receiver<LanBroadcastRequest>,
     clear_pt_rx: Receiver<ClearPtSendsRequest>,
+    transfer_policy_rx: Receiver<TransferPolicyRequest>,
+    app_datagram_rx: Receiver<AppDatagramRequest>,
     status_tx: Sender<StatusUpdate>,
     contacts: ContactPubkeys,
     sync_records_provider: SyncRecordsProvider,
     event_proxy: OptionalEventProxy,
     phonebook_req_rx: Receiver<SocketAddr>,
+    nat_probe_rx: Receiver<()>,
+    pipe_kick: Arc<tokio::sync::Notify>,
+    pipe_closed: Arc<AtomicBool>,
     peer_store: Arc<Mutex<crate::network::fgtw::PeerStore>>,
 ) {
     use tokio::net::UdpSocket as TokioUdpSocket;
@@ -813,41 +1081,42 @@ async fn run_checker(
         }
     };
 
-    // Start TCP listener for CLUTCH large payloads (same port as UDP) Try IPv6 first (dual-stack), fall back to IPv4 Skip on Android - tokio TcpListener has issues with accept() returning EINVAL
+    // Start TCP listener for CLUTCH large payloads (same port as UDP), both families: dual-stack, or one listener per family where the platform refuses it (tcp::bind_listeners) Skip on Android - tokio TcpListener has issues with accept() returning EINVAL
     #[cfg(not(target_os = "android"))]
-    let tcp_listener = {
+    let (tcp_listener, tcp_listener_v4) = {
         let udp_port = std_socket
             .local_addr()
             .map(|a| a.port())
             .unwrap_or(PHOTON_PORT);
-        // Try IPv6 dual-stack first (accepts both IPv4 and IPv6 on most systems)
-        let tcp_addr_v6 = SocketAddr::new(
-            std::net::IpAddr::V6(std::net::Ipv6Addr::UNSPECIFIED),
-            udp_port,
-        );
-        match tokio::net::TcpListener::bind(tcp_addr_v6).await {
-            Ok(listener) => {
-                crate::logf!("Status: TCP listening on [::]:{}  (dual-stack)", udp_port);
-                Some(listener)
-            }
-            Err(_) => {
-                // Fall back to IPv4 only
-                let tcp_addr_v4 = SocketAddr::new(std::net::IpAddr::V4(local_ip), udp_port);
-                match tokio::net::TcpListener::bind(tcp_addr_v4).await {
-                    Ok(listener) => {
-                        crate::logf!("Status: TCP listening on {} (IPv4 only)", tcp_addr_v4);
-                        Some(listener)
+        match crate::network::tcp::bind_listeners(udp_port) {
+            Ok((main, v4)) => {
+                let main = tokio::net::TcpListener::from_std(main);
+                let v4 = v4.and_then(|l| tokio::net::TcpListener::from_std(l).ok());
+                match main {
+                    Ok(main) => {
+                        crate::logf!("Status: TCP listening on port {} ({})", udp_port, if v4.is_some() { "IPv6 + IPv4" } else { "dual-stack" });
+                        (Some(main), v4)
                     }
                     Err(e) => {
                         crate::logf!("Status: Failed to bind TCP: {}", e);
-                        None
+                        (None, None)
                     }
                 }
             }
+            Err(e) => {
+                crate::logf!("Status: Failed to bind TCP: {}", e);
+                (None, None)
+            }
         }
     };
     #[cfg(target_os = "android")]
-    let tcp_listener: Option<tokio::net::TcpListener> = None;
+    let (tcp_listener, tcp_listener_v4): (Option<tokio::net::TcpListener>, Option<tokio::net::TcpListener>) = (None, None);
+
+    // IPv4 half of a split UDP binding (dual-stack refused), read in the receiver's select! beside the main socket
+    let socket_v4: Option<TokioUdpSocket> = udp::split_v4().and_then(|s| {
+        s.set_nonblocking(true).ok()?;
+        TokioUdpSocket::from_std(s).ok()
+    });
 
     let pending: Arc<Mutex<Vec<PendingPing>>> = Arc::new(Mutex::new(Vec::new()));
 
@@ -855,16 +1124,33 @@ async fn run_checker(
     let pending_probes: Arc<Mutex<crate::network::traverse::punch::PendingProbes>> =
         Arc::new(Mutex::new(crate::network::traverse::punch::PendingProbes::new()));
 
+    // The NAT-type probe in flight, shared the same way: the main loop starts it (two `Reflect`s), the receiver feeds it each `ReflectResponse`.
+    let nat_probe: Arc<Mutex<crate::network::traverse::nat::NatProbe>> =
+        Arc::new(Mutex::new(crate::network::traverse::nat::NatProbe::new()));
+
     // Track consecutive failed pings per contact (hysteresis - don't flip offline on 1 lost packet)
     let failed_pings: Arc<Mutex<Vec<([u8; 32], u8)>>> = Arc::new(Mutex::new(Vec::new()));
     const OFFLINE_THRESHOLD: u8 = 3;
 
-    // PT manager for large transfers - shared with receiver task
+    // Anti-replay for signed pings and CLUTCH offers, shared by the UDP/relay receiver and the TCP receiver (network/replay.rs)
+    let replay: Arc<Mutex<ReplayGuard>> = Arc::new(Mutex::new(ReplayGuard::new()));
+
+    // Signature-failure tally per (source IP, claimed pubkey); a pair over SIG_FAIL_THRESHOLD is refused for SIG_BLOCK by verify_from
+    let sig_failures: Arc<Mutex<SigFailures>> = Arc::new(Mutex::new(SigFailures::new()));
+
+    // PT manager for large transfers - shared with receiver task. The inbound size cap and the auto-download limit start from settings; StatusChecker::set_max_transfer_size / set_auto_download_limit change them live.
     let pt: Arc<Mutex<PTManager>> = Arc::new(Mutex::new(PTManager::new(keypair.clone())));
+    {
+        let settings = crate::storage::settings::Settings::load_or_create();
+        let mut pt_mgr = pt.lock().unwrap();
+        pt_mgr.set_max_transfer_size(settings.max_transfer_bytes());
+        pt_mgr.set_auto_accept_limit(settings.auto_download_limit());
+    }
 
     let socket_recv = socket.clone();
     let pending_recv = pending.clone();
     let pending_probes_recv = pending_probes.clone();
+    let nat_probe_recv = nat_probe.clone();
     let our_pubkey_recv = our_pubkey.clone();
     let keypair_recv = keypair.clone();
     let status_tx_recv = status_tx.clone();
@@ -873,35 +1159,30 @@ async fn run_checker(
     let event_proxy_recv = event_proxy.clone();
     let pt_recv = pt.clone();
     let failed_pings_recv = failed_pings.clone();
+    let replay_recv = replay.clone();
+    let sig_failures_recv = sig_failures.clone();
     let peer_store_recv = peer_store.clone();
 
+    #[cfg(feature = "metrics")]
+    crate::network::fgtw::metrics::serve();
+
     // Spawn multicast listener for LAN peer discovery Multicast is more reliable than broadcast across different network configurations
     {
         let status_tx_mcast = status_tx.clone();
         let event_proxy_mcast = event_proxy.clone();
         tokio::spawn(async move {
-            // Photon-specific multicast group in administratively scoped range (239.x.x.x) Address derived from random entropy: 0x68C790 -> 239.104.199.144
-            let multicast_addr: Ipv4Addr = Ipv4Addr::new(239, 104, 199, 144);
+            let multicast_addr = crate::network::lan::MULTICAST_V4;
             let multicast_port = crate::MULTICAST_PORT;
 
-            // Create socket bound to multicast port
-            let socket = match std::net::UdpSocket::bind(format!("0.0.0.0:{}", multicast_port)) {
+            // Bound to the multicast port, joined to the group, broadcast receive on (subnet broadcast fallback)
+            let socket = match crate::network::lan::bind_listener_v4(multicast_port) {
                 Ok(s) => s,
                 Err(e) => {
-                    crate::logf!("LAN: Could not bind multicast socket: {}", e);
+                    crate::logf!("LAN: Could not set up multicast socket: {}", e);
                     return;
                 }
             };
 
-            // Enable broadcast receive (for subnet broadcast fallback)
-            let _ = socket.set_broadcast(true);
-
-            // Join multicast group
-            if let Err(e) = socket.join_multicast_v4(&multicast_addr, &Ipv4Addr::UNSPECIFIED) {
-                crate::logf!("LAN: Failed to join multicast group: {}", e);
-                return;
-            }
-
             // Set non-blocking for async
             if let Err(e) = socket.set_nonblocking(true) {
                 crate::logf!("LAN: Failed to set non-blocking: {}", e);
@@ -950,9 +1231,7 @@ async fn run_checker(
         let status_tx_mcast6 = status_tx.clone();
         let event_proxy_mcast6 = event_proxy.clone();
         tokio::spawn(async move {
-            // IPv6 multicast group: ff02::68c7:9014 (link-local scope with our random bytes)
-            let multicast_addr: std::net::Ipv6Addr =
-                std::net::Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0x68c7, 0x9014);
+            let multicast_addr = crate::network::lan::MULTICAST_V6;
             let multicast_port = crate::MULTICAST_PORT;
 
             // Create IPv6-only socket using libc to set IPV6_V6ONLY before binding This prevents dual-stack conflict with the IPv4 multicast socket on same port
@@ -1089,11 +1368,12 @@ async fn run_checker(
         let status_tx_tcp = status_tx.clone();
         let event_proxy_tcp = event_proxy.clone();
         let contacts_tcp = contacts.clone();
+        let replay_tcp = replay.clone();
         tokio::spawn(async move {
             crate::log("Status: TCP receiver task started");
             loop {
-                // Async accept - sleeps until connection arrives (no polling)
-                match listener.accept().await {
+                // Async accept - sleeps until connection arrives (no polling), on either family's listener
+                match crate::network::tcp::accept_either(&listener, tcp_listener_v4.as_ref()).await {
                     Ok((stream, src_addr)) => {
                         crate::logf!("Status: TCP connection from {}", src_addr);
                         // Convert to std TcpStream for tcp::recv (uses VSF L field for framing)
@@ -1154,6 +1434,10 @@ async fn run_checker(
                                                     crate::logf!("TCP: ClutchOffer REJECTED from {} - sender not in contacts (pubkey: {})", src_addr, hex::encode(&sender_pubkey[..8]));
                                                     continue;
                                                 }
+                                                if let Err(why) = admit_offer(&replay_tcp, &contacts_tcp, &sender_pubkey, &data) {
+                                                    crate::logf!("TCP: ClutchOffer REJECTED from {} - {} (pubkey: {})", src_addr, why, hex::encode(&sender_pubkey[..8]));
+                                                    continue;
+                                                }
                                                 crate::log("Status: Received ClutchOffer via TCP (VSF verified)");
                                                 send_status_update(
                                                     &status_tx_tcp,
@@ -1248,17 +1532,34 @@ async fn run_checker(
     {
         let our_dev_hex = hex::encode(our_device_pk);
         let inject_tx_pipe = inject_tx.clone();
+        // Per-sender relay byte budget — survives reconnects, so dropping the socket doesn't reset a flooder's window.
+        let mut relay_budget = crate::network::fgtw::relay::RelayBudget::from_settings(&crate::storage::settings::Settings::load_or_create());
+        let (pt_pipe, keypair_pipe, contacts_pipe) = (pt.clone(), keypair.clone(), contacts.clone());
         tokio::spawn(async move {
             use futures::StreamExt;
             use tokio_tungstenite::tungstenite::Message;
             let url = format!("wss://fgtw.org/pipe?dev={}", our_dev_hex);
             crate::logf!("PIPE: relay pipe task started (dev {}...)", &our_dev_hex[..8]);
             loop {
+                if pipe_closed.load(Ordering::SeqCst) {
+                    crate::log("PIPE: closed for shutdown");
+                    return;
+                }
                 match tokio_tungstenite::connect_async(&url).await {
                     Ok((ws_stream, _)) => {
                         crate::log("PIPE: connected — relay is a live socket now");
                         let (_, mut read) = ws_stream.split();
-                        while let Some(msg) = read.next().await {
+                        loop {
+                            let msg = tokio::select! {
+                                m = read.next() => match m {
+                                    Some(m) => m,
+                                    None => break,
+                                },
+                                _ = pipe_kick.notified() => {
+                                    crate::log("PIPE: reconnect requested — dropping the current socket");
+                                    break;
+                                }
+                            };
                             match msg {
                                 Ok(Message::Binary(data)) => {
                                     // Peel the authenticated relay envelope the worker now forwards intact. The
@@ -1267,7 +1568,30 @@ async fn run_checker(
                                     // bytes, not the RELAY_ADDR sentinel. Inject only the inner payload — it's
                                     // byte-identical to a direct message, so the dispatch below is untouched.
                                     match crate::network::fgtw::relay::peel_relay_envelope(&data) {
-                                        Some((sender_key, inner)) => {
+                                        Some(crate::network::fgtw::relay::RelayEnvelope { sender: sender_key, payload: inner, wants_receipt }) => {
+                                            // Over its budget → refused before dispatch. The sender's retransmit backoff is the 429: it comes back later and gets in once its window has drained. A signer outside the contacts set draws on the shared stranger pool.
+                                            let known = contacts_pipe.lock().unwrap().iter().any(|p| *p.as_bytes() == sender_key);
+                                            if let Err(retry) = relay_budget.admit(&sender_key, known, data.len(), std::time::Instant::now()) {
+                                                crate::logf!("PIPE: ← {}B from {} refused — over relay cap, window clears in {}s", data.len(), hex::encode(sender_key), retry.as_secs());
+                                                continue;
+                                            }
+                                            #[cfg(feature = "metrics")]
+                                            crate::network::fgtw::metrics::note_relay_bytes(data.len());
+                                            // A receipt for one of OUR relayed PT transfers — it closes the transfer here; there's nothing to dispatch.
+                                            if let Some(hash) = crate::network::fgtw::relay::parse_relay_receipt(&inner) {
+                                                let matched = pt_pipe.lock().unwrap().handle_relay_receipt(sender_key, hash);
+                                                crate::logf!("PIPE: ← delivery receipt from {} ({})", hex::encode(&sender_key[..4]), if matched { "transfer complete" } else { "no matching transfer" });
+                                                continue;
+                                            }
+                                            if wants_receipt {
+                                                let receipt = crate::network::fgtw::relay::build_relay_receipt(&inner);
+                                                let keypair = keypair_pipe.clone();
+                                                tokio::spawn(async move {
+                                                    if let Err(e) = crate::network::fgtw::relay::send_via_relay(&keypair, &sender_key, &receipt).await {
+                                                        crate::logf!("PIPE: delivery receipt to {} failed: {}", hex::encode(&sender_key[..4]), e);
+                                                    }
+                                                });
+                                            }
                                             crate::logf!("PIPE: ← {}B envelope from {} → {}B inner (injecting)", data.len(), hex::encode(&sender_key[..4]), inner.len());
                                             if inject_tx_pipe.send(inner).await.is_err() {
                                                 // Receiver task gone — the whole status task is tearing down.
@@ -1295,8 +1619,11 @@ async fn run_checker(
                         crate::logf!("PIPE: connect failed: {} — retrying", e);
                     }
                 }
-                // Reconnect: hold the pipe open for the life of the session so a relay-only peer stays reachable.
-                tokio::time::sleep(Duration::from_secs(5)).await;
+                // Reconnect: hold the pipe open for the life of the session so a relay-only peer stays reachable. A kick skips the backoff.
+                tokio::select! {
+                    _ = tokio::time::sleep(Duration::from_secs(5)) => {}
+                    _ = pipe_kick.notified() => {}
+                }
             }
         });
     }
@@ -1317,6 +1644,12 @@ async fn run_checker(
             let mut injected_holder: Option<Vec<u8>> = None;
             let recv_result: std::io::Result<(usize, SocketAddr)> = tokio::select! {
                 r = socket_recv.recv_from(&mut buf) => r,
+                // A split binding's IPv4 socket: held like an injected frame, but with its real source
+                r = udp::recv_split_v4(socket_v4.as_ref()) => r.map(|(bytes, src)| {
+                    let n = bytes.len();
+                    injected_holder = Some(bytes);
+                    (n, src)
+                }),
                 injected = inject_rx.recv() => match injected {
                     Some(bytes) => {
                         let n = bytes.len();
@@ -1336,317 +1669,314 @@ async fn run_checker(
                         None => &buf[..len],
                     };
 
-                    // Check for PT DATA packets first (start with 'd') NOTE: Individual DATA packets not logged - only completion/failure
-                    if is_pt_data(msg_bytes) {
-                        if let Some(data) = PTData::from_bytes(msg_bytes) {
-                            // Handle data and collect responses (must drop lock before await)
-                            let (ack_bytes, complete_bytes, received_data, inbound_stats) = {
-                                // Capture the stream BEFORE `data` is moved into handle_data: completion + drain are stream-scoped so concurrent transfers from the same peer (CLUTCH offer + KEM response) don't get cross-wired and silently dropped.
-                                let stream_id = data.stream_id;
-                                let mut pt_mgr = pt_recv.lock().unwrap();
-                                let ack = pt_mgr.handle_data(src_addr, data);
-                                let complete = pt_mgr.check_inbound_complete(src_addr, stream_id);
-                                let stats = pt_mgr.inbound_stats(&src_addr);
-                                let data = if complete.is_some() {
-                                    pt_mgr.take_inbound_data(src_addr, stream_id)
+                    // Centralized UDP RX logging - THE ONLY place incoming packets are logged. Individual PT DATA packets are not — only a transfer's completion/failure.
+                    #[cfg(feature = "development")]
+                    if !is_pt_data(msg_bytes) {
+                        udp::log_received(msg_bytes, &src_addr);
+                    }
+
+                    // Everything PT — DATA, the VSF control packets (SPEC/ACK/NAK/CONTROL/COMPLETE), app datagrams — goes thru the one dispatch the Transport loop uses (`PTManager::handle_datagram`); what follows is only the app's reaction to it. Contacts only for a SPEC or an app datagram: a stranger's is dropped unanswered.
+                    let pt_handled = {
+                        let is_contact = |pk: &[u8; 32]| {
+                            let sender = DevicePubkey::from_bytes(*pk);
+                            contacts_recv.lock().unwrap().iter().any(|p| *p == sender)
+                        };
+                        let mut pt_mgr = pt_recv.lock().unwrap();
+                        pt_mgr
+                            .handle_datagram(src_addr, msg_bytes, &is_contact)
+                            .map(|handled| (handled, pt_mgr.take_app_deliveries(), pt_mgr.take_app_datagrams()))
+                    };
+                    if let Some((handled, delivered, app_datagrams)) = pt_handled {
+                        // Replies go out awaited, after the lock is dropped: an ACK or COMPLETE the socket can't take right now waits for room rather than being dropped.
+                        for reply in &handled.replies {
+                            udp::send(&socket_recv, reply, src_addr).await;
+                        }
+                        for id in delivered {
+                            send_status_update(&status_tx_recv, StatusUpdate::AppDatagramDelivered { id }, &event_proxy_recv);
+                        }
+                        for d in app_datagrams {
+                            send_status_update(
+                                &status_tx_recv,
+                                StatusUpdate::AppDatagramReceived {
+                                    sender_pubkey: d.from,
+                                    payload: d.payload,
+                                    sender_addr: d.peer_addr,
+                                },
+                                &event_proxy_recv,
+                            );
+                        }
+                        // The auto-download policy is holding this SPEC for the user (its reply was a Pause).
+                        if let Some((stream_id, total_size)) = handled.held {
+                            send_status_update(&status_tx_recv, StatusUpdate::TransferHeld { peer_addr: src_addr, stream_id, total_size }, &event_proxy_recv);
+                        }
+                        if let Some((data, inbound_stats)) = handled.received {
+                            // Log utilization summary
+                            if let Some((packets, bytes, duplicates, duration_ms)) =
+                                inbound_stats
+                            {
+                                let total_recv = packets + duplicates;
+                                let utilization = if total_recv > 0 {
+                                    (packets as f64 / total_recv as f64) * 100.0
                                 } else {
-                                    None
+                                    100.0
                                 };
-                                (ack, complete, data, stats)
-                            };
-                            // Now send responses (lock is dropped)
-                            if let Some(ack) = ack_bytes {
-                                udp::send(&socket_recv, &ack, src_addr).await;
+                                let thruput_kbps = if duration_ms > 0 {
+                                    (bytes as f64 * 8.0) / (duration_ms as f64)
+                                } else {
+                                    0.0
+                                };
+                                let thruput_str = if thruput_kbps >= 1000.0 {
+                                    format!("{:.1} Mbps", thruput_kbps / 1000.0)
+                                } else {
+                                    format!("{:.0} kbps", thruput_kbps)
+                                };
+                                crate::logf!("PT: ← {} OK | {} | {:.1}s | {} pkts | {:.0}% util ({} dups)", src_addr, thruput_str, duration_ms as f64 / 1000.0, packets, utilization, duplicates);
+                            } else {
+                                crate::logf!("PT: ← {} OK | {} bytes", src_addr, data.len());
                             }
-                            if let Some(complete) = complete_bytes {
-                                udp::send(&socket_recv, &complete, src_addr).await;
-                                if let Some(data) = received_data {
-                                    // Log utilization summary
-                                    if let Some((packets, bytes, duplicates, duration_ms)) =
-                                        inbound_stats
-                                    {
-                                        let total_recv = packets + duplicates;
-                                        let utilization = if total_recv > 0 {
-                                            (packets as f64 / total_recv as f64) * 100.0
-                                        } else {
-                                            100.0
-                                        };
-                                        let thruput_kbps = if duration_ms > 0 {
-                                            (bytes as f64 * 8.0) / (duration_ms as f64)
-                                        } else {
-                                            0.0
-                                        };
-                                        let thruput_str = if thruput_kbps >= 1000.0 {
-                                            format!("{:.1} Mbps", thruput_kbps / 1000.0)
-                                        } else {
-                                            format!("{:.0} kbps", thruput_kbps)
-                                        };
-                                        crate::logf!("PT: ← {} OK | {} | {:.1}s | {} pkts | {:.0}% util ({} dups)", src_addr, thruput_str, duration_ms as f64 / 1000.0, packets, utilization, duplicates);
-                                    } else {
-                                        crate::logf!("PT: ← {} OK | {} bytes", src_addr, data.len());
-                                    }
 
-                                    // Inspect completed PT data with VSF inspector
-                                    if let Ok(inspection) = vsf::inspect::inspect_vsf(&data) {
-                                        crate::logf!("PT: Received VSF ({} bytes):\n{}", data.len(), inspection);
-                                    } else {
-                                        crate::logf!("PT: Received {} bytes - NOT valid VSF", data.len());
-                                    }
+                            // Inspect completed PT data with VSF inspector
+                            if let Ok(inspection) = vsf::inspect::inspect_vsf(&data) {
+                                crate::logf!("PT: Received VSF ({} bytes):\n{}", data.len(), inspection);
+                            } else {
+                                crate::logf!("PT: Received {} bytes - NOT valid VSF", data.len());
+                            }
 
-                                    // Parse PT data as CLUTCH message and emit appropriate event
-                                    use crate::network::fgtw::protocol::{
-                                        parse_clutch_complete_vsf_without_recipient_check,
-                                        parse_clutch_kem_response_vsf_without_recipient_check,
-                                        parse_clutch_offer_vsf_without_recipient_check,
-                                    };
+                            // Parse PT data as CLUTCH message and emit appropriate event
+                            use crate::network::fgtw::protocol::{
+                                parse_clutch_complete_vsf_without_recipient_check,
+                                parse_clutch_kem_response_vsf_without_recipient_check,
+                                parse_clutch_offer_vsf_without_recipient_check,
+                            };
 
-                                    // Helper to check if sender is a known contact (defense-in-depth) Note: PT SPEC validation should have already rejected unknown senders
-                                    let is_known_sender_pt = |pubkey_bytes: &[u8; 32]| -> bool {
-                                        let sender = DevicePubkey::from_bytes(*pubkey_bytes);
-                                        let contact_list = contacts_recv.lock().unwrap();
-                                        contact_list.iter().any(|p| *p == sender)
-                                    };
+                            // Helper to check if sender is a known contact (defense-in-depth) Note: PT SPEC validation should have already rejected unknown senders
+                            let is_known_sender_pt = |pubkey_bytes: &[u8; 32]| -> bool {
+                                let sender = DevicePubkey::from_bytes(*pubkey_bytes);
+                                let contact_list = contacts_recv.lock().unwrap();
+                                contact_list.iter().any(|p| *p == sender)
+                            };
 
-                                    // Trust gate BEFORE the ~500KB CLUTCH section parse: the signer pubkey is a cheap header extraction (no section walk), so an untrusted sender is dropped before we parse their payload. The per-message checks below remain as defence-in-depth.
-                                    if let Ok(signer) =
-                                        vsf::verification::extract_signer_pubkey(&data)
-                                    {
-                                        if !is_known_sender_pt(&signer) {
-                                            crate::logf!("PT: CLUTCH message REJECTED before parse - sender not in contacts (pubkey: {})", hex::encode(&signer[..signer.len().min(8)]));
-                                            continue;
-                                        }
-                                    }
+                            // Trust gate BEFORE the ~500KB CLUTCH section parse: the signer pubkey is a cheap header extraction (no section walk), so an untrusted sender is dropped before we parse their payload. The per-message checks below remain as defence-in-depth.
+                            if let Ok(signer) =
+                                vsf::verification::extract_signer_pubkey(&data)
+                            {
+                                if !is_known_sender_pt(&signer) {
+                                    crate::logf!("PT: CLUTCH message REJECTED before parse - sender not in contacts (pubkey: {})", hex::encode(&signer[..signer.len().min(8)]));
+                                    continue;
+                                }
+                            }
 
-                                    // Try to parse as ClutchOffer
-                                    if let Ok((
-                                        payload,
-                                        sender_pubkey,
-                                        offer_provenance,
+                            // Try to parse as ClutchOffer
+                            if let Ok((
+                                payload,
+                                sender_pubkey,
+                                offer_provenance,
+                                conversation_token,
+                            )) = parse_clutch_offer_vsf_without_recipient_check(&data)
+                            {
+                                // Defense-in-depth: verify sender again
+                                if !is_known_sender_pt(&sender_pubkey) {
+                                    crate::logf!("PT: ClutchOffer REJECTED (defense-in-depth) - pubkey: {}", hex::encode(&sender_pubkey[..8]));
+                                    continue;
+                                }
+                                if let Err(why) = admit_offer(&replay_recv, &contacts_recv, &sender_pubkey, &data) {
+                                    crate::logf!("PT: ClutchOffer REJECTED - {} (pubkey: {})", why, hex::encode(&sender_pubkey[..8]));
+                                    continue;
+                                }
+                                crate::log("PT: Parsed as ClutchOffer (VSF verified)");
+                                send_status_update(
+                                    &status_tx_recv,
+                                    StatusUpdate::ClutchOfferReceived {
                                         conversation_token,
-                                    )) = parse_clutch_offer_vsf_without_recipient_check(&data)
-                                    {
-                                        // Defense-in-depth: verify sender again
-                                        if !is_known_sender_pt(&sender_pubkey) {
-                                            crate::logf!("PT: ClutchOffer REJECTED (defense-in-depth) - pubkey: {}", hex::encode(&sender_pubkey[..8]));
-                                            continue;
-                                        }
-                                        crate::log("PT: Parsed as ClutchOffer (VSF verified)");
-                                        send_status_update(
-                                            &status_tx_recv,
-                                            StatusUpdate::ClutchOfferReceived {
-                                                conversation_token,
-                                                offer_provenance,
-                                                sender_pubkey,
-                                                payload,
-                                                sender_addr: src_addr,
-                                            },
-                                            &event_proxy_recv,
-                                        );
-                                    }
-                                    // Try to parse as ClutchKemResponse
-                                    else if let Ok((
-                                        payload,
+                                        offer_provenance,
                                         sender_pubkey,
-                                        ceremony_id,
-                                        conversation_token,
-                                    )) =
-                                        parse_clutch_kem_response_vsf_without_recipient_check(&data)
-                                    {
-                                        // Defense-in-depth: verify sender again
-                                        if !is_known_sender_pt(&sender_pubkey) {
-                                            crate::logf!("PT: ClutchKemResponse REJECTED (defense-in-depth) - pubkey: {}", hex::encode(&sender_pubkey[..8]));
-                                            continue;
-                                        }
-                                        crate::log(
-                                            "PT: Parsed as ClutchKemResponse (VSF verified)",
-                                        );
-                                        send_status_update(
-                                            &status_tx_recv,
-                                            StatusUpdate::ClutchKemResponseReceived {
-                                                conversation_token,
-                                                ceremony_id,
-                                                sender_pubkey,
-                                                payload,
-                                                sender_addr: src_addr,
-                                            },
-                                            &event_proxy_recv,
-                                        );
-                                    }
-                                    // Try to parse as ClutchComplete
-                                    else if let Ok((
                                         payload,
-                                        sender_pubkey,
+                                        sender_addr: src_addr,
+                                    },
+                                    &event_proxy_recv,
+                                );
+                            }
+                            // Try to parse as ClutchKemResponse
+                            else if let Ok((
+                                payload,
+                                sender_pubkey,
+                                ceremony_id,
+                                conversation_token,
+                            )) =
+                                parse_clutch_kem_response_vsf_without_recipient_check(&data)
+                            {
+                                // Defense-in-depth: verify sender again
+                                if !is_known_sender_pt(&sender_pubkey) {
+                                    crate::logf!("PT: ClutchKemResponse REJECTED (defense-in-depth) - pubkey: {}", hex::encode(&sender_pubkey[..8]));
+                                    continue;
+                                }
+                                crate::log(
+                                    "PT: Parsed as ClutchKemResponse (VSF verified)",
+                                );
+                                send_status_update(
+                                    &status_tx_recv,
+                                    StatusUpdate::ClutchKemResponseReceived {
+                                        conversation_token,
                                         ceremony_id,
+                                        sender_pubkey,
+                                        payload,
+                                        sender_addr: src_addr,
+                                    },
+                                    &event_proxy_recv,
+                                );
+                            }
+                            // Try to parse as ClutchComplete
+                            else if let Ok((
+                                payload,
+                                sender_pubkey,
+                                ceremony_id,
+                                conversation_token,
+                            )) =
+                                parse_clutch_complete_vsf_without_recipient_check(&data)
+                            {
+                                // Defense-in-depth: verify sender again
+                                if !is_known_sender_pt(&sender_pubkey) {
+                                    crate::logf!("PT: ClutchComplete REJECTED (defense-in-depth) - pubkey: {}", hex::encode(&sender_pubkey[..8]));
+                                    continue;
+                                }
+                                crate::log("PT: Parsed as ClutchComplete (VSF verified)");
+                                send_status_update(
+                                    &status_tx_recv,
+                                    StatusUpdate::ClutchCompleteReceived {
                                         conversation_token,
-                                    )) =
-                                        parse_clutch_complete_vsf_without_recipient_check(&data)
-                                    {
-                                        // Defense-in-depth: verify sender again
-                                        if !is_known_sender_pt(&sender_pubkey) {
-                                            crate::logf!("PT: ClutchComplete REJECTED (defense-in-depth) - pubkey: {}", hex::encode(&sender_pubkey[..8]));
-                                            continue;
-                                        }
-                                        crate::log("PT: Parsed as ClutchComplete (VSF verified)");
-                                        send_status_update(
-                                            &status_tx_recv,
-                                            StatusUpdate::ClutchCompleteReceived {
-                                                conversation_token,
-                                                ceremony_id,
-                                                sender_pubkey,
-                                                payload,
-                                                sender_addr: src_addr,
-                                            },
-                                            &event_proxy_recv,
-                                        );
-                                    }
-                                    // Try to parse as history request (hist_req)
-                                    else if let Ok((payload, sender_pubkey)) =
-                                        crate::network::fgtw::protocol::parse_history_request_vsf(
-                                            &data,
-                                        )
-                                    {
-                                        if !is_known_sender_pt(&sender_pubkey) {
-                                            crate::log("PT: hist_req REJECTED - unknown sender");
-                                            continue;
-                                        }
-                                        send_status_update(
-                                            &status_tx_recv,
-                                            StatusUpdate::HistoryRequestReceived {
-                                                conversation_token: payload.conversation_token,
-                                                before_osc: payload.before_osc,
-                                                limit: payload.limit,
-                                                request_id: payload.request_id,
-                                                sent_osc: payload.sent_osc,
-                                                sender_pubkey: DevicePubkey::from_bytes(
-                                                    sender_pubkey,
-                                                ),
-                                                sender_addr: src_addr,
-                                            },
-                                            &event_proxy_recv,
-                                        );
-                                    }
-                                    // Try to parse as history page (hist_page)
-                                    else if let Ok((
-                                        (conversation_token, request_id, sealed),
+                                        ceremony_id,
                                         sender_pubkey,
-                                    )) = crate::network::fgtw::protocol::parse_history_page_vsf(
-                                        &data,
-                                    ) {
-                                        if !is_known_sender_pt(&sender_pubkey) {
-                                            crate::log("PT: hist_page REJECTED - unknown sender");
-                                            continue;
-                                        }
-                                        send_status_update(
-                                            &status_tx_recv,
-                                            StatusUpdate::HistoryPageReceived {
-                                                conversation_token,
-                                                request_id,
-                                                sealed,
-                                                sender_pubkey: DevicePubkey::from_bytes(
-                                                    sender_pubkey,
-                                                ),
-                                                sender_addr: src_addr,
-                                            },
-                                            &event_proxy_recv,
-                                        );
-                                    }
-                                    // Try to parse as a blind frame (blind_put/ack/get/srv — tiny, but PT delivery is possible under fallback routing)
-                                    else if let Some((kind, payload, sender_pubkey)) =
-                                        crate::network::fgtw::protocol::parse_any_blind_frame(
-                                            &data,
-                                        )
-                                    {
-                                        if !is_known_sender_pt(&sender_pubkey) {
-                                            crate::log("PT: blind frame REJECTED - unknown sender");
-                                            continue;
-                                        }
-                                        send_status_update(
-                                            &status_tx_recv,
-                                            StatusUpdate::BlindFrameReceived {
-                                                kind,
-                                                conversation_token: payload.conversation_token,
-                                                request_id: payload.request_id,
-                                                blob: payload.blob,
-                                                found: payload.found,
-                                                sent_osc: payload.sent_osc,
-                                                sender_pubkey: DevicePubkey::from_bytes(
-                                                    sender_pubkey,
-                                                ),
-                                                sender_addr: src_addr,
-                                            },
-                                            &event_proxy_recv,
-                                        );
-                                    } else if let Ok(crate::network::fgtw::protocol::FgtwMessage::AvatarResponse {
-                                        timestamp: _,
-                                        responder_pubkey,
-                                        provenance_hash,
-                                        signature,
-                                        avatar_vsf,
-                                    }) = crate::network::fgtw::protocol::FgtwMessage::from_vsf_bytes(&data)
-                                    {
-                                        // A P2P avatar answer big enough to ride PT (typical: ~24KB AV1) — same verify + emit as the UDP arm. This was the "PT: Received unknown 23.9KB" drop: the PT completion chain knew clutch/hist/blind but not av_resp, so large avatars silently fell thru to the FGTW fallback.
-                                        let provenance: [u8; 32] = blake3::hash(&avatar_vsf).into();
-                                        if provenance == provenance_hash
-                                            && verify_provenance_signature(&provenance_hash, &responder_pubkey, &signature)
-                                        {
-                                            crate::logf!("PT: avatar response reassembled ({} bytes)", avatar_vsf.len());
-                                            send_status_update(
-                                                &status_tx_recv,
-                                                StatusUpdate::AvatarReceived {
-                                                    responder_pubkey,
-                                                    avatar_vsf,
-                                                    sender_addr: src_addr,
-                                                },
-                                                &event_proxy_recv,
-                                            );
-                                        } else {
-                                            crate::log("PT: avatar response REJECTED (bad signature)");
-                                        }
-                                    } else {
-                                        // Unknown PT data - emit generic event for debugging
-                                        crate::logf!("PT: Failed to parse {} bytes as CLUTCH message", data.len());
-                                        send_status_update(
-                                            &status_tx_recv,
-                                            StatusUpdate::PTReceived {
-                                                peer_addr: src_addr,
-                                                data,
-                                            },
-                                            &event_proxy_recv,
-                                        );
-                                    }
+                                        payload,
+                                        sender_addr: src_addr,
+                                    },
+                                    &event_proxy_recv,
+                                );
+                            }
+                            // Try to parse as history request (hist_req)
+                            else if let Ok((payload, sender_pubkey)) =
+                                crate::network::fgtw::protocol::parse_history_request_vsf(
+                                    &data,
+                                )
+                            {
+                                if !is_known_sender_pt(&sender_pubkey) {
+                                    crate::log("PT: hist_req REJECTED - unknown sender");
+                                    continue;
                                 }
+                                send_status_update(
+                                    &status_tx_recv,
+                                    StatusUpdate::HistoryRequestReceived {
+                                        conversation_token: payload.conversation_token,
+                                        before_osc: payload.before_osc,
+                                        limit: payload.limit,
+                                        request_id: payload.request_id,
+                                        sent_osc: payload.sent_osc,
+                                        sender_pubkey: DevicePubkey::from_bytes(
+                                            sender_pubkey,
+                                        ),
+                                        sender_addr: src_addr,
+                                    },
+                                    &event_proxy_recv,
+                                );
+                            }
+                            // Try to parse as history page (hist_page)
+                            else if let Ok((
+                                (conversation_token, request_id, sealed),
+                                sender_pubkey,
+                            )) = crate::network::fgtw::protocol::parse_history_page_vsf(
+                                &data,
+                            ) {
+                                if !is_known_sender_pt(&sender_pubkey) {
+                                    crate::log("PT: hist_page REJECTED - unknown sender");
+                                    continue;
+                                }
+                                send_status_update(
+                                    &status_tx_recv,
+                                    StatusUpdate::HistoryPageReceived {
+                                        conversation_token,
+                                        request_id,
+                                        sealed,
+                                        sender_pubkey: DevicePubkey::from_bytes(
+                                            sender_pubkey,
+                                        ),
+                                        sender_addr: src_addr,
+                                    },
+                                    &event_proxy_recv,
+                                );
+                            }
+                            // Try to parse as a blind frame (blind_put/ack/get/srv — tiny, but PT delivery is possible under fallback routing)
+                            else if let Some((kind, payload, sender_pubkey)) =
+                                crate::network::fgtw::protocol::parse_any_blind_frame(
+                                    &data,
+                                )
+                            {
+                                if !is_known_sender_pt(&sender_pubkey) {
+                                    crate::log("PT: blind frame REJECTED - unknown sender");
+                                    continue;
+                                }
+                                send_status_update(
+                                    &status_tx_recv,
+                                    StatusUpdate::BlindFrameReceived {
+                                        kind,
+                                        conversation_token: payload.conversation_token,
+                                        request_id: payload.request_id,
+                                        blob: payload.blob,
+                                        found: payload.found,
+                                        sent_osc: payload.sent_osc,
+                                        sender_pubkey: DevicePubkey::from_bytes(
+                                            sender_pubkey,
+                                        ),
+                                        sender_addr: src_addr,
+                                    },
+                                    &event_proxy_recv,
+                                );
+                            } else if let Ok(crate::network::fgtw::protocol::FgtwMessage::AvatarResponse {
+                                timestamp: _,
+                                responder_pubkey,
+                                provenance_hash,
+                                signature,
+                                avatar_vsf,
+                            }) = crate::network::fgtw::protocol::FgtwMessage::from_vsf_bytes(&data)
+                            {
+                                // A P2P avatar answer big enough to ride PT (typical: ~24KB AV1) — same verify + emit as the UDP arm. This was the "PT: Received unknown 23.9KB" drop: the PT completion chain knew clutch/hist/blind but not av_resp, so large avatars silently fell thru to the FGTW fallback.
+                                let provenance: [u8; 32] = blake3::hash(&avatar_vsf).into();
+                                if crate::crypto::ct::eq(&provenance, &provenance_hash)
+                                    && verify_from(&sig_failures_recv, src_addr, &provenance_hash, &responder_pubkey, &signature)
+                                {
+                                    crate::logf!("PT: avatar response reassembled ({} bytes)", avatar_vsf.len());
+                                    send_status_update(
+                                        &status_tx_recv,
+                                        StatusUpdate::AvatarReceived {
+                                            responder_pubkey,
+                                            avatar_vsf,
+                                            sender_addr: src_addr,
+                                        },
+                                        &event_proxy_recv,
+                                    );
+                                } else {
+                                    crate::log("PT: avatar response REJECTED (bad signature)");
+                                }
+                            } else {
+                                // Unknown PT data - emit generic event for debugging
+                                crate::logf!("PT: Failed to parse {} bytes as CLUTCH message", data.len());
+                                send_status_update(
+                                    &status_tx_recv,
+                                    StatusUpdate::PTReceived {
+                                        peer_addr: src_addr,
+                                        data,
+                                    },
+                                    &event_proxy_recv,
+                                );
                             }
                         }
                         continue;
                     }
 
-                    // Centralized UDP RX logging - THE ONLY place incoming packets are logged
-                    #[cfg(feature = "development")]
-                    udp::log_received(msg_bytes, &src_addr);
-
                     // Handle LAN discovery packets (same port as main socket now)
                     if let Some(lan_update) = parse_lan_discovery(msg_bytes, src_addr, &our_device_pk) {
                         send_status_update(&status_tx_recv, lan_update, &event_proxy_recv);
                         continue;
                     }
 
-                    // Try to parse as PT VSF packets (SPEC, ACK, NAK, CONTROL, COMPLETE)
-                    if let Some(pt_handled) = handle_pt_vsf_packet(
-                        msg_bytes,
-                        src_addr,
-                        &pt_recv,
-                        &socket_recv,
-                        &status_tx_recv,
-                        &event_proxy_recv,
-                        &contacts_recv,
-                    )
-                    .await
-                    {
-                        if pt_handled {
-                            continue;
-                        }
-                    }
-
                     // Try to parse small direct UDP VSF messages (ClutchComplete, etc.) These are sent directly without PT overhead for efficiency
                     if msg_bytes.len() >= 4
                         && &msg_bytes[0..3] == b"R\xC3\x85"
@@ -1687,11 +2017,15 @@ async fn run_checker(
                             }
                             // ClutchOffer (~548KB) and ClutchKemResponse (~32KB) arriving as a WHOLE frame — this is the RELAY-INJECTED path.
                             // Direct sends shard these through PT and parse them in the PT-transfer-complete branch above, but a relayed message is injected as one datagram tagged RELAY_ADDR, so it never touches PT and only clutch_complete was parsed here — the offer + KEM were silently dropped, so the ceremony never got past the offer over the relay (presence worked, but no KEM ever came back).
-                            // Parse them here too. The parsers verify the signature internally; the app's CLUTCH handler gates action on fold-respecting knows_device.
+                            // Parse them here too. The parsers verify the signature internally; admit_offer drops a sender outside the contacts set before the replay guard, and the app's CLUTCH handler still gates action on fold-respecting knows_device.
                             // No packet-ack: unlike a PT-carried frame, a relayed one isn't in a stop-and-wait queue awaiting one.
                             if let Ok((payload, sender_pubkey, offer_provenance, conversation_token)) =
                                 crate::network::fgtw::protocol::parse_clutch_offer_vsf_without_recipient_check(msg_bytes)
                             {
+                                if let Err(why) = admit_offer(&replay_recv, &contacts_recv, &sender_pubkey, msg_bytes) {
+                                    crate::logf!("RELAY-INJECT: ClutchOffer REJECTED - {} (pubkey: {})", why, hex::encode(&sender_pubkey[..8]));
+                                    continue;
+                                }
                                 crate::log("RELAY-INJECT: Received ClutchOffer (VSF verified)");
                                 send_status_update(
                                     &status_tx_recv,
@@ -1849,7 +2183,7 @@ async fn run_checker(
                             }
                             match message {
                                 FgtwMessage::StatusPing {
-                                    timestamp: _,
+                                    timestamp,
                                     sender_pubkey,
                                     provenance_hash,
                                     signature,
@@ -1864,7 +2198,9 @@ async fn run_checker(
                                     }
 
                                     // Verify signature
-                                    if !verify_provenance_signature(
+                                    if !verify_from(
+                                        &sig_failures_recv,
+                                        src_addr,
                                         &provenance_hash,
                                         &sender_pubkey,
                                         &signature,
@@ -1872,6 +2208,19 @@ async fn run_checker(
                                         continue;
                                     }
 
+                                    // Replay gate: a captured ping verifies forever, so its signed timestamp must be fresh and this exact ping unseen on this path
+                                    if let Err(why) = admit_ping(
+                                        &mut replay_recv.lock().unwrap(),
+                                        &sender_pubkey,
+                                        timestamp,
+                                        &provenance_hash,
+                                        src_addr == RELAY_ADDR,
+                                        eagle_time_now(),
+                                    ) {
+                                        crate::logf!("Status: PING from {} REJECTED - {}", hex::encode(&sender_pubkey.as_bytes()[..8]), why);
+                                        continue;
+                                    }
+
                                     // Reset failure counter - they're clearly online if they're pinging us
                                     {
                                         let mut failures = failed_pings_recv.lock().unwrap();
@@ -1888,6 +2237,8 @@ async fn run_checker(
                                             sync_records: vec![],
                                             display_name: None,
                                             avatar_pin: None,
+                                            away: None,
+                                            rtt: None,
                                         },
                                         &event_proxy_recv,
                                     );
@@ -1913,6 +2264,7 @@ async fn run_checker(
                                         observed_addr: Some(udp::canon_socketaddr(src_addr)),
                                         display_name: profile_name(),
                                         avatar_pin: avatar_pin(),
+                                        away: away(),
                                     };
 
                                     let pong_bytes = pong.to_vsf_bytes();
@@ -1939,13 +2291,14 @@ async fn run_checker(
                                     observed_addr,
                                     display_name,
                                     avatar_pin,
+                                    away,
                                 } => {
                                     // Find and remove matching pending ping
                                     let pending_ping = {
                                         let mut list = pending_recv.lock().unwrap();
                                         if let Some(idx) = list
                                             .iter()
-                                            .position(|p| p.provenance_hash == provenance_hash)
+                                            .position(|p| crate::crypto::ct::eq(&p.provenance_hash, &provenance_hash))
                                         {
                                             Some(list.swap_remove(idx))
                                         } else {
@@ -1969,7 +2322,9 @@ async fn run_checker(
                                     }
 
                                     // Verify signature
-                                    if !verify_provenance_signature(
+                                    if !verify_from(
+                                        &sig_failures_recv,
+                                        src_addr,
                                         &provenance_hash,
                                         &responder_pubkey,
                                         &signature,
@@ -2012,6 +2367,8 @@ async fn run_checker(
                                             sync_records,
                                             display_name,
                                             avatar_pin,
+                                            away: Some(away),
+                                            rtt: Some(pending_ping.sent_at.elapsed()),
                                         },
                                         &event_proxy_recv,
                                     );
@@ -2029,7 +2386,9 @@ async fn run_checker(
                                     // Verify signature (CHAIN format provenance)
                                     let provenance =
                                         compute_chat_provenance(&conversation_token, &prev_msg_hp);
-                                    if !verify_provenance_signature(
+                                    if !verify_from(
+                                        &sig_failures_recv,
+                                        src_addr,
                                         &provenance,
                                         &sender_pubkey,
                                         &signature,
@@ -2067,6 +2426,8 @@ async fn run_checker(
                                             sync_records: vec![],
                                             display_name: None,
                                             avatar_pin: None,
+                                            away: Some(false),
+                                            rtt: None,
                                         },
                                         &event_proxy_recv,
                                     );
@@ -2103,7 +2464,9 @@ async fn run_checker(
                                         acked_eagle_time,
                                         &plaintext_hash,
                                     );
-                                    if !verify_provenance_signature(
+                                    if !verify_from(
+                                        &sig_failures_recv,
+                                        src_addr,
                                         &provenance,
                                         &sender_pubkey,
                                         &signature,
@@ -2140,8 +2503,10 @@ async fn run_checker(
                                         .concat(),
                                     )
                                     .into();
-                                    if provenance != provenance_hash
-                                        || !verify_provenance_signature(
+                                    if !crate::crypto::ct::eq(&provenance, &provenance_hash)
+                                        || !verify_from(
+                                            &sig_failures_recv,
+                                            src_addr,
                                             &provenance_hash,
                                             &sender_pubkey,
                                             &signature,
@@ -2172,8 +2537,10 @@ async fn run_checker(
 
                                     // Verify provenance is the avatar bytes' hash, then the signature
                                     let provenance: [u8; 32] = blake3::hash(&avatar_vsf).into();
-                                    if provenance != provenance_hash
-                                        || !verify_provenance_signature(
+                                    if !crate::crypto::ct::eq(&provenance, &provenance_hash)
+                                        || !verify_from(
+                                            &sig_failures_recv,
+                                            src_addr,
                                             &provenance_hash,
                                             &responder_pubkey,
                                             &signature,
@@ -2201,7 +2568,9 @@ async fn run_checker(
                                     signature,
                                 } => {
                                     // Open-tier STUN: answer ANY signed node (not just contacts) with the source address we observed the request arrive from — this is what lets peers reflect for one another so nobody needs a central STUN server. Reveals only the requester's own address, so it's safe to serve openly; the P4 "serve directory" toggle will gate it (default on).
-                                    if !verify_provenance_signature(
+                                    if !verify_from(
+                                        &sig_failures_recv,
+                                        src_addr,
                                         &provenance_hash,
                                         &sender_pubkey,
                                         &signature,
@@ -2232,13 +2601,28 @@ async fn run_checker(
                                     observed_addr,
                                 } => {
                                     // Open-tier reflexive answer. Verify it's a signed reply, then feed the quorum buffer as UNtrusted — a stranger's claim about our address needs corroboration from a second source before we adopt and re-publish it (anti-poison). A contact's echo arrives via the trusted pong path instead.
-                                    if !verify_provenance_signature(
+                                    if !verify_from(
+                                        &sig_failures_recv,
+                                        src_addr,
                                         &provenance_hash,
                                         &responder_pubkey,
                                         &signature,
                                     ) {
                                         continue;
                                     }
+                                    // The same answer may be one half of a NAT-type probe — matched by the reflector's source address, which is exactly what the comparison needs.
+                                    let nat = nat_probe_recv.lock().unwrap().record(
+                                        udp::canon_socketaddr(src_addr),
+                                        udp::canon_socketaddr(observed_addr),
+                                    );
+                                    if let Some(nat) = nat {
+                                        crate::logf!("TRAVERSE: NAT type = {}", nat.label());
+                                        send_status_update(
+                                            &status_tx_recv,
+                                            StatusUpdate::NatClassified { nat },
+                                            &event_proxy_recv,
+                                        );
+                                    }
                                     if let Some(addr) = reflexive.record(
                                         udp::canon_socketaddr(observed_addr),
                                         *responder_pubkey.as_bytes(),
@@ -2271,7 +2655,9 @@ async fn run_checker(
                                     if !is_contact {
                                         continue;
                                     }
-                                    if !verify_provenance_signature(
+                                    if !verify_from(
+                                        &sig_failures_recv,
+                                        src_addr,
                                         &provenance_hash,
                                         &sender_pubkey,
                                         &signature,
@@ -2304,7 +2690,9 @@ async fn run_checker(
                                     if !is_contact {
                                         continue;
                                     }
-                                    if !verify_provenance_signature(
+                                    if !verify_from(
+                                        &sig_failures_recv,
+                                        src_addr,
                                         &provenance_hash,
                                         &responder_pubkey,
                                         &signature,
@@ -2354,7 +2742,9 @@ async fn run_checker(
                                     if !is_contact {
                                         continue;
                                     }
-                                    if !verify_provenance_signature(
+                                    if !verify_from(
+                                        &sig_failures_recv,
+                                        src_addr,
                                         &provenance_hash,
                                         &sender_pubkey,
                                         &signature,
@@ -2527,6 +2917,44 @@ async fn run_checker(
             }
         }
 
+        // NAT-type probe: `Reflect` from the data socket to two directory nodes at different IPs. Best-effort like the phonebook ask — a lost answer just leaves the type unknown until the next probe.
+        while nat_probe_rx.try_recv().is_ok() {
+            let candidates: Vec<SocketAddr> = peer_store
+                .lock()
+                .unwrap()
+                .get_all_peers()
+                .iter()
+                .filter(|p| p.device_pubkey != our_pubkey)
+                .map(|p| udp::canon_socketaddr(p.ip))
+                .collect();
+            let Some(reflectors) = crate::network::traverse::nat::pick_reflectors(&candidates) else {
+                crate::logf!("TRAVERSE: NAT probe skipped — fewer than two reflectors at distinct IPs ({} peers known)", candidates.len());
+                continue;
+            };
+            let local = std_socket
+                .local_addr()
+                .ok()
+                .map(|a| SocketAddr::new(std::net::IpAddr::V4(local_ip), a.port()));
+            nat_probe.lock().unwrap().start(reflectors, local);
+            for addr in reflectors {
+                let ts = eagle_time_now();
+                let prov = compute_provenance_hash(&our_pubkey, ts);
+                let sig = keypair.sign(&prov);
+                let mut sig_bytes = [0u8; 64];
+                sig_bytes.copy_from_slice(&sig.to_bytes());
+                let req = FgtwMessage::Reflect {
+                    timestamp: ts,
+                    sender_pubkey: our_pubkey.clone(),
+                    provenance_hash: prov,
+                    signature: sig_bytes,
+                };
+                let bytes = req.to_vsf_bytes();
+                if !bytes.is_empty() {
+                    udp::send(&socket, &bytes, addr).await;
+                }
+            }
+        }
+
         // Drop hole-punch probes that never round-tripped (unreachable candidate / symmetric NAT), so pending_probes doesn't grow unbounded across ping cycles.
         {
             pending_probes.lock().unwrap().expire(Instant::now());
@@ -2572,6 +3000,8 @@ async fn run_checker(
                             sync_records: vec![], // No sync for offline
                             display_name: None,
                             avatar_pin: None,
+                            away: None,
+                            rtt: None,
                         },
                         &event_proxy,
                     );
@@ -2947,21 +3377,11 @@ async fn run_checker(
 
         // Process LAN discovery requests via multicast (more reliable than broadcast)
         while let Ok(request) = lan_broadcast_rx.try_recv() {
-            let packet = udp::build_lan_discovery(request.our_handle_proof, request.our_port, our_device_pk);
+            // Blinded: only contacts holding our handle_proof can tell who's beaconing (see network/lan.rs)
+            let packet = crate::network::lan::build(&request.our_handle_proof, &our_device_pk, request.our_port);
 
-            // IPv4 multicast: 239.104.199.144 (from random entropy 0x68C790)
-            let mcast_v4 = SocketAddr::new(
-                std::net::IpAddr::V4(Ipv4Addr::new(239, 104, 199, 144)),
-                crate::MULTICAST_PORT,
-            );
-
-            // IPv6 multicast: ff02::68c7:9014 (link-local scope with our random bytes)
-            let mcast_v6 = SocketAddr::new(
-                std::net::IpAddr::V6(std::net::Ipv6Addr::new(
-                    0xff02, 0, 0, 0, 0, 0, 0x68c7, 0x9014,
-                )),
-                crate::MULTICAST_PORT,
-            );
+            let mcast_v4 = SocketAddr::new(std::net::IpAddr::V4(crate::network::lan::MULTICAST_V4), crate::MULTICAST_PORT);
+            let mcast_v6 = SocketAddr::new(std::net::IpAddr::V6(crate::network::lan::MULTICAST_V6), crate::MULTICAST_PORT);
 
             // Send to IPv4 multicast
             if let Ok(mcast_sock) = UdpSocket::bind("0.0.0.0:0") {
@@ -2994,11 +3414,47 @@ async fn run_checker(
             pt_mgr.clear_outbound(&request.peer_addr);
         }
 
+        // Transfer policy: the size cap, the auto-download limit, and the user's answers for held transfers (SPEC ACK resumes the sender, Refuse drops it).
+        while let Ok(request) = transfer_policy_rx.try_recv() {
+            let reply = {
+                let mut pt_mgr = pt.lock().unwrap();
+                match request {
+                    TransferPolicyRequest::Limit(limit) => {
+                        pt_mgr.set_auto_accept_limit(limit);
+                        None
+                    }
+                    TransferPolicyRequest::MaxSize(bytes) => {
+                        pt_mgr.set_max_transfer_size(bytes);
+                        None
+                    }
+                    TransferPolicyRequest::Accept { peer_addr, stream_id } => pt_mgr.accept_spec(peer_addr, stream_id).map(|b| (peer_addr, b)),
+                    TransferPolicyRequest::Decline { peer_addr, stream_id } => pt_mgr.decline_spec(peer_addr, stream_id).map(|b| (peer_addr, b)),
+                }
+            };
+            if let Some((peer_addr, bytes)) = reply {
+                udp::send(&socket, &bytes, peer_addr).await;
+            }
+        }
+
+        // App datagrams from the embedder: signed for their recipient and queued behind anything else in flight to that peer.
+        while let Ok(request) = app_datagram_rx.try_recv() {
+            let sent = pt.lock().unwrap().send_app_datagram(request.peer_addr, request.recipient_pubkey, &request.payload, request.id);
+            match sent {
+                Ok(bytes) if !bytes.is_empty() => {
+                    udp::send(&socket, &bytes, request.peer_addr).await;
+                }
+                Ok(_) => {}
+                Err(e) => crate::logf!("PT: app datagram refused: {}", e),
+            }
+        }
+
         // PT periodic tick - handles timeouts, retries, TCP+relay fallback
         {
             let mut pt_mgr = pt.lock().unwrap();
             let to_send = pt_mgr.tick();
             let keypair_for_relay = pt_mgr.keypair().clone();
+            #[cfg(feature = "metrics")]
+            crate::network::fgtw::metrics::publish(pt_mgr.active_transfers(), peer_store.lock().unwrap().peer_count());
             drop(pt_mgr);
 
             for tick in to_send {
@@ -3006,17 +3462,20 @@ async fn run_checker(
                 udp::send(&socket, &tick.wire_bytes, tick.peer_addr).await;
 
                 // TCP fallback: send the WHOLE VSF payload once (set by PT tick after the UDP SPEC went ~1s unacked). Not the PT shard — TCP is reliable + ordered and the VSF `l` field self-frames the length, so the receiver's tcp::recv reads it whole and the existing CLUTCH dispatch parses it directly.
-                if let Some(tcp_payload) = &tick.tcp_payload {
-                    if let Err(e) = crate::network::tcp::send_tcp(tcp_payload, tick.peer_addr).await
-                    {
-                        crate::logf!("PT: TCP send failed to {}: {}", tick.peer_addr, e);
+                // Raced over every candidate address (primary first, alternate a stagger behind) — first to connect carries it.
+                if let Some(fallback) = &tick.tcp_fallback {
+                    match crate::network::tcp::send_tcp(&fallback.payload, &fallback.addrs).await {
+                        Ok(addr) if addr != tick.peer_addr => crate::logf!("PT: TCP fallback to {} went via {}", tick.peer_addr, addr),
+                        Ok(_) => {}
+                        Err(e) => crate::logf!("PT: TCP send failed to {}: {}", tick.peer_addr, e),
                     }
                 }
 
                 // If both UDP and TCP exhausted, try relay via /conduit
                 if let Some(relay_info) = tick.relay {
                     crate::logf!("PT: Relaying to {} via /conduit", hex::encode(&relay_info.recipient_pubkey[..4]));
-                    match crate::network::fgtw::relay::send_via_relay(
+                    // Receipted: the relay is the path this transfer actually rides now, so the recipient's receipt is what completes it.
+                    match crate::network::fgtw::relay::send_via_relay_receipted(
                         &keypair_for_relay,
                         &relay_info.recipient_pubkey,
                         &relay_info.payload,
@@ -3053,6 +3512,36 @@ fn verify_provenance_signature(
     verifying_key.verify(provenance_hash, &sig).is_ok()
 }
 
+/// Gate a signed ping before it's answered: its provenance must be the one its sender and timestamp derive — so the signature covers the timestamp too — and the replay guard must not have seen it on this path.
+fn admit_ping(
+    guard: &mut ReplayGuard,
+    sender_pubkey: &DevicePubkey,
+    timestamp: i64,
+    provenance_hash: &[u8; 32],
+    via_relay: bool,
+    now: i64,
+) -> Result<(), &'static str> {
+    if !crate::crypto::ct::eq(compute_provenance_hash(sender_pubkey, timestamp), provenance_hash) {
+        return Err("provenance doesn't match timestamp");
+    }
+    guard.check_ping(sender_pubkey.as_bytes(), timestamp, provenance_hash, via_relay, now)
+}
+
+/// Gate a verified CLUTCH offer: its signer must be a device we answer for (the contacts set, which mirrors `Contact::knows_device`) BEFORE the replay guard sees it — a stranger's offers must not take guard slots from contacts — then its signed pinned send-time goes thru ReplayGuard::check_offer. A send-time outside the window is logged on its own with the offset, since an honest peer with a wrong clock fails here too.
+fn admit_offer(guard: &Mutex<ReplayGuard>, contacts: &ContactPubkeys, sender_pubkey: &[u8; 32], vsf_bytes: &[u8]) -> Result<(), &'static str> {
+    let sender = DevicePubkey::from_bytes(*sender_pubkey);
+    if !contacts.lock().unwrap().iter().any(|p| *p == sender) {
+        return Err("sender not in contacts");
+    }
+    let send_time = crate::network::fgtw::protocol::clutch_offer_send_time(vsf_bytes).map_err(|_| "no send-time")?;
+    let now = eagle_time_now();
+    let verdict = guard.lock().unwrap().check_offer(sender_pubkey, send_time, now);
+    if let Err(why @ ("stale send-time" | "send-time in the future")) = verdict {
+        crate::logf!("CLUTCH: offer from {} {} — its clock is {}s off ours (clock skew, or a replay)", hex::encode(&sender_pubkey[..8]), why, (send_time - now) / crate::OSC_PER_SEC);
+    }
+    verdict
+}
+
 // NOTE: compute_clutch_provenance and compute_clutch_complete_provenance REMOVED They were only used by the legacy v1 ClutchOffer/ClutchInit/ClutchResponse/ClutchComplete Full 8-primitive CLUTCH uses different provenance via build_clutch_offer_vsf()
 
 /// Compute provenance hash for encrypted chat message (CHAIN format) provenance = BLAKE3(conversation_token || prev_msg_hp)
@@ -3078,121 +3567,6 @@ fn compute_ack_provenance_v2(
     hasher.update(b"ack");
     *hasher.finalize().as_bytes()
 }
-/// Handle PT VSF packets (SPEC, ACK, NAK, CONTROL, COMPLETE) Returns Some(true) if packet was handled, Some(false) if not a PT packet, None on error
-///
-/// Security: SPEC packets are only accepted from known contacts (sender pubkey validated)
-async fn handle_pt_vsf_packet(
-    msg_bytes: &[u8],
-    src_addr: SocketAddr,
-    pt: &Arc<Mutex<PTManager>>,
-    socket: &Arc<tokio::net::UdpSocket>,
-    _status_tx: &Sender<StatusUpdate>,
-    _event_proxy: &OptionalEventProxy,
-    contacts: &ContactPubkeys,
-) -> Option<bool> {
-    // Try to parse as PT packet (supports both header-only and section formats)
-    let parsed = parse_pt_packet(msg_bytes)?;
-
-    match parsed {
-        // Header-only format (new compact format)
-        ParsedPtPacket::HeaderOnly {
-            name,
-            provenance_hash,
-            values,
-        } => {
-            match name.as_str() {
-                "pt_ack" => {
-                    if let Some(ack) = PTAck::from_vsf_header(provenance_hash, &values) {
-                        // Handle ACK - state transitions happen in handle_ack Completion check and cleanup handled by main loop via transfer_id
-                        let response_packets = {
-                            let mut pt_mgr = pt.lock().unwrap();
-                            pt_mgr.handle_ack(src_addr, ack)
-                        };
-                        for pkt in response_packets {
-                            udp::send(socket, &pkt, src_addr).await;
-                        }
-                        return Some(true);
-                    }
-                }
-                "pt_nak" => {
-                    if let Some(nak) = PTNak::from_vsf_header(&values) {
-                        // NOTE: NAK not logged individually - handled silently
-                        let response_packets = {
-                            let mut pt_mgr = pt.lock().unwrap();
-                            pt_mgr.handle_nak(src_addr, nak)
-                        };
-                        for pkt in response_packets {
-                            udp::send(socket, &pkt, src_addr).await;
-                        }
-                        return Some(true);
-                    }
-                }
-                "pt_ctrl" => {
-                    if let Some(control) = PTControl::from_vsf_header(&values) {
-                        // NOTE: CONTROL not logged - handled silently
-                        let mut pt_mgr = pt.lock().unwrap();
-                        pt_mgr.handle_control(src_addr, control);
-                        return Some(true);
-                    }
-                }
-                "pt_done" => {
-                    if let Some(complete) = PTComplete::from_vsf_header(provenance_hash, &values) {
-                        // Log completion (success or failure)
-                        if !complete.success {
-                            crate::logf!("PT: Transfer FAILED from {}", src_addr);
-                        }
-                        // Handle completion - state transitions happen in handle_complete Completion check and cleanup handled by main loop via transfer_id
-                        {
-                            let mut pt_mgr = pt.lock().unwrap();
-                            pt_mgr.handle_complete(src_addr, complete);
-                        }
-                        return Some(true);
-                    }
-                }
-                _ => {}
-            }
-        }
-
-        // Section format (SPEC uses full section, not header-only)
-        ParsedPtPacket::Section {
-            name,
-            fields,
-            sender_pubkey,
-        } => {
-            if name == "pt_spec" {
-                if let Some(spec) = PTSpec::from_vsf_fields(&fields) {
-                    // SECURITY: Validate sender before accepting any transfer Only accept SPEC from known contacts to prevent resource exhaustion
-                    let is_known_contact = match sender_pubkey {
-                        Some(pubkey_bytes) => {
-                            let sender = DevicePubkey::from_bytes(pubkey_bytes);
-                            let contact_list = contacts.lock().unwrap();
-                            contact_list.iter().any(|p| *p == sender)
-                        }
-                        None => false, // No pubkey = unsigned = reject
-                    };
-
-                    if !is_known_contact {
-                        crate::logf!("PT: SPEC REJECTED from {} - sender not in contacts (pubkey: {})", src_addr, sender_pubkey
-                                .map(|p| hex::encode(&p[..8]))
-                                .unwrap_or_else(|| "none".to_string()));
-                        // Silent drop - don't send ACK, don't accept transfer
-                        return Some(true);
-                    }
-
-                    crate::logf!("PT: SPEC accepted from {} - {} packets, {} bytes", src_addr, spec.total_packets, spec.total_size);
-                    let spec_ack = {
-                        let mut pt_mgr = pt.lock().unwrap();
-                        pt_mgr.handle_spec(src_addr, spec)
-                    };
-                    udp::send(socket, &spec_ack, src_addr).await;
-                    return Some(true);
-                }
-            }
-        }
-    }
-
-    Some(false)
-}
 
 /// Parse LAN discovery packet from main UDP socket Returns StatusUpdate::LanPeerDiscovered if valid, None otherwise
 fn parse_lan_discovery(
@@ -3200,107 +3574,16 @@ fn parse_lan_discovery(
     src_addr: SocketAddr,
     our_device_pubkey: &[u8; 32],
 ) -> Option<StatusUpdate> {
-    let (handle_proof, local_ip, port, beacon_device) = udp::parse_lan_discovery(packet, src_addr)?;
-    // Our own beacon loops back to us (multicast loopback + broadcast self-delivery). Pre-fleet that was harmless — our own handle_proof was never a contact — but the self-conversation makes our handle a contact, so accepting our own beacon overwrites that contact's LAN address with OUR OWN IP and every send boomerangs back to ourselves (observed: phone retransmitting to itself for 20+ minutes). The fleet shares one handle_proof, so self is detected by the beacon's device key; the source-IP test is only the fallback for pre-ke beacons, and it misses on multi-homed devices (Android wifi + cellular CLAT have different IPs and get_local_ip sees the cellular one).
-    match beacon_device {
-        Some(ke) if ke == *our_device_pubkey => return None,
-        None if udp::get_local_ip() == Some(local_ip) => return None,
-        _ => {}
-    }
-    crate::logf!("LAN: Received discovery from {} (handle_proof: {}..., port: {})", src_addr, hex::encode(&handle_proof[..4]), port);
-    Some(StatusUpdate::LanPeerDiscovered {
-        handle_proof,
-        local_ip,
-        port,
-    })
-}
-
-/// Parsed PT packet info - either from header inline field or section body
-enum ParsedPtPacket {
-    /// Header-only format: (pt_name:value1,value2,...) with provenance hash
-    HeaderOnly {
-        name: String,
-        provenance_hash: [u8; 32],
-        values: Vec<vsf::VsfType>,
-    },
-    /// Section format: [pt_name (field:value)...] with optional sender pubkey from signature
-    Section {
-        name: String,
-        fields: Vec<(String, vsf::VsfType)>,
-        /// Sender's Ed25519 public key from header signature (for authentication)
-        sender_pubkey: Option<[u8; 32]>,
-    },
-}
-
-/// Parse VSF PT packet - supports both header-only and section formats
-fn parse_pt_packet(bytes: &[u8]) -> Option<ParsedPtPacket> {
-    use vsf::file_format::VsfHeader;
-
-    let (header, header_end) = VsfHeader::decode(bytes).ok()?;
-
-    // Extract provenance hash from header
-    let provenance_hash = match &header.provenance_hash {
-        vsf::VsfType::hp(hash) if hash.len() == 32 => {
-            let mut arr = [0u8; 32];
-            arr.copy_from_slice(hash);
-            arr
-        }
-        _ => return None,
-    };
-
-    // Check for header-only format first (inline fields like pt_ack, pt_nak, pt_ctrl, pt_done) These have fields with values directly in the header, no section body
-    for field in &header.fields {
-        if field.name.starts_with("pt_") && field.offset_bytes == 0 && field.size_bytes == 0 {
-            // This is a header-only field with inline values We need to re-parse to get the actual values
-            if let Some(values) = parse_header_inline_values(bytes, &field.name) {
-                return Some(ParsedPtPacket::HeaderOnly {
-                    name: field.name.clone(),
-                    provenance_hash,
-                    values,
-                });
-            }
+    use crate::network::lan::BeaconId;
+    let crate::network::lan::LanBeacon { id, local_ip, port } = crate::network::lan::parse(packet, src_addr)?;
+    // Our own beacon loops back to us (multicast loopback + broadcast self-delivery). Pre-fleet that was harmless — our own handle_proof was never a contact — but the self-conversation makes our handle a contact, so accepting our own beacon overwrites that contact's LAN address with OUR OWN IP and every send boomerangs back to ourselves (observed: phone retransmitting to itself for 20+ minutes). A clear beacon names its device, so ours is dropped here; a blinded one can only be opened with a handle_proof, which this task doesn't hold — the app opens it against contacts only, never the self-contact or siblings, so our own looped-back beacon matches nothing there.
+    if let BeaconId::Clear { device, .. } = id {
+        if device == *our_device_pubkey {
+            return None;
         }
     }
-
-    // Extract sender pubkey from header signature (if present) This is the Ed25519 public key used to sign the packet
-    let sender_pubkey = match &header.signer_pubkey {
-        Some(vsf::VsfType::ke(key)) if key.len() == 32 => {
-            let mut arr = [0u8; 32];
-            arr.copy_from_slice(key);
-            Some(arr)
-        }
-        _ => None,
-    };
-
-    // Fall back to section body parsing — primary_section resolves the near-form name from the header TOC (the knowledge lives in the vsf crate now).
-    let section = header.primary_section(bytes, header_end).ok()?;
-    let section_name = section.name.clone();
-
-    let fields: Vec<(String, vsf::VsfType)> = section
-        .fields
-        .iter()
-        .filter_map(|f| f.values.first().map(|v| (f.name.clone(), v.clone())))
-        .collect();
-
-    Some(ParsedPtPacket::Section {
-        name: section_name,
-        fields,
-        sender_pubkey,
-    })
-}
-
-/// Parse inline values from a header field by name Returns the values for (name:val1,val2,...) format
-fn parse_header_inline_values(bytes: &[u8], target_name: &str) -> Option<Vec<vsf::VsfType>> {
-    use vsf::file_format::VsfHeader;
-
-    let (header, _) = VsfHeader::decode(bytes).ok()?;
-
-    // Find the target field in the header and return its inline values
-    header
-        .fields
-        .iter()
-        .find(|f| f.name == target_name)
-        .map(|f| f.inline_values.clone())
+    crate::logf!("LAN: Received discovery from {} (port: {})", src_addr, port);
+    Some(StatusUpdate::LanPeerDiscovered { id, local_ip, port })
 }
 
 /// Parse VSF fields from bytes (legacy section-only format) Parse a PT VSF packet, returns (section_name, fields)
@@ -3311,3 +3594,92 @@ fn parse_pt_vsf_fields(bytes: &[u8]) -> Option<(String, Vec<(String, vsf::VsfTyp
         ParsedPtPacket::HeaderOnly { .. } => None, // Can't convert header-only to named fields
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replayed_ping_is_rejected() {
+        let secret = ed25519_dalek::SigningKey::from_bytes(&[0x24; 32]);
+        let keypair = Keypair { public: (&secret).into(), secret };
+        let sender = DevicePubkey::from_bytes(*keypair.public.as_bytes());
+        let timestamp = eagle_time_now();
+        let provenance_hash = compute_provenance_hash(&sender, timestamp);
+        let ping = FgtwMessage::StatusPing {
+            timestamp,
+            sender_pubkey: sender.clone(),
+            provenance_hash,
+            signature: keypair.sign(&provenance_hash).to_bytes(),
+        };
+        let wire = ping.to_vsf_bytes();
+
+        // The receive path's checks, on the same captured bytes twice.
+        let mut guard = ReplayGuard::new();
+        let mut admit = |bytes: &[u8], via_relay: bool| match FgtwMessage::from_vsf_bytes(bytes) {
+            Ok(FgtwMessage::StatusPing { timestamp, sender_pubkey, provenance_hash, signature }) => {
+                assert!(verify_provenance_signature(&provenance_hash, &sender_pubkey, &signature));
+                admit_ping(&mut guard, &sender_pubkey, timestamp, &provenance_hash, via_relay, eagle_time_now())
+            }
+            _ => panic!("not a ping"),
+        };
+        assert_eq!(admit(&wire, false), Ok(()));
+        assert_eq!(admit(&wire, false), Err("replayed"));
+        // The sender's own relay copy is a second path, not a replay — once.
+        assert_eq!(admit(&wire, true), Ok(()));
+        assert_eq!(admit(&wire, true), Err("replayed"));
+
+        // A validly signed provenance re-stamped with another timestamp doesn't bind it.
+        let mut guard = ReplayGuard::new();
+        assert_eq!(
+            admit_ping(&mut guard, &sender, timestamp + 1, &provenance_hash, false, timestamp),
+            Err("provenance doesn't match timestamp")
+        );
+    }
+
+    #[test]
+    fn offers_from_strangers_never_reach_the_replay_guard() {
+        let guard = Mutex::new(ReplayGuard::new());
+        let contacts: ContactPubkeys = Arc::new(Mutex::new(vec![DevicePubkey::from_bytes([0x21; 32])]));
+        // The contact check comes first: a stranger is refused before its bytes are even read for a send-time.
+        assert_eq!(admit_offer(&guard, &contacts, &[0x99; 32], b"not an offer"), Err("sender not in contacts"));
+        // A contact gets as far as the send-time.
+        assert_eq!(admit_offer(&guard, &contacts, &[0x21; 32], b"not an offer"), Err("no send-time"));
+    }
+
+    #[test]
+    fn forged_signatures_get_the_source_dropped() {
+        let secret = ed25519_dalek::SigningKey::from_bytes(&[0x42; 32]);
+        let keypair = Keypair { public: (&secret).into(), secret };
+        let claimed = DevicePubkey::from_bytes([0x11; 32]);
+        let provenance_hash = compute_provenance_hash(&claimed, eagle_time_now());
+        // Signed by a key that isn't the one claimed.
+        let forged = keypair.sign(&provenance_hash).to_bytes();
+
+        let failures = Mutex::new(SigFailures::new());
+        let forger = SocketAddr::from(([203, 0, 113, 9], 4383));
+        for i in 0..SIG_FAIL_THRESHOLD {
+            assert!(!failures.lock().unwrap().blocked(forger, &claimed, Instant::now()), "blocked after {i}");
+            // Each try from a fresh port: still one source.
+            let from = SocketAddr::from(([203, 0, 113, 9], 5000 + i as u16));
+            assert!(!verify_from(&failures, from, &provenance_hash, &claimed, &forged));
+        }
+        let now = Instant::now();
+        assert!(failures.lock().unwrap().blocked(forger, &claimed, now));
+        assert!(!failures.lock().unwrap().blocked(SocketAddr::from(([198, 51, 100, 1], 4383)), &claimed, now));
+        assert!(!failures.lock().unwrap().blocked(forger, &claimed, now + SIG_BLOCK + Duration::from_secs(1)));
+
+        // Only the pair is dropped: an honest peer behind the same IP still verifies.
+        let honest_pubkey = DevicePubkey::from_bytes(keypair.public.to_bytes());
+        let honest_hash = compute_provenance_hash(&honest_pubkey, eagle_time_now());
+        let signed = keypair.sign(&honest_hash).to_bytes();
+        assert!(verify_from(&failures, forger, &honest_hash, &honest_pubkey, &signed));
+
+        // Forgeries thru the relay pipe count too, and block only the pubkey they claim there.
+        for _ in 0..SIG_FAIL_THRESHOLD {
+            assert!(!verify_from(&failures, RELAY_ADDR, &provenance_hash, &claimed, &forged));
+        }
+        assert!(failures.lock().unwrap().blocked(RELAY_ADDR, &claimed, Instant::now()));
+        assert!(verify_from(&failures, RELAY_ADDR, &honest_hash, &honest_pubkey, &signed));
+    }
+}
@@ -139,18 +139,63 @@ pub fn connect_and_send(addr: SocketAddr, data: &[u8]) -> std::io::Result<()> {
     Ok(())
 }
 
-/// Async TCP send for PT fallback (fully async, no blocking) No external framing - VSF header contains L (file length)
-pub async fn send_tcp(data: &[u8], addr: SocketAddr) -> std::io::Result<()> {
+/// Head start each connect in a race gets before the next candidate is tried too (RFC 8305's Connection Attempt Delay). Long enough that a healthy first choice usually wins alone, short enough that a dead one costs a quarter second, not a connect timeout.
+pub const CONNECT_STAGGER: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// Happy-eyeballs connect: try `addrs` in order (primary first), starting each next one when the previous has had [`CONNECT_STAGGER`] without connecting — or at once when it fails — and keep the first connection to succeed. Attempts still pending are dropped with the race, which cancels them. `Err` when every candidate fails or `within` runs out.
+pub async fn connect_race(
+    addrs: &[SocketAddr],
+    stagger: std::time::Duration,
+    within: std::time::Duration,
+) -> std::io::Result<(tokio::net::TcpStream, SocketAddr)> {
+    use futures::stream::{FuturesUnordered, StreamExt};
+
+    async fn attempt(addr: SocketAddr) -> std::io::Result<(tokio::net::TcpStream, SocketAddr)> {
+        tokio::net::TcpStream::connect(addr).await.map(|s| (s, addr))
+    }
+
+    let race = async {
+        let mut attempts = FuturesUnordered::new();
+        let mut next = 0;
+        let mut last_err = std::io::Error::new(std::io::ErrorKind::InvalidInput, "no address to connect to");
+        loop {
+            if attempts.is_empty() {
+                let Some(&addr) = addrs.get(next) else {
+                    return Err(last_err);
+                };
+                attempts.push(attempt(addr));
+                next += 1;
+            }
+            tokio::select! {
+                Some(result) = attempts.next() => match result {
+                    Ok(won) => return Ok(won),
+                    Err(e) => {
+                        crate::logf!("TCP: connect to candidate failed: {}", e);
+                        last_err = e;
+                    }
+                },
+                _ = tokio::time::sleep(stagger), if next < addrs.len() => {
+                    attempts.push(attempt(addrs[next]));
+                    next += 1;
+                }
+            }
+        }
+    };
+    tokio::time::timeout(within, race)
+        .await
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "TCP connect timeout"))?
+}
+
+/// Async TCP send for PT fallback (fully async, no blocking) No external framing - VSF header contains L (file length). Connects by [`connect_race`] over every candidate address (primary first); returns the one that carried it.
+pub async fn send_tcp(data: &[u8], addrs: &[SocketAddr]) -> std::io::Result<SocketAddr> {
     use tokio::io::AsyncWriteExt;
     use tokio::time::timeout;
 
     let connect_timeout = std::time::Duration::from_secs(10);
     let write_timeout = std::time::Duration::from_secs(30);
 
-    // Connect with timeout
-    let mut stream = timeout(connect_timeout, tokio::net::TcpStream::connect(addr))
-        .await
-        .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "TCP connect timeout"))??;
+    // Connect with timeout, racing the candidates
+    let (mut stream, addr) = connect_race(addrs, CONNECT_STAGGER, connect_timeout).await?;
 
     // No length prefix - VSF header contains L (file length) Write with timeout
     timeout(write_timeout, async {
@@ -158,7 +203,8 @@ pub async fn send_tcp(data: &[u8], addr: SocketAddr) -> std::io::Result<()> {
         stream.flush().await
     })
     .await
-    .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "TCP write timeout"))?
+    .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "TCP write timeout"))??;
+    Ok(addr)
 }
 
 /// Send a framed CLUTCH message Format: [payload_type:1][handle_proof:32][payload]
@@ -190,6 +236,88 @@ pub fn parse_clutch_frame(data: &[u8]) -> Option<(u8, [u8; 32], Vec<u8>)> {
     Some((payload_type, handle_proof, payload))
 }
 
+/// The photon port's TCP listeners, bound the way [`super::udp::bind_photon_udp`] binds UDP: one dual-stack listener, or IPv6 and IPv4 listeners on the same port when the platform refuses dual-stack (second is `Some` only then). A busy port is an error. Both come back non-blocking, ready for `tokio::net::TcpListener::from_std`.
+pub fn bind_listeners(port: u16) -> std::io::Result<(std::net::TcpListener, Option<std::net::TcpListener>)> {
+    use std::io::ErrorKind::AddrInUse;
+    use std::net::Ipv4Addr;
+
+    let (main, v4) = match bind_dual_stack(port) {
+        Ok(l) => (l, None),
+        Err(e) if e.kind() == AddrInUse => return Err(e),
+        Err(e) => {
+            crate::logf!("TCP: dual-stack refused on port {} ({}) - binding IPv6 and IPv4 separately", port, e);
+            match bind_v6_only(port) {
+                Ok(v6) => {
+                    let port = v6.local_addr()?.port();
+                    match std::net::TcpListener::bind((Ipv4Addr::UNSPECIFIED, port)) {
+                        Ok(v4) => (v6, Some(v4)),
+                        Err(e) if e.kind() == AddrInUse => return Err(e),
+                        Err(e) => {
+                            crate::logf!("TCP: no IPv4 on port {} ({}) - IPv6 only", port, e);
+                            (v6, None)
+                        }
+                    }
+                }
+                Err(e) if e.kind() == AddrInUse => return Err(e),
+                Err(e) => {
+                    crate::logf!("TCP: no IPv6 on port {} ({}) - IPv4 only", port, e);
+                    (std::net::TcpListener::bind((Ipv4Addr::UNSPECIFIED, port))?, None)
+                }
+            }
+        }
+    };
+    main.set_nonblocking(true)?;
+    if let Some(v4) = &v4 {
+        v4.set_nonblocking(true)?;
+    }
+    Ok((main, v4))
+}
+
+#[cfg(unix)]
+fn bind_dual_stack(port: u16) -> std::io::Result<std::net::TcpListener> {
+    listen_in6(port, false)
+}
+
+#[cfg(not(unix))]
+fn bind_dual_stack(_port: u16) -> std::io::Result<std::net::TcpListener> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "dual-stack bind needs IPV6_V6ONLY before bind"))
+}
+
+#[cfg(unix)]
+fn bind_v6_only(port: u16) -> std::io::Result<std::net::TcpListener> {
+    listen_in6(port, true)
+}
+
+/// Windows' `[::]` default is IPv6-only, which is what the split binding wants.
+#[cfg(not(unix))]
+fn bind_v6_only(port: u16) -> std::io::Result<std::net::TcpListener> {
+    std::net::TcpListener::bind((std::net::Ipv6Addr::UNSPECIFIED, port))
+}
+
+#[cfg(unix)]
+fn listen_in6(port: u16, v6only: bool) -> std::io::Result<std::net::TcpListener> {
+    use std::os::fd::AsRawFd;
+    let fd = super::udp::bind_in6(libc::SOCK_STREAM, port, v6only)?;
+    if unsafe { libc::listen(fd.as_raw_fd(), 128) } < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(std::net::TcpListener::from(fd))
+}
+
+/// The next connection on either listener of a [`bind_listeners`] pair.
+pub async fn accept_either(
+    main: &tokio::net::TcpListener,
+    v4: Option<&tokio::net::TcpListener>,
+) -> std::io::Result<(tokio::net::TcpStream, SocketAddr)> {
+    match v4 {
+        Some(v4) => tokio::select! {
+            r = main.accept() => r,
+            r = v4.accept() => r,
+        },
+        None => main.accept().await,
+    }
+}
+
 /// Async TCP listener wrapper for tokio
 #[cfg(not(target_os = "android"))]
 pub struct TcpListener {
@@ -215,3 +343,35 @@ impl TcpListener {
         Ok((std_stream, addr))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn connect_race_picks_the_live_candidate_promptly() {
+        let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        rt.block_on(async {
+            let live = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let live_addr = live.local_addr().unwrap();
+            // TEST-NET-1 (RFC 5737): routed nowhere, so a connect there hangs or fails — never answers.
+            let dead: SocketAddr = "192.0.2.1:4383".parse().unwrap();
+
+            // The dead one is the primary: the live one starts a stagger later and wins long before any timeout.
+            let started = Instant::now();
+            let (_stream, won) = connect_race(&[dead, live_addr], Duration::from_millis(100), Duration::from_secs(10))
+                .await
+                .unwrap();
+            assert_eq!(won, live_addr);
+            assert!(started.elapsed() < Duration::from_secs(2), "took {:?}", started.elapsed());
+
+            // Nothing alive: an error, not a hang past the budget.
+            let refused = {
+                let l = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+                l.local_addr().unwrap()
+            };
+            assert!(connect_race(&[dead, refused], Duration::from_millis(100), Duration::from_millis(500)).await.is_err());
+        });
+    }
+}
@@ -3,6 +3,7 @@
 //! A candidate is one address at which a peer might be reachable. Traversal gathers a set per peer (their LAN address, their reflexive/public address, an IPv6 host address) and punches toward all of them; the first to round-trip wins. Priority orders which we *prefer* when several validate, and lets [`CandidateSet::best_pair`] reproduce the exact `(primary, alt)` shape `Contact::race_addrs` returns today, so the transport contract downstream is unchanged.
 //!
 //! Ordering, best first:
+//! 0. **Same-NAT LAN** — the peer's LAN address when they show the same public IPv4 as us: both ends sit behind one NAT, so the LAN hop is a sure thing and every other path leaves the building only to come back (or hairpins, or relays).
 //! 1. **Global IPv6 host** — no NAT in the path at all, so it needs no hole-punch; just works when both ends have v6.
 //! 2. **IPv6 reflexive** — v6 seen from outside (rare; behind a v6 firewall).
 //! 3. **IPv4 LAN host** — same-subnet / hairpin: avoids the router's often-broken hairpin and AP isolation.
@@ -13,6 +14,8 @@ use std::net::SocketAddr;
 /// What kind of address a candidate is — determines its priority and how it was learned.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CandidateKind {
+    /// A peer LAN address confirmed by a shared public IPv4 plus a heard LAN beacon or a matching /24 — see [`crate::network::traverse::gather::same_nat`].
+    SameNatLan,
     /// A global (routable, non-ULA, non-link-local) IPv6 address — reachable directly, no NAT.
     HostV6,
     /// A usable IPv4 LAN address (a peer's `local_ip`) — for same-subnet / hairpin reach.
@@ -42,6 +45,7 @@ impl Candidate {
 /// Priority of a candidate (higher = tried/preferred first). See the module ordering.
 pub fn priority(kind: CandidateKind, addr: &SocketAddr) -> u32 {
     match kind {
+        CandidateKind::SameNatLan => 120,
         CandidateKind::HostV6 => 100,
         CandidateKind::Reflexive if addr.is_ipv6() => 80,
         CandidateKind::HostV4Lan => 60,
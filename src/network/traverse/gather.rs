@@ -115,6 +115,44 @@ pub fn gather_peer_candidates(contact: &Contact) -> CandidateSet {
     set
 }
 
+/// Is `contact` behind the same NAT as us? Two things must hold. Some public address we hold for them (the active `ip` or a device endpoint's) carries the same IPv4 as `our_public` — our reflexive address as peers see it. And the site is corroborated locally: we heard their LAN beacon this session ([`Contact::lan_beacon_heard`]), or one of their LAN addresses sits on our `/24` (`our_lan`). A shared public IPv4 alone isn't proof — a carrier-grade NAT or a big campus NAT puts strangers' networks behind one address, and promoting their private LAN there would aim sends at somebody else's `192.168.1.x`.
+/// Only v4: v6 has no NAT to share, and a matching v6 would just be the same host.
+pub fn same_nat(contact: &Contact, our_public: Option<IpAddr>, our_lan: Option<std::net::Ipv4Addr>) -> bool {
+    let Some(IpAddr::V4(ours)) = our_public.map(|ip| ip.to_canonical()) else {
+        return false;
+    };
+    let shares_public = !ours.is_unspecified()
+        && contact
+            .ip
+            .into_iter()
+            .chain(contact.device_endpoints.iter().filter_map(|ep| ep.public))
+            .any(|a| a.ip().to_canonical() == IpAddr::V4(ours));
+    let on_our_lan = || {
+        contact
+            .local_ip
+            .into_iter()
+            .chain(contact.device_endpoints.iter().filter_map(|ep| match ep.lan.map(|a| a.ip()) {
+                Some(IpAddr::V4(v4)) => Some(v4),
+                _ => None,
+            }))
+            .any(|v4| peer_lan_reachable(v4, our_lan))
+    };
+    shares_public && (contact.lan_beacon_heard || on_our_lan())
+}
+
+/// [`gather_peer_candidates`], plus the same-NAT shortcut: when the contact shows our public IPv4 ([`same_nat`]), their LAN candidates are promoted to [`CandidateKind::SameNatLan`] and lead the send/punch order — ahead of the public address the router would have to hairpin, and ahead of the relay that an unpunchable NAT would otherwise leave us on. `our_public = None` (reflexive not learned yet) is plain `gather_peer_candidates`; `our_lan` is our own LAN IPv4 (`udp::get_local_ip`), which `same_nat` checks their LAN addresses against.
+pub fn gather_peer_candidates_behind(contact: &Contact, our_public: Option<IpAddr>, our_lan: Option<std::net::Ipv4Addr>) -> CandidateSet {
+    let mut set = gather_peer_candidates(contact);
+    if same_nat(contact, our_public, our_lan) {
+        for c in set.sorted() {
+            if c.kind == CandidateKind::HostV4Lan {
+                set.add(Candidate::new(c.addr, CandidateKind::SameNatLan));
+            }
+        }
+    }
+    set
+}
+
 /// True if `peer` is a private IPv4 NOT on our `/24` (a foreign LAN we can't reach) — the exact address a caller with our-LAN should refuse to send to directly.
 /// `our_v4 == None` (LAN unknown) means any private peer address is unvouchable, hence foreign.
 /// A public/global v4 is never foreign (returns false).
@@ -153,3 +191,52 @@ pub fn gather_own_candidates(
 
     set
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DevicePubkey, HandleText};
+
+    fn a(s: &str) -> SocketAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn same_public_ip_routes_to_the_lan_candidate_first() {
+        let v6 = a("[2001:db8::7]:4383");
+        let mut c = Contact::new(HandleText::new("friend"), [0x11; 32], DevicePubkey::from_bytes([1u8; 32]))
+            .with_ip(a("203.0.113.7:50123"))
+            .with_local_ip(Some("192.168.1.20".parse().unwrap()), 4383);
+        c.endpoint_mut(&[1u8; 32]).public = Some(v6);
+        let lan = a("192.168.1.20:4383");
+
+        let our_lan = Some("192.168.1.5".parse().unwrap());
+
+        // Different NAT: the global v6 host leads as before, LAN behind it.
+        let elsewhere = Some("198.51.100.9".parse().unwrap());
+        assert!(!same_nat(&c, elsewhere, our_lan));
+        assert_eq!(gather_peer_candidates_behind(&c, elsewhere, our_lan).best_pair(), Some((v6, Some(lan))));
+
+        // Same public IPv4 (a different port — the NAT maps each socket separately) and their LAN on our /24: LAN goes first.
+        let here = Some("203.0.113.7".parse().unwrap());
+        assert!(same_nat(&c, here, our_lan));
+        let order: Vec<_> = gather_peer_candidates_behind(&c, here, our_lan).sorted().into_iter().map(|c| c.addr).collect();
+        assert_eq!(order, [lan, v6, a("203.0.113.7:50123")]);
+
+        // Same public IPv4 but nothing local backs it (a carrier-grade NAT): a foreign subnet, or our LAN unknown, isn't the same site.
+        let other_lan = Some("10.0.0.5".parse().unwrap());
+        assert!(!same_nat(&c, here, other_lan));
+        assert!(!same_nat(&c, here, None));
+        assert_eq!(gather_peer_candidates_behind(&c, here, other_lan).best_pair(), Some((v6, Some(lan))));
+
+        // ...until their LAN beacon reaches us: that's the same site whatever the subnets.
+        c.lan_beacon_heard = true;
+        assert!(same_nat(&c, here, other_lan));
+        assert!(!same_nat(&c, elsewhere, other_lan), "a beacon alone isn't a shared NAT");
+
+        // Our reflexive seen thru a v4-mapped socket still counts; unknown / v6-only never does.
+        assert!(same_nat(&c, Some("::ffff:203.0.113.7".parse().unwrap()), our_lan));
+        assert!(!same_nat(&c, None, our_lan));
+        assert!(!same_nat(&c, Some(v6.ip()), our_lan));
+    }
+}
@@ -10,6 +10,7 @@
 
 pub mod candidate;
 pub mod gather;
+pub mod nat;
 pub mod punch;
 pub mod reflexive;
 pub mod session;
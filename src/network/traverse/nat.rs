@@ -0,0 +1,133 @@
+//! NAT-type diagnosis — why direct paths fail and everything lands on the relay.
+//!
+//! The classic STUN mapping test, with FGTW directory nodes as the reflectors: send a signed `Reflect` from the data socket to two reflectors at *different* IPs and compare the `observed_addr` each echoes back.
+//! - Both echo our own LAN address → no NAT in the way (**Open**).
+//! - Both echo the same public address → the NAT maps one internal socket to one external port regardless of destination (**Cone** — full, restricted and port-restricted cones all look like this from two queries, and all of them punch).
+//! - The echoes differ → the mapping is per-destination (**Symmetric**): the port a friend learns from FGTW is not the port our punch will leave from, so unless the other side is open/cone the conversation rides the relay.
+//!
+//! Distinguishing the cone flavours needs a reflector that answers from a second address (RFC 5780's CHANGE-REQUEST), which FGTW nodes don't do; the punch logic doesn't need the distinction anyway.
+
+use std::net::SocketAddr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NatType {
+    Open,
+    Cone,
+    Symmetric,
+}
+
+impl NatType {
+    /// Short name for the settings/diagnostics row.
+    pub fn label(self) -> &'static str {
+        match self {
+            NatType::Open => "open (no NAT)",
+            NatType::Cone => "cone NAT",
+            NatType::Symmetric => "symmetric NAT",
+        }
+    }
+
+    /// What the user should hear about it — only symmetric is worth interrupting for.
+    pub fn warning(self) -> Option<&'static str> {
+        match self {
+            NatType::Symmetric => Some("Symmetric NAT — direct connections unlikely, messages will go via relay"),
+            _ => None,
+        }
+    }
+}
+
+/// Classify from two reflectors' views of the same socket. `local` is the socket's own LAN address (IP + bound port), if known.
+pub fn classify(local: Option<SocketAddr>, first: SocketAddr, second: SocketAddr) -> NatType {
+    if first != second {
+        NatType::Symmetric
+    } else if Some(first) == local {
+        NatType::Open
+    } else {
+        NatType::Cone
+    }
+}
+
+/// Two reflectors from `candidates` with distinct IPs in the same family — two ports on one host would see the same mapping from a symmetric NAT that keys on destination IP only, and a v4 answer can't be compared with a v6 one.
+pub fn pick_reflectors(candidates: &[SocketAddr]) -> Option<[SocketAddr; 2]> {
+    candidates.iter().enumerate().find_map(|(i, a)| {
+        candidates[i + 1..]
+            .iter()
+            .find(|b| b.ip() != a.ip() && b.is_ipv4() == a.is_ipv4())
+            .map(|b| [*a, *b])
+    })
+}
+
+/// One probe in flight: which reflectors were asked and what they've answered so far.
+#[derive(Debug, Default)]
+pub struct NatProbe {
+    local: Option<SocketAddr>,
+    asked: Vec<SocketAddr>,
+    answers: Vec<SocketAddr>,
+}
+
+impl NatProbe {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Begin a fresh probe toward `reflectors` (any previous unfinished one is abandoned).
+    pub fn start(&mut self, reflectors: [SocketAddr; 2], local: Option<SocketAddr>) {
+        *self = Self { local, asked: reflectors.to_vec(), answers: Vec::new() };
+    }
+
+    /// A `ReflectResponse` arrived from `reflector` saying it saw us at `observed`. Answers from anyone not asked (an ordinary directory reflection, a replay) are ignored, as is a second answer from the same reflector. `Some` once both have answered — the probe is then finished.
+    pub fn record(&mut self, reflector: SocketAddr, observed: SocketAddr) -> Option<NatType> {
+        let idx = self.asked.iter().position(|a| *a == reflector)?;
+        self.asked.swap_remove(idx);
+        self.answers.push(observed);
+        match self.answers[..] {
+            [first, second] => {
+                let nat = classify(self.local, first, second);
+                *self = Self::default();
+                Some(nat)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn a(s: &str) -> SocketAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn two_reflector_observations_classify_the_nat() {
+        let local = Some(a("192.168.1.20:4383"));
+        let (r1, r2) = (a("203.0.113.5:4383"), a("198.51.100.9:4383"));
+
+        // Same public mapping from both → cone (punchable).
+        let mut p = NatProbe::new();
+        p.start([r1, r2], local);
+        assert_eq!(p.record(r1, a("81.2.69.160:4383")), None);
+        assert_eq!(p.record(r1, a("81.2.69.160:4383")), None, "a repeat answer from the same reflector is not the second view");
+        assert_eq!(p.record(a("192.0.2.1:4383"), a("81.2.69.160:9999")), None, "stranger's reflection is ignored");
+        assert_eq!(p.record(r2, a("81.2.69.160:4383")), Some(NatType::Cone));
+
+        // Per-destination port → symmetric, and that's the one that warns.
+        p.start([r1, r2], local);
+        p.record(r2, a("81.2.69.160:50001"));
+        let nat = p.record(r1, a("81.2.69.160:50017")).unwrap();
+        assert_eq!(nat, NatType::Symmetric);
+        assert!(nat.warning().unwrap().contains("relay"));
+
+        // Both see our LAN socket verbatim → no NAT.
+        p.start([r1, r2], Some(a("203.0.113.77:4383")));
+        p.record(r1, a("203.0.113.77:4383"));
+        assert_eq!(p.record(r2, a("203.0.113.77:4383")), Some(NatType::Open));
+        assert_eq!(NatType::Open.warning(), None);
+
+        // A finished probe ignores late answers.
+        assert_eq!(p.record(r1, a("203.0.113.77:4383")), None);
+
+        assert_eq!(pick_reflectors(&[r1, a("203.0.113.5:3546"), a("[2001:db8::1]:4383"), r2]), Some([r1, r2]));
+        assert_eq!(pick_reflectors(&[r1, a("203.0.113.5:3546")]), None);
+    }
+}
@@ -69,7 +69,7 @@ impl ReflexiveState {
         self.v6
     }
 
-    /// This node's adopted public IP (prefers v4, falls back to v6). Currently informational; kept for candidate gathering and any future same-NAT/hairpin use (the old `Contact::best_addr` path was dead and removed — `race_addrs` already covers same-NAT by racing the LAN candidate).
+    /// This node's adopted public IP (prefers v4, falls back to v6). Informational here; the app's copy of the adopted address (`PhotonApp.our_reflexive`) is what same-NAT detection compares against (`gather::same_nat` → `Contact::race_addrs_behind`).
     pub fn public_ip(&self) -> Option<IpAddr> {
         self.v4.map(|a| a.ip()).or_else(|| self.v6.map(|a| a.ip()))
     }
@@ -4,7 +4,137 @@
 
 #[cfg(feature = "development")]
 use super::inspect::vsf_inspect;
-use std::net::SocketAddr;
+use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+use std::sync::Mutex;
+
+/// The IPv4 half of a split binding — set when the platform refused dual-stack and the photon port was bound once per family. [`send`] routes IPv4 destinations thru it and the status receiver reads it alongside the main socket. `None` (the usual case) when the main socket is dual-stack or is itself IPv4. One process-wide slot, like the profile-name slot in status.rs: the socket is bound inside HandleQuery and read by the status task, which have no other path between them.
+static SPLIT_V4: Mutex<Option<UdpSocket>> = Mutex::new(None);
+
+/// The photon port's UDP socket(s): `socket` is dual-stack when the platform allows it; otherwise it's the IPv6 socket (or IPv4, on a host with no IPv6) and `v4` holds the IPv4 one, bound to the same port.
+pub struct PhotonUdp {
+    pub socket: UdpSocket,
+    pub v4: Option<UdpSocket>,
+}
+
+/// Bind `port` (0 = ephemeral) for both address families: one dual-stack socket with `IPV6_V6ONLY` explicitly off, or — when the platform refuses that (Windows, OpenBSD, `bindv6only=1`) — an IPv6-only and an IPv4 socket on the same port. A busy port is an error so the caller can try the next one.
+pub fn bind_photon_udp(port: u16) -> std::io::Result<PhotonUdp> {
+    bind_families(port, bind_dual_stack)
+}
+
+fn bind_families(
+    port: u16,
+    dual: impl FnOnce(u16) -> std::io::Result<UdpSocket>,
+) -> std::io::Result<PhotonUdp> {
+    match dual(port) {
+        Ok(socket) => return Ok(PhotonUdp { socket, v4: None }),
+        Err(e) if e.kind() == std::io::ErrorKind::AddrInUse => return Err(e),
+        Err(e) => crate::logf!("Network: dual-stack UDP refused on port {} ({}) - binding IPv6 and IPv4 separately", port, e),
+    }
+    match bind_in6_udp(port, true) {
+        Ok(v6) => {
+            let port = v6.local_addr()?.port();
+            match UdpSocket::bind((Ipv4Addr::UNSPECIFIED, port)) {
+                Ok(v4) => Ok(PhotonUdp { socket: v6, v4: Some(v4) }),
+                Err(e) if e.kind() == std::io::ErrorKind::AddrInUse => Err(e),
+                Err(e) => {
+                    crate::logf!("Network: no IPv4 on port {} ({}) - IPv6 only", port, e);
+                    Ok(PhotonUdp { socket: v6, v4: None })
+                }
+            }
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::AddrInUse => Err(e),
+        Err(e) => {
+            crate::logf!("Network: no IPv6 on port {} ({}) - IPv4 only", port, e);
+            Ok(PhotonUdp { socket: UdpSocket::bind((Ipv4Addr::UNSPECIFIED, port))?, v4: None })
+        }
+    }
+}
+
+/// One `[::]:port` UDP socket that also takes IPv4 (as `::ffff:a.b.c.d`). `IPV6_V6ONLY` is set off before the bind rather than left to the platform default, which differs (Linux/macOS off, Windows on).
+#[cfg(unix)]
+pub fn bind_dual_stack(port: u16) -> std::io::Result<UdpSocket> {
+    bind_in6_udp(port, false)
+}
+
+/// No pre-bind socket options here without a raw socket API: report dual-stack as refused so the caller binds each family.
+#[cfg(not(unix))]
+pub fn bind_dual_stack(_port: u16) -> std::io::Result<UdpSocket> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "dual-stack bind needs IPV6_V6ONLY before bind"))
+}
+
+#[cfg(unix)]
+fn bind_in6_udp(port: u16, v6only: bool) -> std::io::Result<UdpSocket> {
+    bind_in6(libc::SOCK_DGRAM, port, v6only).map(UdpSocket::from)
+}
+
+/// Windows' `[::]` default is IPv6-only, which is what the split binding wants.
+#[cfg(not(unix))]
+fn bind_in6_udp(port: u16, _v6only: bool) -> std::io::Result<UdpSocket> {
+    UdpSocket::bind((std::net::Ipv6Addr::UNSPECIFIED, port))
+}
+
+/// A socket of `kind` (`SOCK_DGRAM` / `SOCK_STREAM`) bound to `[::]:port` with `IPV6_V6ONLY` set to `v6only` BEFORE the bind — the only point at which it can be changed. Stream sockets also get `SO_REUSEADDR` (as tokio/std set it) so a restart isn't blocked by TIME_WAIT; the caller listens.
+#[cfg(unix)]
+pub(crate) fn bind_in6(kind: libc::c_int, port: u16, v6only: bool) -> std::io::Result<std::os::fd::OwnedFd> {
+    use std::os::fd::{FromRawFd, OwnedFd};
+
+    let fd = unsafe { libc::socket(libc::AF_INET6, kind, 0) };
+    if fd < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // Owned from here: every early return closes it.
+    let owned = unsafe { OwnedFd::from_raw_fd(fd) };
+    let set = |level: libc::c_int, name: libc::c_int, on: bool| {
+        let value: libc::c_int = on as libc::c_int;
+        let ret = unsafe {
+            libc::setsockopt(
+                fd,
+                level,
+                name,
+                &value as *const _ as *const libc::c_void,
+                std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+            )
+        };
+        if ret < 0 {
+            Err(std::io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    };
+    set(libc::IPPROTO_IPV6, libc::IPV6_V6ONLY, v6only)?;
+    if kind == libc::SOCK_STREAM {
+        set(libc::SOL_SOCKET, libc::SO_REUSEADDR, true)?;
+    }
+
+    let mut addr: libc::sockaddr_in6 = unsafe { std::mem::zeroed() };
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    {
+        addr.sin6_len = std::mem::size_of::<libc::sockaddr_in6>() as u8;
+    }
+    addr.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+    addr.sin6_port = port.to_be();
+    let ret = unsafe {
+        libc::bind(
+            fd,
+            &addr as *const _ as *const libc::sockaddr,
+            std::mem::size_of::<libc::sockaddr_in6>() as libc::socklen_t,
+        )
+    };
+    if ret < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(owned)
+}
+
+/// Route IPv4 sends thru `v4` from now on (a split binding's IPv4 half); `None` clears it.
+pub fn set_split_v4(v4: Option<UdpSocket>) {
+    *SPLIT_V4.lock().unwrap() = v4;
+}
+
+/// A handle on the split binding's IPv4 socket, for a receiver to read — `None` when the main socket covers IPv4.
+pub fn split_v4() -> Option<UdpSocket> {
+    SPLIT_V4.lock().unwrap().as_ref().and_then(|s| s.try_clone().ok())
+}
 
 /// Centralized UDP TX - logs via vsf_inspect then sends This is THE ONLY place UDP packets should be transmitted (except LAN broadcast) Normalize a UNICAST destination for the main dual-stack (`[::]`) photon socket: a v6 socket cannot send to a plain `SocketAddr::V4` — the datagram is silently dropped — it must target the IPv4-mapped form `[::ffff:a.b.c.d]`. Some send paths construct raw V4 dests (e.g. `race_addrs` builds the LAN address from a stored `Ipv4Addr`) while others reuse a kernel-supplied `::ffff:` address (an incoming packet's src), so unicast delivery was inconsistent: ACKs (src-derived, mapped) arrived but chat messages (race_addrs, raw V4) vanished. Mapping here makes every unicast send go out in a form the dual-stack socket accepts. Only the async `send` (always the dual-stack socket) maps; `send_sync` is left raw because it serves v4 multicast/broadcast on dedicated v4 sockets, which must NOT be mapped.
 fn map_v4_for_dualstack(addr: SocketAddr) -> SocketAddr {
@@ -32,7 +162,18 @@ pub async fn send(socket: &tokio::net::UdpSocket, data: &[u8], addr: SocketAddr)
     if data.is_empty() {
         return;
     }
-    let addr = map_v4_for_dualstack(addr);
+    // Split binding: an IPv4 destination leaves by the IPv4 socket, the v6-only main socket can't reach it.
+    if let SocketAddr::V4(v4_addr) = canon_socketaddr(addr) {
+        if let Some(v4) = SPLIT_V4.lock().unwrap().as_ref() {
+            let _ = send_sync(v4, data, SocketAddr::V4(v4_addr));
+            return;
+        }
+    }
+    // Only a dual-stack (IPv6) socket wants the mapped form; an IPv4-only main socket takes v4 as-is.
+    let addr = match socket.local_addr() {
+        Ok(SocketAddr::V4(_)) => canon_socketaddr(addr),
+        _ => map_v4_for_dualstack(addr),
+    };
     #[cfg(feature = "development")]
     {
         let msg = vsf_inspect(data, "UDP", "TX", &addr.to_string());
@@ -43,6 +184,17 @@ pub async fn send(socket: &tokio::net::UdpSocket, data: &[u8], addr: SocketAddr)
     let _ = socket.send_to(data, addr).await;
 }
 
+/// The next datagram on a split binding's IPv4 socket, as an owned buffer and its source. Never resolves when there isn't one, so a receive loop can `select!` on it unconditionally.
+pub async fn recv_split_v4(socket: Option<&tokio::net::UdpSocket>) -> std::io::Result<(Vec<u8>, SocketAddr)> {
+    let Some(socket) = socket else {
+        return std::future::pending().await;
+    };
+    let mut buf = vec![0u8; 65536];
+    let (len, src) = socket.recv_from(&mut buf).await?;
+    buf.truncate(len);
+    Ok((buf, src))
+}
+
 /// Synchronous version for non-async contexts (LAN broadcast uses std::net::UdpSocket)
 pub fn send_sync(
     socket: &std::net::UdpSocket,
@@ -59,6 +211,63 @@ pub fn send_sync(
     socket.send_to(data, addr)
 }
 
+/// Ask the kernel for a `bytes` receive buffer (SO_RCVBUF) on `socket` and return the size it actually granted. Never assume the request stuck: Linux doubles it for bookkeeping and caps it at `net.core.rmem_max`, macOS caps at `kern.ipc.maxsockbuf`.
+#[cfg(unix)]
+pub fn set_recv_buffer(socket: &std::net::UdpSocket, bytes: usize) -> std::io::Result<usize> {
+    use std::os::unix::io::AsRawFd;
+    let want = bytes.min(libc::c_int::MAX as usize) as libc::c_int;
+    let ret = unsafe {
+        libc::setsockopt(
+            socket.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_RCVBUF,
+            &want as *const _ as *const libc::c_void,
+            std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+        )
+    };
+    if ret < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    recv_buffer(socket)
+}
+
+/// The receive buffer (SO_RCVBUF) `socket` has now, in bytes.
+#[cfg(unix)]
+pub fn recv_buffer(socket: &std::net::UdpSocket) -> std::io::Result<usize> {
+    use std::os::unix::io::AsRawFd;
+    let mut got: libc::c_int = 0;
+    let mut len = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+    let ret = unsafe {
+        libc::getsockopt(
+            socket.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_RCVBUF,
+            &mut got as *mut _ as *mut libc::c_void,
+            &mut len,
+        )
+    };
+    if ret < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(got.max(0) as usize)
+}
+
+#[cfg(not(unix))]
+pub fn set_recv_buffer(_socket: &std::net::UdpSocket, _bytes: usize) -> std::io::Result<usize> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "SO_RCVBUF is only set on unix"))
+}
+
+/// Size the photon socket's receive buffer from the `udp_rcvbuf_kib` setting (0 = leave the OS default) and log what was granted — a grant well under the request means the system cap needs raising, not the setting.
+pub fn apply_recv_buffer(socket: &std::net::UdpSocket, kib: u16) {
+    if kib == 0 {
+        return;
+    }
+    match set_recv_buffer(socket, usize::from(kib) << 10) {
+        Ok(granted) => crate::logf!("Network: UDP receive buffer {} KiB requested, {} KiB granted", kib, granted >> 10),
+        Err(e) => crate::logf!("Network: UDP receive buffer {} KiB not applied: {}", kib, e),
+    }
+}
+
 /// Log received UDP packet (call this in the receive loop)
 #[cfg(feature = "development")]
 pub fn log_received(data: &[u8], addr: &SocketAddr) {
@@ -142,82 +351,6 @@ fn get_broadcast_from_system(local_ip: &std::net::Ipv4Addr) -> Option<std::net::
     None
 }
 
-/// Parse LAN discovery packet Returns (handle_proof, ip, port, device_pubkey) if valid, None otherwise handle_proof is extracted from the VSF header's provenance hash (hp) device_pubkey (ke) is None on beacons from builds that predate it; receivers use it to drop their OWN looped-back beacon (a fleet shares one handle_proof, so the handle alone can't tell self from sibling).
-pub fn parse_lan_discovery(
-    packet: &[u8],
-    src_addr: SocketAddr,
-) -> Option<([u8; 32], std::net::Ipv4Addr, u16, Option<[u8; 32]>)> {
-    use vsf::file_format::{VsfHeader, VsfSection};
-    use vsf::VsfType;
-
-    // Parse header to get provenance hash (sender identity) and find section start Note: No is_original() check - LAN discovery is a simple unsigned broadcast
-    let (header, header_end) = VsfHeader::decode(packet).ok()?;
-
-    // Extract handle_proof from header provenance hash
-    let handle_proof = match header.provenance_hash {
-        VsfType::hp(bytes) if bytes.len() == 32 => {
-            let mut hp = [0u8; 32];
-            hp.copy_from_slice(&bytes);
-            hp
-        }
-        _ => return None,
-    };
-
-    // Parse section
-    let mut ptr = header_end;
-    let section = VsfSection::parse(packet, &mut ptr).ok()?;
-
-    if section.name != "pt_disc" {
-        return None;
-    }
-
-    // Extract port (u4 = u16)
-    let port = match section.get_field("port") {
-        Some(field) => match field.values.first() {
-            Some(VsfType::u4(p)) => *p,
-            _ => return None,
-        },
-        None => return None,
-    };
-
-    // Sender's device pubkey (ke) — optional for wire compat with pre-ke beacons
-    let device_pubkey = section.get_field("ke").and_then(|f| match f.values.first() {
-        Some(VsfType::ke(bytes)) if bytes.len() == 32 => {
-            let mut k = [0u8; 32];
-            k.copy_from_slice(bytes);
-            Some(k)
-        }
-        _ => None,
-    });
-
-    // Handle both native IPv4 and IPv4-mapped IPv6 addresses
-    let src_ip = match src_addr.ip() {
-        std::net::IpAddr::V4(ip) => ip,
-        std::net::IpAddr::V6(ip6) => ip6.to_ipv4_mapped()?,
-    };
-
-    Some((handle_proof, src_ip, port, device_pubkey))
-}
-
-/// Build LAN discovery broadcast packet handle_proof is stored in VSF header as provenance hash (hp) for identity One-shot broadcast - no rolling hash needed (provenance_only) device_pubkey rides along (ke) so receivers can tell WHICH fleet device is beaconing — and in particular drop their own looped-back beacon instead of learning themselves as a peer.
-pub fn build_lan_discovery(handle_proof: [u8; 32], port: u16, device_pubkey: [u8; 32]) -> Vec<u8> {
-    use vsf::{VsfBuilder, VsfType};
-
-    VsfBuilder::new()
-        .creation_time_oscillations(vsf::eagle_time_oscillations())
-        .provenance_hash(handle_proof) // Identity in header - no registry lookup needed
-        .provenance_only() // No rolling hash - one-shot broadcast
-        .add_section(
-            "pt_disc",
-            vec![
-                ("port".to_string(), VsfType::u4(port)),
-                ("ke".to_string(), VsfType::ke(device_pubkey.to_vec())),
-            ],
-        )
-        .build()
-        .unwrap_or_default()
-}
-
 #[cfg(test)]
 mod lan_addr_tests {
     use super::is_usable_lan_ipv4;
@@ -237,3 +370,66 @@ mod lan_addr_tests {
         assert!(is_usable_lan_ipv4(Ipv4Addr::new(192, 0, 1, 4)));
     }
 }
+
+#[cfg(all(test, unix))]
+mod recv_buffer_tests {
+    use super::*;
+
+    #[test]
+    fn receive_buffer_request_is_applied() {
+        let socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let before = recv_buffer(&socket).unwrap();
+        // Under every kernel's default cap, so the whole request is granted (Linux reports it doubled).
+        let want = 96 << 10;
+        let granted = set_recv_buffer(&socket, want).unwrap();
+        assert_eq!(granted, recv_buffer(&socket).unwrap());
+        assert!(granted >= want, "asked {want}, granted {granted} (was {before})");
+        assert!(granted <= 4 * want, "granted {granted} for {want}");
+    }
+}
+
+#[cfg(all(test, unix))]
+mod dual_stack_tests {
+    use super::*;
+    use std::net::Ipv6Addr;
+    use std::time::Duration;
+
+    /// Send one datagram from `from` to `to`'s port on loopback, and read it off `at`.
+    fn lands(from: std::net::IpAddr, to_port: u16, at: &UdpSocket) -> bool {
+        let tx = UdpSocket::bind((from, 0)).unwrap();
+        tx.send_to(b"hi", (from, to_port)).unwrap();
+        at.set_read_timeout(Some(Duration::from_secs(2))).unwrap();
+        let mut buf = [0u8; 8];
+        matches!(at.recv_from(&mut buf), Ok((2, _)))
+    }
+
+    #[test]
+    fn both_families_reach_the_photon_port() {
+        let v4_loop = std::net::IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let v6_loop = std::net::IpAddr::V6(Ipv6Addr::LOCALHOST);
+        if UdpSocket::bind((v6_loop, 0)).is_err() {
+            return; // no IPv6 loopback on this host — nothing dual to test
+        }
+
+        // Dual-stack: one socket takes both.
+        let dual = bind_photon_udp(0).unwrap();
+        assert!(dual.v4.is_none(), "this host allows dual-stack");
+        let port = dual.socket.local_addr().unwrap().port();
+        assert!(lands(v4_loop, port, &dual.socket));
+        assert!(lands(v6_loop, port, &dual.socket));
+
+        // Dual-stack refused: one socket per family, same port, each receiving its own.
+        let refused = |_| Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "test"));
+        let split = bind_families(0, refused).unwrap();
+        let v4 = split.v4.expect("IPv4 bound separately");
+        let port = split.socket.local_addr().unwrap().port();
+        assert!(split.socket.local_addr().unwrap().is_ipv6());
+        assert_eq!(v4.local_addr().unwrap().port(), port);
+        assert!(lands(v4_loop, port, &v4));
+        assert!(lands(v6_loop, port, &split.socket));
+
+        // A port that's taken is reported, not papered over with a half binding.
+        let busy = bind_families(port, refused).err().map(|e| e.kind());
+        assert_eq!(busy, Some(std::io::ErrorKind::AddrInUse));
+    }
+}
@@ -27,6 +27,10 @@ pub fn set_background_desired(on: bool) {
 
 /// Default-on enrollment, called once per launch: if backgrounding is desired and no login item exists yet, write it. Idempotent and best-effort — a failure logs and the session still runs resident; the next launch retries.
 pub fn ensure_enrolled() {
+    // Unit tests construct `PhotonApp` — never register the test binary as a login item.
+    if cfg!(test) {
+        return;
+    }
     if background_desired() && !enabled() {
         match enable() {
             Ok(()) => crate::log("RESIDENT: default-on — login item written (settings toggle to opt out)"),
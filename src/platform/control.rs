@@ -1,6 +1,6 @@
 //! Second-launch handoff — the control channel a fresh `photon-messenger` invocation uses to tell the RESIDENT instance "show yourself" instead of dying with an "already running" error.
-//! One verb, one direction: `show\n`. No state crosses; the resident side reacts by surfacing its window (`PhotonEvent::ShowWindow` → `EventResponse::ShowWindow`). Anything richer belongs in the app protocol, not here.
-//! Transport per platform: a Unix domain socket at `<data_dir>/control.sock` (created ONLY after the flock single-instance guard is won, so a stale path can be unlinked safely), and on Windows the single-instance TcpListener itself doubles as the channel (it already exists, it's already dir-keyed, and any same-user process that could connect could equally just launch the app — "show the window" needs no authentication).
+//! Two verbs, one direction: `show\n`, and `add <token> <link>\n` when the second launch was a clicked `photon://add/…` link (platform::link). The resident side queues the link and surfaces its window (`PhotonEvent::ShowWindow` → `EventResponse::ShowWindow`), whose wake also runs the tick that drains it. Anything richer belongs in the app protocol, not here.
+//! Transport per platform: a Unix domain socket at `<data_dir>/control.sock` (created ONLY after the flock single-instance guard is won, so a stale path can be unlinked safely), and on Windows the single-instance TcpListener itself doubles as the channel (it already exists, it's already dir-keyed, and any same-user process that could connect could equally just launch the app — "show the window" needs no authentication). `add` does: on Windows any local process can reach the loopback port, so it must echo the resident session's rpc token (`platform::rpc`, read from the owner-only `rpc.token`), and even then the link only arms a prompt the user has to press.
 
 use std::io::{Read, Write};
 
@@ -41,7 +41,17 @@ pub fn spawn_accept_thread(proxy: std::sync::Arc<dyn fluor::host::WakeSender<cra
     };
     std::thread::spawn(move || {
         let handle = |buf: &[u8], proxy: &std::sync::Arc<dyn fluor::host::WakeSender<crate::ui::PhotonEvent>>| {
-            if buf.starts_with(b"show") {
+            if let Some(rest) = buf.strip_prefix(b"add ") {
+                let mut parts = rest.splitn(2, |&b| b == b' ');
+                let (token, link) = (parts.next().unwrap_or_default(), parts.next().unwrap_or_default());
+                if !crate::platform::rpc::token_matches(token) {
+                    crate::log("CONTROL: refused a contact-link handoff without the session token");
+                    return;
+                }
+                crate::log("CONTROL: contact link handed over by a second launch");
+                crate::platform::link::on_link(String::from_utf8_lossy(link).trim().to_string());
+                let _ = proxy.send(crate::ui::PhotonEvent::ShowWindow);
+            } else if buf.starts_with(b"show") {
                 crate::log("CONTROL: show requested by a second launch — surfacing the window");
                 let _ = proxy.send(crate::ui::PhotonEvent::ShowWindow);
             }
@@ -51,9 +61,8 @@ pub fn spawn_accept_thread(proxy: std::sync::Arc<dyn fluor::host::WakeSender<cra
             ControlListener::Unix(l) => {
                 for stream in l.incoming() {
                     let Ok(mut s) = stream else { continue };
-                    let mut buf = [0u8; 16];
-                    let n = s.read(&mut buf).unwrap_or(0);
-                    handle(&buf[..n], &proxy);
+                    let buf = read_verb(&mut s);
+                    handle(&buf, &proxy);
                 }
             }
             ControlListener::Tcp(l) => {
@@ -61,22 +70,40 @@ pub fn spawn_accept_thread(proxy: std::sync::Arc<dyn fluor::host::WakeSender<cra
                     let Ok(mut s) = stream else { continue };
                     // Loopback-only by bind; a brief read deadline so a port-scanner's half-open connect can't wedge the accept loop.
                     let _ = s.set_read_timeout(Some(std::time::Duration::from_millis(500)));
-                    let mut buf = [0u8; 16];
-                    let n = s.read(&mut buf).unwrap_or(0);
-                    handle(&buf[..n], &proxy);
+                    let buf = read_verb(&mut s);
+                    handle(&buf, &proxy);
                 }
             }
         }
     });
 }
 
+/// One verb line, read to EOF/newline but never past "add " + a token + the link cap — the sender writes one line and closes.
+fn read_verb(s: &mut impl Read) -> Vec<u8> {
+    let mut buf = Vec::new();
+    let _ = s.take((crate::platform::link::MAX_LINK_LEN + 80) as u64).read_to_end(&mut buf);
+    buf
+}
+
 /// Second-launch side: ask the resident instance to surface. `true` = delivered (the caller should exit 0 quietly); `false` = nobody answered (stale lock? different failure — caller falls back to the old already-running error).
 pub fn request_show(data_dir: &std::path::Path) -> bool {
+    send_verb(data_dir, b"show\n")
+}
+
+/// Second-launch side for a clicked contact link: hand it to the resident instance (which also surfaces), authenticated with its rpc token. Same `true`/`false` contract as [`request_show`]; `false` too when the token can't be read (the caller falls back to a plain show).
+pub fn request_link(data_dir: &std::path::Path, link: &str) -> bool {
+    let Some(token) = crate::platform::rpc::read_token(data_dir) else {
+        return false;
+    };
+    send_verb(data_dir, format!("add {} {}\n", token, link.trim()).as_bytes())
+}
+
+fn send_verb(data_dir: &std::path::Path, verb: &[u8]) -> bool {
     #[cfg(unix)]
     {
         let path = socket_path(data_dir);
         if let Ok(mut s) = std::os::unix::net::UnixStream::connect(&path) {
-            return s.write_all(b"show\n").is_ok();
+            return s.write_all(verb).is_ok();
         }
         false
     }
@@ -86,7 +113,7 @@ pub fn request_show(data_dir: &std::path::Path) -> bool {
         let h = blake3::hash(data_dir.to_string_lossy().as_bytes());
         let port = 20000 + (u16::from_le_bytes([h.as_bytes()[0], h.as_bytes()[1]]) % 20000);
         if let Ok(mut s) = std::net::TcpStream::connect(("127.0.0.1", port)) {
-            return s.write_all(b"show\n").is_ok();
+            return s.write_all(verb).is_ok();
         }
         false
     }
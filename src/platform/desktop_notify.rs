@@ -14,6 +14,11 @@ pub fn set_window_visible(visible: bool) {
     WINDOW_VISIBLE.store(visible, Ordering::Relaxed);
 }
 
+/// Whether the window is currently shown (the tray's left-click toggle reads this to pick hide vs surface).
+pub fn window_visible() -> bool {
+    WINDOW_VISIBLE.load(Ordering::Relaxed)
+}
+
 pub fn set_window_focused(focused: bool) {
     WINDOW_FOCUSED.store(focused, Ordering::Relaxed);
 }
@@ -0,0 +1,267 @@
+//! System-wide "bring Photon forward" hotkey. The chord is a plain string in `settings.vsf` (`hotkey`, default `Ctrl+Shift+P`, empty = off) so it can be hand-edited like every other settings knob; [`Hotkey::parse`] turns it into modifiers + one key, and each backend maps that onto its native registration.
+//! Activation rides the same wake proxy as the tray and the second-launch handoff: `PhotonEvent::FocusRequested` → the UI thread surfaces and focuses the window.
+//!
+//! Linux: `XGrabKey` on the X root window from a dedicated thread (the grab is per-display-connection, so the thread owns its own `XOpenDisplay`). Covers X11 sessions and XWayland-focused apps; a pure-Wayland compositor offers no global grab to clients, so there the registration simply fails and is logged.
+//! Windows: `RegisterHotKey` on a message-pump thread (thread-scoped hotkey, no window needed).
+//! macOS: no backend yet (Carbon `RegisterEventHotKey` needs a main-thread event target) — `spawn` logs and returns.
+//! Registration failure (chord already owned by another app, no display) is never fatal: the app runs without the hotkey.
+
+/// Modifier bits for a parsed chord.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Mods {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    /// Win / Cmd / Super.
+    pub logo: bool,
+}
+
+/// A parsed hotkey: modifiers + one key. Letters are stored upper-case; digits as themselves; `F1`..`F24` as `Function(n)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hotkey {
+    pub mods: Mods,
+    pub key: HotkeyKey,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotkeyKey {
+    /// `A`..`Z` or `0`..`9`.
+    Char(char),
+    /// `F1`..`F24`.
+    Function(u8),
+    Space,
+}
+
+impl Hotkey {
+    /// Parse `"Ctrl+Shift+P"`-style strings: `+`-separated, case-insensitive, modifiers in any order, exactly one non-modifier key, last. Accepts `Control`/`Ctrl`, `Shift`, `Alt`/`Option`, `Super`/`Win`/`Cmd`/`Meta`. A chord with no modifier is refused — a bare letter grabbed system-wide would eat that letter in every other app.
+    pub fn parse(s: &str) -> Result<Hotkey, String> {
+        let mut mods = Mods::default();
+        let mut key = None;
+        for part in s.split('+').map(str::trim) {
+            if key.is_some() {
+                return Err(format!("'{part}' after the key — the key must come last"));
+            }
+            match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => mods.ctrl = true,
+                "shift" => mods.shift = true,
+                "alt" | "option" => mods.alt = true,
+                "super" | "win" | "cmd" | "meta" => mods.logo = true,
+                "space" => key = Some(HotkeyKey::Space),
+                "" => return Err("empty chord component".to_string()),
+                p => key = Some(parse_key(p)?),
+            }
+        }
+        let Some(key) = key else {
+            return Err("no key in chord".to_string());
+        };
+        if mods == Mods::default() {
+            return Err("a global hotkey needs at least one modifier".to_string());
+        }
+        Ok(Hotkey { mods, key })
+    }
+
+    /// X11 representation: `(keysym, modifier mask)`. Latin-letter keysyms are the lower-case ASCII codepoints (XK_p = 0x70), digits are their ASCII, F1 = 0xFFBE onward, space = 0x20. Masks are the core protocol's ShiftMask/ControlMask/Mod1 (Alt)/Mod4 (Super).
+    pub fn to_x11(&self) -> (u64, u32) {
+        let keysym = match self.key {
+            HotkeyKey::Char(c) => c.to_ascii_lowercase() as u64,
+            HotkeyKey::Function(n) => 0xFFBE + (n as u64 - 1),
+            HotkeyKey::Space => 0x20,
+        };
+        let mut mask = 0u32;
+        if self.mods.shift {
+            mask |= 1 << 0; // ShiftMask
+        }
+        if self.mods.ctrl {
+            mask |= 1 << 2; // ControlMask
+        }
+        if self.mods.alt {
+            mask |= 1 << 3; // Mod1Mask
+        }
+        if self.mods.logo {
+            mask |= 1 << 6; // Mod4Mask
+        }
+        (keysym, mask)
+    }
+
+    /// Win32 representation: `(fsModifiers, virtual-key code)` for `RegisterHotKey`. VK codes for `A`..`Z` / `0`..`9` are their upper-case ASCII; VK_F1 = 0x70 onward; VK_SPACE = 0x20. MOD_NOREPEAT (0x4000) is always set so holding the chord doesn't machine-gun focus requests.
+    pub fn to_win32(&self) -> (u32, u32) {
+        let vk = match self.key {
+            HotkeyKey::Char(c) => c as u32,
+            HotkeyKey::Function(n) => 0x70 + (n as u32 - 1),
+            HotkeyKey::Space => 0x20,
+        };
+        let mut mods = 0x4000u32; // MOD_NOREPEAT
+        if self.mods.alt {
+            mods |= 0x0001; // MOD_ALT
+        }
+        if self.mods.ctrl {
+            mods |= 0x0002; // MOD_CONTROL
+        }
+        if self.mods.shift {
+            mods |= 0x0004; // MOD_SHIFT
+        }
+        if self.mods.logo {
+            mods |= 0x0008; // MOD_WIN
+        }
+        (mods, vk)
+    }
+}
+
+fn parse_key(p: &str) -> Result<HotkeyKey, String> {
+    let mut chars = p.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if c.is_ascii_alphanumeric() {
+            return Ok(HotkeyKey::Char(c.to_ascii_uppercase()));
+        }
+    }
+    if let Some(n) = p.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+        if (1..=24).contains(&n) {
+            return Ok(HotkeyKey::Function(n));
+        }
+    }
+    Err(format!("unknown key '{p}'"))
+}
+
+/// Register the hotkey from `chord` and deliver activations as `PhotonEvent::FocusRequested`. Empty chord = disabled; a bad chord or a failed registration logs and leaves the app without one.
+pub fn spawn(chord: &str, proxy: std::sync::Arc<dyn fluor::host::WakeSender<crate::ui::PhotonEvent>>) {
+    if chord.trim().is_empty() {
+        crate::log("HOTKEY: disabled (empty chord in settings.vsf)");
+        return;
+    }
+    let hotkey = match Hotkey::parse(chord) {
+        Ok(h) => h,
+        Err(e) => {
+            crate::logf!("HOTKEY: '{}' not usable ({}) — no global hotkey this session", chord, e);
+            return;
+        }
+    };
+    let chord = chord.to_string();
+    let _ = std::thread::Builder::new()
+        .name("hotkey".to_string())
+        .spawn(move || backend::run(hotkey, &chord, proxy));
+}
+
+#[cfg(target_os = "linux")]
+mod backend {
+    use super::Hotkey;
+    use fluor::host::WakeSender;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use x11::xlib;
+
+    /// Set by the error handler when the server refuses the grab (BadAccess = another client already holds the chord).
+    static GRAB_FAILED: AtomicBool = AtomicBool::new(false);
+
+    unsafe extern "C" fn on_x_error(_d: *mut xlib::Display, _e: *mut xlib::XErrorEvent) -> i32 {
+        GRAB_FAILED.store(true, Ordering::Relaxed);
+        0
+    }
+
+    pub fn run(hotkey: Hotkey, chord: &str, proxy: Arc<dyn WakeSender<crate::ui::PhotonEvent>>) {
+        unsafe {
+            let display = xlib::XOpenDisplay(std::ptr::null());
+            if display.is_null() {
+                crate::logf!("HOTKEY: no X display — '{}' not registered (pure Wayland has no client-side global grab)", chord);
+                return;
+            }
+            let root = xlib::XDefaultRootWindow(display);
+            let (keysym, mask) = hotkey.to_x11();
+            let keycode = xlib::XKeysymToKeycode(display, keysym);
+            if keycode == 0 {
+                crate::logf!("HOTKEY: '{}' has no keycode on this keyboard layout", chord);
+                xlib::XCloseDisplay(display);
+                return;
+            }
+            // Grab with every combination of the lock modifiers (CapsLock = LockMask, NumLock = Mod2) — X matches the modifier state EXACTLY, so without these the chord dies whenever NumLock is on.
+            let locks = [0, xlib::LockMask, xlib::Mod2Mask, xlib::LockMask | xlib::Mod2Mask];
+            let previous = xlib::XSetErrorHandler(Some(on_x_error));
+            for lock in locks {
+                xlib::XGrabKey(display, keycode as i32, mask | lock, root, 1, xlib::GrabModeAsync, xlib::GrabModeAsync);
+            }
+            xlib::XSync(display, 0);
+            xlib::XSetErrorHandler(previous);
+            if GRAB_FAILED.load(Ordering::Relaxed) {
+                crate::logf!("HOTKEY: '{}' is already taken by another application — not registered", chord);
+                xlib::XCloseDisplay(display);
+                return;
+            }
+            crate::logf!("HOTKEY: '{}' registered (X11 root grab)", chord);
+            let mut event: xlib::XEvent = std::mem::zeroed();
+            loop {
+                xlib::XNextEvent(display, &mut event);
+                if event.get_type() == xlib::KeyPress {
+                    let _ = proxy.send(crate::ui::PhotonEvent::FocusRequested);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod backend {
+    use super::Hotkey;
+    use fluor::host::WakeSender;
+    use std::sync::Arc;
+    use windows::Win32::UI::Input::KeyboardAndMouse::{RegisterHotKey, HOT_KEY_MODIFIERS};
+    use windows::Win32::UI::WindowsAndMessaging::{GetMessageW, MSG, WM_HOTKEY};
+
+    /// Thread-scoped hotkey id (any value 0x0000..0xBFFF is ours to pick).
+    const HOTKEY_ID: i32 = 1;
+
+    pub fn run(hotkey: Hotkey, chord: &str, proxy: Arc<dyn WakeSender<crate::ui::PhotonEvent>>) {
+        let (mods, vk) = hotkey.to_win32();
+        unsafe {
+            // NULL hwnd = WM_HOTKEY is posted to THIS thread's queue, so the pump below is the whole backend.
+            if RegisterHotKey(None, HOTKEY_ID, HOT_KEY_MODIFIERS(mods), vk).is_err() {
+                crate::logf!("HOTKEY: '{}' is already taken by another application — not registered", chord);
+                return;
+            }
+            crate::logf!("HOTKEY: '{}' registered (RegisterHotKey)", chord);
+            let mut msg = MSG::default();
+            while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+                if msg.message == WM_HOTKEY {
+                    let _ = proxy.send(crate::ui::PhotonEvent::FocusRequested);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+mod backend {
+    use super::Hotkey;
+    use fluor::host::WakeSender;
+    use std::sync::Arc;
+
+    pub fn run(_hotkey: Hotkey, chord: &str, _proxy: Arc<dyn WakeSender<crate::ui::PhotonEvent>>) {
+        crate::logf!("HOTKEY: no backend for this platform yet — '{}' not registered", chord);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ctrl_shift_p_into_platform_codes() {
+        let hk = Hotkey::parse("Ctrl+Shift+P").expect("parse");
+        assert_eq!(hk.mods, Mods { ctrl: true, shift: true, alt: false, logo: false });
+        assert_eq!(hk.key, HotkeyKey::Char('P'));
+        // X11: XK_p with ShiftMask | ControlMask.
+        assert_eq!(hk.to_x11(), (0x70, 0b101));
+        // Win32: MOD_NOREPEAT | MOD_CONTROL | MOD_SHIFT, VK 'P'.
+        assert_eq!(hk.to_win32(), (0x4006, 0x50));
+        // Case and spacing don't matter; modifier order doesn't either.
+        assert_eq!(Hotkey::parse(" shift + ctrl + p "), Ok(hk));
+    }
+
+    #[test]
+    fn rejects_unusable_chords() {
+        assert!(Hotkey::parse("P").is_err()); // no modifier
+        assert!(Hotkey::parse("Ctrl+Shift").is_err()); // no key
+        assert!(Hotkey::parse("Ctrl+P+Shift").is_err()); // key not last
+        assert!(Hotkey::parse("Ctrl+Hyper").is_err()); // unknown key
+        assert!(Hotkey::parse("Ctrl++P").is_err()); // empty component
+        assert_eq!(Hotkey::parse("Alt+F12").map(|h| h.to_win32()), Ok((0x4001, 0x7B)));
+    }
+}
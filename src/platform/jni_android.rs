@@ -487,7 +487,7 @@ pub extern "C" fn Java_com_photon_messenger_PhotonActivity_nativeOnScale(
     ctx.shell.on_scale(scale_factor);
 }
 
-/// Avatar from image picker. NOT in AndroidShell — photon-specific (decodes via the existing avatar pipeline). Funnels raw file bytes (JPEG/PNG/WebP — Android side intentionally does NOT decode thru `BitmapFactory` because that destroys ICC profile data) thru `PhotonApp::set_avatar_from_file`, which encodes to VSF, saves to the encrypted handle-keyed store, reloads, colour-converts to BT.2020 γ=2.0 for the surface buffer, and (when a handle_proof is available) uploads to FGTW.
+/// Avatar from image picker. NOT in AndroidShell — photon-specific (decodes via the existing avatar pipeline). Funnels raw file bytes (JPEG/PNG/WebP — Android side intentionally does NOT decode thru `BitmapFactory` because that destroys ICC profile data) to the crop screen (`PhotonApp::frame_avatar`), then thru `PhotonApp::set_avatar_from_file` with the framed square, which encodes to VSF, saves to the encrypted handle-keyed store, reloads, colour-converts to BT.2020 γ=2.0 for the surface buffer, and (when a handle_proof is available) uploads to FGTW.
 #[cfg(target_os = "android")]
 #[no_mangle]
 pub extern "C" fn Java_com_photon_messenger_PhotonActivity_nativeSetAvatarFromFile(
@@ -506,7 +506,7 @@ pub extern "C" fn Java_com_photon_messenger_PhotonActivity_nativeSetAvatarFromFi
             return;
         }
     };
-    ctx.shell.app().set_avatar_from_file(bytes);
+    ctx.shell.app().frame_avatar(bytes);
 }
 
 /// Per-frame poll for the sticky session broadcast signal. Returns `1` after a successful attest (Kotlin should call `service.sendSessionBroadcast()`), `-1` after a vault nuke (Kotlin should call `service.clearSessionBroadcast()`), `0` otherwise. One-shot.
@@ -642,6 +642,136 @@ pub extern "C" fn Java_com_photon_messenger_PhotonNfc_nativeOnNfcSecret(
     }
 }
 
+/// A `photon://add/…` VIEW intent (link clicked in a browser or another app) → the link carrier the tick drains. Static, no context pointer: the intent can arrive in onCreate before the native context exists.
+#[cfg(target_os = "android")]
+#[no_mangle]
+pub extern "C" fn Java_com_photon_messenger_PhotonActivity_nativeOnLink(
+    mut env: JNIEnv<'_>,
+    _class: JClass<'_>,
+    link: JString<'_>,
+) {
+    match env.get_string(&link) {
+        Ok(s) => {
+            crate::platform::link::on_link(s.into());
+            info!("PhotonActivity: contact link received");
+        }
+        Err(e) => error!("nativeOnLink: read link failed: {:?}", e),
+    }
+}
+
+// ── PhotonQr bridge (verification-QR scanning): same lifecycle as PhotonNfc — the Kotlin object registers at nativeInit, Rust opens the scanner thru the global ref, the scanned bytes come down via nativeOnQrScanned. ──
+#[cfg(target_os = "android")]
+static PHOTON_QR: std::sync::OnceLock<(jni::JavaVM, jni::objects::GlobalRef)> = std::sync::OnceLock::new();
+
+#[cfg(target_os = "android")]
+#[no_mangle]
+pub extern "C" fn Java_com_photon_messenger_PhotonQr_nativeInit(
+    env: JNIEnv<'_>,
+    obj: jni::objects::JObject<'_>,
+) {
+    let vm = env.get_java_vm();
+    let gref = env.new_global_ref(&obj);
+    if let (Ok(vm), Ok(gref)) = (vm, gref) {
+        let _ = PHOTON_QR.set((vm, gref));
+        info!("PhotonQr: bridge registered");
+    }
+}
+
+/// Kotlin scanner result → the code's text as UTF-8 bytes. Capped at 4 KiB (the largest QR holds under 3 KB) so a hostile code can't hand us an arbitrary buffer.
+#[cfg(target_os = "android")]
+#[no_mangle]
+pub extern "C" fn Java_com_photon_messenger_PhotonQr_nativeOnQrScanned(
+    mut env: JNIEnv<'_>,
+    _obj: jni::objects::JObject<'_>,
+    bytes: jni::objects::JByteArray<'_>,
+) {
+    if let Ok(v) = env.convert_byte_array(&bytes) {
+        if !v.is_empty() && v.len() <= 4 << 10 {
+            crate::network::qr_scan::on_scanned(v);
+            info!("PhotonQr: code scanned");
+        }
+    }
+}
+
+/// Call a no-arg PhotonQr method ("startScan") from any Rust thread. No-op if the bridge never registered.
+#[cfg(target_os = "android")]
+pub fn qr_call(method: &str) {
+    let Some((vm, obj)) = PHOTON_QR.get() else {
+        return;
+    };
+    if let Ok(mut env) = vm.attach_current_thread() {
+        if env.call_method(obj.as_obj(), method, "()V", &[]).is_err() {
+            let _ = env.exception_clear();
+        }
+    }
+}
+
+// ── PhotonVoice bridge (voice memos): same lifecycle as PhotonQr — the Kotlin object registers at nativeInit, Rust drives record/play thru the global ref, a finished clip comes down via nativeOnRecorded. ──
+#[cfg(target_os = "android")]
+static PHOTON_VOICE: std::sync::OnceLock<(jni::JavaVM, jni::objects::GlobalRef)> = std::sync::OnceLock::new();
+
+#[cfg(target_os = "android")]
+#[no_mangle]
+pub extern "C" fn Java_com_photon_messenger_PhotonVoice_nativeInit(
+    env: JNIEnv<'_>,
+    obj: jni::objects::JObject<'_>,
+) {
+    let vm = env.get_java_vm();
+    let gref = env.new_global_ref(&obj);
+    if let (Ok(vm), Ok(gref)) = (vm, gref) {
+        let _ = PHOTON_VOICE.set((vm, gref));
+        info!("PhotonVoice: bridge registered");
+    }
+}
+
+/// Kotlin recorder result → the Ogg-Opus clip, its length, and the level samples. Oversized clips are dropped here; `VoiceMemo::from_recording` holds the length cap.
+#[cfg(target_os = "android")]
+#[no_mangle]
+pub extern "C" fn Java_com_photon_messenger_PhotonVoice_nativeOnRecorded(
+    env: JNIEnv<'_>,
+    _obj: jni::objects::JObject<'_>,
+    opus: jni::objects::JByteArray<'_>,
+    duration_ms: jni::sys::jint,
+    levels: jni::objects::JByteArray<'_>,
+) {
+    let (Ok(opus), Ok(levels)) = (env.convert_byte_array(&opus), env.convert_byte_array(&levels)) else {
+        return;
+    };
+    if opus.len() <= crate::types::MAX_VOICE_BYTES && duration_ms > 0 {
+        crate::platform::voice::on_recorded(opus, duration_ms as u32, levels);
+        info!("PhotonVoice: memo recorded ({} ms)", duration_ms);
+    }
+}
+
+/// Call a no-arg PhotonVoice method ("startRecording" / "stopRecording" / "cancelRecording") from any Rust thread. No-op if the bridge never registered.
+#[cfg(target_os = "android")]
+pub fn voice_call(method: &str) {
+    let Some((vm, obj)) = PHOTON_VOICE.get() else {
+        return;
+    };
+    if let Ok(mut env) = vm.attach_current_thread() {
+        if env.call_method(obj.as_obj(), method, "()V", &[]).is_err() {
+            let _ = env.exception_clear();
+        }
+    }
+}
+
+/// Call a single-byte-array PhotonVoice method ("play") — the clip to play.
+#[cfg(target_os = "android")]
+pub fn voice_call_bytes(method: &str, a: &[u8]) {
+    let Some((vm, obj)) = PHOTON_VOICE.get() else {
+        return;
+    };
+    if let Ok(mut env) = vm.attach_current_thread() {
+        let Ok(arr) = env.byte_array_from_slice(a) else {
+            return;
+        };
+        if env.call_method(obj.as_obj(), method, "([B)V", &[(&arr).into()]).is_err() {
+            let _ = env.exception_clear();
+        }
+    }
+}
+
 pub fn nfc_call(method: &str) {
     let Some((vm, obj)) = PHOTON_NFC.get() else {
         return;
@@ -0,0 +1,45 @@
+//! Incoming `photon://add/…` contact links ([`crate::types::ContactCard::to_link`]) — the hand-off from whatever opened the link to the running app.
+//! Every route lands in one carrier the tick drains: a launch argument (Linux `.desktop` `%u`, the Windows `HKCU\Software\Classes\photon` handler — both written by the installers), the second-launch control channel when a resident instance already runs (`add <link>`, see control.rs), and on Android the activity's VIEW intent (`nativeOnLink`). macOS registers the scheme in Info.plist, but delivers the URL as an Apple Event the window host doesn't surface yet — a click there opens Photon without the contact; paste still works everywhere.
+
+use std::sync::Mutex;
+
+/// The largest link we accept from outside. A card link is ~200 chars; anything far past that isn't ours.
+pub const MAX_LINK_LEN: usize = 1 << 10;
+
+static PENDING: Mutex<Option<String>> = Mutex::new(None);
+
+/// Queue a link for the app. Newest wins — two clicks before the tick runs only need the last. Oversized input is dropped here so no route has to remember the cap.
+pub fn on_link(link: String) {
+    if link.len() > MAX_LINK_LEN {
+        crate::logf!("LINK: dropped a {}-byte link (cap {})", link.len(), MAX_LINK_LEN);
+        return;
+    }
+    *PENDING.lock().unwrap() = Some(link);
+}
+
+/// Drain the queued link, if any (tick).
+pub fn take_link() -> Option<String> {
+    PENDING.lock().unwrap().take()
+}
+
+/// The first launch argument that looks like a photon link (scheme compared case-insensitively — some launchers normalise it). The OS appends the clicked URL to the registered command line.
+pub fn link_from_args(args: impl Iterator<Item = String>) -> Option<String> {
+    args.skip(1).find(|a| a.get(..9).is_some_and(|s| s.eq_ignore_ascii_case("photon://")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_link_among_launch_args() {
+        let args = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>().into_iter();
+        assert_eq!(link_from_args(args(&["photon-messenger", "--background"])), None);
+        assert_eq!(
+            link_from_args(args(&["photon-messenger", "--background", "Photon://add/abc"])).as_deref(),
+            Some("Photon://add/abc")
+        );
+        // argv[0] is the binary, never a link, even if a wrapper named it oddly.
+        assert_eq!(link_from_args(args(&["photon://add/x"])), None);
+    }
+}
@@ -1,5 +1,7 @@
 #[cfg(target_os = "android")]
 pub mod jni_android;
+pub mod link;
+pub mod voice;
 
 #[cfg(not(target_os = "android"))]
 pub mod autostart;
@@ -8,4 +10,8 @@ pub mod control;
 #[cfg(not(target_os = "android"))]
 pub mod desktop_notify;
 #[cfg(not(target_os = "android"))]
+pub mod hotkey;
+#[cfg(not(target_os = "android"))]
+pub mod rpc;
+#[cfg(not(target_os = "android"))]
 pub mod tray;
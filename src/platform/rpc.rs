@@ -0,0 +1,340 @@
+//! Local scripting socket: one JSON request per line in, one JSON response per line out. Five methods — `list_contacts`, `send_message {handle, text}`, `status {handle}`, and the whole-profile backup pair `export_backup {path, passphrase}` / `import_backup {path, passphrase}` (storage::backup; the archive is a file the script names) — served by the running app (or a [`HeadlessProfile`](crate::headless::HeadlessProfile)) thru the [`Target`] trait.
+//! Unlike the second-launch control channel's `show` (`control.rs`, unauthenticated "show yourself"), this one can send messages as the user, so every request carries a per-session token: 32 random bytes, hex, minted each launch into `<data_dir>/rpc.token`, a file created owner-only (never chmod-ed after the fact). A script reads the file and echoes it back; a process that can't read the user's data dir can't drive the app. The control channel's `add` verb carries the same token ([`token_matches`]).
+//! Transport: a Unix domain socket at `<data_dir>/rpc.sock`; on Windows a loopback TcpListener on an OS-chosen port, published in `<data_dir>/rpc.port` next to the token.
+//! The accept thread never touches app state: it parses + authenticates, parks the request in [`INBOX`] with a reply channel, and wakes the UI thread (`PhotonEvent::ControlRequest`); `tick` drains the inbox thru [`serve_pending`] on the thread that owns the contacts.
+
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// A request line's size cap — the largest legitimate line is a `send_message` carrying one chat message; a client that streams without a newline is cut off here instead of growing the buffer without bound.
+const MAX_LINE: u64 = 1 << 20;
+
+/// How long a connection waits for the UI thread to answer one request. The UI thread drains the inbox every tick, so this only trips if the app is wedged.
+const REPLY_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Request {
+    ListContacts,
+    SendMessage { handle: String, text: String },
+    Status { handle: String },
+    ExportBackup { path: String, passphrase: String },
+    ImportBackup { path: String, passphrase: String },
+}
+
+/// What the socket drives. Implemented by `PhotonApp` (live send over the chain) and `HeadlessProfile` (queued in the contact's outbox).
+pub trait Target {
+    /// Every friend: `{name, party_id, online}`.
+    fn contact_rows(&self) -> Vec<Value>;
+    /// Send (or queue) `text` to the contact for `handle`. `Ok(true)` = handed to the network now (the live app), `Ok(false)` = queued in the contact's outbox for the app to send on a later tick (a headless profile).
+    fn send_text(&mut self, handle: &str, text: &str) -> Result<bool, String>;
+    /// `None` = not a contact.
+    fn online(&self, handle: &str) -> Option<bool>;
+    /// The whole profile as one sealed archive ([`export_all`](crate::storage::backup::export_all)).
+    fn export_backup(&self, passphrase: &str) -> Result<Vec<u8>, String>;
+    /// Merge a sealed archive into the profile ([`import_all`](crate::storage::backup::import_all)) and pick up the contacts it brought.
+    fn import_backup(&mut self, archive: &[u8], passphrase: &str) -> Result<crate::storage::backup::ImportReport, String>;
+}
+
+/// Parse one request line, checking its token. Returns the request's `id` (echoed back verbatim, `null` if absent) alongside the request, or the id plus an error message.
+pub fn parse_request(line: &str, token: &str) -> Result<(Value, Request), (Value, String)> {
+    let v: Value = serde_json::from_str(line).map_err(|e| (Value::Null, format!("bad json: {}", e)))?;
+    let id = v.get("id").cloned().unwrap_or(Value::Null);
+    let sent = v.get("token").and_then(Value::as_str).unwrap_or("");
+    {
+        use subtle::ConstantTimeEq;
+        if !bool::from(sent.as_bytes().ct_eq(token.as_bytes())) {
+            return Err((id, "bad token".to_string()));
+        }
+    }
+    let param = |name: &str| -> Result<String, (Value, String)> {
+        v.get("params")
+            .and_then(|p| p.get(name))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| (id.clone(), format!("missing string param '{}'", name)))
+    };
+    let request = match v.get("method").and_then(Value::as_str) {
+        Some("list_contacts") => Request::ListContacts,
+        Some("send_message") => Request::SendMessage { handle: param("handle")?, text: param("text")? },
+        Some("status") => Request::Status { handle: param("handle")? },
+        Some("export_backup") => Request::ExportBackup { path: param("path")?, passphrase: param("passphrase")? },
+        Some("import_backup") => Request::ImportBackup { path: param("path")?, passphrase: param("passphrase")? },
+        Some(other) => return Err((id, format!("unknown method '{}'", other))),
+        None => return Err((id, "missing method".to_string())),
+    };
+    Ok((id, request))
+}
+
+/// Run one request against `target`, building the response object.
+pub fn dispatch(target: &mut dyn Target, id: Value, request: &Request) -> Value {
+    match request {
+        Request::ListContacts => json!({"id": id, "result": target.contact_rows()}),
+        Request::SendMessage { handle, text } => match target.send_text(handle, text) {
+            Ok(sent) => json!({"id": id, "result": {"sent": sent}}),
+            Err(e) => error(id, &e),
+        },
+        Request::Status { handle } => match target.online(handle) {
+            Some(online) => json!({"id": id, "result": {"online": online}}),
+            None => error(id, "not a contact"),
+        },
+        Request::ExportBackup { path, passphrase } => {
+            let written = target.export_backup(passphrase).and_then(|archive| {
+                crate::storage::write_file(std::path::Path::new(path), &archive, "backup archive")
+                    .map(|_| archive.len())
+                    .map_err(|e| e.to_string())
+            });
+            match written {
+                Ok(bytes) => json!({"id": id, "result": {"bytes": bytes}}),
+                Err(e) => error(id, &e),
+            }
+        }
+        Request::ImportBackup { path, passphrase } => {
+            let imported = crate::storage::read_file(std::path::Path::new(path), "backup archive")
+                .map_err(|e| e.to_string())
+                .and_then(|archive| target.import_backup(&archive, passphrase));
+            match imported {
+                Ok(r) => json!({"id": id, "result": {"contacts": r.contacts, "messages": r.messages, "entries": r.entries}}),
+                Err(e) => error(id, &e),
+            }
+        }
+    }
+}
+
+/// The `list_contacts` row for one contact. Contacts don't keep the handle string, so it's the petname (empty until they publish one) plus the party id.
+pub fn contact_row(c: &crate::types::Contact) -> Value {
+    json!({"name": c.petname, "party_id": hex::encode(c.handle_hash), "online": c.is_online})
+}
+
+fn error(id: Value, msg: &str) -> Value {
+    json!({"id": id, "error": msg})
+}
+
+/// Requests parked by the accept thread for the UI thread, each with the channel its connection is waiting on.
+static INBOX: std::sync::Mutex<Vec<(Value, Request, mpsc::Sender<Value>)>> = std::sync::Mutex::new(Vec::new());
+
+/// UI thread: answer every parked request. Called from `tick`; cheap when the inbox is empty.
+pub fn serve_pending(target: &mut dyn Target) {
+    let pending = std::mem::take(&mut *INBOX.lock().unwrap());
+    for (id, request, reply) in pending {
+        let _ = reply.send(dispatch(target, id, &request));
+    }
+}
+
+/// This session's token, once minted — the control channel checks its `add` verb against it.
+static SESSION_TOKEN: std::sync::Mutex<Option<String>> = std::sync::Mutex::new(None);
+
+/// Whether `sent` is this session's token. False before one was minted (scripting disabled → nothing authenticates).
+pub fn token_matches(sent: &[u8]) -> bool {
+    use subtle::ConstantTimeEq;
+    SESSION_TOKEN.lock().unwrap().as_ref().is_some_and(|t| bool::from(sent.ct_eq(t.as_bytes())))
+}
+
+/// Client side: the resident instance's token from `<data_dir>/rpc.token`, for a second launch that has to authenticate a control verb. `None` = unreadable (no resident instance minted one, or we're not its user).
+pub fn read_token(data_dir: &std::path::Path) -> Option<String> {
+    let bytes = crate::storage::read_file(&data_dir.join("rpc.token"), "rpc token").ok()?;
+    Some(String::from_utf8(bytes).ok()?.trim().to_string())
+}
+
+/// A fresh session token at `<data_dir>/rpc.token`. The file is created owner-only (0600 on Unix) under a random temp name, filled, fsynced and renamed over the last launch's token, so no reader ever sees it with looser permissions or half-written. Lives only for this launch: the next `spawn` mints another.
+fn mint_token(data_dir: &std::path::Path) -> std::io::Result<String> {
+    let token = hex::encode(rand::random::<[u8; 32]>());
+    std::fs::create_dir_all(data_dir)?;
+    let tmp = data_dir.join(format!("rpc.token.{}", hex::encode(rand::random::<[u8; 8]>())));
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let written = options
+        .open(&tmp)
+        .and_then(|mut f| {
+            f.write_all(token.as_bytes())?;
+            f.sync_all()
+        })
+        .and_then(|_| std::fs::rename(&tmp, data_dir.join("rpc.token")));
+    if written.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    written.map(|_| token)
+}
+
+/// Serve one connection: a request per line until the client hangs up.
+fn serve_connection<S: std::io::Read + Write>(stream: S, token: &str, proxy: &std::sync::Arc<dyn fluor::host::WakeSender<crate::ui::PhotonEvent>>) {
+    let mut reader = BufReader::new(stream);
+    loop {
+        let mut line = String::new();
+        match (&mut reader).take(MAX_LINE).read_line(&mut line) {
+            Ok(0) | Err(_) => return,
+            Ok(_) => {}
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match parse_request(&line, token) {
+            Ok((id, request)) => {
+                let (tx, rx) = mpsc::channel();
+                INBOX.lock().unwrap().push((id.clone(), request, tx));
+                let _ = proxy.send(crate::ui::PhotonEvent::ControlRequest);
+                rx.recv_timeout(REPLY_TIMEOUT).unwrap_or_else(|_| error(id, "app did not answer"))
+            }
+            Err((id, e)) => {
+                crate::logf!("RPC: rejected request: {}", e);
+                error(id, &e)
+            }
+        };
+        let out = reader.get_mut();
+        if writeln!(out, "{}", response).is_err() {
+            return;
+        }
+    }
+}
+
+/// Resident side: mint the session token, bind the socket and start the accept thread. Called once from `set_event_proxy` — only the single-instance lock holder gets that far, so unlinking a leftover `rpc.sock` is safe. Failure logs and leaves scripting disabled; the app runs regardless.
+pub fn spawn(data_dir: &std::path::Path, proxy: std::sync::Arc<dyn fluor::host::WakeSender<crate::ui::PhotonEvent>>) {
+    let token = match mint_token(data_dir) {
+        Ok(t) => t,
+        Err(e) => {
+            crate::logf!("RPC: token write failed: {} (scripting socket disabled)", e);
+            return;
+        }
+    };
+    *SESSION_TOKEN.lock().unwrap() = Some(token.clone());
+    #[cfg(unix)]
+    {
+        let path = data_dir.join("rpc.sock");
+        let _ = std::fs::remove_file(&path);
+        let listener = match std::os::unix::net::UnixListener::bind(&path) {
+            Ok(l) => l,
+            Err(e) => {
+                crate::logf!("RPC: bind {} failed: {} (scripting socket disabled)", path.display(), e);
+                return;
+            }
+        };
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(s) = stream else { continue };
+                let (token, proxy) = (token.clone(), proxy.clone());
+                std::thread::spawn(move || serve_connection(s, &token, &proxy));
+            }
+        });
+    }
+    #[cfg(not(unix))]
+    {
+        let listener = match std::net::TcpListener::bind(("127.0.0.1", 0)) {
+            Ok(l) => l,
+            Err(e) => {
+                crate::logf!("RPC: bind failed: {} (scripting socket disabled)", e);
+                return;
+            }
+        };
+        let port = listener.local_addr().map(|a| a.port()).unwrap_or(0);
+        if let Err(e) = crate::storage::write_file(&data_dir.join("rpc.port"), port.to_string().as_bytes(), "rpc port") {
+            crate::logf!("RPC: port file write failed: {} (scripting socket disabled)", e);
+            return;
+        }
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(s) = stream else { continue };
+                let (token, proxy) = (token.clone(), proxy.clone());
+                std::thread::spawn(move || serve_connection(s, &token, &proxy));
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::headless::HeadlessProfile;
+    use crate::types::DevicePubkey;
+
+    const TOKEN: &str = "0123abcd";
+
+    #[test]
+    fn request_lines_parse_and_authenticate() {
+        let ok = r#"{"id":7,"token":"0123abcd","method":"send_message","params":{"handle":"bob","text":"hi"}}"#;
+        assert_eq!(
+            parse_request(ok, TOKEN).unwrap(),
+            (json!(7), Request::SendMessage { handle: "bob".into(), text: "hi".into() })
+        );
+        assert_eq!(
+            parse_request(r#"{"token":"0123abcd","method":"list_contacts"}"#, TOKEN).unwrap(),
+            (Value::Null, Request::ListContacts)
+        );
+
+        let err = |line: &str| parse_request(line, TOKEN).unwrap_err();
+        assert_eq!(err(r#"{"id":1,"token":"nope","method":"list_contacts"}"#), (json!(1), "bad token".to_string()));
+        assert_eq!(err(r#"{"id":2,"method":"list_contacts"}"#).1, "bad token");
+        assert_eq!(err(r#"{"id":3,"token":"0123abcd","method":"status"}"#).1, "missing string param 'handle'");
+        assert_eq!(err(r#"{"id":4,"token":"0123abcd","method":"rm -rf"}"#).1, "unknown method 'rm -rf'");
+        assert_eq!(
+            err(r#"{"id":5,"token":"0123abcd","method":"export_backup","params":{"path":"/tmp/p.vsf"}}"#).1,
+            "missing string param 'passphrase'"
+        );
+        assert_eq!(
+            parse_request(r#"{"token":"0123abcd","method":"import_backup","params":{"path":"/tmp/p.vsf","passphrase":"pw"}}"#, TOKEN).unwrap().1,
+            Request::ImportBackup { path: "/tmp/p.vsf".into(), passphrase: "pw".into() }
+        );
+        assert!(err("not json").1.starts_with("bad json"));
+    }
+
+    #[test]
+    fn control_link_handoff_needs_the_session_token() {
+        *SESSION_TOKEN.lock().unwrap() = Some(TOKEN.to_string());
+        assert!(token_matches(b"0123abcd"));
+        assert!(!token_matches(b"0123abce"));
+        assert!(!token_matches(b""));
+    }
+
+    #[test]
+    fn send_message_reaches_the_target_and_is_queued() {
+        let seed = *ihi::handle_to_hash("me-rpc-test").as_bytes();
+        let device = [48u8; 32];
+        let remove = || {
+            if let Ok(ring) = kete::vault_ring_paths(crate::storage::APP, &seed, &device) {
+                for p in &ring {
+                    let _ = std::fs::remove_file(p);
+                }
+            }
+        };
+        remove();
+
+        let mut profile = HeadlessProfile::open(seed, device).unwrap();
+        profile.add_contact("bob", [7u8; 32], DevicePubkey::from_bytes([8u8; 32])).unwrap();
+
+        let line = r#"{"id":"a","token":"0123abcd","method":"send_message","params":{"handle":"bob","text":"from a script"}}"#;
+        let (id, request) = parse_request(line, TOKEN).unwrap();
+        assert_eq!(dispatch(&mut profile, id, &request), json!({"id": "a", "result": {"sent": false}}));
+        assert_eq!(profile.pending("bob").unwrap()[0].content, "from a script");
+
+        let (id, request) = parse_request(r#"{"id":"b","token":"0123abcd","method":"status","params":{"handle":"carol"}}"#, TOKEN).unwrap();
+        assert_eq!(dispatch(&mut profile, id, &request), json!({"id": "b", "error": "not a contact"}));
+        let listed = dispatch(&mut profile, json!("c"), &Request::ListContacts);
+        assert_eq!(listed["result"].as_array().unwrap().len(), 1);
+
+        drop(profile);
+        remove();
+    }
+
+    #[test]
+    fn minted_token_is_owner_only_from_the_start() {
+        let dir = std::env::temp_dir().join(format!("photon-rpc-token-{}", std::process::id()));
+        let first = mint_token(&dir).unwrap();
+        let second = mint_token(&dir).unwrap();
+        assert_ne!(first, second, "each launch mints a new token");
+        assert_eq!(read_token(&dir).as_deref(), Some(second.as_str()));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(dir.join("rpc.token")).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let leftovers: Vec<_> = std::fs::read_dir(&dir).unwrap().filter_map(Result::ok).map(|e| e.file_name()).collect();
+        assert_eq!(leftovers, ["rpc.token"], "no temp file is left behind");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
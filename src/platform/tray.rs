@@ -1,12 +1,70 @@
-//! System-tray presence for desktop resident mode — the thing next to the clock. Left-click (or menu "Show") surfaces the hidden window thru the same `PhotonEvent::ShowWindow` path the second-launch handoff uses; "Exit" is THE deliberate quit affordance residency was missing (close hides, tray exits). The icon is the round orb — same circular-mask discipline as `fluor::host::icon::Icon::to_rgba_circular`, sourced from the shipped round asset so tray and taskbar can't disagree.
+//! System-tray presence for desktop resident mode — the thing next to the clock. Left-click TOGGLES the window (`PhotonEvent::ToggleWindow`: hide when shown, surface when hidden); menu "Show" always surfaces thru the same `PhotonEvent::ShowWindow` path the second-launch handoff uses; the tooltip carries the unread total ([`set_unread`]); "Exit" is THE deliberate quit affordance residency was missing (close hides, tray exits). The icon is the round orb — same circular-mask discipline as `fluor::host::icon::Icon::to_rgba_circular`, sourced from the shipped round asset so tray and taskbar can't disagree.
 //!
 //! Linux: StatusNotifierItem via ksni (pure Rust zbus — no GTK, cross-compiles clean). GNOME needs the AppIndicator extension to SHOW SNI items (KDE/XFCE show them natively); without it the icon simply doesn't appear and nothing else breaks — resident behaviour still works via the second-launch handoff.
 //! Windows (Shell_NotifyIcon) and macOS (NSStatusItem, main-thread-bound) are the next backends; until then `spawn` logs and returns, and residency works without a tray there too.
 
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Unread-message total across every conversation, as the UI thread last published it. The tray backends read it when building the tooltip; [`set_unread`] nudges them to re-read on change.
+static UNREAD: AtomicU32 = AtomicU32::new(0);
+
+/// What a close request does, given the residency setting and the one-shot deliberate-quit override (Shift+Escape / tray Exit).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloseAction {
+    /// Hide the window; network, timers and notifications keep running behind the tray icon.
+    Hide,
+    /// Let the host exit the process.
+    Exit,
+}
+
+/// The close-behaviour decision `on_close_requested` acts on: resident mode hides unless the user asked for the real quit; non-resident always exits.
+pub fn close_action(resident: bool, exit_requested: bool) -> CloseAction {
+    if resident && !exit_requested {
+        CloseAction::Hide
+    } else {
+        CloseAction::Exit
+    }
+}
+
+/// Tooltip line for a given unread total — just the name when there's nothing waiting, so a quiet tray reads quiet.
+pub fn tooltip_text(unread: u32) -> String {
+    match unread {
+        0 => "Photon".to_string(),
+        1 => "Photon \u{2014} 1 unread message".to_string(),
+        n => format!("Photon \u{2014} {n} unread messages"),
+    }
+}
+
+/// Publish the unread total. Called every tick; a no-op unless the number actually moved, so the backends only repaint their tooltip on a real change.
+pub fn set_unread(total: u32) {
+    if UNREAD.swap(total, Ordering::Relaxed) == total {
+        return;
+    }
+    #[cfg(target_os = "linux")]
+    linux::refresh();
+    #[cfg(target_os = "windows")]
+    windows_tray::refresh();
+    #[cfg(target_os = "macos")]
+    macos_tray::refresh();
+}
+
 #[cfg(target_os = "linux")]
 mod linux {
     use fluor::host::WakeSender;
-    use std::sync::Arc;
+    use std::sync::{Arc, OnceLock};
+
+    /// The live SNI handle — kept (instead of forgotten) so an unread change can ask the host to re-read the tooltip.
+    pub static HANDLE: OnceLock<ksni::Handle<PhotonTray>> = OnceLock::new();
+
+    /// Ask the status-bar host to re-query our properties (tooltip). Rides the shared tokio runtime like the SNI service itself.
+    pub fn refresh() {
+        if let Some(handle) = HANDLE.get() {
+            let handle = handle.clone();
+            crate::network::http::runtime().spawn(async move {
+                let _ = handle.update(|_| {}).await;
+            });
+        }
+    }
 
     pub struct PhotonTray {
         pub proxy: Arc<dyn WakeSender<crate::ui::PhotonEvent>>,
@@ -19,6 +77,12 @@ mod linux {
         fn title(&self) -> String {
             "Photon".into()
         }
+        fn tool_tip(&self) -> ksni::ToolTip {
+            ksni::ToolTip {
+                title: super::tooltip_text(super::UNREAD.load(super::Ordering::Relaxed)),
+                ..Default::default()
+            }
+        }
         fn icon_pixmap(&self) -> Vec<ksni::Icon> {
             // The shipped round RGBA asset (transparent corners, AA rim) → SNI's network-byte-order ARGB32.
             let Ok(img) = image::load_from_memory(include_bytes!("../../assets/icon-64.png")) else {
@@ -34,7 +98,7 @@ mod linux {
             vec![ksni::Icon { width: w, height: h, data: argb }]
         }
         fn activate(&mut self, _x: i32, _y: i32) {
-            let _ = self.proxy.send(crate::ui::PhotonEvent::ShowWindow);
+            let _ = self.proxy.send(crate::ui::PhotonEvent::ToggleWindow);
         }
         fn menu(&self) -> Vec<ksni::menu::MenuItem<Self>> {
             use ksni::menu::*;
@@ -71,8 +135,8 @@ pub fn spawn(proxy: std::sync::Arc<dyn fluor::host::WakeSender<crate::ui::Photon
     crate::network::http::runtime().spawn(async move {
         match tray.spawn().await {
             Ok(handle) => {
-                // The handle is the update/shutdown capability; the icon lives for the process in v1 (despawn-on-toggle-off comes with a handle plumb-thru), so park it forever.
-                std::mem::forget(handle);
+                // The handle is the update/shutdown capability; the icon lives for the process in v1 (despawn-on-toggle-off comes later), so park it for the tooltip refreshes.
+                let _ = linux::HANDLE.set(handle);
                 crate::log("TRAY: orb parked next to the clock (SNI; GNOME needs the AppIndicator extension to show it)");
             }
             Err(e) => crate::logf!("TRAY: SNI registration failed ({}) — no status-bar host? resident mode still works via relaunch-to-surface", e),
@@ -82,6 +146,7 @@ pub fn spawn(proxy: std::sync::Arc<dyn fluor::host::WakeSender<crate::ui::Photon
 
 #[cfg(target_os = "windows")]
 mod windows_tray {
+    use std::sync::atomic::{AtomicIsize, Ordering};
     use std::sync::Arc;
     use std::sync::OnceLock;
 
@@ -91,11 +156,11 @@ mod windows_tray {
     use windows::Win32::Graphics::Gdi::{CreateBitmap, DeleteObject};
     use windows::Win32::System::LibraryLoader::GetModuleHandleW;
     use windows::Win32::UI::Shell::{
-        Shell_NotifyIconW, NIF_ICON, NIF_MESSAGE, NIF_TIP, NIM_ADD, NOTIFYICONDATAW,
+        Shell_NotifyIconW, NIF_ICON, NIF_MESSAGE, NIF_TIP, NIM_ADD, NIM_MODIFY, NOTIFYICONDATAW,
     };
     use windows::Win32::UI::WindowsAndMessaging::{
         AppendMenuW, CreateIconIndirect, CreatePopupMenu, CreateWindowExW, DefWindowProcW,
-        DispatchMessageW, GetCursorPos, GetMessageW, RegisterClassW, RegisterWindowMessageW,
+        DispatchMessageW, GetCursorPos, GetMessageW, PostMessageW, RegisterClassW, RegisterWindowMessageW,
         SetForegroundWindow, TrackPopupMenu, TranslateMessage, HICON, HMENU, ICONINFO, MF_STRING,
         MSG, TPM_BOTTOMALIGN, TPM_NONOTIFY, TPM_RETURNCMD, TPM_RIGHTBUTTON, WINDOW_EX_STYLE,
         WINDOW_STYLE, WM_APP, WM_LBUTTONUP, WM_RBUTTONUP, WNDCLASSW,
@@ -106,7 +171,12 @@ mod windows_tray {
     /// Explorer's "TaskbarCreated" broadcast id — a shell restart destroys every tray icon, and re-adding on this message is how an icon survives it.
     static TASKBAR_CREATED: OnceLock<u32> = OnceLock::new();
 
+    /// The hidden tray window, as a raw handle value (HWND isn't Send) — the UI thread posts tooltip refreshes to it; 0 until the pump thread has created it.
+    static TRAY_HWND: AtomicIsize = AtomicIsize::new(0);
+
     const WM_TRAY_CALLBACK: u32 = WM_APP + 1;
+    /// Posted by [`refresh`]: re-read the unread total and NIM_MODIFY the tooltip on the pump thread that owns the icon.
+    const WM_TRAY_REFRESH: u32 = WM_APP + 2;
     const MENU_SHOW: usize = 1;
     const MENU_EXIT: usize = 2;
 
@@ -139,6 +209,23 @@ mod windows_tray {
         icon
     }
 
+    /// Copy the current tooltip text into the fixed 128-wide `szTip` field (truncating, NUL-terminated by `wide`).
+    fn fill_tip(nid: &mut NOTIFYICONDATAW) {
+        let tip = wide(&super::tooltip_text(super::UNREAD.load(Ordering::Relaxed)));
+        let n = tip.len().min(nid.szTip.len());
+        nid.szTip[..n].copy_from_slice(&tip[..n]);
+    }
+
+    /// Cross-thread nudge: the icon belongs to the pump thread, so post rather than touch it from the UI thread.
+    pub fn refresh() {
+        let raw = TRAY_HWND.load(Ordering::Relaxed);
+        if raw != 0 {
+            unsafe {
+                let _ = PostMessageW(HWND(raw as *mut _), WM_TRAY_REFRESH, WPARAM(0), LPARAM(0));
+            }
+        }
+    }
+
     unsafe fn add_icon(hwnd: HWND) {
         let mut nid = NOTIFYICONDATAW {
             cbSize: std::mem::size_of::<NOTIFYICONDATAW>() as u32,
@@ -151,17 +238,28 @@ mod windows_tray {
         if let Some(icon) = orb_icon() {
             nid.hIcon = icon;
         }
-        let tip = wide("Photon");
-        nid.szTip[..tip.len().min(128)].copy_from_slice(&tip[..tip.len().min(128)]);
+        fill_tip(&mut nid);
         let _ = Shell_NotifyIconW(NIM_ADD, &nid);
     }
 
     unsafe extern "system" fn wndproc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+        if msg == WM_TRAY_REFRESH {
+            let mut nid = NOTIFYICONDATAW {
+                cbSize: std::mem::size_of::<NOTIFYICONDATAW>() as u32,
+                hWnd: hwnd,
+                uID: 1,
+                uFlags: NIF_TIP,
+                ..Default::default()
+            };
+            fill_tip(&mut nid);
+            let _ = Shell_NotifyIconW(NIM_MODIFY, &nid);
+            return LRESULT(0);
+        }
         if msg == WM_TRAY_CALLBACK {
             match lparam.0 as u32 {
                 WM_LBUTTONUP => {
                     if let Some(proxy) = PROXY.get() {
-                        let _ = proxy.send(crate::ui::PhotonEvent::ShowWindow);
+                        let _ = proxy.send(crate::ui::PhotonEvent::ToggleWindow);
                     }
                 }
                 WM_RBUTTONUP => {
@@ -245,6 +343,7 @@ mod windows_tray {
                     return;
                 };
                 add_icon(hwnd);
+                TRAY_HWND.store(hwnd.0 as isize, Ordering::Relaxed);
                 crate::log("TRAY: orb parked next to the clock (Shell_NotifyIcon; Windows may fold new icons into the ^ overflow until the user drags them out)");
                 let mut msg = MSG::default();
                 while GetMessageW(&mut msg, None, 0, 0).as_bool() {
@@ -272,7 +371,7 @@ mod macos_tray {
     use objc2::runtime::AnyObject;
     use objc2::{define_class, msg_send, AllocAnyThread, MainThreadMarker, MainThreadOnly};
     use objc2_app_kit::{NSImage, NSMenu, NSMenuItem, NSStatusBar, NSStatusItem};
-    use objc2_foundation::{ns_string, NSData, NSObject, NSSize};
+    use objc2_foundation::{ns_string, NSData, NSObject, NSSize, NSString};
 
     /// The wake proxy — a process singleton like the Windows backend; the target object reads it from here so it stays `'static` without threading lifetimes thru objc.
     static PROXY: OnceLock<Arc<dyn WakeSender<crate::ui::PhotonEvent>>> = OnceLock::new();
@@ -303,6 +402,20 @@ mod macos_tray {
         }
     );
 
+    /// Re-set the status button's tooltip from the unread total. Main-thread only (AppKit); `set_unread` is called from the UI thread, which IS the main thread under winit.
+    pub fn refresh() {
+        let (Some(&raw), Some(mtm)) = (PARKED.get(), MainThreadMarker::new()) else {
+            return;
+        };
+        unsafe {
+            let item = &*(raw as *const NSStatusItem);
+            if let Some(button) = item.button(mtm) {
+                let tip = NSString::from_str(&super::tooltip_text(super::UNREAD.load(super::Ordering::Relaxed)));
+                let _: () = msg_send![&*button, setToolTip: &*tip];
+            }
+        }
+    }
+
     pub fn spawn(proxy: Arc<dyn WakeSender<crate::ui::PhotonEvent>>) {
         if PROXY.set(proxy).is_err() {
             return; // One icon per process.
@@ -349,6 +462,7 @@ mod macos_tray {
             let _ = PARKED.set(Retained::into_raw(item) as usize);
             std::mem::forget(target);
         }
+        refresh();
         crate::log("TRAY: orb parked in the menu bar (NSStatusItem)");
     }
 }
@@ -363,3 +477,23 @@ pub fn spawn(proxy: std::sync::Arc<dyn fluor::host::WakeSender<crate::ui::Photon
 pub fn spawn(_proxy: std::sync::Arc<dyn fluor::host::WakeSender<crate::ui::PhotonEvent>>) {
     crate::log("TRAY: no backend for this platform — residency still works via relaunch-to-surface");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn close_hides_only_when_resident_and_not_a_deliberate_quit() {
+        assert_eq!(close_action(true, false), CloseAction::Hide);
+        assert_eq!(close_action(true, true), CloseAction::Exit);
+        assert_eq!(close_action(false, false), CloseAction::Exit);
+        assert_eq!(close_action(false, true), CloseAction::Exit);
+    }
+
+    #[test]
+    fn tooltip_names_the_unread_total() {
+        assert_eq!(tooltip_text(0), "Photon");
+        assert_eq!(tooltip_text(1), "Photon \u{2014} 1 unread message");
+        assert_eq!(tooltip_text(12), "Photon \u{2014} 12 unread messages");
+    }
+}
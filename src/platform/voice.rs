@@ -0,0 +1,66 @@
+//! Voice-memo capture + playback — the platform half of [`crate::types::VoiceMemo`].
+//!
+//! Android records with the system `MediaRecorder` straight to Ogg-Opus (API 29+) and plays with `MediaPlayer` (PhotonVoice.kt), so no codec ships in the binary. The finished clip comes down thru [`on_recorded`] with its length and level samples; the tick drains it with [`take_recorded`] and sends. Desktop doesn't support voice memos: there's no capture or playback backend, so it receives and shows them with a "not playable on desktop" note, offers no record control, and says so when one is tapped. Playback writes the clip to a cache file only long enough to hand MediaPlayer a descriptor, then deletes it.
+
+use std::sync::Mutex;
+
+/// A finished recording awaiting the tick: `(ogg_opus, duration_ms, levels)`. One deep — only one recording runs at a time.
+static RECORDED: Mutex<Option<(Vec<u8>, u32, Vec<u8>)>> = Mutex::new(None);
+
+/// Kotlin recorder result → here (via JNI).
+pub fn on_recorded(opus: Vec<u8>, duration_ms: u32, levels: Vec<u8>) {
+    *RECORDED.lock().unwrap() = Some((opus, duration_ms, levels));
+}
+
+/// Drain the finished recording, if any (UI tick).
+pub fn take_recorded() -> Option<(Vec<u8>, u32, Vec<u8>)> {
+    RECORDED.lock().unwrap().take()
+}
+
+/// Whether this platform can record — the compose bar offers hold-to-record only where it does something.
+pub fn can_record() -> bool {
+    cfg!(target_os = "android")
+}
+
+/// Whether this platform can play a memo back.
+pub fn can_play() -> bool {
+    cfg!(target_os = "android")
+}
+
+/// Start recording (press of the hold-to-record control).
+pub fn start_recording() {
+    imp::call("startRecording");
+}
+
+/// Stop and keep the recording (release) — it arrives thru [`on_recorded`].
+pub fn stop_recording() {
+    imp::call("stopRecording");
+}
+
+/// Stop and throw the recording away (released off the control, or the conversation closed mid-record).
+pub fn cancel_recording() {
+    imp::call("cancelRecording");
+}
+
+/// Play an Ogg-Opus clip, replacing whatever is playing.
+pub fn play(opus: &[u8]) {
+    imp::play(opus);
+}
+
+#[cfg(target_os = "android")]
+mod imp {
+    pub(super) fn call(method: &str) {
+        crate::platform::jni_android::voice_call(method);
+    }
+
+    pub(super) fn play(opus: &[u8]) {
+        crate::platform::jni_android::voice_call_bytes("play", opus);
+    }
+}
+
+#[cfg(not(target_os = "android"))]
+mod imp {
+    pub(super) fn call(_method: &str) {}
+
+    pub(super) fn play(_opus: &[u8]) {}
+}
@@ -0,0 +1,346 @@
+//! Whole-profile backup — one encrypted VSF archive carrying everything needed to bring this identity up on another machine: the contact index, each contact's state and avatar, every 1:1 conversation's messages, the friendship chains, the linked settings, and our own avatar.
+//!
+//! The vault is AEAD'd under a key that mixes in the DEVICE secret, so its bytes can't simply be copied to a new machine. The archive is instead sealed under a key derived from the identity seed AND a user passphrase — the identity seed alone is handle-derived (anyone who can type the handle can derive it), so it can't be the only secret. The passphrase is therefore what a leaked archive's attacker has to guess, so the key is stretched with Argon2id under a random per-archive salt (stored in the archive header): every guess costs [`ARGON_M_KIB`] of memory and several passes, and nothing precomputed carries over from one archive to the next.
+//!
+//! Import MERGES into whatever the target vault already holds: contacts dedupe by party id (`handle_hash`), messages by eagle-time (their row key), and any other entry is written only where the target has none — local data always wins. Not carried: the fleet key and sibling list (a new machine joins the fleet by pairing, not by restore) and the CLUTCH ceremony scratch (memory-only by design).
+//!
+//! [`rotate_encryption`], which moves a vault to a new seed on the SAME device, doesn't go through the archive walk at all: it copies every live record (the fleet key and siblings included), re-addressing only what is named by the vault seed. It is library-only — no screen or socket verb calls it: every session's vault seed IS its identity seed (handle-derived, so it never changes), leaving the app no seed change to follow. It's the migration for the day tohu's `vault_seed` stops mirroring `identity_seed`.
+
+use crate::storage::contacts::{
+    load_contact_list, load_conversation, save_contact_list, save_messages_page, ContactIdentity,
+};
+use crate::storage::{vault_key, FlatStorage, StorageError};
+use crate::types::ChatMessage;
+use vsf::schema::{SectionBuilder, SectionSchema, TypeConstraint};
+use vsf::VsfType;
+
+/// What an import added to the target vault (everything it skipped was already there).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ImportReport {
+    pub contacts: usize,
+    pub messages: usize,
+    pub entries: usize,
+}
+
+/// Archive plaintext schema. Rows are multi-value fields: `contact` mirrors the contact-index row, `msg` is one message, `entry` one raw vault entry (address + plaintext bytes).
+fn backup_schema() -> SectionSchema {
+    SectionSchema::new("backup")
+        .field("contact", TypeConstraint::Any) // hP proof, ke party id, ge avatar pin, x petname
+        .field("msg", TypeConstraint::Any) // ke party id, e6 time, x text, u3 out, u3 delivered, u3 recovered, v'r' ack hash (empty = none), v'r' image (empty = none), x file name (empty = no file), v'r' file bytes, v'r' voice clip (empty = no memo), u6 voice ms, v'r' voice waveform
+        .field("entry", TypeConstraint::Any) // x domain, v'r' scope (empty = the vault's own seed), v'r' bytes
+}
+
+/// One raw vault entry to carry: `vault_key(domain, scope)`, with `None` scope meaning the vault's OWN seed — re-scoped on merge, so the contacts index and settings land at the target vault's own address.
+type EntryRef = (&'static str, Option<[u8; 32]>);
+
+/// Archive header: magic + format version, then the Argon2id salt. Neither is secret; the AEAD'd body follows.
+const ARCHIVE_MAGIC: &[u8; 4] = b"pbk\x01";
+const SALT_LEN: usize = 16;
+/// Argon2id memory per guess, in KiB (64 MiB).
+pub const ARGON_M_KIB: u32 = 1 << 16;
+/// Argon2id passes and lanes. Together with the memory, a few hundred ms per key on a desktop — paid once per export/import, and per guess by anyone brute-forcing a leaked archive.
+const ARGON_T: u32 = 3;
+const ARGON_P: u32 = 1;
+
+/// Archive key: `Argon2id(identity_seed ‖ passphrase, salt)`. Any passphrase change yields an unrelated key, so a wrong one fails the AEAD rather than decoding garbage.
+fn backup_key(identity_seed: &[u8; 32], passphrase: &str, salt: &[u8; SALT_LEN]) -> Result<[u8; 32], StorageError> {
+    let params = argon2::Params::new(ARGON_M_KIB, ARGON_T, ARGON_P, Some(32)).map_err(|e| StorageError::Crypto(e.to_string()))?;
+    let mut input = zeroize::Zeroizing::new(Vec::with_capacity(32 + passphrase.len()));
+    input.extend_from_slice(identity_seed);
+    input.extend_from_slice(passphrase.as_bytes());
+    let mut key = [0u8; 32];
+    argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+        .hash_password_into(&input, salt, &mut key)
+        .map_err(|e| StorageError::Crypto(e.to_string()))?;
+    Ok(key)
+}
+
+fn parse_err(e: impl std::fmt::Display) -> StorageError {
+    StorageError::Parse(e.to_string())
+}
+
+fn raw(bytes: &[u8]) -> VsfType {
+    VsfType::v(b'r', bytes.to_vec())
+}
+
+fn as_raw(v: &VsfType) -> Option<&[u8]> {
+    match v {
+        VsfType::v(b'r', data) => Some(data),
+        _ => None,
+    }
+}
+
+fn as_32(v: &VsfType) -> Option<[u8; 32]> {
+    match v {
+        VsfType::hP(b) | VsfType::ke(b) | VsfType::v(b'r', b) => b.as_slice().try_into().ok(),
+        _ => None,
+    }
+}
+
+/// Export the whole profile held in `storage` (whose vault seed IS the identity seed) as one sealed archive.
+pub fn export_all(storage: &FlatStorage, passphrase: &str) -> Result<Vec<u8>, StorageError> {
+    use rand::RngCore;
+    let plain = snapshot(storage, storage.vault_seed())?;
+    let mut salt = [0u8; SALT_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let key = backup_key(storage.vault_seed(), passphrase, &salt)?;
+    let mut archive = ARCHIVE_MAGIC.to_vec();
+    archive.extend_from_slice(&salt);
+    archive.extend(crate::storage::encrypt_bytes(&plain, &key).map_err(StorageError::Crypto)?);
+    Ok(archive)
+}
+
+/// Merge a sealed archive into `storage`. The archive must belong to the same identity (its key mixes in this vault's seed) and passphrase; anything else fails the AEAD with nothing written.
+pub fn import_all(storage: &FlatStorage, archive: &[u8], passphrase: &str) -> Result<ImportReport, StorageError> {
+    let body = archive
+        .strip_prefix(ARCHIVE_MAGIC)
+        .filter(|rest| rest.len() > SALT_LEN)
+        .ok_or_else(|| StorageError::Parse("not a photon backup archive".to_string()))?;
+    let (salt, sealed) = body.split_at(SALT_LEN);
+    let key = backup_key(storage.vault_seed(), passphrase, salt.try_into().expect("SALT_LEN bytes"))?;
+    let plain = crate::storage::decrypt_bytes(sealed, &key).map_err(StorageError::Crypto)?;
+    let report = merge(storage, &plain)?;
+    crate::logf!(
+        "BACKUP: imported {} contacts, {} messages, {} entries",
+        report.contacts,
+        report.messages,
+        report.entries
+    );
+    Ok(report)
+}
+
+/// Vault entries addressed by the vault's OWN seed — the only raw addresses a rotation has to move. Every other entry is scoped by a peer's seed, a friendship id or our identity seed, none of which a rotation changes, so it keeps its address.
+const SEED_SCOPED: [&str; 4] = ["settings", "contacts", "siblings", "fleet_key"];
+
+/// Re-encrypt this device's vault under `new_seed`: EVERY live record of the old vault is copied into a fresh vault under the new one — seed-scoped entries re-addressed, conversation tables re-filed under the new seed's table ids — verified against the old vault's full live set, and only then is the old vault deleted. That delete is the commit point — a crash before it leaves the old vault whole and authoritative (a partial new vault from the crashed run is wiped on the next attempt); a crash after it leaves only the complete new one. Returns the number of records moved. Library-only: nothing in the app calls it yet (see the module doc).
+pub fn rotate_encryption(old_seed: [u8; 32], new_seed: [u8; 32], device_secret: [u8; 32]) -> Result<usize, StorageError> {
+    if old_seed == new_seed {
+        return Ok(0);
+    }
+    let app = crate::storage::APP;
+    let [new_primary, new_shadow] =
+        kete::vault_ring_paths(app, &new_seed, &device_secret).map_err(|e| StorageError::Vault(e.to_string()))?;
+    // Leftovers from an interrupted rotation are never trusted — the old vault is still the source of truth.
+    let _ = std::fs::remove_file(&new_primary);
+    let _ = std::fs::remove_file(&new_shadow);
+    let discard = |reason: StorageError| {
+        let _ = std::fs::remove_file(&new_primary);
+        let _ = std::fs::remove_file(&new_shadow);
+        reason
+    };
+
+    let (records, parties) = {
+        let old = FlatStorage::new(app, old_seed, device_secret)?;
+        let mut parties: Vec<[u8; 32]> = load_contact_list(&old)?.iter().map(|c| c.party_id).collect();
+        parties.extend(crate::storage::contacts::load_all_siblings([0u8; 32], &old).iter().map(|s| s.handle_hash));
+        (crate::storage::compact::live_records(&old)?, parties)
+    };
+    let moved = fill_rotated(&records, &parties, old_seed, new_seed, device_secret).map_err(discard)?;
+
+    let [old_primary, old_shadow] =
+        kete::vault_ring_paths(app, &old_seed, &device_secret).map_err(|e| StorageError::Vault(e.to_string()))?;
+    std::fs::remove_file(&old_primary).map_err(|e| StorageError::Vault(e.to_string()))?;
+    let _ = std::fs::remove_file(&old_shadow);
+    crate::logf!("STORAGE: rotated vault encryption ({} records)", moved);
+    Ok(moved)
+}
+
+/// Where a record of the vault under `old_seed` lives in the vault under `new_seed`.
+fn rotated_addr(addr: &[u8; 32], old_seed: &[u8; 32], new_seed: &[u8; 32]) -> [u8; 32] {
+    match SEED_SCOPED.iter().find(|d| vault_key(d, old_seed) == *addr) {
+        Some(d) => vault_key(d, new_seed),
+        None => *addr,
+    }
+}
+
+/// Write the old vault's live `records` into a fresh vault under `new_seed`, verify it against them, then re-file each party's conversation under the new table id.
+fn fill_rotated(records: &[([u8; 32], Vec<u8>)], parties: &[[u8; 32]], old_seed: [u8; 32], new_seed: [u8; 32], device_secret: [u8; 32]) -> Result<usize, StorageError> {
+    let new = FlatStorage::new(crate::storage::APP, new_seed, device_secret)?;
+    for (addr, bytes) in records {
+        new.write_addr(&rotated_addr(addr, &old_seed, &new_seed), bytes)?;
+    }
+    // Verify against the SOURCE's live set, record by record — not a re-walk of the copy through the same lens that made it.
+    for (addr, bytes) in records {
+        if new.read_addr(&rotated_addr(addr, &old_seed, &new_seed))?.as_deref() != Some(&bytes[..]) {
+            return Err(StorageError::Vault("rotation verify: new vault differs from old — old vault kept".to_string()));
+        }
+    }
+    for party in parties {
+        crate::storage::contacts::retable_conversation(party, &old_seed, &new)?;
+    }
+    Ok(records.len())
+}
+
+/// The plaintext snapshot of a vault's profile (the archive body). Our own avatar is addressed by `identity_seed` (the avatar module's key), so it carries an explicit scope, unlike the vault-scoped entries. What only makes sense on THIS device — the fleet key, the sibling index — is left out: a new machine joins the fleet by pairing.
+fn snapshot(storage: &FlatStorage, identity_seed: &[u8; 32]) -> Result<Vec<u8>, StorageError> {
+    let seed = *storage.vault_seed();
+    let mut builder = backup_schema().build();
+    let mut entries: Vec<EntryRef> = vec![("settings", None), ("avatar", Some(*identity_seed))];
+
+    for c in load_contact_list(storage)? {
+        builder = builder
+            .append_multi(
+                "contact",
+                vec![
+                    VsfType::hP(c.handle_proof.to_vec()),
+                    VsfType::ke(c.party_id.to_vec()),
+                    VsfType::ge(c.avatar_pin.to_vec()),
+                    VsfType::x(c.name.clone()),
+                ],
+            )
+            .map_err(parse_err)?;
+        entries.push(("state", Some(c.party_id)));
+        entries.push(("avatar", Some(c.party_id)));
+        if let Ok(contact) = crate::storage::contacts::load_contact_state(&c, storage) {
+            if let Some(fid) = contact.friendship_id {
+                entries.push(("chains", Some(*fid.as_bytes())));
+            }
+        }
+        for m in load_conversation(&c.party_id, storage)? {
+            builder = builder
+                .append_multi(
+                    "msg",
+                    vec![
+                        VsfType::ke(c.party_id.to_vec()),
+                        VsfType::e(vsf::types::EtType::e6(m.timestamp)),
+                        VsfType::x(m.content),
+                        VsfType::u3(m.is_outgoing as u8),
+                        VsfType::u3(m.delivered as u8),
+                        VsfType::u3(m.recovered as u8),
+                        raw(m.ack_hash.as_ref().map_or(&[][..], |h| &h[..])),
+                        raw(m.image.as_deref().unwrap_or_default()),
+                        VsfType::x(m.attachment.as_ref().map_or(String::new(), |f| f.name.clone())),
+                        raw(m.attachment.as_ref().map_or(&[][..], |f| &f.bytes[..])),
+                        raw(m.voice.as_ref().map_or(&[][..], |memo| &memo.opus[..])),
+                        VsfType::u6(m.voice.as_ref().map_or(0, |memo| memo.duration_ms as u64)),
+                        raw(m.voice.as_ref().map_or(&[][..], |memo| &memo.waveform[..])),
+                    ],
+                )
+                .map_err(parse_err)?;
+        }
+    }
+
+    for (domain, scope) in entries {
+        if let Some(bytes) = storage.read_addr(&vault_key(domain, &scope.unwrap_or(seed)))? {
+            let scope_bytes = scope.as_ref().map_or(&[][..], |s| &s[..]);
+            builder = builder
+                .append_multi("entry", vec![VsfType::x(domain.to_string()), raw(scope_bytes), raw(&bytes)])
+                .map_err(parse_err)?;
+        }
+    }
+
+    builder.encode().map_err(parse_err)
+}
+
+/// Merge a plaintext snapshot into `storage` — never overwriting what's already there.
+fn merge(storage: &FlatStorage, plain: &[u8]) -> Result<ImportReport, StorageError> {
+    let section = SectionBuilder::parse(backup_schema(), plain)
+        .map_err(|e| StorageError::Parse(format!("backup parse: {}", e)))?;
+    let mut report = ImportReport::default();
+
+    // Raw entries first, so a contact added below finds its state already in place.
+    for field in section.get_fields("entry") {
+        let v = &field.values;
+        let (Some(VsfType::x(domain)), Some(scope), Some(bytes)) = (v.first(), v.get(1).and_then(as_raw), v.get(2).and_then(as_raw)) else {
+            continue;
+        };
+        let scope: [u8; 32] = match scope.len() {
+            0 => *storage.vault_seed(),
+            _ => match scope.try_into() {
+                Ok(s) => s,
+                Err(_) => continue,
+            },
+        };
+        let addr = vault_key(domain, &scope);
+        if storage.read_addr(&addr)?.is_none() {
+            storage.write_addr(&addr, bytes)?;
+            report.entries += 1;
+        }
+    }
+
+    let mut list = load_contact_list(storage)?;
+    for field in section.get_fields("contact") {
+        let v = &field.values;
+        let (Some(handle_proof), Some(party_id)) = (v.first().and_then(as_32), v.get(1).and_then(as_32)) else {
+            continue;
+        };
+        if list.iter().any(|c| c.party_id == party_id) {
+            continue;
+        }
+        let avatar_pin = match v.get(2) {
+            Some(VsfType::ge(b)) if b.len() == 64 => b.as_slice().try_into().unwrap(),
+            _ => [0u8; 64],
+        };
+        let name = match v.get(3) {
+            Some(VsfType::x(s)) => s.clone(),
+            _ => String::new(),
+        };
+        list.push(ContactIdentity { handle_proof, party_id, name, avatar_pin });
+        report.contacts += 1;
+    }
+    if report.contacts > 0 {
+        save_contact_list(&list, storage)?;
+    }
+
+    // Messages, grouped per conversation so each one's existing rows are read once.
+    let mut incoming: Vec<([u8; 32], Vec<ChatMessage>)> = Vec::new();
+    for field in section.get_fields("msg") {
+        let v = &field.values;
+        let (Some(party), Some(VsfType::e(vsf::types::EtType::e6(timestamp))), Some(VsfType::x(content))) =
+            (v.first().and_then(as_32), v.get(1), v.get(2))
+        else {
+            continue;
+        };
+        let flag = |i: usize| v.get(i).and_then(|x| x.as_usize()).unwrap_or(0) != 0;
+        let msg = ChatMessage {
+            content: content.clone(),
+            timestamp: *timestamp,
+            is_outgoing: flag(3),
+            delivered: flag(4),
+            recovered: flag(5),
+            ack_hash: v.get(6).and_then(as_raw).and_then(|b| b.try_into().ok()),
+            failed: false,
+            image: v.get(7).and_then(as_raw).filter(|b| !b.is_empty()).map(<[u8]>::to_vec),
+            attachment: crate::storage::contacts::attachment_from_columns(
+                match v.get(8) {
+                    Some(VsfType::x(name)) if !name.is_empty() => Some(name.as_str()),
+                    _ => None,
+                },
+                v.get(9).and_then(as_raw),
+            ),
+            voice: crate::storage::contacts::voice_from_columns(
+                v.get(10).and_then(as_raw).filter(|b| !b.is_empty()),
+                v.get(11).and_then(|x| x.as_usize()).map(|ms| ms as u64),
+                v.get(12).and_then(as_raw),
+            ),
+        };
+        match incoming.iter_mut().find(|(p, _)| *p == party) {
+            Some((_, msgs)) => msgs.push(msg),
+            None => incoming.push((party, vec![msg])),
+        }
+    }
+    for (party, msgs) in incoming {
+        let have: std::collections::HashSet<i64> =
+            load_conversation(&party, storage)?.iter().map(|m| m.timestamp).collect();
+        let fresh: Vec<ChatMessage> = msgs.into_iter().filter(|m| !have.contains(&m.timestamp)).collect();
+        save_messages_page(&party, &fresh, storage)?;
+        report.messages += fresh.len();
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The archive key is Argon2id over the per-archive salt: the same seed and passphrase seal to a different key under each salt, and never to the old unsalted blake3 derivation. The vault round trips are in `tests/backup.rs`.
+    #[test]
+    fn backup_key_is_salted_argon2id() {
+        let seed = *ihi::handle_to_hash("me-backup-test").as_bytes();
+        let key = backup_key(&seed, "correct horse", &[1; SALT_LEN]).unwrap();
+        assert_eq!(key, backup_key(&seed, "correct horse", &[1; SALT_LEN]).unwrap());
+        assert_ne!(key, backup_key(&seed, "correct horse", &[2; SALT_LEN]).unwrap());
+        assert_ne!(key, backup_key(&seed, "wrong horse", &[1; SALT_LEN]).unwrap());
+        assert_ne!(key, blake3::derive_key("photon.backup.v0", &[&seed[..], b"correct horse"].concat()));
+    }
+}
@@ -1,10 +1,15 @@
 //! Cloud contact storage via FGTW blob endpoints.
 //!
-//! Stores encrypted contact list on FGTW so users can backup contacts. Each device gets its own blob slot (key includes device_secret).
+//! Stores encrypted contact list on FGTW so users can backup contacts. Each device gets its own blob slot (key includes device_secret); what the sibling devices hold reaches a download thru the fleet roster they push ([`merge_fleet_roster`]).
 //!
 //! Key derivation:
 //! - Storage key: BLAKE3(identity_seed || device_secret || "contacts_storage_key_v0")
-//! - Encryption key: BLAKE3(identity_seed || device_secret || "contacts_v0")
+//! - Sync key: BLAKE3(identity_seed || device_secret || "contacts_sync_v1" || generation) — used for nothing but this blob, so a leaked sync key exposes neither the seed nor any other key derived from it, and bumping [`SYNC_KEY_GENERATION`] re-keys the blob alone. The blob names its generation in a plaintext header ([`seal_sync_blob`]).
+//! - Legacy encryption key: BLAKE3(identity_seed || device_secret || "contacts_v0") — headerless blobs from older builds; read once, then re-sealed under the sync key by the next upload.
+//!
+//! Each row carries its contact's `roster_updated` as a version, so a download merges instead of clobbering ([`merge_cloud_contacts`], then [`merge_fleet_roster`] for the siblings' lists): contacts either side has are kept, a contact edited on both sides takes the newer edit, and a sibling's newer removal removes it.
+//!
+//! Uploads are batched: a contact change marks a [`SyncBatch`] rather than pushing the whole list at once, and the list goes up once the changes have gone quiet — a roster merge or a run of adds is one upload, not one each.
 //!
 //! Security:
 //! - identity_seed = BLAKE3(VsfType::x(handle)) - private
@@ -16,6 +21,7 @@ use blake3::Hasher;
 use vsf::schema::{SectionSchema, TypeConstraint};
 use vsf::{VsfSection, VsfType};
 
+use crate::network::fgtw::fleet::RosterEntry;
 use crate::storage::{decrypt_bytes, encrypt_bytes};
 use crate::types::{Contact, DevicePubkey, TrustLevel};
 
@@ -40,7 +46,7 @@ impl std::fmt::Display for CloudError {
 }
 
 /// Contact data stored in cloud (minimal for recovery) — the PIN-SET, never a handle string (docs/identity-profile.md).
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct CloudContact {
     pub handle_proof: [u8; 32],
     /// The pinned identity pubkey (party id).
@@ -52,6 +58,10 @@ pub struct CloudContact {
     pub device_pubkey: [u8; 32],
     pub trust_level: u8,
     pub added: i64,
+    /// Pinned to the top of the contacts list. An optional 8th row value — 7-value rows from older builds read as unpinned.
+    pub pinned: bool,
+    /// The row's version: the contact's `roster_updated` (last edit to a synced field). An optional 9th row value — older rows read as `added`, i.e. never edited.
+    pub updated: i64,
 }
 
 impl From<&Contact> for CloudContact {
@@ -64,6 +74,8 @@ impl From<&Contact> for CloudContact {
             device_pubkey: *c.public_identity.as_bytes(),
             trust_level: trust_level_to_u8(c.trust_level),
             added: c.added,
+            pinned: c.pinned,
+            updated: c.roster_updated,
         }
     }
 }
@@ -78,7 +90,7 @@ pub fn contacts_storage_key(identity_seed: &[u8; 32], device_secret: &[u8; 32])
     URL_SAFE_NO_PAD.encode(hash.as_bytes())
 }
 
-/// Derive encryption key for contacts blob
+/// Derive the pre-sync-key encryption key — only for opening headerless blobs older builds wrote.
 pub fn contacts_encryption_key(identity_seed: &[u8; 32], device_secret: &[u8; 32]) -> [u8; 32] {
     let mut hasher = Hasher::new();
     hasher.update(identity_seed);
@@ -87,6 +99,52 @@ pub fn contacts_encryption_key(identity_seed: &[u8; 32], device_secret: &[u8; 32
     *hasher.finalize().as_bytes()
 }
 
+/// Sync-key generation new blobs are sealed under. Bump to rotate: blobs carry their generation, so older ones still open.
+pub const SYNC_KEY_GENERATION: u8 = 1;
+
+/// Plaintext header of a sealed sync blob, followed by the generation byte.
+const SYNC_BLOB_MAGIC: &[u8; 3] = b"pcs";
+
+/// Derive the contacts sync key for `generation`.
+pub fn contacts_sync_key(identity_seed: &[u8; 32], device_secret: &[u8; 32], generation: u8) -> [u8; 32] {
+    let mut hasher = Hasher::new();
+    hasher.update(identity_seed);
+    hasher.update(device_secret);
+    hasher.update(b"contacts_sync_v1");
+    hasher.update(&[generation]);
+    *hasher.finalize().as_bytes()
+}
+
+/// Encrypt contacts for the cloud under the current sync key: `"pcs" ‖ generation ‖ ciphertext`.
+pub fn seal_sync_blob(
+    contacts: &[CloudContact],
+    identity_seed: &[u8; 32],
+    device_secret: &[u8; 32],
+) -> Result<Vec<u8>, CloudError> {
+    let key = contacts_sync_key(identity_seed, device_secret, SYNC_KEY_GENERATION);
+    let mut blob = SYNC_BLOB_MAGIC.to_vec();
+    blob.push(SYNC_KEY_GENERATION);
+    blob.extend(encode_contacts(contacts, &key)?);
+    Ok(blob)
+}
+
+/// Decrypt a cloud contacts blob: under the sync key of the generation it names, or — headerless, or a header that's really a legacy nonce — under the legacy key.
+pub fn open_sync_blob(
+    blob: &[u8],
+    identity_seed: &[u8; 32],
+    device_secret: &[u8; 32],
+) -> Result<Vec<CloudContact>, CloudError> {
+    if let Some(rest) = blob.strip_prefix(SYNC_BLOB_MAGIC) {
+        if let Some((&generation, sealed)) = rest.split_first() {
+            let key = contacts_sync_key(identity_seed, device_secret, generation);
+            if let Ok(contacts) = decode_contacts(sealed, &key) {
+                return Ok(contacts);
+            }
+        }
+    }
+    decode_contacts(blob, &contacts_encryption_key(identity_seed, device_secret))
+}
+
 /// Schema for cloud_contacts section
 fn cloud_contacts_schema() -> SectionSchema {
     SectionSchema::new("cloud_contacts")
@@ -117,6 +175,8 @@ pub fn encode_contacts(
                     VsfType::ke(c.device_pubkey.to_vec()),
                     VsfType::u3(c.trust_level),
                     VsfType::e(vsf::types::EtType::e6(c.added)),
+                    VsfType::u3(c.pinned as u8),
+                    VsfType::e(vsf::types::EtType::e6(c.updated)),
                 ],
             )
             .map_err(|e| CloudError::Parse(e.to_string()))?;
@@ -172,6 +232,11 @@ pub fn decode_contacts(
                 VsfType::e(vsf::types::EtType::e6(osc)) => *osc,
                 _ => 0,
             };
+            let pinned = matches!(field.values.get(7), Some(VsfType::u3(v)) if *v != 0);
+            let updated = match field.values.get(8) {
+                Some(VsfType::e(vsf::types::EtType::e6(osc))) => *osc,
+                _ => added,
+            };
 
             contacts.push(CloudContact {
                 handle_proof,
@@ -181,6 +246,8 @@ pub fn decode_contacts(
                 device_pubkey,
                 trust_level,
                 added,
+                pinned,
+                updated,
             });
         }
     }
@@ -200,8 +267,87 @@ impl CloudContact {
         );
         contact.trust_level = u8_to_trust_level(self.trust_level);
         contact.added = self.added;
+        contact.pinned = self.pinned;
+        contact.roster_updated = self.updated;
         contact
     }
+
+    /// Bring an existing local contact's synced fields to this row's. Identity (party id, device key) is never touched — the row is the same contact by `handle_proof`. Returns whether anything changed, so the caller knows to save.
+    pub fn apply_to(&self, contact: &mut Contact) -> bool {
+        if *self == CloudContact::from(&*contact) {
+            return false;
+        }
+        contact.petname = self.name.clone();
+        contact.avatar_pin = self.avatar_pin;
+        contact.trust_level = u8_to_trust_level(self.trust_level);
+        contact.added = self.added;
+        contact.pinned = self.pinned;
+        contact.roster_updated = self.updated;
+        true
+    }
+}
+
+/// Merge two copies of a contact list — at download, this device's and its cloud blob. Additions union (local order first, then the rows only `remote` has). A contact on both sides takes the newer `updated`; equal versions fall to a digest of the synced fields, so every device merging the same two lists lands on the same row whichever side it calls local. `added` keeps the earlier of the two.
+pub fn merge_cloud_contacts(local: &[CloudContact], remote: &[CloudContact]) -> Vec<CloudContact> {
+    let mut merged = local.to_vec();
+    for theirs in remote {
+        match merged.iter_mut().find(|c| c.handle_proof == theirs.handle_proof) {
+            Some(ours) => {
+                let added = ours.added.min(theirs.added);
+                if (theirs.updated, row_digest(theirs)) > (ours.updated, row_digest(ours)) {
+                    *ours = theirs.clone();
+                }
+                ours.added = added;
+            }
+            None => merged.push(theirs.clone()),
+        }
+    }
+    merged
+}
+
+/// Fold the fleet roster — the sibling devices' contact lists, as they push them (`fleet::push_roster`) — into `local`, by the same per-row version. A roster entry newer than our row wins the fields it carries (name, and the avatar pin unless it's unpinned); pinned and trust are this device's own and stay. A newer tombstone drops the row. An entry we have no row for is added, unless it's a tombstone. Ties keep ours: the roster slot resolves exact ties itself, and our next push carries our copy.
+pub fn merge_fleet_roster(local: &[CloudContact], roster: &[RosterEntry]) -> Vec<CloudContact> {
+    let mut merged = local.to_vec();
+    for e in roster {
+        match merged.iter().position(|c| c.handle_proof == e.handle_proof) {
+            Some(pos) if e.updated > merged[pos].updated => {
+                if e.tombstone {
+                    merged.remove(pos);
+                    continue;
+                }
+                let ours = &mut merged[pos];
+                ours.name = e.name.clone();
+                if e.avatar_pin != [0u8; 64] {
+                    ours.avatar_pin = e.avatar_pin;
+                }
+                ours.added = ours.added.min(e.added);
+                ours.updated = e.updated;
+            }
+            Some(_) => {}
+            None if e.tombstone => {}
+            None => merged.push(CloudContact {
+                handle_proof: e.handle_proof,
+                party_id: e.handle_hash,
+                avatar_pin: e.avatar_pin,
+                name: e.name.clone(),
+                device_pubkey: e.public_identity,
+                trust_level: trust_level_to_u8(TrustLevel::Stranger),
+                added: e.added,
+                pinned: false,
+                updated: e.updated,
+            }),
+        }
+    }
+    merged
+}
+
+/// Tie-break between two edits of one contact carrying the same version.
+fn row_digest(c: &CloudContact) -> [u8; 32] {
+    let mut hasher = Hasher::new();
+    hasher.update(&c.avatar_pin);
+    hasher.update(&[c.trust_level, c.pinned as u8]);
+    hasher.update(c.name.as_bytes());
+    *hasher.finalize().as_bytes()
 }
 
 /// Encrypt for cloud storage — thin wrapper over [`crate::storage::encrypt_bytes`] that maps the stringified error into [`CloudError::Encryption`]. Wire format (12-byte nonce + ChaCha20-Poly1305 ciphertext + 16-byte auth tag) is identical to local-disk blobs by construction.
@@ -238,7 +384,7 @@ fn u8_to_trust_level(v: u8) -> TrustLevel {
 
 /// Sync contacts to FGTW cloud storage (blocking)
 ///
-/// Uploads current contacts to cloud. Call after any contact change.
+/// Uploads current contacts to cloud. For changes made in-session, mark a [`SyncBatch`] instead and upload when it comes due.
 ///
 /// # Arguments
 /// * `contacts` - Current contacts list * `identity_seed` - Our identity seed (BLAKE3 of VSF-normalized handle) * `device_keypair` - Device Ed25519 keypair * `handle_proof` - 32-byte handle proof (proves registered user)
@@ -248,20 +394,28 @@ pub fn sync_contacts_to_cloud(
     device_keypair: &crate::network::fgtw::Keypair,
     handle_proof: &[u8; 32],
 ) -> Result<(), CloudError> {
-    use crate::network::fgtw::{put_blob_blocking, BlobError};
-
     // Convert contacts to cloud format
     let cloud_contacts: Vec<CloudContact> = contacts.iter().map(CloudContact::from).collect();
+    sync_cloud_contacts(&cloud_contacts, identity_seed, device_keypair, handle_proof)
+}
+
+/// [`sync_contacts_to_cloud`] for rows already in cloud format — what the UI thread snapshots and hands to a worker after a synced field (the pin) changes, without cloning whole contacts.
+pub fn sync_cloud_contacts(
+    cloud_contacts: &[CloudContact],
+    identity_seed: &[u8; 32],
+    device_keypair: &crate::network::fgtw::Keypair,
+    handle_proof: &[u8; 32],
+) -> Result<(), CloudError> {
+    use crate::network::fgtw::{put_blob_blocking, BlobError};
 
     // Derive keys
     let device_secret = device_keypair.secret.as_bytes();
     let storage_key = contacts_storage_key(identity_seed, device_secret);
-    let encryption_key = contacts_encryption_key(identity_seed, device_secret);
 
     // Encode and encrypt
-    let encrypted = encode_contacts(&cloud_contacts, &encryption_key)?;
+    let encrypted = seal_sync_blob(&cloud_contacts, identity_seed, device_secret)?;
 
-    crate::logf!("Cloud: Uploading {} contacts ({} bytes encrypted)", contacts.len(), encrypted.len());
+    crate::logf!("Cloud: Uploading {} contacts ({} bytes encrypted)", cloud_contacts.len(), encrypted.len());
 
     #[cfg(feature = "development")]
     crate::log("Cloud: About to call put_blob_blocking...");
@@ -294,7 +448,6 @@ pub fn load_contacts_from_cloud(
     // Derive keys
     let device_secret = device_keypair.secret.as_bytes();
     let storage_key = contacts_storage_key(identity_seed, device_secret);
-    let encryption_key = contacts_encryption_key(identity_seed, device_secret);
 
     // Download from FGTW
     let encrypted = match get_blob_blocking(&storage_key) {
@@ -313,7 +466,7 @@ pub fn load_contacts_from_cloud(
     crate::logf!("Cloud: Downloaded contacts blob ({} bytes)", encrypted.len());
 
     // Decrypt and decode
-    let contacts = decode_contacts(&encrypted, &encryption_key)?;
+    let contacts = open_sync_blob(&encrypted, identity_seed, device_secret)?;
     crate::logf!("Cloud: Decoded {} contacts", contacts.len());
     Ok(Some(contacts))
 }
@@ -336,10 +489,9 @@ pub async fn upload_contacts_to_cloud(
     // Derive keys
     let device_secret = device_keypair.secret.as_bytes();
     let storage_key = contacts_storage_key(identity_seed, device_secret);
-    let encryption_key = contacts_encryption_key(identity_seed, device_secret);
 
     // Encode and encrypt
-    let encrypted = encode_contacts(&cloud_contacts, &encryption_key)?;
+    let encrypted = seal_sync_blob(&cloud_contacts, identity_seed, device_secret)?;
 
     // Upload to FGTW
     put_blob(&storage_key, &encrypted, device_keypair, handle_proof)
@@ -364,7 +516,6 @@ pub async fn download_contacts_from_cloud(
     // Derive keys
     let device_secret = device_keypair.secret.as_bytes();
     let storage_key = contacts_storage_key(identity_seed, device_secret);
-    let encryption_key = contacts_encryption_key(identity_seed, device_secret);
 
     // Download from FGTW
     let encrypted = match get_blob(&storage_key).await {
@@ -381,14 +532,199 @@ pub async fn download_contacts_from_cloud(
     };
 
     // Decrypt and decode
-    let contacts = decode_contacts(&encrypted, &encryption_key)?;
+    let contacts = open_sync_blob(&encrypted, identity_seed, device_secret)?;
     Ok(Some(contacts))
 }
 
+// ============================================================================
+// Batching ============================================================================
+
+/// Quiet time after the last contact change before the list is uploaded.
+pub const SYNC_QUIET: std::time::Duration = std::time::Duration::from_secs(1 << 1);
+/// Longest a change waits, however busy the list stays — changes arriving faster than [`SYNC_QUIET`] still reach the cloud.
+pub const SYNC_MAX_DELAY: std::time::Duration = std::time::Duration::from_secs(1 << 5);
+
+/// Debounced cloud upload: contact changes [`touch`](Self::touch) it, and one upload is owed once they've been quiet for [`SYNC_QUIET`] (or [`SYNC_MAX_DELAY`] after the first). Owns no contacts — whoever takes the upload snapshots the list as it is then, so every change before it rides along.
+#[derive(Debug, Default)]
+pub struct SyncBatch {
+    /// (first, latest) change not yet uploaded.
+    pending: Option<(std::time::Instant, std::time::Instant)>,
+}
+
+impl SyncBatch {
+    /// The contact list changed at `now`.
+    pub fn touch(&mut self, now: std::time::Instant) {
+        let first = self.pending.map_or(now, |(first, _)| first);
+        self.pending = Some((first, now));
+    }
+
+    /// When the owed upload is due; `None` with nothing pending.
+    pub fn due_at(&self) -> Option<std::time::Instant> {
+        self.pending.map(|(first, latest)| (latest + SYNC_QUIET).min(first + SYNC_MAX_DELAY))
+    }
+
+    /// `true` once when the upload is due at `now` — the caller uploads.
+    pub fn take_due(&mut self, now: std::time::Instant) -> bool {
+        if self.due_at().is_some_and(|at| now >= at) {
+            self.pending = None;
+            return true;
+        }
+        false
+    }
+
+    /// `true` if anything is pending, due or not — what exit uploads before quitting.
+    pub fn take_pending(&mut self) -> bool {
+        self.pending.take().is_some()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn row(id: u8, name: &str, added: i64, updated: i64) -> CloudContact {
+        CloudContact {
+            handle_proof: [id; 32],
+            party_id: [id; 32],
+            avatar_pin: [0; 64],
+            name: name.to_string(),
+            device_pubkey: [id; 32],
+            trust_level: 1,
+            added,
+            pinned: false,
+            updated,
+        }
+    }
+
+    #[test]
+    fn divergent_device_lists_merge_instead_of_clobbering() {
+        // Both devices started from {1 "alice", 2 "bob"}. A renamed alice and added carol; B pinned bob, renamed alice too (earlier), and added dave.
+        let device_a = vec![row(1, "Alice (work)", 10, 300), row(2, "bob", 20, 20), row(3, "carol", 250, 250)];
+        let mut b_bob = row(2, "bob", 20, 280);
+        b_bob.pinned = true;
+        let device_b = vec![row(1, "Ally", 10, 200), b_bob.clone(), row(4, "dave", 260, 260)];
+
+        let merged = merge_cloud_contacts(&device_a, &device_b);
+        let expected = vec![row(1, "Alice (work)", 10, 300), b_bob, row(3, "carol", 250, 250), row(4, "dave", 260, 260)];
+        assert_eq!(merged, expected);
+
+        // Whichever device merges, the rows agree (order aside) — including a same-version conflict.
+        let mut a_tie = device_a.clone();
+        a_tie.push(row(5, "eve", 30, 400));
+        let mut b_tie = device_b.clone();
+        b_tie.push(row(5, "Evie", 30, 400));
+        let sorted = |mut v: Vec<CloudContact>| {
+            v.sort_by_key(|c| c.handle_proof);
+            v
+        };
+        assert_eq!(sorted(merge_cloud_contacts(&a_tie, &b_tie)), sorted(merge_cloud_contacts(&b_tie, &a_tie)));
+
+        // The version survives the blob, and a pre-version row reads as never edited.
+        let key = [9u8; 32];
+        let back = decode_contacts(&encode_contacts(&merged, &key).unwrap(), &key).unwrap();
+        assert_eq!(back, merged);
+        let mut old = Contact::from_pin("x".to_string(), [0; 64], [7; 32], [7; 32], DevicePubkey::from_bytes([7; 32]));
+        old.added = 5;
+        old.roster_updated = 5;
+        let mut changed = CloudContact::from(&old);
+        changed.name = "renamed".to_string();
+        changed.updated = 9;
+        assert!(changed.apply_to(&mut old));
+        assert_eq!((old.petname.as_str(), old.roster_updated), ("renamed", 9));
+        assert!(!changed.apply_to(&mut old));
+    }
+
+    /// A contact row as a sibling pushes it to the fleet roster (the app's `current_roster`).
+    fn pushed(c: &CloudContact) -> RosterEntry {
+        RosterEntry {
+            handle_proof: c.handle_proof,
+            handle_hash: c.party_id,
+            public_identity: c.device_pubkey,
+            name: c.name.clone(),
+            avatar_pin: c.avatar_pin,
+            added: c.added,
+            updated: c.updated,
+            tombstone: false,
+            ceremony_owner: [0u8; 32],
+            woven: false,
+        }
+    }
+
+    #[test]
+    fn two_devices_merge_each_others_lists_thru_the_fleet_roster() {
+        // Both devices started from {1 "alice", 2 "bob", 3 "carol"}. A renamed alice, pinned bob and added dave; B renamed bob, removed carol and added erin.
+        let mut a_bob = row(2, "bob", 20, 250);
+        a_bob.pinned = true;
+        let device_a = vec![row(1, "Alice (work)", 10, 300), a_bob, row(3, "carol", 30, 30), row(4, "dave", 260, 260)];
+        let device_b = vec![row(1, "alice", 10, 10), row(2, "Bobby", 20, 280), row(5, "erin", 270, 270)];
+        let mut carol_gone = pushed(&row(3, "carol", 30, 290));
+        carol_gone.tombstone = true;
+        let roster_a: Vec<RosterEntry> = device_a.iter().map(pushed).collect();
+        let roster_b: Vec<RosterEntry> = device_b.iter().map(pushed).chain([carol_gone.clone()]).collect();
+
+        // Each device pushed its list; the slot is both merged. Each then downloads it.
+        let slot = crate::network::fgtw::fleet::merge_rosters(roster_a, roster_b);
+        let sorted = |mut v: Vec<CloudContact>| {
+            v.sort_by_key(|c| c.handle_proof);
+            v
+        };
+        let on_a = merge_fleet_roster(&device_a, &slot);
+        let on_b = merge_fleet_roster(&device_b, &slot);
+
+        // On A: B's newer rename of bob lands on A's pinned row (the pin is A's own), carol goes, erin arrives.
+        let mut bobby = row(2, "Bobby", 20, 280);
+        bobby.pinned = true;
+        let mut erin = row(5, "erin", 270, 270);
+        erin.trust_level = trust_level_to_u8(TrustLevel::Stranger);
+        assert_eq!(sorted(on_a.clone()), vec![row(1, "Alice (work)", 10, 300), bobby, row(4, "dave", 260, 260), erin]);
+
+        // On B: A's newer rename of alice lands, bob keeps B's newer name, dave arrives, and A's older carol doesn't come back.
+        let mut dave = row(4, "dave", 260, 260);
+        dave.trust_level = trust_level_to_u8(TrustLevel::Stranger);
+        assert_eq!(sorted(on_b.clone()), vec![row(1, "Alice (work)", 10, 300), row(2, "Bobby", 20, 280), dave, row(5, "erin", 270, 270)]);
+
+        // Both land on the same synced fields for every contact.
+        let synced = |v: &[CloudContact]| v.iter().map(|c| (c.handle_proof, c.name.clone(), c.avatar_pin, c.updated)).collect::<Vec<_>>();
+        assert_eq!(synced(&sorted(on_a)), synced(&sorted(on_b)));
+
+        // A tombstone older than our own edit doesn't remove it.
+        let mut stale = carol_gone;
+        stale.updated = 20;
+        assert_eq!(merge_fleet_roster(&device_a, &[stale]), device_a);
+    }
+
+    #[test]
+    fn rapid_changes_batch_into_one_upload() {
+        use std::time::{Duration, Instant};
+        let t0 = Instant::now();
+        let mut batch = SyncBatch::default();
+        let mut uploads = 0;
+        // Ten changes 100 ms apart, polled every 100 ms for ten seconds.
+        for i in 0..100u32 {
+            let now = t0 + Duration::from_millis(100) * i;
+            if i < 10 {
+                batch.touch(now);
+            }
+            if batch.take_due(now) {
+                uploads += 1;
+                assert_eq!(now, t0 + Duration::from_millis(900) + SYNC_QUIET, "due a quiet period after the last change");
+            }
+        }
+        assert_eq!(uploads, 1);
+        assert_eq!(batch.due_at(), None);
+
+        // A list that never goes quiet is still uploaded by the max delay.
+        for i in 0..(SYNC_MAX_DELAY.as_secs() as u32) {
+            batch.touch(t0 + Duration::from_secs(1) * i);
+        }
+        assert_eq!(batch.due_at(), Some(t0 + SYNC_MAX_DELAY));
+
+        // Shutdown takes whatever is pending, once.
+        assert!(batch.take_pending());
+        assert!(!batch.take_pending());
+        assert!(!batch.take_due(t0 + SYNC_MAX_DELAY * 2));
+    }
+
     #[test]
     fn test_key_derivation() {
         let identity_seed = [1u8; 32];
@@ -405,6 +741,31 @@ mod tests {
         assert_ne!(storage_hash.as_bytes(), &encryption_key);
     }
 
+    #[test]
+    fn sync_blob_is_sealed_under_the_sync_key_only() {
+        let identity_seed = [1u8; 32];
+        let device_secret = [2u8; 32];
+        let contacts = vec![row(1, "alice", 10, 10)];
+        let blob = seal_sync_blob(&contacts, &identity_seed, &device_secret).unwrap();
+        assert_eq!(&blob[..4], b"pcs\x01");
+        let sealed = &blob[4..];
+
+        // The sync key opens it; the identity seed, the legacy key and another generation's key don't.
+        let sync_key = contacts_sync_key(&identity_seed, &device_secret, SYNC_KEY_GENERATION);
+        assert_eq!(decode_contacts(sealed, &sync_key).unwrap(), contacts);
+        assert!(decode_contacts(sealed, &identity_seed).is_err());
+        assert!(decode_contacts(sealed, &contacts_encryption_key(&identity_seed, &device_secret)).is_err());
+        assert!(decode_contacts(sealed, &contacts_sync_key(&identity_seed, &device_secret, SYNC_KEY_GENERATION + 1)).is_err());
+        assert_ne!(sync_key, contacts_encryption_key(&identity_seed, &device_secret));
+        assert_eq!(open_sync_blob(&blob, &identity_seed, &device_secret).unwrap(), contacts);
+
+        // A headerless blob from an older build still opens, under the legacy key.
+        let legacy = encode_contacts(&contacts, &contacts_encryption_key(&identity_seed, &device_secret)).unwrap();
+        assert_eq!(open_sync_blob(&legacy, &identity_seed, &device_secret).unwrap(), contacts);
+        // Someone else's seed opens neither.
+        assert!(open_sync_blob(&blob, &[9u8; 32], &device_secret).is_err());
+    }
+
     #[test]
     fn test_contacts_roundtrip() {
         let contacts = vec![
@@ -416,6 +777,8 @@ mod tests {
                 device_pubkey: [2u8; 32],
                 trust_level: 1,
                 added: 1234567890,
+                pinned: true,
+                updated: 1234567990,
             },
             CloudContact {
                 handle_proof: [3u8; 32],
@@ -425,6 +788,8 @@ mod tests {
                 device_pubkey: [4u8; 32],
                 trust_level: 2,
                 added: 1234567891,
+                pinned: false,
+                updated: 1234567891,
             },
         ];
 
@@ -437,6 +802,8 @@ mod tests {
         assert_eq!(decoded[0].handle_proof, [1u8; 32]);
         assert_eq!(decoded[1].name, "bob");
         assert_eq!(decoded[1].trust_level, 2);
+        assert!(decoded[0].pinned && !decoded[1].pinned);
+        assert_eq!(decoded[0].updated, 1234567990);
     }
 
     #[test]
@@ -449,6 +816,8 @@ mod tests {
             device_pubkey: [2u8; 32],
             trust_level: 1,
             added: 1234567890,
+            pinned: false,
+            updated: 1234567890,
         }];
 
         let key1 = [42u8; 32];
@@ -0,0 +1,195 @@
+//! Vault compaction: rebuild a vault from only its LIVE records. The vault is a durable dual-mirror file that grows (fallocate + zero) but never shrinks — deleted entries, superseded chain states and rewritten message rows leave their old blocks behind. Compaction walks every live address the engine still maps (contact state, avatars, chains, settings, every rārangi conversation row and catalog, and anything a later module adds), rebuilds a fresh vault holding exactly those records at exactly those addresses, and reports how many bytes that gave back. It never decides what is worth keeping: a whitelist of known domains silently drops whatever it forgot.
+//!
+//! Crash safety rides a journal: the live set is sealed under a device-bound key and written atomically (`write_file`: temp + fsync + rename + read-back) BEFORE the old vault files are touched. From that moment the journal is the source of truth until the rebuilt vault verifies against it, after which the journal is deleted. An interrupted run is finished by [`finish_interrupted`], which [`open_shared`] runs first — and a journal that can't be finished keeps the vault shut, since opening the half-rebuilt ring would let the app write over the only complete copy.
+//!
+//! Compaction can't run under a live engine, so it's scheduled rather than called: whatever leaves dead blocks worth reclaiming (a cleared conversation, a retention sweep that pruned) marks it [`owe`]d in `settings.vsf`, and the next launch's first [`open_shared`] — before any engine exists on the vault — runs it.
+
+use crate::storage::{FlatStorage, StorageError};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use vsf::schema::{SectionBuilder, SectionSchema, TypeConstraint};
+use vsf::VsfType;
+
+fn vault_err(e: impl std::fmt::Display) -> StorageError {
+    StorageError::Vault(e.to_string())
+}
+
+/// Journal key: `blake3_kdf("photon.compact.v0", vault_seed ‖ device_secret)` — bound to this device like the vault itself, so a journal left on disk is as opaque as the vault it's rebuilding.
+fn journal_key(vault_seed: &[u8; 32], device_secret: &[u8; 32]) -> [u8; 32] {
+    let mut input = [0u8; 64];
+    input[..32].copy_from_slice(vault_seed);
+    input[32..].copy_from_slice(device_secret);
+    blake3::derive_key(&format!("{}.compact.v0", crate::storage::APP.id), &input)
+}
+
+/// The vault's ring paths plus the journal path beside them. The journal's name is derived (not a `.tmp`/`.compacting` extension) so it has the same opaque 32-char base64url shape as everything else in the data dir.
+fn paths(vault_seed: &[u8; 32], device_secret: &[u8; 32]) -> Result<([PathBuf; 2], PathBuf), StorageError> {
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    let ring = kete::vault_ring_paths(crate::storage::APP, vault_seed, device_secret).map_err(vault_err)?;
+    let name = blake3::derive_key(
+        &format!("{}.compact.journal.v0", crate::storage::APP.id),
+        &journal_key(vault_seed, device_secret),
+    );
+    let dir = ring[0].parent().map(PathBuf::from).unwrap_or_default();
+    let journal = dir.join(URL_SAFE_NO_PAD.encode(&name[..24]));
+    Ok((ring, journal))
+}
+
+/// Where a compaction of this device's vault under `vault_seed` keeps its journal. The file exists only between [`seal_journal`] and the verified rebuild.
+pub fn journal_path(vault_seed: &[u8; 32], device_secret: &[u8; 32]) -> Result<PathBuf, StorageError> {
+    Ok(paths(vault_seed, device_secret)?.1)
+}
+
+fn on_disk_size(ring: &[PathBuf; 2]) -> u64 {
+    ring.iter().filter_map(|p| std::fs::metadata(p).ok()).map(|m| m.len()).sum()
+}
+
+/// Journal plaintext schema: one `rec` multi-value field per live record (v'r' address, v'r' plaintext bytes).
+fn journal_schema() -> SectionSchema {
+    SectionSchema::new("compaction").field("rec", TypeConstraint::Any)
+}
+
+/// Every live record in the vault, address-sorted so two walks of the same contents compare equal.
+pub(super) fn live_records(storage: &FlatStorage) -> Result<Vec<([u8; 32], Vec<u8>)>, StorageError> {
+    let mut addrs = storage.live_addrs()?;
+    addrs.sort_unstable();
+    let mut records = Vec::with_capacity(addrs.len());
+    for addr in addrs {
+        if let Some(bytes) = storage.read_addr(&addr)? {
+            records.push((addr, bytes));
+        }
+    }
+    Ok(records)
+}
+
+fn encode_records(records: &[([u8; 32], Vec<u8>)]) -> Result<Vec<u8>, StorageError> {
+    let mut builder = journal_schema().build();
+    for (addr, bytes) in records {
+        builder = builder
+            .append_multi("rec", vec![VsfType::v(b'r', addr.to_vec()), VsfType::v(b'r', bytes.clone())])
+            .map_err(|e| StorageError::Parse(e.to_string()))?;
+    }
+    builder.encode().map_err(|e| StorageError::Parse(e.to_string()))
+}
+
+/// Parse a journal back into its records. Any malformed row fails the whole parse — a journal we can't read completely is not one we may rebuild from.
+fn decode_records(plain: &[u8]) -> Result<Vec<([u8; 32], Vec<u8>)>, StorageError> {
+    let section = SectionBuilder::parse(journal_schema(), plain).map_err(|e| StorageError::Parse(format!("compaction journal parse: {}", e)))?;
+    section
+        .get_fields("rec")
+        .iter()
+        .map(|field| match (field.values.first(), field.values.get(1)) {
+            (Some(VsfType::v(b'r', addr)), Some(VsfType::v(b'r', bytes))) => match <[u8; 32]>::try_from(addr.as_slice()) {
+                Ok(addr) => Ok((addr, bytes.clone())),
+                Err(_) => Err(StorageError::Parse("compaction journal: bad record address".to_string())),
+            },
+            _ => Err(StorageError::Parse("compaction journal: malformed record".to_string())),
+        })
+        .collect()
+}
+
+/// Rebuild the vault from the journal's records: wipe the ring, write every record back at its address, verify the fresh vault's live set is exactly the journal's, then drop the journal (the commit point). Re-runnable: a crash anywhere in here just means the next call starts the rebuild again from the same journal.
+fn rebuild(vault_seed: [u8; 32], device_secret: [u8; 32], ring: &[PathBuf; 2], journal: &PathBuf, records: &[([u8; 32], Vec<u8>)]) -> Result<(), StorageError> {
+    for p in ring {
+        let _ = std::fs::remove_file(p);
+    }
+    {
+        let fresh = FlatStorage::new(crate::storage::APP, vault_seed, device_secret)?;
+        for (addr, bytes) in records {
+            fresh.write_addr(addr, bytes)?;
+        }
+        if live_records(&fresh)? != records {
+            return Err(StorageError::Vault("compact verify: rebuilt vault differs from journal — journal kept".to_string()));
+        }
+    }
+    std::fs::remove_file(journal).map_err(vault_err)
+}
+
+/// Finish a compaction that was interrupted after its journal was written. A no-op when there's no journal (the normal case). A journal that won't decrypt or parse is left in place and reported — it may be the only copy of the profile. Callers open the vault through [`open_shared`], which refuses to proceed on any error here.
+pub fn finish_interrupted(vault_seed: &[u8; 32], device_secret: &[u8; 32]) -> Result<bool, StorageError> {
+    let (ring, journal) = paths(vault_seed, device_secret)?;
+    let Ok(sealed) = std::fs::read(&journal) else {
+        return Ok(false);
+    };
+    let plain = crate::storage::decrypt_bytes(&sealed, &journal_key(vault_seed, device_secret)).map_err(StorageError::Crypto)?;
+    rebuild(*vault_seed, *device_secret, &ring, &journal, &decode_records(&plain)?)?;
+    crate::logf!("STORAGE: finished an interrupted vault compaction");
+    Ok(true)
+}
+
+/// Set by this process's first successful [`open_shared`]. From then on an engine may be live on the vault, so an owed compaction waits for the next launch.
+static OPENED: AtomicBool = AtomicBool::new(false);
+
+/// The one way the app opens its vault: finish any interrupted compaction, run an [`owe`]d one if this is the process's first open, then [`FlatStorage::open_shared`]. A journal that can't be finished is an error, not a log line — the ring beside it may be a half-written rebuild, and an engine opened on that would commit over the journal's complete copy the moment anything saved. A failed owed compaction is only logged: the vault is intact, and the debt stays for the next launch.
+pub fn open_shared(vault_seed: [u8; 32], device_secret: [u8; 32]) -> Result<Arc<FlatStorage>, StorageError> {
+    finish_interrupted(&vault_seed, &device_secret)
+        .map_err(|e| StorageError::Vault(format!("interrupted compaction not finished — vault left closed: {}", e)))?;
+    if !OPENED.swap(true, Ordering::SeqCst) && crate::storage::settings::Settings::load_or_create().compact_owed {
+        match compact(vault_seed, device_secret) {
+            Ok(_) => {
+                let mut settings = crate::storage::settings::Settings::load_or_create();
+                settings.compact_owed = false;
+                settings.save();
+            }
+            Err(e) => crate::logf!("STORAGE: owed compaction failed, retrying next launch: {}", e),
+        }
+    }
+    FlatStorage::open_shared(crate::storage::APP, vault_seed, device_secret)
+}
+
+/// Schedule a compaction for the next launch (persisted as `settings.vsf` `compact_owed`). Called by whatever just left dead blocks in the vault.
+pub fn owe() {
+    let mut settings = crate::storage::settings::Settings::load_or_create();
+    if !settings.compact_owed {
+        settings.compact_owed = true;
+        settings.save();
+    }
+}
+
+/// The first half of [`compact`]: seal the vault's live set into the journal, after which the journal — not the ring — is the source of truth. Returns the records sealed. On its own it leaves exactly what a crash mid-compaction leaves, for [`finish_interrupted`] to complete. Same no-live-engine rule as [`compact`].
+pub fn seal_journal(identity_seed: [u8; 32], device_secret: [u8; 32]) -> Result<Vec<([u8; 32], Vec<u8>)>, StorageError> {
+    let journal = journal_path(&identity_seed, &device_secret)?;
+    let records = {
+        let storage = FlatStorage::new(crate::storage::APP, identity_seed, device_secret)?;
+        live_records(&storage)?
+    };
+    let plain = encode_records(&records)?;
+    let sealed = crate::storage::encrypt_bytes(&plain, &journal_key(&identity_seed, &device_secret)).map_err(StorageError::Crypto)?;
+    crate::storage::write_file(&journal, &sealed, "compaction journal").map_err(vault_err)?;
+    Ok(records)
+}
+
+/// Compact this device's vault in place, returning the bytes reclaimed on disk. Must run with no engine open on the vault (before `open_shared`, or after every handle is dropped) — it replaces the files out from under any live one. The app doesn't call this directly: it [`owe`]s one, and the next launch's [`open_shared`] runs it.
+pub fn compact(identity_seed: [u8; 32], device_secret: [u8; 32]) -> Result<u64, StorageError> {
+    finish_interrupted(&identity_seed, &device_secret)?;
+    let (ring, journal) = paths(&identity_seed, &device_secret)?;
+    let before = on_disk_size(&ring);
+
+    let records = seal_journal(identity_seed, device_secret)?;
+    rebuild(identity_seed, device_secret, &ring, &journal, &records)?;
+    let reclaimed = before.saturating_sub(on_disk_size(&ring));
+    crate::logf!("STORAGE: compacted vault — {} records kept, {} bytes reclaimed", records.len(), reclaimed);
+    Ok(reclaimed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The vault-touching tests live in tests/compact.rs, their own process, so they can point `PHOTON_DATA_DIR` at a temp root.
+
+    #[test]
+    fn journal_records_round_trip_and_a_malformed_row_fails_the_whole_parse() {
+        let records = vec![([1u8; 32], b"one".to_vec()), ([2u8; 32], Vec::new())];
+        assert_eq!(decode_records(&encode_records(&records).unwrap()).unwrap(), records);
+
+        let short_addr = journal_schema()
+            .build()
+            .append_multi("rec", vec![VsfType::v(b'r', vec![3u8; 31]), VsfType::v(b'r', b"x".to_vec())])
+            .unwrap()
+            .encode()
+            .unwrap();
+        assert!(decode_records(&short_addr).is_err());
+    }
+}
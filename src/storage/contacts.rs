@@ -5,11 +5,14 @@
 //! - Contact state: `vault_key("state", their_identity_seed)`
 //! - Contact keypairs: `vault_key("keypairs", their_identity_seed)`
 //! - Contact slots: `vault_key("slots", their_identity_seed)`
+//! - Contact outbox: `vault_key("outbox", their_identity_seed)` — messages queued where they couldn't be sent (headless), absent when none are owed
 //!
 //! Messages are NOT here — conversation content lives in the rārangi conversation DB keyed by `friendship_id`, not per-peer under a contact.
 //!
 //! All encryption, addressing, and atomicity is handled by FlatStorage.
 
+use crate::storage::migrate::{load_current, Store};
+use crate::storage::unflushed::{self, Unflushed};
 use crate::storage::{FlatStorage, StorageError};
 use crate::types::{
     ClutchState, Contact, ContactId, DevicePubkey, FriendshipId, Seed, TrustLevel,
@@ -61,8 +64,9 @@ fn contact_key(their_identity_seed: &[u8; 32], domain: &str) -> [u8; 32] {
 // ============================================================================ Contact List (Index) - Static Identity Data (Schema-validated) ============================================================================
 
 /// Schema for contact_list section Each contact field contains: (handle_proof: hb, handle: x)
-fn contact_list_schema() -> SectionSchema {
+pub(crate) fn contact_list_schema() -> SectionSchema {
     SectionSchema::new("contact_list")
+        .field("version", TypeConstraint::AnyUnsigned) // absent = v1 (pre-versioning); see storage::migrate
         // Contact field allows mixed types (hash, string) - use Any
         .field("contact", TypeConstraint::Any)
 }
@@ -72,8 +76,19 @@ pub fn save_contact_list(
     contacts: &[ContactIdentity],
     storage: &FlatStorage,
 ) -> Result<(), StorageError> {
-    let schema = contact_list_schema();
-    let mut builder = schema.build();
+    let result = encode_contact_list(contacts).and_then(|bytes| {
+        storage.write_addr(&crate::storage::vault_key("contacts", storage.vault_seed()), &bytes)
+    });
+    unflushed::record(Unflushed::ContactList, &result);
+    result
+}
+
+/// Encode the contact index at the CURRENT schema version.
+pub(crate) fn encode_contact_list(contacts: &[ContactIdentity]) -> Result<Vec<u8>, StorageError> {
+    let mut builder = contact_list_schema()
+        .build()
+        .set("version", Store::ContactList.current())
+        .map_err(|e| StorageError::Parse(e.to_string()))?;
 
     for c in contacts {
         builder = builder
@@ -89,29 +104,32 @@ pub fn save_contact_list(
             .map_err(|e| StorageError::Parse(e.to_string()))?;
     }
 
-    let vsf_bytes = builder
+    builder
         .encode()
-        .map_err(|e| StorageError::Parse(e.to_string()))?;
-
-    storage.write_addr(
-        &crate::storage::vault_key("contacts", storage.vault_seed()),
-        &vsf_bytes,
-    )
+        .map_err(|e| StorageError::Parse(e.to_string()))
 }
 
-/// Load the contact list from encrypted index with schema validation
+/// Load the contact list from encrypted index with schema validation. An older-version index is upgraded (and re-saved) on the way in.
 pub fn load_contact_list(storage: &FlatStorage) -> Result<Vec<ContactIdentity>, StorageError> {
-    let vsf_bytes =
-        match storage.read_addr(&crate::storage::vault_key("contacts", storage.vault_seed()))? {
-            Some(b) => b,
-            None => return Ok(Vec::new()),
-        };
+    let Some(vsf_bytes) = load_current(
+        storage,
+        Store::ContactList,
+        &crate::storage::vault_key("contacts", storage.vault_seed()),
+    )?
+    else {
+        return Ok(Vec::new());
+    };
 
     #[cfg(feature = "development")]
     crate::network::inspect::vsf_read_decrypted(&vsf_bytes, "contacts/index");
 
+    decode_contact_list(&vsf_bytes)
+}
+
+/// Decode the contact index rows. Row layout is unchanged across v1 → v2 (v2 only adds the header version), so this reads either.
+pub(crate) fn decode_contact_list(vsf_bytes: &[u8]) -> Result<Vec<ContactIdentity>, StorageError> {
     let schema = contact_list_schema();
-    let builder = SectionBuilder::parse(schema, &vsf_bytes)
+    let builder = SectionBuilder::parse(schema, vsf_bytes)
         .map_err(|e| StorageError::Parse(format!("Contact list parse: {}", e)))?;
 
     let mut contacts = Vec::new();
@@ -180,10 +198,22 @@ fn contact_state_schema() -> SectionSchema {
         .field("identity_ended", TypeConstraint::AnyUnsigned) // bool: the chain vanished after a fold — owner ended the identity. Absent = false.
         .field("identity_superseded", TypeConstraint::AnyUnsigned) // bool: a different-genesis chain claimed this name — a stranger. Absent = false.
         .field("unread", TypeConstraint::AnyUnsigned) // u32: inbound messages not yet seen (conversation wasn't the active view when they landed). Absent = 0 (legacy contacts load as read).
+        .field("verified", TypeConstraint::AnyUnsigned) // bool: safety number confirmed out of band. Absent = false (unverified).
+        .field("muted", TypeConstraint::AnyUnsigned) // bool: no notification / chime / unread ring for this conversation. Absent = false.
+        .field("pinned", TypeConstraint::AnyUnsigned) // bool: listed above every unpinned contact. Absent = false.
+        .field("archived", TypeConstraint::AnyUnsigned) // bool: hidden from the main list until a message arrives. Absent = false.
+        .field("hist_floor", TypeConstraint::Any) // e6: rows at/before this were cleared or pruned here — recovery won't re-add them. Absent = 0.
+        .field("retention", TypeConstraint::Any) // multi-value (keep_last u5, keep_days u4): this conversation's own retention policy. Absent = follow the settings default.
 }
 
 /// Save contact state (mutable data) with schema validation
 pub fn save_contact_state(contact: &Contact, storage: &FlatStorage) -> Result<(), StorageError> {
+    let result = write_contact_state(contact, storage);
+    unflushed::record(Unflushed::ContactState(contact.handle_hash), &result);
+    result
+}
+
+fn write_contact_state(contact: &Contact, storage: &FlatStorage) -> Result<(), StorageError> {
     // Key the state entry off the contact's party id (`handle_hash`), not a re-derivation from the handle string. For friends the two are equal by construction (`Contact::new`), so this is a no-op; for fleet siblings the party id is device-derived (`sibling_party_id`) — deriving from the handle would collide every sibling AND the self-contact onto one state entry.
     let identity_seed = contact.handle_hash;
 
@@ -348,6 +378,36 @@ pub fn save_contact_state(contact: &Contact, storage: &FlatStorage) -> Result<()
             .set("unread", contact.unread_count)
             .map_err(|e| StorageError::Parse(e.to_string()))?;
     }
+    if contact.verified {
+        builder = builder
+            .set("verified", true)
+            .map_err(|e| StorageError::Parse(e.to_string()))?;
+    }
+    if contact.muted {
+        builder = builder
+            .set("muted", true)
+            .map_err(|e| StorageError::Parse(e.to_string()))?;
+    }
+    if contact.pinned {
+        builder = builder
+            .set("pinned", true)
+            .map_err(|e| StorageError::Parse(e.to_string()))?;
+    }
+    if contact.archived {
+        builder = builder
+            .set("archived", true)
+            .map_err(|e| StorageError::Parse(e.to_string()))?;
+    }
+    if contact.history_floor > 0 {
+        builder = builder
+            .set("hist_floor", VsfType::e(vsf::types::EtType::e6(contact.history_floor)))
+            .map_err(|e| StorageError::Parse(e.to_string()))?;
+    }
+    if let Some(r) = contact.retention {
+        builder = builder
+            .append_multi("retention", vec![VsfType::u5(r.keep_last), VsfType::u4(r.keep_days)])
+            .map_err(|e| StorageError::Parse(e.to_string()))?;
+    }
 
     let vsf_bytes = builder
         .encode()
@@ -474,6 +534,16 @@ fn apply_contact_state(contact: &mut Contact, vsf_bytes: &[u8]) -> Result<(), St
     }
     // Unread counter — absent (legacy vaults, fully-read conversations) reads as 0.
     contact.unread_count = section.get_value::<u32>("unread").unwrap_or(0);
+    contact.verified = section.get_value::<bool>("verified").unwrap_or(false);
+    contact.muted = section.get_value::<bool>("muted").unwrap_or(false);
+    contact.pinned = section.get_value::<bool>("pinned").unwrap_or(false);
+    contact.archived = section.get_value::<bool>("archived").unwrap_or(false);
+    contact.history_floor = section.get_fields("hist_floor").first().and_then(|f| f.values.first()).map_or(0, vsf_to_oscillations);
+    contact.retention = section.get_fields("retention").first().and_then(|f| {
+        let n = |i: usize| f.values.get(i).and_then(|v| v.as_usize());
+        // Saturate an out-of-range hand edit rather than wrapping it to a tiny (history-destroying) bound.
+        Some(crate::types::Retention { keep_last: n(0)?.min(u32::MAX as usize) as u32, keep_days: n(1)?.min(u16::MAX as usize) as u16 })
+    });
     // Friend-side blind deposits: (device ke, blob tensor, at e6) per multi-value field.
     for field in section.get_fields("blind") {
         if field.values.len() >= 3 {
@@ -508,6 +578,10 @@ fn apply_contact_state(contact: &mut Contact, vsf_bytes: &[u8]) -> Result<(), St
     if let Some(v) = section.get_fields("fleet_members_ts").first().and_then(|f| f.values.first()) {
         contact.fleet_members_ts = vsf_to_oscillations(v);
     }
+    // Absent = never heard from (or saved before last_seen was read back).
+    if let Some(v) = section.get_fields("last_seen").first().and_then(|f| f.values.first()) {
+        contact.last_seen = Some(vsf_to_oscillations(v));
+    }
     // Roster LWW clock: absent = never bumped past creation, so `added` (set by the index-row load) stands.
     if let Some(v) = section.get_fields("roster_updated").first().and_then(|f| f.values.first()) {
         contact.roster_updated = vsf_to_oscillations(v);
@@ -542,36 +616,52 @@ fn apply_contact_state(contact: &mut Contact, vsf_bytes: &[u8]) -> Result<(), St
 // ============================================================================ Sibling Index — own-fleet devices (fleet weave) ============================================================================
 
 /// Schema for the sibling index: one `device` field per sibling device pubkey. Siblings can't live in the contacts index — it's keyed by handle string and dedups on it, so every sibling (sharing OUR handle) would collapse into one entry.
-fn sibling_list_schema() -> SectionSchema {
-    SectionSchema::new("sibling_list").field("device", TypeConstraint::Ed25519Key)
+pub(crate) fn sibling_list_schema() -> SectionSchema {
+    SectionSchema::new("sibling_list")
+        .field("version", TypeConstraint::AnyUnsigned) // absent = v1 (pre-versioning); see storage::migrate
+        .field("device", TypeConstraint::Ed25519Key)
 }
 
 /// Save the sibling device-pubkey index at `vault_key("siblings", vault_seed)`.
 pub fn save_sibling_list(devices: &[[u8; 32]], storage: &FlatStorage) -> Result<(), StorageError> {
-    let schema = sibling_list_schema();
-    let mut builder = schema.build();
+    storage.write_addr(
+        &crate::storage::vault_key("siblings", storage.vault_seed()),
+        &encode_sibling_list(devices)?,
+    )
+}
+
+/// Encode the sibling index at the CURRENT schema version.
+pub(crate) fn encode_sibling_list(devices: &[[u8; 32]]) -> Result<Vec<u8>, StorageError> {
+    let mut builder = sibling_list_schema()
+        .build()
+        .set("version", Store::SiblingList.current())
+        .map_err(|e| StorageError::Parse(e.to_string()))?;
     for d in devices {
         builder = builder
             .append_multi("device", vec![VsfType::ke(d.to_vec())])
             .map_err(|e| StorageError::Parse(e.to_string()))?;
     }
-    let vsf_bytes = builder
+    builder
         .encode()
-        .map_err(|e| StorageError::Parse(e.to_string()))?;
-    storage.write_addr(
-        &crate::storage::vault_key("siblings", storage.vault_seed()),
-        &vsf_bytes,
-    )
+        .map_err(|e| StorageError::Parse(e.to_string()))
 }
 
-/// Load the sibling device-pubkey index. Missing entry = empty fleet knowledge (single-device or pre-feature vault).
+/// Load the sibling device-pubkey index. Missing entry = empty fleet knowledge (single-device or pre-feature vault). An older-version index is upgraded (and re-saved) on the way in.
 pub fn load_sibling_list(storage: &FlatStorage) -> Result<Vec<[u8; 32]>, StorageError> {
-    let vsf_bytes =
-        match storage.read_addr(&crate::storage::vault_key("siblings", storage.vault_seed()))? {
-            Some(b) => b,
-            None => return Ok(Vec::new()),
-        };
-    let builder = SectionBuilder::parse(sibling_list_schema(), &vsf_bytes)
+    let Some(vsf_bytes) = load_current(
+        storage,
+        Store::SiblingList,
+        &crate::storage::vault_key("siblings", storage.vault_seed()),
+    )?
+    else {
+        return Ok(Vec::new());
+    };
+    decode_sibling_list(&vsf_bytes)
+}
+
+/// Decode the sibling index rows (layout unchanged across v1 → v2).
+pub(crate) fn decode_sibling_list(vsf_bytes: &[u8]) -> Result<Vec<[u8; 32]>, StorageError> {
+    let builder = SectionBuilder::parse(sibling_list_schema(), vsf_bytes)
         .map_err(|e| StorageError::Parse(format!("Sibling list parse: {}", e)))?;
     let mut devices = Vec::new();
     for field in builder.get_fields("device") {
@@ -699,6 +789,7 @@ pub fn delete_contact(identity_seed: &[u8; 32], storage: &FlatStorage) -> Result
     storage.delete_addr(&contact_key(identity_seed, "state"))?;
     storage.delete_addr(&contact_key(identity_seed, "keypairs"))?;
     storage.delete_addr(&contact_key(identity_seed, "slots"))?;
+    storage.delete_addr(&contact_key(identity_seed, "outbox"))?;
     Ok(())
 }
 
@@ -739,6 +830,45 @@ fn u8_to_trust_level(v: u8) -> TrustLevel {
     }
 }
 
+// ============================================================================ Outbox - Messages Queued Without a Chain Driver ============================================================================
+
+/// Schema for a contact's outbox: one `msg` multi-value field per queued message (e6 queued_at, x text), oldest first.
+fn outbox_schema() -> SectionSchema {
+    SectionSchema::new("outbox").field("msg", TypeConstraint::Any)
+}
+
+/// Messages queued to a contact by something that can't encrypt and send — the headless profile — as (queued_at, text), oldest first. The app sends each thru its normal compose path the first tick it holds that contact's friendship chain, then saves back what's left ([`save_outbox`]).
+pub fn load_outbox(their_identity_seed: &[u8; 32], storage: &FlatStorage) -> Result<Vec<(i64, String)>, StorageError> {
+    let Some(bytes) = storage.read_addr(&contact_key(their_identity_seed, "outbox"))? else {
+        return Ok(Vec::new());
+    };
+    let section = SectionBuilder::parse(outbox_schema(), &bytes).map_err(|e| StorageError::Parse(format!("outbox parse: {}", e)))?;
+    Ok(section
+        .get_fields("msg")
+        .iter()
+        .filter_map(|field| match (field.values.first(), field.values.get(1)) {
+            (Some(queued_at), Some(VsfType::x(text))) => Some((vsf_to_oscillations(queued_at), text.clone())),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Replace a contact's outbox with `queued`. An empty one deletes the entry.
+pub fn save_outbox(their_identity_seed: &[u8; 32], queued: &[(i64, String)], storage: &FlatStorage) -> Result<(), StorageError> {
+    let addr = contact_key(their_identity_seed, "outbox");
+    if queued.is_empty() {
+        storage.delete_addr(&addr)?;
+        return Ok(());
+    }
+    let mut builder = outbox_schema().build();
+    for (queued_at, text) in queued {
+        builder = builder
+            .append_multi("msg", vec![VsfType::e(vsf::types::EtType::e6(*queued_at)), VsfType::x(text.clone())])
+            .map_err(|e| StorageError::Parse(e.to_string()))?;
+    }
+    storage.write_addr(&addr, &builder.encode().map_err(|e| StorageError::Parse(e.to_string()))?)
+}
+
 // ============================================================================ CLUTCH Keypairs Storage (~600KB, stored separately) ============================================================================
 
 use crate::crypto::clutch::ClutchAllKeypairs;
@@ -822,6 +952,12 @@ fn conversation_id(my_seed: &[u8; 32], their_identity_seed: &[u8; 32]) -> [u8; 3
 
 /// Save a contact's messages as rows in the conversation table. Idempotent: each message is written at its sequence index, so re-saving the same history overwrites row-for-row identically.
 pub fn save_messages(contact: &Contact, storage: &FlatStorage) -> Result<(), StorageError> {
+    let result = write_messages(contact, storage);
+    unflushed::record(Unflushed::Messages(contact.handle_hash), &result);
+    result
+}
+
+fn write_messages(contact: &Contact, storage: &FlatStorage) -> Result<(), StorageError> {
     if contact.messages.is_empty() {
         return Ok(()); // Nothing to save
     }
@@ -847,6 +983,18 @@ pub fn save_messages(contact: &Contact, storage: &FlatStorage) -> Result<(), Sto
         if msg.recovered {
             rec = rec.set("recovered", 1u64);
         }
+        // image: an image message's JPEG — absent on text rows.
+        if let Some(img) = &msg.image {
+            rec = rec.set("image", img.clone());
+        }
+        // file + file_name: a file message's attachment — absent on every other row.
+        if let Some(file) = &msg.attachment {
+            rec = rec.set("file", file.bytes.clone()).set("file_name", file.name.clone());
+        }
+        // voice + voice_ms + voice_wave: a voice message's memo — absent on every other row.
+        if let Some(memo) = &msg.voice {
+            rec = rec.set("voice", memo.opus.clone()).set("voice_ms", memo.duration_ms as u64).set("voice_wave", memo.waveform.clone());
+        }
         db.put_row_in(&table, Pk::Int(msg.timestamp as u64), &rec)
             .map_err(|e| StorageError::Vault(e.to_string()))?;
     }
@@ -857,14 +1005,54 @@ pub fn save_messages(contact: &Contact, storage: &FlatStorage) -> Result<(), Sto
     Ok(())
 }
 
+/// A file message's attachment from its row's `file` + `file_name` columns (both written together by the save paths).
+fn load_attachment(rec: &Record) -> Option<crate::types::Attachment> {
+    attachment_from_columns(rec.text("file_name"), rec.bytes("file"))
+}
+
+/// The attachment a `file_name` + `file` column pair describes — `None` unless both are present. Shared with the backup import, which carries the same two columns.
+pub(crate) fn attachment_from_columns(name: Option<&str>, bytes: Option<&[u8]>) -> Option<crate::types::Attachment> {
+    Some(crate::types::Attachment { name: name?.to_string(), bytes: bytes?.to_vec() })
+}
+
+/// A voice message's memo from its row's `voice` + `voice_ms` (+ `voice_wave`, which may be empty) columns.
+fn load_voice(rec: &Record) -> Option<crate::types::VoiceMemo> {
+    voice_from_columns(rec.bytes("voice"), rec.uint("voice_ms"), rec.bytes("voice_wave"))
+}
+
+/// The memo a `voice` + `voice_ms` (+ `voice_wave`) column set describes — `None` without the clip or a length that fits. Shared with the backup import, which carries the same three columns.
+pub(crate) fn voice_from_columns(opus: Option<&[u8]>, duration_ms: Option<u64>, waveform: Option<&[u8]>) -> Option<crate::types::VoiceMemo> {
+    Some(crate::types::VoiceMemo {
+        duration_ms: u32::try_from(duration_ms?).ok()?,
+        waveform: waveform.map_or(Vec::new(), <[u8]>::to_vec),
+        opus: opus?.to_vec(),
+    })
+}
+
 /// Load a contact's messages from the conversation table, in counter order (which is chronological).
 pub fn load_messages(contact: &mut Contact, storage: &FlatStorage) -> Result<(), StorageError> {
     // Use the contact's cached identity seed (handle_hash), not a re-derivation from the handle.
-    let table = conversation_id(storage.vault_seed(), &contact.handle_hash);
+    contact.messages = load_conversation(&contact.handle_hash, storage)?;
 
+    #[cfg(feature = "development")]
+    crate::logf!("STORAGE: Loaded {} messages for seed {}", contact.messages.len(), hex::encode(&contact.handle_hash[..4]));
+
+    Ok(())
+}
+
+/// Every message of the 1:1 conversation with `their_identity_seed`, chronological — [`load_messages`] without a `Contact` to hang them on (the backup path walks conversations by party id).
+pub fn load_conversation(
+    their_identity_seed: &[u8; 32],
+    storage: &FlatStorage,
+) -> Result<Vec<ChatMessage>, StorageError> {
+    load_table(&conversation_id(storage.vault_seed(), their_identity_seed), storage)
+}
+
+/// Every message row of one conversation table, chronological.
+fn load_table(table: &[u8; 32], storage: &FlatStorage) -> Result<Vec<ChatMessage>, StorageError> {
     let db = Db::open(storage).map_err(|e| StorageError::Vault(e.to_string()))?;
     let pks = db
-        .list_in(&table)
+        .list_in(table)
         .map_err(|e| StorageError::Vault(e.to_string()))?;
 
     // Sort keys numerically — the catalog yields INSERTION order, which matched chronological order only while rows were appended live. History recovery inserts OLDER rows later, so trusting insertion order would interleave the conversation. Key = eagle_time, so numeric sort = time sort.
@@ -877,10 +1065,10 @@ pub fn load_messages(contact: &mut Contact, storage: &FlatStorage) -> Result<(),
         .collect();
     keys.sort_unstable();
 
-    contact.messages.clear();
+    let mut messages = Vec::with_capacity(keys.len());
     for key in keys {
         let Some(rec) = db
-            .get_row_in(&table, Pk::Int(key))
+            .get_row_in(table, Pk::Int(key))
             .map_err(|e| StorageError::Vault(e.to_string()))?
         else {
             continue;
@@ -892,20 +1080,78 @@ pub fn load_messages(contact: &mut Contact, storage: &FlatStorage) -> Result<(),
             .bytes("ack_hash")
             .filter(|b| b.len() == 32)
             .map(|b| b.try_into().unwrap());
-        contact.messages.push(ChatMessage {
+        messages.push(ChatMessage {
             content: content.to_string(),
             timestamp: rec.time("timestamp").unwrap_or(0),
             is_outgoing: rec.uint("is_outgoing").unwrap_or(0) != 0,
             delivered: rec.uint("delivered").unwrap_or(0) != 0,
             ack_hash,
             recovered: rec.uint("recovered").unwrap_or(0) != 0,
+            failed: false,
+            image: rec.bytes("image").map(|b| b.to_vec()),
+            attachment: load_attachment(&rec),
+            voice: load_voice(&rec),
         });
     }
+    Ok(messages)
+}
 
-    #[cfg(feature = "development")]
-    crate::logf!("STORAGE: Loaded {} messages for seed {}", contact.messages.len(), hex::encode(&contact.handle_hash[..4]));
+/// Move the conversation with `their_identity_seed` from the table a vault under `old_vault_seed` addressed it by into this vault's own table — whole rows, media columns included. Rotation copies the old vault's raw entries across first (conversation tables are named from the vault seed, so the rows arrive under the OLD table); this re-files them, checks the new table reads back message-for-message identical to the old one, and only then deletes the old rows. Returns the rows moved.
+pub(super) fn retable_conversation(
+    their_identity_seed: &[u8; 32],
+    old_vault_seed: &[u8; 32],
+    storage: &FlatStorage,
+) -> Result<usize, StorageError> {
+    let from = conversation_id(old_vault_seed, their_identity_seed);
+    let to = conversation_id(storage.vault_seed(), their_identity_seed);
+    if from == to {
+        return Ok(0);
+    }
+    let mut db = Db::open(storage).map_err(|e| StorageError::Vault(e.to_string()))?;
+    let keys: Vec<u64> = db
+        .list_in(&from)
+        .map_err(|e| StorageError::Vault(e.to_string()))?
+        .into_iter()
+        .filter_map(|pk| match pk {
+            Pk::Int(t) => Some(t),
+            _ => None,
+        })
+        .collect();
+    for &key in &keys {
+        if let Some(rec) = db.get_row_in(&from, Pk::Int(key)).map_err(|e| StorageError::Vault(e.to_string()))? {
+            db.put_row_in(&to, Pk::Int(key), &rec).map_err(|e| StorageError::Vault(e.to_string()))?;
+        }
+    }
+    if messages_digest(&load_table(&from, storage)?) != messages_digest(&load_table(&to, storage)?) {
+        return Err(StorageError::Vault("conversation move: new table differs from old".to_string()));
+    }
+    for &key in &keys {
+        db.delete_row_in(&from, Pk::Int(key)).map_err(|e| StorageError::Vault(e.to_string()))?;
+    }
+    Ok(keys.len())
+}
 
-    Ok(())
+/// Digest over every persisted field of a conversation (content, flags, ack hash, media) — two tables that digest equal hold the same history.
+fn messages_digest(msgs: &[ChatMessage]) -> [u8; 32] {
+    let mut h = blake3::Hasher::new();
+    for m in msgs {
+        h.update(&m.timestamp.to_le_bytes());
+        h.update(&[m.is_outgoing as u8, m.delivered as u8, m.recovered as u8]);
+        h.update(&m.ack_hash.unwrap_or_default());
+        for part in [
+            m.content.as_bytes(),
+            m.image.as_deref().unwrap_or_default(),
+            m.attachment.as_ref().map_or(&[][..], |a| a.name.as_bytes()),
+            m.attachment.as_ref().map_or(&[][..], |a| &a.bytes[..]),
+            m.voice.as_ref().map_or(&[][..], |v| &v.opus[..]),
+            m.voice.as_ref().map_or(&[][..], |v| &v.waveform[..]),
+        ] {
+            h.update(&(part.len() as u64).to_le_bytes());
+            h.update(part);
+        }
+        h.update(&m.voice.as_ref().map_or(0, |v| v.duration_ms).to_le_bytes());
+    }
+    *h.finalize().as_bytes()
 }
 
 /// Persist ONLY the given rows into the conversation table (same field layout as [`save_messages`]). History recovery lands pages of ~50 rows at a time — rewriting the whole conversation per page would be O(n) per page; this is O(page).
@@ -933,6 +1179,15 @@ pub fn save_messages_page(
         if msg.recovered {
             rec = rec.set("recovered", 1u64);
         }
+        if let Some(img) = &msg.image {
+            rec = rec.set("image", img.clone());
+        }
+        if let Some(file) = &msg.attachment {
+            rec = rec.set("file", file.bytes.clone()).set("file_name", file.name.clone());
+        }
+        if let Some(memo) = &msg.voice {
+            rec = rec.set("voice", memo.opus.clone()).set("voice_ms", memo.duration_ms as u64).set("voice_wave", memo.waveform.clone());
+        }
         db.put_row_in(&table, Pk::Int(msg.timestamp as u64), &rec)
             .map_err(|e| StorageError::Vault(e.to_string()))?;
     }
@@ -991,6 +1246,10 @@ pub fn load_message_page_before(
             delivered: rec.uint("delivered").unwrap_or(0) != 0,
             ack_hash: None, // never leaves this device; not part of a served page
             recovered: rec.uint("recovered").unwrap_or(0) != 0,
+            failed: false,
+            image: rec.bytes("image").map(|b| b.to_vec()),
+            attachment: load_attachment(&rec),
+            voice: load_voice(&rec),
         });
         taken += 1;
     }
@@ -1004,6 +1263,102 @@ pub fn load_message_page_before(
     Ok((page, more))
 }
 
+/// Clear the local history of the conversation with `contact`: every row is overwritten with its text and media zeroed, then deleted; the in-memory list is emptied and the history floor raised so recovery can't refill it. `reset_chains` also deletes the friendship's persisted chains — the caller then owns re-running the ceremony and dropping its in-memory chains. The caller persists contact state (the floor). Returns how many rows went.
+/// The overwrite leaves the engine holding nothing but zeros for each key, but the vault is append-only: the original rows' sealed blocks stay in the file until a [`compact`](crate::storage::compact()) rebuilds it from live records. The caller finishes the wipe by [`owe`](crate::storage::compact::owe)-ing one, which the next launch runs before the vault opens.
+pub fn wipe_messages(contact: &mut Contact, storage: &FlatStorage, reset_chains: bool) -> Result<usize, StorageError> {
+    let table = conversation_id(storage.vault_seed(), &contact.handle_hash);
+    let stored = load_conversation(&contact.handle_hash, storage)?;
+    let mut db = Db::open(storage).map_err(|e| StorageError::Vault(e.to_string()))?;
+    for m in &stored {
+        let mut zeroed = Record::new()
+            .set("content", "\0".repeat(m.content.len()))
+            .set("timestamp", Value::Time(m.timestamp));
+        if let Some(img) = &m.image {
+            zeroed = zeroed.set("image", vec![0u8; img.len()]);
+        }
+        if let Some(file) = &m.attachment {
+            zeroed = zeroed.set("file", vec![0u8; file.bytes.len()]).set("file_name", "\0".repeat(file.name.len()));
+        }
+        if let Some(memo) = &m.voice {
+            zeroed = zeroed.set("voice", vec![0u8; memo.opus.len()]).set("voice_ms", 0u64).set("voice_wave", vec![0u8; memo.waveform.len()]);
+        }
+        db.put_row_in(&table, Pk::Int(m.timestamp as u64), &zeroed)
+            .map_err(|e| StorageError::Vault(e.to_string()))?;
+    }
+    let keys: Vec<u64> = db
+        .list_in(&table)
+        .map_err(|e| StorageError::Vault(e.to_string()))?
+        .into_iter()
+        .filter_map(|pk| match pk {
+            Pk::Int(t) => Some(t),
+            _ => None,
+        })
+        .collect();
+    for &key in &keys {
+        db.delete_row_in(&table, Pk::Int(key))
+            .map_err(|e| StorageError::Vault(e.to_string()))?;
+    }
+    let newest = keys.iter().copied().max().map_or(0, |k| k as i64);
+    let newest = contact.messages.iter().map(|m| m.timestamp).fold(newest, i64::max);
+    contact.history_floor = contact.history_floor.max(newest);
+    contact.messages.clear();
+    if reset_chains {
+        if let Some(fid) = contact.friendship_id.as_ref() {
+            crate::storage::friendship::delete_friendship_chains(fid, storage)?;
+        }
+    }
+    crate::logf!("STORAGE: wiped {} message row(s) for {} (chains reset: {})", keys.len(), crate::fp(&contact.handle_hash), reset_chains);
+    Ok(keys.len())
+}
+
+/// Enforce `policy` on the conversation with `contact`: [`prune_conversation`], then [`drop_pruned`]. Returns how many messages went.
+pub fn apply_retention(
+    contact: &mut Contact,
+    policy: crate::types::Retention,
+    now: i64,
+    storage: &FlatStorage,
+) -> Result<usize, StorageError> {
+    let doomed = prune_conversation(&contact.handle_hash, policy, now, storage)?;
+    drop_pruned(contact, &doomed);
+    Ok(doomed.len())
+}
+
+/// The storage half of retention: delete the rows `policy` prunes from the conversation with `party_id` and return their timestamps. Works from the stored conversation, not the in-memory list, so rows a history page landed but the UI hasn't loaded are swept too. Touches no `Contact`, so the app runs it on a worker. The freed vault space is reclaimed by the next compaction.
+pub fn prune_conversation(
+    party_id: &[u8; 32],
+    policy: crate::types::Retention,
+    now: i64,
+    storage: &FlatStorage,
+) -> Result<Vec<i64>, StorageError> {
+    if policy.is_forever() {
+        return Ok(Vec::new());
+    }
+    let stored = load_conversation(party_id, storage)?;
+    let doomed = policy.prunable(&stored, now);
+    if doomed.is_empty() {
+        return Ok(doomed);
+    }
+    let table = conversation_id(storage.vault_seed(), party_id);
+    let mut db = Db::open(storage).map_err(|e| StorageError::Vault(e.to_string()))?;
+    for &t in &doomed {
+        db.delete_row_in(&table, Pk::Int(t as u64))
+            .map_err(|e| StorageError::Vault(e.to_string()))?;
+    }
+    crate::logf!("STORAGE: retention pruned {} message(s) for {}", doomed.len(), crate::fp(party_id));
+    Ok(doomed)
+}
+
+/// The memory half of retention: drop the messages [`prune_conversation`] deleted (`doomed`, their timestamps) from `contact.messages` and raise its history floor past them.
+pub fn drop_pruned(contact: &mut Contact, doomed: &[i64]) {
+    if doomed.is_empty() {
+        return;
+    }
+    // Every settled row at or before the newest pruned one went (both bounds cut a prefix), so that's exactly the floor recovery must respect.
+    contact.history_floor = contact.history_floor.max(doomed.iter().copied().max().unwrap_or(0));
+    let doomed: std::collections::HashSet<i64> = doomed.iter().copied().collect();
+    contact.messages.retain(|m| !doomed.contains(&m.timestamp) || (m.is_outgoing && !m.delivered));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1078,6 +1433,10 @@ mod tests {
                 delivered: true,
                 ack_hash: None,
                 recovered: false,
+                failed: false,
+                image: None,
+                attachment: None,
+                voice: None,
             },
             ChatMessage {
                 content: "hey".to_string(),
@@ -1086,6 +1445,10 @@ mod tests {
                 delivered: false,
                 ack_hash: Some([0x7Au8; 32]), // received msg: its ACK hash must survive the round-trip
                 recovered: false,
+                failed: false,
+                image: None,
+                attachment: None,
+                voice: None,
             },
             ChatMessage {
                 content: "👋 unicode".to_string(),
@@ -1094,6 +1457,10 @@ mod tests {
                 delivered: false,
                 ack_hash: None,
                 recovered: true, // friend-attested provenance must survive the round-trip
+                failed: false,
+                image: None,
+                attachment: None,
+                voice: None,
             },
         ];
 
@@ -1285,6 +1652,173 @@ mod tests {
         }
     }
 
+    /// Last-seen: a presence report moves the stamp (at minute grain, so a pong every sweep isn't a write every sweep), and the stamp survives a vault close/reopen so "last seen 2h ago" still reads right after a restart.
+    #[test]
+    fn last_seen_updates_on_pong_and_survives_reload() {
+        use crate::types::contact::LAST_SEEN_GRAIN_OSC;
+        use crate::types::HandleText;
+
+        let device_secret = [43u8; 32];
+        let vault_seed = *ihi::handle_to_hash("me-last-seen-test").as_bytes();
+        let app = crate::storage::APP;
+
+        let mut c = Contact::new(HandleText::new("erin"), [0x88; 32], DevicePubkey::from_bytes([0x30; 32]));
+        assert_eq!(c.last_seen, None);
+        let t = 1_000_000 * crate::OSC_PER_SEC;
+        assert!(c.update_last_seen(t), "first pong stamps");
+        assert!(c.last_seen_dirty);
+        assert!(!c.update_last_seen(t + 5 * crate::OSC_PER_SEC), "the next sweep's pong is inside the grain");
+        assert!(c.update_last_seen(t + LAST_SEEN_GRAIN_OSC));
+        assert_eq!(c.last_seen, Some(t + LAST_SEEN_GRAIN_OSC));
+        c.verified = true; // rides the same state entry — a confirmed safety number must survive the reopen too
+        c.muted = true; // and so does a per-contact mute
+        c.pinned = true; // and a pin
+        c.archived = true; // and an archive
+
+        let identity = ContactIdentity {
+            handle_proof: [0x88; 32],
+            party_id: crate::crypto::clutch::identity_party_id(&crate::types::Handle::to_identity_seed("erin")),
+            name: String::new(),
+            avatar_pin: [0u8; 64],
+        };
+        {
+            let storage = FlatStorage::new(app, vault_seed, device_secret).unwrap();
+            save_contact_state(&c, &storage).unwrap();
+        }
+        let storage = FlatStorage::new(app, vault_seed, device_secret).unwrap();
+        let loaded = load_contact_state(&identity, &storage).unwrap();
+        assert_eq!(loaded.last_seen, Some(t + LAST_SEEN_GRAIN_OSC));
+        assert!(!loaded.last_seen_dirty);
+        assert!(loaded.verified);
+        assert!(loaded.muted);
+        assert!(loaded.pinned);
+        assert!(loaded.archived);
+
+        if let Ok([primary, shadow]) = kete::vault_ring_paths(app, &vault_seed, &device_secret) {
+            let _ = std::fs::remove_file(primary);
+            let _ = std::fs::remove_file(shadow);
+        }
+    }
+
+    /// Retention on a real vault: a conversation past `keep_last` loses its oldest settled rows (store AND memory), the newest survive, and an old un-ACKed outgoing message survives regardless — it's still owed a delivery. The policy itself rides contact state across a reopen.
+    #[test]
+    fn retention_prunes_old_rows_but_keeps_recent_and_pending() {
+        use crate::types::{HandleText, Retention};
+
+        let device_secret = [47u8; 32];
+        let vault_seed = *ihi::handle_to_hash("me-retention-test").as_bytes();
+        let app = crate::storage::APP;
+        let day = 86_400 * crate::OSC_PER_SEC;
+        let now = 1_000 * day;
+
+        let mut c = Contact::new(HandleText::new("fern"), [0x91; 32], DevicePubkey::from_bytes([0x31; 32]));
+        // Ten settled messages a day apart (oldest first), plus one ancient outgoing message never ACKed.
+        c.messages.push(ChatMessage {
+            content: "still sending".to_string(),
+            timestamp: now - 500 * day,
+            is_outgoing: true,
+            delivered: false,
+            ack_hash: None,
+            recovered: false,
+            failed: false,
+            image: None,
+            attachment: None,
+            voice: None,
+        });
+        for i in 0..10i64 {
+            c.messages.push(ChatMessage {
+                content: format!("msg {i}"),
+                timestamp: now - (10 - i) * day,
+                is_outgoing: i % 2 == 0,
+                delivered: true,
+                ack_hash: None,
+                recovered: false,
+                failed: false,
+                image: None,
+                attachment: None,
+                voice: None,
+            });
+        }
+        let policy = Retention { keep_last: 4, keep_days: 0 };
+        c.retention = Some(policy);
+
+        let storage = FlatStorage::new(app, vault_seed, device_secret).unwrap();
+        save_messages(&c, &storage).unwrap();
+        assert_eq!(apply_retention(&mut c, policy, now, &storage).unwrap(), 6);
+
+        let kept: Vec<String> = load_conversation(&c.handle_hash, &storage).unwrap().into_iter().map(|m| m.content).collect();
+        assert_eq!(kept, ["still sending", "msg 6", "msg 7", "msg 8", "msg 9"]);
+        assert_eq!(c.messages.len(), 5, "memory mirrors the store");
+        assert_eq!(c.history_floor, now - 5 * day, "recovery must not refill what retention pruned");
+        // A second sweep finds nothing left to prune.
+        assert_eq!(apply_retention(&mut c, policy, now, &storage).unwrap(), 0);
+
+        // The days bound: 2 days keeps only the last two settled messages; pending still stays.
+        assert_eq!(Retention { keep_last: 0, keep_days: 2 }.prunable(&c.messages, now), [now - 4 * day, now - 3 * day]);
+        assert!(Retention::default().prunable(&c.messages, now).is_empty());
+
+        save_contact_state(&c, &storage).unwrap();
+        let identity = ContactIdentity {
+            handle_proof: [0x91; 32],
+            party_id: crate::crypto::clutch::identity_party_id(&crate::types::Handle::to_identity_seed("fern")),
+            name: String::new(),
+            avatar_pin: [0u8; 64],
+        };
+        drop(storage);
+        let storage = FlatStorage::new(app, vault_seed, device_secret).unwrap();
+        assert_eq!(load_contact_state(&identity, &storage).unwrap().retention, Some(policy));
+
+        if let Ok([primary, shadow]) = kete::vault_ring_paths(app, &vault_seed, &device_secret) {
+            let _ = std::fs::remove_file(primary);
+            let _ = std::fs::remove_file(shadow);
+        }
+    }
+
+    /// Clearing a conversation on a real vault: every row is gone from the store (across a reopen), memory is empty, the floor covers the newest wiped row, and a chain reset drops the persisted chains too.
+    #[test]
+    fn wipe_messages_empties_store_and_memory() {
+        use crate::types::HandleText;
+
+        let device_secret = [53u8; 32];
+        let vault_seed = *ihi::handle_to_hash("me-wipe-test").as_bytes();
+        let app = crate::storage::APP;
+
+        let mut c = Contact::new(HandleText::new("gale"), [0x92; 32], DevicePubkey::from_bytes([0x32; 32]));
+        for t in [100i64, 200, 300] {
+            c.messages.push(ChatMessage {
+                content: format!("secret {t}"),
+                timestamp: t,
+                is_outgoing: t == 200,
+                delivered: true,
+                ack_hash: None,
+                recovered: false,
+                failed: false,
+                image: None,
+                attachment: None,
+                voice: None,
+            });
+        }
+        let fid = FriendshipId::derive(&[[0x92; 32], c.handle_hash]);
+        c.friendship_id = Some(fid);
+        {
+            let storage = FlatStorage::new(app, vault_seed, device_secret).unwrap();
+            save_messages(&c, &storage).unwrap();
+            storage.write_addr(&crate::storage::vault_key("chains", fid.as_bytes()), b"chains").unwrap();
+            assert_eq!(wipe_messages(&mut c, &storage, true).unwrap(), 3);
+        }
+        assert!(c.messages.is_empty());
+        assert_eq!(c.history_floor, 300);
+
+        let storage = FlatStorage::new(app, vault_seed, device_secret).unwrap();
+        assert!(load_conversation(&c.handle_hash, &storage).unwrap().is_empty());
+        assert!(storage.read_addr(&crate::storage::vault_key("chains", fid.as_bytes())).unwrap().is_none(), "chain reset deletes the persisted chains");
+
+        if let Ok([primary, shadow]) = kete::vault_ring_paths(app, &vault_seed, &device_secret) {
+            let _ = std::fs::remove_file(primary);
+            let _ = std::fs::remove_file(shadow);
+        }
+    }
+
     /// Newest-first cursor pagination over a real vault: head page = the newest rows, the cursor walk visits everything exactly once, terminates with more=false — and `load_messages` returns time-sorted output even though recovery inserts OLDER rows into the catalog LATER.
     #[test]
     fn history_pagination_walk_and_load_sort() {
@@ -1305,6 +1839,10 @@ mod tests {
             delivered: t % 2 == 0,
             ack_hash: None,
             recovered: t <= 60, // the "older, recovered" half
+            failed: false,
+            image: None,
+            attachment: None,
+            voice: None,
         };
         let newer: Vec<ChatMessage> = (61..=120).map(make).collect();
         let older: Vec<ChatMessage> = (1..=60).map(make).collect();
@@ -8,6 +8,20 @@ use fgtw::fstate::{
     DeviceSetting, DeviceSettings, SettingEntry,
 };
 
+/// Zoom (`ru`) bounds a stored `display.zoom` is held to on load — the same range the host's release build lets Ctrl +/− reach. A value outside it can only come from a hand-edited vault, another build's wider debug range, or a merged device map from a newer client; restoring it raw could open a 1-glyph-wide or unreadably tiny window.
+pub const ZOOM_MIN: f32 = 0.125;
+pub const ZOOM_MAX: f32 = 2.0;
+
+/// Decode a stored `display.zoom` value (f32 LE) into the `ru` to restore. Wrong length, NaN, infinite or non-positive → `None` (keep the host's 1.0); anything else is clamped into `[ZOOM_MIN, ZOOM_MAX]` — a genuine bound on untrusted stored input, not a masked bug.
+pub fn decode_zoom(bytes: &[u8]) -> Option<f32> {
+    let raw: [u8; 4] = bytes.try_into().ok()?;
+    let ru = f32::from_le_bytes(raw);
+    if !ru.is_finite() || ru <= 0.0 {
+        return None;
+    }
+    Some(ru.clamp(ZOOM_MIN, ZOOM_MAX))
+}
+
 /// The cached settings state for this identity, plus which device WE are (the single-writer key for our own map).
 #[derive(Debug, Clone)]
 pub struct FleetSettings {
@@ -170,4 +184,20 @@ mod tests {
         // Idempotent: merging the same state again changes nothing.
         assert!(!fs.merge_from(fs.global.clone(), fs.devices.clone()));
     }
+
+    #[test]
+    fn stored_zoom_is_clamped_on_load_and_in_range_value_applies() {
+        let mut fs = FleetSettings::new([7; 32]);
+        // Out of range (a wider debug build's 4×) → held to the release max.
+        fs.set("display.zoom", 4.0f32.to_le_bytes().to_vec(), 100);
+        assert_eq!(fs.effective("display.zoom").and_then(decode_zoom), Some(ZOOM_MAX));
+        assert_eq!(decode_zoom(&0.01f32.to_le_bytes()), Some(ZOOM_MIN));
+        // In range → restored exactly.
+        fs.set("display.zoom", 1.5f32.to_le_bytes().to_vec(), 200);
+        assert_eq!(fs.effective("display.zoom").and_then(decode_zoom), Some(1.5));
+        // Garbage never becomes a zoom.
+        assert_eq!(decode_zoom(&f32::NAN.to_le_bytes()), None);
+        assert_eq!(decode_zoom(&(-1.0f32).to_le_bytes()), None);
+        assert_eq!(decode_zoom(&[1, 2, 3]), None);
+    }
 }
@@ -7,6 +7,7 @@
 use vsf::schema::{SectionSchema, TypeConstraint};
 use vsf::VsfType;
 
+use crate::storage::unflushed::{self, Unflushed};
 use crate::storage::{FlatStorage, StorageError};
 use crate::types::{FriendshipChains, FriendshipId};
 
@@ -34,6 +35,8 @@ fn chains_schema() -> SectionSchema {
         .field("pending_prev_msg_hp", TypeConstraint::AnyHash) // hp
         .field("pending_msg_hp", TypeConstraint::AnyHash) // hp
         .field("pending_ciphertext", TypeConstraint::Wrapped(b'X')) // vX: ciphertext bytes
+        // Pending woven strands (v7) — one field per pending message: its eagle_time (e6) then each frozen strand as x, so the ACK after a restart advances with the same bytes the receiver used
+        .field("pending_strands", TypeConstraint::Any)
         // Bidirectional entropy state (v3)
         .field("last_received_weave", TypeConstraint::AnyHash) // hp: derived weave hash (32 bytes)
         .field("last_sent_weave", TypeConstraint::AnyHash) // hp: what we sent (what they received)
@@ -55,6 +58,15 @@ fn chains_key(friendship_id: &FriendshipId) -> [u8; 32] {
 pub fn save_friendship_chains(
     chains: &FriendshipChains,
     storage: &FlatStorage,
+) -> Result<(), StorageError> {
+    let result = write_friendship_chains(chains, storage);
+    unflushed::record(Unflushed::Chains(*chains.id().as_bytes()), &result);
+    result
+}
+
+fn write_friendship_chains(
+    chains: &FriendshipChains,
+    storage: &FlatStorage,
 ) -> Result<(), StorageError> {
     let friendship_id = chains.id();
 
@@ -62,7 +74,7 @@ pub fn save_friendship_chains(
     let schema = chains_schema();
     let mut builder = schema
         .build()
-        .set("version", 6u8) // v6: adds the optional history_key (v5 = last_received_times)
+        .set("version", 7u8) // v7: adds pending_strands (v6 = history_key, v5 = last_received_times)
         .map_err(|e| StorageError::Parse(e.to_string()))?
         .set(
             "friendship_id",
@@ -137,6 +149,18 @@ pub fn save_friendship_chains(
                 vec![VsfType::v(b'X', pending.ciphertext.clone())],
             )
             .map_err(|e| StorageError::Parse(e.to_string()))?;
+
+        // Strands are keyed by the message's eagle_time rather than by position, so an anchor message (no strands) still writes a field and a pre-v7 file simply has none.
+        let mut strands = vec![VsfType::e(vsf::types::EtType::e6(pending.eagle_time))];
+        strands.extend(
+            pending
+                .woven_strands
+                .iter()
+                .map(|s| VsfType::x(String::from_utf8_lossy(s).into_owned())),
+        );
+        builder = builder
+            .append_multi("pending_strands", strands)
+            .map_err(|e| StorageError::Parse(e.to_string()))?;
     }
 
     // === Bidirectional entropy state (v3) ===
@@ -350,6 +374,23 @@ pub fn load_friendship_chains(
         .min(msg_hps.len())
         .min(ciphertexts.len());
 
+    // === Pending woven strands (v7) — eagle_time → frozen strands; absent (pre-v7 file) weaves nothing ===
+    let mut strands_by_time: std::collections::HashMap<i64, Vec<Vec<u8>>> =
+        std::collections::HashMap::new();
+    for field in section.get_fields("pending_strands") {
+        let Some(VsfType::e(vsf::types::EtType::e6(osc))) = field.values.first() else {
+            continue;
+        };
+        let strands = field.values[1..]
+            .iter()
+            .filter_map(|v| match v {
+                VsfType::x(s) => Some(s.as_bytes().to_vec()),
+                _ => None,
+            })
+            .collect();
+        strands_by_time.insert(*osc, strands);
+    }
+
     let pending_messages: Vec<PendingMessage> = (0..pending_count)
         .map(|i| PendingMessage {
             eagle_time: eagle_times[i],
@@ -358,8 +399,8 @@ pub fn load_friendship_chains(
             prev_msg_hp: prev_msg_hps[i],
             msg_hp: msg_hps[i],
             ciphertext: ciphertexts[i].clone(),
-            // Not persisted (runtime-only braid-strand snapshot). A pending message reloaded after restart weaves no strands; in practice pending messages are short-lived (cleared on ACK) so this edge only matters if the app restarts mid-flight with an unacked message AND its braid strands were non-empty — a known minor gap, not the steady-state desync this fix addresses.
-            woven_strands: Vec::new(),
+            // Persisted since v7 so the ACK that lands after a restart advances with the exact strands frozen at send time. A pre-v7 file has none and falls back to weaving nothing.
+            woven_strands: strands_by_time.remove(&eagle_times[i]).unwrap_or_default(),
             // Reliability state is runtime-only. A pending message reloaded after restart is eligible to resend immediately (attempts reset to 1, deadline = its eagle_time so it's already due).
             attempts: 1,
             next_retry_osc: eagle_times[i],
@@ -492,6 +533,45 @@ mod tests {
         assert_eq!(loaded.history_key(), chains.history_key());
     }
 
+    #[test]
+    fn unacked_message_survives_restart_and_clears_on_ack() {
+        let alice = [1u8; 32];
+        let bob = [2u8; 32];
+        let eggs: Vec<[u8; 32]> = (0..8).map(|i| [i as u8; 32]).collect();
+        let mut chains = FriendshipChains::from_clutch(&[alice, bob], &eggs);
+        let storage = FlatStorage::new(crate::storage::APP, [0xAC; 32], [0xBD; 32]).unwrap();
+
+        // Queue one message (braided over one peer strand) and persist, as send_chain_message does before the network send.
+        let sent_at = 1 << 40;
+        let plaintext = b"see you at eight".to_vec();
+        let hash = *blake3::hash(&plaintext).as_bytes();
+        let strands = vec![b"dinner?".to_vec()];
+        chains.add_pending(sent_at, plaintext.clone(), hash, [3; 32], [4; 32], vec![9; 48], strands.clone());
+        // The same send persisted twice must still go out once.
+        chains.add_pending(sent_at, plaintext, hash, [3; 32], [4; 32], vec![9; 48], strands.clone());
+        save_friendship_chains(&chains, &storage).unwrap();
+
+        // "Restart": reload from disk and re-enqueue.
+        let now = sent_at + (1 << 30);
+        let mut reloaded = load_friendship_chains(chains.id(), &storage).unwrap();
+        assert_eq!(reloaded.requeue_after_restart(now), 1);
+        assert_eq!(reloaded.pending_messages()[0].woven_strands, strands);
+
+        // Re-sent on the first sweep...
+        let due = reloaded.collect_due_retransmits(now);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].0, sent_at);
+        assert_eq!(due[0].2, vec![9; 48]);
+
+        // ...and cleared for good once the ACK lands.
+        assert!(reloaded.process_ack(&alice, sent_at, &hash));
+        assert!(reloaded.pending_messages().is_empty());
+        save_friendship_chains(&reloaded, &storage).unwrap();
+        let mut again = load_friendship_chains(chains.id(), &storage).unwrap();
+        assert_eq!(again.requeue_after_restart(now), 0);
+        assert!(again.collect_due_retransmits(now).is_empty());
+    }
+
     #[test]
     fn history_key_deterministic_both_sides() {
         // The both-sides property: identical participants + eggs (what CLUTCH guarantees at completion) → identical history keys; different eggs → different keys.
@@ -0,0 +1,169 @@
+//! Schema versions for the vault's VSF stores, and the registry that upgrades old ones on load.
+//!
+//! Each versioned store carries a `version` field in its section header. A record without one predates versioning and reads as v1. On load, [`load_current`] walks the record up thru [`MIGRATIONS`] one version at a time and re-saves the result, so every record is upgraded once and never misparsed. A record from a NEWER build is refused instead of being read with an older schema that would drop its new fields.
+//!
+//! Friendship chains keep their own in-loader version handling (`friendship.rs`, v2 → v6 are all additive optional fields), so they aren't in this registry.
+
+use crate::storage::contacts::{
+    contact_list_schema, decode_contact_list, decode_sibling_list, encode_contact_list, encode_sibling_list,
+    sibling_list_schema,
+};
+use crate::storage::{FlatStorage, StorageError};
+use vsf::schema::{SectionBuilder, SectionSchema};
+
+/// A versioned vault store.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Store {
+    /// The contacts index (`vault_key("contacts", vault_seed)`).
+    ContactList,
+    /// The own-fleet sibling index (`vault_key("siblings", vault_seed)`).
+    SiblingList,
+}
+
+impl Store {
+    /// The version this build writes.
+    pub fn current(self) -> u8 {
+        match self {
+            Store::ContactList => 2,
+            Store::SiblingList => 2,
+        }
+    }
+
+    fn schema(self) -> SectionSchema {
+        match self {
+            Store::ContactList => contact_list_schema(),
+            Store::SiblingList => sibling_list_schema(),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Store::ContactList => "contact list",
+            Store::SiblingList => "sibling list",
+        }
+    }
+}
+
+/// One upgrade step: a record at version N in, the same record at version N + 1 out.
+pub type Migration = fn(&[u8]) -> Result<Vec<u8>, StorageError>;
+
+/// (store, from-version, step to from-version + 1). Every version below a store's `current()` needs exactly one entry here.
+pub const MIGRATIONS: &[(Store, u8, Migration)] = &[
+    (Store::ContactList, 1, contact_list_v1_to_v2),
+    (Store::SiblingList, 1, sibling_list_v1_to_v2),
+];
+
+/// v1 → v2 only stamps the header version; the rows are unchanged, so decode + re-encode is the whole step.
+fn contact_list_v1_to_v2(bytes: &[u8]) -> Result<Vec<u8>, StorageError> {
+    encode_contact_list(&decode_contact_list(bytes)?)
+}
+
+/// v1 → v2 only stamps the header version (see [`contact_list_v1_to_v2`]).
+fn sibling_list_v1_to_v2(bytes: &[u8]) -> Result<Vec<u8>, StorageError> {
+    encode_sibling_list(&decode_sibling_list(bytes)?)
+}
+
+/// The schema version a stored record was written at (absent field = v1).
+pub fn version_of(store: Store, bytes: &[u8]) -> Result<u8, StorageError> {
+    let section = SectionBuilder::parse(store.schema(), bytes)
+        .map_err(|e| StorageError::Parse(format!("{} parse: {}", store.name(), e)))?;
+    let version = section
+        .get_fields("version")
+        .first()
+        .and_then(|f| f.values.first())
+        .and_then(|v| v.as_usize())
+        .unwrap_or(1);
+    u8::try_from(version).map_err(|_| StorageError::Parse(format!("{} version {} out of range", store.name(), version)))
+}
+
+/// Bring a record up to the current version. Returns the bytes and whether anything changed (the caller re-saves).
+pub fn upgrade(store: Store, mut bytes: Vec<u8>) -> Result<(Vec<u8>, bool), StorageError> {
+    let mut version = version_of(store, &bytes)?;
+    if version > store.current() {
+        return Err(StorageError::Parse(format!(
+            "{} is v{}, newer than this build's v{} — update Photon to read it",
+            store.name(),
+            version,
+            store.current()
+        )));
+    }
+    let migrated = version < store.current();
+    while version < store.current() {
+        let Some((_, _, step)) = MIGRATIONS.iter().find(|(s, from, _)| *s == store && *from == version) else {
+            return Err(StorageError::Parse(format!("no migration for {} v{}", store.name(), version)));
+        };
+        bytes = step(&bytes)?;
+        version += 1;
+    }
+    Ok((bytes, migrated))
+}
+
+/// Read a versioned entry, upgrading it to the current version and writing the upgrade back before returning it. `None` when the entry doesn't exist.
+pub fn load_current(storage: &FlatStorage, store: Store, addr: &[u8; 32]) -> Result<Option<Vec<u8>>, StorageError> {
+    let Some(bytes) = storage.read_addr(addr)? else {
+        return Ok(None);
+    };
+    let (bytes, migrated) = upgrade(store, bytes)?;
+    if migrated {
+        storage.write_addr(addr, &bytes)?;
+        crate::logf!("STORAGE: migrated {} to v{}", store.name(), store.current());
+    }
+    Ok(Some(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::contacts::load_contact_list;
+    use crate::storage::vault_key;
+    use vsf::VsfType;
+
+    #[test]
+    fn v1_contact_list_is_migrated_and_resaved_on_load() {
+        let seed = *ihi::handle_to_hash("me-migrate-test").as_bytes();
+        let device = [46u8; 32];
+        let remove = || {
+            if let Ok(ring) = kete::vault_ring_paths(crate::storage::APP, &seed, &device) {
+                for p in &ring {
+                    let _ = std::fs::remove_file(p);
+                }
+            }
+        };
+        remove();
+
+        // A pre-versioning index: same rows, no header version.
+        let v1 = contact_list_schema()
+            .build()
+            .append_multi(
+                "contact",
+                vec![
+                    VsfType::hP(vec![1; 32]),
+                    VsfType::ke(vec![2; 32]),
+                    VsfType::ge(vec![3; 64]),
+                    VsfType::x("Bob".to_string()),
+                ],
+            )
+            .unwrap()
+            .encode()
+            .unwrap();
+        assert_eq!(version_of(Store::ContactList, &v1).unwrap(), 1);
+
+        let storage = FlatStorage::new(crate::storage::APP, seed, device).unwrap();
+        let addr = vault_key("contacts", &seed);
+        storage.write_addr(&addr, &v1).unwrap();
+
+        let contacts = load_contact_list(&storage).unwrap();
+        assert_eq!(contacts.len(), 1);
+        assert_eq!((contacts[0].party_id, contacts[0].name.as_str()), ([2; 32], "Bob"));
+        let stored = storage.read_addr(&addr).unwrap().unwrap();
+        assert_eq!(version_of(Store::ContactList, &stored).unwrap(), Store::ContactList.current());
+
+        // Already current → untouched; from a newer build → refused, not misread.
+        assert!(!upgrade(Store::ContactList, stored).unwrap().1);
+        let future = contact_list_schema().build().set("version", 9u8).unwrap().encode().unwrap();
+        assert!(upgrade(Store::ContactList, future).is_err());
+
+        drop(storage);
+        remove();
+    }
+}
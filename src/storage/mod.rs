@@ -1,13 +1,21 @@
+pub mod backup;
 pub mod cloud;
+pub mod compact;
 pub mod contacts;
 pub mod device_binding;
 pub mod fleet_settings;
 pub mod friendship;
+pub mod migrate;
 pub mod settings;
+pub mod unflushed;
 
 // The storage adapter (was `flat.rs`) now lives in the shared `kete` crate. Re-export its surface so existing call sites — `crate::storage::FlatStorage`, `StorageError`, `encrypt_bytes`/`decrypt_bytes` (used by cloud.rs) — keep resolving unchanged.
 pub use kete::{decrypt_bytes, encrypt_bytes, App, FlatStorage, StorageError};
 
+// Whole-vault maintenance passes, callable as `storage::rotate_encryption` / `storage::compact`.
+pub use backup::rotate_encryption;
+pub use compact::compact;
+
 /// Photon's app namespace for kete. `id`/`dir` reproduce the original baked-in `"photon"` / `"Photon"` constants exactly, so every existing vault's filename and KDF contexts are unchanged.
 pub const APP: kete::App<'static> = kete::App {
     id: "photon",
@@ -29,7 +37,22 @@ pub fn vault_key(domain: &str, scope: &[u8; 32]) -> [u8; 32] {
     blake3::derive_key(&format!("{}.storage.entry.v0", APP.id), &input)
 }
 
-/// Returns ~/.config/photon/ (or Android equivalent). All Photon files live here.
+/// Env var that relocates the whole data root. Read at every path resolution, so setting it before the first storage access moves everything.
+pub const DATA_DIR_ENV: &str = "PHOTON_DATA_DIR";
+
+/// The data-root override, if one is set (`PHOTON_DATA_DIR` or [`set_data_dir`]). Empty counts as unset.
+pub fn data_dir_override() -> Option<std::path::PathBuf> {
+    std::env::var_os(DATA_DIR_ENV)
+        .filter(|v| !v.is_empty())
+        .map(std::path::PathBuf::from)
+}
+
+/// Programmatic twin of `PHOTON_DATA_DIR`, for portable installs and test harnesses. It sets the variable itself rather than a private static, so the vault layer (which reads the same variable) and every path here resolve one root. Call at startup before spawning threads — the environment is process-global.
+pub fn set_data_dir(dir: impl AsRef<std::path::Path>) {
+    std::env::set_var(DATA_DIR_ENV, dir.as_ref());
+}
+
+/// Returns ~/.config/photon/ (or Android equivalent), or the `PHOTON_DATA_DIR` override. All Photon files live here.
 pub fn photon_config_dir() -> Result<std::path::PathBuf, std::io::Error> {
     #[cfg(target_os = "android")]
     {
@@ -40,12 +63,9 @@ pub fn photon_config_dir() -> Result<std::path::PathBuf, std::io::Error> {
     }
     #[cfg(not(target_os = "android"))]
     {
-        // Dev-only override: PHOTON_DATA_DIR points a whole instance (vault + log + lock) at a separate dir, so a second instance can run isolated for two-party testing (pair with PHOTON_FINGERPRINT for a distinct device identity). Compiled out of release so production has no escape hatch from the single-instance lock.
-        #[cfg(feature = "development")]
-        if let Ok(custom) = std::env::var("PHOTON_DATA_DIR") {
-            if !custom.is_empty() {
-                return Ok(std::path::PathBuf::from(custom));
-            }
+        // PHOTON_DATA_DIR points a whole instance (vault + settings + log + lock) at one separate root: portable installs carry their data beside the binary, and a second instance can run isolated for two-party testing (pair with PHOTON_FINGERPRINT for a distinct device identity). The single-instance lock is keyed to this dir, so an override is a separate instance by design, not an escape from the lock.
+        if let Some(custom) = data_dir_override() {
+            return Ok(custom);
         }
         dirs::config_dir().map(|p| p.join("photon")).ok_or_else(|| {
             std::io::Error::new(std::io::ErrorKind::NotFound, "config dir not found")
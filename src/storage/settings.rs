@@ -1,7 +1,7 @@
 //! User-adjustable app settings, persisted as a plain (unencrypted) VSF file at `photon_config_dir()/settings.vsf`. Settings are non-secret operational knobs (not identity or conversation data), so they live in the config dir, NOT the encrypted vault.
 //!
-//! Today the only knobs are the diagnostic-log hex elision lengths (`hex_head` / `hex_tail`): how many head/tail bytes of a large binary VSF field the inspector prints before eliding the middle.
-//! The defaults keep whole-session logs readable instead of dumping kilobytes of hex per packet.
+//! The knobs are the diagnostic-log hex elision lengths (`hex_head` / `hex_tail`): how many head/tail bytes of a large binary VSF field the inspector prints before eliding the middle — plus the last window geometry (`window_w` / `window_h` / `window_maximized`), so the next launch reopens the window the size the user left it, the global focus hotkey chord (`hotkey`, e.g. `Ctrl+Shift+P`; empty = off), the launch-screen attestation retry policy (`attest_retries` / `attest_backoff_s`, see `ui::attest_retry`), the sleep/resume detection threshold (`resume_gap_s`, see `ui::sweeps::ResumeDetector`), the per-contact relay budget (`relay_cap_mib`, see `network::fgtw::relay::RelayBudget`), the active-tier presence ping interval (`ping_base_s`, see `ui::sweeps::PingBackoff`), the photon socket's receive buffer (`udp_rcvbuf_kib`, see `network::udp::apply_recv_buffer`), the peer-update WebSocket keepalive (`ws_keepalive_s`, see `network::peer_updates::Keepalive`), the largest inbound PT transfer (`max_transfer_mib`, see `network::pt::PTManager::set_max_transfer_size`), the size past which an inbound transfer waits for a tap (`auto_download_mib`, see `network::pt::PTManager::set_auto_accept_limit`), and the default message-history retention (`retain_last` / `retain_days`, see `types::Retention`; a conversation can override it) — plus `cloud_sync_owed`, a contact upload the last exit couldn't land within its timeout, left for the next launch, and `compact_owed`, a vault compaction the next launch runs before opening the vault (see `storage::compact::owe`).
+//! The hex defaults keep whole-session logs readable instead of dumping kilobytes of hex per packet.
 //!
 //! Resolution order (highest priority first):
 //!   1. `VSF_HEX_HEAD` / `VSF_HEX_TAIL` environment variables (quick per-run override; read by vsf)
@@ -18,12 +18,90 @@ use vsf::VsfType;
 const HEX_HEAD_DEFAULT: usize = 32;
 const HEX_TAIL_DEFAULT: usize = 32;
 
-#[derive(Clone, Copy, Debug)]
+/// Default global focus chord written into a fresh `settings.vsf` — P for Photon; Ctrl+Shift keeps it clear of every common single-modifier app shortcut.
+const HOTKEY_DEFAULT: &str = "Ctrl+Shift+P";
+
+/// Transient attestation failures retried before the launch screen shows the error, and the first retry's wait in seconds (doubling after). Matches `RetryPolicy::default()`.
+const ATTEST_RETRIES_DEFAULT: u8 = 4;
+const ATTEST_BACKOFF_S_DEFAULT: u8 = 1 << 1;
+
+/// Seconds the process must have been frozen (suspend, hibernate) before a tick counts as a resume and forces a reconnect. Half a minute outlasts any scheduler hiccup yet is already long enough for NAT mappings and the relay pipe to have died.
+const RESUME_GAP_S_DEFAULT: u8 = 1 << 5;
+
+/// MiB one sender may push down our relay pipe per minute. A CLUTCH offer is ~0.5 MiB and chat is bytes, so 16 leaves a ceremony plus a busy conversation far under the cap while a flooder is cut off within a minute.
+const RELAY_CAP_MIB_DEFAULT: u8 = 1 << 4;
+
+/// Seconds between presence sweeps while the user is active. 5 keeps rings live within a glance; the idle tiers and the offline backoff stretch from there.
+const PING_BASE_S_DEFAULT: u8 = 5;
+
+/// KiB of kernel receive buffer asked for on the photon UDP socket. OS defaults (~200 KiB on Linux) fill in a few milliseconds of a fast PT window and the overflow is dropped silently — it shows up only as retransmits. 2 MiB holds a full window at LAN speed; the kernel may grant less (Linux caps at `net.core.rmem_max`), and the granted size is logged.
+const UDP_RCVBUF_KIB_DEFAULT: u16 = 1 << 11;
+
+/// Seconds of silence on the FGTW peer-update WebSocket before we ping it. Carrier and home NATs forget an idle TCP mapping in as little as a minute, and a forgotten one never errors — updates just stop — so ~half that keeps the mapping warm with a frame each way.
+const WS_KEEPALIVE_S_DEFAULT: u8 = 1 << 5;
+
+/// MiB of the largest inbound PT transfer we take. The receive buffer a SPEC sizes is allocated up front, so this bounds what one SPEC can make us allocate; 16 is twice the largest attachment, headroom for the message envelope. Matches `PTManager::DEFAULT_MAX_TRANSFER_SIZE`.
+const MAX_TRANSFER_MIB_DEFAULT: u8 = 1 << 4;
+
+/// MiB above which an inbound transfer waits for a tap instead of downloading. 0 = download everything, the behaviour before the policy existed.
+const AUTO_DOWNLOAD_MIB_DEFAULT: u8 = 0;
+
+/// Minutes without keyboard or pointer input before our presence reads "away". 5 outlasts reading a long message; 0 = never away.
+const AWAY_AFTER_MIN_DEFAULT: u8 = 5;
+
+/// Default history retention: keep everything. Pruning someone's history is the user's call, never a silent default.
+const RETAIN_LAST_DEFAULT: u32 = 0;
+const RETAIN_DAYS_DEFAULT: u16 = 0;
+
+/// Smallest restored window edge in pixels. A saved size below this (hand-edited file, a monitor that shrank) would reopen as an unusable sliver, so restore widens it back to at least this much — 1 << 7 still fits the avatar + a row of text at ru 1.
+const WINDOW_MIN_EDGE: u32 = 1 << 7;
+
+#[derive(Clone, Debug)]
 pub struct Settings {
     /// Bytes shown at the head of a large binary field in logs before elision.
     pub hex_head: usize,
     /// Bytes shown at the tail of a large binary field in logs before elision.
     pub hex_tail: usize,
+    /// Last NORMAL (un-maximized) window width in pixels; 0 = never saved, use the launch default.
+    pub window_w: u32,
+    /// Last NORMAL (un-maximized) window height in pixels; 0 = never saved, use the launch default.
+    pub window_h: u32,
+    /// Whether the window was maximized when it last closed. The normal size above is kept separately so un-maximizing after a restore returns to the user's own size, not the screen size.
+    pub window_maximized: bool,
+    /// System-wide chord that raises + focuses the window (`platform::hotkey` syntax). Empty = no global hotkey.
+    pub hotkey: String,
+    /// Retries of a transient attestation failure (0 = surface it at once).
+    pub attest_retries: u8,
+    /// Seconds before the first attestation retry; doubles each retry.
+    pub attest_backoff_s: u8,
+    /// Unexplained gap between ticks, in seconds, that counts as a resume from sleep (0 = detection off).
+    pub resume_gap_s: u8,
+    /// Relay bytes accepted per contact device (and from all strangers together) per minute, in MiB (0 = unlimited).
+    pub relay_cap_mib: u8,
+    /// Active-tier presence sweep interval in seconds (0 is read as 1).
+    pub ping_base_s: u8,
+    /// Receive buffer (SO_RCVBUF) requested for the photon UDP socket, in KiB (0 = leave the OS default).
+    pub udp_rcvbuf_kib: u16,
+    /// Idle seconds before the peer-update WebSocket is pinged (0 = no keepalive).
+    pub ws_keepalive_s: u8,
+    /// Largest inbound PT transfer, in MiB (0 is read as the default).
+    pub max_transfer_mib: u8,
+    /// Inbound transfers over this many MiB are held for the user to accept (0 = download everything).
+    pub auto_download_mib: u8,
+    /// Input idle minutes before our presence reads "away" (0 = never).
+    pub away_after_min: u8,
+    /// Default retention: newest messages kept per conversation (0 = no count bound).
+    pub retain_last: u32,
+    /// Default retention: days of history kept per conversation (0 = no age bound).
+    pub retain_days: u16,
+    /// Red squiggles under unknown words in long compose drafts. Display only — sending never looks at it. Off by default: the bundled word list is small enough that ordinary words get squiggled.
+    pub spell_check: bool,
+    /// Do-not-disturb: incoming messages are stored and counted unread, but neither notify nor chime.
+    pub dnd: bool,
+    /// The last exit's contact-list cloud upload failed or outran its timeout. The next launch uploads once its session is back.
+    pub cloud_sync_owed: bool,
+    /// A vault compaction is owed: something deleted data whose old blocks are still on disk. Run by the next launch's first vault open, before any engine is live.
+    pub compact_owed: bool,
 }
 
 impl Default for Settings {
@@ -31,6 +109,26 @@ impl Default for Settings {
         Self {
             hex_head: HEX_HEAD_DEFAULT,
             hex_tail: HEX_TAIL_DEFAULT,
+            window_w: 0,
+            window_h: 0,
+            window_maximized: false,
+            hotkey: HOTKEY_DEFAULT.to_string(),
+            attest_retries: ATTEST_RETRIES_DEFAULT,
+            attest_backoff_s: ATTEST_BACKOFF_S_DEFAULT,
+            resume_gap_s: RESUME_GAP_S_DEFAULT,
+            relay_cap_mib: RELAY_CAP_MIB_DEFAULT,
+            ping_base_s: PING_BASE_S_DEFAULT,
+            udp_rcvbuf_kib: UDP_RCVBUF_KIB_DEFAULT,
+            ws_keepalive_s: WS_KEEPALIVE_S_DEFAULT,
+            max_transfer_mib: MAX_TRANSFER_MIB_DEFAULT,
+            auto_download_mib: AUTO_DOWNLOAD_MIB_DEFAULT,
+            away_after_min: AWAY_AFTER_MIN_DEFAULT,
+            retain_last: RETAIN_LAST_DEFAULT,
+            retain_days: RETAIN_DAYS_DEFAULT,
+            spell_check: false,
+            dnd: false,
+            cloud_sync_owed: false,
+            compact_owed: false,
         }
     }
 }
@@ -39,6 +137,36 @@ fn settings_schema() -> SectionSchema {
     SectionSchema::new("settings")
         .field("hex_head", TypeConstraint::AnyUnsigned)
         .field("hex_tail", TypeConstraint::AnyUnsigned)
+        .field("window_w", TypeConstraint::AnyUnsigned)
+        .field("window_h", TypeConstraint::AnyUnsigned)
+        .field("window_maximized", TypeConstraint::AnyUnsigned)
+        .field("hotkey", TypeConstraint::Utf8Text)
+        .field("attest_retries", TypeConstraint::AnyUnsigned)
+        .field("attest_backoff_s", TypeConstraint::AnyUnsigned)
+        .field("resume_gap_s", TypeConstraint::AnyUnsigned)
+        .field("relay_cap_mib", TypeConstraint::AnyUnsigned)
+        .field("ping_base_s", TypeConstraint::AnyUnsigned)
+        .field("udp_rcvbuf_kib", TypeConstraint::AnyUnsigned)
+        .field("ws_keepalive_s", TypeConstraint::AnyUnsigned)
+        .field("max_transfer_mib", TypeConstraint::AnyUnsigned)
+        .field("auto_download_mib", TypeConstraint::AnyUnsigned)
+        .field("away_after_min", TypeConstraint::AnyUnsigned)
+        .field("retain_last", TypeConstraint::AnyUnsigned)
+        .field("retain_days", TypeConstraint::AnyUnsigned)
+        .field("spell_check", TypeConstraint::AnyUnsigned)
+        .field("dnd", TypeConstraint::AnyUnsigned)
+        .field("cloud_sync_owed", TypeConstraint::AnyUnsigned)
+        .field("compact_owed", TypeConstraint::AnyUnsigned)
+}
+
+/// Fit a saved window size onto the monitor it's reopening on. The monitor may be smaller than the one the size was saved on (laptop undocked from a 4K panel), so each edge is capped at the monitor's — a saved rect hanging off the work area would otherwise open partly off-screen with its title bar unreachable. Edges below `WINDOW_MIN_EDGE` are widened back up (never past the monitor). `None` when nothing was saved (either edge 0), so the caller falls back to its launch default.
+pub fn clamp_window_size(saved: (u32, u32), monitor: (u32, u32)) -> Option<(u32, u32)> {
+    if saved.0 == 0 || saved.1 == 0 {
+        return None;
+    }
+    // Both caps are genuine bounds, not papering over a bug: the saved value comes off disk and the monitor is whatever's plugged in today.
+    let fit = |edge: u32, limit: u32| edge.max(WINDOW_MIN_EDGE).min(limit);
+    Some((fit(saved.0, monitor.0), fit(saved.1, monitor.1)))
 }
 
 fn settings_path() -> Option<std::path::PathBuf> {
@@ -59,6 +187,46 @@ impl Settings {
             .map_err(|e| e.to_string())?
             .append_multi("hex_tail", vec![VsfType::u3(tail)])
             .map_err(|e| e.to_string())?
+            .append_multi("window_w", vec![VsfType::u5(self.window_w)])
+            .map_err(|e| e.to_string())?
+            .append_multi("window_h", vec![VsfType::u5(self.window_h)])
+            .map_err(|e| e.to_string())?
+            .append_multi("window_maximized", vec![VsfType::u3(self.window_maximized as u8)])
+            .map_err(|e| e.to_string())?
+            .append_multi("hotkey", vec![VsfType::x(self.hotkey.clone())])
+            .map_err(|e| e.to_string())?
+            .append_multi("attest_retries", vec![VsfType::u3(self.attest_retries)])
+            .map_err(|e| e.to_string())?
+            .append_multi("attest_backoff_s", vec![VsfType::u3(self.attest_backoff_s)])
+            .map_err(|e| e.to_string())?
+            .append_multi("resume_gap_s", vec![VsfType::u3(self.resume_gap_s)])
+            .map_err(|e| e.to_string())?
+            .append_multi("relay_cap_mib", vec![VsfType::u3(self.relay_cap_mib)])
+            .map_err(|e| e.to_string())?
+            .append_multi("ping_base_s", vec![VsfType::u3(self.ping_base_s)])
+            .map_err(|e| e.to_string())?
+            .append_multi("udp_rcvbuf_kib", vec![VsfType::u4(self.udp_rcvbuf_kib)])
+            .map_err(|e| e.to_string())?
+            .append_multi("ws_keepalive_s", vec![VsfType::u3(self.ws_keepalive_s)])
+            .map_err(|e| e.to_string())?
+            .append_multi("max_transfer_mib", vec![VsfType::u3(self.max_transfer_mib)])
+            .map_err(|e| e.to_string())?
+            .append_multi("auto_download_mib", vec![VsfType::u3(self.auto_download_mib)])
+            .map_err(|e| e.to_string())?
+            .append_multi("away_after_min", vec![VsfType::u3(self.away_after_min)])
+            .map_err(|e| e.to_string())?
+            .append_multi("retain_last", vec![VsfType::u5(self.retain_last)])
+            .map_err(|e| e.to_string())?
+            .append_multi("retain_days", vec![VsfType::u4(self.retain_days)])
+            .map_err(|e| e.to_string())?
+            .append_multi("spell_check", vec![VsfType::u3(self.spell_check as u8)])
+            .map_err(|e| e.to_string())?
+            .append_multi("dnd", vec![VsfType::u3(self.dnd as u8)])
+            .map_err(|e| e.to_string())?
+            .append_multi("cloud_sync_owed", vec![VsfType::u3(self.cloud_sync_owed as u8)])
+            .map_err(|e| e.to_string())?
+            .append_multi("compact_owed", vec![VsfType::u3(self.compact_owed as u8)])
+            .map_err(|e| e.to_string())?
             .encode()
             .map_err(|e| e.to_string())
     }
@@ -81,6 +249,68 @@ impl Settings {
             if let Some(v) = read("hex_tail") {
                 s.hex_tail = v;
             }
+            if let Some(v) = read("window_w") {
+                s.window_w = v as u32;
+            }
+            if let Some(v) = read("window_h") {
+                s.window_h = v as u32;
+            }
+            if let Some(v) = read("window_maximized") {
+                s.window_maximized = v != 0;
+            }
+            // Both are one-byte knobs; an out-of-range hand edit saturates rather than wrapping to a tiny value.
+            if let Some(v) = read("attest_retries") {
+                s.attest_retries = v.min(255) as u8;
+            }
+            if let Some(v) = read("attest_backoff_s") {
+                s.attest_backoff_s = v.min(255) as u8;
+            }
+            if let Some(v) = read("resume_gap_s") {
+                s.resume_gap_s = v.min(255) as u8;
+            }
+            if let Some(v) = read("relay_cap_mib") {
+                s.relay_cap_mib = v.min(255) as u8;
+            }
+            if let Some(v) = read("ping_base_s") {
+                s.ping_base_s = v.min(255) as u8;
+            }
+            if let Some(v) = read("udp_rcvbuf_kib") {
+                s.udp_rcvbuf_kib = v.min(u16::MAX as usize) as u16;
+            }
+            if let Some(v) = read("ws_keepalive_s") {
+                s.ws_keepalive_s = v.min(255) as u8;
+            }
+            if let Some(v) = read("max_transfer_mib") {
+                s.max_transfer_mib = v.min(255) as u8;
+            }
+            if let Some(v) = read("auto_download_mib") {
+                s.auto_download_mib = v.min(255) as u8;
+            }
+            if let Some(v) = read("away_after_min") {
+                s.away_after_min = v.min(255) as u8;
+            }
+            // Saturate too: a wrapped hand edit would turn "keep a lot" into "keep almost nothing".
+            if let Some(v) = read("retain_last") {
+                s.retain_last = v.min(u32::MAX as usize) as u32;
+            }
+            if let Some(v) = read("retain_days") {
+                s.retain_days = v.min(u16::MAX as usize) as u16;
+            }
+            if let Some(v) = read("spell_check") {
+                s.spell_check = v != 0;
+            }
+            if let Some(v) = read("dnd") {
+                s.dnd = v != 0;
+            }
+            if let Some(v) = read("cloud_sync_owed") {
+                s.cloud_sync_owed = v != 0;
+            }
+            if let Some(v) = read("compact_owed") {
+                s.compact_owed = v != 0;
+            }
+            if let Some(VsfType::x(chord)) = builder.get_fields("hotkey").first().and_then(|f| f.values.first()) {
+                s.hotkey = chord.clone();
+            }
         }
         s
     }
@@ -105,6 +335,32 @@ impl Settings {
         }
     }
 
+    /// Write the current settings to `settings.vsf` (atomic write + verify via `write_file`). Failures are logged by `write_file` and otherwise ignored — losing a window size is never worth interrupting the user.
+    pub fn save(&self) {
+        let Some(path) = settings_path() else {
+            return;
+        };
+        if let Ok(bytes) = self.encode() {
+            let _ = crate::storage::write_file(&path, &bytes, "settings");
+        }
+    }
+
+    /// The global default retention policy (a conversation's own `Contact::retention` overrides it).
+    pub fn retention(&self) -> crate::types::Retention {
+        crate::types::Retention { keep_last: self.retain_last, keep_days: self.retain_days }
+    }
+
+    /// The largest inbound PT transfer in bytes, for `PTManager::set_max_transfer_size`. 0 MiB would refuse every transfer, attachments and CLUTCH offers alike, so it reads as the default.
+    pub fn max_transfer_bytes(&self) -> u32 {
+        let mib = if self.max_transfer_mib == 0 { MAX_TRANSFER_MIB_DEFAULT } else { self.max_transfer_mib };
+        u32::from(mib) << 20
+    }
+
+    /// The auto-download limit in bytes, for `PTManager::set_auto_accept_limit`; `None` (0 MiB) downloads everything.
+    pub fn auto_download_limit(&self) -> Option<u32> {
+        (self.auto_download_mib > 0).then(|| u32::from(self.auto_download_mib) << 20)
+    }
+
     /// No-op: vsf removed the runtime `set_hex_elision` API; hex elision is now a compile-time constant in vsf's inspect module. Settings are still persisted to disk for when/if vsf adds the runtime API back.
     pub fn apply(&self) {}
 }
@@ -115,11 +371,64 @@ mod tests {
 
     #[test]
     fn settings_roundtrip() {
-        let s = Settings { hex_head: 48, hex_tail: 8 };
+        let s = Settings {
+            hex_head: 48,
+            hex_tail: 8,
+            window_w: 540,
+            window_h: 1080,
+            window_maximized: true,
+            hotkey: "Alt+F9".to_string(),
+            attest_retries: 0,
+            attest_backoff_s: 5,
+            resume_gap_s: 90,
+            relay_cap_mib: 3,
+            ping_base_s: 12,
+            udp_rcvbuf_kib: 8192,
+            ws_keepalive_s: 0,
+            max_transfer_mib: 40,
+            auto_download_mib: 4,
+            away_after_min: 0,
+            retain_last: 5000,
+            retain_days: 90,
+            spell_check: true,
+            dnd: true,
+            cloud_sync_owed: true,
+            compact_owed: true,
+        };
         let bytes = s.encode().expect("encode");
         let back = Settings::decode(&bytes);
         assert_eq!(back.hex_head, 48);
         assert_eq!(back.hex_tail, 8);
+        assert_eq!((back.window_w, back.window_h), (540, 1080));
+        assert!(back.window_maximized);
+        assert_eq!(back.hotkey, "Alt+F9");
+        assert_eq!((back.attest_retries, back.attest_backoff_s), (0, 5));
+        assert_eq!(back.resume_gap_s, 90);
+        assert_eq!(back.relay_cap_mib, 3);
+        assert_eq!(back.ping_base_s, 12);
+        assert_eq!(back.udp_rcvbuf_kib, 8192);
+        assert_eq!(back.ws_keepalive_s, 0);
+        assert_eq!((back.max_transfer_mib, back.max_transfer_bytes()), (40, 40 << 20));
+        assert_eq!(back.auto_download_limit(), Some(4 << 20));
+        assert_eq!(Settings::default().auto_download_limit(), None);
+        assert_eq!(back.away_after_min, 0);
+        assert_eq!((back.retain_last, back.retain_days), (5000, 90));
+        assert!(back.spell_check);
+        assert!(!Settings::default().spell_check, "off until a real dictionary ships");
+        assert!(back.dnd);
+        assert!(back.cloud_sync_owed);
+        assert!(back.compact_owed);
+    }
+
+    #[test]
+    fn clamp_window_size_fits_saved_rect_onto_smaller_monitor() {
+        // Saved on a 4K panel, reopened on 1920×1080: both edges hang off the work area and get pulled back inside it.
+        assert_eq!(clamp_window_size((2400, 2000), (1920, 1080)), Some((1920, 1080)));
+        // A sliver is widened to the minimum edge; a size that already fits is untouched.
+        assert_eq!(clamp_window_size((3, 700), (1920, 1080)), Some((WINDOW_MIN_EDGE, 700)));
+        assert_eq!(clamp_window_size((270, 540), (1920, 1080)), Some((270, 540)));
+        // Never saved → caller uses its launch default.
+        assert_eq!(clamp_window_size((0, 540), (1920, 1080)), None);
     }
 
     #[test]
@@ -127,5 +436,6 @@ mod tests {
         let d = Settings::decode(b"not a vsf doc");
         assert_eq!(d.hex_head, HEX_HEAD_DEFAULT);
         assert_eq!(d.hex_tail, HEX_TAIL_DEFAULT);
+        assert_eq!(d.max_transfer_bytes(), crate::network::pt::PTManager::DEFAULT_MAX_TRANSFER_SIZE);
     }
 }
@@ -0,0 +1,59 @@
+//! Entries whose in-memory state is ahead of the vault. Every save is immediate, so this is normally empty: a failed `save_contact_list` / `save_contact_state` / `save_messages` / `save_friendship_chains` adds its entry, the next successful save of the same entry clears it, and a mutation that holds its save back calls [`mark`]. The app's exit flush writes exactly what [`owed`] lists instead of rewriting the whole vault.
+
+use std::sync::Mutex;
+
+/// One vault entry the exit flush may owe a write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unflushed {
+    /// The contact-list index.
+    ContactList,
+    /// A contact's state entry, by party id (`handle_hash`).
+    ContactState([u8; 32]),
+    /// A contact's conversation rows, by party id.
+    Messages([u8; 32]),
+    /// A friendship's chain state, by friendship id.
+    Chains([u8; 32]),
+}
+
+static UNFLUSHED: Mutex<Vec<Unflushed>> = Mutex::new(Vec::new());
+
+/// Record a save's outcome: a failure leaves `entry` owed, a success settles it.
+pub fn record<T, E>(entry: Unflushed, result: &Result<T, E>) {
+    let mut owed = UNFLUSHED.lock().unwrap();
+    owed.retain(|e| *e != entry);
+    if result.is_err() {
+        owed.push(entry);
+    }
+}
+
+/// `entry` changed in memory without being saved.
+pub fn mark(entry: Unflushed) {
+    let mut owed = UNFLUSHED.lock().unwrap();
+    if !owed.contains(&entry) {
+        owed.push(entry);
+    }
+}
+
+/// The owed entries, oldest first. Saving one settles it, or keeps it owed if the write fails again.
+pub fn owed() -> Vec<Unflushed> {
+    UNFLUSHED.lock().unwrap().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_saves_stay_owed_until_one_succeeds() {
+        let (a, b) = (Unflushed::Messages([0xE1; 32]), Unflushed::Chains([0xE2; 32]));
+        record(a, &Err::<(), ()>(()));
+        record(a, &Err::<(), ()>(()));
+        mark(b);
+        mark(b);
+        assert_eq!(owed().iter().filter(|e| **e == a || **e == b).count(), 2);
+
+        record(a, &Ok::<(), ()>(()));
+        record(b, &Ok::<(), ()>(()));
+        assert!(!owed().contains(&a) && !owed().contains(&b));
+    }
+}
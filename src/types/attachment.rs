@@ -0,0 +1,196 @@
+//! File attachments — arbitrary bytes riding a chat message. The metadata (name, size, BLAKE3) travels as siblings of the caption in the `message` field (`d` name, `u5` size, `hb` hash) and the bytes as `v'f'`; PT shards the whole payload like any large message. The caption ([`Attachment::caption`]) is what salts the chain, so the file bytes never enter the chain math.
+
+use vsf::VsfType;
+
+/// Largest file a message carries, sent or accepted. Every byte lives in the sender's pending-retransmit queue and both vaults' history rows, so this is a chat attachment, not a file-transfer service.
+pub const MAX_ATTACHMENT_BYTES: usize = 1 << 23;
+
+/// Longest filename kept, in bytes (longer names are cut at a char boundary).
+pub const MAX_NAME_LEN: usize = 1 << 8;
+
+/// A named file on a message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Attachment {
+    pub name: String,
+    pub bytes: Vec<u8>,
+}
+
+impl Attachment {
+    /// Wrap `bytes` under `name`. The name is reduced to a bare filename — no directories, no separators, no control chars — so a peer can't aim the receiver's save at another path. `Err` is a user-facing hint.
+    pub fn new(name: &str, bytes: Vec<u8>) -> Result<Self, String> {
+        if bytes.len() > MAX_ATTACHMENT_BYTES {
+            return Err(format!("That file is too big to send — attachments up to {} MB.", MAX_ATTACHMENT_BYTES >> 20));
+        }
+        Ok(Self { name: clean_name(name), bytes })
+    }
+
+    /// BLAKE3 of the bytes — what the receiver checks the reassembled file against.
+    pub fn hash(&self) -> [u8; 32] {
+        *blake3::hash(&self.bytes).as_bytes()
+    }
+
+    /// The message caption: what an older client shows in the row and what salts the chain.
+    pub fn caption(&self) -> String {
+        format!("[file] {}", self.name)
+    }
+
+    /// The chip's size line ("12.3 KB").
+    pub fn size_label(&self) -> String {
+        let n = self.bytes.len();
+        if n < 1 << 10 {
+            format!("{} B", n)
+        } else if n < 1 << 20 {
+            format!("{:.1} KB", n as f64 / (1 << 10) as f64)
+        } else {
+            format!("{:.1} MB", n as f64 / (1 << 20) as f64)
+        }
+    }
+
+    /// Write the file into `dir` under its name without overwriting anything already there — a clash becomes `name (1).ext`, `name (2).ext`, … Returns the path written.
+    pub fn save_into(&self, dir: &std::path::Path) -> std::io::Result<std::path::PathBuf> {
+        use std::io::Write;
+        let name = std::path::Path::new(&self.name);
+        let stem = name.file_stem().map_or(self.name.clone(), |s| s.to_string_lossy().into_owned());
+        let ext = name.extension().map_or(String::new(), |e| format!(".{}", e.to_string_lossy()));
+        // Bounded: past this many same-named files something else is wrong, and the loop must end.
+        for n in 0..1 << 10 {
+            let candidate = if n == 0 { self.name.clone() } else { format!("{} ({}){}", stem, n, ext) };
+            let path = dir.join(candidate);
+            match std::fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut f) => {
+                    f.write_all(&self.bytes)?;
+                    return Ok(path);
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Err(std::io::Error::new(std::io::ErrorKind::AlreadyExists, "too many files with that name"))
+    }
+
+    /// The values this attachment adds to a chat `message` field.
+    pub fn wire_values(&self) -> Vec<VsfType> {
+        vec![
+            VsfType::d(self.name.clone()),
+            VsfType::u5(self.bytes.len() as u32),
+            VsfType::hb(self.hash().to_vec()),
+            VsfType::v(b'f', self.bytes.clone()),
+        ]
+    }
+
+    /// Reassemble an attachment from a received `message` field's values (any order). `None` unless all four parts are present, within bounds, and the bytes match both the declared size and hash — a truncated or altered file is never offered for saving.
+    pub fn from_wire(values: &[VsfType]) -> Option<Self> {
+        let (mut name, mut size, mut hash, mut bytes) = (None, None, None, None);
+        for value in values {
+            match value {
+                VsfType::d(n) => name = Some(n.as_str()),
+                VsfType::u5(s) => size = Some(*s as usize),
+                VsfType::hb(h) => hash = <[u8; 32]>::try_from(h.as_slice()).ok(),
+                VsfType::v(b'f', b) => bytes = Some(b),
+                _ => {}
+            }
+        }
+        let bytes = bytes?;
+        if bytes.len() > MAX_ATTACHMENT_BYTES || size? != bytes.len() || hash? != *blake3::hash(bytes).as_bytes() {
+            return None;
+        }
+        Some(Self { name: clean_name(name?), bytes: bytes.clone() })
+    }
+}
+
+/// Characters Windows refuses in a filename. `:` matters most: `notes.txt:x` writes an NTFS alternate stream and `C:x` is drive-relative.
+const RESERVED_CHARS: [char; 7] = [':', '<', '>', '"', '|', '?', '*'];
+
+/// The last path component of `name` with control chars and [`RESERVED_CHARS`] dropped, bounded to [`MAX_NAME_LEN`]; "file" if nothing's left. A Windows device name (`CON`, `NUL`, `COM1`…, any extension, any case) gets a `_` prefix, so saving it can't open the device.
+fn clean_name(name: &str) -> String {
+    let base = name.rsplit(['/', '\\']).next().unwrap_or_default();
+    let kept: String = base.chars().filter(|c| !c.is_control() && !RESERVED_CHARS.contains(c)).collect();
+    // Windows drops trailing dots and spaces, so "CON. " would still be the device.
+    let trim = |s: &str| s.trim_start().trim_end_matches(|c: char| c == '.' || c.is_whitespace()).to_string();
+    let mut out = trim(&kept);
+    if is_device_name(&out) {
+        out.insert(0, '_');
+    }
+    if out.len() > MAX_NAME_LEN {
+        let mut cut = MAX_NAME_LEN;
+        while !out.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        out = trim(&out[..cut]);
+    }
+    if out.is_empty() {
+        "file".to_string()
+    } else {
+        out
+    }
+}
+
+/// Whether Windows reads `name` as a device: its stem (before the first `.`) is `CON`, `PRN`, `AUX`, `NUL`, `COM1`-`COM9` or `LPT1`-`LPT9`.
+fn is_device_name(name: &str) -> bool {
+    let stem = name.split('.').next().unwrap_or_default().trim_end().to_ascii_uppercase();
+    match stem.as_str() {
+        "CON" | "PRN" | "AUX" | "NUL" => true,
+        _ => (stem.starts_with("COM") || stem.starts_with("LPT")) && matches!(stem.as_bytes().get(3..), Some([b'1'..=b'9'])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blob_round_trips_thru_the_message_field() {
+        let blob: Vec<u8> = (0..3000u32).map(|i| (i * 7 % 251) as u8).chain([0, 0xFF, 0]).collect();
+        let sent = Attachment::new("report.bin", blob.clone()).unwrap();
+
+        // The sender's message field — caption plus the attachment parts — flattened exactly as the chain send does, then parsed back as the receiver does.
+        let mut values = vec![VsfType::x(sent.caption())];
+        values.extend(sent.wire_values());
+        let payload = vsf::schema::section::FieldValue::new("message", values).flatten();
+        let mut ptr = 0;
+        let field = vsf::file_format::VsfField::parse(&payload, &mut ptr).unwrap();
+        let got = Attachment::from_wire(&field.values).expect("attachment");
+        assert_eq!(got.name, "report.bin");
+        assert_eq!(got.bytes, blob);
+
+        // A flipped byte fails the hash; a missing part is no attachment.
+        let mut tampered = sent.wire_values();
+        if let VsfType::v(_, b) = &mut tampered[3] {
+            b[0] ^= 1;
+        }
+        assert_eq!(Attachment::from_wire(&tampered), None);
+        assert_eq!(Attachment::from_wire(&sent.wire_values()[..3]), None);
+    }
+
+    #[test]
+    fn names_are_bare_filenames_and_saves_never_clobber() {
+        let a = |n: &str| Attachment::new(n, vec![1]).unwrap().name;
+        assert_eq!(a("../../.bashrc"), ".bashrc");
+        assert_eq!(a("C:\\Users\\me\\notes.txt"), "notes.txt");
+        assert_eq!(a("bad\u{0}\nname.pdf"), "badname.pdf");
+        assert_eq!(a(".."), "file");
+        assert_eq!(a("notes.txt:hidden"), "notes.txthidden");
+        assert_eq!(a("C:evil.exe"), "Cevil.exe");
+        assert_eq!(a("what?<>|*\"now\".txt"), "whatnow.txt");
+        assert_eq!(a("CON"), "_CON");
+        assert_eq!(a("nul.txt"), "_nul.txt");
+        assert_eq!(a("Com3.tar.gz"), "_Com3.tar.gz");
+        assert_eq!(a("lpt9. "), "_lpt9");
+        assert_eq!(a("COM10.txt"), "COM10.txt");
+        assert_eq!(a("console.log"), "console.log");
+        assert_eq!(a("report. . "), "report");
+        assert_eq!(a(&"é".repeat(MAX_NAME_LEN)).len(), MAX_NAME_LEN);
+        assert!(Attachment::new("big", vec![0; MAX_ATTACHMENT_BYTES + 1]).is_err());
+
+        // Saving never clobbers: the second copy gets a numbered name.
+        let dir = std::env::temp_dir().join(format!("photon-attach-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = Attachment::new("notes.txt", b"hi".to_vec()).unwrap();
+        let first = file.save_into(&dir).unwrap();
+        let second = file.save_into(&dir).unwrap();
+        assert_eq!(first.file_name().unwrap(), "notes.txt");
+        assert_eq!(second.file_name().unwrap(), "notes (1).txt");
+        assert_eq!(std::fs::read(&second).unwrap(), b"hi");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
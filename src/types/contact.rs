@@ -54,6 +54,50 @@ pub struct ChatMessage {
     pub ack_hash: Option<[u8; 32]>,
     /// `true` when this row was RECOVERED from a friend's copy of the conversation (history recovery after a client reset) rather than witnessed by this device as a signed wire frame. Friend-attested provenance: the friend could in principle have altered it. Persisted so phase-2 fleet recovery (self-attested rows) can supersede friend-attested ones, and so a UI cue can exist later. No UI treatment yet.
     pub recovered: bool,
+    /// Outgoing only: the retransmit sweep gave up without an ACK (`MAX_SEND_ATTEMPTS` spent, every path — direct, alt, relay — tried). Drives the "not delivered — tap to retry" row; cleared by a late ACK or a retry. Runtime-only: the retransmit budget is runtime state too, so a new session starts the message fresh rather than inheriting a verdict it hasn't reached.
+    pub failed: bool,
+    /// Image messages: the JPEG the chain carried (`ui::chat_image`); `content` is then [`IMAGE_PLACEHOLDER`]. Persisted with the row.
+    pub image: Option<Vec<u8>>,
+    /// File messages: the attached file (`types::attachment`); `content` is then its caption. Persisted with the row.
+    pub attachment: Option<crate::types::Attachment>,
+    /// Voice messages: the recorded memo (`types::voice`); `content` is then its caption. Persisted with the row.
+    pub voice: Option<crate::types::VoiceMemo>,
+}
+
+/// Message-history retention: keep the newest `keep_last` messages and/or the ones younger than `keep_days` days. 0 turns that bound off; both 0 = keep forever (the default). A message goes when EITHER bound excludes it, except an un-ACKed outgoing one — it's still owed a delivery, and the retransmit sweep works off this history.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Retention {
+    pub keep_last: u32,
+    pub keep_days: u16,
+}
+
+impl Retention {
+    pub fn is_forever(&self) -> bool {
+        self.keep_last == 0 && self.keep_days == 0
+    }
+
+    /// Timestamps of the messages this policy prunes from a chronological history at `now` (eagle time). Pending (outgoing, not delivered) messages are never in the result, and they don't use up `keep_last` slots either.
+    pub fn prunable(&self, messages: &[ChatMessage], now: i64) -> Vec<i64> {
+        if self.is_forever() {
+            return Vec::new();
+        }
+        let pending = |m: &ChatMessage| m.is_outgoing && !m.delivered;
+        let settled = messages.iter().filter(|m| !pending(m)).count();
+        // keep_last = 0 means no count bound: nothing is over it.
+        let over = if self.keep_last == 0 { 0 } else { settled.saturating_sub(self.keep_last as usize) };
+        let cutoff = if self.keep_days == 0 {
+            i64::MIN
+        } else {
+            now.saturating_sub(self.keep_days as i64 * 86_400 * crate::OSC_PER_SEC)
+        };
+        messages
+            .iter()
+            .filter(|m| !pending(m))
+            .enumerate()
+            .filter(|(i, m)| *i < over || m.timestamp < cutoff)
+            .map(|(_, m)| m.timestamp)
+            .collect()
+    }
 }
 
 impl ChatMessage {
@@ -65,6 +109,10 @@ impl ChatMessage {
             delivered: false,
             ack_hash: None,
             recovered: false,
+            failed: false,
+            image: None,
+            attachment: None,
+            voice: None,
         }
     }
 
@@ -77,6 +125,10 @@ impl ChatMessage {
             delivered: false,
             ack_hash: None,
             recovered: false,
+            failed: false,
+            image: None,
+            attachment: None,
+            voice: None,
         }
     }
 
@@ -85,6 +137,24 @@ impl ChatMessage {
         self.ack_hash = Some(ack_hash);
         self
     }
+
+    /// Attach an image message's JPEG.
+    pub fn with_image(mut self, jpeg: Vec<u8>) -> Self {
+        self.image = Some(jpeg);
+        self
+    }
+
+    /// Attach a file message's file.
+    pub fn with_attachment(mut self, file: crate::types::Attachment) -> Self {
+        self.attachment = Some(file);
+        self
+    }
+
+    /// Attach a voice message's memo.
+    pub fn with_voice(mut self, memo: crate::types::VoiceMemo) -> Self {
+        self.voice = Some(memo);
+        self
+    }
 }
 
 /// Runtime state machine for friend-assisted history recovery on one conversation. Lives on the Contact (never persisted whole — the durable bits are the `hist_oldest` cursor + `hist_complete` flag in contact state). Newest-first cursor pagination: `oldest_recovered_osc` walks DOWN from `i64::MAX` (head page) as pages land.
@@ -133,6 +203,9 @@ impl std::fmt::Display for HandleText {
 /// Reserved sentinel content for the hidden chain-weave probe message. After CLUTCH reaches Complete, each device sends exactly one message with this exact content to validate the ratchet end-to-end. The receive path recognises it, advances/ACKs the chain like any message, but suppresses the chat bubble. The control bytes (SOH/STX around the tag) make a collision with a real user message effectively impossible.
 pub const CHAIN_PROBE_MARKER: &str = "\u{1}\u{2}photon-chain-probe\u{2}\u{1}";
 
+/// Caption of an image message: what the chain salts with and braids on (the image bytes stay out of the chain math), and what a client that predates images shows in the row.
+pub const IMAGE_PLACEHOLDER: &str = "[image]";
+
 /// State of the CLUTCH key ceremony for a contact
 ///
 /// Slot-based design: each party has a slot indexed by sorted handle_hash position. Ceremony completes when all slots have both offer and kem_secrets filled, AND both parties have exchanged matching eggs_proof values.
@@ -156,6 +229,9 @@ pub struct DeviceEndpoint {
     pub online: bool,
 }
 
+/// Resolution of [`Contact::last_seen`]: one minute. The row reads "last seen 2h ago", so finer is invisible, and coarser than the ping cadence is what keeps presence from becoming a vault write per pong.
+pub const LAST_SEEN_GRAIN_OSC: i64 = 60 * crate::OSC_PER_SEC;
+
 #[derive(Clone, Debug)]
 pub struct Contact {
     pub id: ContactId,
@@ -240,7 +316,10 @@ pub struct Contact {
     pub ceremony_owner: Option<[u8; 32]>,
     /// Roster-adopted display truth: the OWNER's ceremony completed ("secured on <device>"). NEVER unlocks our own compose — that stays gated on OUR chain (chain_woven) until chain state travels (braid.md §14).
     pub owner_woven: bool,
+    /// Eagle time they were last heard from (pong, ping or chat from any of their devices) — what an offline row's "last seen 2h ago" reads. Persisted in the state entry, at [`LAST_SEEN_GRAIN_OSC`] resolution so a contact on the 5s ping cadence doesn't rewrite the vault every sweep. `None` = never heard.
     pub last_seen: Option<i64>,
+    /// Runtime: `last_seen` moved since the last state save — the post-drain sweep persists + clears it.
+    pub last_seen_dirty: bool,
     pub is_online: bool, // True when we have confirmed bidirectional comms
     /// True when the ONLY working path to this contact is the FGTW relay (no direct socket — the asymmetric-reachability case). Drives the lime-yellow presence (theme::RING_RELAY_COLOUR) instead of the direct-connection green, so a relayed link is never mistaken for a direct one. Set when a message arrives via relay / a direct path is proven unreachable; cleared the moment a direct path validates. Not persisted (a session-scoped reachability fact).
     pub reached_via_relay: bool,
@@ -253,8 +332,7 @@ pub struct Contact {
     pub text_y: f32,     // Cached text Y position (set during draw)
     // Avatar cache - fetched from FGTW by handle Storage key is deterministic: BLAKE3(BLAKE3(handle) || "avatar")
     pub avatar_pixels: Option<Vec<u8>>, // Full 256x256 VSF RGB pixels (cached)
-    pub avatar_scaled: Option<Vec<u8>>, // Pre-scaled to current display size
-    pub avatar_scaled_diameter: usize,  // Diameter the scaled pixels were rendered for
+    pub avatar_scaled: crate::ui::avatar_render::ScaledAvatarCache, // Pre-scaled per display diameter (row / header / panel)
 
     // Chain weave probe — after CLUTCH reaches Complete, both devices auto-exchange one hidden probe chat message each way to prove the ratchet works end-to-end. Once proven, the ceremony proof rebroadcast is cancelled (clutch_proof_resends_left = 0). Runtime-only, not persisted: a resumed Complete contact already has a working chain and needs no re-probe.
     /// The chain has been validated end-to-end (our probe/message got ACKed AND we saw theirs). Gates the status line from "weaving the chain" to "secured" and stops the ceremony rebroadcast.
@@ -273,6 +351,12 @@ pub struct Contact {
     pub last_heard: Option<std::time::Instant>,
     /// Runtime-only: this contact has received at least one presence VERDICT this session — a pong OR a 3-consecutive-timeout — via the StatusUpdate::Online drain. Fixes the §4.2 takeover boot race: siblings start `is_online = false` before the first sweep, so "owner absent" is meaningless until probed. Never persisted.
     pub presence_probed: bool,
+    /// Runtime-only: one of this contact's devices announced itself on our LAN (a `LanPeerDiscovered` beacon that opened against their handle_proof) this session — local corroboration that a shared public IPv4 really is the same site (see [`crate::network::traverse::gather::same_nat`]). Never persisted.
+    pub lan_beacon_heard: bool,
+    /// Runtime-only link quality: rolling ping→pong RTT (fed from `StatusUpdate::Online::rtt`, any of their devices) and the Good/Fair/Poor bucket the contact row's signal bars draw. Reset on the offline edge so a reconnect over a different path (relay vs direct) isn't judged by the old one. Never persisted.
+    pub rtt: crate::network::quality::RttEstimate,
+    /// Runtime-only: their last pong said they're away (idle past their own threshold). Cleared by a chat from them or the offline edge; an online row shows it as the away badge. Never persisted.
+    pub away: bool,
     /// Runtime-only: when we last rang this contact's doorbell — the client-side debounce above the worker's per-target guard. One wake per re-ring window no matter how much traffic queues behind it.
     pub last_ring: Option<std::time::Instant>,
     /// Runtime-only fork detector: consecutive inbound chat frames from this contact that passed signature + chain-link checks but decrypted to garbage (VSF parse failure) — the signature of a chain FORK (the two sides advanced different key material). Reset on any successful decrypt. At the threshold a SIBLING contact triggers the fleet-key chain_reset repair; a friend contact only logs (friend-side repair waits for the fleet-plane linearizer).
@@ -299,6 +383,18 @@ pub struct Contact {
     pub blind_probe_missed: bool,
     /// Count of real inbound friend messages that landed while this conversation was NOT front-of-eyes (conversation screen not active for this contact, or the window hidden/unfocused). Drives the contacts-list unread treatment: the inner relationship-coloured ring + heavier name + float-to-top — never a count glyph, never a timer. Cleared (and re-persisted) the moment the conversation becomes the active view; persisted in contact state so unread survives a restart. Probes and sibling fleet-sync frames never bump it.
     pub unread_count: u32,
+    /// The user compared safety numbers with this friend out of band ([`crate::crypto::safety`]) and confirmed they match — we pinned their real key, not a lookup MITM's. Set and cleared only by hand from the contact panel's Verify page; persisted in contact state (absent = false). Unverified friends' messages render lighter.
+    pub verified: bool,
+    /// Muted by hand from the contact panel's Manage page: their messages still land and are stored, but neither notify, chime nor bump the unread ring ([`crate::ui::alerts::decide`]). Persisted in contact state (absent = false).
+    pub muted: bool,
+    /// Pinned to the top of the contacts list by hand (Manage page). Persisted in contact state and carried in the cloud contacts blob (absent = false).
+    pub pinned: bool,
+    /// Archived by hand (Manage page): hidden from the default contacts list, listed under the "archived" filter. A new message from them unarchives ([`crate::ui::alerts::Arrival::unarchive`]). Persisted in contact state (absent = false).
+    pub archived: bool,
+    /// This conversation's own retention policy, overriding the global default in `settings.vsf` (`retain_last` / `retain_days`). `None` = follow the default. Persisted in contact state (absent = None).
+    pub retention: Option<Retention>,
+    /// History floor: rows at or before this eagle time were deliberately removed on this device (a clear, or a retention prune), so history recovery — friend pages and sibling pushes alike — must not bring them back. 0 = nothing removed. Persisted in contact state (absent = 0).
+    pub history_floor: i64,
 }
 
 /// Contact identifier - BLAKE3 hash of the contact's public identity key This provides deterministic, collision-resistant identification
@@ -393,6 +489,7 @@ impl Contact {
             ceremony_owner: None,
             owner_woven: false,
             last_seen: None,
+            last_seen_dirty: false,
             is_online: false,           // Starts offline until we confirm comms
             reached_via_relay: false,   // Direct until proven relay-only
             messages: Vec::new(),       // No messages yet
@@ -403,8 +500,7 @@ impl Contact {
             text_x: 0.0,                // Set during first draw
             text_y: 0.0,                // Set during first draw
             avatar_pixels: None,        // Fetched from FGTW by handle when online
-            avatar_scaled: None,        // Scaled on demand for display
-            avatar_scaled_diameter: 0,
+            avatar_scaled: Default::default(), // Scaled on demand for display
             chain_woven: false,           // Chain not yet proven end-to-end (probe pending)
             probe_sent: false,            // Chain-weave probe not sent yet
             their_probe_seen: false,      // Haven't seen their chain-weave probe yet
@@ -414,6 +510,9 @@ impl Contact {
             clutch_offer_stall_cycles: 0, // No stalled-offer cycles yet
             last_heard: None,             // No signed traffic from them yet this session
             presence_probed: false,       // No presence verdict yet this session
+            lan_beacon_heard: false,      // No LAN beacon from them yet this session
+            rtt: Default::default(),      // No round trip timed yet
+            away: false,                  // Present until a pong says otherwise
             last_ring: None,              // Doorbell never rung this session
             chain_fail_streak: 0,
             last_chain_reset_nonce: None,
@@ -426,6 +525,12 @@ impl Contact {
             blind_in_flight: None,        // No blind op in flight
             blind_probe_missed: false,    // No probe answered found=0 yet
             unread_count: 0,              // Nothing unseen yet
+            verified: false,              // Safety number not compared yet
+            muted: false,                 // Alerts on until muted by hand
+            pinned: false,                // Listed in vault order until pinned
+            archived: false,              // On the main list
+            retention: None,              // Follow the global default
+            history_floor: 0,             // Nothing deliberately removed
         }
     }
 
@@ -487,8 +592,30 @@ impl Contact {
         self
     }
 
-    pub fn update_last_seen(&mut self, timestamp: i64) {
-        self.last_seen = Some(timestamp);
+    /// Flag our undelivered outgoing message stamped `eagle_time` as failed (its retransmits ran out). Returns whether a row changed.
+    pub fn mark_failed(&mut self, eagle_time: i64) -> bool {
+        match self
+            .messages
+            .iter_mut()
+            .rev()
+            .find(|m| m.is_outgoing && !m.delivered && m.timestamp == eagle_time)
+        {
+            Some(m) if !m.failed => {
+                m.failed = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Note that they were heard from at `now_osc`. Only moves `last_seen` (and marks it for saving) once the stored stamp is a full grain old; returns whether it moved.
+    pub fn update_last_seen(&mut self, now_osc: i64) -> bool {
+        if self.last_seen.is_some_and(|t| now_osc.saturating_sub(t) < LAST_SEEN_GRAIN_OSC) {
+            return false;
+        }
+        self.last_seen = Some(now_osc);
+        self.last_seen_dirty = true;
+        true
     }
 
     /// Returns the (primary, alternate) address pair for racing a transfer across the reachable paths. A punch-validated direct path (from NAT traversal) wins as primary when present, with the public/LAN kept as the alternate so PT still races if the validated mapping went stale. Otherwise: primary is the LAN address (preferred — no router hairpin, no AP isolation), alternate is the public address; and when no LAN address is known, primary is the public address and alternate is `None`. PT sends the SPEC to both and locks onto whichever ACKs first (see [`crate::network::pt::PtManager::send_with_pubkey_and_alt`]).
@@ -518,9 +645,15 @@ impl Contact {
     }
 
     pub fn race_addrs(&self) -> Option<(SocketAddr, Option<SocketAddr>)> {
+        self.race_addrs_behind(None, None)
+    }
+
+    /// [`race_addrs`](Self::race_addrs) knowing our own public IP (`PhotonApp.our_reflexive`) and LAN IPv4 (`udp::get_local_ip`): a contact showing that same public IPv4, and on our LAN by beacon or subnet, is behind our NAT, so its LAN address leads (see [`crate::network::traverse::gather::gather_peer_candidates_behind`]).
+    pub fn race_addrs_behind(&self, our_public: Option<std::net::IpAddr>, our_lan: Option<std::net::Ipv4Addr>) -> Option<(SocketAddr, Option<SocketAddr>)> {
+        use crate::network::traverse::gather::gather_peer_candidates_behind;
         // A punch-validated direct path wins — it's proven reachable right now. Keep the best DISTINCT candidate as the alternate so a stale NAT mapping still falls back via PT's race.
         if let Some((validated, _at)) = self.validated_path {
-            let alt = crate::network::traverse::gather::gather_peer_candidates(self)
+            let alt = gather_peer_candidates_behind(self, our_public, our_lan)
                 .sorted()
                 .into_iter()
                 .map(|c| c.addr)
@@ -529,7 +662,7 @@ impl Contact {
         }
 
         // No proven path yet: try candidates in priority order — global IPv6 host first (no NAT, no punch), then IPv6 reflexive, then IPv4 LAN, then IPv4 reflexive (the punched WAN path). This is the v6-first send order; it replaces the old LAN-first-then-public choice, so a reachable v6 address is tried before a v4 LAN address that may belong to a foreign network (the common `192.168.0.x` collision between two default home routers). Falls back to nothing only when we know no address at all.
-        crate::network::traverse::gather::gather_peer_candidates(self).best_pair()
+        gather_peer_candidates_behind(self, our_public, our_lan).best_pair()
     }
 
     /// True once the CLUTCH ceremony is Complete — which is cryptographically impossible unless BOTH parties ran it, so it doubles as the mutual-consent signal ("we each added the other"). Used to gate friend-only behaviour like the direct peer-to-peer avatar exchange.
@@ -0,0 +1,172 @@
+//! Contact card — the out-of-band "this is me" payload behind the verification QR.
+//!
+//! Carries exactly the pin-set a contact row is built from (handle_proof, party id, one device pubkey) and nothing else: never the handle string, which derives the identity seed (docs/identity-profile.md). A scanned card either confirms an existing contact's pins (→ verified) or builds a new contact via [`Contact::from_pin`] without an FGTW lookup. Encoded as one small VSF document; the QR carries its base64url text form, since phone scanners return a code's TEXT reliably and its raw binary only thru a charset guess. The same text behind a [`LINK_PREFIX`] is the shareable `photon://add/…` link (platform::link routes a clicked one in).
+
+use super::{Contact, DevicePubkey};
+use vsf::VsfType;
+
+/// VSF section name of an encoded card.
+const CARD_SECTION: &str = "card";
+
+/// Scheme + verb of a shareable contact link: `photon://add/<base64url card>`.
+pub const LINK_PREFIX: &str = "photon://add/";
+
+/// The shareable pin-set for one identity, as seen from the device that shows it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContactCard {
+    pub handle_proof: [u8; 32],
+    /// Identity pubkey — the party id every contact row keys on ([`crate::crypto::clutch::identity_party_id`]).
+    pub party_id: [u8; 32],
+    /// The showing device's own pubkey — the first device the new contact will talk to.
+    pub device_pubkey: DevicePubkey,
+}
+
+impl ContactCard {
+    /// Encode as a compact VSF document: one `card` section of three fixed-width fields.
+    pub fn to_vsf(&self) -> Option<Vec<u8>> {
+        vsf::VsfBuilder::new()
+            .creation_time_oscillations(vsf::eagle_time_oscillations())
+            .provenance_only()
+            .add_section(
+                CARD_SECTION,
+                vec![
+                    ("hp".to_string(), VsfType::hb(self.handle_proof.to_vec())),
+                    ("pid".to_string(), VsfType::ke(self.party_id.to_vec())),
+                    ("dev".to_string(), self.device_pubkey.to_vsf()),
+                ],
+            )
+            .build()
+            .ok()
+    }
+
+    /// Decode a card. `None` for anything that isn't exactly a well-formed card — a scanner hands us arbitrary QR bytes, so every field is length-checked.
+    pub fn from_vsf(bytes: &[u8]) -> Option<Self> {
+        let (_, header_end) = vsf::file_format::VsfHeader::decode(bytes).ok()?;
+        let mut ptr = 0usize;
+        let section = vsf::file_format::VsfSection::parse(bytes.get(header_end..)?, &mut ptr).ok()?;
+        if section.name != CARD_SECTION {
+            return None;
+        }
+        let field32 = |name: &str| -> Option<[u8; 32]> {
+            match section.get_field(name)?.values.first()? {
+                VsfType::hb(b) | VsfType::ke(b) => b.as_slice().try_into().ok(),
+                _ => None,
+            }
+        };
+        Some(Self {
+            handle_proof: field32("hp")?,
+            party_id: field32("pid")?,
+            device_pubkey: DevicePubkey::from_bytes(field32("dev")?),
+        })
+    }
+
+    /// The VSF as unpadded base64url — the QR's content.
+    pub fn to_text(&self) -> Option<String> {
+        use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+        Some(URL_SAFE_NO_PAD.encode(self.to_vsf()?))
+    }
+
+    /// Inverse of [`Self::to_text`]; surrounding whitespace (a trailing newline from a paste or scanner) is ignored.
+    pub fn from_text(s: &str) -> Option<Self> {
+        use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+        Self::from_vsf(&URL_SAFE_NO_PAD.decode(s.trim()).ok()?)
+    }
+
+    /// The shareable link form: [`LINK_PREFIX`] + [`Self::to_text`].
+    pub fn to_link(&self) -> Option<String> {
+        Some(format!("{LINK_PREFIX}{}", self.to_text()?))
+    }
+
+    /// Parse a contact link. Tolerates surrounding whitespace, an upper-cased scheme (some launchers normalise it) and a trailing `/` a browser may append; anything else that isn't exactly a card is `None`.
+    pub fn from_link(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let head = s.get(..LINK_PREFIX.len())?;
+        if !head.eq_ignore_ascii_case(LINK_PREFIX) {
+            return None;
+        }
+        Self::from_text(s[LINK_PREFIX.len()..].trim_end_matches('/'))
+    }
+
+    /// What "copy my handle" puts on the clipboard: the handle on its own line (so a friend can type it into search), then the link (so a friend on Photon can just click it). `handle` is `None` when this run never saw it typed — a resumed session — and the link goes alone. The handle is only ever here because its owner asked to share it; the card itself still never carries it.
+    pub fn share_text(&self, handle: Option<&str>) -> Option<String> {
+        let link = self.to_link()?;
+        Some(match handle.map(str::trim).filter(|h| !h.is_empty()) {
+            Some(h) => format!("{h}\n{link}"),
+            None => link,
+        })
+    }
+
+    /// Build a fresh contact row from the card's pins — the no-lookup add. Petname and avatar pin start empty, exactly as after a search add.
+    pub fn to_contact(&self) -> Contact {
+        Contact::from_pin(String::new(), [0u8; 64], self.handle_proof, self.party_id, self.device_pubkey.clone())
+    }
+
+    /// Does this card describe `contact`? Same party id, and the device is one we already trust for it — a card for the right identity from a device its fleet fold excluded doesn't verify.
+    pub fn matches(&self, contact: &Contact) -> bool {
+        contact.handle_hash == self.party_id && contact.knows_device(self.device_pubkey.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn card_round_trips_thru_vsf_and_rejects_garbage() {
+        let card = ContactCard {
+            handle_proof: [0x5a; 32],
+            party_id: crate::crypto::clutch::identity_party_id(&[0x11; 32]),
+            device_pubkey: DevicePubkey::from_bytes([0x77; 32]),
+        };
+        let bytes = card.to_vsf().unwrap();
+        assert_eq!(ContactCard::from_vsf(&bytes), Some(card.clone()));
+        // Small enough for a comfortable QR (byte mode, EC level M tops out near 2.3 KB).
+        assert!(bytes.len() < 256, "card is {} bytes", bytes.len());
+        assert_eq!(ContactCard::from_vsf(b"not a card"), None);
+        assert_eq!(ContactCard::from_vsf(&bytes[..bytes.len() / 2]), None);
+        // The QR's text form round-trips too, tolerating a scanner's trailing newline.
+        let text = card.to_text().unwrap();
+        assert_eq!(ContactCard::from_text(&format!("{text}\n")), Some(card.clone()));
+        let contact = card.to_contact();
+        assert!(card.matches(&contact));
+    }
+
+    #[test]
+    fn contact_round_trips_thru_a_link() {
+        let card = ContactCard {
+            handle_proof: [0x3c; 32],
+            party_id: crate::crypto::clutch::identity_party_id(&[0x42; 32]),
+            device_pubkey: DevicePubkey::from_bytes([0x99; 32]),
+        };
+        let link = card.to_link().unwrap();
+        assert!(link.starts_with(LINK_PREFIX));
+        // What a clicked link hands us may be upper-cased, slash-terminated or newline-padded.
+        let parsed = ContactCard::from_link(&format!(" PHOTON://ADD/{}/\n", &link[LINK_PREFIX.len()..])).unwrap();
+        assert_eq!(parsed, card);
+        let contact = parsed.to_contact();
+        assert_eq!(contact.handle_proof, card.handle_proof);
+        assert_eq!(contact.handle_hash, card.party_id);
+        assert!(contact.knows_device(card.device_pubkey.as_bytes()));
+        assert!(!contact.verified);
+        // Not a link, or a link to something that isn't a card.
+        assert_eq!(ContactCard::from_link(&link[LINK_PREFIX.len()..]), None);
+        assert_eq!(ContactCard::from_link("photon://add/AAAA"), None);
+        assert_eq!(ContactCard::from_link("photon://"), None);
+    }
+
+    #[test]
+    fn share_text_puts_the_handle_above_a_working_link() {
+        let card = ContactCard {
+            handle_proof: [0x21; 32],
+            party_id: crate::crypto::clutch::identity_party_id(&[0x22; 32]),
+            device_pubkey: DevicePubkey::from_bytes([0x23; 32]),
+        };
+        let text = card.share_text(Some(" nick \n")).unwrap();
+        let (handle, link) = text.split_once('\n').unwrap();
+        assert_eq!(handle, "nick");
+        assert_eq!(ContactCard::from_link(link), Some(card.clone()));
+        // No handle known (or blank) → just the link.
+        assert_eq!(card.share_text(None), card.to_link());
+        assert_eq!(card.share_text(Some("  ")), card.to_link());
+    }
+}
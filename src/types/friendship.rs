@@ -10,6 +10,33 @@
 
 use crate::crypto::chain::{Chain, CHAIN_SIZE};
 
+/// The braid for the next send: up to TWO distinct prior PEER messages to weave into this chain step, as `(eagle_time, x-text)` sorted by time so both peers frame `derive_fresh_link` identically. Eligible = incoming messages in the last ≤256 of `messages` (chronological) — any stored incoming row is one the receive path already ACKed, so the sender knows the peer holds it. Probe rows are never eligible: the peer stores no row for its own probe, so a woven probe ref would be unresolvable on their side. 0 eligible → weave nothing (anchor), 1 → a single strand, ≥2 → two distinct, picked with `gen_range` (bounded, bias-free) — never modulo.
+pub fn pick_woven(messages: &[crate::types::ChatMessage]) -> Vec<(i64, Vec<u8>)> {
+    use rand::Rng;
+    let window: Vec<&crate::types::ChatMessage> = messages
+        .iter()
+        .rev()
+        .filter(|m| !m.is_outgoing && m.content != crate::types::CHAIN_PROBE_MARKER)
+        .take(256)
+        .collect();
+    let mut chosen: Vec<(i64, Vec<u8>)> = Vec::new();
+    let mut rng = rand::thread_rng();
+    if window.len() == 1 {
+        chosen.push((window[0].timestamp, window[0].content.as_bytes().to_vec()));
+    } else if window.len() >= 2 {
+        let i = rng.gen_range(0..window.len());
+        let mut j = rng.gen_range(0..window.len() - 1);
+        if j >= i {
+            j += 1; // map [0, len-1) → [0, len)\{i} so j is distinct from i, uniformly
+        }
+        for &idx in &[i, j] {
+            chosen.push((window[idx].timestamp, window[idx].content.as_bytes().to_vec()));
+        }
+    }
+    chosen.sort_by_key(|(t, _)| *t);
+    chosen
+}
+
 /// Ceremony ID: deterministic CLUTCH ceremony identifier.
 ///
 /// Derived via spaghettify from handle_hashes + sorted ping provenances:
@@ -799,6 +826,42 @@ impl FriendshipChains {
         }
     }
 
+    /// Indices of `pending_messages` in CHAIN order: each run starts at a message whose `prev_msg_hp` names no other pending message and follows `msg_hp` → `prev_msg_hp` links from there. Insertion order usually matches, but a reload, a dedup or a requeue can shuffle it, and the receiver's `verify_chain_link` rejects a message whose predecessor it hasn't seen. Runs start oldest-first by eagle_time; anything the links don't reach (never true for real hashes) trails in eagle_time order rather than being dropped.
+    fn chain_order(&self) -> Vec<usize> {
+        let pending = &self.pending_messages;
+        let mut by_time: Vec<usize> = (0..pending.len()).collect();
+        by_time.sort_by_key(|&i| pending[i].eagle_time);
+        let mut placed = vec![false; pending.len()];
+        let mut order = Vec::with_capacity(pending.len());
+        for &root in &by_time {
+            if placed[root] || pending.iter().any(|m| m.msg_hp == pending[root].prev_msg_hp) {
+                continue;
+            }
+            let mut cur = root;
+            loop {
+                placed[cur] = true;
+                order.push(cur);
+                match by_time
+                    .iter()
+                    .find(|&&j| !placed[j] && pending[j].prev_msg_hp == pending[cur].msg_hp)
+                {
+                    Some(&next) => cur = next,
+                    None => break,
+                }
+            }
+        }
+        order.extend(by_time.into_iter().filter(|&i| !placed[i]));
+        order
+    }
+
+    /// Pending messages in chain order (see [`Self::chain_order`]) — what every replay must walk so the receiver sees each predecessor first.
+    pub fn pending_in_chain_order(&self) -> Vec<&PendingMessage> {
+        self.chain_order()
+            .into_iter()
+            .map(|i| &self.pending_messages[i])
+            .collect()
+    }
+
     /// Reliability sweep: collect every unacked pending message whose backoff deadline has passed, bump its attempt count + next deadline, and return the data needed to resend it. Drives the tick-based retransmit so a dropped message OR a dropped ACK self-heals (we keep resending until the ACK lands; the receiver dedupes by eagle_time). Messages that have exhausted `MAX_SEND_ATTEMPTS` are NOT returned here (the caller treats them as undelivered) but are left in pending so a late ACK can still clear them.
     ///
     /// Returns `(eagle_time, prev_msg_hp, ciphertext, attempts_now, exhausted)` per due message.
@@ -807,7 +870,9 @@ impl FriendshipChains {
         now_osc: i64,
     ) -> Vec<(i64, [u8; 32], Vec<u8>, u8, bool)> {
         let mut due = Vec::new();
-        for msg in self.pending_messages.iter_mut() {
+        // Walk in chain order so a batch that comes due together goes out predecessor-first.
+        for i in self.chain_order() {
+            let msg = &mut self.pending_messages[i];
             if msg.attempts >= MAX_SEND_ATTEMPTS {
                 continue; // exhausted — don't resend, but keep pending for a possible late ACK
             }
@@ -843,6 +908,27 @@ impl FriendshipChains {
         rearmed
     }
 
+    /// User-requested retry of one given-up message ("tap to retry"): reset its backoff so the next retransmit sweep sends it thru the normal path again, with a fresh `MAX_SEND_ATTEMPTS` budget. `false` = not pending (already ACKed, or this session never queued it).
+    pub fn retry_pending(&mut self, eagle_time: i64, now_osc: i64) -> bool {
+        let Some(msg) = self.pending_messages.iter_mut().find(|m| m.eagle_time == eagle_time) else {
+            return false;
+        };
+        msg.attempts = 0;
+        msg.next_retry_osc = now_osc;
+        true
+    }
+
+    /// Startup re-enqueue: called once per chain right after it loads from disk, so every message that was in flight when the app went down goes back on the retransmit sweep with a fresh `MAX_SEND_ATTEMPTS` budget. Entries sharing an eagle_time (the same send persisted twice) collapse to the first, so one message is never resent as two. A message the peer already decrypted is still resent on purpose: its ACK is what advances our chain, and the receiver drops the duplicate by eagle_time and re-ACKs it. Returns how many are queued.
+    pub fn requeue_after_restart(&mut self, now_osc: i64) -> usize {
+        let mut seen = std::collections::HashSet::new();
+        self.pending_messages.retain(|m| seen.insert(m.eagle_time));
+        for msg in self.pending_messages.iter_mut() {
+            msg.attempts = 1;
+            msg.next_retry_osc = now_osc; // due on the first sweep
+        }
+        self.pending_messages.len()
+    }
+
     /// Get pending messages that come after a given hash pointer. Used for resync: peer says "I have hash X", we return messages after X.
     ///
     /// Returns Vec of (eagle_time, ciphertext, prev_msg_hp) for resending.
@@ -851,7 +937,8 @@ impl FriendshipChains {
         let mut result = Vec::new();
 
         // Check if after_hash matches our anchor (they want everything) or if it's somewhere in our pending chain
-        for pending in &self.pending_messages {
+        let ordered = self.pending_in_chain_order();
+        for pending in ordered.iter() {
             if found_start {
                 result.push((
                     pending.eagle_time,
@@ -874,8 +961,7 @@ impl FriendshipChains {
 
         // If after_hash is one of our anchors, return all pending
         if !found_start && self.first_message_anchors.iter().any(|a| a == after_hash) {
-            return self
-                .pending_messages
+            return ordered
                 .iter()
                 .map(|p| (p.eagle_time, p.ciphertext.clone(), p.prev_msg_hp))
                 .collect();
@@ -950,6 +1036,39 @@ impl FriendshipChains {
         self.last_sent_hash = Some(msg_hp);
     }
 
+    /// Build the chat message VSF the receiver parses — `(message: x{text}, hp{incorporated_hp}, e6{woven_time}…, extra…, hR{pad})`, field order shuffled to enforce type-marker (not positional) parsing — and encrypt it on our chain ([`Self::prepare_send`]). `woven` is the braid from [`pick_woven`]; `extra` the wire values riding outside the chain math (an image's v'j', a file's or voice memo's fields). Only the bare x-text salts the chain. Returns `(ciphertext, prev_msg_hp)`, or `None` if `our_handle_hash` isn't a participant.
+    pub fn seal_chat(
+        &mut self,
+        our_handle_hash: &[u8; 32],
+        text: &str,
+        woven: Vec<(i64, Vec<u8>)>,
+        extra: Vec<vsf::VsfType>,
+        eagle_time: i64,
+    ) -> Option<(Vec<u8>, [u8; 32])> {
+        use rand::seq::SliceRandom;
+
+        let incorporated_hp = self.last_incorporated_hp().copied().unwrap_or([0u8; 32]);
+        let mut values = vec![vsf::VsfType::x(text.to_string()), vsf::VsfType::hp(incorporated_hp.to_vec())];
+        // The braid: name each woven peer message by its eagle_time (e6). 0, 1, or 2 of these.
+        for (t, _) in &woven {
+            values.push(vsf::VsfType::e(vsf::EtType::e6(*t)));
+        }
+        values.extend(extra);
+        // Short random pad (median ~53B) for traffic-analysis resistance.
+        let pad_len = rand::random::<u8>().min(rand::random::<u8>()).min(rand::random::<u8>()) as usize;
+        if pad_len > 0 {
+            let pad: Vec<u8> = (0..pad_len).map(|_| rand::random()).collect();
+            values.push(vsf::VsfType::hR(pad));
+        }
+        values.shuffle(&mut rand::thread_rng());
+        let payload = vsf::schema::section::FieldValue::new("message", values).flatten();
+
+        let strands = woven.into_iter().map(|(_, content)| content).collect();
+        let (ciphertext, prev_msg_hp, _msg_hp, _plaintext_hash) =
+            self.prepare_send(our_handle_hash, payload, text.as_bytes().to_vec(), eagle_time, strands)?;
+        Some((ciphertext, prev_msg_hp))
+    }
+
     /// Encrypt a fresh outgoing message on OUR chain and record it pending.
     ///
     /// The exact inverse of the receive path: derive the salt from our previous plaintext, generate the scratch pad, encrypt with our current chain key. `plaintext` is the already-VSF-encoded message body (the `(message: x{text}, hp{incorporated_hp}, hR{pad})` field the receiver parses) — the caller builds it so this layer stays agnostic to message shape.
@@ -1534,4 +1653,70 @@ mod tests {
         // Re-arming past the newest tip revives nothing.
         assert_eq!(chains.rearm_pending_after(t0 + 10 * one_s, far), 0);
     }
+
+    #[test]
+    fn retransmits_replay_in_chain_order_not_insertion_order() {
+        let alice = [1u8; 32];
+        let bob = [2u8; 32];
+        let eggs: Vec<[u8; 32]> = (0..8).map(|i| [i as u8; 32]).collect();
+        let mut chains = FriendshipChains::from_clutch(&[alice, bob], &eggs);
+        let one_s = vsf::OSCILLATIONS_PER_SECOND as i64;
+        let t0 = 1_000_000_000i64;
+
+        // a → b → c linked by msg_hp/prev_msg_hp, queued as c, a, b (what a reload or requeue can leave behind).
+        let anchor = [0u8; 32];
+        chains.add_pending(t0 + 2 * one_s, vec![3], [0xCC; 32], [12; 32], [13; 32], vec![3], vec![]);
+        chains.add_pending(t0, vec![1], [0xAA; 32], anchor, [11; 32], vec![1], vec![]);
+        chains.add_pending(t0 + one_s, vec![2], [0xBB; 32], [11; 32], [12; 32], vec![2], vec![]);
+
+        let order: Vec<i64> = chains.pending_in_chain_order().iter().map(|m| m.eagle_time).collect();
+        assert_eq!(order, vec![t0, t0 + one_s, t0 + 2 * one_s]);
+
+        // All three come due in one sweep and go out predecessor-first.
+        let due = chains.collect_due_retransmits(t0 + 60 * one_s);
+        let sent: Vec<(i64, [u8; 32])> = due.iter().map(|d| (d.0, d.1)).collect();
+        assert_eq!(sent, vec![(t0, anchor), (t0 + one_s, [11; 32]), (t0 + 2 * one_s, [12; 32])]);
+
+        // A resync from the middle of the chain follows the same order.
+        let after: Vec<i64> = chains.get_pending_after(&[11; 32]).iter().map(|p| p.0).collect();
+        assert_eq!(after, vec![t0 + one_s, t0 + 2 * one_s]);
+    }
+
+    #[test]
+    fn exhausted_message_is_marked_failed_and_retry_requeues_it() {
+        use crate::types::{ChatMessage, Contact, DevicePubkey, HandleText};
+
+        let alice = [1u8; 32];
+        let bob = [2u8; 32];
+        let eggs: Vec<[u8; 32]> = (0..8).map(|i| [i as u8; 32]).collect();
+        let mut chains = FriendshipChains::from_clutch(&[alice, bob], &eggs);
+        let one_s = vsf::OSCILLATIONS_PER_SECOND as i64;
+        let t0 = 1_000_000_000i64;
+
+        let mut contact = Contact::new(HandleText::new("bob"), [0x22; 32], DevicePubkey::from_bytes([2u8; 32]));
+        contact.messages.push(ChatMessage::new_with_timestamp("hello?".into(), true, t0));
+        chains.add_pending(t0, b"hello?".to_vec(), [0xAA; 32], [0; 32], [9; 32], vec![1], vec![]);
+
+        // Every fallback spent: the sweep reports the give-up, and the caller flags the row.
+        let mut now = t0;
+        let mut gave_up = false;
+        while !gave_up {
+            now += one_s * 60;
+            for (eagle_time, _, _, _, exhausted) in chains.collect_due_retransmits(now) {
+                if exhausted {
+                    gave_up = contact.mark_failed(eagle_time);
+                }
+            }
+        }
+        assert!(contact.messages[0].failed);
+        assert!(!contact.mark_failed(t0), "already flagged");
+        assert!(chains.collect_due_retransmits(now + one_s * 3600).is_empty());
+
+        // Tap to retry: re-queued on the normal retransmit path with a fresh budget.
+        assert!(chains.retry_pending(t0, now));
+        let due = chains.collect_due_retransmits(now);
+        assert_eq!(due.len(), 1);
+        assert_eq!((due[0].0, due[0].3, due[0].4), (t0, 1, false));
+        assert!(!chains.retry_pending(t0 + 1, now), "nothing pending at that stamp");
+    }
 }
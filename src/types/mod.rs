@@ -1,15 +1,21 @@
+pub mod attachment;
 pub mod contact;
+pub mod contact_card;
 pub mod device;
 pub mod friendship;
 pub mod handle;
 pub mod peer;
 pub mod seed;
 pub mod shard;
+pub mod voice;
 
+pub use attachment::*;
 pub use contact::*;
+pub use contact_card::*;
 pub use device::*;
 pub use friendship::*;
 pub use handle::*;
 // pub use peer::*;
 pub use seed::*;
 // pub use shard::*;
+pub use voice::*;
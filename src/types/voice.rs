@@ -0,0 +1,127 @@
+//! Voice memos — a recorded Opus clip riding a chat message. The clip goes as `v'o'` (Ogg-Opus, as the platform recorder writes it), its length in ms as `u6`, and a coarse loudness envelope as `v'w'` so the row draws its waveform without decoding audio. Like images and files the caption ([`VoiceMemo::caption`]) is the only chain salt; the audio never enters the chain math. Capture + playback are per-platform (`platform::voice`).
+
+use vsf::VsfType;
+
+/// Longest memo recorded or accepted, in ms (~2 min). Past this it's a voice call, not a memo.
+pub const MAX_VOICE_MS: u32 = 1 << 17;
+
+/// Largest encoded clip accepted. Opus speech at ~24 kbit/s runs ~3 KB/s, so a full-length memo is ~400 KB; this leaves headroom for a higher-bitrate recorder.
+pub const MAX_VOICE_BYTES: usize = 1 << 20;
+
+/// Bars in a memo's waveform.
+pub const WAVEFORM_BARS: usize = 1 << 6;
+
+/// A recorded voice memo.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VoiceMemo {
+    /// Length of the clip.
+    pub duration_ms: u32,
+    /// Peak loudness per bar (0–255), [`WAVEFORM_BARS`] long (shorter only for a clip with fewer level samples).
+    pub waveform: Vec<u8>,
+    /// The Ogg-Opus clip.
+    pub opus: Vec<u8>,
+}
+
+impl VoiceMemo {
+    /// A finished recording: the clip, its length, and the recorder's level samples (0–255, evenly spaced in time) folded down to the waveform. `Err` is a user-facing hint.
+    pub fn from_recording(opus: Vec<u8>, duration_ms: u32, levels: &[u8]) -> Result<Self, String> {
+        if opus.is_empty() || duration_ms == 0 {
+            return Err("That recording came out empty.".to_string());
+        }
+        if duration_ms > MAX_VOICE_MS || opus.len() > MAX_VOICE_BYTES {
+            return Err(format!("Voice memos run up to {} — that one's too long.", clock(MAX_VOICE_MS)));
+        }
+        Ok(Self { duration_ms, waveform: waveform(levels, WAVEFORM_BARS), opus })
+    }
+
+    /// The message caption: what an older client shows in the row and what salts the chain.
+    pub fn caption(&self) -> String {
+        format!("[voice {}]", self.duration_label())
+    }
+
+    /// "m:ss".
+    pub fn duration_label(&self) -> String {
+        clock(self.duration_ms)
+    }
+
+    /// The values this memo adds to a chat `message` field.
+    pub fn wire_values(&self) -> Vec<VsfType> {
+        vec![
+            VsfType::u6(self.duration_ms as u64),
+            VsfType::v(b'w', self.waveform.clone()),
+            VsfType::v(b'o', self.opus.clone()),
+        ]
+    }
+
+    /// Reassemble a memo from a received `message` field's values (any order). `None` unless the clip and a length are present and within bounds; a missing or oversized waveform just draws flat.
+    pub fn from_wire(values: &[VsfType]) -> Option<Self> {
+        let (mut duration, mut wave, mut opus) = (None, Vec::new(), None);
+        for value in values {
+            match value {
+                VsfType::u6(ms) => duration = u32::try_from(*ms).ok(),
+                VsfType::v(b'w', w) if w.len() <= WAVEFORM_BARS => wave = w.clone(),
+                VsfType::v(b'o', o) => opus = Some(o),
+                _ => {}
+            }
+        }
+        let (duration_ms, opus) = (duration?, opus?);
+        if duration_ms == 0 || duration_ms > MAX_VOICE_MS || opus.is_empty() || opus.len() > MAX_VOICE_BYTES {
+            return None;
+        }
+        Some(Self { duration_ms, waveform: wave, opus: opus.clone() })
+    }
+}
+
+/// Fold evenly spaced level samples into `bars` peaks (each bar keeps the loudest sample in its span). Fewer samples than bars → one bar per sample.
+pub fn waveform(levels: &[u8], bars: usize) -> Vec<u8> {
+    if levels.len() <= bars {
+        return levels.to_vec();
+    }
+    (0..bars)
+        .map(|b| {
+            let (from, to) = (b * levels.len() / bars, (b + 1) * levels.len() / bars);
+            levels[from..to].iter().copied().max().unwrap_or(0)
+        })
+        .collect()
+}
+
+fn clock(ms: u32) -> String {
+    let s = ms.div_ceil(1000);
+    format!("{}:{:02}", s / 60, s % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memo_and_duration_round_trip_thru_the_message_field() {
+        // Recorder output is mocked: any bytes stand in for the Ogg-Opus clip.
+        let levels: Vec<u8> = (0..500u32).map(|i| (i * 13 % 256) as u8).collect();
+        let memo = VoiceMemo::from_recording(vec![0x4F, 0x67, 0x67, 0x53, 1, 2, 3], 12_345, &levels).unwrap();
+        assert_eq!(memo.waveform.len(), WAVEFORM_BARS);
+        assert_eq!(memo.caption(), "[voice 0:13]");
+
+        let mut values = vec![VsfType::x(memo.caption())];
+        values.extend(memo.wire_values());
+        let payload = vsf::schema::section::FieldValue::new("message", values).flatten();
+        let mut ptr = 0;
+        let field = vsf::file_format::VsfField::parse(&payload, &mut ptr).unwrap();
+        let got = VoiceMemo::from_wire(&field.values).expect("memo");
+        assert_eq!(got, memo);
+        assert_eq!(got.duration_ms, 12_345);
+
+        // No clip, or a length past the cap, is no memo.
+        assert_eq!(VoiceMemo::from_wire(&memo.wire_values()[..2]), None);
+        let mut long = memo.wire_values();
+        long[0] = VsfType::u6(MAX_VOICE_MS as u64 + 1);
+        assert_eq!(VoiceMemo::from_wire(&long), None);
+        assert!(VoiceMemo::from_recording(vec![1], MAX_VOICE_MS + 1, &[]).is_err());
+    }
+
+    #[test]
+    fn waveform_keeps_each_spans_peak() {
+        assert_eq!(waveform(&[1, 9, 2, 3, 7, 0], 3), vec![9, 3, 7]);
+        assert_eq!(waveform(&[5, 6], 4), vec![5, 6]);
+    }
+}
@@ -0,0 +1,113 @@
+//! What an arriving friend message sets off — the maths half. The receive path has always stored the message first; this decides the rest: the unread count (the contacts-list double ring), and the alert (system notification + chime). Do-not-disturb silences the alert only — the message still lands, still counts unread, and is tallied in the held backlog the Ready strip shows until DND goes off. A muted contact goes further: no alert and no unread ring, for that one conversation.
+
+/// What a stored message sets off.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Arrival {
+    /// Bump the contact's unread count — nobody was looking at this conversation.
+    pub unread: bool,
+    /// Ring: notification + chime. The desktop notifier still applies its own window-attended gate and msg_hp dedup on top.
+    pub notify: bool,
+    /// Alerting was held back by do-not-disturb — count it in the backlog.
+    pub held: bool,
+    /// Bring an archived conversation back to the main list. Muting doesn't stop it: muted silences the alert, not the conversation, so a muted chat still resurfaces — quietly, with no unread ring.
+    pub unarchive: bool,
+}
+
+/// The decision for one message from a contact. `looking` = its conversation is the active, attended view. A sibling (our own device propagating a conversation) never counts or rings: nobody sent us anything; nor does a muted contact, by choice.
+pub fn decide(looking: bool, sibling: bool, muted: bool, dnd: bool) -> Arrival {
+    let real = !sibling && !muted;
+    Arrival { unread: real && !looking, notify: real && !dnd, held: real && dnd, unarchive: !sibling }
+}
+
+/// Do-not-disturb: the switch (persisted in `settings.vsf`) plus the backlog of messages it kept quiet this session.
+#[derive(Debug, Default)]
+pub struct Dnd {
+    on: bool,
+    held: u32,
+}
+
+impl Dnd {
+    pub fn new(on: bool) -> Self {
+        Self { on, held: 0 }
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.on
+    }
+
+    /// Messages kept quiet since DND went on — the backlog indicator's count.
+    pub fn held(&self) -> u32 {
+        self.held
+    }
+
+    /// Tally one arrival; counts only what DND actually held back.
+    pub fn record(&mut self, arrival: Arrival) {
+        if arrival.held {
+            self.held = self.held.saturating_add(1);
+        }
+    }
+
+    /// Flip the switch. Turning it off clears the backlog — the messages themselves wait in their conversations' unread rings.
+    pub fn set(&mut self, on: bool) {
+        self.on = on;
+        if !on {
+            self.held = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ChatMessage, Contact, DevicePubkey, HandleText};
+
+    #[test]
+    fn dnd_silences_but_still_stores_and_counts() {
+        let mut contact = Contact::new(HandleText::new("friend"), [0x11; 32], DevicePubkey::from_bytes([1; 32]));
+        let mut dnd = Dnd::new(true);
+
+        // The receive path's order: store, then act on the decision.
+        contact.insert_message_sorted(ChatMessage::new("are you there?".to_string(), false));
+        let arrival = decide(false, contact.is_sibling, contact.muted, dnd.is_on());
+        if arrival.unread {
+            contact.unread_count += 1;
+        }
+        dnd.record(arrival);
+
+        assert!(!arrival.notify);
+        assert_eq!(contact.messages.len(), 1);
+        assert_eq!(contact.unread_count, 1);
+        assert_eq!(dnd.held(), 1);
+
+        // Off: the backlog clears, the next message rings again.
+        dnd.set(false);
+        assert_eq!(dnd.held(), 0);
+        assert!(decide(false, false, false, dnd.is_on()).notify);
+        // Siblings never ring or count, DND or not.
+        assert_eq!(decide(false, true, false, false), Arrival { unread: false, notify: false, held: false, unarchive: false });
+    }
+
+    #[test]
+    fn muted_contact_neither_rings_nor_counts() {
+        let mut noisy = Contact::new(HandleText::new("noisy"), [0x21; 32], DevicePubkey::from_bytes([2; 32]));
+        let mut quiet = Contact::new(HandleText::new("quiet"), [0x22; 32], DevicePubkey::from_bytes([3; 32]));
+        noisy.muted = true;
+        let mut dnd = Dnd::new(false);
+        for c in [&mut noisy, &mut quiet] {
+            c.insert_message_sorted(ChatMessage::new("ping".to_string(), false));
+            let arrival = decide(false, c.is_sibling, c.muted, dnd.is_on());
+            assert_eq!(arrival.notify, !c.muted);
+            if arrival.unread {
+                c.unread_count += 1;
+            }
+            dnd.record(arrival);
+        }
+        // Both stored; only the unmuted one shows the unread ring.
+        assert_eq!((noisy.messages.len(), noisy.unread_count), (1, 0));
+        assert_eq!((quiet.messages.len(), quiet.unread_count), (1, 1));
+        // A muted contact isn't DND's backlog either — but its message still brings an archived chat back.
+        dnd.set(true);
+        assert!(!decide(false, false, true, dnd.is_on()).held);
+        assert!(decide(false, false, true, dnd.is_on()).unarchive);
+    }
+}
@@ -0,0 +1,193 @@
+//! Launch-screen attestation retry: a transient network failure (FGTW unreachable, a chain read that came back mid-write) is re-fired on an exponential backoff instead of dropping the user to `LaunchState::Error` to re-type and re-attest. Rejections — a taken handle, not in the fleet, a storage failure — are never retried; they surface at once. The policy is `settings.vsf`'s `attest_retries` / `attest_backoff_s`.
+
+use crate::ui::state::{AppState, LaunchState};
+use std::time::{Duration, Instant};
+
+/// Error text that marks a failure worth retrying. HandleQuery reports errors as plain strings (the fleet client's short messages, verbatim), so the classification is by content: connectivity and indeterminate-read wording only.
+const TRANSIENT_MARKERS: &[&str] = &[
+    "no connection",
+    "connection",
+    "timed out",
+    "timeout",
+    "unreachable",
+    "network",
+    "dns",
+    "temporarily",
+    "chain unverifiable", // a KV read-lag serving a half-written chain — indeterminate, the worker says "retry"
+    "502",
+    "503",
+    "504",
+];
+
+/// Error text that is a verdict, not a hiccup — checked first so e.g. "storage init failed: … network path …" is never retried.
+const FATAL_MARKERS: &[&str] = &["not in the fleet", "storage", "taken", "claimed", "busy"];
+
+/// Is this attestation error worth retrying?
+pub fn is_transient(error: &str) -> bool {
+    let e = error.to_lowercase();
+    !FATAL_MARKERS.iter().any(|m| e.contains(m)) && TRANSIENT_MARKERS.iter().any(|m| e.contains(m))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt; 0 = never retry (the old behaviour).
+    pub max_retries: u8,
+    /// Wait before the first retry; doubles each retry up to `cap`.
+    pub base: Duration,
+    pub cap: Duration,
+}
+
+impl Default for RetryPolicy {
+    /// Four retries at 2 s, 4 s, 8 s, 16 s: half a minute of riding out a flaky link before the user is asked to do anything.
+    fn default() -> Self {
+        Self { max_retries: 4, base: Duration::from_secs(1 << 1), cap: Duration::from_secs(1 << 5) }
+    }
+}
+
+impl RetryPolicy {
+    /// Built from the user's settings (`attest_backoff_s` 0 is read as 1 s — a zero backoff would hammer FGTW in a tight loop).
+    pub fn from_settings(s: &crate::storage::settings::Settings) -> Self {
+        let base = Duration::from_secs(u64::from(s.attest_backoff_s.max(1)));
+        Self { max_retries: s.attest_retries, base, cap: base * (1 << 4) }
+    }
+
+    /// Un-jittered wait before retry number `attempt` (1-based).
+    pub fn delay(&self, attempt: u8) -> Duration {
+        let doublings = u32::from(attempt.saturating_sub(1)).min(16);
+        self.base.saturating_mul(1 << doublings).min(self.cap)
+    }
+}
+
+/// How one attestation round came back, as far as retrying cares.
+pub enum Attempt<'a> {
+    Success,
+    Error(&'a str),
+}
+
+/// What to do about it.
+#[derive(Debug, PartialEq)]
+pub enum Step {
+    /// Attested — on to Ready.
+    Ready,
+    /// Re-fire the same request at `at`.
+    Retry { attempt: u8, of: u8, at: Instant },
+    /// Surface the error; the user takes it from here.
+    Fail(String),
+}
+
+impl Step {
+    /// The launch screen's state for this step.
+    pub fn state(&self) -> AppState {
+        match self {
+            Step::Ready => AppState::Ready,
+            Step::Retry { attempt, of, .. } => AppState::Launch(LaunchState::Retrying { attempt: *attempt, of: *of }),
+            Step::Fail(e) => AppState::Launch(LaunchState::Error(e.clone())),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct AttestRetry {
+    pub policy: RetryPolicy,
+    attempt: u8,
+    due: Option<Instant>,
+}
+
+impl AttestRetry {
+    pub fn new(policy: RetryPolicy) -> Self {
+        Self { policy, attempt: 0, due: None }
+    }
+
+    /// Feed one round's outcome. `jitter` spreads the wait (pass `crate::jitter_dur` in the app, identity in tests) so a network blip doesn't bring every client back on the same second.
+    pub fn on_result(&mut self, result: Attempt, now: Instant, jitter: impl Fn(Duration) -> Duration) -> Step {
+        match result {
+            Attempt::Success => {
+                self.reset();
+                Step::Ready
+            }
+            Attempt::Error(e) if is_transient(e) && self.attempt < self.policy.max_retries => {
+                self.attempt += 1;
+                let at = now + jitter(self.policy.delay(self.attempt));
+                self.due = Some(at);
+                Step::Retry { attempt: self.attempt, of: self.policy.max_retries, at }
+            }
+            Attempt::Error(e) => {
+                self.reset();
+                Step::Fail(e.to_string())
+            }
+        }
+    }
+
+    /// When the pending retry fires (for `wake_at`).
+    pub fn due_at(&self) -> Option<Instant> {
+        self.due
+    }
+
+    /// `true` once, when the pending retry comes due — the caller re-fires its request.
+    pub fn take_due(&mut self, now: Instant) -> bool {
+        match self.due {
+            Some(at) if now >= at => {
+                self.due = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Forget any pending retry (a fresh submit, an edit to the handle, leaving Launch).
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+        self.due = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transient_failure_retries_then_success_reaches_ready() {
+        let policy = RetryPolicy { max_retries: 2, base: Duration::from_secs(2), cap: Duration::from_secs(3) };
+        let mut r = AttestRetry::new(policy);
+        let t0 = Instant::now();
+        let same = |d| d;
+
+        let step = r.on_result(Attempt::Error("No connection to FGTW"), t0, same);
+        assert_eq!(step, Step::Retry { attempt: 1, of: 2, at: t0 + Duration::from_secs(2) });
+        assert_eq!(step.state(), AppState::Launch(LaunchState::Retrying { attempt: 1, of: 2 }));
+        assert!(!r.take_due(t0 + Duration::from_secs(1)));
+        assert!(r.take_due(t0 + Duration::from_secs(2)));
+        assert!(!r.take_due(t0 + Duration::from_secs(2)), "fires once");
+
+        // Second failure backs off (capped), then the re-fired request lands.
+        let t1 = t0 + Duration::from_secs(2);
+        assert_eq!(
+            r.on_result(Attempt::Error("chain unverifiable: Truncated"), t1, same),
+            Step::Retry { attempt: 2, of: 2, at: t1 + Duration::from_secs(3) }
+        );
+        let step = r.on_result(Attempt::Success, t1 + Duration::from_secs(3), same);
+        assert_eq!(step.state(), AppState::Ready);
+        assert_eq!(r.due_at(), None);
+    }
+
+    #[test]
+    fn rejections_and_exhausted_retries_surface_the_error() {
+        let mut r = AttestRetry::new(RetryPolicy { max_retries: 1, ..RetryPolicy::default() });
+        let now = Instant::now();
+        let same = |d| d;
+        assert_eq!(r.on_result(Attempt::Error("this handle is taken"), now, same), Step::Fail("this handle is taken".into()));
+        assert_eq!(r.on_result(Attempt::Error("storage init failed: network drive"), now, same), Step::Fail("storage init failed: network drive".into()));
+
+        assert!(matches!(r.on_result(Attempt::Error("request timed out"), now, same), Step::Retry { attempt: 1, .. }));
+        assert_eq!(
+            r.on_result(Attempt::Error("request timed out"), now, same).state(),
+            AppState::Launch(LaunchState::Error("request timed out".into()))
+        );
+        // Exhaustion resets the budget for the user's next manual submit.
+        assert!(matches!(r.on_result(Attempt::Error("request timed out"), now, same), Step::Retry { attempt: 1, .. }));
+
+        assert!(!is_transient("not in the fleet"));
+        assert_eq!(RetryPolicy::default().delay(1), Duration::from_secs(2));
+        assert_eq!(RetryPolicy::default().delay(9), Duration::from_secs(32));
+    }
+}
@@ -5,10 +5,12 @@
 /// Avatar size in pixels (256x256 square)
 pub const AVATAR_SIZE: usize = 256;
 
+use super::avatar_crop::Region;
 use ed25519_dalek::{SigningKey, VerifyingKey};
 use vsf::VsfType;
 use img_parts::jpeg::Jpeg;
 use img_parts::png::Png;
+use img_parts::webp::WebP;
 use img_parts::ImageICC;
 use rav1e::prelude::*;
 
@@ -53,15 +55,32 @@ struct IccColourConverter {
 
 /// Encodes an image as a circular AV1-compressed avatar in VSF RGB colourspace
 ///
-/// Supports JPEG, PNG, and WebP formats with ICC profile colour management.
+/// Supports JPEG, PNG, WebP, GIF, TIFF and (desktop) HEIC with ICC profile colour management — see [`sniff_format`].
 ///
 /// # Arguments
 /// * `image_data` - Raw image file bytes
+/// * `crop` - The square framed on the crop screen; `None` = the largest centred square
 ///
 /// # Returns
 /// Raw AV1 OBU bitstream encoded with VSF RGB colourspace (256x256)
-pub fn encode_avatar_from_image(image_data: &[u8]) -> Result<Vec<u8>, String> {
-    encode_avatar_rgb_f32(&image_to_avatar_rgb_f32(image_data)?)
+pub fn encode_avatar_from_image(image_data: &[u8], crop: Option<Region>) -> Result<Vec<u8>, String> {
+    encode_avatar_rgb_f32(&image_to_avatar_rgb_f32(image_data, crop)?)
+}
+
+/// Wrap raw clipboard RGBA (arboard's `ImageData`: tightly packed, 4 bytes/pixel, row-major) as PNG file bytes, so a pasted image runs the exact decode → EXIF/ICC → crop → encode → save → upload pipeline a dropped file does. Lossless, so the avatar is built from the same pixels the user copied. Errors on a zero-sized image or a byte count that doesn't match the dimensions (a clipboard owner lying about its format).
+pub fn rgba_to_png(width: usize, height: usize, rgba: &[u8]) -> Result<Vec<u8>, String> {
+    use image::ImageEncoder;
+    if width == 0 || height == 0 {
+        return Err("empty clipboard image".to_string());
+    }
+    if width.checked_mul(height).and_then(|n| n.checked_mul(4)) != Some(rgba.len()) {
+        return Err(format!("clipboard image is {}x{} but carries {} bytes", width, height, rgba.len()));
+    }
+    let mut png = Vec::new();
+    image::codecs::png::PngEncoder::new(&mut png)
+        .write_image(rgba, width as u32, height as u32, image::ExtendedColorType::Rgba8)
+        .map_err(|e| format!("clipboard image PNG wrap failed: {}", e))?;
+    Ok(png)
 }
 
 /// The SLOW half of avatar-set: rav1e AV1 encode of the prepared 256×256 γ2 f32 pixels. Seconds on a dev build — never call on the UI thread (docs: the "considerable delay before the avatar shows" was this + the upload running synchronously before display).
@@ -69,30 +88,270 @@ pub fn encode_avatar_rgb_f32(vsf_rgb_f32: &[f32]) -> Result<Vec<u8>, String> {
     encode_av1(vsf_rgb_f32, AVATAR_SIZE)
 }
 
+/// Longest animated avatar kept, in frames — a looping clip, not a video: every frame is a full 256² AV1 still riding the same vault slot and upload, so a long GIF is cut here rather than ballooning the blob.
+pub const AVATAR_MAX_FRAMES: usize = 1 << 5;
+
+/// A decoded avatar: one frame for a still, several for a looping clip. Every frame is `size × size × 3` u8 RGB.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AvatarAnimation {
+    pub size: usize,
+    pub frames: Vec<Vec<u8>>,
+    /// Per-frame display time in ms, parallel to `frames`. A still carries a single 0.
+    pub durations_ms: Vec<u32>,
+}
+
+impl AvatarAnimation {
+    /// More than one frame — worth a wakeup timer.
+    pub fn is_animated(&self) -> bool {
+        self.frames.len() > 1
+    }
+
+    /// The frame showing `elapsed_ms` into the (looping) clip, and the ms until it changes. A still — or a clip whose durations sum to zero — always shows frame 0 and never changes.
+    pub fn frame_at(&self, elapsed_ms: u64) -> (usize, Option<u64>) {
+        let total: u64 = self.durations_ms.iter().map(|&d| d as u64).sum();
+        if !self.is_animated() || total == 0 {
+            return (0, None);
+        }
+        let mut t = elapsed_ms % total;
+        for (i, &d) in self.durations_ms.iter().enumerate() {
+            let d = d as u64;
+            if t < d {
+                return (i, Some(d - t));
+            }
+            t -= d;
+        }
+        (0, None)
+    }
+
+    /// Run every frame thru `f` (the display colour conversion), keeping the timing.
+    pub fn map_frames(self, f: impl Fn(&[u8]) -> Vec<u8>) -> Self {
+        AvatarAnimation {
+            size: self.size,
+            frames: self.frames.iter().map(|px| f(px)).collect(),
+            durations_ms: self.durations_ms,
+        }
+    }
+}
+
+/// GIF/WebP delays of 10ms or less are the formats' "as fast as possible" authoring junk, which every browser plays at 100ms — same rule here, so a 0-delay clip neither strobes nor spins the wakeup loop.
+fn frame_delay_ms(ms: u32) -> u32 {
+    if ms <= 10 {
+        100
+    } else {
+        ms
+    }
+}
+
+/// Animated counterpart of [`image_to_avatar_rgb_f32`]: a GIF or animated WebP with more than one frame → prepared pixels per frame (first `AVATAR_MAX_FRAMES`) plus their delays in ms. `Ok(None)` for anything that isn't a multi-frame clip, so the caller falls thru to the still path. Frames skip the ICC lookup — GIF carries no profile and both formats are sRGB in practice.
+pub fn image_to_avatar_frames(image_data: &[u8], crop: Option<Region>) -> Result<Option<(Vec<Vec<f32>>, Vec<u32>)>, String> {
+    use image::AnimationDecoder;
+    let cursor = std::io::Cursor::new(image_data);
+    let frames = match image::guess_format(image_data) {
+        Ok(image::ImageFormat::Gif) => image::codecs::gif::GifDecoder::new(cursor)
+            .map_err(|e| format!("Failed to decode GIF: {}", e))?
+            .into_frames(),
+        Ok(image::ImageFormat::WebP) => {
+            let decoder = image::codecs::webp::WebPDecoder::new(cursor)
+                .map_err(|e| format!("Failed to decode WebP: {}", e))?;
+            if !decoder.has_animation() {
+                return Ok(None);
+            }
+            decoder.into_frames()
+        }
+        _ => return Ok(None),
+    };
+    let mut pixels = Vec::new();
+    let mut durations_ms = Vec::new();
+    for frame in frames.take(AVATAR_MAX_FRAMES) {
+        let frame = frame.map_err(|e| format!("Failed to decode animation frame: {}", e))?;
+        let (numer, denom) = frame.delay().numer_denom_ms();
+        durations_ms.push(frame_delay_ms(numer / denom.max(1)));
+        let img = image::DynamicImage::ImageRgba8(frame.into_buffer());
+        pixels.push(prepare_avatar_rgb_f32(&img, None, crop)?);
+    }
+    if pixels.len() < 2 {
+        return Ok(None);
+    }
+    Ok(Some((pixels, durations_ms)))
+}
+
+/// Payload schema for an animated avatar: the frame count, then per frame its duration (ms) and its own AV1 still. Frames stay independent stills so each decodes with the unchanged single-picture `decode_avatar`, and frame 0 alone is the still fallback.
+fn avatar_anim_schema() -> vsf::schema::SectionSchema {
+    use vsf::schema::{SectionSchema, TypeConstraint};
+    SectionSchema::new("anim")
+        .field("frames", TypeConstraint::AnyUnsigned)
+        .field("dur", TypeConstraint::AnyUnsigned) // ms, one per frame
+        .field("av1", TypeConstraint::Wrapped(b'a')) // AV1 still, one per frame
+}
+
+/// The SLOW half of an animated avatar-set: AV1-encode every prepared frame and pack them with the count + durations as an `anim` VSF section. The packed bytes are a LOCAL format: they go to the device's own clip slot ([`save_avatar_animation_from_seed`]), while the shared avatar slot — what the wall upload, siblings and friends read — keeps the bare AV1 still of frame 0 that every client decodes. Builds before animation read a packed payload as a broken AV1 and show nothing, and no peer advertises that it can play a clip yet, so the clip stays off the wire until one does. [`decode_avatar_animation`] reads both forms.
+pub fn encode_animated_avatar(frames: &[Vec<f32>], durations_ms: &[u32]) -> Result<Vec<u8>, String> {
+    if frames.is_empty() || frames.len() > AVATAR_MAX_FRAMES || frames.len() != durations_ms.len() {
+        return Err(format!(
+            "animated avatar needs 1..={} frames with one duration each (got {} frames, {} durations)",
+            AVATAR_MAX_FRAMES,
+            frames.len(),
+            durations_ms.len()
+        ));
+    }
+    let mut builder = avatar_anim_schema()
+        .build()
+        .append_multi("frames", vec![VsfType::u5(frames.len() as u32)])
+        .map_err(|e| e.to_string())?;
+    for (rgb, &ms) in frames.iter().zip(durations_ms) {
+        builder = builder
+            .append_multi("dur", vec![VsfType::u5(ms)])
+            .map_err(|e| e.to_string())?
+            .append_multi("av1", vec![VsfType::v(b'a', encode_avatar_rgb_f32(rgb)?)])
+            .map_err(|e| e.to_string())?;
+    }
+    builder.encode().map_err(|e| e.to_string())
+}
+
+/// Decode an avatar payload into frames + timing: a packed `anim` section, or a bare AV1 still (every avatar written before animation, and every still since) as a one-frame clip. Frames must be square and all one size.
+pub fn decode_avatar_animation(payload: &[u8]) -> Result<AvatarAnimation, String> {
+    let Ok(section) = vsf::schema::SectionBuilder::parse(avatar_anim_schema(), payload) else {
+        let (width, height, pixels) = decode_avatar(payload)?;
+        if width != height {
+            return Err(format!("avatar is {}x{}, not square", width, height));
+        }
+        return Ok(AvatarAnimation { size: width, frames: vec![pixels], durations_ms: vec![0] });
+    };
+    let count = section
+        .get_fields("frames")
+        .first()
+        .and_then(|f| f.values.first())
+        .and_then(|v| v.as_usize())
+        .ok_or("animated avatar missing frame count")?;
+    let durations_ms: Vec<u32> = section
+        .get_fields("dur")
+        .iter()
+        .filter_map(|f| f.values.first())
+        .filter_map(|v| v.as_usize())
+        .map(|ms| ms as u32)
+        .collect();
+    let stills: Vec<&[u8]> = section
+        .get_fields("av1")
+        .iter()
+        .filter_map(|f| f.values.first())
+        .filter_map(|v| match v {
+            VsfType::v(_, data) => Some(data.as_slice()),
+            _ => None,
+        })
+        .collect();
+    if count == 0 || count > AVATAR_MAX_FRAMES || stills.len() != count || durations_ms.len() != count {
+        return Err(format!(
+            "animated avatar claims {} frames but carries {} stills / {} durations",
+            count,
+            stills.len(),
+            durations_ms.len()
+        ));
+    }
+    let mut size = 0;
+    let mut frames = Vec::with_capacity(count);
+    for still in stills {
+        let (width, height, pixels) = decode_avatar(still)?;
+        if width != height || (size != 0 && width != size) {
+            return Err(format!("animated avatar frame is {}x{} (first frame {})", width, height, size));
+        }
+        size = width;
+        frames.push(pixels);
+    }
+    Ok(AvatarAnimation { size, frames, durations_ms })
+}
+
 /// Quantize the prepared f32 γ2 pixels to the u8 form the display path uses (identical to what the AV1 round-trip decodes to, 8-bit) — so the UI can show the avatar the same frame it was picked, before the encode even starts.
 pub fn avatar_rgb_f32_to_u8(vsf_rgb_f32: &[f32]) -> Vec<u8> {
     vsf_rgb_f32.iter().map(|v| (v * 255.0 + 0.5) as u8).collect()
 }
 
-/// The FAST half of avatar-set: decode + EXIF/ICC handling + centre-crop + Lanczos resize + circular mask + γ2 — everything except the AV1 encode. Milliseconds; safe on the UI thread for the instant-display path.
-pub fn image_to_avatar_rgb_f32(image_data: &[u8]) -> Result<Vec<f32>, String> {
-    use resize::Type::Lanczos3;
-    use rgb::FromSlice;
-    use vsf::colour::convert::delinearize_gamma2_f32 as delinearize_gamma2;
+/// Container formats an avatar can be set from, by their leading bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SourceFormat {
+    Jpeg,
+    Png,
+    WebP,
+    Gif,
+    Tiff,
+    /// HEIF with an HEVC still — iPhone photos. Decoded thru libheif on desktop builds with the `heic` feature only.
+    Heic,
+}
+
+/// What the file is, from its magic bytes — so a format the pipeline can't read fails with its name instead of a decoder's "unknown format". HEIF brands are read from the `ftyp` box: `heic`/`heix`/`mif1`-style HEVC stills are HEIC, `avif`/`avis` are named and refused.
+pub fn sniff_format(data: &[u8]) -> Result<SourceFormat, String> {
+    const UNSUPPORTED: &str = "use JPEG, PNG, WebP, GIF, TIFF or HEIC";
+    match data {
+        [0xFF, 0xD8, 0xFF, ..] => return Ok(SourceFormat::Jpeg),
+        [0x89, b'P', b'N', b'G', ..] => return Ok(SourceFormat::Png),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => return Ok(SourceFormat::WebP),
+        [b'G', b'I', b'F', b'8', ..] => return Ok(SourceFormat::Gif),
+        [b'I', b'I', 0x2A, 0x00, ..] | [b'M', b'M', 0x00, 0x2A, ..] => return Ok(SourceFormat::Tiff),
+        [b'B', b'M', ..] => return Err(format!("Unsupported image format: BMP — {UNSUPPORTED}")),
+        _ => {}
+    }
+    if data.len() >= 12 && &data[4..8] == b"ftyp" {
+        // Major brand, then (after the minor version) the compatible brands, to the box's end.
+        let box_end = (u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as usize).clamp(12, data.len());
+        let brands = std::iter::once(&data[8..12]).chain(data[16.min(box_end)..box_end].chunks_exact(4));
+        let mut heif = false;
+        for brand in brands {
+            match brand {
+                b"avif" | b"avis" => return Err(format!("Unsupported image format: AVIF — {UNSUPPORTED}")),
+                b"heic" | b"heix" | b"heim" | b"heis" | b"hevc" | b"hevx" => return Ok(SourceFormat::Heic),
+                b"mif1" | b"msf1" => heif = true,
+                _ => {}
+            }
+        }
+        if heif {
+            return Ok(SourceFormat::Heic);
+        }
+    }
+    Err(format!("Unrecognised image format — {UNSUPPORTED}"))
+}
+
+/// HEIC → RGB via libheif. libheif applies the container's rotation / mirror boxes itself (HEIF's orientation lives there, not in EXIF), so the result is already upright.
+#[cfg(all(feature = "heic", not(any(target_os = "redox", target_os = "android"))))]
+fn decode_heic(image_data: &[u8]) -> Result<image::DynamicImage, String> {
+    use libheif_rs::{ColorSpace, HeifContext, LibHeif, RgbChroma};
+    let ctx = HeifContext::read_from_bytes(image_data).map_err(|e| format!("Failed to read HEIC: {}", e))?;
+    let handle = ctx.primary_image_handle().map_err(|e| format!("Failed to read HEIC: {}", e))?;
+    let decoded = LibHeif::new()
+        .decode(&handle, ColorSpace::Rgb(RgbChroma::Rgb), None)
+        .map_err(|e| format!("Failed to decode HEIC: {}", e))?;
+    let plane = decoded.planes().interleaved.ok_or("HEIC decode produced no RGB plane")?;
+    let (w, h) = (plane.width as usize, plane.height as usize);
+    let mut rgb = Vec::with_capacity(w * h * 3);
+    for row in plane.data.chunks(plane.stride).take(h) {
+        rgb.extend_from_slice(&row[..w * 3]);
+    }
+    image::RgbImage::from_raw(w as u32, h as u32, rgb)
+        .map(image::DynamicImage::ImageRgb8)
+        .ok_or_else(|| "HEIC plane is shorter than its dimensions".to_string())
+}
 
-    let size = AVATAR_SIZE;
+/// No libheif linked (Android, Redox, or a desktop build without the `heic` feature): a HEIC file is refused by name.
+#[cfg(any(not(feature = "heic"), target_os = "redox", target_os = "android"))]
+fn decode_heic(_image_data: &[u8]) -> Result<image::DynamicImage, String> {
+    Err("HEIC isn't supported in this build — export the photo as JPEG".to_string())
+}
 
-    // Detect format and extract ICC profile
-    let icc_profile_bytes = extract_icc_profile(image_data)?;
+/// The ICC profile in a HEIC's `colr` box (`prof` / `rICC`); `None` for an nclx-tagged or untagged file.
+#[cfg(all(feature = "heic", not(any(target_os = "redox", target_os = "android"))))]
+fn extract_heic_icc(image_data: &[u8]) -> Option<Vec<u8>> {
+    let ctx = libheif_rs::HeifContext::read_from_bytes(image_data).ok()?;
+    ctx.primary_image_handle().ok()?.color_profile_raw().map(|p| p.data)
+}
 
-    // Parse ICC profile once (if present)
-    let icc_converter = if let Some(ref profile) = icc_profile_bytes {
-        Some(parse_icc_converter(profile)?)
-    } else {
-        None
-    };
+#[cfg(any(not(feature = "heic"), target_os = "redox", target_os = "android"))]
+fn extract_heic_icc(_image_data: &[u8]) -> Option<Vec<u8>> {
+    None
+}
 
-    // Decode image to RGB Note: image crate has default memory limits (~512MB decoded). This is fine - avatars are 256x256 output, huge sources should be resized first.
+/// Decode an image file upright: EXIF orientation applied, so the pixels are the ones the user sees — what the crop screen previews and what [`Region`]s index. Errors name an unsupported format (see [`sniff_format`]).
+pub fn decode_oriented(image_data: &[u8]) -> Result<image::DynamicImage, String> {
+    if sniff_format(image_data)? == SourceFormat::Heic {
+        return decode_heic(image_data);
+    }
     // Decode via ImageReader (not load_from_memory) so the EXIF orientation is readable — a phone photo carries rotate-after-decode flags, and ignoring them rendered those avatars sideways.
     use image::ImageDecoder;
     let mut decoder = image::ImageReader::new(std::io::Cursor::new(image_data))
@@ -106,20 +365,53 @@ pub fn image_to_avatar_rgb_f32(image_data: &[u8]) -> Result<Vec<f32>, String> {
     let mut img = image::DynamicImage::from_decoder(decoder)
         .map_err(|e| format!("Failed to decode image: {}", e))?;
     img.apply_orientation(orientation);
+    Ok(img)
+}
+
+/// The FAST half of avatar-set: decode + EXIF/ICC handling + crop (the framed `crop`, else centred) + Lanczos resize + circular mask + γ2 — everything except the AV1 encode. Milliseconds; safe on the UI thread for the instant-display path.
+pub fn image_to_avatar_rgb_f32(image_data: &[u8], crop: Option<Region>) -> Result<Vec<f32>, String> {
+    // Detect format and extract ICC profile
+    let icc_profile_bytes = extract_icc_profile(image_data)?;
+
+    // Parse ICC profile once (if present). One this parser can't read (LUT-based, CMYK, truncated) takes the sRGB fallback like an untagged file instead of refusing the image.
+    let icc_converter = icc_profile_bytes.as_deref().and_then(|profile| {
+        parse_icc_converter(profile)
+            .map_err(|e| crate::logf!("Avatar: ICC profile unusable, treating as sRGB: {}", e))
+            .ok()
+    });
+
+    // Decode image to RGB Note: image crate has default memory limits (~512MB decoded). This is fine - avatars are 256x256 output, huge sources should be resized first.
+    let img = decode_oriented(image_data)?;
+
+    prepare_avatar_rgb_f32(&img, icc_converter.as_ref(), crop)
+}
+
+/// Decoded image → prepared avatar pixels: crop (the framed square, else centred), colour-convert to linear VSF RGB (thru the ICC profile when the file carried one, else sRGB), Lanczos resize, circular mask, γ2. Shared by the still path and every frame of an animated one.
+fn prepare_avatar_rgb_f32(
+    img: &image::DynamicImage,
+    icc_converter: Option<&IccColourConverter>,
+    crop: Option<Region>,
+) -> Result<Vec<f32>, String> {
+    use resize::Type::Lanczos3;
+    use rgb::FromSlice;
+    use vsf::colour::convert::delinearize_gamma2_f32 as delinearize_gamma2;
+
+    let size = AVATAR_SIZE;
 
     let orig_width = img.width() as usize;
     let orig_height = img.height() as usize;
 
-    // Center-crop to square before color conversion
-    let crop_size = orig_width.min(orig_height);
-    let crop_x = (orig_width - crop_size) / 2;
-    let crop_y = (orig_height - crop_size) / 2;
+    // Crop to the framed square (or the centred one) before color conversion
+    let Region { x: crop_x, y: crop_y, side: crop_size } = match crop {
+        Some(region) => region.clamped(orig_width, orig_height),
+        None => Region::centred(orig_width, orig_height),
+    };
 
     // Convert cropped region to linear VSF RGB (f32) Handle both 8-bit and 16-bit source images
     let mut linear_vsf_cropped = vec![0.0f32; crop_size * crop_size * 3];
 
     use image::DynamicImage;
-    match &img {
+    match img {
         DynamicImage::ImageRgb16(_) | DynamicImage::ImageRgba16(_) => {
             // 16-bit image: convert with /65536 normalization
             let rgb16_img = img.to_rgb16();
@@ -132,20 +424,10 @@ pub fn image_to_avatar_rgb_f32(image_data: &[u8]) -> Result<Vec<f32>, String> {
                     let g = rgb_pixels[src_idx + 1];
                     let b = rgb_pixels[src_idx + 2];
 
-                    let linear_vsf = if let Some(ref converter) = icc_converter {
+                    let linear_vsf = if let Some(converter) = icc_converter {
                         convert_pixel_linear_u16(r, g, b, converter)
                     } else {
-                        // No ICC profile - assume sRGB (legacy but needed for compatibility)
-                        #[allow(deprecated)]
-                        {
-                            use vsf::colour::convert::apply_matrix_3x3_f32;
-                            use vsf::colour::legacy::convert::linearize_srgb;
-                            use vsf::colour::SRGB2VSF_RGB;
-                            let r_lin = linearize_srgb(r as f32 / 65536.);
-                            let g_lin = linearize_srgb(g as f32 / 65536.);
-                            let b_lin = linearize_srgb(b as f32 / 65536.);
-                            apply_matrix_3x3_f32(&SRGB2VSF_RGB, &[r_lin, g_lin, b_lin])
-                        }
+                        srgb_fallback_linear([r as f32 / 65536., g as f32 / 65536., b as f32 / 65536.])
                     };
 
                     linear_vsf_cropped[dst_idx] = linear_vsf[0];
@@ -166,20 +448,10 @@ pub fn image_to_avatar_rgb_f32(image_data: &[u8]) -> Result<Vec<f32>, String> {
                     let g = rgb_pixels[src_idx + 1];
                     let b = rgb_pixels[src_idx + 2];
 
-                    let linear_vsf = if let Some(ref converter) = icc_converter {
+                    let linear_vsf = if let Some(converter) = icc_converter {
                         convert_pixel_linear(r, g, b, converter)
                     } else {
-                        // No ICC profile - assume sRGB (legacy but needed for compatibility)
-                        #[allow(deprecated)]
-                        {
-                            use vsf::colour::convert::apply_matrix_3x3_f32;
-                            use vsf::colour::legacy::convert::linearize_srgb_u8;
-                            use vsf::colour::SRGB2VSF_RGB;
-                            let r_lin = linearize_srgb_u8(r);
-                            let g_lin = linearize_srgb_u8(g);
-                            let b_lin = linearize_srgb_u8(b);
-                            apply_matrix_3x3_f32(&SRGB2VSF_RGB, &[r_lin, g_lin, b_lin])
-                        }
+                        srgb_fallback_linear([r as f32 / 255., g as f32 / 255., b as f32 / 255.])
                     };
 
                     linear_vsf_cropped[dst_idx] = linear_vsf[0];
@@ -274,11 +546,25 @@ fn extract_icc_profile(image_data: &[u8]) -> Result<Option<Vec<u8>>, String> {
         }
     }
 
+    // Try WebP - the extended format's ICCP chunk
+    if let Ok(webp) = WebP::from_bytes(image_data.to_vec().into()) {
+        if let Some(icc) = webp.icc_profile() {
+            return Ok(Some(icc.to_vec()));
+        }
+    }
+
     // Try TIFF - ICC profile is in tag 34675 (InterColorProfile)
     if let Some(icc) = extract_tiff_icc(image_data) {
         return Ok(Some(icc));
     }
 
+    // Try HEIC - the colr box, via libheif (desktop)
+    if sniff_format(image_data) == Ok(SourceFormat::Heic) {
+        if let Some(icc) = extract_heic_icc(image_data) {
+            return Ok(Some(icc));
+        }
+    }
+
     // No ICC profile found - will assume sRGB
     Ok(None)
 }
@@ -533,6 +819,16 @@ fn apply_trc_normalized(normalized: f32, trc: &TrcCurve) -> f32 {
     }
 }
 
+/// The colour space of an image with no ICC profile (or one the parser can't read): sRGB, IEC 61966-2-1 — what browsers, screenshots and untagged phone exports all mean. Normalised code values → linear by the piecewise sRGB curve (linear toe below 0.04045, 2.4 power above), then the sRGB → VSF RGB primaries matrix, negatives clamped like the profile path. The same for 8- and 16-bit sources; only the normalisation differs.
+fn srgb_fallback_linear(rgb: [f32; 3]) -> [f32; 3] {
+    use vsf::colour::convert::apply_matrix_3x3_f32;
+    use vsf::colour::SRGB2VSF_RGB;
+
+    let linear = rgb.map(|c| if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) });
+    let vsf = apply_matrix_3x3_f32(&SRGB2VSF_RGB, &linear);
+    [vsf[0].max(0.), vsf[1].max(0.), vsf[2].max(0.)]
+}
+
 /// Fast per-pixel conversion from ICC RGB (u8) to linear VSF RGB
 #[inline]
 fn convert_pixel_linear(r: u8, g: u8, b: u8, converter: &IccColourConverter) -> [f32; 3] {
@@ -982,11 +1278,37 @@ pub fn load_avatar_from_bytes_from_seed(
     load_avatar_from_bytes_with_key(vsf_data, &derive_avatar_encryption_key_from_seed(identity_seed))
 }
 
-/// `load_avatar_from_bytes` with the pinned AES key (a contact under the pin-set).
+/// `load_avatar_from_bytes` with the pinned AES key (a contact under the pin-set). An animated avatar loads as its first frame — the still fallback every non-animating surface shows.
 pub fn load_avatar_from_bytes_with_key(
     vsf_data: &[u8],
     key: &[u8; 32],
 ) -> Option<(usize, Vec<u8>)> {
+    let mut anim = load_avatar_animation_from_bytes_with_key(vsf_data, key)?;
+    Some((anim.size, anim.frames.swap_remove(0)))
+}
+
+/// Every frame of this identity's avatar, for the surfaces that animate: the device's own clip slot when it's at least as new as the shared avatar slot, else the shared slot (a still comes back as a one-frame clip). A still adopted later — a sibling's set, a wall recovery — outdates the clip, so the clip never shadows a newer avatar. A poisoned entry is left for the still load path to evict.
+pub fn load_avatar_animation_from_seed(
+    identity_seed: &[u8; 32],
+    storage: &std::sync::Arc<crate::storage::FlatStorage>,
+) -> Option<AvatarAnimation> {
+    let key = derive_avatar_encryption_key_from_seed(identity_seed);
+    let still = storage.read_addr(&crate::storage::vault_key("avatar", identity_seed)).ok()??;
+    if let Ok(Some(clip)) = storage.read_addr(&crate::storage::vault_key(AVATAR_CLIP_DOMAIN, identity_seed)) {
+        if avatar_vsf_timestamp(&clip) >= avatar_vsf_timestamp(&still) {
+            if let Some(anim) = load_avatar_animation_from_bytes_with_key(&clip, &key) {
+                return Some(anim);
+            }
+        }
+    }
+    load_avatar_animation_from_bytes_with_key(&still, &key)
+}
+
+/// Verified parse → decrypt → decode of an avatar VSF into all its frames.
+fn load_avatar_animation_from_bytes_with_key(
+    vsf_data: &[u8],
+    key: &[u8; 32],
+) -> Option<AvatarAnimation> {
     // Verified parse: hp + (hb | signature) checked before any field is trusted.
     let encrypted = match avatar_encrypted_payload(vsf_data) {
         Ok(payload) => payload,
@@ -1005,21 +1327,14 @@ pub fn load_avatar_from_bytes_with_key(
         }
     };
 
-    // Decode AV1 to pixels (dimensions come from AV1 bitstream)
-    let (width, height, pixels) = match decode_avatar(&av1_data) {
-        Ok(result) => result,
+    // Decode AV1 to pixels (dimensions come from AV1 bitstream; non-square is refused)
+    match decode_avatar_animation(&av1_data) {
+        Ok(anim) => Some(anim),
         Err(e) => {
-            crate::logf!("Avatar: decode_avatar failed: {}", e);
-            return None;
+            crate::logf!("Avatar: decode failed: {}", e);
+            None
         }
-    };
-
-    // Avatar must be square
-    if width != height {
-        return None;
     }
-
-    Some((width, pixels))
 }
 
 /// Save avatar to disk as VSF by handle Uses "image" section with "pixels" field containing v'e'(encrypted v'a'(AV1)) Only people who know the handle plaintext can decrypt the avatar. Stored in avatars/ directory using handle-based storage key
@@ -1037,6 +1352,36 @@ pub fn save_avatar_from_seed(
     identity_seed: &[u8; 32],
     storage: &std::sync::Arc<crate::storage::FlatStorage>,
 ) -> Result<(), crate::storage::StorageError> {
+    let vsf_bytes = build_avatar_vsf(av1_data, identity_seed)?;
+    // Save to the vault at the identity seed's avatar address
+    save_avatar_to_cache_from_seed(identity_seed, &vsf_bytes, storage)
+}
+
+/// Vault domain of the device's own animated-avatar slot — see [`encode_animated_avatar`] for why the clip lives beside the shared still rather than in it.
+const AVATAR_CLIP_DOMAIN: &str = "avatar_anim";
+
+/// Save a packed clip ([`encode_animated_avatar`]) to this device's clip slot, sealed like the still. Save the still first: the clip only shows while it's at least as new as the shared slot.
+pub fn save_avatar_animation_from_seed(
+    packed: &[u8],
+    identity_seed: &[u8; 32],
+    storage: &std::sync::Arc<crate::storage::FlatStorage>,
+) -> Result<(), crate::storage::StorageError> {
+    let vsf_bytes = build_avatar_vsf(packed, identity_seed)?;
+    storage.write_addr(&crate::storage::vault_key(AVATAR_CLIP_DOMAIN, identity_seed), &vsf_bytes)?;
+    Ok(())
+}
+
+/// Drop this device's clip slot — a still was set, or the avatar removed.
+pub fn delete_avatar_animation_from_seed(
+    identity_seed: &[u8; 32],
+    storage: &std::sync::Arc<crate::storage::FlatStorage>,
+) -> Result<(), crate::storage::StorageError> {
+    storage.delete_addr(&crate::storage::vault_key(AVATAR_CLIP_DOMAIN, identity_seed))?;
+    Ok(())
+}
+
+/// The avatar VSF both vault slots hold: `image` section, `pixels` = v'e'(encrypted v'a'(payload)), stamped now.
+fn build_avatar_vsf(av1_data: &[u8], identity_seed: &[u8; 32]) -> Result<Vec<u8>, crate::storage::StorageError> {
     use vsf::{VsfBuilder, VsfType};
 
     // Encrypt AV1 data (wraps in v'a' then encrypts)
@@ -1044,17 +1389,14 @@ pub fn save_avatar_from_seed(
         .map_err(crate::storage::StorageError::Crypto)?;
 
     // Build VSF with v'e' wrapped encrypted payload. The default build carries hp + hb — a provenance-only doc is UNVERIFIABLE under read_verified and would be rejected on every load.
-    let vsf_bytes = VsfBuilder::new()
+    VsfBuilder::new()
         .creation_time_oscillations(vsf::eagle_time_oscillations())
         .add_section(
             "image",
             vec![("pixels".to_string(), VsfType::v(b'e', encrypted))],
         )
         .build()
-        .map_err(|e| crate::storage::StorageError::Crypto(format!("{}", e)))?;
-
-    // Save to the vault at the identity seed's avatar address
-    save_avatar_to_cache_from_seed(identity_seed, &vsf_bytes, storage)
+        .map_err(|e| crate::storage::StorageError::Crypto(format!("{}", e)))
 }
 
 const FGTW_URL: &str = "https://fgtw.org";
@@ -1125,9 +1467,6 @@ pub fn get_local_avatar_timestamp_from_seed(
     identity_seed: &[u8; 32],
     storage: &std::sync::Arc<crate::storage::FlatStorage>,
 ) -> Option<i64> {
-    use vsf::types::EagleTime;
-    use vsf::VsfType;
-
     let addr = crate::storage::vault_key("avatar", identity_seed);
 
     let vsf_data = match storage.read_addr(&addr) {
@@ -1144,15 +1483,20 @@ pub fn get_local_avatar_timestamp_from_seed(
         }
     };
 
-    // Verified header read to extract the creation timestamp. A timestamp we can't read (or that rides an unverifiable doc) is NOT a timestamp — return None, never a fabricated 0 (a zero would mean "created at the dawn of time" and would always lose the newer-wins comparison, silently clobbering a fine local avatar with the server's).
-    let (header, _) = vsf::verification::read_verified(&vsf_data, None).ok()?;
+    let ts = avatar_vsf_timestamp(&vsf_data);
+    #[cfg(feature = "development")]
+    crate::logf!("Avatar: Local timestamp = {}", format!("{:?}", ts));
+    ts
+}
+
+/// A stored avatar VSF's eagle-time creation stamp. Verified header read: a timestamp we can't read (or that rides an unverifiable doc) is NOT a timestamp — return None, never a fabricated 0 (a zero would mean "created at the dawn of time" and would always lose the newer-wins comparison, silently clobbering a fine local avatar with the server's).
+fn avatar_vsf_timestamp(vsf_data: &[u8]) -> Option<i64> {
+    use vsf::types::EagleTime;
+    use vsf::VsfType;
+
+    let (header, _) = vsf::verification::read_verified(vsf_data, None).ok()?;
     match header.creation_time {
-        Some(VsfType::e(et)) => {
-            let ts = EagleTime::new(et).oscillations();
-            #[cfg(feature = "development")]
-            crate::logf!("Avatar: Local timestamp = {}", format!("{:?}", ts));
-            ts
-        }
+        Some(VsfType::e(et)) => EagleTime::new(et).oscillations(),
         _ => None,
     }
 }
@@ -2035,3 +2379,229 @@ pub fn scale_avatar(src: &[u8], diameter: usize) -> Option<Vec<u8>> {
     resizer.resize(src_rgb, dst_rgb).ok()?;
     Some(dst)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clipboard_rgba_runs_the_avatar_decode_path() {
+        // A synthetic 16×8 clipboard image (red→blue ramp, opaque) — non-square, so the centre-crop runs too.
+        let (w, h) = (16usize, 8usize);
+        let mut rgba = Vec::with_capacity(w * h * 4);
+        for y in 0..h {
+            for x in 0..w {
+                rgba.extend_from_slice(&[(x * 16) as u8, (y * 32) as u8, 255 - (x * 16) as u8, 255]);
+            }
+        }
+        let png = rgba_to_png(w, h, &rgba).expect("png wrap");
+        let pixels = image_to_avatar_rgb_f32(&png, None).expect("avatar decode");
+        assert_eq!(pixels.len(), AVATAR_SIZE * AVATAR_SIZE * 3);
+        assert!(pixels.iter().all(|v| v.is_finite()));
+        // A framed square runs the same path.
+        let framed = image_to_avatar_rgb_f32(&png, Some(Region { x: 2, y: 1, side: 6 })).expect("framed decode");
+        assert_eq!(framed.len(), AVATAR_SIZE * AVATAR_SIZE * 3);
+        // Non-image / inconsistent clipboard payloads are refused before they reach the pipeline.
+        assert!(rgba_to_png(0, 8, &[]).is_err());
+        assert!(rgba_to_png(w, h, &rgba[..rgba.len() - 1]).is_err());
+    }
+
+    /// A `w × h` RGB ramp as lossless WebP.
+    fn webp_sample(w: u32, h: u32) -> Vec<u8> {
+        use image::ImageEncoder;
+        let rgb: Vec<u8> = (0..w * h).flat_map(|i| [(i % 251) as u8, (i * 7 % 253) as u8, 0x80]).collect();
+        let mut out = Vec::new();
+        image::codecs::webp::WebPEncoder::new_lossless(&mut out)
+            .write_image(&rgb, w, h, image::ExtendedColorType::Rgb8)
+            .expect("webp encode");
+        out
+    }
+
+    #[test]
+    fn webp_decodes_to_its_dimensions_and_keeps_its_icc() {
+        let webp = webp_sample(24, 10);
+        assert_eq!(sniff_format(&webp), Ok(SourceFormat::WebP));
+        let img = decode_oriented(&webp).expect("webp decode");
+        assert_eq!((img.width(), img.height()), (24, 10));
+        assert_eq!(image_to_avatar_rgb_f32(&webp, None).expect("avatar").len(), AVATAR_SIZE * AVATAR_SIZE * 3);
+
+        // An ICCP chunk comes back out for the colour conversion.
+        let mut tagged = WebP::from_bytes(webp.into()).expect("webp parse");
+        tagged.set_icc_profile(Some(b"not-a-real-profile".to_vec().into()));
+        let mut bytes = Vec::new();
+        tagged.encoder().write_to(&mut bytes).expect("webp write");
+        assert_eq!(extract_icc_profile(&bytes), Ok(Some(b"not-a-real-profile".to_vec())));
+    }
+
+    #[cfg(all(feature = "heic", not(any(target_os = "redox", target_os = "android"))))]
+    #[test]
+    fn heic_decodes_to_its_dimensions() {
+        // A checked-in 32×20 HEVC still (tests/fixtures/README.md): encoding one here would need libheif's x265 plugin, which many installs lack — decoding only needs libde265.
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/still-32x20.heic");
+        let heic = std::fs::read(path).unwrap_or_else(|e| panic!("HEIC fixture {}: {}", path, e));
+        let (w, h) = (32u32, 20u32);
+
+        assert_eq!(sniff_format(&heic), Ok(SourceFormat::Heic));
+        let img = decode_oriented(&heic).expect("heic decode");
+        assert_eq!((img.width(), img.height()), (w, h));
+        assert_eq!(image_to_avatar_rgb_f32(&heic, None).expect("avatar").len(), AVATAR_SIZE * AVATAR_SIZE * 3);
+    }
+
+    #[test]
+    fn exif_orientation_turns_every_tag_upright() {
+        // The picture as seen: 3 wide, 2 tall, every pixel distinct.
+        let (w, h) = (3usize, 2usize);
+        let upright = |x: usize, y: usize| [(x * 80) as u8, (y * 200) as u8, 0x40, 0xFF];
+        for tag in 1u16..=8 {
+            // How a camera stores that picture under each EXIF tag: 5–8 are sideways (h wide, w tall).
+            let (sw, sh) = if tag >= 5 { (h, w) } else { (w, h) };
+            let mut stored = Vec::with_capacity(sw * sh * 4);
+            for y in 0..sh {
+                for x in 0..sw {
+                    let (ux, uy) = match tag {
+                        1 => (x, y),
+                        2 => (w - 1 - x, y),
+                        3 => (w - 1 - x, h - 1 - y),
+                        4 => (x, h - 1 - y),
+                        5 => (y, x),
+                        6 => (w - 1 - y, x),
+                        7 => (w - 1 - y, h - 1 - x),
+                        _ => (y, h - 1 - x),
+                    };
+                    stored.extend_from_slice(&upright(ux, uy));
+                }
+            }
+            // A little-endian TIFF IFD carrying just Orientation (0x0112, SHORT).
+            let mut exif = b"II\x2a\x00\x08\x00\x00\x00\x01\x00\x12\x01\x03\x00\x01\x00\x00\x00".to_vec();
+            exif.extend_from_slice(&tag.to_le_bytes());
+            exif.extend_from_slice(&[0; 6]);
+            let mut png = Png::from_bytes(rgba_to_png(sw, sh, &stored).expect("png").into()).expect("png parse");
+            png.set_exif(Some(exif.into()));
+            let mut bytes = Vec::new();
+            png.encoder().write_to(&mut bytes).expect("png write");
+
+            let img = decode_oriented(&bytes).expect("decode").to_rgba8();
+            assert_eq!((img.width() as usize, img.height() as usize), (w, h), "tag {tag}");
+            for y in 0..h {
+                for x in 0..w {
+                    assert_eq!(img.get_pixel(x as u32, y as u32).0, upright(x, y), "tag {tag} at ({x}, {y})");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn untagged_image_is_read_as_srgb() {
+        use vsf::colour::convert::{apply_matrix_3x3_f32, delinearize_gamma2_f32};
+        // The curve: the linear toe, mid-grey at ~21 % light, white at full — thru the primaries matrix the fallback applies.
+        for (code, lin) in [(0.04f32, 0.04 / 12.92), (0.5, 0.21404), (1.0, 1.0)] {
+            let (got, want) = (srgb_fallback_linear([code; 3]), apply_matrix_3x3_f32(&vsf::colour::SRGB2VSF_RGB, &[lin; 3]));
+            for ch in 0..3 {
+                assert!((got[ch] - want[ch].max(0.)).abs() < 1e-4, "code {code} channel {ch}");
+            }
+        }
+
+        // A flat untagged PNG (no iCCP chunk) → every pixel inside the circle is that colour thru the sRGB fallback, γ2-encoded.
+        let (w, h) = (8usize, 8usize);
+        let colour = [200u8, 90, 30];
+        let rgba: Vec<u8> = (0..w * h).flat_map(|_| [colour[0], colour[1], colour[2], 0xFF]).collect();
+        let png = rgba_to_png(w, h, &rgba).expect("png");
+        assert_eq!(extract_icc_profile(&png), Ok(None));
+        let pixels = image_to_avatar_rgb_f32(&png, None).expect("avatar");
+        let linear = srgb_fallback_linear(colour.map(|c| c as f32 / 255.));
+        let centre = (AVATAR_SIZE / 2 * AVATAR_SIZE + AVATAR_SIZE / 2) * 3;
+        for ch in 0..3 {
+            let want = delinearize_gamma2_f32(linear[ch]);
+            assert!((pixels[centre + ch] - want).abs() < 1e-3, "channel {ch}: {} vs {want}", pixels[centre + ch]);
+        }
+
+        // A 16-bit untagged source of the same colour lands on the same values.
+        let wide = image::DynamicImage::ImageRgb16(image::ImageBuffer::from_fn(w as u32, h as u32, |_, _| image::Rgb(colour.map(|c| c as u16 * 257))));
+        let pixels16 = prepare_avatar_rgb_f32(&wide, None, None).expect("16-bit");
+        for ch in 0..3 {
+            assert!((pixels16[centre + ch] - pixels[centre + ch]).abs() < 1e-3);
+        }
+        // An unreadable profile is treated the same as none.
+        let mut tagged = Png::from_bytes(png.into()).expect("png parse");
+        tagged.set_icc_profile(Some(b"not-a-real-profile".to_vec().into()));
+        let mut bytes = Vec::new();
+        tagged.encoder().write_to(&mut bytes).expect("png write");
+        assert_eq!(image_to_avatar_rgb_f32(&bytes, None).expect("bad profile falls back"), pixels);
+    }
+
+    #[test]
+    fn unsupported_formats_are_named() {
+        let avif = [&[0, 0, 0, 0x1C][..], b"ftypavif", &[0, 0, 0, 0], b"avifmif1miaf"].concat();
+        assert!(decode_oriented(&avif).unwrap_err().contains("AVIF"));
+        assert!(decode_oriented(b"BM\x3a\0\0\0").unwrap_err().contains("BMP"));
+        assert!(decode_oriented(b"plain text").unwrap_err().starts_with("Unrecognised image format"));
+        // A HEIF that only declares the generic brand is still read as HEIC.
+        let heif = [&[0, 0, 0, 0x18][..], b"ftypmif1", &[0, 0, 0, 0], b"mif1miaf"].concat();
+        assert_eq!(sniff_format(&heif), Ok(SourceFormat::Heic));
+    }
+
+    #[test]
+    fn animated_avatar_frames_and_timing_round_trip() {
+        // Three flat-grey frames of rising brightness — distinct enough to survive the lossy encode in order.
+        let frames: Vec<Vec<f32>> = [0.2f32, 0.5, 0.8]
+            .iter()
+            .map(|&v| vec![v; AVATAR_SIZE * AVATAR_SIZE * 3])
+            .collect();
+        let durations = [40u32, 120, 500];
+        let payload = encode_animated_avatar(&frames, &durations).expect("encode");
+        let anim = decode_avatar_animation(&payload).expect("decode");
+        assert_eq!(anim.size, AVATAR_SIZE);
+        assert_eq!(anim.frames.len(), 3);
+        assert_eq!(anim.durations_ms, durations);
+        let centre = |px: &[u8]| px[(AVATAR_SIZE / 2 * AVATAR_SIZE + AVATAR_SIZE / 2) * 3 + 1];
+        assert!(centre(&anim.frames[0]) < centre(&anim.frames[1]));
+        assert!(centre(&anim.frames[1]) < centre(&anim.frames[2]));
+        // Timing: frame boundaries at 40 and 160ms, looping every 660ms.
+        assert_eq!(anim.frame_at(0), (0, Some(40)));
+        assert_eq!(anim.frame_at(40), (1, Some(120)));
+        assert_eq!(anim.frame_at(659), (2, Some(1)));
+        assert_eq!(anim.frame_at(660 + 45), (1, Some(115)));
+        // A bare AV1 still decodes as a one-frame clip that never advances.
+        let still = decode_avatar_animation(&encode_avatar_rgb_f32(&frames[0]).expect("still")).expect("decode still");
+        assert_eq!(still.frames.len(), 1);
+        assert_eq!(still.frame_at(10_000), (0, None));
+        assert!(encode_animated_avatar(&frames, &durations[..2]).is_err());
+    }
+
+    #[test]
+    fn clip_stays_beside_the_shared_still_until_a_newer_still_lands() {
+        let seed = *ihi::handle_to_hash("me-avatar-clip-test").as_bytes();
+        let device = [49u8; 32];
+        let remove = || {
+            if let Ok(ring) = kete::vault_ring_paths(crate::storage::APP, &seed, &device) {
+                for p in &ring {
+                    let _ = std::fs::remove_file(p);
+                }
+            }
+        };
+        remove();
+        let storage = std::sync::Arc::new(crate::storage::FlatStorage::new(crate::storage::APP, seed, device).unwrap());
+        let frames: Vec<Vec<f32>> = [0.2f32, 0.8].iter().map(|&v| vec![v; AVATAR_SIZE * AVATAR_SIZE * 3]).collect();
+
+        // A set: the still, then the clip.
+        save_avatar_from_seed(&encode_avatar_rgb_f32(&frames[0]).unwrap(), &seed, &storage).unwrap();
+        save_avatar_animation_from_seed(&encode_animated_avatar(&frames, &[100, 100]).unwrap(), &seed, &storage).unwrap();
+        assert_eq!(load_avatar_animation_from_seed(&seed, &storage).unwrap().frames.len(), 2);
+        // What the upload reads is still a bare AV1 picture — the format a build without animation decodes.
+        let shared = storage.read_addr(&crate::storage::vault_key("avatar", &seed)).unwrap().unwrap();
+        let av1 = extract_av1_data_from_seed(&shared, &seed).unwrap();
+        assert_eq!(decode_avatar(&av1).unwrap().0, AVATAR_SIZE);
+
+        // A sibling's newer still is adopted into the shared slot: it outranks the old clip.
+        save_avatar_from_seed(&encode_avatar_rgb_f32(&frames[1]).unwrap(), &seed, &storage).unwrap();
+        assert_eq!(load_avatar_animation_from_seed(&seed, &storage).unwrap().frames.len(), 1);
+
+        // A shared slot written by a build that stored the packed clip there still reads, as every frame.
+        save_avatar_from_seed(&encode_animated_avatar(&frames, &[100, 100]).unwrap(), &seed, &storage).unwrap();
+        delete_avatar_animation_from_seed(&seed, &storage).unwrap();
+        assert_eq!(load_avatar_animation_from_seed(&seed, &storage).unwrap().frames.len(), 2);
+
+        drop(storage);
+        remove();
+    }
+}
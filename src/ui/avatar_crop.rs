@@ -0,0 +1,140 @@
+//! Avatar framing before it's set — the maths half. A picked, dropped or pasted image no longer goes straight to the encoder: the crop screen shows it fitted in a square box with a circle over it. Dragging moves the circle, the wheel (or the −/+ pills on touch) grows and shrinks it, and Use hands the circle's bounding [`Region`] of the source to the avatar pipeline — which cuts exactly that square instead of the old centre crop.
+//!
+//! [`Circle`] and [`Region`] are in source pixels (after EXIF orientation, the same pixels the encoder sees); [`View`] maps between them and the screen.
+
+/// Smallest circle allowed, as a fraction of the image's short edge — tighter than this and the 256² avatar is mostly upscale blur.
+pub const MIN_FRACTION: f32 = 1.0 / 8.0;
+
+/// Zoom step per wheel notch / −+ tap: the circle's radius shrinks (zoom in) or grows (zoom out) by this factor.
+pub const ZOOM_STEP: f32 = 1.125;
+
+/// The square of source pixels an avatar is cut from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Region {
+    pub x: usize,
+    pub y: usize,
+    pub side: usize,
+}
+
+impl Region {
+    /// The largest centred square — what every avatar got before framing, and still what an unframed set uses.
+    pub fn centred(w: usize, h: usize) -> Self {
+        let side = w.min(h);
+        Self { x: (w - side) / 2, y: (h - side) / 2, side }
+    }
+
+    /// This region pulled inside a `w × h` image, at least one pixel — so a region framed on one decode stays valid against another (an animation's frames).
+    pub fn clamped(self, w: usize, h: usize) -> Self {
+        let side = self.side.clamp(1, w.min(h).max(1));
+        Self { x: self.x.min(w.saturating_sub(side)), y: self.y.min(h.saturating_sub(side)), side }
+    }
+}
+
+/// The circle being framed, in source pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Circle {
+    pub cx: f32,
+    pub cy: f32,
+    pub r: f32,
+}
+
+impl Circle {
+    /// The largest centred circle — the starting frame, identical to the old centre crop.
+    pub fn fit(w: usize, h: usize) -> Self {
+        Self { cx: w as f32 * 0.5, cy: h as f32 * 0.5, r: w.min(h) as f32 * 0.5 }
+    }
+
+    /// Scale the radius by `factor` about the centre (> 1 takes in more of the picture), kept inside the image.
+    pub fn zoom(self, factor: f32, w: usize, h: usize) -> Self {
+        Self { r: self.r * factor, ..self }.clamped(w, h)
+    }
+
+    /// Move the centre by `(dx, dy)` source pixels, kept inside the image.
+    pub fn pan(self, dx: f32, dy: f32, w: usize, h: usize) -> Self {
+        Self { cx: self.cx + dx, cy: self.cy + dy, ..self }.clamped(w, h)
+    }
+
+    /// Radius within [`MIN_FRACTION`] of the short edge and half of it; the whole circle on the image.
+    fn clamped(self, w: usize, h: usize) -> Self {
+        let short = w.min(h) as f32;
+        let r = self.r.clamp(short * MIN_FRACTION, short * 0.5);
+        Self { cx: self.cx.clamp(r, w as f32 - r), cy: self.cy.clamp(r, h as f32 - r), r }
+    }
+
+    /// The source square the circle sits in, rounded to whole pixels and inside the image — what the encoder crops to.
+    pub fn region(self, w: usize, h: usize) -> Region {
+        let side = (self.r * 2.0).round() as usize;
+        let x = (self.cx - self.r).round().max(0.0) as usize;
+        let y = (self.cy - self.r).round().max(0.0) as usize;
+        Region { x, y, side }.clamped(w, h)
+    }
+}
+
+/// The preview on screen: the source drawn at `scale` screen pixels per source pixel with its top-left at `origin`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct View {
+    pub scale: f32,
+    pub origin: (f32, f32),
+}
+
+impl View {
+    /// A `w × h` image fitted, centred, into the square screen box with top-left `(x, y)` and edge `side`.
+    pub fn fit(w: usize, h: usize, (x, y): (f32, f32), side: f32) -> Self {
+        let scale = side / w.max(h).max(1) as f32;
+        Self { scale, origin: (x + (side - w as f32 * scale) * 0.5, y + (side - h as f32 * scale) * 0.5) }
+    }
+
+    /// A source point on screen.
+    pub fn to_screen(&self, (sx, sy): (f32, f32)) -> (f32, f32) {
+        (self.origin.0 + sx * self.scale, self.origin.1 + sy * self.scale)
+    }
+
+    /// A screen point in source pixels.
+    pub fn to_source(&self, (x, y): (f32, f32)) -> (f32, f32) {
+        ((x - self.origin.0) / self.scale, (y - self.origin.1) / self.scale)
+    }
+
+    /// A circle drawn on screen, centre `(x, y)` radius `r`, as the same circle in source pixels.
+    pub fn circle_to_source(&self, (x, y): (f32, f32), r: f32) -> Circle {
+        let (cx, cy) = self.to_source((x, y));
+        Circle { cx, cy, r: r / self.scale }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn screen_circle_maps_to_source_pixels() {
+        // A 400×200 photo fitted into a 200 px box at (100, 100): half scale, letterboxed 50 px top and bottom.
+        let (w, h) = (400, 200);
+        let view = View::fit(w, h, (100.0, 100.0), 200.0);
+        assert_eq!(view, View { scale: 0.5, origin: (100.0, 150.0) });
+
+        // A 25 px circle drawn at (250, 200) covers source pixels (250..350, 50..150).
+        let circle = view.circle_to_source((250.0, 200.0), 25.0);
+        assert_eq!(circle, Circle { cx: 300.0, cy: 100.0, r: 50.0 });
+        assert_eq!(circle.region(w, h), Region { x: 250, y: 50, side: 100 });
+        assert_eq!(view.to_screen((circle.cx, circle.cy)), (250.0, 200.0));
+
+        // The starting frame is the old centre crop.
+        assert_eq!(Circle::fit(w, h).region(w, h), Region::centred(w, h));
+        assert_eq!(Region::centred(w, h), Region { x: 100, y: 0, side: 200 });
+    }
+
+    #[test]
+    fn circle_stays_on_the_image() {
+        let (w, h) = (400, 200);
+        let c = Circle::fit(w, h);
+        // Zooming out stops at the short edge; zooming in stops at MIN_FRACTION of it.
+        assert_eq!(c.zoom(4.0, w, h).r, 100.0);
+        assert_eq!(c.zoom(0.01, w, h).r, 200.0 * MIN_FRACTION);
+        // Panning past the right edge pins the circle against it.
+        let small = c.zoom(0.5, w, h).pan(1000.0, -1000.0, w, h);
+        assert_eq!((small.cx, small.cy), (350.0, 50.0));
+        assert_eq!(small.region(w, h), Region { x: 300, y: 0, side: 100 });
+        // A region framed on a bigger decode is pulled inside a smaller one.
+        assert_eq!(Region { x: 350, y: 150, side: 300 }.clamped(w, h), Region { x: 200, y: 0, side: 200 });
+    }
+}
@@ -37,6 +37,51 @@ pub fn update_avatar_scaled(src: &[u8], src_size: usize, dst_diameter: usize) ->
     dst
 }
 
+/// How many scaled sizes one contact keeps: list row, conversation header, contact panel, plus a spare so a zoom step doesn't evict a live size.
+pub const AVATAR_SCALED_SLOTS: usize = 1 << 2;
+
+/// Per-contact LRU of Mitchell-resampled avatars keyed by diameter. A contact is drawn at several sizes (list row, conversation header, About page), and a single-slot cache re-resampled every time the screen swapped between them; scaled avatars are small (a row avatar is a few KB), so a handful per contact is cheap.
+#[derive(Clone, Debug, Default)]
+pub struct ScaledAvatarCache {
+    /// `(diameter, diameter² × 3 RGB)`, most recently used first.
+    entries: Vec<(usize, Vec<u8>)>,
+    /// Resamples run since creation — one per miss.
+    resamples: usize,
+}
+
+impl ScaledAvatarCache {
+    /// The avatar at `diameter`: a hit moves it to the front; a miss resamples `src` (`src_size² × 3`) and evicts the least recently used size past `AVATAR_SCALED_SLOTS`.
+    pub fn get_or_scale(&mut self, src: &[u8], src_size: usize, diameter: usize) -> &[u8] {
+        match self.entries.iter().position(|(d, _)| *d == diameter) {
+            Some(i) => {
+                let hit = self.entries.remove(i);
+                self.entries.insert(0, hit);
+            }
+            None => {
+                self.resamples += 1;
+                self.entries.insert(0, (diameter, update_avatar_scaled(src, src_size, diameter)));
+                self.entries.truncate(AVATAR_SCALED_SLOTS);
+            }
+        }
+        &self.entries[0].1
+    }
+
+    /// The cached avatar at `diameter`, recency untouched — the draw path, after `get_or_scale` ran ahead of the immutable borrow.
+    pub fn get(&self, diameter: usize) -> Option<&[u8]> {
+        self.entries.iter().find(|(d, _)| *d == diameter).map(|(_, px)| px.as_slice())
+    }
+
+    /// Drop every size — the source pixels changed.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Resamples run so far (cache misses).
+    pub fn resamples(&self) -> usize {
+        self.resamples
+    }
+}
+
 /// Paint a circular avatar at `(cx, cy)` with fractional `radius`, sampling from a `scaled_diameter × scaled_diameter` BT.2020 γ=2.0 RGB texture. AA edge over the outer half-pixel; composes via `under()` so the caller can paint avatars on top of an existing partial composite. `clip` restricts painting to a sub-rect (e.g. a scrolling list's visible region); `None` = whole buffer.
 pub fn draw_avatar(
     canvas: &mut Canvas,
@@ -118,3 +163,81 @@ pub fn draw_avatar(
         }
     }
 }
+
+/// The default UNSET avatar: a deterministic per-identity gradient (seeded from the public proof), spherical-shaded like the dev orb, as a `diam×diam×3` visible-RGB buffer for `draw_avatar`. Replaces the flat grey placeholder — everyone without a set avatar shows a distinct little lit orb keyed to their identity, identical on every device that knows the proof. Each channel is its own plane `z = a·x + b·y` (`a,b ∈ [-1,1]`, raw clamped z, no normalization) × the dome `√(1−x_c²−y_c²)`.
+pub fn gradient_avatar_rgb(mut seed: u64, diam: usize) -> Vec<u8> {
+    fn splitmix(state: &mut u64) -> u64 {
+        *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = *state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+    let unit = |s: &mut u64| (splitmix(s) >> 11) as f64 / (1u64 << 53) as f64; // [0,1)
+    // Slopes CUBED: still [-1,1] (extremes still reach the full [-4pi,4pi] → 4 periods) but concentrated near zero, so most channels are gentle low-frequency gradients — not a busy 4x plaid.
+    let (r_a, r_b) = ((unit(&mut seed) * 2.0 - 1.0).powi(3), (unit(&mut seed) * 2.0 - 1.0).powi(3));
+    let (g_a, g_b) = ((unit(&mut seed) * 2.0 - 1.0).powi(3), (unit(&mut seed) * 2.0 - 1.0).powi(3));
+    let (b_a, b_b) = ((unit(&mut seed) * 2.0 - 1.0).powi(3), (unit(&mut seed) * 2.0 - 1.0).powi(3));
+    // Each channel is a sine plane wave: disk mapped to [-4pi, 4pi] per axis, z = a*x + b*y, then (sin(z)+1)/2 -> [0,1] (bounded, no clip); circle vignette over the top.
+    let denom = diam.saturating_sub(1).max(1) as f64;
+    let s = 4.0 * std::f64::consts::PI;
+    let mut out = vec![0u8; diam * diam * 3];
+    for py in 0..diam {
+        for px in 0..diam {
+            let xc = 2.0 * (px as f64 / denom) - 1.0;
+            let yc = 2.0 * (py as f64 / denom) - 1.0;
+            let (x, y) = (xc * s, yc * s);
+            let vignette = (1.0 - xc * xc - yc * yc).max(0.0).sqrt();
+            let ch = |a: f64, b: f64| ((((a * x + b * y).sin() + 1.0) / 2.0) * vignette * 255.0) as u8;
+            let i = (py * diam + px) * 3;
+            out[i] = ch(r_a, r_b);
+            out[i + 1] = ch(g_a, g_b);
+            out[i + 2] = ch(b_a, b_b);
+        }
+    }
+    out
+}
+
+/// Seed a gradient from a 32-byte public proof (`handle_proof`) — deterministic per identity. Shown while a contact's avatar is unset OR still downloading, and swapped for the real pixels the frame they land.
+pub fn proof_gradient_seed(proof: &[u8; 32]) -> u64 {
+    u64::from_le_bytes(proof[..8].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholder_is_stable_per_identity_and_distinct_across_identities() {
+        let alice = [0x11u8; 32];
+        let mut bob = alice;
+        bob[0] ^= 1;
+        let a1 = gradient_avatar_rgb(proof_gradient_seed(&alice), 48);
+        let a2 = gradient_avatar_rgb(proof_gradient_seed(&alice), 48);
+        assert_eq!(a1.len(), 48 * 48 * 3);
+        assert_eq!(a1, a2);
+        assert_ne!(a1, gradient_avatar_rgb(proof_gradient_seed(&bob), 48));
+    }
+
+    #[test]
+    fn scaled_cache_reuses_a_diameter_and_resamples_a_new_one_once() {
+        let src = vec![0x80u8; 32 * 32 * 3];
+        let mut cache = ScaledAvatarCache::default();
+        assert_eq!(cache.get_or_scale(&src, 32, 12).len(), 12 * 12 * 3);
+        cache.get_or_scale(&src, 32, 12);
+        assert_eq!(cache.resamples(), 1);
+        cache.get_or_scale(&src, 32, 20);
+        cache.get_or_scale(&src, 32, 20);
+        cache.get_or_scale(&src, 32, 12);
+        assert_eq!(cache.resamples(), 2);
+        assert!(cache.get(20).is_some());
+        // Past the slot count the least recently used size goes; new pixels drop everything.
+        for d in [4, 5, 6] {
+            cache.get_or_scale(&src, 32, d);
+        }
+        assert!(cache.get(20).is_none());
+        assert!(cache.get(12).is_some());
+        cache.clear();
+        assert!(cache.get(12).is_none());
+    }
+}
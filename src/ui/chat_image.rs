@@ -0,0 +1,254 @@
+//! Image messages — a dropped file → a bounded JPEG that rides the friendship chain as the `message` field's `v'j'` value (the caption slot carries [`crate::types::IMAGE_PLACEHOLDER`] so an older client still shows a row).
+//!
+//! The wire copy is re-encoded, never the original file: EXIF orientation is baked in and the metadata (GPS, camera serial) dropped, and the long edge is held to [`MAX_EDGE`] so a phone photo shards into a few hundred PT DATA packets instead of thousands.
+//!
+//! A drop that isn't an image goes out as a [`crate::types::Attachment`] instead ([`prepare_drop`]).
+//!
+//! In the conversation an image row is a thumbnail, not a line of text: [`row_height`] is the one place its height is decided, so the scroll extent, the virtualized window and the drag-select bands all agree with what's drawn. Tapping the row expands it over the history.
+
+/// Largest source file we'll even read. A 50 MP phone photo is ~15 MB; past this it's not a photo a chat should carry.
+pub const MAX_DROP_BYTES: u64 = 1 << 25;
+
+/// Longest edge of the sent image, in pixels — plenty for a chat bubble and its click-to-expand view.
+pub const MAX_EDGE: u32 = 1 << 10;
+
+/// Largest encoded image a message carries (sent or accepted). The encoder steps quality then size down until it fits.
+pub const MAX_IMAGE_BYTES: usize = 1 << 19;
+
+/// JPEG qualities tried in order, per edge length, before the edge halves.
+const QUALITIES: [u8; 3] = [85, 70, 55];
+
+/// File bytes → the JPEG an image message carries. `Err` is a user-facing hint (not an image / unreadable / can't fit).
+pub fn prepare_chat_image(file_bytes: &[u8]) -> Result<Vec<u8>, String> {
+    use image::ImageDecoder;
+    if file_bytes.len() as u64 > MAX_DROP_BYTES {
+        return Err(format!("That file is too big to send — images up to {} MB.", MAX_DROP_BYTES >> 20));
+    }
+    let reader = image::ImageReader::new(std::io::Cursor::new(file_bytes))
+        .with_guessed_format()
+        .map_err(|_| "Only images can be dropped into a conversation.".to_string())?;
+    if reader.format().is_none() {
+        return Err("Only images can be dropped into a conversation.".to_string());
+    }
+    let mut decoder = reader.into_decoder().map_err(|e| format!("Couldn't read that image: {}", e))?;
+    let orientation = decoder.orientation().unwrap_or(image::metadata::Orientation::NoTransforms);
+    let mut img = image::DynamicImage::from_decoder(decoder).map_err(|e| format!("Couldn't read that image: {}", e))?;
+    img.apply_orientation(orientation);
+
+    let mut edge = MAX_EDGE;
+    // Floor at 1/8 of MAX_EDGE: a 128 px image that still can't fit in MAX_IMAGE_BYTES isn't a real photo.
+    while edge >= MAX_EDGE >> 3 {
+        let rgb = img.thumbnail(edge, edge).to_rgb8();
+        for q in QUALITIES {
+            let mut out = Vec::new();
+            image::codecs::jpeg::JpegEncoder::new_with_quality(&mut out, q)
+                .encode_image(&rgb)
+                .map_err(|e| format!("Couldn't encode that image: {}", e))?;
+            if out.len() <= MAX_IMAGE_BYTES {
+                return Ok(out);
+            }
+        }
+        edge >>= 1;
+    }
+    Err("That image won't compress small enough to send.".to_string())
+}
+
+/// What a dropped file becomes: an inline image when it reads as one, otherwise a named file attachment.
+#[derive(Debug)]
+pub enum Dropped {
+    Image(Vec<u8>),
+    File(crate::types::Attachment),
+}
+
+/// A dropped file's `name` + bytes → what the conversation sends. Anything the image reader recognises and can bound rides as an image; everything else — and an image too stubborn to shrink — goes as a file, so a drop is never refused just for its type.
+pub fn prepare_drop(name: &str, bytes: Vec<u8>) -> Result<Dropped, String> {
+    if image::guess_format(&bytes).is_ok() {
+        if let Ok(jpeg) = prepare_chat_image(&bytes) {
+            return Ok(Dropped::Image(jpeg));
+        }
+    }
+    crate::types::Attachment::new(name, bytes).map(Dropped::File)
+}
+
+/// Longest edge of an inline thumbnail, in layout units (`ReadyLayout::unit_height`) — about a third of a phone-width conversation.
+pub const THUMB_EDGE_UNITS: f32 = 8.0;
+
+/// Drawn size of an image with pixel size `dims` fitted inside a `max_edge`-square box, aspect kept. Never scaled past its own pixels: a small sticker stays crisp rather than blowing up to the box.
+pub fn fit(dims: (u32, u32), max_edge: f32) -> (f32, f32) {
+    let (w, h) = (dims.0.max(1) as f32, dims.1.max(1) as f32);
+    // min(1): upscaling only blurs; the box is a ceiling, not a target.
+    let s = (max_edge / w.max(h)).min(1.0);
+    (w * s, h * s)
+}
+
+/// Height of one history row: a text line is `line_h`; an image row is its thumbnail plus one line of breathing room (a quarter above, the rest below around the divider). `dims` is `None` for text rows and for image bytes that didn't decode — those fall back to showing the placeholder caption on one line.
+pub fn row_height(dims: Option<(u32, u32)>, line_h: f32, thumb_edge: f32) -> f32 {
+    match dims {
+        Some(d) => fit(d, thumb_edge).1 + line_h,
+        None => line_h,
+    }
+}
+
+/// Longest edge a received image may have and still be decoded. Our encoder never sends past [`MAX_EDGE`]; the slack admits a sender with a larger cap, and anything past it is refused from its header before a pixel is allocated — an image message is untrusted input, and one giant canvas would otherwise be an OOM.
+pub const MAX_DECODE_EDGE: u32 = MAX_EDGE << 2;
+
+/// Render state of one image message: its pixel size (a header read — all layout needs) and the pixels decoded at the size last drawn, so a still frame never re-decodes. The decode itself runs on a worker, never the render thread.
+#[derive(Debug, Default)]
+pub struct ChatThumb {
+    /// Pixel size of the carried JPEG; `None` when it won't decode or is past [`MAX_DECODE_EDGE`].
+    pub dims: Option<(u32, u32)>,
+    /// `(w, h, w·h·3 RGB)` at the last drawn size.
+    scaled: Option<(usize, usize, Vec<u8>)>,
+    /// The decode in flight: the size it was asked for, and where its pixels land (`None` = it wouldn't decode).
+    pending: Option<((usize, usize), std::sync::mpsc::Receiver<Option<Vec<u8>>>)>,
+}
+
+impl ChatThumb {
+    /// Read the size out of the JPEG header without decoding the pixels.
+    pub fn probe(jpeg: &[u8]) -> Self {
+        let dims = image::ImageReader::new(std::io::Cursor::new(jpeg))
+            .with_guessed_format()
+            .ok()
+            .and_then(|r| r.into_dimensions().ok())
+            .filter(|&(w, h)| w.max(h) <= MAX_DECODE_EDGE);
+        Self { dims, ..Self::default() }
+    }
+
+    /// The image as `w × h` RGB, once a worker has decoded + resampled it at that size. The first ask at a size starts the decode and returns `None`; `wake` runs on the worker when the pixels are ready, so the caller can redraw. One decode runs at a time per image. An image that fails to decode drops its `dims`, falling back to the placeholder row.
+    pub fn pixels(&mut self, jpeg: &[u8], w: usize, h: usize, wake: impl FnOnce() + Send + 'static) -> Option<&[u8]> {
+        use std::sync::mpsc::TryRecvError;
+        if w == 0 || h == 0 || self.dims.is_none() {
+            return None;
+        }
+        if let Some((size, rx)) = &self.pending {
+            match rx.try_recv() {
+                Ok(Some(px)) => {
+                    self.scaled = Some((size.0, size.1, px));
+                    self.pending = None;
+                }
+                Ok(None) => {
+                    self.dims = None;
+                    self.pending = None;
+                    return None;
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => self.pending = None,
+            }
+        }
+        let ready = matches!(&self.scaled, Some((sw, sh, _)) if *sw == w && *sh == h);
+        if !ready && self.pending.is_none() {
+            let (tx, rx) = std::sync::mpsc::channel();
+            let jpeg = jpeg.to_vec();
+            std::thread::spawn(move || {
+                let _ = tx.send(decode_scaled(&jpeg, w, h));
+                wake();
+            });
+            self.pending = Some(((w, h), rx));
+        }
+        match &self.scaled {
+            Some((sw, sh, px)) if *sw == w && *sh == h => Some(px.as_slice()),
+            _ => None,
+        }
+    }
+}
+
+/// Decode `jpeg` under [`MAX_DECODE_EDGE`] limits — enforced by the decoder too, so a header that lied to [`ChatThumb::probe`] still can't allocate past them — and resample to `w × h` RGB.
+fn decode_scaled(jpeg: &[u8], w: usize, h: usize) -> Option<Vec<u8>> {
+    let mut reader = image::ImageReader::new(std::io::Cursor::new(jpeg)).with_guessed_format().ok()?;
+    let mut limits = image::Limits::default();
+    limits.max_image_width = Some(MAX_DECODE_EDGE);
+    limits.max_image_height = Some(MAX_DECODE_EDGE);
+    limits.max_alloc = Some(MAX_DECODE_EDGE as u64 * MAX_DECODE_EDGE as u64 * 4);
+    reader.limits(limits);
+    let img = reader.decode().ok()?;
+    Some(img.resize_exact(w as u32, h as u32, image::imageops::FilterType::CatmullRom).to_rgb8().into_raw())
+}
+
+/// Paint a `w × h` RGB image with its top-left at `(x, y)`, opaque, composed thru `under()` like every other photon rasterizer (see `avatar_render::draw_avatar`).
+pub fn draw_image(canvas: &mut fluor::canvas::Canvas, x: f32, y: f32, rgb: &[u8], w: usize, h: usize, clip: Option<fluor::paint::Clip>) {
+    use fluor::pixel::{Blend, BlendMode};
+    let (width, height) = (canvas.width, canvas.height);
+    if w == 0 || h == 0 || width == 0 || height == 0 || rgb.len() < w * h * 3 {
+        return;
+    }
+    let (x0, y0) = (x.round() as i32, y.round() as i32);
+    let Some((x_start, y_start, x_end, y_end)) =
+        fluor::paint::Clip::intersect_bbox(clip, width, height, x0, x0 + w as i32, y0, y0 + h as i32)
+    else {
+        return;
+    };
+    canvas.damage.add_bounds(x_start, y_start, x_end, y_end);
+    let pixels: &mut [u32] = canvas.pixels;
+    for py in y_start..y_end {
+        let tex_row = (py as i32 - y0) as usize * w;
+        for px in x_start..x_end {
+            let i = (tex_row + (px as i32 - x0) as usize) * 3;
+            let visible = (rgb[i] as u32) << 16 | (rgb[i + 1] as u32) << 8 | rgb[i + 2] as u32;
+            let dark = fluor::theme::dark(fluor::theme::fmt(visible)) & 0x00FFFFFF;
+            let idx = py * width + px;
+            pixels[idx] = pixels[idx].under(0xFF00_0000 | dark, BlendMode::Normal);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(w: u32, h: u32) -> Vec<u8> {
+        let img = image::RgbImage::from_fn(w, h, |x, y| image::Rgb([(x % 256) as u8, (y % 256) as u8, ((x ^ y) % 256) as u8]));
+        let mut out = Vec::new();
+        img.write_to(&mut std::io::Cursor::new(&mut out), image::ImageFormat::Png).unwrap();
+        out
+    }
+
+    #[test]
+    fn big_image_is_bounded_and_non_image_rejected() {
+        let jpeg = prepare_chat_image(&png(3000, 1500)).unwrap();
+        assert!(jpeg.len() <= MAX_IMAGE_BYTES);
+        let back = image::load_from_memory(&jpeg).unwrap();
+        assert_eq!((back.width(), back.height()), (MAX_EDGE, MAX_EDGE / 2));
+
+        assert!(prepare_chat_image(b"just some text, not pixels").is_err());
+        assert!(prepare_chat_image(&vec![0u8; MAX_DROP_BYTES as usize + 1]).is_err());
+
+        // Dropped: pixels go as an image, anything else as a named file.
+        assert!(matches!(prepare_drop("a.png", png(64, 64)), Ok(Dropped::Image(_))));
+        match prepare_drop("notes.txt", b"just text".to_vec()) {
+            Ok(Dropped::File(f)) => assert_eq!((f.name.as_str(), f.bytes.as_slice()), ("notes.txt", &b"just text"[..])),
+            other => panic!("expected a file, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn image_row_height_follows_the_fitted_thumbnail() {
+        let (line_h, edge) = (20.0, 160.0);
+        assert_eq!(row_height(None, line_h, edge), line_h);
+        // Landscape 1024×512 → 160×80 in the box, plus a line.
+        assert_eq!(fit((1024, 512), edge), (160.0, 80.0));
+        assert_eq!(row_height(Some((1024, 512)), line_h, edge), 100.0);
+        // Portrait is bounded by height; a small image is never upscaled.
+        assert_eq!(fit((320, 640), edge), (80.0, 160.0));
+        assert_eq!(row_height(Some((40, 30)), line_h, edge), 50.0);
+
+        // The header probe agrees with the encoder, and decoding — on a worker — lands at the asked-for size.
+        let jpeg = prepare_chat_image(&png(2048, 1024)).unwrap();
+        let mut thumb = ChatThumb::probe(&jpeg);
+        assert_eq!(thumb.dims, Some((MAX_EDGE, MAX_EDGE / 2)));
+        let (woke, wait) = std::sync::mpsc::channel();
+        assert!(thumb.pixels(&jpeg, 160, 80, move || woke.send(()).unwrap()).is_none(), "the first ask only starts the decode");
+        wait.recv().unwrap();
+        assert_eq!(thumb.pixels(&jpeg, 160, 80, || {}).map(|p| p.len()), Some(160 * 80 * 3));
+        assert_eq!(ChatThumb::probe(b"not a jpeg").dims, None);
+    }
+
+    #[test]
+    fn oversized_images_never_decode() {
+        // Past the edge cap: refused from the header, no decode ever started.
+        let wide = png(MAX_DECODE_EDGE + 1, 1);
+        let mut thumb = ChatThumb::probe(&wide);
+        assert_eq!(thumb.dims, None);
+        assert!(thumb.pixels(&wide, 10, 1, || panic!("no decode for a refused image")).is_none());
+        assert!(decode_scaled(&wide, 10, 1).is_none(), "the decoder enforces the same cap");
+    }
+}
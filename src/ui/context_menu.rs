@@ -0,0 +1,126 @@
+//! Per-message context menu on the conversation screen: right-click (desktop) or long-press (Android) on a history row opens a short list of the actions that row actually supports. The maths half — which message is under the pointer ([`open_at`], against the same [`RowGeom`]s drag-select resolves against), which actions it gets ([`actions_for`]), and where the items land ([`item_rects`]). PhotonApp paints the items, hit-tests a pick against the same rects (geometric, like the history rows — no hit ids) and routes it to the existing copy / open / retry paths.
+
+use crate::types::ChatMessage;
+use crate::ui::message_select::RowGeom;
+
+/// How long a touch must hold still on a history row before the menu opens (Android; a mouse has a right button). The platform's own long-press timeout.
+pub const LONG_PRESS: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Most items a menu paints — [`actions_for`] never offers more than three, so this only bounds a future action list.
+pub const MAX_ITEMS: usize = 1 << 3;
+
+/// One menu entry. Each maps onto a path the history already had: Copy = the drag-select copy for one whole message, View/Save/Play = `open_history_row`, Retry = `retry_failed_message`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageAction {
+    Copy,
+    View,
+    Save,
+    Play,
+    Retry,
+}
+
+impl MessageAction {
+    pub fn label(self) -> &'static str {
+        match self {
+            MessageAction::Copy => "Copy text",
+            MessageAction::View => "View image",
+            MessageAction::Save => "Save to Downloads",
+            MessageAction::Play => "Play",
+            MessageAction::Retry => "Retry send",
+        }
+    }
+}
+
+/// The actions `msg` supports, in menu order. A failed outgoing row offers Retry instead of opening its payload (open_history_row refuses it too — the tap there IS retry).
+pub fn actions_for(msg: &ChatMessage) -> Vec<MessageAction> {
+    let mut out = Vec::new();
+    if !msg.content.is_empty() {
+        out.push(MessageAction::Copy);
+    }
+    let failed = msg.is_outgoing && msg.failed;
+    if !failed {
+        if msg.image.is_some() {
+            out.push(MessageAction::View);
+        } else if msg.attachment.is_some() {
+            out.push(MessageAction::Save);
+        } else if msg.voice.is_some() {
+            out.push(MessageAction::Play);
+        }
+    }
+    if failed {
+        out.push(MessageAction::Retry);
+    }
+    out
+}
+
+/// An open menu: the conversation + visible message it acts on, the point it was opened at, and its items.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContextMenu {
+    /// Contact index of the conversation — a menu never acts on another conversation.
+    pub contact: usize,
+    /// Index in the VISIBLE (probe-filtered) list, the same index `RowGeom::msg` carries.
+    pub msg: usize,
+    pub x: f32,
+    pub y: f32,
+    pub actions: Vec<MessageAction>,
+}
+
+/// Hit-test a right-click / long-press at `(x, y)` against this frame's `rows` (`messages` = the visible list, oldest first). `None` when the point is on no row (a divider gap, above or below the history) or the message has nothing to offer.
+pub fn open_at(rows: &[RowGeom], messages: &[&ChatMessage], contact: usize, x: f32, y: f32) -> Option<ContextMenu> {
+    let row = rows.iter().find(|r| y >= r.top && y < r.bottom)?;
+    let actions = actions_for(messages.get(row.msg)?);
+    if actions.is_empty() {
+        return None;
+    }
+    Some(ContextMenu { contact, msg: row.msg, x, y, actions })
+}
+
+/// Item rectangles `(x0, y0, x1, y1)`, stacked downward from the open point and flipped left / up when they'd run off the `buf_w × buf_h` viewport, so a menu opened at the screen edge stays whole.
+pub fn item_rects(menu: &ContextMenu, item_w: f32, item_h: f32, buf_w: f32, buf_h: f32) -> Vec<(f32, f32, f32, f32)> {
+    let n = menu.actions.len().min(MAX_ITEMS);
+    let total_h = item_h * n as f32;
+    let x0 = if menu.x + item_w > buf_w { menu.x - item_w } else { menu.x };
+    let y0 = if menu.y + total_h > buf_h { menu.y - total_h } else { menu.y };
+    // Still off-screen after the flip (a viewport smaller than the menu) → pin to the top-left edge rather than paint at negative coordinates.
+    let (x0, y0) = (x0.max(0.0), y0.max(0.0));
+    (0..n).map(|i| (x0, y0 + item_h * i as f32, x0 + item_w, y0 + item_h * (i + 1) as f32)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn right_click_maps_to_the_row_under_it_and_its_actions() {
+        let text = ChatMessage::new_with_timestamp("hello".to_string(), false, 1);
+        let mut failed = ChatMessage::new_with_timestamp("did not go".to_string(), true, 2);
+        failed.failed = true;
+        let silent = ChatMessage::new_with_timestamp(String::new(), true, 3);
+        let messages = [&text, &failed, &silent];
+        let edges: Vec<f32> = (0..=5).map(|i| (i * 10) as f32).collect();
+        let rows = [
+            RowGeom { msg: 0, top: 0.0, bottom: 20.0, edges: edges.clone() },
+            RowGeom { msg: 1, top: 24.0, bottom: 44.0, edges: edges.clone() },
+            RowGeom { msg: 2, top: 48.0, bottom: 68.0, edges },
+        ];
+
+        let menu = open_at(&rows, &messages, 3, 15.0, 30.0).expect("menu");
+        assert_eq!((menu.contact, menu.msg), (3, 1));
+        assert_eq!(menu.actions, vec![MessageAction::Copy, MessageAction::Retry]);
+        assert_eq!(open_at(&rows, &messages, 3, 15.0, 5.0).unwrap().actions, vec![MessageAction::Copy]);
+
+        // The divider gap, past the history, or a row with nothing to offer → no menu.
+        assert_eq!(open_at(&rows, &messages, 3, 15.0, 22.0), None);
+        assert_eq!(open_at(&rows, &messages, 3, 15.0, 90.0), None);
+        assert_eq!(open_at(&rows, &messages, 3, 15.0, 50.0), None);
+    }
+
+    #[test]
+    fn menu_flips_to_stay_on_screen() {
+        let menu = ContextMenu { contact: 0, msg: 0, x: 390.0, y: 290.0, actions: vec![MessageAction::Copy, MessageAction::Retry] };
+        let rects = item_rects(&menu, 100.0, 20.0, 400.0, 300.0);
+        assert_eq!(rects, vec![(290.0, 250.0, 390.0, 270.0), (290.0, 270.0, 390.0, 290.0)]);
+        let roomy = ContextMenu { x: 10.0, y: 10.0, ..menu };
+        assert_eq!(item_rects(&roomy, 100.0, 20.0, 400.0, 300.0)[0], (10.0, 10.0, 110.0, 30.0));
+    }
+}
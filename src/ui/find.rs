@@ -0,0 +1,136 @@
+//! In-conversation find (Ctrl+F) — the maths half. [`matches`] scans the decrypted history for the query and returns each hit as a selection span over the visible list, so render paints hits with the same band a drag-selection uses ([`crate::ui::message_select::row_span`]). [`Find`] holds the hits and the one being looked at: a new query lands on the newest hit — the history is read from the bottom, so that's the one nearest the view — Enter walks back in time, Shift+Enter forward again, wrapping at either end.
+
+use crate::ui::message_select::TextPos;
+
+/// Every case-insensitive occurrence of `query` in `messages` (the visible list, oldest first) as `(start, end)` caret spans, oldest first and left to right within a message. Hits don't overlap; an empty query finds nothing.
+pub fn matches(messages: &[&str], query: &str) -> Vec<(TextPos, TextPos)> {
+    let q: Vec<char> = query.chars().collect();
+    if q.is_empty() {
+        return Vec::new();
+    }
+    let mut out = Vec::new();
+    for (mi, text) in messages.iter().enumerate() {
+        let chars: Vec<char> = text.chars().collect();
+        let mut i = 0;
+        while i + q.len() <= chars.len() {
+            if chars[i..i + q.len()].iter().zip(&q).all(|(a, b)| a.to_lowercase().eq(b.to_lowercase())) {
+                out.push(((mi, i), (mi, i + q.len())));
+                i += q.len();
+            } else {
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Scroll offset (the history's `message_scroll_offset`: 0 = newest row at the bottom) that centres a row in a band `view_h` tall. `lift` and `height` are the row's entries from [`crate::ui::message_select::row_lifts`] and the height list; the caller clamps to the scroll extent.
+pub fn reveal_offset(lift: f32, height: f32, view_h: f32) -> f32 {
+    (lift + height * 0.5 - view_h * 0.5).max(0.0)
+}
+
+/// The open find bar's state: the query it last ran, its hits and the current one.
+#[derive(Debug, Default)]
+pub struct Find {
+    query: String,
+    /// Visible-message count the hits were taken over — a message landing while the bar is open re-runs the search.
+    of: usize,
+    hits: Vec<(TextPos, TextPos)>,
+    current: Option<usize>,
+}
+
+impl Find {
+    /// Re-run against the history and the typed `query` when either changed. A new query jumps to the newest hit; a changed history keeps the hit being looked at when it's still there. Returns true on a new query — the caller scrolls to the current hit.
+    pub fn update(&mut self, messages: &[&str], query: &str) -> bool {
+        let new_query = query != self.query;
+        if !new_query && messages.len() == self.of {
+            return false;
+        }
+        let keep = if new_query { None } else { self.current() };
+        self.query = query.to_string();
+        self.of = messages.len();
+        self.hits = matches(messages, query);
+        self.current = keep
+            .and_then(|hit| self.hits.iter().position(|&h| h == hit))
+            .or(self.hits.len().checked_sub(1));
+        new_query
+    }
+
+    /// Enter (`older` true) steps to the previous hit up the history, Shift+Enter to the next one down. Wraps at either end.
+    pub fn step(&mut self, older: bool) {
+        let n = self.hits.len();
+        if let Some(c) = self.current.as_mut() {
+            *c = if older { (*c + n - 1) % n } else { (*c + 1) % n };
+        }
+    }
+
+    /// The hit being looked at.
+    pub fn current(&self) -> Option<(TextPos, TextPos)> {
+        self.current.and_then(|c| self.hits.get(c).copied())
+    }
+
+    /// Every hit, oldest first.
+    pub fn hits(&self) -> &[(TextPos, TextPos)] {
+        &self.hits
+    }
+
+    /// The bar's count: "2 of 5", numbered from the newest hit the way Enter walks them. Empty while nothing is typed.
+    pub fn label(&self) -> String {
+        match self.current {
+            _ if self.query.is_empty() => String::new(),
+            Some(c) => format!("{} of {}", self.hits.len() - c, self.hits.len()),
+            None => "no matches".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_hits_and_walks_them_newest_first() {
+        let history = ["Lunch tomorrow?", "sure, lunch at noon", "nothing here", "LUNCH lunch!", "ok"];
+        assert_eq!(
+            matches(&history, "lunch"),
+            vec![((0, 0), (0, 5)), ((1, 6), (1, 11)), ((3, 0), (3, 5)), ((3, 6), (3, 11))]
+        );
+        assert!(matches(&history, "").is_empty());
+        // Non-overlapping: "aa" in "aaa" is one hit.
+        assert_eq!(matches(&["aaa"], "aa"), vec![((0, 0), (0, 2))]);
+
+        let mut find = Find::default();
+        assert!(find.update(&history, "lunch"));
+        assert_eq!(find.current(), Some(((3, 6), (3, 11))));
+        assert_eq!(find.label(), "1 of 4");
+        // Enter goes back up the history, wrapping from the oldest to the newest; Shift+Enter comes back down.
+        let mut order = Vec::new();
+        for _ in 0..4 {
+            find.step(true);
+            order.push(find.current().unwrap().0);
+        }
+        assert_eq!(order, vec![(3, 0), (1, 6), (0, 0), (3, 6)]);
+        find.step(false);
+        assert_eq!(find.current(), Some(((0, 0), (0, 5))));
+        assert_eq!(find.label(), "4 of 4");
+
+        // A new message keeps the hit being looked at; the same query doesn't re-jump.
+        let longer = ["Lunch tomorrow?", "sure, lunch at noon", "nothing here", "LUNCH lunch!", "ok", "lunch was good"];
+        assert!(!find.update(&longer, "lunch"));
+        assert_eq!(find.current(), Some(((0, 0), (0, 5))));
+        assert_eq!(find.hits().len(), 5);
+
+        assert!(find.update(&longer, "dinner"));
+        assert_eq!((find.current(), find.label()), (None, "no matches".to_string()));
+        find.step(true);
+        assert_eq!(find.current(), None);
+    }
+
+    #[test]
+    fn reveal_centres_the_row() {
+        // A row whose middle sits 300 px above the newest row's bottom, in a 200 px band: scroll 200 brings it to the middle.
+        assert_eq!(reveal_offset(290.0, 20.0, 200.0), 200.0);
+        // The newest rows are already on screen — never a negative offset.
+        assert_eq!(reveal_offset(0.0, 20.0, 200.0), 0.0);
+    }
+}
@@ -0,0 +1,94 @@
+//! Frame-timing readout for the perf overlay (Ctrl+Shift+F) — the maths half. The host only renders when something is damaged, so these are the numbers of frames that actually happened: FPS and mean frame time over a short window of render-to-render intervals, and how many ticks (event-loop wakes) turned into a redraw.
+
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// Render intervals kept for the rolling average — about a second at 60 Hz, short enough that the readout follows a scroll or an animation starting and stopping.
+const WINDOW: usize = 1 << 6;
+
+/// An interval longer than this is an idle gap (nothing was damaged), not a slow frame — it would read as "2 fps" after every pause, so it's left out of the average.
+const IDLE_GAP_MS: f32 = 1000.;
+
+#[derive(Debug, Default)]
+pub struct FrameStats {
+    intervals: VecDeque<f32>,
+    last_frame: Option<Instant>,
+    ticks: u64,
+    redraws: u64,
+}
+
+impl FrameStats {
+    /// A render happened at `now`.
+    pub fn record_frame(&mut self, now: Instant) {
+        if let Some(last) = self.last_frame.replace(now) {
+            self.record_interval(now.duration_since(last).as_secs_f32() * 1000.);
+        }
+        self.redraws += 1;
+    }
+
+    /// One render-to-render interval in milliseconds (idle gaps dropped).
+    pub fn record_interval(&mut self, ms: f32) {
+        if !(0. ..IDLE_GAP_MS).contains(&ms) {
+            return;
+        }
+        if self.intervals.len() == WINDOW {
+            self.intervals.pop_front();
+        }
+        self.intervals.push_back(ms);
+    }
+
+    /// One event-loop tick.
+    pub fn record_tick(&mut self) {
+        self.ticks += 1;
+    }
+
+    /// Mean frame time over the window, `None` until two frames have landed.
+    pub fn avg_frame_ms(&self) -> Option<f32> {
+        (!self.intervals.is_empty()).then(|| self.intervals.iter().sum::<f32>() / self.intervals.len() as f32)
+    }
+
+    /// Frames per second implied by the mean frame time.
+    pub fn fps(&self) -> Option<f32> {
+        self.avg_frame_ms().filter(|ms| *ms > 0.).map(|ms| 1000. / ms)
+    }
+
+    /// The overlay's one line: `"60 fps · 16.7 ms · 12/40 redraws"`.
+    pub fn readout(&self) -> String {
+        let timing = match (self.fps(), self.avg_frame_ms()) {
+            (Some(fps), Some(ms)) => format!("{:.0} fps \u{b7} {:.1} ms", fps, ms),
+            _ => "\u{2013} fps".to_string(),
+        };
+        format!("{} \u{b7} {}/{} redraws", timing, self.redraws, self.ticks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_time_average_rolls_over_its_window_and_skips_idle_gaps() {
+        let mut s = FrameStats::default();
+        assert_eq!(s.avg_frame_ms(), None);
+        assert_eq!(s.readout(), "\u{2013} fps \u{b7} 0/0 redraws");
+
+        s.record_interval(10.);
+        s.record_interval(20.);
+        assert_eq!(s.avg_frame_ms(), Some(15.));
+        // A pause with nothing damaged isn't a frame time.
+        s.record_interval(5000.);
+        assert_eq!(s.avg_frame_ms(), Some(15.));
+
+        // Fill the window with 20 ms frames: the early 10/20 roll out.
+        for _ in 0..WINDOW {
+            s.record_interval(20.);
+        }
+        assert_eq!(s.avg_frame_ms(), Some(20.));
+        assert_eq!(s.fps(), Some(50.));
+
+        s.record_tick();
+        s.record_tick();
+        s.redraws = 1;
+        assert_eq!(s.readout(), "50 fps \u{b7} 20.0 ms \u{b7} 1/2 redraws");
+    }
+}
@@ -0,0 +1,146 @@
+//! Inline formatting for message text — `*bold*`, `_italic_`, `` `code` `` — the maths half. The stored (and encrypted) plaintext keeps its markers; only the history row renders them away. [`runs`] cuts a message into styled runs over its raw chars, the markers as zero-width runs of their own, so caret stops and selection still index the raw text one-to-one.
+//!
+//! A marker opens only at the start of the text or after a non-alphanumeric char, with a non-space char after it, and closes only before the end or a non-alphanumeric char, with a non-space char before it — so `snake_case`, `2*3*4` and a lone `*` stay literal. Bold and italic nest; code is literal inside. An unclosed marker is plain text.
+
+/// How a run is drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Style {
+    pub bold: bool,
+    pub italic: bool,
+    pub mono: bool,
+}
+
+/// A stretch of raw chars `from..to` drawn one way. `marker` runs are the `*`/`_`/`` ` `` themselves: kept in the text, never drawn, zero width.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Run {
+    pub from: usize,
+    pub to: usize,
+    pub style: Style,
+    pub marker: bool,
+}
+
+fn is_marker(c: char) -> bool {
+    matches!(c, '*' | '_' | '`')
+}
+
+fn can_open(chars: &[char], i: usize) -> bool {
+    (i == 0 || !chars[i - 1].is_alphanumeric()) && chars.get(i + 1).is_some_and(|c| !c.is_whitespace() && *c != chars[i])
+}
+
+fn can_close(chars: &[char], i: usize, end: usize) -> bool {
+    !chars[i - 1].is_whitespace() && (i + 1 == end || !chars[i + 1].is_alphanumeric())
+}
+
+fn push(out: &mut Vec<Run>, from: usize, to: usize, style: Style, marker: bool) {
+    if from == to {
+        return;
+    }
+    match out.last_mut() {
+        Some(last) if last.to == from && last.style == style && !last.marker && !marker => last.to = to,
+        _ => out.push(Run { from, to, style, marker }),
+    }
+}
+
+fn parse(chars: &[char], from: usize, to: usize, style: Style, out: &mut Vec<Run>) {
+    let mut plain = from;
+    let mut i = from;
+    while i < to {
+        let c = chars[i];
+        if is_marker(c) && can_open(chars, i) {
+            let close = (i + 2..to).find(|&j| chars[j] == c && can_close(chars, j, to));
+            if let Some(j) = close {
+                push(out, plain, i, style, false);
+                push(out, i, i + 1, style, true);
+                let inner = match c {
+                    '*' => Style { bold: true, ..style },
+                    '_' => Style { italic: true, ..style },
+                    _ => Style { mono: true, ..style },
+                };
+                if c == '`' {
+                    push(out, i + 1, j, inner, false);
+                } else {
+                    parse(chars, i + 1, j, inner, out);
+                }
+                push(out, j, j + 1, style, true);
+                i = j + 1;
+                plain = i;
+                continue;
+            }
+        }
+        i += 1;
+    }
+    push(out, plain, to, style, false);
+}
+
+/// `chars` cut into runs covering every char once, in order. Unformatted text is a single plain run.
+pub fn runs(chars: &[char]) -> Vec<Run> {
+    let mut out = Vec::new();
+    parse(chars, 0, chars.len(), Style::default(), &mut out);
+    out
+}
+
+/// True when `runs` is one plain run — the row can draw the raw text as-is.
+pub fn is_plain(runs: &[Run]) -> bool {
+    runs.iter().all(|r| !r.marker && r.style == Style::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Each drawn run as (text, style letters) — markers dropped, as a row renders it.
+    fn drawn(s: &str) -> Vec<(String, String)> {
+        let chars: Vec<char> = s.chars().collect();
+        let rs = runs(&chars);
+        // Runs tile the raw text: markers stay in it.
+        assert_eq!(rs.first().map_or(0, |r| r.from), 0);
+        assert!(rs.windows(2).all(|w| w[0].to == w[1].from));
+        assert_eq!(rs.last().map_or(0, |r| r.to), chars.len());
+        rs.iter()
+            .filter(|r| !r.marker)
+            .map(|r| {
+                let mut tag = String::new();
+                for (on, l) in [(r.style.bold, 'b'), (r.style.italic, 'i'), (r.style.mono, 'm')] {
+                    if on {
+                        tag.push(l);
+                    }
+                }
+                (chars[r.from..r.to].iter().collect(), tag)
+            })
+            .collect()
+    }
+
+    fn run(text: &str, tag: &str) -> (String, String) {
+        (text.to_string(), tag.to_string())
+    }
+
+    #[test]
+    fn parses_mixed_markup() {
+        assert_eq!(
+            drawn("say *hi* to _all_ of `x_y*` and *_both_*!"),
+            vec![
+                run("say ", ""),
+                run("hi", "b"),
+                run(" to ", ""),
+                run("all", "i"),
+                run(" of ", ""),
+                run("x_y*", "m"),
+                run(" and ", ""),
+                run("both", "bi"),
+                run("!", ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn literal_markers_stay_plain() {
+        for s in ["snake_case_name", "2*3*4", "a * b * c", "*open only", "**", "_ x_", "`"] {
+            let chars: Vec<char> = s.chars().collect();
+            assert!(is_plain(&runs(&chars)), "{s}");
+        }
+        // The marker chars are their own zero-width runs at their raw indices.
+        let chars: Vec<char> = "*a*".chars().collect();
+        let rs = runs(&chars);
+        assert_eq!(rs.iter().filter(|r| r.marker).map(|r| r.from).collect::<Vec<_>>(), vec![0, 2]);
+    }
+}
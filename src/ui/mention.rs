@@ -0,0 +1,325 @@
+//! `@`-mention autocomplete for the compose box — the maths half. PhotonApp re-runs [`Picker::update`] after every edit of the draft; while a mention is being typed it paints the ranked contact names over the compose box, and picking one rewrites the `@query` span via [`complete`].
+//!
+//! Names are ours alone — the friend reading the message calls the same person something else — so a mention never travels as a name. Sending turns each `@name` of a known contact ([`references`]) into that contact's party id ([`encode`]); the message is stored and woven into the chain in that form on both sides, and each reader renders it back as `@` + their own name for the party ([`shown`]), which the history row makes tappable.
+//!
+//! A mention starts at an `@` at the top of the draft or right after whitespace (so `me@host` never triggers) and runs to the caret with no whitespace in between. Candidates are the names contacts render as (`Contact::display_name`) — photon keeps no handle text at rest, so the name IS what a mention completes to. Ranking is [`fuzzy_score`]: the query's chars must appear in order; a prefix beats a word-start hit beats a scattered one, and ties go to the shorter name.
+
+/// Rows the picker shows at most — past five the list covers the conversation it's meant to sit over.
+pub const MAX_SUGGESTIONS: usize = 5;
+
+/// The mention being typed: the `@`'s char index and what's been typed after it, up to the caret.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trigger {
+    pub at: usize,
+    pub query: String,
+}
+
+/// The mention under the caret, if the caret sits in one.
+pub fn trigger(chars: &[char], cursor: usize) -> Option<Trigger> {
+    let cursor = cursor.min(chars.len());
+    let mut at = cursor;
+    loop {
+        at = at.checked_sub(1)?;
+        match chars[at] {
+            '@' => break,
+            c if c.is_whitespace() => return None,
+            _ => {}
+        }
+    }
+    if at > 0 && !chars[at - 1].is_whitespace() {
+        return None;
+    }
+    Some(Trigger { at, query: chars[at + 1..cursor].iter().collect() })
+}
+
+/// How well `name` matches `query` (case-insensitive), `None` when the query's chars don't all appear in order. Every matched char scores 1, a match at a word start 4 more, one straight after the previous match 2 more, and a match at the very first char 8 more — greedy left to right, which is plenty for names a few words long.
+pub fn fuzzy_score(query: &str, name: &str) -> Option<u32> {
+    let name: Vec<char> = name.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut from = 0;
+    let mut last: Option<usize> = None;
+    for q in query.chars().flat_map(char::to_lowercase) {
+        let i = from + name[from..].iter().position(|&c| c == q)?;
+        score += 1;
+        if i == 0 {
+            score += 8;
+        }
+        if i == 0 || !name[i - 1].is_alphanumeric() {
+            score += 4;
+        }
+        if last.is_some_and(|l| l + 1 == i) {
+            score += 2;
+        }
+        last = Some(i);
+        from = i + 1;
+    }
+    Some(score)
+}
+
+/// The candidates matching `query`, best first, as their ids (PhotonApp passes contact indices). An empty query (just `@`) lists candidates in the order given. At most [`MAX_SUGGESTIONS`].
+pub fn suggest(query: &str, names: &[(usize, String)]) -> Vec<usize> {
+    let mut scored: Vec<(u32, &str, usize)> = names
+        .iter()
+        .filter_map(|(id, name)| fuzzy_score(query, name).map(|s| (s, name.as_str(), *id)))
+        .collect();
+    // Stable: equal scores and lengths keep the caller's order.
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.chars().count().cmp(&b.1.chars().count())));
+    scored.into_iter().take(MAX_SUGGESTIONS).map(|(_, _, id)| id).collect()
+}
+
+/// The draft with `trigger`'s `@query` span (from the `@` to `cursor`) replaced by `@name ` — the trailing space closes the mention so typing carries on — and the caret just after it.
+pub fn complete(chars: &[char], cursor: usize, trigger: &Trigger, name: &str) -> (Vec<char>, usize) {
+    let cursor = cursor.min(chars.len());
+    let mut out: Vec<char> = chars[..trigger.at].to_vec();
+    out.push('@');
+    out.extend(name.chars());
+    out.push(' ');
+    let caret = out.len();
+    // A space already after the caret would double up.
+    let rest = &chars[cursor..];
+    out.extend(rest.strip_prefix(&[' ']).unwrap_or(rest));
+    (out, caret)
+}
+
+/// The contact a `@` at char `at` names, with the char index just past the reference: the longest candidate name that follows the `@` (case-insensitive) and ends at a word boundary, so `@Nick Spiker` beats `@Nick`. The `@` must start a word, like a typed mention. `None` for an unknown name.
+pub fn resolve<T: Copy>(chars: &[char], at: usize, names: &[(T, String)]) -> Option<(T, usize)> {
+    if chars.get(at) != Some(&'@') || (at > 0 && !chars[at - 1].is_whitespace()) {
+        return None;
+    }
+    let rest = &chars[at + 1..];
+    names
+        .iter()
+        .filter_map(|(id, name)| {
+            let name: Vec<char> = name.chars().collect();
+            let hit = !name.is_empty()
+                && rest.len() >= name.len()
+                && rest.iter().zip(&name).all(|(a, b)| a.to_lowercase().eq(b.to_lowercase()))
+                && rest.get(name.len()).is_none_or(|c| !c.is_alphanumeric());
+            hit.then_some((*id, at + 1 + name.len()))
+        })
+        .max_by_key(|&(_, end)| end)
+}
+
+/// Every reference to a known contact in a message, as `(from, to, id)` char spans (`from` at the `@`), in order.
+pub fn references<T: Copy>(chars: &[char], names: &[(T, String)]) -> Vec<(usize, usize, T)> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if let Some((id, end)) = resolve(chars, i, names) {
+            out.push((i, end, id));
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+    out
+}
+
+/// Hex digits of the party id in a sent mention.
+const TOKEN_HEX: usize = 64;
+
+/// A mention as sent and stored: `@[` + the party id in hex + `]`.
+pub fn token(party: &[u8; 32]) -> String {
+    format!("@[{}]", hex::encode(party))
+}
+
+/// The draft as it goes out: every `@name` of a known contact, keyed here by party id, becomes that contact's [`token`]. Everything else is sent verbatim.
+pub fn encode(chars: &[char], names: &[([u8; 32], String)]) -> String {
+    let mut out = String::with_capacity(chars.len());
+    let mut last = 0;
+    for (from, to, party) in references(chars, names) {
+        out.extend(&chars[last..from]);
+        out.push_str(&token(&party));
+        last = to;
+    }
+    out.extend(&chars[last..]);
+    out
+}
+
+/// The party id of a [`token`] starting at char `at`, with the char index just past it.
+fn parse_token(chars: &[char], at: usize) -> Option<([u8; 32], usize)> {
+    let end = at + 2 + TOKEN_HEX;
+    if chars.get(at..at + 2) != Some(&['@', '['][..]) || chars.get(end) != Some(&']') {
+        return None;
+    }
+    let digits: String = chars[at + 2..end].iter().collect();
+    let party: [u8; 32] = hex::decode(digits).ok()?.try_into().ok()?;
+    Some((party, end + 1))
+}
+
+/// A stored message as the reader sees it: each [`token`] becomes `@` + `name_of`'s name for that party, with `(from, to, id)` char spans of those names in the shown text. A party `name_of` doesn't know shows as `@` + its id's first bytes, with no span. Text without a token is borrowed as is.
+pub fn shown<'a, T>(content: &'a str, name_of: impl Fn(&[u8; 32]) -> Option<(T, String)>) -> (std::borrow::Cow<'a, str>, Vec<(usize, usize, T)>) {
+    if !content.contains("@[") {
+        return (std::borrow::Cow::Borrowed(content), Vec::new());
+    }
+    let chars: Vec<char> = content.chars().collect();
+    let (mut out, mut spans) = (String::with_capacity(content.len()), Vec::new());
+    let (mut i, mut len) = (0, 0);
+    while i < chars.len() {
+        if let Some((party, end)) = parse_token(&chars, i) {
+            let name = match name_of(&party) {
+                Some((id, name)) => {
+                    let from = len;
+                    len += 1 + name.chars().count();
+                    spans.push((from, len, id));
+                    name
+                }
+                None => {
+                    len += 1 + 16;
+                    hex::encode(&party[..8])
+                }
+            };
+            out.push('@');
+            out.push_str(&name);
+            i = end;
+        } else {
+            out.push(chars[i]);
+            len += 1;
+            i += 1;
+        }
+    }
+    (std::borrow::Cow::Owned(out), spans)
+}
+
+/// The picker's live state: which mention is open, its ranked candidates and the highlighted row.
+#[derive(Debug, Default)]
+pub struct Picker {
+    pub trigger: Option<Trigger>,
+    pub matches: Vec<usize>,
+    pub selected: usize,
+    /// The `@` the user dismissed with Esc — that mention stays closed until the caret leaves it.
+    dismissed: Option<usize>,
+}
+
+impl Picker {
+    /// Re-derive from the draft and caret after an edit or caret move. Returns true if what the picker shows changed.
+    pub fn update(&mut self, chars: &[char], cursor: usize, names: &[(usize, String)]) -> bool {
+        let trigger = trigger(chars, cursor);
+        if trigger.as_ref().map(|t| t.at) != self.dismissed {
+            self.dismissed = None;
+        }
+        let trigger = trigger.filter(|t| Some(t.at) != self.dismissed);
+        let matches = trigger.as_ref().map(|t| suggest(&t.query, names)).unwrap_or_default();
+        if trigger == self.trigger && matches == self.matches {
+            return false;
+        }
+        // A new query re-ranks the list — the highlight goes back to the best match.
+        self.selected = 0;
+        self.trigger = trigger;
+        self.matches = matches;
+        true
+    }
+
+    /// Whether there are rows to show.
+    pub fn is_open(&self) -> bool {
+        self.trigger.is_some() && !self.matches.is_empty()
+    }
+
+    /// ↑/↓: move the highlight, wrapping at either end.
+    pub fn step(&mut self, down: bool) {
+        let n = self.matches.len();
+        if n == 0 {
+            return;
+        }
+        self.selected = if down { (self.selected + 1) % n } else { (self.selected + n - 1) % n };
+    }
+
+    /// The highlighted candidate's id.
+    pub fn chosen(&self) -> Option<usize> {
+        self.matches.get(self.selected).copied()
+    }
+
+    /// Esc: close the picker for this mention.
+    pub fn dismiss(&mut self) {
+        self.dismissed = self.trigger.take().map(|t| t.at);
+        self.matches.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(s: &str) -> Vec<char> {
+        s.chars().collect()
+    }
+
+    fn names() -> Vec<(usize, String)> {
+        ["Bob", "Dominic Reyes", "Nick Spiker", "Anita", "Nina"]
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (i, n.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn typing_at_ni_ranks_contacts_and_completes_at_the_caret() {
+        let draft = chars("thanks @ni see you");
+        let cursor = 10;
+        let t = trigger(&draft, cursor).unwrap();
+        assert_eq!(t, Trigger { at: 7, query: "ni".to_string() });
+        // Prefix hits first, then the mid-word hits — the shorter name winning each tie; "Bob" doesn't match at all.
+        assert_eq!(suggest(&t.query, &names()), vec![4, 2, 3, 1]);
+
+        let mut picker = Picker::default();
+        assert!(picker.update(&draft, cursor, &names()));
+        picker.step(true);
+        assert_eq!(picker.chosen(), Some(2));
+        let (out, caret) = complete(&draft, cursor, &t, "Nick Spiker");
+        assert_eq!(out.iter().collect::<String>(), "thanks @Nick Spiker see you");
+        assert_eq!(caret, 20);
+    }
+
+    #[test]
+    fn trigger_needs_a_word_start_and_no_whitespace() {
+        assert_eq!(trigger(&chars("@"), 1), Some(Trigger { at: 0, query: String::new() }));
+        assert_eq!(trigger(&chars("mail me@host"), 12), None);
+        assert_eq!(trigger(&chars("@nick hi"), 8), None);
+        // Caret inside the mention: the query is what's before it.
+        assert_eq!(trigger(&chars("@nick"), 3).map(|t| t.query), Some("ni".to_string()));
+    }
+
+    #[test]
+    fn message_references_resolve_to_their_contact() {
+        let t = chars("ask @nick spiker, not @Nickel or @nobody");
+        // The full two-word name wins over any shorter prefix; "@Nickel" isn't "@Nick" + more; "@nobody" names no contact.
+        let names = vec![(7, "Nick".to_string()), (2, "Nick Spiker".to_string()), (4, "Nina".to_string())];
+        assert_eq!(resolve(&t, 4, &names), Some((2, 16)));
+        assert_eq!(resolve(&t, 22, &names), None);
+        assert_eq!(resolve(&t, 33, &names), None);
+        assert_eq!(resolve(&t, 0, &names), None);
+        assert_eq!(references(&t, &names), vec![(4, 16, 2)]);
+        assert!(references(&chars("mail me@Nina"), &names).is_empty());
+    }
+
+    #[test]
+    fn mentions_travel_as_party_ids_and_render_in_the_readers_names() {
+        let (nick, nina) = ([0x11; 32], [0x22; 32]);
+        let ours = vec![(nick, "Nick".to_string()), (nina, "Nina".to_string())];
+        let sent = encode(&chars("hi @nick and @Nina, not me@Nina"), &ours);
+        assert_eq!(sent, format!("hi {} and {}, not me@Nina", token(&nick), token(&nina)));
+
+        // The reader knows Nick by another name and doesn't know Nina at all.
+        let (text, spans) = shown(&sent, |p| (*p == nick).then(|| (7usize, "Nicholas".to_string())));
+        assert_eq!(text, "hi @Nicholas and @2222222222222222, not me@Nina");
+        assert_eq!(spans, vec![(3, 12, 7)]);
+
+        // No token: borrowed untouched, and a malformed one stays text.
+        assert!(matches!(shown("plain @Nick", |_| None::<(usize, String)>).0, std::borrow::Cow::Borrowed(_)));
+        assert_eq!(shown("@[zz]", |_| None::<(usize, String)>).0, "@[zz]");
+    }
+
+    #[test]
+    fn dismissed_mention_stays_closed_until_left() {
+        let mut picker = Picker::default();
+        picker.update(&chars("@n"), 2, &names());
+        assert!(picker.is_open());
+        picker.dismiss();
+        picker.update(&chars("@ni"), 3, &names());
+        assert!(!picker.is_open());
+        // Leaving the mention re-arms it.
+        picker.update(&chars("@ni "), 4, &names());
+        picker.update(&chars("@ni @n"), 6, &names());
+        assert!(picker.is_open());
+    }
+}
@@ -0,0 +1,395 @@
+//! Drag-selection across the rendered conversation history (and the launch screen's one-line status/error text, via [`centered_row`]) — the maths half. Render publishes one [`RowGeom`] per on-screen message (its vertical band — one text line, or an image thumbnail's height — + the x of every caret stop along it); a drag from `anchor` to `head` maps thru [`selection_range`] to an ordered `(message, char)` span, and [`selected_text`] slices the copy out of the message contents.
+//!
+//! Message indices are positions in the VISIBLE (probe-filtered) list, oldest first — the same order the list lays out top to bottom, so "earlier in the list" and "higher on screen" agree and an upward drag orders exactly like a downward one.
+
+/// One laid-out message row, in viewport pixels.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RowGeom {
+    /// Index of the message in the visible list (oldest = 0).
+    pub msg: usize,
+    /// Top edge of the row's band (inclusive).
+    pub top: f32,
+    /// Bottom edge of the row's band (exclusive).
+    pub bottom: f32,
+    /// Absolute x of every caret stop: `edges[0]` is the left of the first char, `edges[i]` the boundary after char `i − 1`, so `edges.len()` = char count + 1.
+    pub edges: Vec<f32>,
+}
+
+/// A point in the history: message `msg`, caret stop `ch` (0 = before its first char).
+pub type TextPos = (usize, usize);
+
+/// Caret stop nearest `x` along one row — a click past a glyph's midpoint lands after it, same rule as the textbox.
+fn char_at(edges: &[f32], x: f32) -> usize {
+    edges
+        .windows(2)
+        .take_while(|w| (w[0] + w[1]) * 0.5 <= x)
+        .count()
+}
+
+/// Map a viewport point to a [`TextPos`]. `rows` must be ordered top to bottom (oldest first). Above every row pins to the start of the first; below every row pins to the end of the last; a point in the gap between two rows lands at the start of the lower one, so a drag that ends between messages selects whole lines without a dangling partial. `None` when nothing is laid out.
+pub fn pos_at(rows: &[RowGeom], x: f32, y: f32) -> Option<TextPos> {
+    let first = rows.first()?;
+    if y < first.top {
+        return Some((first.msg, 0));
+    }
+    for row in rows {
+        if y < row.top {
+            return Some((row.msg, 0));
+        }
+        if y < row.bottom {
+            return Some((row.msg, char_at(&row.edges, x)));
+        }
+    }
+    let last = rows.last()?;
+    Some((last.msg, last.edges.len().saturating_sub(1)))
+}
+
+/// Ordered `(start, end)` span selected by a drag from `anchor` to `head` (either direction). `None` when nothing is laid out or the drag covers no characters (a plain click).
+pub fn selection_range(rows: &[RowGeom], anchor: (f32, f32), head: (f32, f32)) -> Option<(TextPos, TextPos)> {
+    let a = pos_at(rows, anchor.0, anchor.1)?;
+    let b = pos_at(rows, head.0, head.1)?;
+    let (start, end) = if a <= b { (a, b) } else { (b, a) };
+    (start != end).then_some((start, end))
+}
+
+/// The selected characters of `messages` (visible list, oldest first), one message per line. A row's span runs from `start.1` on the first message to `end.1` on the last; every message strictly between contributes its whole text.
+pub fn selected_text(messages: &[&str], range: (TextPos, TextPos)) -> String {
+    let ((m0, c0), (m1, c1)) = range;
+    let mut out = String::new();
+    for (mi, text) in messages.iter().enumerate().take(m1 + 1).skip(m0) {
+        let from = if mi == m0 { c0 } else { 0 };
+        let to = if mi == m1 { c1 } else { usize::MAX };
+        if mi != m0 {
+            out.push('\n');
+        }
+        out.extend(text.chars().skip(from).take(to.saturating_sub(from)));
+    }
+    out
+}
+
+/// The char span of message `msg` covered by `range`, as `(from, to)` caret stops (`to` exclusive, `usize::MAX` = to the end). `None` when the message is outside the selection — render uses this to paint each row's highlight.
+pub fn row_span(msg: usize, range: (TextPos, TextPos)) -> Option<(usize, usize)> {
+    let ((m0, c0), (m1, c1)) = range;
+    if msg < m0 || msg > m1 {
+        return None;
+    }
+    let from = if msg == m0 { c0 } else { 0 };
+    let to = if msg == m1 { c1 } else { usize::MAX };
+    Some((from, to))
+}
+
+/// What a press-and-drag selects by: single click → characters, double → whole words, triple → whole messages (the history's "line").
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SelectUnit {
+    #[default]
+    Char,
+    Word,
+    Message,
+}
+
+/// Counts presses that land close together in time and space into a click streak (capped at three) — what turns a press on the history into a double- or triple-click.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ClickStreak {
+    last: Option<(std::time::Instant, (f32, f32))>,
+    count: u8,
+}
+
+impl ClickStreak {
+    /// A press at `at`, `now`: it continues the streak when it's within `interval` of the previous press AND within `slop` pixels of it on both axes, else starts a new one. Returns the unit the streak selects by.
+    pub fn press(&mut self, now: std::time::Instant, at: (f32, f32), interval: std::time::Duration, slop: f32) -> SelectUnit {
+        let continues = self.last.is_some_and(|(t, (x, y))| {
+            now.saturating_duration_since(t) <= interval && (at.0 - x).abs() <= slop && (at.1 - y).abs() <= slop
+        });
+        self.count = if continues { (self.count + 1).min(3) } else { 1 };
+        self.last = Some((now, at));
+        match self.count {
+            1 => SelectUnit::Char,
+            2 => SelectUnit::Word,
+            _ => SelectUnit::Message,
+        }
+    }
+}
+
+/// Index of the GLYPH under `x` along one row (not the nearest caret stop — a double-click on a word's last letter means that word, not the gap after it). Left of the first glyph → 0; right of the last → the char count, which [`crate::ui::text_words::word_at`] reads as the last char.
+fn glyph_at(edges: &[f32], x: f32) -> usize {
+    edges.iter().skip(1).take_while(|&&e| e <= x).count()
+}
+
+/// Ordered span a double/triple-click drag from `anchor` to `head` covers: every word (or whole message) either end touches, and everything between. `messages` = the visible texts, oldest first, indexed like the rows. [`SelectUnit::Char`] is plain [`selection_range`].
+pub fn unit_range(rows: &[RowGeom], messages: &[&str], anchor: (f32, f32), head: (f32, f32), unit: SelectUnit) -> Option<(TextPos, TextPos)> {
+    if unit == SelectUnit::Char {
+        return selection_range(rows, anchor, head);
+    }
+    let span = |(x, y): (f32, f32)| -> Option<(TextPos, TextPos)> {
+        // On a row → the glyph under the pointer; in a gap or off the list → wherever `pos_at` pins it.
+        let (msg, at) = match rows.iter().find(|r| y >= r.top && y < r.bottom) {
+            Some(row) => (row.msg, glyph_at(&row.edges, x)),
+            None => pos_at(rows, x, y)?,
+        };
+        let chars: Vec<char> = messages.get(msg)?.chars().collect();
+        let (from, to) = match unit {
+            SelectUnit::Word => crate::ui::text_words::word_at(&chars, at),
+            _ => (0, chars.len()),
+        };
+        Some(((msg, from), (msg, to)))
+    };
+    let (a0, a1) = span(anchor)?;
+    let (b0, b1) = span(head)?;
+    let (start, end) = (a0.min(b0), a1.max(b1));
+    (start != end).then_some((start, end))
+}
+
+/// Keyboard scrolling of the history: Home/End jump to the oldest/newest message, PageUp/PageDown move one viewport.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScrollKey {
+    Home,
+    End,
+    PageUp,
+    PageDown,
+}
+
+/// The history scroll offset after `key`, from `offset` (0 = newest at the bottom, larger = further back). `max_scroll` is the content height past the viewport, `view_h` the viewport's height. Always lands in `0..=max_scroll` — a keyed jump never rubber-bands, and a stale offset past either end is pulled back in.
+pub fn key_scroll(offset: f32, key: ScrollKey, max_scroll: f32, view_h: f32) -> f32 {
+    let offset = offset.clamp(0.0, max_scroll);
+    let target = match key {
+        ScrollKey::Home => max_scroll,
+        ScrollKey::End => 0.0,
+        ScrollKey::PageUp => offset + view_h,
+        ScrollKey::PageDown => offset - view_h,
+    };
+    target.clamp(0.0, max_scroll)
+}
+
+/// The newest message has scrolled out of the list band: `offset` (0 = pinned to the newest) is past the newest row's own height `newest_h`. A message landing now would arrive unseen, so it neither yanks the view down nor counts as read.
+pub fn away_from_latest(offset: f32, newest_h: f32) -> bool {
+    offset > newest_h
+}
+
+/// Whether the floating "new messages ↓" pill shows: the user is reading further back ([`away_from_latest`]) AND `unread` messages have landed on this conversation since.
+pub fn show_jump_to_latest(offset: f32, newest_h: f32, unread: u32) -> bool {
+    unread > 0 && away_from_latest(offset, newest_h)
+}
+
+/// A single centred line as a [`RowGeom`] (message 0) — the launch screen's status/error text, drawn with `draw_text_center` at `cx`. `prefix_widths[i]` is the measured width of the first `i + 1` chars, so the last entry is the whole line's width; caret stops start half that left of `cx`.
+pub fn centered_row(prefix_widths: &[f32], cx: f32, top: f32, bottom: f32) -> RowGeom {
+    let left = cx - prefix_widths.last().copied().unwrap_or(0.0) * 0.5;
+    let mut edges = Vec::with_capacity(prefix_widths.len() + 1);
+    edges.push(left);
+    edges.extend(prefix_widths.iter().map(|w| left + w));
+    RowGeom { msg: 0, top, bottom, edges }
+}
+
+/// Rows laid out past each edge of the viewport band, so a wheel step or a drag that reaches just off-screen finds its neighbour already in `rows`.
+pub const ROW_OVERSCAN: usize = 1 << 1;
+
+/// How far each row's bottom edge sits above the newest row's in a bottom-anchored history: `lifts[i]` is the summed height of every row newer than `i`, so the newest is 0 and `lifts[0] + heights[0]` is the whole history's height. Rows are a text line or an image thumbnail tall (`chat_image::row_height`), so position comes from this running sum rather than a fixed pitch.
+pub fn row_lifts(heights: &[f32]) -> Vec<f32> {
+    let mut lifts = vec![0.0; heights.len()];
+    for i in (0..heights.len().saturating_sub(1)).rev() {
+        lifts[i] = lifts[i + 1] + heights[i + 1];
+    }
+    lifts
+}
+
+/// First index in `0..n` where `pred` turns false (`pred` must be true-then-false).
+fn partition(n: usize, pred: impl Fn(usize) -> bool) -> usize {
+    let (mut lo, mut hi) = (0, n);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        if pred(mid) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// The visible-list indices a bottom-anchored history needs laid out: every row whose band intersects `band` (top, bottom), plus `overscan` each side. `newest_bottom` is the newest row's bottom edge (scroll already applied); row `i` spans `newest_bottom − lifts[i] − heights[i] .. newest_bottom − lifts[i]`. An off-screen row's position is known from [`row_lifts`] without measuring it, so scroll extent and selection indices stay exact while only this window is rasterized — two binary searches, whatever the history length.
+pub fn visible_range(heights: &[f32], lifts: &[f32], band: (f32, f32), newest_bottom: f32, overscan: usize) -> std::ops::Range<usize> {
+    let n = heights.len().min(lifts.len());
+    let (top, bottom) = band;
+    // Rows are ordered top to bottom, so both edges only grow with the index: the wholly-above rows are a prefix, the not-yet-below rows are a prefix.
+    let start = partition(n, |i| newest_bottom - lifts[i] <= top);
+    let end = partition(n, |i| newest_bottom - lifts[i] - heights[i] < bottom);
+    let start = start.min(end).saturating_sub(overscan);
+    let end = end.saturating_add(overscan).min(n);
+    start..end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two one-line messages, 10px per char from x = 0, rows stacked 20px tall with a 4px divider gap.
+    fn two_rows() -> Vec<RowGeom> {
+        let edges = |n: usize| (0..=n).map(|i| (i * 10) as f32).collect();
+        vec![
+            RowGeom { msg: 0, top: 0.0, bottom: 20.0, edges: edges(5) },
+            RowGeom { msg: 1, top: 24.0, bottom: 44.0, edges: edges(5) },
+        ]
+    }
+
+    #[test]
+    fn drag_across_two_messages_selects_tail_of_first_and_head_of_second() {
+        let rows = two_rows();
+        // From the middle of "hello" (past the 'e' midpoint → after "he") to inside "world" (before the 'r' midpoint → after "wo").
+        let range = selection_range(&rows, (16.0, 10.0), (24.0, 30.0)).expect("range");
+        assert_eq!(range, ((0, 2), (1, 2)));
+        assert_eq!(selected_text(&["hello", "world"], range), "llo\nwo");
+        // Dragging the other way selects the same span.
+        assert_eq!(selection_range(&rows, (24.0, 30.0), (16.0, 10.0)), Some(range));
+        assert_eq!(row_span(0, range), Some((2, usize::MAX)));
+        assert_eq!(row_span(1, range), Some((0, 2)));
+    }
+
+    #[test]
+    fn off_list_points_pin_and_plain_click_selects_nothing() {
+        let rows = two_rows();
+        // Above the list → start of the first; below → end of the last: the whole history.
+        let all = selection_range(&rows, (99.0, -50.0), (0.0, 500.0)).expect("range");
+        assert_eq!(all, ((0, 0), (1, 5)));
+        assert_eq!(selected_text(&["hello", "world"], all), "hello\nworld");
+        // Divider gap lands at the start of the lower row.
+        assert_eq!(pos_at(&rows, 30.0, 22.0), Some((1, 0)));
+        // A click without movement is no selection.
+        assert_eq!(selection_range(&rows, (12.0, 5.0), (12.0, 5.0)), None);
+        assert_eq!(selection_range(&[], (0.0, 0.0), (9.0, 9.0)), None);
+    }
+
+    #[test]
+    fn double_click_selects_the_word_and_triple_the_message() {
+        use std::time::{Duration, Instant};
+        let rows = two_rows();
+        let texts = ["hi yo", "world"];
+        let interval = Duration::from_millis(400);
+        let t0 = Instant::now();
+        let mut streak = ClickStreak::default();
+        // Two fast presses on the 'y' of "hi yo" (x 34 is inside its 30..40 glyph, right of its midpoint).
+        assert_eq!(streak.press(t0, (34.0, 10.0), interval, 4.0), SelectUnit::Char);
+        let unit = streak.press(t0 + Duration::from_millis(150), (35.0, 11.0), interval, 4.0);
+        assert_eq!(unit, SelectUnit::Word);
+        let word = unit_range(&rows, &texts, (35.0, 11.0), (35.0, 11.0), unit).expect("word");
+        assert_eq!(word, ((0, 3), (0, 5)));
+        assert_eq!(selected_text(&texts, word), "yo");
+        // A third press takes the whole message; a fourth stays at message.
+        let unit = streak.press(t0 + Duration::from_millis(300), (35.0, 11.0), interval, 4.0);
+        assert_eq!(unit_range(&rows, &texts, (35.0, 11.0), (35.0, 11.0), unit), Some(((0, 0), (0, 5))));
+        assert_eq!(streak.press(t0 + Duration::from_millis(450), (35.0, 11.0), interval, 4.0), SelectUnit::Message);
+        // Dragging a word selection onto the next message extends by whole words.
+        let words = unit_range(&rows, &texts, (5.0, 10.0), (12.0, 30.0), SelectUnit::Word).unwrap();
+        assert_eq!(selected_text(&texts, words), "hi yo\nworld");
+
+        // Too slow, or too far away, starts over.
+        let mut streak = ClickStreak::default();
+        streak.press(t0, (34.0, 10.0), interval, 4.0);
+        assert_eq!(streak.press(t0 + Duration::from_millis(500), (34.0, 10.0), interval, 4.0), SelectUnit::Char);
+        assert_eq!(streak.press(t0 + Duration::from_millis(600), (60.0, 10.0), interval, 4.0), SelectUnit::Char);
+    }
+
+    #[test]
+    fn keyed_scroll_snaps_to_the_ends_and_pages_by_one_viewport() {
+        // 1000px of history in a 300px band: 700px of scroll.
+        let (max, view) = (700.0, 300.0);
+        assert_eq!(key_scroll(250.0, ScrollKey::End, max, view), 0.0);
+        assert_eq!(key_scroll(250.0, ScrollKey::Home, max, view), max);
+        assert_eq!(key_scroll(100.0, ScrollKey::PageUp, max, view), 100.0 + view);
+        assert_eq!(key_scroll(400.0, ScrollKey::PageDown, max, view), 400.0 - view);
+        // Pages stop at the ends instead of overshooting; a rubber-banded offset is pulled back first.
+        assert_eq!(key_scroll(600.0, ScrollKey::PageUp, max, view), max);
+        assert_eq!(key_scroll(100.0, ScrollKey::PageDown, max, view), 0.0);
+        assert_eq!(key_scroll(-40.0, ScrollKey::PageUp, max, view), view);
+        // History shorter than the band: nothing to scroll.
+        assert_eq!(key_scroll(0.0, ScrollKey::Home, 0.0, view), 0.0);
+    }
+
+    #[test]
+    fn jump_to_latest_shows_only_when_scrolled_back_with_new_arrivals() {
+        let newest_h = 20.0;
+        // Pinned to the newest, or only part of the newest row scrolled off: arrivals are in view.
+        assert!(!show_jump_to_latest(0.0, newest_h, 3));
+        assert!(!show_jump_to_latest(15.0, newest_h, 3));
+        // Reading back with nothing new: no pill.
+        assert!(away_from_latest(400.0, newest_h));
+        assert!(!show_jump_to_latest(400.0, newest_h, 0));
+        // Reading back and a message lands.
+        assert!(show_jump_to_latest(400.0, newest_h, 1));
+        // Scrolling back down to the newest hides it again even before the count clears.
+        assert!(!show_jump_to_latest(0.0, newest_h, 1));
+    }
+
+    #[test]
+    fn drag_over_centred_error_maps_to_its_char_range() {
+        // "no route to fgtw" in 10px glyphs, centred at x = 200 → spans 120..280.
+        let error = "no route to fgtw";
+        let widths: Vec<f32> = (1..=error.chars().count()).map(|n| n as f32 * 10.0).collect();
+        let row = centered_row(&widths, 200.0, 50.0, 70.0);
+        assert_eq!(row.edges.first(), Some(&120.0));
+        assert_eq!(row.edges.last(), Some(&280.0));
+        let rows = [row];
+        // From the right half of the 'r' in "route" (x 157, past its 155 midpoint → after it) to the left half of the 'f' in "fgtw" (x 243 → before it).
+        let range = selection_range(&rows, (157.0, 60.0), (243.0, 60.0)).expect("range");
+        assert_eq!(range, ((0, 4), (0, 12)));
+        assert_eq!(selected_text(&[error], range), "oute to ");
+        // A drag from left of the text to past its end takes the whole message; a right-to-left drag orders the same.
+        let all = selection_range(&rows, (0.0, 60.0), (400.0, 60.0)).unwrap();
+        assert_eq!(selected_text(&[error], all), error);
+        assert_eq!(selection_range(&rows, (400.0, 60.0), (0.0, 60.0)), Some(all));
+        // An empty status line still yields one caret stop, so a drag over it selects nothing.
+        assert_eq!(selection_range(&[centered_row(&[], 200.0, 50.0, 70.0)], (0.0, 60.0), (400.0, 60.0)), None);
+    }
+
+    #[test]
+    fn huge_history_lays_out_only_the_viewport_window() {
+        // 5000 rows of 20px in a 600px band: ~30 on screen.
+        let (n, pitch, band) = (5000, 20.0, (100.0, 700.0));
+        let heights = vec![pitch; n];
+        let lifts = row_lifts(&heights);
+        // Scrolled to the newest: the window ends at the last row.
+        let newest = visible_range(&heights, &lifts, band, 700.0, ROW_OVERSCAN);
+        assert_eq!(newest.end, n);
+        assert!(newest.len() <= 30 + 2 * ROW_OVERSCAN + 2, "laid out {}", newest.len());
+        // Scrolled 2000 rows back: the same small window, now mid-history, and every row in it really intersects the band (± overscan).
+        let mid = visible_range(&heights, &lifts, band, 700.0 + 2000.0 * pitch, ROW_OVERSCAN);
+        assert!(mid.len() <= 30 + 2 * ROW_OVERSCAN + 2, "laid out {}", mid.len());
+        let y = |i: usize| 690.0 + 2000.0 * pitch - (n - 1 - i) as f32 * pitch;
+        assert!(y(mid.start + ROW_OVERSCAN) - pitch * 0.5 <= band.0 + pitch);
+        assert!(y(mid.end - 1 - ROW_OVERSCAN) + pitch * 0.5 >= band.1 - pitch);
+        // Rows just outside the window are genuinely off-screen.
+        assert!(y(mid.start - 1) + pitch * 0.5 < band.0);
+        assert!(y(mid.end) - pitch * 0.5 > band.1);
+        // Degenerate inputs.
+        assert_eq!(visible_range(&[], &[], band, 700.0, ROW_OVERSCAN), 0..0);
+        assert_eq!(visible_range(&[0.0; 3], &row_lifts(&[0.0; 3]), band, 690.0, ROW_OVERSCAN), 0..3);
+    }
+
+    #[test]
+    fn image_row_stretches_scroll_extent_and_owns_its_band() {
+        // Text, a 100px image row, text — each text line 20px, in a 60px band.
+        let heights = [20.0, 100.0, 20.0];
+        let lifts = row_lifts(&heights);
+        assert_eq!(lifts, vec![120.0, 20.0, 0.0]);
+        let content_h = lifts[0] + heights[0];
+        assert_eq!(content_h, 140.0);
+        let band = (0.0, 60.0);
+        // Overscroll range is what the image adds, not three fixed pitches.
+        let max_scroll = (content_h - (band.1 - band.0)).max(0.0);
+        assert_eq!(max_scroll, 80.0);
+
+        // At the newest: the image's lower part and the last line are on screen; the oldest row is not.
+        assert_eq!(visible_range(&heights, &lifts, band, 60.0, 0), 1..3);
+        // Scrolled fully back: the first line and the image's top; the newest line has left.
+        assert_eq!(visible_range(&heights, &lifts, band, 60.0 + max_scroll, 0), 0..2);
+
+        // A press anywhere on the image's tall band resolves to that message, not a neighbour.
+        let rows: Vec<RowGeom> = (0..3)
+            .map(|i| {
+                let bottom = 60.0 + max_scroll - lifts[i];
+                RowGeom { msg: i, top: bottom - heights[i], bottom, edges: vec![0.0, 10.0] }
+            })
+            .collect();
+        assert_eq!(pos_at(&rows, 0.0, 25.0).map(|p| p.0), Some(1));
+        assert_eq!(pos_at(&rows, 0.0, 115.0).map(|p| p.0), Some(1));
+        assert_eq!(pos_at(&rows, 0.0, 125.0).map(|p| p.0), Some(2));
+    }
+}
@@ -24,6 +24,46 @@ pub mod colour_convert;
 // Avatar paint — Mitchell resize + AA textured circle into a fluor `Canvas`.
 pub mod avatar_render;
 
+// Avatar framing before it's set — a circle over the fitted image → the source square the encoder crops to.
+pub mod avatar_crop;
+
+// Launch-screen attestation retry — transient-error classification + exponential backoff behind `LaunchState::Retrying`.
+pub mod attest_retry;
+
+// Perf overlay maths — rolling frame-time average + tick/redraw counts behind the Ctrl+Shift+F readout.
+pub mod frame_stats;
+
+// Background network sweep clocks — what `force_reconnect` makes due at once.
+pub mod sweeps;
+
+// Verification QR — ContactCard → module grid → canvas (the contact panel's Verify page).
+pub mod qr;
+
+// Conversation-history drag-select maths — drag points → ordered (message, char) span → copied text.
+pub mod message_select;
+
+// Per-message context menu (right-click / long-press) — row hit-test, the actions a message supports, on-screen item rects.
+pub mod context_menu;
+
+// Textbox undo/redo history — edit snapshots (text + caret), typing-run coalescing, bounded depth behind Ctrl+Z / Ctrl+Y.
+pub mod text_undo;
+
+// Word boundaries for Ctrl+←/→ and Ctrl+Backspace/Delete in textboxes — word / punctuation / space runs.
+pub mod text_words;
+// Compose-box spell-check — bundled word list, misspelled char ranges for the squiggle pass.
+pub mod spell;
+// `@`-mention autocomplete — trigger detection, fuzzy contact ranking, completion of the mention span.
+pub mod mention;
+// Inline *bold* / _italic_ / `code` in message rows — styled runs over the raw text, markers kept.
+pub mod markup;
+// Arriving-message alerts — unread / notify decision, do-not-disturb and its held backlog.
+pub mod alerts;
+// In-conversation find (Ctrl+F) — match spans over the history, Enter / Shift+Enter walk order, scroll-to-hit.
+pub mod find;
+
+// Image messages — dropped file → bounded, EXIF-baked JPEG for the chain's `message` field.
+pub mod chat_image;
+
 pub use state::{AppState, FoundPeer, LaunchState, SearchResult, SettingsPage};
 
 // Settings-panel stub: a minimal on/off `Checkbox` widget (fluor has no toggle/checkbox) styled to match the Button/Textbox family.
@@ -37,7 +77,7 @@ pub mod theme;
 
 // The fluor-hosted `FluorApp` impl. Drives desktop via `host-winit` and Android via `host-android`.
 pub mod photon_app;
-pub use photon_app::PhotonApp;
+pub use photon_app::{PhotonApp, SendError};
 
 /// Custom events for cross-thread communication with the event loop. On desktop, background tasks clone the `EventLoopProxy<PhotonEvent>` from `PhotonApp::set_event_proxy` and call `send_event` to wake the UI thread; on Android the same proxy type exists (data-only) but background work pokes the activity via JNI callbacks instead — the variants stay shared so the FluorApp::on_user_event handler is the same code on both platforms.
 #[derive(Debug, Clone)]
@@ -50,6 +90,8 @@ pub enum PhotonEvent {
     MessageReceived,
     /// Network update available (status, CLUTCH, avatar, etc.) - wake event loop
     NetworkUpdate,
+    /// The FGTW peer-update WebSocket came up, or dropped and is backing off before a reconnect
+    PeerUpdateLink(crate::network::peer_updates::LinkState),
     /// Background CLUTCH keypair generation completed
     ClutchKeygenComplete,
     /// Background CLUTCH KEM encapsulation completed
@@ -58,4 +100,12 @@ pub enum PhotonEvent {
     ClutchCeremonyComplete,
     /// A second launch handed off "surface yourself" over the control channel (desktop resident mode) — the handler un-hides the window via `EventResponse::ShowWindow`.
     ShowWindow,
+    /// Tray icon left-click: hide the window if it's showing, surface it if it's hidden.
+    ToggleWindow,
+    /// The global hotkey fired (`platform::hotkey`) — raise and focus the window from wherever it is (tray, background, behind other windows).
+    FocusRequested,
+    /// A scripting-socket request is parked in `platform::rpc`'s inbox — a pure wake; `tick` answers it.
+    ControlRequest,
+    /// A chat image finished decoding on its worker (`chat_image::ChatThumb::pixels`) — marks the scene dirty so the row paints.
+    ChatImageDecoded,
 }
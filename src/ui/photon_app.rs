@@ -1,12 +1,25 @@
 //! [`PhotonApp`]: the [`fluor::host::app::FluorApp`] impl that hosts Photon on desktop. Owns the app state machine (`AppState`), network handles, contact list, and the per-screen widgets (Launch / Ready / Searching / Conversation), drawing the chrome (perimeter, shadow, window buttons, app-icon orb) plus each screen's content, and routing cross-thread wake-ups thru `FluorApp::on_user_event` with the [`super::PhotonEvent`] payload.
 
+use super::alerts;
+use super::avatar_crop;
 use super::chromatic_wave::chromatic_wave;
 use fluor::text::TextStyle;
 use super::launch_layout::{AttestBlockLayout, LaunchLayout};
+use super::context_menu;
+use super::find;
+use super::markup;
+use super::mention;
+use super::message_select;
+use super::spell;
+use super::text_undo;
+use super::text_words;
+use super::avatar_render::{gradient_avatar_rgb, proof_gradient_seed};
 use super::photon_logo::paint_photon_logo;
+use super::frame_stats::FrameStats;
+use super::sweeps::SweepClocks;
 use super::ready_layout::ReadyLayout;
 use super::settings_layout::SettingsLayout;
-use super::state::{AppState, ContactPage, LaunchState, SettingsPage};
+use super::state::{contact_nav, contact_order, AppState, ContactNavKey, ContactNavOutcome, ContactPage, ListEntry, LaunchState, SettingsPage};
 use super::theme;
 use super::PhotonEvent;
 #[cfg(not(target_os = "android"))]
@@ -175,6 +188,103 @@ fn draw_hourglass(canvas: &mut Canvas, cx: f32, cy: f32, size: f32, angle_deg: f
     }
 }
 
+/// Text style for a formatted message run: bold steps the weight up two notches, italic leans the glyphs by the same shear the pending-name rows use. Code runs use the row's own style on a fixed pitch (see [`markup_edges`]).
+fn markup_style(size: f32, colour: u32, weight: u16, style: markup::Style) -> TextStyle {
+    let s = TextStyle::new(size, colour).weight(if style.bold { (weight + 200).min(800) } else { weight });
+    if style.italic {
+        s.shear(0.2126)
+    } else {
+        s
+    }
+}
+
+/// Caret-stop x offsets (one per raw char, plus the end; the first is 0) of a formatted message laid out left to right. A marker takes no room, a code char one cell the width of `0` (Oxanium's figures are tabular, so that's the face's pitch), any other run the width its own weight and shear measure to — bold is wider, so the row is sized off these, never the raw text. With `per_char` false only whole runs are measured and stops inside a run sit at its start: enough to place and draw the row; a live selection asks for every stop.
+fn markup_edges(text: &mut fluor::text::TextRenderer, chars: &[char], runs: &[markup::Run], size: f32, colour: u32, weight: u16, per_char: bool) -> Vec<f32> {
+    let mut edges = vec![0.0; chars.len() + 1];
+    let mut x = 0.0;
+    for run in runs {
+        let start = x;
+        if !run.marker {
+            let style = markup_style(size, colour, weight, run.style);
+            let cell = run.style.mono.then(|| text.measure_text("0", &style));
+            let mut prefix = String::new();
+            for k in run.from..run.to {
+                edges[k] = match cell {
+                    Some(c) => start + c * (k - run.from) as f32,
+                    None if per_char && k > run.from => start + text.measure_text(&prefix, &style),
+                    None => start,
+                };
+                prefix.push(chars[k]);
+            }
+            x = start + cell.map_or_else(|| text.measure_text(&prefix, &style), |c| c * (run.to - run.from) as f32);
+        } else {
+            edges[run.from] = start;
+        }
+        edges[run.to] = x;
+    }
+    edges
+}
+
+/// Paint `colour` under `range` across the published history rows — the drag-selection band, also what the find bar's hits are drawn with. Rows outside the range, or published without caret stops, get nothing. Call after the glyphs: the under-blend lands the band beneath them.
+fn paint_span_band(canvas: &mut Canvas, rows: &[message_select::RowGeom], range: (message_select::TextPos, message_select::TextPos), colour: u32, clip: Option<fluor::paint::Clip>) {
+    for row in rows {
+        let Some((from, to)) = message_select::row_span(row.msg, range) else {
+            continue;
+        };
+        let (Some(&x0), Some(&x1)) = (row.edges.get(from), row.edges.get(to.min(row.edges.len().saturating_sub(1)))) else {
+            continue;
+        };
+        paint::fill_rect(canvas, x0 as isize, row.top as isize, (x1 - x0) as isize, (row.bottom - row.top) as isize, colour, clip, None);
+    }
+}
+
+/// Draw a formatted message with its left edge at `left`, off the stops [`markup_edges`] measured. Code runs draw one glyph per cell over an attachment-chip tint (painted after the glyphs, so the under-blend lands it beneath them).
+#[allow(clippy::too_many_arguments)]
+fn draw_markup(
+    canvas: &mut Canvas,
+    text: &mut fluor::text::TextRenderer,
+    chars: &[char],
+    runs: &[markup::Run],
+    edges: &[f32],
+    left: f32,
+    y: f32,
+    size: f32,
+    colour: u32,
+    weight: u16,
+    clip: Option<fluor::paint::Clip>,
+) {
+    for run in runs.iter().filter(|r| !r.marker) {
+        let style = markup_style(size, colour, weight, run.style);
+        let (x0, x1) = (left + edges[run.from], left + edges[run.to]);
+        let s: String = chars[run.from..run.to].iter().collect();
+        if run.style.mono {
+            let cell = (x1 - x0) / (run.to - run.from) as f32;
+            let mut buf = [0u8; 4];
+            for (k, ch) in s.chars().enumerate() {
+                text.draw_text_center(canvas, ch.encode_utf8(&mut buf), x0 + cell * (k as f32 + 0.5), y, &style, clip, None);
+            }
+            let pad = size * 0.15;
+            paint::fill_rect(canvas, (x0 - pad) as isize, (y - size * 0.7) as isize, (x1 - x0 + pad * 2.0) as isize, (size * 1.4) as isize, *theme::ATTACHMENT_CHIP_COLOUR, clip, None);
+        } else {
+            text.draw_text_left(canvas, &s, x0, y, &style, clip, None);
+        }
+    }
+}
+
+/// A spell-check wave from `x0` to `x1` along `y`: a triangle wave of peak-to-peak `amp`, period 4·`amp`, stroked as one-pixel columns `amp / 2` thick.
+fn draw_squiggle(canvas: &mut Canvas, x0: f32, x1: f32, y: f32, amp: f32, colour: u32) {
+    let amp = amp.max(1.0);
+    let thick = (amp * 0.5).ceil().max(1.0) as isize;
+    let period = amp * 4.0;
+    let mut x = x0.floor();
+    while x < x1 {
+        let phase = ((x - x0) / period).fract();
+        let dy = (1.0 - (phase * 2.0 - 1.0).abs() * 2.0) * amp * 0.5;
+        paint::fill_rect(canvas, x as isize, (y + dy).round() as isize, 1, thick, colour, None, None);
+        x += 1.0;
+    }
+}
+
 /// Draw an upward-pointing arrowhead (a filled 4-vertex chevron) centred at (cx, cy), sized to a `size`×`size` box — the send-button glyph, painted OVER the already-drawn pill (the window-controls pattern: fill the button first, draw the symbol after). The four vertices: apex (top centre), right wing tip, bottom notch (centre, pulled up so it reads as a chevron with thickness, not a solid triangle), left wing tip. `colour` is α+darkness packed. Composites via source-over onto the existing (opaque pill) pixel, writing the result OPAQUE — so it CAN'T be an under() write (that would be discarded on the opaque pill). Crucially it does NOT touch the hit map: the pill already stamped the full silhouette, so the hover overlay (which wrap-adds a FILL-calibrated delta onto every hit-id pixel) tints only the pill, never the near-white glyph. Stamping the glyph's hit id here cooked the hover — don't. Coverage feathers the 1px boundary against the actual pill colour; `colour`'s α scales the glyph.
 fn draw_up_arrowhead(canvas: &mut Canvas, cx: f32, cy: f32, size: f32, colour: u32) {
     // Geometry as fractions of the box: apex up top, wings at the bottom corners, notch pulled up so the shape is a chevron (^) with visible thickness.
@@ -277,8 +387,6 @@ fn draw_up_arrowhead(canvas: &mut Canvas, cx: f32, cy: f32, size: f32, colour: u
 
 
 // Tiered presence-ping cadence — frequent while the user is engaged, sparse once they've walked away, so an idle/unfocused window isn't waking the radio every few seconds for rings nobody is watching. The tier is chosen by time-since-last-interaction; any interaction (input or focus gain) resets the clock AND fires an immediate sweep, so presence is always fresh the moment the user looks, regardless of how far the cadence had backed off.
-/// Active tier: sweep every 5s while interacting (idle < `PRESENCE_IDLE_NEAR`).
-const PRESENCE_PING_ACTIVE: std::time::Duration = std::time::Duration::from_secs(5);
 /// Idle tier: sweep every 1min once idle past `PRESENCE_IDLE_NEAR`.
 const PRESENCE_PING_IDLE: std::time::Duration = std::time::Duration::from_secs(60);
 /// Deep-idle tier: sweep every 15min once idle past `PRESENCE_IDLE_FAR`.
@@ -296,6 +404,12 @@ const PRESENCE_IDLE_FAR: std::time::Duration = std::time::Duration::from_secs(10
 /// unaffected. Supersedes the never-wired `traverse::session::keepalive_due`.
 const VALIDATED_PATH_KEEPALIVE: std::time::Duration = std::time::Duration::from_secs(20);
 
+/// How long quit waits on the upload of a contact batch still pending at exit. Past it the batch is owed to the next launch (`cloud_sync_owed`) — the worker still talking to fgtw.org dies with the process.
+const EXIT_UPLOAD_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// The contact-blob upload the exit path runs: `storage::cloud::sync_cloud_contacts`, or a stub under test.
+type CloudUpload = fn(&[crate::storage::cloud::CloudContact], &[u8; 32], &crate::network::fgtw::Keypair, &[u8; 32]) -> Result<(), crate::storage::cloud::CloudError>;
+
 /// One deterministic aesthetic channel in `[0, 1]` from a relationship digest: `blake3(name ‖ digest)`, first 8 bytes as u64, divided by `u64::MAX`. Same convention as chirp's `channel_unit` (the chime derivation) — duplicated here rather than imported because chirp is desktop-gated and colour must build on every target. Keep the two in lockstep.
 fn aesthetic_channel_unit(name: &str, digest: &[u8; 32]) -> f32 {
     let mut h = blake3::Hasher::new();
@@ -447,12 +561,19 @@ fn chord_hint_bbox(viewport: Viewport, vw: usize, vh: usize) -> PixelRect {
     PixelRect::new(x0, y0, x1, y1)
 }
 
+/// Strip the perf overlay's readout lives in: the top eighth of the window, full width. Generous on purpose — the text is sized off the launch layout (the zoom watermark's size), and re-deriving that here to the pixel buys nothing but lockstep upkeep; an eighth holds it at any aspect.
+fn perf_overlay_band(vw: usize, vh: usize) -> PixelRect {
+    PixelRect::new(0, 0, vw, vh >> 3)
+}
+
 /// Which textbox a registry entry is, so callers that need per-role behaviour can branch (freeze keys off Launch-vs-Contacts busy state; the launch box gates the Attest button; the contacts box filters the contact list). Generic concerns — focus, IME routing, blink — ignore the role and treat every entry the same. Add the conversation compose bar here when it lands.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum TextboxRole {
     LaunchHandle,
     ContactsSearch,
     MessageCompose,
+    /// The conversation find bar's query box — registered so its focus, IME and blinkie behave like the compose box it stands in for.
+    MessageFind,
     /// The Diagnostics optional-note field — in the registry so click-to-focus raises the Android IME + blinkie like every other box.
     SettingsNote,
     /// Any You-page profile field (display name, first, email, a custom one, …) or the add-a-field entry — same registry so click-to-focus raises the IME + blinkie. The form treats them all alike; the `field_id` that distinguishes them lives on [`ProfileField`], not here.
@@ -533,12 +654,16 @@ enum YouRow {
     IdentityHeader,
     /// The identity fingerprint read-out.
     IdentityFp,
+    /// "Copy contact link" pill — our `photon://add/…` card on the clipboard.
+    LinkPill,
     /// "Update" action pill.
     SavePill,
     /// Empty breathing row (between the action pills).
     Blank,
     /// "Change avatar…" action pill.
     AvatarPill,
+    /// "Remove avatar" pill (two-tap) — back to the placeholder.
+    RemoveAvatarPill,
 }
 
 /// Build the ordered You-page row plan from the current field set: fields grouped under their tier header (only non-empty tiers get a header), then the add-field affordance, the reassurance note, the identity read-out, and the action pills. Pure over `fields` so render / layout / scroll-extent all agree on the row count and order.
@@ -561,9 +686,11 @@ fn you_rows_plan(fields: &[ProfileField]) -> Vec<YouRow> {
     rows.push(YouRow::Note);
     rows.push(YouRow::IdentityHeader);
     rows.push(YouRow::IdentityFp);
+    rows.push(YouRow::LinkPill);
     rows.push(YouRow::SavePill);
     rows.push(YouRow::Blank);
     rows.push(YouRow::AvatarPill);
+    rows.push(YouRow::RemoveAvatarPill);
     rows
 }
 
@@ -622,6 +749,11 @@ fn ansi_line_to_spans(line: &str, default_colour: u32) -> Vec<(String, u32)> {
 /// Viewer cap: the newest 2^15 decoded records. A capped 16 MiB log holds more than anyone scrolls thru in-app; `photonlog` serves the full-file cases.
 const DIAG_LOG_MAX_ROWS: usize = 1 << 15;
 
+/// Width of a voice row's waveform strip, in message-font sizes.
+const VOICE_WAVE_UNITS: f32 = 8.0;
+/// Dim note after a voice row's length where memos can't be played (desktop: no capture or playback backend).
+const VOICE_UNSUPPORTED_NOTE: &str = "not playable on desktop";
+
 /// The Region for the `i`th decoded record on the Diagnostics log viewer: HALF the natural line height (a log wants density), below the full-height header row, shifted by the content scroll. Shared by the render loop and the extent math so the scroll bound matches the drawn rows exactly.
 fn diag_log_row_rect(layout: &SettingsLayout, scroll: Coord, i: usize) -> fluor::region::Region {
     let inset = layout.content_inset();
@@ -635,6 +767,43 @@ fn diag_log_row_rect(layout: &SettingsLayout, scroll: Coord, i: usize) -> fluor:
     )
 }
 
+/// The launch-screen request an attestation retry re-fires. A probe carries nothing: the handle is still in the (frozen) textbox, so it's re-read rather than held a second time.
+#[derive(Clone)]
+enum AttestRequest {
+    Probe,
+    WithRoots(tohu::SessionIdentity),
+}
+
+/// Why [`PhotonApp::send_message`] didn't put a message on the wire. Nothing was appended or persisted in any of these cases, so the caller can keep the compose text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SendError {
+    /// No contact at that index.
+    NoSuchContact,
+    /// Empty text — an empty compose box is a liveness probe, not a message.
+    Empty,
+    /// CLUTCH hasn't completed, so there's no chain to encrypt on.
+    ClutchIncomplete,
+    /// The contact is Complete but its friendship chains aren't loaded.
+    NoChain,
+    /// No address (direct, LAN, or reflexive) known for the contact yet.
+    NoAddress,
+    /// Our party id isn't a participant of the chain (no session, or the chain belongs to someone else).
+    NotParticipant,
+}
+
+impl std::fmt::Display for SendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SendError::NoSuchContact => write!(f, "not a contact"),
+            SendError::Empty => write!(f, "empty message"),
+            SendError::ClutchIncomplete => write!(f, "key exchange with this contact isn't complete"),
+            SendError::NoChain => write!(f, "friendship chains missing for this contact"),
+            SendError::NoAddress => write!(f, "no known address for this contact yet"),
+            SendError::NotParticipant => write!(f, "we are not a participant of this chain"),
+        }
+    }
+}
+
 /// Photon-desktop as a `FluorApp`. Owns fluor's `DefaultChrome` (window frame), the dense hit-id counter for widget allocation, and an optional event-loop proxy clone for waking from background tasks.
 ///
 /// `chrome` is `Option` because [`DefaultChrome::new`] needs the actual viewport size, which the host doesn't hand the app until [`FluorApp::init`] fires. `new()` is parameterless; everything else allocates in `init`.
@@ -646,6 +815,10 @@ pub struct PhotonApp {
     bg_scroll: isize,
     /// Whether to paint the top-centre zoom-percentage watermark. The host swallows the zoom events (Ctrl/Cmd + scroll / ± / 0) and updates `ctx.viewport.ru` directly, so we can't observe a zoom event — instead `render` arms this when `ru` changes WHILE a zoom modifier is held, and the `ModifiersChanged` handler clears it the instant the modifier is released. Not time-based: it persists exactly as long as Ctrl/Cmd stays down after a zoom began. (Android pinch — show from two-fingers-down to release — waits on fluor's multi-touch `Touch` event, which doesn't exist yet.)
     zoom_hint: bool,
+    /// Ctrl+Shift+F perf overlay: FPS, mean frame time and ticks-vs-redraws, top-left on every screen. Off by default; a report aid, not chrome.
+    perf_overlay: bool,
+    /// Rolling frame timing feeding `perf_overlay` — recorded every tick/render whether or not the overlay is up, so it reads true the instant it's toggled on.
+    frame_stats: FrameStats,
     /// Previous frame's `ru`, for the frame-to-frame change detection that arms `zoom_hint`. Seeded to 1.0 (the host's default zoom).
     last_ru: f32,
     /// Wave-phase animation accumulator for the "query in flight" cue. Advances at `2π rad/s` (1 full cycle/sec) in `tick()` while `state == LaunchState::Attesting` (or future `AppState::Searching`); held constant otherwise so the wave stays idle when the app is. Summed into the scroll-driven base phase in `render()`. Wraps mod TAU each frame so it stays in `[0, 2π)` and float precision doesn't drift over a long-running query.
@@ -664,21 +837,10 @@ pub struct PhotonApp {
     blink_timer: BlinkTimer,
     /// The screen `tick()` last saw — its per-tick diff against `self.state` is THE page-change hook: any screen swap drops textbox focus (and with it the blinkey + Android IME) no matter which of the many `self.state =` sites caused it. Screen granularity, not state granularity: Launch sub-states are one screen (Error→Fresh happens ON the recovery keystroke — defocusing would eat it), Ready↔Searching share the contacts screen (the search box owns the in-flight search), each Settings page counts as its own.
     last_screen: AppState,
-    /// Last time `tick()` ran the background presence ping sweep (`ping_contacts`). `None` until the first sweep. Paired with `last_interaction` to drive the tiered cadence (see `presence_ping_interval`): `tick()` re-pings when due and `wake_at()` schedules the next due sweep so presence refreshes even while idle. Without this, contacts only flipped online when you opened their conversation.
-    last_presence_ping: Option<Instant>,
+    /// When each periodic network sweep (presence ping, own-fleet re-fold, stalled-address re-fetch) last ran, plus the owed forced FGTW re-announce. See [`SweepClocks`] and `force_reconnect`.
+    sweeps: SweepClocks,
     /// Last time the user interacted with the app (any input event, or window focus-gain). `None` until the first interaction. The presence sweep tapers with idle time — frequent while you're actively using it, sparse when you've walked away — so an unfocused, untouched window isn't hitting the network every few seconds. Reset on interaction, which also triggers an immediate sweep so rings are fresh the instant you look. See `presence_ping_interval`.
     last_interaction: Option<Instant>,
-    /// Last time an already-running device re-folded its OWN fleet chain to catch a device add/remove it may have missed. The hub `fleet` event is the fast path but best-effort (a dropped WebSocket = a missed add), so this periodic re-fold is the reliable doorbell: without it, an existing device never learns a newly-added sibling until relaunch — it wouldn't answer the new device's presence pings (→ shows it offline) and its Fleet list would stay stale. `None` until the first poll.
-    last_fleet_refold: Option<Instant>,
-    /// Last time we pulsed a background resume to re-fetch a stalled contact's address. Address
-    /// discovery (`contact.ip`) only refreshes on attest echo / roster / search — there is no
-    /// periodic re-fetch — so a contact whose initial fetch failed (flaky cellular fgtw) is stuck
-    /// with no address: its CLUTCH offer can't send, name/avatar (which ride the pong) never
-    /// arrive, and it loops keygen forever. While any contact is blocked this way we pulse a
-    /// lightweight background resume on a fast cadence; one success learns the address and
-    /// fire-on-learn punches + the offer sends. `None` until the first pulse. (Stopgap for the
-    /// peer-gossip fix, TICKETS T0.)
-    last_stalled_refetch: Option<Instant>,
     /// Shared peer store (self-signed routing records), cloned from HandleQuery's. Populated by
     /// fgtw fetches AND by phonebook-gossip responses (see status.rs); the app harvests learned
     /// addresses from it for stalled contacts whose own fgtw fetch keeps failing. `None` until init.
@@ -687,6 +849,11 @@ pub struct PhotonApp {
     handle_query: Option<HandleQuery>,
     /// Per-contact presence + CLUTCH ceremony driver. Shares HandleQuery's UDP socket; pings contacts, receives pongs (→ `is_online`), and runs the slot-based CLUTCH offer/KEM/complete exchange. `None` until init. Ported from the retired `app.rs` — the fluor migration left this whole subsystem behind, so contacts showed offline and CLUTCH never started.
     status_checker: Option<crate::network::status::StatusChecker>,
+    /// FGTW's live peer-update WebSocket: pushes a contact's new address the moment it moves, ahead of the next directory fetch. Drained each tick into `refresh_contact_addrs_from_peers`. Desktop only (Android learns of moves thru FCM). `None` until init.
+    #[cfg(not(target_os = "android"))]
+    peer_updates: Option<crate::network::PeerUpdateClient>,
+    /// The peer-update link's last reported state (`PhotonEvent::PeerUpdateLink`). `None` until it first reports.
+    peer_link: Option<crate::network::peer_updates::LinkState>,
     /// Pubkeys the status checker will answer pings from — kept in lockstep with `self.contacts` (seeded on resume-load, appended on add). Shared `Arc<Mutex<..>>` with the checker thread.
     contact_pubkeys: crate::network::status::ContactPubkeys,
     /// Last-received-message markers per conversation, for retransmit. Inert in v1 (messaging not yet ported) — an empty shared vec the checker reads and never finds anything in.
@@ -714,6 +881,8 @@ pub struct PhotonApp {
         crate::types::friendship::FriendshipId,
         crate::types::friendship::FriendshipChains,
     )>,
+    /// Headless outbox drain, per contact party id: `None` = drained (or found empty) this session, `Some(t)` = a send failed, retry at `t`. Cleared with the session. See [`Self::drain_outboxes`].
+    outbox_drain: std::collections::HashMap<[u8; 32], Option<Instant>>,
     /// Last `[` Press timestamp; `None` until first press. Combined with `chord_lb_release` decides whether `[` is currently held — see `brackets_held`.
     chord_lb_press: Option<Instant>,
     /// Last `[` Release timestamp. `None` until first release.
@@ -762,6 +931,8 @@ pub struct PhotonApp {
     message_textbox: Option<Textbox>,
     /// Send button overlaid inside `message_textbox`'s right edge — mirrors the contacts-screen search `+` button (same size, same overlay treatment). Clicking it sends the compose box contents, same as pressing Enter.
     message_send_btn: Option<Button>,
+    /// The find bar's query box (Ctrl+F on an open conversation). Stands in for `message_textbox` while the bar is open: Enter / Shift+Enter walk the hits, Esc closes it.
+    find_textbox: Option<Textbox>,
     /// Encrypted local storage — initialized after attestation success with the device secret + handle. Held behind an `Arc` so it can be handed to the avatar background-download/sync threads (a plain `&FlatStorage` borrow can't cross `thread::spawn`); the inner `Mutex<Vault>` makes `Arc<FlatStorage>` `Send + Sync`.
     storage: Option<std::sync::Arc<crate::storage::FlatStorage>>,
     /// Contact list. Populated from `AttestationData.contacts` on attestation success and grown by `submit_add_friend` → `HandleQuery::search` results. Persisted to FlatStorage on add.
@@ -836,6 +1007,20 @@ pub struct PhotonApp {
     probed_session: Option<tohu::SessionIdentity>,
     /// Canonical spelling of the handle `probed_session` was derived from — the confirm press fires the stashed roots ONLY when the box still canonicalizes to this, so stale roots can never attest a different identity than the one on screen.
     probed_handle: Option<String>,
+    /// The attestation request last fired from the launch screen — what a transient-failure retry re-sends. `None` before the first submit.
+    attest_request: Option<AttestRequest>,
+    /// Backoff state for `LaunchState::Retrying` (policy from `settings.vsf`).
+    attest_retry: super::attest_retry::AttestRetry,
+    /// Contact changes not yet pushed to the cloud blob — uploaded once they go quiet (`advance_protocol`), and at exit within `EXIT_UPLOAD_TIMEOUT`; only a failed or overrunning exit upload is left owed to the next launch (`cloud_sync_owed`).
+    cloud_sync: crate::storage::cloud::SyncBatch,
+    /// The last exit's upload failed or timed out, leaving the batch owed (`settings.vsf` `cloud_sync_owed`) — batched on the first attest success, after the cloud merge has landed, so the upload can't drop rows only the blob held.
+    cloud_sync_owed: bool,
+    /// Sleep/resume watch: fed every tick, told every scheduled wake; a gap the clocks can't explain forces a reconnect (threshold `settings.vsf` `resume_gap_s`).
+    resume: super::sweeps::ResumeDetector,
+    /// Away presence: `last_interaction` idle past `settings.vsf` `away_after_min` flips it; the status thread's pongs carry the flag to our friends.
+    away: super::sweeps::AwayClock,
+    /// Active presence tier: sweep every `settings.vsf` `ping_base_s` while interacting (idle < `PRESENCE_IDLE_NEAR`). Also the step each long-offline contact's ping backoff doubles from.
+    ping_base: std::time::Duration,
     /// Join flow: status line on the add-mode launch screen.
     add_join_status: String,
     /// Join flow: the fixed-width fleet-masked words (this device's own pubkey under the identity mask) displayed for the user to type on an existing device. `Some` = the words screen is up. The screen stays up until membership folds (green = leaving this screen) or the user cancels.
@@ -862,6 +1047,18 @@ pub struct PhotonApp {
     device_avatar_scaled_diameter: usize,
     /// HitId reserved for the Ready-screen self-avatar circle. Allocated in `init` alongside the other widget IDs; stamped into `chrome.hit_test_map` during the Ready render so a tap on the circle dispatches to the avatar code path (open the image picker on Android).
     avatar_hit_id: HitId,
+    /// HitId for the Ready-screen "copy my handle" label in the handle slot under the avatar — copies [`ContactCard::share_text`](crate::types::ContactCard::share_text) to the clipboard.
+    share_hit_id: HitId,
+    /// A `photon://add/…` link that parsed and names someone new, waiting on the user: links arrive from outside (a clicked URL, a second launch, a paste), so nothing is added until the prompt in the Ready hint slot is pressed. Any other press drops it.
+    pending_link: Option<crate::types::ContactCard>,
+    /// HitId for that prompt — stamped over the hint slot only while `pending_link` is Some.
+    link_confirm_hit_id: HitId,
+    /// Inbound transfers the auto-download policy (`auto_download_mib`) is holding, oldest first: `(peer_addr, stream_id, total_size, held_at)`. The first is prompted in the Ready hint slot once no link prompt is up; pressing it downloads, any other press declines it. Pruned past `PTManager::HOLD_TIMEOUT` (the checker has let go by then) and capped at `PTManager::MAX_PENDING_SPECS`, like the checker's own queue.
+    held_transfers: Vec<(std::net::SocketAddr, u8, u32, std::time::Instant)>,
+    /// HitId for the held-transfer prompt — stamped over the hint slot only while it shows.
+    transfer_accept_hit_id: HitId,
+    /// This run's canonical handle, captured from the launch box at attest success so "copy my handle" can share it. Memory only, never persisted or logged — the handle derives the identity seed (docs/identity-profile.md). A resumed session never typed it: `None`, and the share copies the link alone.
+    our_handle: Option<String>,
     /// KnownHandle fork pills — pick-another-name / it's-mine (docs/lifecycle.md D1). Plain hit rects, Pressed-arm dispatch.
     known_pick_hit: HitId,
     known_mine_hit: HitId,
@@ -870,7 +1067,15 @@ pub struct PhotonApp {
     /// One-shot absolute-zoom restore (the persisted per-device `display.zoom`), handed to the host via `FluorApp::take_zoom_request`. Set when settings load; the host applies + clears it.
     pending_zoom_restore: Option<f32>,
     /// The picked avatar's display pixels, arriving from the OFF-THREAD set pipeline (decode runs there too — a 50MP photo must not stall a frame). Installed + repainted in tick.
-    avatar_set_rx: Option<std::sync::mpsc::Receiver<Vec<u8>>>,
+    avatar_set_rx: Option<std::sync::mpsc::Receiver<crate::ui::avatar::AvatarAnimation>>,
+    /// The hourly retention sweep's result while it runs on its worker (`spawn_retention_sweep`): `(party id, pruned timestamps)` per conversation it cut. `Some` = a sweep is in flight, so the next one waits.
+    retention_rx: Option<std::sync::mpsc::Receiver<Vec<([u8; 32], Vec<i64>)>>>,
+    /// A picked / dropped / pasted image decoded for the crop screen, arriving from its off-thread decode (`frame_avatar`). Drained in tick, which opens [`AppState::AvatarCrop`].
+    avatar_framing_rx: Option<std::sync::mpsc::Receiver<Result<AvatarFraming, String>>>,
+    /// The image on the crop screen and the circle framed over it. `Some` only while [`AppState::AvatarCrop`] is up.
+    avatar_framing: Option<AvatarFraming>,
+    /// This device's animated avatar (frames in display colour), the instant its loop started, and the frame currently installed in `device_avatar_pixels`. `None` for a still, and always on Android — there, and on every surface but the Ready-screen self-avatar, an animated avatar shows its first frame.
+    device_avatar_anim: Option<(crate::ui::avatar::AvatarAnimation, Instant, usize)>,
     /// One-shot Android image-picker request. Set when the user taps the avatar; consumed by the JNI poll (`nativePollAvatarPicker`) which signals the Activity to launch `ACTION_GET_CONTENT`. Stays `None` on idle frames so the Activity doesn't churn.
     pending_picker_request: bool,
     /// One-shot signal for the Android sticky session broadcast: 1=send, -1=clear, 0=nothing. Set by attest success and []n nuke.
@@ -879,8 +1084,14 @@ pub struct PhotonApp {
     active_contact: Option<usize>,
     /// Base hit ID for contact rows. Row `i` gets `contact_hit_base + i`. Allocated in `init` after the other widget IDs.
     contact_hit_base: HitId,
+    /// Hit ID for the contacts list's "archived" filter link (under the last row).
+    archived_toggle_hit: HitId,
+    /// The contacts list shows the archived conversations instead of the main list. Toggled by the filter link; session-only.
+    show_archived: bool,
     /// Hit ID for the "← Contacts" back button on the Conversation screen.
     back_btn_hit_id: HitId,
+    /// Base of the crop screen's hit block: +0 zoom out, +1 zoom in, +2 Cancel, +3 Use, +4 the image (press-drag pans the circle).
+    avatar_crop_hit_base: HitId,
     /// Hit ID for the "Start fresh (wipe this device)" line on the JOIN words screen — a removed device's only self-clean path (it can't attest → can't reach Security).
     join_startfresh_hit_id: HitId,
     /// "Copy words" tappable on the JOIN words screen — puts the space-separated pairing words on the clipboard so they can ride any channel (email, messenger) to the device that types them, instead of being read + retyped by hand.
@@ -919,24 +1130,86 @@ pub struct PhotonApp {
     // --- Settings panel (STUB) ---
     /// Base hit id for the settings nav-rail rows. Row `i` (page `SettingsPage::ALL[i]`) stamps `settings_nav_base + i`. Allocated in `init`.
     settings_nav_base: HitId,
-    /// Contact-panel action pills (slot 0 = Boot). Allocated alongside the settings blocks.
+    /// Contact-panel action pills (slot 0 = Boot, 1 = mark verified, 2 = scan a verification QR, 3 = history retention, 4 = clear history, 5 = clear + reset chains). Allocated alongside the settings blocks.
     contact_panel_btn_base: HitId,
     /// Contact-panel nav-rail rows. Row `i` (page `ContactPage::ALL[i]`) stamps `contact_nav_base + i`.
     contact_nav_base: HitId,
     /// Boot two-tap arm (event-shown, interaction-cleared — any other press on the panel disarms).
     contact_boot_armed: bool,
+    /// Clear-history two-tap arm: `Some(false)` = "Clear history" armed, `Some(true)` = "Clear + reset encryption" armed. Same interaction-cleared rule as Boot.
+    contact_wipe_armed: Option<bool>,
+    /// Our own verification QR (the Verify page's "scan me"), encoded once per identity and keyed by our party id — the page redraws every frame, the encode needn't.
+    verify_qr: Option<([u8; 32], crate::ui::qr::QrMatrix)>,
     /// One-shot residency bypass: Shift+Escape sets it so the next close-requested actually exits instead of hiding.
     exit_requested: bool,
+    /// `shutdown` has run — the exit path flushes and closes the pipe exactly once.
+    shut_down: bool,
+    /// Desktop avatar paste target: set by tapping our avatar on Ready, cleared by any other press — while set (and no textbox is focused) Ctrl+V reads an image from the clipboard into the avatar.
+    #[cfg_attr(any(target_os = "redox", target_os = "android"), allow(dead_code))]
+    avatar_paste_armed: bool,
+    /// Keyboard-focused contact on the Ready list (contact index, not row position — survives the unread float reordering rows). Drawn with the hover vocabulary; Up/Down move it, Enter opens it. Kept across a conversation round-trip so Esc lands back on the same row.
+    contact_key_focus: Option<usize>,
+    /// Set when an arrow key moved `contact_key_focus`: the next Ready frame scrolls the focused row into view, then clears it (a mouse-wheel scroll must not be yanked back).
+    contact_key_reveal: bool,
+    /// Conversation history drag-select: the press point while a drag is live (`None` otherwise). Set by a press on bare history, cleared on release.
+    msg_select_anchor: Option<(f32, f32)>,
+    /// Current pointer position of the live history drag (the moving end of the selection).
+    msg_select_head: (f32, f32),
+    /// Multi-click streak over the history and the unit the live drag selects by — double-click = words, triple = whole messages.
+    msg_click_streak: message_select::ClickStreak,
+    msg_select_unit: message_select::SelectUnit,
+    /// The settled history selection, tagged with the contact index it belongs to so it never paints onto another conversation. Any press drops it.
+    msg_selection: Option<(usize, (message_select::TextPos, message_select::TextPos))>,
+    /// Per-row geometry of the on-screen history, published by render each conversation frame — what drag points resolve against. Caret edges are only measured while a selection is live.
+    msg_rows: Vec<message_select::RowGeom>,
+    /// The open find bar over the conversation history (`None` = closed): its hits, painted with the selection band, and the current one.
+    msg_find: Option<find::Find>,
+    /// `(lift, height)` of every visible history row on the last conversation frame, published only while the find bar is open — a hit anywhere in the history is scrolled to off these.
+    msg_row_lifts: Vec<(f32, f32)>,
+    /// Launch-screen status/error line drag-select: press point while live, the moving end, and the settled span — same maths as the history ([`message_select::centered_row`]), so an error can be copied into a bug report.
+    status_select_anchor: Option<(f32, f32)>,
+    status_select_head: (f32, f32),
+    status_selection: Option<(message_select::TextPos, message_select::TextPos)>,
+    /// The status line on the last launch frame and its row geometry (caret edges measured only while a selection is live). `None` when no status is shown — a press then has nothing to select.
+    status_row: Option<(String, message_select::RowGeom)>,
+    /// Vertical band `(top, bottom)` of the history list on the last conversation frame — a press inside it with no widget under the cursor starts a selection instead of a window drag.
+    msg_list_band: Option<(f32, f32)>,
+    /// Image rows of the open conversation, keyed by message timestamp: header-read sizes for layout plus the decoded thumbnail. Scoped to one conversation (`chat_thumbs_of`, its party id) so it never grows past the history on screen.
+    chat_thumbs: std::collections::HashMap<i64, crate::ui::chat_image::ChatThumb>,
+    chat_thumbs_of: [u8; 32],
+    /// Timestamp of the image message tapped open: it fills the history band until the next press or Esc.
+    chat_image_expanded: Option<i64>,
+    /// Open per-message context menu (right-click, or a still long-press on Android) and the item rects its last frame painted — what a pick hit-tests against. Any press, Esc or leaving the conversation closes it.
+    msg_menu: Option<context_menu::ContextMenu>,
+    msg_menu_rects: Vec<(f32, f32, f32, f32)>,
+    /// When the live history press began — held still past [`context_menu::LONG_PRESS`] it opens the menu instead of selecting (Android).
+    msg_press_at: Option<Instant>,
+    /// Undo/redo history of the textbox that was last edited, tagged with its hit id — editing a different box starts a fresh one, so Ctrl+Z never reaches across fields.
+    text_undo: Option<(HitId, text_undo::UndoStack)>,
+    /// Hold-to-record in progress on the send button (empty compose box): when the press began and the party id it records for. Release over the button sends; release anywhere else throws it away.
+    voice_recording: Option<(Instant, [u8; 32])>,
+    /// A released recording awaiting its clip from the platform recorder — the party id it goes to.
+    voice_pending_for: Option<[u8; 32]>,
+    /// Last NORMAL (un-maximized) window size seen by `on_resize` — what close persists so the next launch reopens at it. `None` until the first resize lands. Maximized resizes don't overwrite it, so un-maximizing after a restore returns to the user's own size.
+    window_size: Option<(u32, u32)>,
+    /// Whether the window is currently maximized (`ctx.is_maximized` at the last resize); persisted alongside `window_size`.
+    window_maximized: bool,
+    /// `ReadyLayout::unit_height` at the last `on_resize` (0 until the first). When a resize changes it — a DPI hop between monitors, a zoom step — the pixel scroll offsets are rescaled by the ratio so every scrolled pane keeps the same content in view.
+    layout_unit: f32,
     /// Base hit id for the settings stub action pills (immediate-mode Buttons — Add device, Lock, Shred, Snapshot, …). Each page draws its pills over a small contiguous slice of this range; clicks land here and log a stub line. Allocated in `init` with a fixed span.
     settings_btn_base: HitId,
     /// Appearance-page theme selector — a real fluor `Dropdown`. Only in the widget walk while the Settings/Appearance page is up.
     settings_theme_dropdown: Option<fluor::widgets::Dropdown>,
     /// Appearance-page zoom / text-size control — a real fluor `Slider`.
     settings_zoom_slider: Option<fluor::widgets::Slider>,
+    /// Appearance-page spell-check on/off — a custom `Checkbox`, mirrored into `spell_check`.
+    settings_spell_check: Option<crate::ui::settings_widgets::Checkbox>,
     /// Recovery-page "be a custodian" opt-in — a custom `Checkbox`.
     settings_custodian_check: Option<crate::ui::settings_widgets::Checkbox>,
     /// Notifications-page global chime on/off — a custom `Checkbox`.
     settings_chime_check: Option<crate::ui::settings_widgets::Checkbox>,
+    /// Notifications-page do-not-disturb — a custom `Checkbox`, mirrored into `dnd`.
+    settings_dnd_check: Option<crate::ui::settings_widgets::Checkbox>,
     /// Notifications-page presence-visibility toggle — a custom `Checkbox`.
     settings_presence_check: Option<crate::ui::settings_widgets::Checkbox>,
     /// Updates-page auto-update on/off — a custom `Checkbox`.
@@ -983,6 +1256,9 @@ pub struct PhotonApp {
     /// Self-update state (docs/updates.md): off-thread check/apply results drain here. tx kept so both channel checks + an apply share ONE receiver.
     update_rx: Option<std::sync::mpsc::Receiver<UpdateEvent>>,
     update_tx: Option<std::sync::mpsc::Sender<UpdateEvent>>,
+    /// Images dropped into a conversation, prepared off-thread: (recipient party id, JPEG or the rejection hint). Lazily created on the first drop, like the update channel.
+    chat_drop_rx: Option<std::sync::mpsc::Receiver<([u8; 32], Result<crate::ui::chat_image::Dropped, String>)>>,
+    chat_drop_tx: Option<std::sync::mpsc::Sender<([u8; 32], Result<crate::ui::chat_image::Dropped, String>)>>,
     /// Per-channel manifest state, populated by the auto-check on each Updates-page open — drives each button's label (target version, dozenal), colour, and enabled-ness.
     update_release: ChannelCheck,
     update_dev: ChannelCheck,
@@ -1009,14 +1285,34 @@ pub struct PhotonApp {
     settings_rail_extent: f32,
     settings_content_extent: f32,
     contacts_scroll_extent: isize,
+    /// The open conversation's history scroll range (content height past the list band) — what keyed scrolling clamps to.
+    msg_scroll_extent: f32,
+    /// Height of the open conversation's newest row on the last frame — how far the history can scroll before that row is out of sight ([`message_select::away_from_latest`]).
+    msg_newest_h: f32,
+    /// Contact mentions (a sent party id we hold as a contact, [`mention::shown`]) in the history rows on the last frame, as (contact index, rect). A press inside one opens that conversation.
+    msg_ref_rects: Vec<(usize, (f32, f32, f32, f32))>,
+    /// The "new messages ↓" pill's rect on the last frame, `None` while it isn't shown. A press inside it jumps to the newest message.
+    msg_jump_rect: Option<(f32, f32, f32, f32)>,
+    /// `@`-mention picker over the compose box ([`mention::Picker`]) — re-derived in `tick` from the focused draft and caret.
+    mention: mention::Picker,
+    /// The picker's rows on the last frame as (contact index, rect) — a press inside one completes that name.
+    mention_rects: Vec<(usize, (f32, f32, f32, f32))>,
+    /// Red squiggles under unknown words in long compose drafts ([`spell::misspelled`]). Display only — send never consults it. Loaded from `settings.vsf`; the Appearance checkbox flips and persists it.
+    spell_check: bool,
+    /// Do-not-disturb ([`alerts::Dnd`]): incoming friend messages still land and count unread, but neither notify nor chime; the Ready strip shows how many it held. Loaded from `settings.vsf`; the Notifications checkbox flips and persists it.
+    dnd: alerts::Dnd,
     settings_shred_armed: bool,
     /// Two-tap confirm armed for the Security page's "Remove & shred" (self-departure from the fleet chain, then crypto-wipe). Mutually exclusive with `settings_shred_armed`; cleared on any page switch, like every destructive arm.
     settings_removeshred_armed: bool,
+    /// Two-tap confirm armed for the You page's "Remove avatar" (deletes the wall copy too). Cleared by any other You-page press or a page switch.
+    you_avatar_remove_armed: bool,
     /// About page: false = show the version as dozenal GLYPHS (the default — proper rendered dozenal, never arabic); true = the version tapped, spell it out in voca words. Toggles on each tap of the version row.
     about_version_spelled: bool,
 
     /// This node's own reflexive (public) address, learned via peer-echoed reflection (see [`crate::network::traverse::reflexive`]). `None` until the first signed pong / `ReflectResponse` echo. Fed forward to candidate gathering and the FGTW announce so our published address is the one seen on the live UDP data socket — not fgtw.org's TLS-flow `cf-connecting-ip`, which is only right for cone NATs.
     our_reflexive: Option<std::net::SocketAddr>,
+    /// Our NAT's mapping behaviour from the last two-reflector probe (see [`crate::network::traverse::nat`]). `None` until a probe completes. Re-probed on every FGTW online edge and reflexive-address change; a Symmetric verdict toasts the relay warning once per change.
+    nat_type: Option<crate::network::traverse::nat::NatType>,
 }
 
 impl PhotonApp {
@@ -1044,8 +1340,11 @@ impl PhotonApp {
             hit_counter: 0,
             event_proxy: None,
             our_reflexive: None,
+            nat_type: None,
             bg_scroll: 0,
             zoom_hint: false,
+            perf_overlay: false,
+            frame_stats: FrameStats::default(),
             last_ru: 1.0,
             attest_anim_phase: 0.,
             last_tick: None,
@@ -1055,13 +1354,14 @@ impl PhotonApp {
             focused: None,
             blink_timer: BlinkTimer::new(),
             last_screen: AppState::default(),
-            last_presence_ping: None,
+            sweeps: SweepClocks::default(),
             last_interaction: None,
-            last_fleet_refold: None,
-            last_stalled_refetch: None,
             peer_store: None,
             handle_query: None,
             status_checker: None,
+            #[cfg(not(target_os = "android"))]
+            peer_updates: None,
+            peer_link: None,
             contact_pubkeys: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
             sync_records: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
             clutch_keygen_tx: {
@@ -1094,6 +1394,7 @@ impl PhotonApp {
             avatar_req_pending: std::collections::HashMap::new(),
             history_serve: std::collections::HashMap::new(),
             friendship_chains: Vec::new(),
+            outbox_drain: std::collections::HashMap::new(),
             chord_lb_press: None,
             chord_lb_release: None,
             chord_rb_press: None,
@@ -1126,6 +1427,7 @@ impl PhotonApp {
             message_textbox: None,
             contacts_plus_btn: None,
             message_send_btn: None,
+            find_textbox: None,
             storage: None,
             contacts: Vec::new(),
             add_in_flight: false,
@@ -1160,6 +1462,16 @@ impl PhotonApp {
             launch_add_mode: false,
             add_join_handle: None,
             probed_session: None,
+            attest_request: None,
+            attest_retry: super::attest_retry::AttestRetry::new(super::attest_retry::RetryPolicy::from_settings(
+                &crate::storage::settings::Settings::load_or_create(),
+            )),
+            cloud_sync: crate::storage::cloud::SyncBatch::default(),
+            cloud_sync_owed: crate::storage::settings::Settings::load_or_create().cloud_sync_owed,
+            resume: super::sweeps::ResumeDetector::from_settings(&crate::storage::settings::Settings::load_or_create()),
+            away: super::sweeps::AwayClock::from_settings(&crate::storage::settings::Settings::load_or_create()),
+            // Floor of 1s: a hand-edited 0 would sweep every tick.
+            ping_base: std::time::Duration::from_secs(u64::from(crate::storage::settings::Settings::load_or_create().ping_base_s.max(1))),
             probed_handle: None,
             add_join_status: String::new(),
             add_join_words: None,
@@ -1174,14 +1486,27 @@ impl PhotonApp {
             device_avatar_scaled: None,
             device_avatar_scaled_diameter: 0,
             avatar_hit_id: HIT_NONE,
+            share_hit_id: HIT_NONE,
+            pending_link: None,
+            link_confirm_hit_id: HIT_NONE,
+            held_transfers: Vec::new(),
+            transfer_accept_hit_id: HIT_NONE,
+            our_handle: None,
             known_pick_hit: HIT_NONE,
             known_mine_hit: HIT_NONE,
             joiner_selected: false,
             pending_zoom_restore: None,
             avatar_set_rx: None,
+            retention_rx: None,
+            avatar_framing_rx: None,
+            avatar_framing: None,
+            device_avatar_anim: None,
             active_contact: None,
             contact_hit_base: HIT_NONE,
             back_btn_hit_id: HIT_NONE,
+            avatar_crop_hit_base: HIT_NONE,
+            archived_toggle_hit: HIT_NONE,
+            show_archived: false,
             join_startfresh_hit_id: HIT_NONE,
             join_copywords_hit_id: HIT_NONE,
             join_words_copied: false,
@@ -1206,12 +1531,45 @@ impl PhotonApp {
             contact_panel_btn_base: HIT_NONE,
             contact_nav_base: HIT_NONE,
             contact_boot_armed: false,
+            contact_wipe_armed: None,
+            verify_qr: None,
             exit_requested: false,
+            shut_down: false,
+            avatar_paste_armed: false,
+            contact_key_focus: None,
+            contact_key_reveal: false,
+            msg_select_anchor: None,
+            msg_find: None,
+            msg_row_lifts: Vec::new(),
+            msg_select_head: (0.0, 0.0),
+            msg_click_streak: message_select::ClickStreak::default(),
+            msg_select_unit: message_select::SelectUnit::Char,
+            msg_selection: None,
+            status_select_anchor: None,
+            status_select_head: (0.0, 0.0),
+            status_selection: None,
+            status_row: None,
+            msg_rows: Vec::new(),
+            msg_list_band: None,
+            chat_thumbs: std::collections::HashMap::new(),
+            chat_thumbs_of: [0u8; 32],
+            chat_image_expanded: None,
+            msg_menu: None,
+            msg_menu_rects: Vec::new(),
+            msg_press_at: None,
+            text_undo: None,
+            voice_recording: None,
+            voice_pending_for: None,
+            window_size: None,
+            window_maximized: false,
+            layout_unit: 0.,
             settings_btn_base: HIT_NONE,
             settings_theme_dropdown: None,
             settings_zoom_slider: None,
             settings_custodian_check: None,
             settings_chime_check: None,
+            settings_dnd_check: None,
+            settings_spell_check: None,
             settings_presence_check: None,
             settings_autoupdate_check: None,
             diag_log_view: false,
@@ -1230,6 +1588,8 @@ impl PhotonApp {
             fleet_release_armed: None,
             update_rx: None,
             update_tx: None,
+            chat_drop_rx: None,
+            chat_drop_tx: None,
             update_release: ChannelCheck::Idle,
             update_dev: ChannelCheck::Idle,
             update_checked: false,
@@ -1245,8 +1605,17 @@ impl PhotonApp {
             settings_rail_extent: 0.0,
             settings_content_extent: 0.0,
             contacts_scroll_extent: 0,
+            msg_scroll_extent: 0.0,
+            msg_newest_h: 0.0,
+            msg_jump_rect: None,
+            msg_ref_rects: Vec::new(),
+            mention: mention::Picker::default(),
+            mention_rects: Vec::new(),
+            spell_check: crate::storage::settings::Settings::load_or_create().spell_check,
+            dnd: alerts::Dnd::new(crate::storage::settings::Settings::load_or_create().dnd),
             settings_shred_armed: false,
             settings_removeshred_armed: false,
+            you_avatar_remove_armed: false,
             about_version_spelled: false,
         }
     }
@@ -1270,8 +1639,84 @@ impl PhotonApp {
         s
     }
 
-    /// Encode + save + reload an avatar image picked from the OS image picker. Pipeline: raw file bytes → `encode_avatar_from_image` (handles JPEG/PNG/WebP and the ICC-profile colour management — VSF spectral γ=2.0 RGB out) → `save_avatar` (encrypted handle-keyed storage) → `load_avatar` (round-trip check) → `vsf_rgb_to_bt2020` (display conversion for the Android BT.2020 buffer tag) → installed as `device_avatar_pixels` with the scaled cache invalidated. Uploads to FGTW when a `handle_proof` is available so other devices can fetch it. Skipped if the user hasn't attested yet (no handle to derive the storage key from).
-    pub fn set_avatar_from_file(&mut self, image_bytes: Vec<u8>) {
+    /// Install this device's avatar (display colour): frame 0 becomes `device_avatar_pixels` — the still every surface shows — and a multi-frame clip starts looping on the Ready screen. Android keeps the first frame: a looping avatar would hold the render loop awake on battery.
+    fn install_device_avatar(&mut self, anim: crate::ui::avatar::AvatarAnimation) {
+        self.device_avatar_pixels = anim.frames.first().cloned();
+        self.device_avatar_scaled = None;
+        self.device_avatar_scaled_diameter = 0;
+        self.device_avatar_anim = (anim.is_animated() && !cfg!(target_os = "android")).then(|| (anim, Instant::now(), 0));
+    }
+
+    /// Redraw hook for an image thumbnail decoding on its worker (`ChatThumb::pixels`): posts `ChatImageDecoded` once the pixels land, so the row fills in without waiting for unrelated input.
+    fn thumb_waker(&self) -> impl FnOnce() + Send + 'static {
+        let wake = self.event_proxy.clone();
+        move || {
+            if let Some(w) = wake.as_ref() {
+                let _ = w.send(crate::ui::PhotonEvent::ChatImageDecoded);
+            }
+        }
+    }
+
+    /// A new avatar image (picked, dropped or pasted) goes to the crop screen first: decoded upright off-thread, then tick opens [`AppState::AvatarCrop`] with the largest centred circle framed. Use hands the framed square to `set_avatar_from_file`. Ignored before attest, same as the set itself.
+    pub fn frame_avatar(&mut self, image_bytes: Vec<u8>) {
+        if self.session.is_none() {
+            crate::log("avatar picker: ignored — not attested yet");
+            return;
+        }
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.avatar_framing_rx = Some(rx);
+        let wake = self.event_proxy.clone();
+        std::thread::spawn(move || {
+            let framing = crate::ui::avatar::decode_oriented(&image_bytes).map(|img| {
+                let dims = (img.width() as usize, img.height() as usize);
+                let preview = if img.width().max(img.height()) > AVATAR_PREVIEW_EDGE {
+                    img.thumbnail(AVATAR_PREVIEW_EDGE, AVATAR_PREVIEW_EDGE).to_rgb8()
+                } else {
+                    img.to_rgb8()
+                };
+                AvatarFraming {
+                    bytes: image_bytes,
+                    dims,
+                    preview,
+                    circle: avatar_crop::Circle::fit(dims.0, dims.1),
+                    view: None,
+                    drag: None,
+                    scaled: None,
+                }
+            });
+            let _ = tx.send(framing);
+            if let Some(w) = wake.as_ref() {
+                let _ = w.send(crate::ui::PhotonEvent::NetworkUpdate);
+            }
+        });
+    }
+
+    /// Leave the crop screen for Ready — with the framed square set as the avatar on `use_it`, else dropping the image.
+    fn finish_avatar_framing(&mut self, use_it: bool) {
+        self.state = AppState::Ready;
+        if let Some(framing) = self.avatar_framing.take().filter(|_| use_it) {
+            let region = framing.circle.region(framing.dims.0, framing.dims.1);
+            crate::logf!("avatar picker: framed {}px square at ({}, {})", region.side, region.x, region.y);
+            self.set_avatar_from_file(framing.bytes, Some(region));
+        }
+        self.scene_dirty = true;
+        if let Some(chrome) = self.chrome.as_mut() {
+            chrome.invalidate_bg();
+            chrome.invalidate_chrome();
+        }
+    }
+
+    /// Crop screen −/+ and wheel: grow (`out`) or shrink the circle one [`avatar_crop::ZOOM_STEP`].
+    fn zoom_avatar_framing(&mut self, out: bool) {
+        if let Some(f) = self.avatar_framing.as_mut() {
+            let factor = if out { avatar_crop::ZOOM_STEP } else { 1.0 / avatar_crop::ZOOM_STEP };
+            f.circle = f.circle.zoom(factor, f.dims.0, f.dims.1);
+            self.scene_dirty = true;
+        }
+    }
+
+    /// Encode + save + reload an avatar image picked from the OS image picker. Pipeline: raw file bytes → `encode_avatar_from_image` (handles JPEG/PNG/WebP/GIF/TIFF, HEIC on desktop builds with the `heic` feature, and the ICC-profile colour management — VSF spectral γ=2.0 RGB out) → `save_avatar` (encrypted handle-keyed storage) → `load_avatar` (round-trip check) → `vsf_rgb_to_bt2020` (display conversion for the Android BT.2020 buffer tag) → installed as `device_avatar_pixels` with the scaled cache invalidated. Uploads to FGTW when a `handle_proof` is available so other devices can fetch it. Skipped if the user hasn't attested yet (no handle to derive the storage key from). `crop` is the square framed on the crop screen (`frame_avatar`); `None` takes the centred square.
+    pub fn set_avatar_from_file(&mut self, image_bytes: Vec<u8>, crop: Option<avatar_crop::Region>) {
         let identity_seed = match &self.session {
             Some(s) => s.identity_seed,
             None => {
@@ -1314,6 +1759,10 @@ impl PhotonApp {
         let avatar_pin = Some(new_pin);
         // Sibling ding: bump the fleet-synced avatar stamp so the fstate event wakes the fleet and their next avatar sync pulls the fresh copy. Bumped at SET time — a sibling racing the upload just gets the old copy once and heals on the next sync (newest-wins).
         self.settings_set("profile.avatar_ts", vsf::eagle_time_oscillations().to_le_bytes().to_vec());
+        // A fresh set supersedes an earlier removal — siblings sync again.
+        if self.avatar_removed_at().is_some() {
+            self.settings_set("profile.avatar_removed", Vec::new());
+        }
         let kp = self.device_keypair.clone();
         let (px_tx, px_rx) = std::sync::mpsc::channel();
         self.avatar_set_rx = Some(px_rx);
@@ -1321,20 +1770,35 @@ impl PhotonApp {
         std::thread::spawn(move || {
             #[cfg(not(target_os = "redox"))]
             let _ = thread_priority::set_current_thread_priority(thread_priority::ThreadPriority::Min);
-            let rgb_f32 = match crate::ui::avatar::image_to_avatar_rgb_f32(&image_bytes) {
-                Ok(p) => p,
-                Err(e) => {
-                    crate::logf!("avatar picker: decode failed: {}", e);
-                    return;
-                }
+            // A multi-frame GIF / WebP becomes a looping clip; anything else (or a clip that fails to decode) takes the still path.
+            let clip = crate::ui::avatar::image_to_avatar_frames(&image_bytes, crop).unwrap_or_else(|e| {
+                crate::logf!("avatar picker: animation decode failed, trying as a still: {}", e);
+                None
+            });
+            let (frames, durations_ms) = match clip {
+                Some(clip) => clip,
+                None => match crate::ui::avatar::image_to_avatar_rgb_f32(&image_bytes, crop) {
+                    Ok(p) => (vec![p], vec![0]),
+                    Err(e) => {
+                        crate::logf!("avatar picker: decode failed: {}", e);
+                        return;
+                    }
+                },
             };
             // Display pixels first — the UI installs them the next tick; the grind continues below.
-            let vsf_rgb = crate::ui::avatar::avatar_rgb_f32_to_u8(&rgb_f32);
-            let _ = px_tx.send(crate::ui::colour_convert::vsf_rgb_to_bt2020(&vsf_rgb));
+            let _ = px_tx.send(crate::ui::avatar::AvatarAnimation {
+                size: crate::ui::avatar::AVATAR_SIZE,
+                frames: frames
+                    .iter()
+                    .map(|f| crate::ui::colour_convert::vsf_rgb_to_bt2020(&crate::ui::avatar::avatar_rgb_f32_to_u8(f)))
+                    .collect(),
+                durations_ms: durations_ms.clone(),
+            });
             if let Some(w) = wake.as_ref() {
                 let _ = w.send(crate::ui::PhotonEvent::NetworkUpdate);
             }
-            let av1_data = match crate::ui::avatar::encode_avatar_rgb_f32(&rgb_f32) {
+            // The shared slot (uploaded, adopted by siblings, fetched by friends) always gets the plain AV1 still of frame 0 — the format every client reads. A clip goes to this device's own slot beside it (see `encode_animated_avatar`).
+            let av1_data = match crate::ui::avatar::encode_avatar_rgb_f32(&frames[0]) {
                 Ok(d) => d,
                 Err(e) => {
                     crate::logf!("avatar picker: encode failed (display keeps the picked image this session): {}", e);
@@ -1347,6 +1811,16 @@ impl PhotonApp {
                 crate::logf!("avatar picker: save failed: {}", e);
                 return;
             }
+            let clip = if frames.len() > 1 {
+                crate::logf!("avatar picker: encoding a {}-frame animated avatar", frames.len());
+                crate::ui::avatar::encode_animated_avatar(&frames, &durations_ms)
+                    .and_then(|packed| crate::ui::avatar::save_avatar_animation_from_seed(&packed, &identity_seed, &storage).map_err(|e| e.to_string()))
+            } else {
+                crate::ui::avatar::delete_avatar_animation_from_seed(&identity_seed, &storage).map_err(|e| e.to_string())
+            };
+            if let Err(e) = clip {
+                crate::logf!("avatar picker: clip slot not updated (the still stands): {}", e);
+            }
             crate::log("avatar picker: encoded + saved");
             match (kp, proof, avatar_pin) {
                 (Some(kp), Some(hp), Some(pin)) => {
@@ -1423,45 +1897,6 @@ fn dev_gradient_orb() -> fluor::host::icon::Icon {
     fluor::host::icon::Icon { width: N, height: N, pixels }
 }
 
-/// The default UNSET avatar: a deterministic per-identity gradient (seeded from the public proof), spherical-shaded like the dev orb, as a `diam×diam×3` visible-RGB buffer for `draw_avatar`. Replaces the flat grey placeholder — everyone without a set avatar shows a distinct little lit orb keyed to their identity, identical on every device that knows the proof. Each channel is its own plane `z = a·x + b·y` (`a,b ∈ [-1,1]`, raw clamped z, no normalization) × the dome `√(1−x_c²−y_c²)`.
-fn gradient_avatar_rgb(mut seed: u64, diam: usize) -> Vec<u8> {
-    fn splitmix(state: &mut u64) -> u64 {
-        *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
-        let mut z = *state;
-        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
-        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
-        z ^ (z >> 31)
-    }
-    let unit = |s: &mut u64| (splitmix(s) >> 11) as f64 / (1u64 << 53) as f64; // [0,1)
-    // Slopes CUBED: still [-1,1] (extremes still reach the full [-4pi,4pi] → 4 periods) but concentrated near zero, so most channels are gentle low-frequency gradients — not a busy 4x plaid.
-    let (r_a, r_b) = ((unit(&mut seed) * 2.0 - 1.0).powi(3), (unit(&mut seed) * 2.0 - 1.0).powi(3));
-    let (g_a, g_b) = ((unit(&mut seed) * 2.0 - 1.0).powi(3), (unit(&mut seed) * 2.0 - 1.0).powi(3));
-    let (b_a, b_b) = ((unit(&mut seed) * 2.0 - 1.0).powi(3), (unit(&mut seed) * 2.0 - 1.0).powi(3));
-    // Each channel is a sine plane wave: disk mapped to [-4pi, 4pi] per axis, z = a*x + b*y, then (sin(z)+1)/2 -> [0,1] (bounded, no clip); circle vignette over the top.
-    let denom = diam.saturating_sub(1).max(1) as f64;
-    let s = 4.0 * std::f64::consts::PI;
-    let mut out = vec![0u8; diam * diam * 3];
-    for py in 0..diam {
-        for px in 0..diam {
-            let xc = 2.0 * (px as f64 / denom) - 1.0;
-            let yc = 2.0 * (py as f64 / denom) - 1.0;
-            let (x, y) = (xc * s, yc * s);
-            let vignette = (1.0 - xc * xc - yc * yc).max(0.0).sqrt();
-            let ch = |a: f64, b: f64| ((((a * x + b * y).sin() + 1.0) / 2.0) * vignette * 255.0) as u8;
-            let i = (py * diam + px) * 3;
-            out[i] = ch(r_a, r_b);
-            out[i + 1] = ch(g_a, g_b);
-            out[i + 2] = ch(b_a, b_b);
-        }
-    }
-    out
-}
-
-/// Seed a gradient from a 32-byte public proof (`handle_proof`) — deterministic per identity.
-fn proof_gradient_seed(proof: &[u8; 32]) -> u64 {
-    u64::from_le_bytes(proof[..8].try_into().unwrap())
-}
-
 /// Map a connectivity bool to the chrome orb tint. Offline = red disk, online = green disk. Visible RGB chosen for high contrast in either light or dark chrome themes; brighten=true on the online state for the eventual icon-overlay case (no-icon today just renders as a solid coloured circle).
 fn orb_tint_for(online: bool) -> fluor::host::chrome::OrbTint {
     // Visible RGB(64, 224, 64) green: darkness = (0xBF, 0x1F, 0xBF); packed α=0xFF. Visible RGB(224, 64, 64) red:   darkness = (0x1F, 0xBF, 0xBF); packed α=0xFF.
@@ -1483,6 +1918,24 @@ struct AddCandidate {
     heard_ble: bool,
 }
 
+/// The crop screen's image: the original file bytes (what the encoder still decodes at full size), its upright source dimensions, a bounded preview, and the circle being framed in source pixels.
+struct AvatarFraming {
+    bytes: Vec<u8>,
+    dims: (usize, usize),
+    /// The upright image, long edge at most [`AVATAR_PREVIEW_EDGE`] — a 50MP photo is never resampled per frame.
+    preview: image::RgbImage,
+    circle: avatar_crop::Circle,
+    /// Where the last frame drew the image — the drag converts pointer motion back to source pixels thru it.
+    view: Option<avatar_crop::View>,
+    /// Pointer position while the image is grabbed.
+    drag: Option<(f32, f32)>,
+    /// The preview resampled to its on-screen size, rebuilt when that size changes.
+    scaled: Option<(usize, usize, Vec<u8>)>,
+}
+
+/// Long-edge cap of the crop screen's preview.
+const AVATAR_PREVIEW_EDGE: u32 = 1 << 10;
+
 /// Off-thread results for the AddDevice flow (candidate watch + bind + rotate), drained in `tick`.
 enum AddDeviceUpdate {
     /// A fresh, signature-verified candidate set from the binding-request registry (the watch thread's periodic/hub-poked list).
@@ -1575,7 +2028,11 @@ impl PhotonApp {
                         && (c.chain_woven || c.handle_hash == our_handle_hash)
                 })
                 .unwrap_or(false);
-            if compose_ready {
+            if self.msg_find.is_some() {
+                if let Some(tb) = self.find_textbox.as_mut() {
+                    f(tb);
+                }
+            } else if compose_ready {
                 if let Some(tb) = self.message_textbox.as_mut() {
                     f(tb);
                 }
@@ -1595,6 +2052,9 @@ impl PhotonApp {
                     if let Some(sl) = self.settings_zoom_slider.as_mut() {
                         f(sl);
                     }
+                    if let Some(cb) = self.settings_spell_check.as_mut() {
+                        f(cb);
+                    }
                 }
                 SettingsPage::Recovery => {
                     if let Some(cb) = self.settings_custodian_check.as_mut() {
@@ -1605,6 +2065,9 @@ impl PhotonApp {
                     if let Some(cb) = self.settings_chime_check.as_mut() {
                         f(cb);
                     }
+                    if let Some(cb) = self.settings_dnd_check.as_mut() {
+                        f(cb);
+                    }
                     if let Some(cb) = self.settings_presence_check.as_mut() {
                         f(cb);
                     }
@@ -1639,6 +2102,59 @@ impl PhotonApp {
     }
 }
 
+/// Scripting socket over the live app: a send rides the same `send_message` path as the compose box, so it's encrypted, persisted and shown exactly like a typed message.
+#[cfg(not(target_os = "android"))]
+impl crate::platform::rpc::Target for PhotonApp {
+    fn contact_rows(&self) -> Vec<serde_json::Value> {
+        self.contacts.iter().filter(|c| !c.is_sibling).map(crate::platform::rpc::contact_row).collect()
+    }
+
+    fn send_text(&mut self, handle: &str, text: &str) -> Result<bool, String> {
+        let pid = crate::crypto::clutch::identity_party_id(&crate::types::Handle::to_identity_seed(handle));
+        let Some(ci) = self.contacts.iter().position(|c| !c.is_sibling && c.handle_hash == pid) else {
+            return Err("not a contact".to_string());
+        };
+        self.send_message(ci, text).map_err(|e| format!("not sent — {}", e))?;
+        self.scene_dirty = true;
+        Ok(true)
+    }
+
+    fn online(&self, handle: &str) -> Option<bool> {
+        let pid = crate::crypto::clutch::identity_party_id(&crate::types::Handle::to_identity_seed(handle));
+        self.contacts.iter().find(|c| !c.is_sibling && c.handle_hash == pid).map(|c| c.is_online)
+    }
+
+    fn export_backup(&self, passphrase: &str) -> Result<Vec<u8>, String> {
+        let storage = self.storage.as_ref().ok_or("no profile open")?;
+        crate::storage::backup::export_all(storage, passphrase).map_err(|e| e.to_string())
+    }
+
+    /// Merged into the vault first, then into memory: contacts the archive added join the list with their history and chains, and a known contact's conversation is reloaded to pick up the rows it gained.
+    fn import_backup(&mut self, archive: &[u8], passphrase: &str) -> Result<crate::storage::backup::ImportReport, String> {
+        let storage = self.storage.clone().ok_or("no profile open")?;
+        let report = crate::storage::backup::import_all(&storage, archive, passphrase).map_err(|e| e.to_string())?;
+        for mut restored in crate::storage::contacts::load_all_contacts(&storage) {
+            if let Err(e) = crate::storage::contacts::load_messages(&mut restored, &storage) {
+                crate::logf!("BACKUP: failed to load restored messages for {}: {}", crate::fp(&restored.handle_proof).as_str(), e);
+            }
+            match self.contacts.iter_mut().find(|c| !c.is_sibling && c.handle_hash == restored.handle_hash) {
+                Some(known) => known.messages = restored.messages,
+                None => self.contacts.push(restored),
+            }
+        }
+        let friendship_ids: Vec<crate::types::FriendshipId> = self.contacts.iter().filter_map(|c| c.friendship_id).collect();
+        for (fid, chains) in crate::storage::friendship::load_all_friendships(&friendship_ids, &storage) {
+            if !self.friendship_chains.iter().any(|(id, _)| *id == fid) {
+                self.friendship_chains.push((fid, chains));
+            }
+        }
+        self.update_sync_records();
+        self.reseed_contact_pubkeys();
+        self.scene_dirty = true;
+        Ok(report)
+    }
+}
+
 impl FluorApp for PhotonApp {
     /// One-shot absolute-zoom restore: the persisted per-device `display.zoom`, set when settings load; the host applies it exactly like a user zoom.
     fn take_zoom_request(&mut self) -> Option<f32> {
@@ -1653,6 +2169,11 @@ impl FluorApp for PhotonApp {
     }
 
     fn initial_size(&self, monitor: (u32, u32)) -> (u32, u32) {
+        // The size the user left the window at last close wins, fitted onto TODAY's monitor (it may be smaller than the one it was saved on). The host places and sizes the window from this alone (no position hook); a maximized close is re-maximized in `init`, over this normal size.
+        let saved = crate::storage::settings::Settings::load_or_create();
+        if let Some(size) = crate::storage::settings::clamp_window_size((saved.window_w, saved.window_h), monitor) {
+            return size;
+        }
         // Portrait launch window — matches the pre-fluor Photon dimensions: height = half the SHORTER screen axis, width = half that. Yields a tall 1:2 (w:h) rectangle on any aspect ratio. Examples: 1920×1080 → 270×540; 1080×1920 → 270×540; 2560×1440 → 360×720.
         let short = monitor.0.min(monitor.1);
         let h = short >> 1;
@@ -1675,6 +2196,12 @@ impl FluorApp for PhotonApp {
         #[cfg(not(target_os = "android"))]
         {
             crate::platform::control::spawn_accept_thread(proxy.clone());
+            // Scripting socket (list contacts / send / status), token-gated — same lock-holder-only reasoning as the control channel.
+            if let Ok(dir) = crate::storage::photon_config_dir() {
+                crate::platform::rpc::spawn(&dir, proxy.clone());
+            }
+            // The global "bring Photon forward" chord from settings.vsf (empty = off; a taken chord logs and the app runs without it).
+            crate::platform::hotkey::spawn(&crate::storage::settings::Settings::load_or_create().hotkey, proxy.clone());
             // Resident from launch → the orb parks next to the clock now; a later toggle-on spawns it then (tray_spawned gates the once-per-process).
             if self.resident_mode {
                 crate::platform::tray::spawn(proxy.clone());
@@ -1689,29 +2216,65 @@ impl FluorApp for PhotonApp {
     }
 
     fn on_close_requested(&mut self) -> bool {
+        // Every close (exit OR resident hide) is the persistence point for the window geometry — event-driven, no write per resize-drag frame.
+        self.save_window_geometry();
         // Shift+Escape's one-shot exit override: the user asked for the REAL close, so decline residency this once and let the host exit.
         if self.exit_requested {
             crate::log("EXIT: deliberate quit (Shift+Escape) — bypassing resident hide");
+            self.shutdown();
             return false;
         }
         // Resident mode: close = hide, keep running (network, timers, notifications). The host does the set_visible(false); we track "nobody's looking" for the notification gate. Non-resident closes exit as ever.
-        if self.resident_mode {
-            #[cfg(not(target_os = "android"))]
-            crate::platform::desktop_notify::set_window_visible(false);
-            crate::log("RESIDENT: window hidden on close — still running; launch photon again to surface it");
-            true
-        } else {
-            false
+        #[cfg(not(target_os = "android"))]
+        {
+            use crate::platform::tray::{close_action, CloseAction};
+            match close_action(self.resident_mode, self.exit_requested) {
+                CloseAction::Hide => {
+                    crate::platform::desktop_notify::set_window_visible(false);
+                    crate::log("RESIDENT: window hidden on close — still running; tray icon or relaunch surfaces it");
+                    true
+                }
+                CloseAction::Exit => {
+                    self.shutdown();
+                    false
+                }
+            }
+        }
+        #[cfg(target_os = "android")]
+        {
+            if !self.resident_mode {
+                self.shutdown();
+            }
+            self.resident_mode
         }
     }
 
     fn on_user_event(&mut self, event: PhotonEvent, _ctx: &mut Context) -> EventResponse {
-        if matches!(event, PhotonEvent::ShowWindow) {
+        // ShowWindow (second launch / tray menu) and FocusRequested (global hotkey) both surface + focus the window, wherever it was hidden.
+        if matches!(event, PhotonEvent::ShowWindow | PhotonEvent::FocusRequested) {
             #[cfg(not(target_os = "android"))]
             crate::platform::desktop_notify::set_window_visible(true);
             self.scene_dirty = true;
             return EventResponse::ShowWindow;
         }
+        // Tray left-click: a shown window hides (the same resident close path as the title-bar button); a hidden one surfaces like ShowWindow.
+        if matches!(event, PhotonEvent::ToggleWindow) {
+            #[cfg(not(target_os = "android"))]
+            {
+                if crate::platform::desktop_notify::window_visible() && self.resident_mode {
+                    return EventResponse::Close;
+                }
+                crate::platform::desktop_notify::set_window_visible(true);
+            }
+            self.scene_dirty = true;
+            return EventResponse::ShowWindow;
+        }
+        if matches!(event, PhotonEvent::ChatImageDecoded) {
+            self.scene_dirty = true;
+        }
+        if let PhotonEvent::PeerUpdateLink(link) = event {
+            self.on_peer_update_link(link);
+        }
         // Every other variant is a pure wake — the loop's tick drains whatever channel the sender filled.
         EventResponse::Pass
     }
@@ -1760,6 +2323,11 @@ impl FluorApp for PhotonApp {
         // Top-left orb's ring doubles as the FGTW connectivity indicator. Initialize red/offline; `try_recv_online` flips to green once the FGTW reports the device is reachable.
         chrome.set_orb_tint(orb_tint_for(false));
         self.chrome = Some(chrome);
+        // A window closed maximized reopens maximized. `initial_size` already sized it to the saved NORMAL size, so un-maximizing returns there; the resulting resize syncs full_edge + `window_maximized` like any user maximize.
+        #[cfg(not(target_os = "android"))]
+        if crate::storage::settings::Settings::load_or_create().window_maximized {
+            ctx.window.set_maximized(true);
+        }
 
         // Launch-screen widgets: handle textbox + attest button. Constructed with placeholder geometry; real geometry lands in `update_widget_layout` (called below and on every resize). Hit IDs are allocated from the shared counter AFTER chrome's four — chrome currently takes 1..=4, launch widgets get 5..=6, contacts widgets get 7..=8.
         self.textbox = Some(Textbox::new(&mut self.hit_counter, 0., 0., 1., 1., 12.));
@@ -1779,6 +2347,8 @@ impl FluorApp for PhotonApp {
         self.message_textbox = Some(Textbox::new(&mut self.hit_counter, 0., 0., 1., 1., 12.));
         // Send button overlaid in the compose box. ASCII ">" (not "→" U+2192 — absent from the Android font, so it rendered blank there; the contacts "+" button proves ASCII renders). Geometry set each frame in `update_widget_layout`. Empty label — the glyph is a drawn 4-vertex up arrowhead (draw_up_arrowhead), not text.
         self.message_send_btn = Some(Button::new(&mut self.hit_counter, 0., 0., 1., 1., 12., ""));
+        // Find-bar query box — laid out on the compose box's rect, which it replaces while the bar is open.
+        self.find_textbox = Some(Textbox::new(&mut self.hit_counter, 0., 0., 1., 1., 12.));
         // Specific subtle hover for the two overlay-in-textbox action buttons (pre-fluor per-control hover colours), instead of the generic saturated BUTTON_HOVER. Held = the SAME subtle fill: these fire on release, so a press must read as "nothing happened yet" — the default BUTTON_HELD ramp flashed a heavy fill mid-press (the "+" ticket).
         if let Some(b) = self.contacts_plus_btn.as_mut() {
             b.set_hover_fill(Some(*theme::SEND_BUTTON_HOVER));
@@ -1791,6 +2361,15 @@ impl FluorApp for PhotonApp {
         // Reserve a hit-id for the Ready-screen avatar circle. Not a Widget — the avatar is just a paint primitive — so click dispatch is handled directly in `on_event`'s MouseInput::Pressed arm, not thru `widget::dispatch_click`. Incrementing the shared counter keeps the contiguous-id contract intact for the `[]h` debug overlay.
         self.hit_counter = self.hit_counter.wrapping_add(1);
         self.avatar_hit_id = self.hit_counter;
+        // "Copy my handle" label on Ready — a plain hit rect, dispatched in on_activate.
+        self.hit_counter = self.hit_counter.wrapping_add(1);
+        self.share_hit_id = self.hit_counter;
+        // "Add from link" confirm prompt in the Ready hint slot — a plain hit rect, dispatched in on_activate.
+        self.hit_counter = self.hit_counter.wrapping_add(1);
+        self.link_confirm_hit_id = self.hit_counter;
+        // Held-transfer "tap to download" prompt, same slot, same dispatch.
+        self.hit_counter = self.hit_counter.wrapping_add(1);
+        self.transfer_accept_hit_id = self.hit_counter;
         // KnownHandle fork pills (pick-another / it's-mine) — plain hit rects like the avatar circle, dispatched in the Pressed arm.
         self.hit_counter = self.hit_counter.wrapping_add(1);
         self.known_pick_hit = self.hit_counter;
@@ -1800,9 +2379,16 @@ impl FluorApp for PhotonApp {
         self.hit_counter = self.hit_counter.wrapping_add(1);
         self.contact_hit_base = self.hit_counter;
         self.hit_counter = self.hit_counter.wrapping_add(255);
+        // The contacts list's archived-filter link.
+        self.hit_counter = self.hit_counter.wrapping_add(1);
+        self.archived_toggle_hit = self.hit_counter;
         // Back button on conversation screen.
         self.hit_counter = self.hit_counter.wrapping_add(1);
         self.back_btn_hit_id = self.hit_counter;
+        // Avatar crop screen: four pills + the image, dispatched in on_activate (pills) and the Pressed arm (the image's drag).
+        self.hit_counter = self.hit_counter.wrapping_add(1);
+        self.avatar_crop_hit_base = self.hit_counter;
+        self.hit_counter = self.hit_counter.wrapping_add(4);
 
         // "Start fresh (wipe this device)" tappable on the JOIN words screen — the only clean path for a device that was REMOVED from a fleet and so can't attest (can't reach the Security page). Two-tap confirm → clean_device_for_reuse.
         self.hit_counter = self.hit_counter.wrapping_add(1);
@@ -1830,7 +2416,7 @@ impl FluorApp for PhotonApp {
         self.hit_counter = self.hit_counter.wrapping_add(31); // pills 0..=31
         self.hit_counter = self.hit_counter.wrapping_add(1);
         self.contact_panel_btn_base = self.hit_counter;
-        self.hit_counter = self.hit_counter.wrapping_add(3); // contact-panel pills 0..=3 (0 = Boot)
+        self.hit_counter = self.hit_counter.wrapping_add(11); // contact-panel pills 0..=11 (0 = Boot)
         self.hit_counter = self.hit_counter.wrapping_add(1);
         self.contact_nav_base = self.hit_counter;
         self.hit_counter = self.hit_counter.wrapping_add(3); // contact-panel rail rows 0..=3
@@ -1845,6 +2431,16 @@ impl FluorApp for PhotonApp {
         ));
         self.settings_zoom_slider =
             Some(fluor::widgets::Slider::new(&mut self.hit_counter, 0., 0., 1., 1., 0.5));
+        self.settings_spell_check = Some(crate::ui::settings_widgets::Checkbox::new(
+            &mut self.hit_counter,
+            "Spell-check long messages",
+            0.,
+            0.,
+            1.,
+            1.,
+            12.,
+            self.spell_check,
+        ));
         self.settings_custodian_check = Some(crate::ui::settings_widgets::Checkbox::new(
             &mut self.hit_counter,
             "Be a custodian for others",
@@ -1865,6 +2461,16 @@ impl FluorApp for PhotonApp {
             12.,
             true,
         ));
+        self.settings_dnd_check = Some(crate::ui::settings_widgets::Checkbox::new(
+            &mut self.hit_counter,
+            "Do not disturb",
+            0.,
+            0.,
+            1.,
+            1.,
+            12.,
+            self.dnd.is_on(),
+        ));
         // DEFAULTS OFF (user mandate): "presence" is the rich self-disclosure broadcast (busy, now-playing, mood) — NOT the online indicator, which is the avatar ring and is never gated by this. Deliberate disclosure is opt-in.
         self.settings_presence_check = Some(crate::ui::settings_widgets::Checkbox::new(
             &mut self.hit_counter,
@@ -2011,6 +2617,11 @@ impl FluorApp for PhotonApp {
             }
             Err(e) => crate::logf!("UI: status checker failed to start: {}", e),
         }
+        // Live address pushes from FGTW; its link state comes back as PhotonEvent::PeerUpdateLink.
+        #[cfg(not(target_os = "android"))]
+        {
+            self.peer_updates = Some(crate::network::PeerUpdateClient::new(proxy.clone()));
+        }
 
         self.handle_query = Some(hq);
 
@@ -2022,11 +2633,7 @@ impl FluorApp for PhotonApp {
             if let Some(kp) = &self.device_keypair {
                 let device_secret = *kp.secret.as_bytes();
                 // open_shared, NEVER new: query_resume below spawns the attest worker, which opens this same vault — a second independent engine racing this one is how the vault corruption happened (stale engine committed over the live one's blocks → seal verification failed at every subsequent open).
-                match crate::storage::FlatStorage::open_shared(
-                    crate::storage::APP,
-                    remembered.vault_seed,
-                    device_secret,
-                ) {
+                match crate::storage::compact::open_shared(remembered.vault_seed, device_secret) {
                     Ok(s) => {
                         // Preserve any IN-FLIGHT ceremony round across this reload. CLUTCH keypairs/slots are ephemeral scratch, so a wholesale reload from disk wipes a live round — and a warm resume (Android foregrounds constantly) then trips the keygen sweep into minting a DIVERGENT round the peer never agreed to. That is exactly what stranded the relay ceremony: the slow relay round-trip outlived the keys, the peer's KEM came back addressed to keys we'd already discarded, and it was dropped as "old keys". Re-key must be deliberate on real failure — never a side effect of a lifecycle event. Snapshot rounds that are still FRESH by eagle time (a genuinely stale one is let go, to be re-keyed cleanly) and restore them after the reload.
                         let now = vsf::eagle_time_oscillations();
@@ -2096,11 +2703,18 @@ impl FluorApp for PhotonApp {
                             self.contacts.iter().filter_map(|c| c.friendship_id).collect();
                         let loaded_chains =
                             crate::storage::friendship::load_all_friendships(&friendship_ids, &s);
-                        for (fid, chains) in loaded_chains {
+                        let now_osc = vsf::eagle_time_oscillations();
+                        let mut requeued = 0;
+                        for (fid, mut chains) in loaded_chains {
                             if !self.friendship_chains.iter().any(|(id, _)| *id == fid) {
+                                // Outgoing queue: whatever was unacked when we went down goes straight back on the retransmit sweep.
+                                requeued += chains.requeue_after_restart(now_osc);
                                 self.friendship_chains.push((fid, chains));
                             }
                         }
+                        if requeued > 0 {
+                            crate::logf!("CHAT: re-queued {} unacked outgoing msg(s) from the last session", requeued);
+                        }
                         self.update_sync_records();
                         // Seed the checker's answerable-pubkey set with every loaded contact's FULL fleet so pongs/offers from any of their devices are honoured.
                         self.reseed_contact_pubkeys();
@@ -2133,13 +2747,19 @@ impl FluorApp for PhotonApp {
                         self.storage = Some(s);
                         // Load this device's avatar from the vault now that storage exists, and colour-convert it for the Ready screen. The vault read needs the just-built storage handle, so this can't run before storage init like the old filesystem path did.
                         if let Some(storage) = self.storage.as_ref() {
-                            self.device_avatar_pixels = crate::ui::avatar::load_avatar_from_seed(
-                                &remembered.identity_seed,
-                                storage,
-                            )
-                            .map(|(_, vsf_rgb)| {
-                                crate::ui::colour_convert::vsf_rgb_to_bt2020(&vsf_rgb)
-                            });
+                            match crate::ui::avatar::load_avatar_animation_from_seed(&remembered.identity_seed, storage) {
+                                Some(anim) => self.install_device_avatar(
+                                    anim.map_frames(crate::ui::colour_convert::vsf_rgb_to_bt2020),
+                                ),
+                                // Undecodable entries go thru the still load, which evicts them.
+                                None => {
+                                    self.device_avatar_pixels = crate::ui::avatar::load_avatar_from_seed(
+                                        &remembered.identity_seed,
+                                        storage,
+                                    )
+                                    .map(|(_, vsf_rgb)| crate::ui::colour_convert::vsf_rgb_to_bt2020(&vsf_rgb));
+                                }
+                            }
                         }
                         // Local vault had no avatar (e.g. this device was cleared) — recover our own from FGTW, where it was published. Off-thread; installs via the avatar drain.
                         if self.device_avatar_pixels.is_none() {
@@ -2178,6 +2798,27 @@ impl FluorApp for PhotonApp {
             #[cfg(not(target_os = "android"))]
             chrome.set_full_edge(ctx.is_maximized);
         }
+        // Track the geometry close will persist. Only a NORMAL resize updates the size; a maximize keeps the last user-chosen size underneath it.
+        #[cfg(not(target_os = "android"))]
+        {
+            self.window_maximized = ctx.is_maximized;
+            if !ctx.is_maximized {
+                self.window_size = Some((ctx.viewport.width_px as u32, ctx.viewport.height_px as u32));
+            }
+        }
+        // The host folds a monitor scale-factor change into a resize with the new physical size, so this is where a DPI hop lands. Layouts and font sizes re-derive from the viewport below; the pixel scroll offsets are the only state that doesn't, so carry them across in layout units.
+        let unit = ReadyLayout::compute(ctx.viewport.width_px as usize, ctx.viewport.height_px as usize, ctx.viewport.ru).unit_height;
+        if self.layout_unit > 0. && unit != self.layout_unit {
+            let (from, to) = (self.layout_unit, unit);
+            self.contacts_scroll = ReadyLayout::rescale_offset(self.contacts_scroll as f32, from, to) as isize;
+            self.settings_rail_scroll = ReadyLayout::rescale_offset(self.settings_rail_scroll, from, to);
+            self.settings_content_scroll = ReadyLayout::rescale_offset(self.settings_content_scroll, from, to);
+            for contact in &mut self.contacts {
+                contact.message_scroll_offset = ReadyLayout::rescale_offset(contact.message_scroll_offset, from, to);
+            }
+            self.scene_dirty = true;
+        }
+        self.layout_unit = unit;
         self.update_widget_layout(ctx);
     }
 
@@ -2190,17 +2831,63 @@ impl FluorApp for PhotonApp {
         mods: fluor::event::ModifiersState,
         ctx: &mut Context,
     ) -> EventResponse {
+        // A contact link waits on this press: its prompt adds the contact, any other activation lets the link go.
+        if let Some(card) = self.pending_link.take() {
+            self.scene_dirty = true;
+            if hit_id == self.link_confirm_hit_id && self.link_confirm_hit_id != HIT_NONE {
+                if self.contacts.iter().any(|c| !c.is_sibling && c.handle_hash == card.party_id) {
+                    self.ready_toast = Some("Already in your contacts".to_string());
+                } else {
+                    self.add_contact_from_card(&card, false);
+                    self.ready_toast = Some("Contact added from link \u{2014} compare safety numbers to verify".to_string());
+                }
+                ctx.window.request_redraw();
+                return EventResponse::Handled;
+            }
+            crate::log("LINK: contact link dismissed without adding");
+        } else if !self.held_transfers.is_empty() {
+            // Likewise a held transfer (prompted only once no link is waiting): its prompt downloads it, any other activation declines it so the sender stops waiting.
+            let (peer_addr, stream_id, total_size, _) = self.held_transfers.remove(0);
+            self.scene_dirty = true;
+            let accepted = hit_id == self.transfer_accept_hit_id && self.transfer_accept_hit_id != HIT_NONE;
+            if let Some(checker) = self.status_checker.as_ref() {
+                if accepted {
+                    checker.accept_transfer(peer_addr, stream_id);
+                } else {
+                    checker.decline_transfer(peer_addr, stream_id);
+                }
+            }
+            crate::logf!("PT: {}-byte transfer from {} {}", total_size, peer_addr, if accepted { "accepted" } else { "declined" });
+            if accepted {
+                ctx.window.request_redraw();
+                return EventResponse::Handled;
+            }
+        }
+
         // Avatar tap on Ready dispatches to the image picker — not a Widget, just a hit-stamp in chrome.hit_test_map. Drops focus first because the picker overlays the whole UI.
         if hit_id == self.avatar_hit_id
             && matches!(self.state, AppState::Ready)
             && self.avatar_hit_id != HIT_NONE
         {
             self.change_focus(None);
-            // Android: a tap opens the system image picker directly (the picker IS the update mechanism — tapping the grey circle is self-evident, so no on-screen prompt). Desktop: no picker — the avatar updates by drag/drop — the tap is swallowed here.
+            // Android: a tap opens the system image picker directly (the picker IS the update mechanism — tapping the grey circle is self-evident, so no on-screen prompt). Desktop: no picker — the avatar updates by drag/drop, or the tap SELECTS it so a following Ctrl+V pastes a clipboard image into it.
             #[cfg(target_os = "android")]
             {
                 self.pending_picker_request = true;
             }
+            #[cfg(not(target_os = "android"))]
+            {
+                self.avatar_paste_armed = true;
+            }
+            ctx.window.request_redraw();
+            return EventResponse::Handled;
+        }
+
+        // "Copy my handle" on Ready: handle + contact link to the clipboard, confirmed by the toast.
+        if hit_id == self.share_hit_id && self.share_hit_id != HIT_NONE && matches!(self.state, AppState::Ready) {
+            self.change_focus(None);
+            self.copy_profile_share();
+            self.scene_dirty = true;
             ctx.window.request_redraw();
             return EventResponse::Handled;
         }
@@ -2314,6 +3001,13 @@ impl FluorApp for PhotonApp {
                 self.contact_boot_armed = false;
                 ctx.window.request_redraw();
             }
+            // Same rule for the clear-history pills: only a second press on the SAME armed pill fires.
+            if let Some(reset) = self.contact_wipe_armed {
+                if hit_id != self.contact_panel_btn_base.wrapping_add(4 + reset as HitId) {
+                    self.contact_wipe_armed = None;
+                    ctx.window.request_redraw();
+                }
+            }
             if self.contact_nav_base != HIT_NONE
                 && hit_id >= self.contact_nav_base
                 && hit_id < self.contact_nav_base.wrapping_add(4)
@@ -2330,22 +3024,75 @@ impl FluorApp for PhotonApp {
             }
             if self.contact_panel_btn_base != HIT_NONE
                 && hit_id >= self.contact_panel_btn_base
-                && hit_id < self.contact_panel_btn_base.wrapping_add(4)
+                && hit_id < self.contact_panel_btn_base.wrapping_add(12)
             {
                 let slot = hit_id - self.contact_panel_btn_base;
-                if slot == 0 {
-                    // Boot (two-tap): first press arms, second fires. Removal is unilateral and local-plus-fleet only — ostracism, not erasure.
-                    if self.contact_boot_armed {
-                        self.contact_boot_armed = false;
-                        self.boot_active_contact();
+                if slot == 1 {
+                    // Verify page: mark / unmark the safety number as compared. Single tap — it's reversible, unlike Boot.
+                    self.toggle_active_contact_verified();
+                    self.scene_dirty = true;
+                    ctx.window.request_redraw();
+                }
+                if slot == 3 {
+                    // Manage page: step this conversation's history retention. Single tap — nothing is deleted until the hourly sweep, so cycling past a preset is harmless.
+                    self.cycle_active_contact_retention();
+                    self.scene_dirty = true;
+                    ctx.window.request_redraw();
+                }
+                if slot == 4 || slot == 5 {
+                    // Manage page: clear history (4) / clear + reset encryption (5). Two-tap — a wipe can't be undone.
+                    let reset = slot == 5;
+                    if self.contact_wipe_armed == Some(reset) {
+                        self.contact_wipe_armed = None;
+                        self.wipe_active_conversation(reset);
                     } else {
-                        self.contact_boot_armed = true;
+                        self.contact_wipe_armed = Some(reset);
                     }
                     self.scene_dirty = true;
                     ctx.window.request_redraw();
                 }
-                return EventResponse::Handled;
-            }
+                if slot == 6 {
+                    // Manage page: mute / unmute this conversation. Single tap — reversible.
+                    self.toggle_active_contact_muted();
+                    self.scene_dirty = true;
+                    ctx.window.request_redraw();
+                }
+                if slot == 7 {
+                    // Manage page: pin / unpin this conversation. Single tap — reversible.
+                    self.toggle_active_contact_pinned();
+                    self.scene_dirty = true;
+                    ctx.window.request_redraw();
+                }
+                if slot == 8 {
+                    // Manage page: archive / restore this conversation. Single tap — reversible, and a message from them restores it anyway.
+                    if let Some(contact) = self.active_contact.and_then(|ci| self.contacts.get_mut(ci)) {
+                        contact.archived = !contact.archived;
+                        if let Some(storage) = self.storage.as_ref() {
+                            if let Err(e) = crate::storage::contacts::save_contact(contact, storage) {
+                                crate::logf!("STORAGE: Failed to save archive flag: {}", e);
+                            }
+                        }
+                    }
+                    self.scene_dirty = true;
+                    ctx.window.request_redraw();
+                }
+                if slot == 2 && crate::network::qr_scan::can_scan() {
+                    // Verify page: open the platform scanner; the result lands asynchronously in the tick drain.
+                    crate::network::qr_scan::start_scan();
+                }
+                if slot == 0 {
+                    // Boot (two-tap): first press arms, second fires. Removal is unilateral and local-plus-fleet only — ostracism, not erasure.
+                    if self.contact_boot_armed {
+                        self.contact_boot_armed = false;
+                        self.boot_active_contact();
+                    } else {
+                        self.contact_boot_armed = true;
+                    }
+                    self.scene_dirty = true;
+                    ctx.window.request_redraw();
+                }
+                return EventResponse::Handled;
+            }
         }
 
         if let AppState::Settings(page) = self.state {
@@ -2366,6 +3113,9 @@ impl FluorApp for PhotonApp {
                         self.settings_removeshred_armed = false;
                         self.settings_shred_armed = false;
                     }
+                    if *p != SettingsPage::You {
+                        self.you_avatar_remove_armed = false;
+                    }
                     // Fresh page starts at the top — a leftover scroll from a longer page would strand a short one mid-air.
                     self.settings_content_scroll = 0.0;
                     // Opening the You page reloads its field boxes from the current settings (fleet-synced state).
@@ -2451,6 +3201,8 @@ impl FluorApp for PhotonApp {
                         self.settings_removeshred_armed = false;
                         tohu::clear_session();
                         self.session = None;
+                        self.our_handle = None;
+                        self.text_undo = None; // drafts typed under this identity don't survive it
                         self.private_s = crate::crypto::blind::PrivateS::None;
                         self.pending_broadcast_signal = -1;
                         self.state = AppState::Launch(LaunchState::Fresh);
@@ -2469,39 +3221,15 @@ impl FluorApp for PhotonApp {
                         // "Remove & shred" → UNSIGN (self-departure from the fleet chain — the only chain remove that exists, self-signed + idempotent), THEN crypto-wipe. Two-tap confirm. The wipe is GATED on the departure landing: if the signed remove can't publish (offline, races exhausted), nothing is wiped — otherwise the fleet would forever list a device whose keys are gone. Plain Shred (orange) remains the wipe-without-departing path.
                         if self.settings_removeshred_armed {
                             self.settings_removeshred_armed = false;
-                            let hp = self.handle_query.as_ref().and_then(|hq| hq.get_handle_proof());
-                            // LAST-MEMBER GATE (identity never dies — supersedes lifecycle D3's LastRites): the fleet's final member cannot sign out, full stop. There is no terminal op — an identity always lives somewhere (the worker refuses zero-member folds too, so this isn't just UI courtesy). Want out of this hardware? Add another device first, then retire this one. A fetch failure counts as last (fail toward refusal, never past it).
-                            if let Some(ref hp_v) = hp {
-                                let last = match crate::network::fgtw::fleet::current_members(hp_v) {
-                                    Ok(m) => m.len() <= 1,
-                                    Err(e) => {
-                                        crate::logf!("SECURITY: member count fetch failed ({}) — treating as last device", e);
-                                        true
-                                    }
-                                };
-                                if last {
-                                    crate::log("SECURITY: last member — sign-out refused (an identity must live somewhere)");
-                                    self.ready_toast = Some("This is your identity's last device — it can't sign out. Add another device first, then retire this one.".to_string());
+                            match self.retire_device(&mut crate::network::fgtw::fleet::LiveDeparture) {
+                                Ok(()) => self.clean_device_for_reuse(),
+                                Err(toast) => {
+                                    self.ready_toast = Some(toast.to_string());
                                     self.scene_dirty = true;
                                     ctx.window.request_redraw();
                                     return EventResponse::Handled;
                                 }
                             }
-                            if let (Some(hp), Some(kp)) = (hp, self.device_keypair.clone()) {
-                                match crate::network::fgtw::fleet::depart_device(&kp, &hp) {
-                                    Ok(()) => {
-                                        crate::log("SECURITY: departed the fleet chain (self-signed remove) — wiping");
-                                        self.clean_device_for_reuse();
-                                    }
-                                    Err(e) => {
-                                        crate::logf!("SECURITY: fleet departure failed ({}) — NOT wiping", e);
-                                        self.ready_toast = Some("Couldn't sign out of the fleet — nothing wiped. Check connection and retry.".to_string());
-                                    }
-                                }
-                            } else {
-                                crate::log("SECURITY: no session/keypair to depart with — NOT wiping");
-                                self.ready_toast = Some("No signed-in identity to remove — use Shred instead.".to_string());
-                            }
                         } else {
                             self.settings_removeshred_armed = true;
                             self.settings_shred_armed = false;
@@ -2513,6 +3241,8 @@ impl FluorApp for PhotonApp {
                         crate::log("settings-stub: self-fleet-removal deferred");
                     }
                 } else if page == SettingsPage::You {
+                    // Any press other than the confirming one disarms "Remove avatar".
+                    let remove_armed = std::mem::take(&mut self.you_avatar_remove_armed);
                     if slot == 0 {
                         // "Update" → persist every field fleet-wide as `profile.<id>` settings, in ONE batched push (not one push per field).
                         self.save_you_profile();
@@ -2525,11 +3255,21 @@ impl FluorApp for PhotonApp {
                         }
                         #[cfg(not(target_os = "android"))]
                         {
-                            self.ready_toast = Some("Drag & drop an image onto the Photon window".to_string());
+                            self.ready_toast = Some("Drag & drop an image onto the Photon window, or tap your avatar and paste one (Ctrl+V)".to_string());
                         }
                     } else if slot == 2 {
                         // "Add" → register the typed label as a custom field (e.g. "Address 2") and append its box.
                         self.add_custom_field();
+                    } else if slot == 3 {
+                        // "Copy contact link" → our card as photon://add/…, to paste into any channel.
+                        self.copy_contact_link();
+                    } else if slot == 4 {
+                        // "Remove avatar" → placeholder everywhere. Two-tap: the wall copy is deleted, so undo means picking the image again.
+                        if remove_armed {
+                            self.remove_avatar();
+                        } else {
+                            self.you_avatar_remove_armed = true;
+                        }
                     }
                 } else if page == SettingsPage::Updates {
                     use crate::network::updates::Channel;
@@ -2600,6 +3340,30 @@ impl FluorApp for PhotonApp {
             return EventResponse::Handled;
         }
 
+        // Crop screen pills: −/+ zoom, Cancel drops the image, Use sets the framed square.
+        if matches!(self.state, AppState::AvatarCrop)
+            && self.avatar_crop_hit_base != HIT_NONE
+            && hit_id >= self.avatar_crop_hit_base
+            && hit_id < self.avatar_crop_hit_base.wrapping_add(4)
+        {
+            match hit_id - self.avatar_crop_hit_base {
+                0 => self.zoom_avatar_framing(true),
+                1 => self.zoom_avatar_framing(false),
+                slot => self.finish_avatar_framing(slot == 3),
+            }
+            ctx.window.request_redraw();
+            return EventResponse::Handled;
+        }
+
+        // The archived-filter link swaps the list between the main rows and the archived ones.
+        if matches!(self.state, AppState::Ready) && hit_id != HIT_NONE && hit_id == self.archived_toggle_hit {
+            self.show_archived = !self.show_archived;
+            self.contact_key_focus = None;
+            self.contacts_scroll = 0;
+            ctx.window.request_redraw();
+            return EventResponse::Handled;
+        }
+
         // Contact row tap — hit IDs in [contact_hit_base, contact_hit_base + 255].
         if matches!(self.state, AppState::Ready)
             && self.contact_hit_base != HIT_NONE
@@ -2608,16 +3372,7 @@ impl FluorApp for PhotonApp {
         {
             let ci = (hit_id - self.contact_hit_base) as usize;
             if ci < self.contacts.len() {
-                crate::logf!("contact-tap: opening conversation with '{}'", self.contacts[ci].display_name());
-                self.active_contact = Some(ci);
-                self.state = AppState::Conversation;
-                // Opening the conversation is the interaction that clears unread (ring + float drop away on the next contacts-list frame).
-                self.clear_unread(ci);
-                self.change_focus(None);
-                // Refresh this contact's presence on conversation-enter so the header reflects reality promptly.
-                self.ping_contact(ci);
-                // Fetch the peer's avatar (once/session) so the conversation header shows it instead of the grey placeholder. Cache-first, network on miss; off-thread. Keyed by the pin-set (hp + party id + avatar key) — no handle.
-                self.spawn_avatar_download(ci);
+                self.open_conversation(ci);
                 ctx.window.request_redraw();
                 return EventResponse::Handled;
             }
@@ -2629,6 +3384,13 @@ impl FluorApp for PhotonApp {
             return EventResponse::Handled;
         }
 
+        // Hold-to-record released over the send button: keep the recording instead of firing a send.
+        if self.message_send_btn.as_ref().is_some_and(|b| b.hit_id() == hit_id) && self.voice_recording.is_some() {
+            self.finish_voice_recording(true);
+            ctx.window.request_redraw();
+            return EventResponse::Handled;
+        }
+
         // Release-activated Buttons (attest / + / send): `dispatch_release` fires only `activate_on_release()` widgets — a Button's `Click::on_click` (→ `fire`) runs here; the Released arm's `take_click` polls then submits. A drag-off yields no activation → no fire, so nothing commits on a mis-touch.
         let response = widget::dispatch_release(self, hit_id, x, y, mods);
         if matches!(response, EventResponse::Handled) {
@@ -2663,6 +3425,27 @@ impl FluorApp for PhotonApp {
                         changed = true;
                     }
                 }
+                // Live history drag-select: the head follows the pointer. The highlight is CONTENT (painted under the message text), so each step claims the full frame.
+                if self.msg_select_anchor.is_some() {
+                    self.msg_select_head = (ctx.cursor_x, ctx.cursor_y);
+                    self.scene_dirty = true;
+                    changed = true;
+                }
+                if self.status_select_anchor.is_some() {
+                    self.status_select_head = (ctx.cursor_x, ctx.cursor_y);
+                    self.scene_dirty = true;
+                    changed = true;
+                }
+                // Crop screen: the grabbed image drags the circle with the pointer, converted to source pixels at the drawn scale.
+                if let Some(f) = self.avatar_framing.as_mut() {
+                    if let (Some((x0, y0)), Some(view)) = (f.drag, f.view) {
+                        let (dx, dy) = ((ctx.cursor_x - x0) / view.scale, (ctx.cursor_y - y0) / view.scale);
+                        f.circle = f.circle.pan(dx, dy, f.dims.0, f.dims.1);
+                        f.drag = Some((ctx.cursor_x, ctx.cursor_y));
+                        self.scene_dirty = true;
+                        changed = true;
+                    }
+                }
                 // Hover only re-walks (and repaints) when the hit under the cursor actually changes — one walk over EVERY active widget, so every textbox/button on every screen inherits hover + the I-beam with no hand-list. Frozen (busy) widgets return `None` from `hover()`, so they stay inert for free.
                 if new_hit != self.hover_hit {
                     // Contact-row hover tint is CONTENT (painted into the canvas, not an overlay delta), so entering/leaving a row needs the full frame the widget-overlay path avoids.
@@ -2744,6 +3527,12 @@ impl FluorApp for PhotonApp {
                         changed = true;
                     }
                 }
+                if let Some(tb) = self.find_textbox.as_mut() {
+                    if tb.is_hovered() {
+                        tb.set_hovered(false);
+                        changed = true;
+                    }
+                }
                 if changed {
                     ctx.window.request_redraw();
                 }
@@ -2767,9 +3556,9 @@ impl FluorApp for PhotonApp {
                 // Feed the desktop-notification gate: focused = someone's looking, stay quiet; unfocused/hidden = ding.
                 #[cfg(not(target_os = "android"))]
                 crate::platform::desktop_notify::set_window_focused(*focused);
-                // On focus GAIN, force an immediate presence sweep so rings are fresh the instant the user looks — clearing last_presence_ping makes the next tick treat a sweep as due regardless of how far the idle cadence had backed off. (last_interaction was already stamped at the top of on_event, resetting the cadence to the active tier.)
+                // On focus GAIN, force an immediate presence sweep so rings are fresh the instant the user looks — clearing sweeps.presence_ping makes the next tick treat a sweep as due regardless of how far the idle cadence had backed off. (last_interaction was already stamped at the top of on_event, resetting the cadence to the active tier.)
                 if *focused {
-                    self.last_presence_ping = None;
+                    self.sweeps.presence_ping = None;
                     ctx.window.request_redraw();
                 }
                 // Chrome's edges + title + orb dim when the window loses focus (palette swap to `WINDOW_*_UNFOCUSED` + `TEXT_COLOUR_UNFOCUSED` + `ORB_DARKEN_UNFOCUSED`). The host independently dims the drop shadow via its own `is_focused` tracker; this handler just propagates to chrome's internal flag so the chrome layer re-rasterizes with the dimmed palette.
@@ -2793,7 +3582,12 @@ impl FluorApp for PhotonApp {
                     }
                     // Rubber-band scrolling on every axis, every platform: past either end the step is asymptotically resisted (never further than `reach` past the bound), and `tick()` eases the overshoot back once the wheel stops. `reach` scales with the window so the give feels the same on a watch and an 8K panel.
                     let reach = ctx.viewport.height_px as f32 / (1 << 3) as f32;
-                    if matches!(self.state, AppState::Ready) {
+                    if matches!(self.state, AppState::AvatarCrop) {
+                        // The crop screen's wheel zooms: up closes in on the face (a smaller circle). A live drag owns the gesture — Android's touch-drag synthesizes wheel events alongside it.
+                        if self.avatar_framing.as_ref().is_some_and(|f| f.drag.is_none()) {
+                            self.zoom_avatar_framing(dy < 0);
+                        }
+                    } else if matches!(self.state, AppState::Ready) {
                         // On the contacts screen the wheel scrolls the WHOLE user section + list as one block. Down-scroll (negative dy) moves the block up (reveals lower contacts), so subtract; render publishes the block extent (`contacts_scroll_extent`) and re-runs `update_widget_layout` so the search box + plus button (whose rects are set off `contacts_scroll`) track the same offset.
                         self.contacts_scroll = rubber_step(
                             self.contacts_scroll as f32,
@@ -2824,6 +3618,8 @@ impl FluorApp for PhotonApp {
                         }
                     } else if matches!(self.state, AppState::Conversation) {
                         // In a conversation the wheel scrolls the message history. The list lays out bottom-up with newest at the bottom; a positive offset pushes messages down (reveals older ones above). Scroll-up (positive dy) shows older → add. Only the 0 end rubber-bands (hi = ∞); the old-history end is backfill-paged, not clamped.
+                        // The rows slide out from under an open message menu, so scrolling closes it.
+                        self.msg_menu = None;
                         if let Some(ci) = self.active_contact {
                             if let Some(contact) = self.contacts.get_mut(ci) {
                                 contact.message_scroll_offset = rubber_step(
@@ -2867,6 +3663,17 @@ impl FluorApp for PhotonApp {
                     .map(|c| c.hit_at(ctx.cursor_x, ctx.cursor_y))
                     .unwrap_or(HIT_NONE);
 
+                // Crop screen: a press on the image grabs the circle; CursorMoved drags it until release.
+                if matches!(self.state, AppState::AvatarCrop)
+                    && hit_id != HIT_NONE
+                    && hit_id == self.avatar_crop_hit_base.wrapping_add(4)
+                {
+                    if let Some(f) = self.avatar_framing.as_mut() {
+                        f.drag = Some((ctx.cursor_x, ctx.cursor_y));
+                    }
+                    return EventResponse::Handled;
+                }
+
                 // Permanence interstitial ("Yes — forever"): a press ANYWHERE other than the attest button cancels back to the pre-proof Fresh state. Editing the handle already cancels; this makes a tap on empty space, the field, the orb — anything else — cancel too, so a stray tap can never corner the user into the forever-claim (on Android "click elsewhere" was otherwise swipe-up → home → long-press → switch away). The attest button press itself is the deliberate confirm, so it's excluded; we fall thru afterwards so the tap still does its normal thing (focus the field, start a drag, open settings, …).
                 if matches!(self.state, AppState::Launch(LaunchState::Confirm)) {
                     let attest_hit = self.attest_btn.as_ref().map(|b| b.hit_id()).unwrap_or(HIT_NONE);
@@ -2955,6 +3762,105 @@ impl FluorApp for PhotonApp {
                     }
                 }
 
+                // An open message menu closes on any press. A press on one of its items runs that action; on bare history it's spent closing the menu (as with an expanded image), while a widget under it still does its normal thing.
+                if let Some(menu) = self.msg_menu.take() {
+                    self.scene_dirty = true;
+                    let (x, y) = (ctx.cursor_x, ctx.cursor_y);
+                    let pick = self.msg_menu_rects.iter().position(|&(x0, y0, x1, y1)| x >= x0 && x < x1 && y >= y0 && y < y1);
+                    if let Some(&action) = pick.and_then(|i| menu.actions.get(i)) {
+                        self.run_message_action(menu.contact, menu.msg, action);
+                        ctx.window.request_redraw();
+                        return EventResponse::Handled;
+                    }
+                    if hit_id == HIT_NONE {
+                        ctx.window.request_redraw();
+                        return EventResponse::Handled;
+                    }
+                }
+
+                // A press on a mention row completes that name into the draft.
+                if self.mention.is_open() && matches!(self.state, AppState::Conversation) {
+                    let (x, y) = (ctx.cursor_x, ctx.cursor_y);
+                    let pick = self.mention_rects.iter().find(|(_, (x0, y0, x1, y1))| x >= *x0 && x < *x1 && y >= *y0 && y < *y1);
+                    if let Some(&(ci, _)) = pick {
+                        self.complete_mention(ci, ctx.text);
+                        ctx.window.request_redraw();
+                        return EventResponse::Handled;
+                    }
+                }
+
+                // A press on a contact reference in the history opens that conversation.
+                if matches!(self.state, AppState::Conversation) {
+                    let (x, y) = (ctx.cursor_x, ctx.cursor_y);
+                    let hit = self.msg_ref_rects.iter().find(|(_, (x0, y0, x1, y1))| x >= *x0 && x < *x1 && y >= *y0 && y < *y1);
+                    if let Some(&(ci, _)) = hit {
+                        if self.active_contact != Some(ci) {
+                            self.open_conversation(ci);
+                        }
+                        ctx.window.request_redraw();
+                        return EventResponse::Handled;
+                    }
+                }
+
+                // The "new messages ↓" pill: jump to the newest and mark them read.
+                if let Some((x0, y0, x1, y1)) = self.msg_jump_rect.filter(|_| matches!(self.state, AppState::Conversation)) {
+                    if ctx.cursor_x >= x0 && ctx.cursor_x < x1 && ctx.cursor_y >= y0 && ctx.cursor_y < y1 {
+                        self.jump_to_latest();
+                        ctx.window.request_redraw();
+                        return EventResponse::Handled;
+                    }
+                }
+
+                // Any press disarms avatar paste — the avatar's own tap re-arms it on release (on_activate runs after this press).
+                self.avatar_paste_armed = false;
+                // A press hands the contacts list back to the pointer — the keyboard-focus highlight would otherwise read as a second hover.
+                self.contact_key_focus = None;
+                // Any press drops the previous history selection; a press on bare history (no widget under it) starts a new drag-select instead of a window drag.
+                self.msg_selection = None;
+                // An expanded image closes on any press; a press on bare history is spent closing it rather than starting a drag.
+                if self.chat_image_expanded.take().is_some() {
+                    self.scene_dirty = true;
+                    if hit_id == HIT_NONE {
+                        ctx.window.request_redraw();
+                        return EventResponse::Handled;
+                    }
+                }
+                // Same for the launch status line: a press on it (bare, no widget) starts selecting its text.
+                self.status_selection = None;
+                if hit_id == HIT_NONE && matches!(self.state, AppState::Launch(_)) {
+                    if let Some((_, row)) = self.status_row.as_ref() {
+                        if ctx.cursor_y >= row.top && ctx.cursor_y < row.bottom {
+                            self.change_focus(None);
+                            self.status_select_anchor = Some((ctx.cursor_x, ctx.cursor_y));
+                            self.status_select_head = (ctx.cursor_x, ctx.cursor_y);
+                            self.scene_dirty = true;
+                            ctx.window.request_redraw();
+                            return EventResponse::Handled;
+                        }
+                    }
+                }
+                if hit_id == HIT_NONE && matches!(self.state, AppState::Conversation) {
+                    if let Some((top, bottom)) = self.msg_list_band {
+                        if ctx.cursor_y >= top && ctx.cursor_y < bottom {
+                            self.change_focus(None);
+                            self.msg_select_anchor = Some((ctx.cursor_x, ctx.cursor_y));
+                            self.msg_select_head = (ctx.cursor_x, ctx.cursor_y);
+                            // Same OS double-click interval as the textboxes; the press must also land within a small, window-scaled distance of the last one.
+                            self.msg_select_unit = self.msg_click_streak.press(
+                                Instant::now(),
+                                (ctx.cursor_x, ctx.cursor_y),
+                                fluor::host::os_input::double_click_interval(),
+                                ctx.viewport.height_px as f32 / (1 << 7) as f32,
+                            );
+                            // Touch has no right button: a still single-press hold opens the message menu instead (`tick`).
+                            self.msg_press_at = (cfg!(target_os = "android") && self.msg_select_unit == message_select::SelectUnit::Char)
+                                .then(Instant::now);
+                            ctx.window.request_redraw();
+                            return EventResponse::Handled;
+                        }
+                    }
+                }
+
                 if hit_id == HIT_NONE {
                     // No widget under the cursor — clear focus, then fall back to resize-edge / title-bar drag. Resize edge takes precedence; clicks anywhere else inside the visible window start a move-drag (which the host promotes to an actual drag once the cursor passes the dead-zone threshold).
                     if self.change_focus(None) {
@@ -2967,6 +3873,12 @@ impl FluorApp for PhotonApp {
                     return EventResponse::StartWindowDrag;
                 }
 
+                // Send button with an empty compose box: hold to record a voice memo (where the platform records). Release is handled in on_activate / the Released arm.
+                if self.message_send_btn.as_ref().is_some_and(|b| b.hit_id() == hit_id) && self.start_voice_recording() {
+                    ctx.window.request_redraw();
+                    return EventResponse::Handled;
+                }
+
                 // Textbox press: photon owns textbox pointer gestures end-to-end — focus + place the caret + drop a drag anchor here (double-click → word, triple → all), extend on drag in `CursorMoved`, finalize on release. `on_activate` therefore SKIPS `dispatch_release` for textboxes, so fluor's on_click can't clobber the selection on release.
                 if self.textbox_press(hit_id, ctx.cursor_x) {
                     ctx.window.request_redraw();
@@ -2977,16 +3889,68 @@ impl FluorApp for PhotonApp {
                 ctx.window.request_redraw();
                 EventResponse::Handled
             }
+            // Right-click on a history row opens its message menu (copy / view / save / play / retry). Off every row it just closes an open one.
+            Event::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Right,
+                ..
+            } if matches!(self.state, AppState::Conversation) => {
+                self.clear_hints();
+                self.msg_select_anchor = None;
+                self.msg_press_at = None;
+                self.open_message_menu(ctx.cursor_x, ctx.cursor_y);
+                ctx.window.request_redraw();
+                EventResponse::Handled
+            }
             Event::MouseInput {
                 state: ElementState::Released,
                 button: MouseButton::Left,
                 ..
             } => {
+                // Crop screen: let go of the circle.
+                if let Some(f) = self.avatar_framing.as_mut() {
+                    f.drag = None;
+                }
+                // A hold-to-record still live here was released off the send button (on_activate keeps one released on it): throw it away.
+                if self.voice_recording.is_some() {
+                    self.finish_voice_recording(false);
+                    ctx.window.request_redraw();
+                }
                 // End any textbox drag-select and finalize the caret/selection (fires on EVERY release, so a drag-off outside the box clears the state too).
                 if self.pointer_down {
                     self.textbox_release();
                     ctx.window.request_redraw();
                 }
+                // End a history drag-select: resolve the final span against the rows on screen now and keep it for Ctrl+C. A plain click (no span) on a failed send is its "tap to retry"; on an image or a file, it opens it (see open_history_row). A double/triple click resolves by whole words / messages and is never a tap.
+                self.msg_press_at = None;
+                if let Some(anchor) = self.msg_select_anchor.take() {
+                    self.msg_select_head = (ctx.cursor_x, ctx.cursor_y);
+                    let unit = self.msg_select_unit;
+                    self.msg_selection = self.active_contact.and_then(|ci| {
+                        self.contacts.get(ci)?;
+                        let shown = self.shown_history(ci);
+                        let texts: Vec<&str> = shown.iter().map(|t| t.as_ref()).collect();
+                        message_select::unit_range(&self.msg_rows, &texts, anchor, self.msg_select_head, unit).map(|r| (ci, r))
+                    });
+                    if self.msg_selection.is_none() && unit == message_select::SelectUnit::Char {
+                        let tapped = self.msg_rows.iter().find(|r| ctx.cursor_y >= r.top && ctx.cursor_y < r.bottom).map(|r| r.msg);
+                        if let (Some(ci), Some(vi)) = (self.active_contact, tapped) {
+                            self.open_history_row(ci, vi);
+                            self.retry_failed_message(ci, vi);
+                        }
+                    }
+                    ctx.window.request_redraw();
+                }
+                // End a status-line drag-select: resolve against the row on screen now; a plain click leaves nothing selected.
+                if let Some(anchor) = self.status_select_anchor.take() {
+                    self.status_select_head = (ctx.cursor_x, ctx.cursor_y);
+                    self.status_selection = self
+                        .status_row
+                        .as_ref()
+                        .and_then(|(_, row)| message_select::selection_range(std::slice::from_ref(row), anchor, self.status_select_head));
+                    self.scene_dirty = true;
+                    ctx.window.request_redraw();
+                }
                 // Attest button: poll `take_click` AFTER release — Button::on_click increments the counter at press; we observe the rising edge here so submit fires once per press/release pair regardless of how chrome dispatches subsequent events.
                 let clicked = self
                     .attest_btn
@@ -3068,8 +4032,59 @@ impl FluorApp for PhotonApp {
                 if ctx.modifiers.control_key() || ctx.modifiers.super_key() {
                     if let Key::Character(c) = &kev.logical_key {
                         let lc = c.to_lowercase();
+                        // Ctrl+V onto the selected avatar (Ready, avatar tapped, no textbox focused): a clipboard image becomes the avatar thru the drop pipeline.
+                        if lc == "v" && self.avatar_paste_armed && self.focused.is_none() && matches!(self.state, AppState::Ready) {
+                            self.paste_avatar_from_clipboard();
+                            self.scene_dirty = true;
+                            ctx.window.request_redraw();
+                            return EventResponse::Handled;
+                        }
+                        // Ctrl+Shift+F: perf overlay. Shift keeps plain Ctrl+F free for find.
+                        if lc == "f" && ctx.modifiers.shift_key() {
+                            self.perf_overlay = !self.perf_overlay;
+                            self.scene_dirty = true;
+                            ctx.window.request_redraw();
+                            return EventResponse::Handled;
+                        }
+                        // Ctrl+F on an open conversation with a history to search: the find bar takes the compose box's place, focused.
+                        let has_history = self
+                            .active_contact
+                            .and_then(|ci| self.contacts.get(ci))
+                            .is_some_and(|c| c.clutch_state == crate::types::ClutchState::Complete);
+                        if lc == "f" && matches!(self.state, AppState::Conversation) && has_history {
+                            self.open_find();
+                            self.scene_dirty = true;
+                            ctx.window.request_redraw();
+                            return EventResponse::Handled;
+                        }
+                        // Ctrl+Shift+R: reconnect now — don't wait out the sweep cadence after a network change.
+                        if lc == "r" && ctx.modifiers.shift_key() {
+                            self.force_reconnect("shortcut");
+                            ctx.window.request_redraw();
+                            return EventResponse::Handled;
+                        }
+                        // A settled history selection owns Ctrl+C — the compose box isn't a clipboard_chord target, so nothing else competes for it on this screen.
+                        if lc == "c" && (self.copy_message_selection() || self.copy_status_selection()) {
+                            return EventResponse::Handled;
+                        }
+                        // Ctrl+Z steps the focused textbox back one edit; Ctrl+Y / Ctrl+Shift+Z forward again. Owned whenever a textbox holds focus, so the letter never falls thru as typing.
+                        if lc == "z" || lc == "y" {
+                            let focused = self.focused;
+                            if let Some(id) = focused.filter(|&id| self.is_textbox(Some(id))) {
+                                if self.step_textbox_history(id, lc == "y" || ctx.modifiers.shift_key(), ctx.text) {
+                                    ctx.window.request_redraw();
+                                    self.blink_timer.start(Instant::now());
+                                }
+                                return EventResponse::Handled;
+                            }
+                        }
                         if lc == "c" || lc == "x" || lc == "v" {
+                            let focused = self.focused;
+                            let before = focused.and_then(|id| self.textbox_snapshot(id));
                             let resp = self.clipboard_chord(&lc, ctx.text);
+                            if let Some(id) = focused {
+                                self.record_textbox_edit(id, before, text_undo::EditKind::Other);
+                            }
                             if matches!(resp, EventResponse::Handled) {
                                 ctx.window.request_redraw();
                                 self.blink_timer.start(Instant::now());
@@ -3080,6 +4095,103 @@ impl FluorApp for PhotonApp {
                 }
 
                 match &kev.logical_key {
+                    // Find bar focused: Enter steps to the older hit, Shift+Enter the newer one; Esc closes the bar and hands the keyboard back to the compose box. Intercepted before delivery so the box never takes a newline. Shift+Esc still quits.
+                    Key::Named(key @ (NamedKey::Enter | NamedKey::Escape))
+                        if matches!(self.state, AppState::Conversation)
+                            && self.msg_find.is_some()
+                            && !(*key == NamedKey::Escape && ctx.modifiers.shift_key())
+                            && self.find_textbox.as_ref().is_some_and(|t| Some(t.hit_id()) == self.focused) =>
+                    {
+                        if *key == NamedKey::Escape {
+                            self.close_find();
+                        } else {
+                            if let Some(find) = self.msg_find.as_mut() {
+                                find.step(!ctx.modifiers.shift_key());
+                            }
+                            self.reveal_find_hit();
+                        }
+                        self.scene_dirty = true;
+                        ctx.window.request_redraw();
+                        EventResponse::Handled
+                    }
+                    // Mention picker open over the compose box: ↑/↓ walk the names, Enter/Tab complete the highlighted one, Esc closes it for this mention. Intercepted before the compose box's Enter = send.
+                    Key::Named(key @ (NamedKey::ArrowUp | NamedKey::ArrowDown | NamedKey::Enter | NamedKey::Tab | NamedKey::Escape))
+                        if matches!(self.state, AppState::Conversation)
+                            && self.mention.is_open()
+                            && !ctx.modifiers.shift_key()
+                            && self.message_textbox.as_ref().is_some_and(|t| Some(t.hit_id()) == self.focused) =>
+                    {
+                        match key {
+                            NamedKey::ArrowUp | NamedKey::ArrowDown => self.mention.step(*key == NamedKey::ArrowDown),
+                            NamedKey::Escape => self.mention.dismiss(),
+                            _ => {
+                                if let Some(ci) = self.mention.chosen() {
+                                    self.complete_mention(ci, ctx.text);
+                                    self.blink_timer.start(Instant::now());
+                                }
+                            }
+                        }
+                        ctx.window.request_redraw();
+                        EventResponse::Handled
+                    }
+                    // Up/Down walk the contacts list in display order (the search box may hold focus — arrows are meaningless in a one-line field, so type-to-filter then arrow works). Moving the keyboard focus drops widget focus so the following Enter opens the row instead of submitting the search.
+                    Key::Named(key @ (NamedKey::ArrowUp | NamedKey::ArrowDown)) if matches!(self.state, AppState::Ready) => {
+                        let step = if *key == NamedKey::ArrowUp { ContactNavKey::Up } else { ContactNavKey::Down };
+                        if let ContactNavOutcome::Focus(ci) = contact_nav(&self.contact_display_order(), self.contact_key_focus, step) {
+                            self.contact_key_focus = Some(ci);
+                            self.contact_key_reveal = true;
+                            self.change_focus(None);
+                            ctx.window.request_redraw();
+                        }
+                        EventResponse::Handled
+                    }
+                    // Word-wise editing in the focused textbox: Ctrl+←/→ jump a word, Ctrl+Backspace/Delete eat one (Option on macOS, where Ctrl+arrows belong to Spaces). Intercepted before delivery — fluor's Textbox steps one char at a time. Shift (extend the selection) stays with the widget.
+                    Key::Named(key @ (NamedKey::ArrowLeft | NamedKey::ArrowRight | NamedKey::Backspace | NamedKey::Delete))
+                        if !ctx.modifiers.shift_key()
+                            && if cfg!(target_os = "macos") { ctx.modifiers.alt_key() } else { ctx.modifiers.control_key() } =>
+                    {
+                        let focused = self.focused;
+                        let Some(id) = focused.filter(|&id| self.is_textbox(Some(id))) else {
+                            return EventResponse::Pass;
+                        };
+                        if self.textbox_word_key(id, *key, ctx.text) {
+                            ctx.window.request_redraw();
+                            self.blink_timer.start(Instant::now());
+                        }
+                        EventResponse::Handled
+                    }
+                    // History keys on an open conversation: PageUp/PageDown page the history by one list band; Home/End jump to the oldest/newest message — unless the compose box holds focus, where they move its caret (Ctrl+Home/End still reach the history).
+                    Key::Named(key @ (NamedKey::Home | NamedKey::End | NamedKey::PageUp | NamedKey::PageDown))
+                        if matches!(self.state, AppState::Conversation)
+                            && (matches!(key, NamedKey::PageUp | NamedKey::PageDown)
+                                || ctx.modifiers.control_key()
+                                || !self.message_textbox.as_ref().is_some_and(|t| Some(t.hit_id()) == self.focused)) =>
+                    {
+                        let step = match key {
+                            NamedKey::Home => message_select::ScrollKey::Home,
+                            NamedKey::End => message_select::ScrollKey::End,
+                            NamedKey::PageUp => message_select::ScrollKey::PageUp,
+                            _ => message_select::ScrollKey::PageDown,
+                        };
+                        let view_h = self.msg_list_band.map_or(0.0, |(top, bottom)| (bottom - top).max(0.0));
+                        if let Some(contact) = self.active_contact.and_then(|ci| self.contacts.get_mut(ci)) {
+                            contact.message_scroll_offset =
+                                message_select::key_scroll(contact.message_scroll_offset, step, self.msg_scroll_extent, view_h);
+                            // Heading for the old edge jumps the history-backfill queue, same as a wheel scrollback.
+                            if matches!(step, message_select::ScrollKey::Home | message_select::ScrollKey::PageUp) {
+                                if let Some(rec) = contact.history_recovery.as_mut().filter(|rec| !rec.complete) {
+                                    rec.urgent = true;
+                                }
+                            }
+                        }
+                        // The rows move out from under an open menu and every hit stamp — same as a wheel step.
+                        self.msg_menu = None;
+                        if let Some(chrome) = self.chrome.as_mut() {
+                            chrome.invalidate_chrome();
+                        }
+                        ctx.window.request_redraw();
+                        EventResponse::Handled
+                    }
                     // Tab cycles focus thru the widget tree in registration order (launch widgets first, then chrome). Intercepted BEFORE delivery so textbox can't swallow it as "\t" insertion.
                     Key::Named(NamedKey::Tab) => {
                         let dir = if ctx.modifiers.shift_key() {
@@ -3111,6 +4223,16 @@ impl FluorApp for PhotonApp {
                             ctx.window.request_redraw();
                             return EventResponse::Handled;
                         }
+                        if matches!(self.state, AppState::Conversation) && self.msg_menu.take().is_some() {
+                            self.scene_dirty = true;
+                            ctx.window.request_redraw();
+                            return EventResponse::Handled;
+                        }
+                        if matches!(self.state, AppState::Conversation) && self.chat_image_expanded.take().is_some() {
+                            self.scene_dirty = true;
+                            ctx.window.request_redraw();
+                            return EventResponse::Handled;
+                        }
                         if matches!(self.state, AppState::Conversation) {
                             self.state = AppState::Ready;
                             self.active_contact = None;
@@ -3124,6 +4246,12 @@ impl FluorApp for PhotonApp {
                             ctx.window.request_redraw();
                             return EventResponse::Handled;
                         }
+                        if matches!(self.state, AppState::AvatarCrop) {
+                            // Escape is the crop screen's Cancel.
+                            self.finish_avatar_framing(false);
+                            ctx.window.request_redraw();
+                            return EventResponse::Handled;
+                        }
                         if matches!(self.state, AppState::AddDevice) {
                             // Escape cancels back to the Fleet page the flow came from.
                             self.end_add_device_flow();
@@ -3143,8 +4271,9 @@ impl FluorApp for PhotonApp {
                             ctx.window.request_redraw();
                             return EventResponse::Handled;
                         }
-                        if matches!(self.state, AppState::Launch(LaunchState::Attesting)) {
+                        if matches!(self.state, AppState::Launch(LaunchState::Attesting | LaunchState::Retrying { .. })) {
                             self.state = AppState::Launch(LaunchState::Fresh);
+                            self.attest_retry.reset();
                             ctx.window.request_redraw();
                             return EventResponse::Handled;
                         }
@@ -3157,6 +4286,20 @@ impl FluorApp for PhotonApp {
                     }
                     // Enter submits the handle when the textbox is focused — intercepted before delivery so the textbox doesn't insert a literal newline. When the attest button is focused, route to its on_key (Button activates on Enter / Space and we observe via take_click in tick / on_event Release path). Both Launch and Ready screens follow the same shape with their respective widgets.
                     Key::Named(NamedKey::Enter) => {
+                        // Crop screen: Enter is Use.
+                        if matches!(self.state, AppState::AvatarCrop) {
+                            self.finish_avatar_framing(true);
+                            ctx.window.request_redraw();
+                            return EventResponse::Handled;
+                        }
+                        // Ready with no widget focused: Enter opens the keyboard-focused contact.
+                        if matches!(self.state, AppState::Ready) && self.focused.is_none() {
+                            if let ContactNavOutcome::Open(ci) = contact_nav(&self.contact_display_order(), self.contact_key_focus, ContactNavKey::Enter) {
+                                self.open_conversation(ci);
+                                ctx.window.request_redraw();
+                                return EventResponse::Handled;
+                            }
+                        }
                         let focused_is_launch_textbox = self
                             .textbox
                             .as_ref()
@@ -3191,6 +4334,7 @@ impl FluorApp for PhotonApp {
                             // Shift+Enter inserts a newline (multi-line compose); plain Enter sends.
                             if ctx.modifiers.shift_key() {
                                 if let Some(focus_id) = self.focused {
+                                    let before = self.textbox_snapshot(focus_id);
                                     let resp = widget::dispatch_key(
                                         self,
                                         focus_id,
@@ -3198,6 +4342,7 @@ impl FluorApp for PhotonApp {
                                         ctx.modifiers,
                                         ctx.text,
                                     );
+                                    self.record_textbox_edit(focus_id, before, text_undo::EditKind::Other);
                                     if matches!(resp, EventResponse::Handled) {
                                         ctx.window.request_redraw();
                                     }
@@ -3266,8 +4411,14 @@ impl FluorApp for PhotonApp {
                                 } else {
                                     None
                                 };
+                            let undo_before = self.textbox_snapshot(focus_id);
                             let resp =
                                 widget::dispatch_key(self, focus_id, kev, ctx.modifiers, ctx.text);
+                            // A plain typed character joins the typing run; backspace, delete, Ctrl+A-then-type etc. are steps of their own (a caret-only key just ends the run).
+                            let typed = matches!(&kev.logical_key, Key::Character(_))
+                                && !(ctx.modifiers.control_key() || ctx.modifiers.super_key());
+                            let kind = if typed { text_undo::EditKind::Typing } else { text_undo::EditKind::Other };
+                            self.record_textbox_edit(focus_id, undo_before, kind);
                             if let Some(before) = launch_text_before {
                                 if self.textbox.as_ref().map(|tb| &tb.chars) != Some(&before) {
                                     self.clear_launch_error();
@@ -3292,6 +4443,8 @@ impl FluorApp for PhotonApp {
                 // Android: soft IME committed `s` (typing, swipe, autocomplete). Route it to whichever textbox holds focus — the attest handle field OR the contacts search box. (This used to be hardcoded to the attest box, so typing on the contacts screen was silently dropped on Android even though focus + the soft keyboard were correct; desktop never hit this because physical keys go thru the focus-generic `widget::dispatch_key`.) Backspace arrives as the literal "\b" character from PhotonSurfaceView's deleteSurroundingText / composing-text replacement path, so peel those off and route to `backspace`; everything else inserts verbatim. No-op when no textbox is focused (focus might sit on the attest button via Tab).
                 let mut handled = false;
                 let words_screen = matches!(self.state, AppState::AddDevice);
+                let focused = self.focused;
+                let undo_before = focused.and_then(|id| self.textbox_snapshot(id));
                 if let Some(tb) = self.focused_textbox_mut() {
                     for c in s.chars() {
                         if c == '\u{0008}' {
@@ -3303,6 +4456,15 @@ impl FluorApp for PhotonApp {
                     }
                     handled = true;
                 }
+                if let Some(id) = focused.filter(|_| handled) {
+                    // One committed char is typing; a swipe word, an autocomplete or a "\b" is a step of its own.
+                    let kind = if s.chars().count() == 1 && !s.contains('\u{0008}') {
+                        text_undo::EditKind::Typing
+                    } else {
+                        text_undo::EditKind::Other
+                    };
+                    self.record_textbox_edit(id, undo_before, kind);
+                }
                 if handled {
                     // Soft-IME edits are edits: tear down the Error/Confirm interstitial exactly like physical keystrokes, so Android can't re-arm stale probed roots either.
                     if matches!(self.state, AppState::Launch(_)) {
@@ -3314,12 +4476,18 @@ impl FluorApp for PhotonApp {
                 }
                 EventResponse::Pass
             }
+            Event::DroppedFile(path) if matches!(self.state, AppState::Conversation) => {
+                // A file dropped on an open conversation is sent — an image inline, anything else as an attachment. Read + (for images) re-encoded off-thread (a big photo's decode is hundreds of ms), sent from tick.
+                self.drop_file_into_conversation(path);
+                ctx.window.request_redraw();
+                EventResponse::Handled
+            }
             Event::DroppedFile(path) => {
-                // Desktop avatar update: a file dropped on the window (Ready screen) is read and run thru the same encode→save→load→install→upload pipeline as the Android picker. Ignored off the Ready screen and when no handle is attested yet (set_avatar_from_file no-ops without a handle). Android has no drop path — it uses the picker.
+                // Desktop avatar update: a file dropped on the window (Ready screen) is read and framed on the crop screen, then run thru the same encode→save→load→install→upload pipeline as the Android picker. Ignored off the Ready screen and when no handle is attested yet (frame_avatar no-ops without a handle). Android has no drop path — it uses the picker.
                 if matches!(self.state, AppState::Ready) {
                     match std::fs::read(path) {
                         Ok(bytes) => {
-                            self.set_avatar_from_file(bytes);
+                            self.frame_avatar(bytes);
                             // Force a FULL repaint, not just a redraw request: on macOS the drop arrives during the drag-session teardown and the incremental present can get swallowed — the avatar then only appeared after the next click. Invalidate everything so the post-drop frame rebuilds + re-presents the whole window.
                             self.scene_dirty = true;
                             if let Some(chrome) = self.chrome.as_mut() {
@@ -3350,7 +4518,7 @@ impl FluorApp for PhotonApp {
         // Next background presence sweep — keeps online/offline rings refreshing while idle (no input/network). Only on Ready; first sweep is due immediately if never run. Interval tapers with idle time, so as the user stays away the scheduled wake naturally pushes further out.
         let presence = matches!(self.state, AppState::Ready).then(|| {
             let now = Instant::now();
-            self.last_presence_ping
+            self.sweeps.presence_ping
                 .map_or(now, |last| last + self.presence_ping_interval(now))
         });
         // Pairing flows: join-words (new device) and add-device matcher/confirm (old device) results arrive on mpsc channels from worker threads, with nothing else guaranteed to drive a tick while the user's hands are off — so poll-drain at 2 Hz while either flow is live. This is channel plumbing, not time-based UI: nothing is shown or cleared on a clock.
@@ -3358,43 +4526,134 @@ impl FluorApp for PhotonApp {
             .then(|| Instant::now() + std::time::Duration::from_millis(500));
         // Periodic own-chain re-fold (the fleet-membership doorbell) — scheduled on the screens where a stale fleet view matters, so it fires even while the desktop window sits idle on the Fleet page. 45s matches advance_protocol's cadence.
         let fleet_refold = matches!(self.state, AppState::Ready | AppState::Conversation | AppState::Settings(_))
-            .then(|| self.last_fleet_refold.map_or_else(Instant::now, |last| last + std::time::Duration::from_secs(45)));
-        // Soonest of all scheduled wakeups.
-        [blink, anim, presence, pairing, fleet_refold].into_iter().flatten().min()
+            .then(|| self.sweeps.fleet_refold.map_or_else(Instant::now, |last| last + std::time::Duration::from_secs(45)));
+        // Animated self-avatar on Ready: wake at the next frame boundary.
+        let avatar_frame = matches!(self.state, AppState::Ready)
+            .then_some(self.device_avatar_anim.as_ref())
+            .flatten()
+            .and_then(|(clip, start, _)| clip.frame_at(start.elapsed().as_millis() as u64).1)
+            .map(|ms| Instant::now() + std::time::Duration::from_millis(ms));
+        // A pending attestation retry fires on its own backoff deadline.
+        let attest_retry = matches!(self.state, AppState::Launch(LaunchState::Retrying { .. }))
+            .then(|| self.attest_retry.due_at())
+            .flatten();
+        // A history press held on Android opens the message menu once it has been still for the long-press timeout.
+        let long_press = self
+            .msg_press_at
+            .filter(|_| self.msg_select_anchor.is_some())
+            .map(|at| at + context_menu::LONG_PRESS);
+        // Going away lands on time, so the next pong already carries it.
+        let away = self.away.deadline(self.last_interaction);
+        // A batched contact upload goes up once its changes have gone quiet, idle window or not.
+        let cloud_sync = self.cloud_sync.due_at();
+        // Soonest of all scheduled wakeups — noted for the resume detector, which reads a tick arriving far past it as a machine that slept thru it.
+        let wake = [blink, anim, presence, pairing, fleet_refold, avatar_frame, attest_retry, long_press, away, cloud_sync].into_iter().flatten().min();
+        self.resume.expect_wake(wake);
+        wake
     }
 
     fn tick(&mut self, ctx: &mut Context) -> bool {
         let now = Instant::now();
         let mut needs_redraw = false;
+        self.frame_stats.record_tick();
 
-        // Toast screen-change watch: capture the screen the toast first renders on; a later mismatch (user navigated) clears it. Clicks/scrolls/zoom never clear a toast — see clear_toast.
-        if self.ready_toast.is_some() {
-            let here = std::mem::discriminant(&self.state);
-            match self.ready_toast_screen {
-                None => self.ready_toast_screen = Some(here),
-                Some(d) if d != here => {
-                    self.clear_toast();
+        // Back from sleep: every NAT mapping, the relay pipe and all presence are stale — reconnect now rather than on the next poll.
+        if let Some(gap) = self.resume.observe(now, std::time::SystemTime::now()) {
+            self.force_reconnect(&format!("resumed after ~{}s asleep", gap.as_secs()));
+            needs_redraw = true;
+        }
+
+        // Idle past the away threshold (or back at the keys): publish it for the pongs.
+        if let Some(away) = self.away.observe(self.last_interaction, now) {
+            crate::network::status::set_away(away);
+            crate::logf!("UI: presence now {}", if away { "away" } else { "present" });
+        }
+
+        // Answer any scripting-socket requests on the thread that owns the contacts.
+        #[cfg(not(target_os = "android"))]
+        crate::platform::rpc::serve_pending(self);
+
+        // Long-press on a history row: a hold whose drag hasn't covered a character yet is a menu request, not a selection — open the menu and drop the drag so the release isn't also a tap.
+        if let (Some(at), Some(anchor)) = (self.msg_press_at, self.msg_select_anchor) {
+            if now >= at + context_menu::LONG_PRESS {
+                self.msg_press_at = None;
+                let still = message_select::selection_range(&self.msg_rows, anchor, self.msg_select_head).is_none();
+                if still && self.open_message_menu(anchor.0, anchor.1) {
+                    self.msg_select_anchor = None;
                     needs_redraw = true;
                 }
-                _ => {}
             }
         }
 
-        // Page-change focus drop (see `last_screen`): a screen swap must never leave the previous screen's textbox focused — the orphaned box kept its blinkey firing and its focus glow lit after navigating away. `change_focus(None)` also lowers the Android IME via the pending-keyboard signal.
-        if self.state != self.last_screen {
-            let same_screen = match (&self.state, &self.last_screen) {
-                (AppState::Launch(_), AppState::Launch(_)) => true,
-                (AppState::Ready | AppState::Searching, AppState::Ready | AppState::Searching) => true,
-                (AppState::Settings(a), AppState::Settings(b)) => a == b,
-                _ => false,
-            };
-            if !same_screen {
-                self.change_focus(None);
-                // A screen swap must also re-raster the CACHED bg layer — it's dirty-gated and nothing else invalidates it on navigation, so the previous screen's backdrop stayed baked beneath the new one (the launch chromatic wave + wordmark showing thru the settings panel; the settings divider-split noise lingering after Back). One noise re-raster per screen change is cheap.
-                if let Some(chrome) = self.chrome.as_mut() {
-                    chrome.invalidate_bg();
-                }
-                needs_redraw = true;
+        // Back down at the newest message (wheel, End, a page) — whatever arrived while reading back is now in view, so it's read. The jump pill does the same on a press.
+        if matches!(self.state, AppState::Conversation) {
+            if let Some(ci) = self.active_contact {
+                let caught_up = self.contacts.get(ci).is_some_and(|c| {
+                    c.unread_count > 0 && !message_select::away_from_latest(c.message_scroll_offset, self.msg_newest_h)
+                });
+                if caught_up {
+                    self.clear_unread(ci);
+                    self.scene_dirty = true;
+                    needs_redraw = true;
+                }
+            }
+        }
+
+        // Re-fire a transiently-failed attestation once its backoff is up.
+        if matches!(self.state, AppState::Launch(LaunchState::Retrying { .. })) && self.attest_retry.take_due(now) {
+            self.refire_attest();
+            needs_redraw = true;
+        }
+
+        // Publish the unread total to the tray tooltip (no-op unless it moved). Siblings never count — they carry fleet sync, not conversation.
+        #[cfg(not(target_os = "android"))]
+        crate::platform::tray::set_unread(
+            self.contacts.iter().filter(|c| !c.is_sibling).map(|c| c.unread_count).sum(),
+        );
+
+        // Animated self-avatar: swap in the frame due now (the Ready screen is the only surface that animates; `wake_at` schedules the next boundary).
+        if matches!(self.state, AppState::Ready) {
+            if let Some((anim, start, shown)) = self.device_avatar_anim.as_mut() {
+                let (frame, _) = anim.frame_at(now.duration_since(*start).as_millis() as u64);
+                if frame != *shown {
+                    *shown = frame;
+                    self.device_avatar_pixels = Some(anim.frames[frame].clone());
+                    self.device_avatar_scaled = None;
+                    self.device_avatar_scaled_diameter = 0;
+                    self.scene_dirty = true;
+                    needs_redraw = true;
+                }
+            }
+        }
+
+        // Toast screen-change watch: capture the screen the toast first renders on; a later mismatch (user navigated) clears it. Clicks/scrolls/zoom never clear a toast — see clear_toast.
+        if self.ready_toast.is_some() {
+            let here = std::mem::discriminant(&self.state);
+            match self.ready_toast_screen {
+                None => self.ready_toast_screen = Some(here),
+                Some(d) if d != here => {
+                    self.clear_toast();
+                    needs_redraw = true;
+                }
+                _ => {}
+            }
+        }
+
+        // Page-change focus drop (see `last_screen`): a screen swap must never leave the previous screen's textbox focused — the orphaned box kept its blinkey firing and its focus glow lit after navigating away. `change_focus(None)` also lowers the Android IME via the pending-keyboard signal.
+        if self.state != self.last_screen {
+            let same_screen = match (&self.state, &self.last_screen) {
+                (AppState::Launch(_), AppState::Launch(_)) => true,
+                (AppState::Ready | AppState::Searching, AppState::Ready | AppState::Searching) => true,
+                (AppState::Settings(a), AppState::Settings(b)) => a == b,
+                _ => false,
+            };
+            if !same_screen {
+                self.change_focus(None);
+                // A screen swap must also re-raster the CACHED bg layer — it's dirty-gated and nothing else invalidates it on navigation, so the previous screen's backdrop stayed baked beneath the new one (the launch chromatic wave + wordmark showing thru the settings panel; the settings divider-split noise lingering after Back). One noise re-raster per screen change is cheap.
+                if let Some(chrome) = self.chrome.as_mut() {
+                    chrome.invalidate_bg();
+                }
+                needs_redraw = true;
             }
             self.last_screen = self.state.clone();
         }
@@ -3437,6 +4696,18 @@ impl FluorApp for PhotonApp {
                 }
             }
         }
+        // Verification QR: a scanned card verifies the contact it names, or adds that identity already verified (network::qr_scan).
+        if let Some(text) = crate::network::qr_scan::take_scanned() {
+            self.apply_scanned_card(&text);
+            needs_redraw = true;
+        }
+        // A clicked photon://add/… link (launch arg, second-launch handoff, Android intent — platform::link). Waits for a session: the add needs our identity.
+        if self.session.is_some() {
+            if let Some(link) = crate::platform::link::take_link() {
+                self.apply_contact_link(&link);
+                needs_redraw = true;
+            }
+        }
 
         // Compute per-tick delta_time for the attest-animation accumulator. `last_tick` is None on the very first tick — bootstrap to "zero elapsed" so the accumulator doesn't take a huge jump on startup.
         let delta_time = match self.last_tick {
@@ -3590,6 +4861,8 @@ impl FluorApp for PhotonApp {
             }
             return Some(combined);
         }
+        // The perf readout changes every frame, so it rides along with any frame that's happening anyway — but never forces one: an idle screen stays idle and the overlay just holds its last numbers.
+        let perf_band = self.perf_overlay.then(|| perf_overlay_band(vw, vh));
         // Pure widget frame (blinkey flip, drag-select growth): union each active widget's self-reported damage. This walks the SAME `visit_app_widgets` registry as dispatch/hover/render, so the gate AUTOMATICALLY mirrors what's drawn — a new textbox's blinkey/selection damage is claimed with zero hand-list (the recurring "new box's blinkie stacks / forces full-screen redraws" bug). `None` = nothing changed, host skips the render entirely.
         let mut combined: Option<PixelRect> = None;
         if let Some(chrome) = self.chrome.as_ref() {
@@ -3602,7 +4875,10 @@ impl FluorApp for PhotonApp {
                 combined = Some(combined.map_or(r, |c| c.union(r)));
             }
         });
-        combined
+        match (combined, perf_band) {
+            (Some(c), Some(band)) => Some(c.union(band)),
+            (c, _) => c,
+        }
     }
 
     fn render(&mut self, target: &mut [u32], ctx: &mut Context) {
@@ -3612,10 +4888,16 @@ impl FluorApp for PhotonApp {
         // Compute chord-held state BEFORE taking the mutable `chrome` borrow — `brackets_held` reads `&self` and the chrome borrow lives thru the entire render. Update `last_chord_held` here too so the next frame's `damage_rect` knows whether to include the hint bbox for the one-frame clear.
         let held_now = self.brackets_held(Instant::now());
         self.last_chord_held = held_now;
+        self.frame_stats.record_frame(Instant::now());
+        let perf_text = self.perf_overlay.then(|| self.frame_stats.readout());
         let show_hitmask = self.show_hitmask;
         // Snapshot the colour table so the post-flatten hitmask overlay can read it after the chrome borrow ends.
         let buf_w = ctx.viewport.width_px as usize;
         let buf_h = ctx.viewport.height_px as usize;
+        // A message menu belongs to the conversation on screen — leaving it (Back, Esc, a contact panel) drops the menu rather than resurrecting it on return.
+        if !matches!(self.state, AppState::Conversation) {
+            self.msg_menu = None;
+        }
 
         // Arm the zoom hint: the host swallows zoom events and mutates `ru` directly, so we detect a zoom by `ru` changing frame-to-frame. Arm only when a zoom modifier is held (so a programmatic/resize ru change wouldn't trigger it, and merely holding Ctrl with no scroll doesn't either — the change is what arms it). `ModifiersChanged` clears it on release.
         let zoom_mod_held = ctx.modifiers.control_key() || ctx.modifiers.super_key();
@@ -3652,7 +4934,7 @@ impl FluorApp for PhotonApp {
                 })
                 .unwrap_or(0);
             format!("{n} {}", if n == 1 { "peer" } else { "peers" })
-        } else if matches!(self.state, AppState::Settings(_)) {
+        } else if matches!(self.state, AppState::Settings(_) | AppState::AvatarCrop) {
             // The settings screen draws its own "Settings" heading in the header band — a chrome title would double up behind it (portrait showed "‹ Network" bleeding thru the heading).
             String::new()
         } else {
@@ -3664,21 +4946,20 @@ impl FluorApp for PhotonApp {
         if matches!(self.state, AppState::Ready) {
             let rl = ReadyLayout::compute(buf_w, buf_h, ctx.viewport.ru);
             let row_h = rl.row_height.max(1) as isize;
-            let filter: String = self
-                .contacts_textbox
-                .as_ref()
-                .map(|t| t.chars.iter().collect::<String>().to_lowercase())
-                .unwrap_or_default();
-            let n_matching = self
-                .contacts
-                .iter()
-                .filter(|c| {
-                    // Must mirror the render pass's `matching` filter exactly (siblings hidden) or the two clamps disagree within a frame.
-                    !c.is_sibling
-                        && (filter.is_empty()
-                            || c.display_name().to_lowercase().contains(&filter))
-                })
-                .count();
+            // The SAME display order the render pass draws from, so the two clamps can't disagree within a frame.
+            let order = self.contact_display_order();
+            let n_matching = order.len();
+            // Keyboard navigation moved the focus: scroll just far enough to bring that row fully on screen (once — the wheel owns the scroll again after).
+            if std::mem::replace(&mut self.contact_key_reveal, false) {
+                if let Some(vis) = self.contact_key_focus.and_then(|ci| order.iter().position(|&o| o == ci)) {
+                    let top = rl.rows.y0 as isize + vis as isize * row_h;
+                    if top < self.contacts_scroll {
+                        self.contacts_scroll = top;
+                    } else if top + row_h - self.contacts_scroll > buf_h as isize {
+                        self.contacts_scroll = top + row_h - buf_h as isize;
+                    }
+                }
+            }
             let block_bottom_at_zero = rl.rows.y0 as isize + n_matching as isize * row_h;
             // The version footer rides the block one row-height past the last row; extend the scroll extent past it (footer gap + a row-height of bottom margin) so the user can scroll the version fully into view instead of the bottom edge swallowing it.
             let block_end = block_bottom_at_zero + row_h * 2;
@@ -3714,7 +4995,7 @@ impl FluorApp for PhotonApp {
             }
             (self.settings_rail_scroll, self.settings_content_scroll)
         } else if let AppState::ContactPanel(cpage) = self.state {
-            // The contact panel rides the SAME scroll fields + extents machinery as settings (it's the structural mirror). Rail = pinned Back + 3 page rows; content rows are fixed per page (About carries the avatar block's extra height as virtual rows).
+            // The contact panel rides the SAME scroll fields + extents machinery as settings (it's the structural mirror). Rail = pinned Back + the page rows; content rows are fixed per page (About carries the avatar block's extra height as virtual rows).
             let sl = SettingsLayout::compute(&ctx.viewport);
             self.settings_rail_extent = (sl.nav_row_h() * (ContactPage::ALL.len() as Coord + 1.0) - sl.rail_inset().h).max(0.0);
             let n = contact_page_rows(cpage);
@@ -3786,6 +5067,9 @@ impl FluorApp for PhotonApp {
         let zoom_text = format!("{}%", (ctx.viewport.ru * 100.0).round() as i64);
         let zoom_cx = buf_w as f32 * 0.5;
         let zoom_cy = zoom_size;
+        // Perf readout, top-left: the zoom watermark's size, one font-size in from the corner (inside `perf_overlay_band`).
+        let perf_x = zoom_size;
+        let perf_cy = zoom_size;
         // Split-borrow `ctx.damage` (consumed by rasterize_bg's first arg) and `ctx.text` (captured by the closure for the logo's text rendering). These are disjoint fields of `Context` so the borrow checker allows both reborrows simultaneously. The closure is non-`move` so the text reborrow ends when rasterize_bg returns, leaving `ctx.text` available for `rasterize_chrome` on the next line.
         let text = &mut *ctx.text;
         // Bg-first compose chain: noise paints opaque, the wave reads it for the `sqrt(c*scale + c_bg²)` blend, then the logo (glow / body / highlight) paints over both via legacy visible-RGB ops. Each step preserves α on the pixels it touches. The wave + logo are Launch-screen chrome — once attested the user shouldn't be staring at the wordmark every time they open the app, so Ready / Searching / Conversation get just the background noise and let their own widgets own the canvas.
@@ -3815,6 +5099,9 @@ impl FluorApp for PhotonApp {
             if show_zoom {
                 text.draw_text_center(canvas, &zoom_text, zoom_cx, zoom_cy, &TextStyle::new(zoom_size, theme::ZOOM_COLOUR).font("Oxanium"), None, None);
             }
+            if let Some(perf) = &perf_text {
+                text.draw_text_left(canvas, perf, perf_x, perf_cy, &TextStyle::new(zoom_size, theme::PERF_COLOUR).font("Oxanium"), None, None);
+            }
             paint::background_noise_split(canvas, shimmer, bg_fullscreen, bg_right_scroll, bg_split_x, bg_left_scroll, None, bg_base);
             // Wave then logo — RMW ops that read the now-opaque noise beneath as their base. The chromatic wave quadrature-blends with the bg colour (sqrt-linear-light) so it MUST follow the noise; the logo composites over the wave/noise. (Watermarks above went before the noise so it composes under them.)
             if on_launch {
@@ -3854,12 +5141,17 @@ impl FluorApp for PhotonApp {
             );
 
             // Status slot — `attest.error` rect above the textbox. Carries either the red error message (`LaunchState::Error`) or the white "Attesting…" indicator (`LaunchState::Attesting`); empty in Fresh. Same geometry for both so they swap in place; colour differentiates "something's wrong" from "we're working". Wave's 1-cycle/sec phase animation pairs with the "Attesting…" line as the secondary cue.
+            let retry_line;
             let status: Option<(&str, u32)> =
                 if self.launch_add_mode && !self.add_join_status.is_empty() {
                     Some((self.add_join_status.as_str(), (*theme::STATUS_TEXT_COLOUR)))
                 } else {
                     match launch_state {
                         LaunchState::Attesting => Some(("Attesting\u{2026}", (*theme::STATUS_TEXT_COLOUR))),
+                        LaunchState::Retrying { attempt, of } => {
+                            retry_line = format!("Network trouble \u{2014} retrying ({attempt}/{of})\u{2026}");
+                            Some((retry_line.as_str(), (*theme::STATUS_TEXT_COLOUR)))
+                        }
                         LaunchState::Error(msg) if !msg.is_empty() => {
                             Some((msg.as_str(), (*theme::ERROR_TEXT_COLOUR)))
                         }
@@ -3873,9 +5165,46 @@ impl FluorApp for PhotonApp {
                     let cx = (error_rect.x0 + error_rect.x1) as f32 * 0.5;
                     let cy = (error_rect.y0 + error_rect.y1) as f32 * 0.5;
                     // Half-height font: status messages are short by convention; full-rect-height is too loud for one-line text and overflows wide messages off the side.
-                    ctx.text.draw_text_center(&mut canvas, text, cx, cy, &TextStyle::new(region_h * 0.5, // Medium weight — readable at small sizes; matches the Oxanium family already loaded in init().
-                        colour).weight(500).font("Oxanium"), None, None);
+                    let style = TextStyle::new(region_h * 0.5, // Medium weight — readable at small sizes; matches the Oxanium family already loaded in init().
+                        colour).weight(500).font("Oxanium");
+                    ctx.text.draw_text_center(&mut canvas, text, cx, cy, &style, None, None);
+                    // Publish the line for drag-select. A changed message drops a selection made over the old one.
+                    if self.status_row.as_ref().is_some_and(|(old, _)| old != text) {
+                        self.status_selection = None;
+                    }
+                    let selecting = self.status_select_anchor.is_some() || self.status_selection.is_some();
+                    let mut widths = Vec::new();
+                    if selecting {
+                        let mut prefix = String::new();
+                        for ch in text.chars() {
+                            prefix.push(ch);
+                            widths.push(ctx.text.measure_text(&prefix, &style));
+                        }
+                    }
+                    let row = message_select::centered_row(&widths, cx, error_rect.y0 as f32, error_rect.y1 as f32);
+                    if let Some(anchor) = self.status_select_anchor {
+                        self.status_selection = message_select::selection_range(std::slice::from_ref(&row), anchor, self.status_select_head);
+                    }
+                    // Painted after the text, so the under-blend lands it beneath the glyphs (same as the history highlight).
+                    if let Some((from, to)) = self.status_selection.and_then(|r| message_select::row_span(0, r)) {
+                        if let (Some(&x0), Some(&x1)) = (row.edges.get(from), row.edges.get(to.min(row.edges.len().saturating_sub(1)))) {
+                            paint::fill_rect(
+                                &mut canvas,
+                                x0 as isize,
+                                row.top as isize,
+                                (x1 - x0) as isize,
+                                (row.bottom - row.top) as isize,
+                                *theme::SELECTION_COLOUR,
+                                None,
+                                None,
+                            );
+                        }
+                    }
+                    self.status_row = Some((text.to_string(), row));
                 }
+            } else {
+                self.status_row = None;
+                self.status_selection = None;
             }
 
             // Permanence warning block (`LaunchState::Confirm`) — drawn in the empty 6-unit band BELOW the attest button, sized with the same ru-scaled math as the join-words rows. The headline takes the error colour for gravity; the detail lines stay in status grey. The button above now reads "Yes — forever"; editing the handle cancels back to Fresh.
@@ -4183,6 +5512,35 @@ impl FluorApp for PhotonApp {
                 ctx.text.draw_text_center(&mut canvas, "drag/drop to update avatar", cx, hcy, &TextStyle::new(size, fluor::theme::HINT_COLOUR).weight(500).font("Oxanium"), None, None);
             }
 
+            // "Copy my handle" in the handle slot under the avatar — tap copies handle + contact link (on_activate). Yields the slot to the desktop avatar hover hint, which sits in the same place.
+            #[cfg(not(target_os = "android"))]
+            let share_visible = !self.avatar_hovered;
+            #[cfg(target_os = "android")]
+            let share_visible = true;
+            {
+                let slot = &ready_layout.handle;
+                let (sy0, sy1) = (slot.y0 as f32 - scroll, slot.y1 as f32 - scroll);
+                // Half the content width either side of centre — generous for a finger, and the slot holds nothing else.
+                let half_w = (slot.x1 - slot.x0) as f32 * 0.25;
+                let shown = share_visible && self.session.is_some();
+                if shown {
+                    let size = (sy1 - sy0) * 0.45;
+                    let label = if self.our_handle.is_some() { "copy my handle" } else { "copy my contact link" };
+                    ctx.text.draw_text_center(&mut canvas, label, cx, (sy0 + sy1) * 0.5, &TextStyle::new(size, *theme::CONTACT_NAME_COLOUR).weight(600).font("Oxanium"), None, None);
+                }
+                // Stamped every frame, cleared when hidden — a stale stamp under the hover hint would still copy.
+                restamp_hit_rect(
+                    &mut chrome.hit_test_map,
+                    buf_w,
+                    buf_h,
+                    (cx - half_w) as isize,
+                    sy0 as isize,
+                    (cx + half_w) as isize,
+                    sy1 as isize,
+                    if shown { self.share_hit_id } else { HIT_NONE },
+                );
+            }
+
             // Contacts-page textbox + plus button. The plus button is OVERLAID inside the textbox right edge and ONLY rendered when the textbox has content — empty textbox shows no button. While an add-friend search is in flight, a rotating hourglass replaces the button (and the button is not hit-stampable, so it can't be re-clicked mid-search).
             //
             // Under-blend is topmost-FIRST (first opaque writer wins colour AND its per-pixel hit stamp). Paint the button/hourglass BEFORE the textbox: the button claims its exact pill silhouette in the framebuffer and hit map, and the textbox drawn under it can't overwrite either (its own stamp is per-opaque-pixel too). No hit re-stamp — the draw yields the correct pill-shaped hit area on its own.
@@ -4264,8 +5622,43 @@ impl FluorApp for PhotonApp {
                 }
             }
 
+            // A contact link awaiting the user's say-so — in the hint slot, pressable. Stamped every frame, cleared when there's nothing pending, like the share label. A held transfer takes the slot once no link is waiting.
+            {
+                let hint = ready_layout.hint;
+                let shown = self.pending_link.is_some() && !hint.is_empty();
+                let held_shown = !shown && !self.held_transfers.is_empty() && !hint.is_empty();
+                let prompt = if let (true, Some(card)) = (shown, self.pending_link.as_ref()) {
+                    Some(format!("Add {} from this link? Tap to add", crate::fp(&card.party_id)))
+                } else if let (true, Some((peer_addr, _, total_size, _))) = (held_shown, self.held_transfers.first()) {
+                    let from = self
+                        .contacts
+                        .iter()
+                        .find(|c| c.ip == Some(*peer_addr) || c.validated_path.is_some_and(|(a, _)| a == *peer_addr))
+                        .map_or_else(|| "A contact".to_string(), |c| c.display_name());
+                    Some(format!("{} wants to send {:.1} MiB \u{2014} tap to download", from, *total_size as f32 / (1 << 20) as f32))
+                } else {
+                    None
+                };
+                if let Some(prompt) = prompt {
+                    let region_h = (hint.y1 - hint.y0) as f32;
+                    let tcx = (hint.x0 + hint.x1) as f32 * 0.5;
+                    let tcy = (hint.y0 + hint.y1) as f32 * 0.5 - scroll;
+                    ctx.text.draw_text_center(&mut canvas, &prompt, tcx, tcy, &TextStyle::new(region_h * 0.6, *theme::SEARCH_FOUND_COLOUR).weight(600).font("Oxanium"), None, None);
+                }
+                restamp_hit_rect(
+                    &mut chrome.hit_test_map,
+                    buf_w,
+                    buf_h,
+                    hint.x0 as isize,
+                    (hint.y0 as f32 - scroll) as isize,
+                    hint.x1 as isize,
+                    (hint.y1 as f32 - scroll) as isize,
+                    if shown { self.link_confirm_hit_id } else if held_shown { self.transfer_accept_hit_id } else { HIT_NONE },
+                );
+            }
+
             // "Device added √" confirmation — in the hint slot ABOVE the search box (not the bottom band). Green; sits until the next click/keystroke clears it via clear_hints (never time-based). Lifts one line when the add-friend result already occupies the hint slot so the two don't overlap.
-            if let Some(msg) = &self.ready_toast {
+            if let (Some(msg), None, true) = (&self.ready_toast, &self.pending_link, self.held_transfers.is_empty()) {
                 let hint = ready_layout.hint;
                 if !hint.is_empty() {
                     let region_h = (hint.y1 - hint.y0) as f32;
@@ -4296,26 +5689,7 @@ impl FluorApp for PhotonApp {
             // Rows now scroll up into (and past) where the user section sat, so the clip can no longer stop at `rows.y0`. Clip top = the top of the content area (0); the chrome title bar composites on top afterwards via `chrome.flatten_into`, exactly as it does for the unclipped avatar that already draws high. Keep the x extent at the rows' columns.
             let rows_clip = fluor::paint::Clip::new(rows.x0, 0, rows.x1, buf_h);
 
-            // Filter by the search text (case-insensitive substring on the handle); empty filter = all.
-            let filter: String = self
-                .contacts_textbox
-                .as_ref()
-                .map(|t| t.chars.iter().collect::<String>().to_lowercase())
-                .unwrap_or_default();
-            let mut matching: Vec<usize> = self
-                .contacts
-                .iter()
-                .enumerate()
-                .filter(|(_, c)| {
-                    // Fleet siblings are infrastructure, not conversations — never listed (device management gets its own page later).
-                    !c.is_sibling
-                        && (filter.is_empty()
-                            || c.display_name().to_lowercase().contains(&filter))
-                })
-                .map(|(i, _)| i)
-                .collect();
-            // FLOAT: unread conversations surface to the top. `matching` is the ONE place display order exists — the row loop draws from it AND stamps each row's hit id with the TRUE contact index it holds, so the tap handler resolves taps with no knowledge of the permutation. Stable sort preserves vault order within each group (incl. the self contact's relative position).
-            matching.sort_by_key(|&ci| u8::from(self.contacts[ci].unread_count == 0));
+            let matching = self.contact_display_order();
 
             // Clamp scroll over the FULL block (user section + rows + version footer), hard-stop at both ends. Down-scroll stops when the version footer (one row past the last row) plus a row of bottom margin reaches the screen bottom; up-scroll stops at rest (0), with the avatar at its natural top. MUST match the pre-chrome clamp above (`block_end = block_bottom_at_zero + row_h*2`) so both passes agree within a frame.
             let block_bottom_at_zero = rows.y0 as isize + matching.len() as isize * row_h;
@@ -4345,29 +5719,25 @@ impl FluorApp for PhotonApp {
                 // Hover/press vocabulary (block tints vetoed): hover = the NAME goes heavier + the presence ring strokes 1px wider; press = the logo's white-glow halo blooms behind the name. No fills, no deltas — weight, stroke, and light.
                 let row_hit_here = self.contact_hit_base.wrapping_add(ci as HitId);
                 let row_pressed = ci < 256 && ctx.pressed_hit != HIT_NONE && ctx.pressed_hit == row_hit_here;
-                let row_hovered = row_pressed || (ci < 256 && ctx.pressed_hit == HIT_NONE && self.hover_hit == row_hit_here);
+                let row_hovered = row_pressed
+                    || (ci < 256 && ctx.pressed_hit == HIT_NONE && self.hover_hit == row_hit_here)
+                    || self.contact_key_focus == Some(ci);
                 let cy = (row_top + row_h / 2) as f32;
                 let _online = self.contacts[ci].is_online;
                 let _online_via_relay = self.contacts[ci].reached_via_relay;
 
                 // Build/refresh the contact's scaled-avatar cache at the row diameter.
-                let has_avatar = self.contacts[ci].avatar_pixels.is_some();
-                if has_avatar
-                    && (self.contacts[ci].avatar_scaled.is_none()
-                        || self.contacts[ci].avatar_scaled_diameter != diam)
-                {
-                    let base = self.contacts[ci].avatar_pixels.as_ref().unwrap();
-                    let scaled = crate::ui::avatar_render::update_avatar_scaled(
-                        base,
-                        crate::ui::avatar::AVATAR_SIZE,
-                        diam,
-                    );
-                    self.contacts[ci].avatar_scaled = Some(scaled);
-                    self.contacts[ci].avatar_scaled_diameter = diam;
+                let contact = &mut self.contacts[ci];
+                if let Some(base) = contact.avatar_pixels.as_ref() {
+                    contact.avatar_scaled.get_or_scale(base, crate::ui::avatar::AVATAR_SIZE, diam);
                 }
 
+                // Away badge: over the avatar's lower-right rim, so it paints first.
+                if self.contacts[ci].is_online && self.contacts[ci].away {
+                    draw_away_badge(&mut canvas, avatar_cx, cy, avatar_r, Some(rows_clip));
+                }
                 // Avatar (or placeholder) is topmost; the presence ring paints UNDER it so only the rim shows.
-                if let Some(scaled) = self.contacts[ci].avatar_scaled.as_ref() {
+                if let Some(scaled) = self.contacts[ci].avatar_scaled.get(diam) {
                     crate::ui::avatar_render::draw_avatar(
                         &mut canvas,
                         avatar_cx,
@@ -4426,6 +5796,15 @@ impl FluorApp for PhotonApp {
                     Some(rows_clip),
                 );
 
+                // Pin marker at the row's right end: a round head on a short needle, in the label grey — present exactly while pinned.
+                if self.contacts[ci].pinned {
+                    let head_r = text_size * 0.18;
+                    let px = rows.x1 as f32 - text_size;
+                    paint::draw_circle(&mut canvas, px, cy - head_r, head_r, *theme::LABEL_COLOUR, Some(rows_clip));
+                    let needle_w = (head_r * 0.3).max(1.0);
+                    paint::fill_rect(&mut canvas, (px - needle_w * 0.5) as isize, (cy - head_r) as isize, needle_w as isize, (head_r * 3.0) as isize, *theme::LABEL_COLOUR, Some(rows_clip), None);
+                }
+
                 // Handle name, vertically centred in the row, clipped to the list region — in this contact's relationship colour (computed above).
                 // "Pending…" reads in SHEAR (the honest oblique — tan 12°): a name-shaped placeholder must not look like a name. Hover reads as WEIGHT (500 → 700), not a fill — and an unread row holds that same 700 weight until opened.
                 let row_weight = if row_hovered || unread { 700 } else { 500 };
@@ -4477,6 +5856,30 @@ impl FluorApp for PhotonApp {
                 }
             }
 
+            // Archived filter link, right-aligned in the gap between the last row and the version footer: "archived (N) ›" while any are put away, "‹ conversations" while the filter is on.
+            let archived_n = self.contacts.iter().filter(|c| c.archived && !c.is_sibling).count();
+            if archived_n > 0 || self.show_archived {
+                let label = if self.show_archived { "\u{2039} conversations".to_string() } else { format!("archived ({archived_n}) \u{203a}") };
+                let ly = (rows.y0 as isize + matching.len() as isize * row_h) as f32 + row_h as f32 * 0.5 - scroll;
+                let link_hovered = self.hover_hit == self.archived_toggle_hit && self.archived_toggle_hit != HIT_NONE;
+                let style = TextStyle::new(text_size * 0.7, *theme::LABEL_COLOUR).weight(if link_hovered { 700 } else { 500 }).font("Oxanium");
+                let lw = ctx.text.measure_text(&label, &style);
+                ctx.text.draw_text_right(&mut canvas, &label, rows.x1 as f32 - text_size, ly, &style, Some(rows_clip), None);
+                let (y0, y1) = ((ly - text_size * 0.5) as isize, (ly + text_size * 0.5) as isize);
+                if y1 > 0 && y0 < buf_h as isize {
+                    restamp_hit_rect(
+                        &mut chrome.hit_test_map,
+                        buf_w,
+                        buf_h,
+                        (rows.x1 as f32 - text_size - lw) as isize,
+                        y0.max(0),
+                        (rows.x1 as f32 - text_size) as isize,
+                        y1.min(buf_h as isize),
+                        self.archived_toggle_hit,
+                    );
+                }
+            }
+
             // Persistent degraded-vault indicator: amber text at the bottom. The matching warm background tint already lives in the noise pass above (we swap BG_BASE → (*theme::BG_BASE_WARNING)) so we add no extra render pass here, just the text glyph. Full details live in the README.
             if self.vault_degraded {
                 // Visible RGB(255, 140, 0) amber. Packed: α=0xFF | darkness = (0x00, 0x73, 0xFF).
@@ -4512,6 +5915,18 @@ impl FluorApp for PhotonApp {
                 ctx.text.draw_text_center(&mut canvas, &label, cx, cy, &TextStyle::new(font_size, CLOCK_TEXT).weight(600).font("Oxanium"), None, None);
             }
 
+            // Do-not-disturb indicator: a quiet label-grey band (informational, not a warning), stacked above the amber banners, carrying the count of messages it has held back. Gone the moment DND is switched off — the messages themselves wait in their unread rings.
+            if self.dnd.is_on() {
+                let band_h = ready_layout.unit_height * 1.5;
+                let rows_below = self.vault_degraded as u8 as f32 + self.clock_off.is_some() as u8 as f32;
+                let cy = buf_h as f32 - band_h * (0.5 + rows_below);
+                let label = match self.dnd.held() {
+                    0 => "do not disturb".to_string(),
+                    n => format!("do not disturb \u{00b7} {n} held"),
+                };
+                ctx.text.draw_text_center(&mut canvas, &label, buf_w as f32 * 0.5, cy, &TextStyle::new(band_h * 0.6, *theme::LABEL_COLOUR).weight(600).font("Oxanium"), None, None);
+            }
+
             // Security & Recovery posture meters, bottom-right of the Ready strip (the dozenal version sits bottom-left). Two orthogonal axes — see `identity_posture`. Drawn into `target` at full opacity (unlike the watermark version) so they read as a real, glanceable status affordance, aligned to the version's baseline band. Read-only for now; the tap-to-device-sheet lands with the first modal primitive.
             {
                 let (sec, rec) = identity_posture();
@@ -4547,7 +5962,7 @@ impl FluorApp for PhotonApp {
         }
 
         // Conversation screen — shows the selected contact's name, clutch state, and (eventually) messages.
-        // Contact panel — the Settings screen's exact structure, contact-scoped: same SettingsLayout, pinned-Back nav rail with page rows (About / Between you / Manage), hairline divider, scrolled natural-height content. Rides the SAME scroll fields/extents as settings.
+        // Contact panel — the Settings screen's exact structure, contact-scoped: same SettingsLayout, pinned-Back nav rail with page rows (About / Verify / Between you / Manage), hairline divider, scrolled natural-height content. Rides the SAME scroll fields/extents as settings.
         if let AppState::ContactPanel(cpage) = self.state {
             let layout = SettingsLayout::compute(&ctx.viewport);
             let mut canvas = Canvas::new(target, buf_w, buf_h, ctx.damage);
@@ -4562,14 +5977,14 @@ impl FluorApp for PhotonApp {
                 // Avatar cache at the About-page diameter, rebuilt BEFORE the immutable contact borrow.
                 let avatar_r = layout.unit * 2.0;
                 let diam = (avatar_r * 2.0) as usize;
-                if cpage == ContactPage::About
-                    && self.contacts[ci].avatar_pixels.is_some()
-                    && (self.contacts[ci].avatar_scaled.is_none() || self.contacts[ci].avatar_scaled_diameter != diam)
-                {
-                    let base = self.contacts[ci].avatar_pixels.as_ref().unwrap();
-                    let scaled = crate::ui::avatar_render::update_avatar_scaled(base, crate::ui::avatar::AVATAR_SIZE, diam);
-                    self.contacts[ci].avatar_scaled = Some(scaled);
-                    self.contacts[ci].avatar_scaled_diameter = diam;
+                if cpage == ContactPage::Verify {
+                    self.ensure_verify_qr();
+                }
+                if cpage == ContactPage::About {
+                    let contact = &mut self.contacts[ci];
+                    if let Some(base) = contact.avatar_pixels.as_ref() {
+                        contact.avatar_scaled.get_or_scale(base, crate::ui::avatar::AVATAR_SIZE, diam);
+                    }
                 }
                 let contact = &self.contacts[ci];
                 let our_hh = self.session.as_ref().map(|s| crate::crypto::clutch::identity_party_id(&s.identity_seed)).unwrap_or([0u8; 32]);
@@ -4654,7 +6069,7 @@ impl FluorApp for PhotonApp {
                         let block = fluor::region::Region::new(rows[0].x, rows[0].y, rows[0].w, rows[0].h * 5.0);
                         let (cx, cy) = (block.center_x(), block.center_y());
                         let ring = ring_tier_colour(contact);
-                        if let Some(scaled) = contact.avatar_scaled.as_ref() {
+                        if let Some(scaled) = contact.avatar_scaled.get(diam) {
                             crate::ui::avatar_render::draw_avatar(&mut canvas, cx, cy, avatar_r, scaled, diam, Some(content_clip));
                         } else {
                             let gd = diam.max(1);
@@ -4695,6 +6110,43 @@ impl FluorApp for PhotonApp {
                         settings_line(&mut canvas, ctx.text, rows[9], "Identity", tspan, *theme::CONTACT_NAME_COLOUR, 600);
                         settings_line(&mut canvas, ctx.text, rows[10], &identity_line, hspan2, *theme::LABEL_COLOUR, 400);
                     }
+                    ContactPage::Verify => {
+                        let n = contact_page_rows(ContactPage::Verify);
+                        let rows = layout.content_scrolled(n, settings_content_scroll).split_v([1.0; 18]);
+                        settings_line(&mut canvas, ctx.text, rows[0], "Safety number", tspan, *theme::CONTACT_NAME_COLOUR, 600);
+                        if is_self || contact.is_sibling {
+                            settings_line(&mut canvas, ctx.text, rows[1], if is_self { "this is you \u{2014} nothing to compare" } else { "a fleet device shares your identity \u{2014} nothing to compare" }, hspan2, *theme::LABEL_COLOUR, 400);
+                        } else {
+                            // Both sides derive this from the same two (party id, pinned device key) pairs, sorted — so their screen shows these exact digits iff each of you pinned the other's real device key.
+                            let our_device = self.device_keypair.as_ref().map(|kp| *kp.public.as_bytes()).unwrap_or([0u8; 32]);
+                            let number = crate::crypto::safety::safety_number((&our_hh, &our_device), (&contact.handle_hash, contact.public_identity.as_bytes()));
+                            for (i, line) in crate::crypto::safety::safety_number_lines(&number).iter().enumerate() {
+                                settings_line(&mut canvas, ctx.text, rows[1 + i], line, tspan, *theme::LABEL_COLOUR, 500);
+                            }
+                            settings_line(&mut canvas, ctx.text, rows[4], "compare in person or over a channel you already trust \u{2014} every digit must match", hspan2, *theme::LABEL_COLOUR, 400);
+                            let pill = fluor::region::Region::new(rows[5].x + rows[5].w * 0.1, rows[5].y, rows[5].w * 0.5, rows[5].h * 0.95);
+                            let label = if contact.verified { "Verified \u{2713} \u{2014} tap to unmark" } else { "They match \u{2014} mark verified" };
+                            draw_stub_pill(&mut canvas, ctx.text, &mut chrome.hit_test_map, buf_w, buf_h, pill, label, self.contact_panel_btn_base.wrapping_add(1), ctx.pressed_hit);
+                            let status = if contact.verified {
+                                "you confirmed these match on both screens"
+                            } else {
+                                "not verified \u{2014} their messages render lighter until you are sure"
+                            };
+                            settings_line(&mut canvas, ctx.text, rows[6], status, hspan2, *theme::LABEL_COLOUR, 400);
+                            // Or skip the reading-aloud: their phone scans our card and verifies on the spot (desktop shows, mobile scans).
+                            settings_line(&mut canvas, ctx.text, rows[8], "Scan instead", tspan, *theme::CONTACT_NAME_COLOUR, 600);
+                            let scan_hint = if crate::network::qr_scan::can_scan() { "let them scan your code below \u{2014} or scan theirs" } else { "let their phone scan your code below" };
+                            settings_line(&mut canvas, ctx.text, rows[9], scan_hint, hspan2, *theme::LABEL_COLOUR, 400);
+                            if crate::network::qr_scan::can_scan() {
+                                let pill = fluor::region::Region::new(rows[10].x + rows[10].w * 0.1, rows[10].y, rows[10].w * 0.5, rows[10].h * 0.95);
+                                draw_stub_pill(&mut canvas, ctx.text, &mut chrome.hit_test_map, buf_w, buf_h, pill, "Scan their code", self.contact_panel_btn_base.wrapping_add(2), ctx.pressed_hit);
+                            }
+                            if let Some((_, m)) = self.verify_qr.as_ref() {
+                                let block = fluor::region::Region::new(rows[11].x, rows[11].y, rows[11].w, rows[11].h * 7.0);
+                                crate::ui::qr::draw_qr(&mut canvas, block.center_x(), block.center_y(), block.h.min(block.w), m, Some(content_clip));
+                            }
+                        }
+                    }
                     ContactPage::Stats => {
                         let n = contact_page_rows(ContactPage::Stats);
                         let rows = layout.content_scrolled(n, settings_content_scroll).split_v([1.0; 9]);
@@ -4726,7 +6178,8 @@ impl FluorApp for PhotonApp {
                         let connection_line = if is_self {
                             "always reachable (this is you)".to_string()
                         } else if contact.is_online {
-                            if contact.reached_via_relay { "connected \u{00b7} via relay".to_string() } else { "connected \u{00b7} direct".to_string() }
+                            let away = if contact.away { " \u{00b7} away" } else { "" };
+                            if contact.reached_via_relay { format!("connected \u{00b7} via relay{away}") } else { format!("connected \u{00b7} direct{away}") }
                         } else {
                             "offline".to_string()
                         };
@@ -4742,7 +6195,7 @@ impl FluorApp for PhotonApp {
                     }
                     ContactPage::Manage => {
                         let n = contact_page_rows(ContactPage::Manage);
-                        let rows = layout.content_scrolled(n, settings_content_scroll).split_v([1.0; 6]);
+                        let rows = layout.content_scrolled(n, settings_content_scroll).split_v([1.0; 18]);
                         settings_line(&mut canvas, ctx.text, rows[0], "Manage", tspan, *theme::CONTACT_NAME_COLOUR, 600);
                         if is_self || contact.is_sibling {
                             settings_line(&mut canvas, ctx.text, rows[1], if is_self { "your own notes can\u{2019}t be booted" } else { "a fleet device signs itself out \u{2014} see Settings \u{2192} Fleet" }, hspan2, *theme::LABEL_COLOUR, 400);
@@ -4753,6 +6206,40 @@ impl FluorApp for PhotonApp {
                             settings_line(&mut canvas, ctx.text, rows[3], "removes them from every device of YOUR fleet", hspan2, *theme::LABEL_COLOUR, 400);
                             settings_line(&mut canvas, ctx.text, rows[4], "they are not told \u{2014} their records stay theirs (ostracism, not erasure)", hspan2, *theme::LABEL_COLOUR, 400);
                         }
+                        // History retention (this conversation's override of the settings default) — a friend or notes-to-self both keep a history; a sibling has none.
+                        if !contact.is_sibling {
+                            settings_line(&mut canvas, ctx.text, rows[6], "History", tspan, *theme::CONTACT_NAME_COLOUR, 600);
+                            let pill = fluor::region::Region::new(rows[7].x + rows[7].w * 0.1, rows[7].y, rows[7].w * 0.5, rows[7].h * 0.95);
+                            let label = format!("Keep: {}", retention_label(contact.retention));
+                            draw_stub_pill(&mut canvas, ctx.text, &mut chrome.hit_test_map, buf_w, buf_h, pill, &label, self.contact_panel_btn_base.wrapping_add(3), ctx.pressed_hit);
+                            settings_line(&mut canvas, ctx.text, rows[8], "older messages are deleted from this device \u{2014} unsent ones never are", hspan2, *theme::LABEL_COLOUR, 400);
+                            let halves = rows[10].split_h([1.0, 1.0]);
+                            let clear_label = if self.contact_wipe_armed == Some(false) { "Tap again \u{2014} clear" } else { "Clear history" };
+                            draw_stub_pill(&mut canvas, ctx.text, &mut chrome.hit_test_map, buf_w, buf_h, halves[0].center_h(0.9), clear_label, self.contact_panel_btn_base.wrapping_add(4), ctx.pressed_hit);
+                            if !is_self {
+                                let reset_label = if self.contact_wipe_armed == Some(true) { "Tap again \u{2014} reset" } else { "Clear + reset encryption" };
+                                draw_stub_pill(&mut canvas, ctx.text, &mut chrome.hit_test_map, buf_w, buf_h, halves[1].center_h(0.9), reset_label, self.contact_panel_btn_base.wrapping_add(5), ctx.pressed_hit);
+                            }
+                            settings_line(&mut canvas, ctx.text, rows[11], "wipes this device\u{2019}s copy only \u{2014} their copy stays theirs", hspan2, *theme::LABEL_COLOUR, 400);
+                        }
+                        // Per-contact mute — a friend only (notes-to-self and siblings never alert).
+                        if !is_self && !contact.is_sibling {
+                            let pill = fluor::region::Region::new(rows[12].x + rows[12].w * 0.1, rows[12].y, rows[12].w * 0.5, rows[12].h * 0.95);
+                            let label = if contact.muted { "Muted \u{2014} tap to unmute" } else { "Mute this conversation" };
+                            draw_stub_pill(&mut canvas, ctx.text, &mut chrome.hit_test_map, buf_w, buf_h, pill, label, self.contact_panel_btn_base.wrapping_add(6), ctx.pressed_hit);
+                            settings_line(&mut canvas, ctx.text, rows[13], "their messages still arrive \u{2014} no notification, chime or unread ring", hspan2, *theme::LABEL_COLOUR, 400);
+                        }
+                        // Pin to the top of the contacts list — any listed conversation, notes-to-self included.
+                        if !contact.is_sibling {
+                            let pill = fluor::region::Region::new(rows[14].x + rows[14].w * 0.1, rows[14].y, rows[14].w * 0.5, rows[14].h * 0.95);
+                            let label = if contact.pinned { "Pinned \u{2014} tap to unpin" } else { "Pin to top" };
+                            draw_stub_pill(&mut canvas, ctx.text, &mut chrome.hit_test_map, buf_w, buf_h, pill, label, self.contact_panel_btn_base.wrapping_add(7), ctx.pressed_hit);
+                            settings_line(&mut canvas, ctx.text, rows[15], "pinned conversations stay above the rest on every device you sign in on", hspan2, *theme::LABEL_COLOUR, 400);
+                            let pill = fluor::region::Region::new(rows[16].x + rows[16].w * 0.1, rows[16].y, rows[16].w * 0.5, rows[16].h * 0.95);
+                            let label = if contact.archived { "Archived \u{2014} tap to restore" } else { "Archive" };
+                            draw_stub_pill(&mut canvas, ctx.text, &mut chrome.hit_test_map, buf_w, buf_h, pill, label, self.contact_panel_btn_base.wrapping_add(8), ctx.pressed_hit);
+                            settings_line(&mut canvas, ctx.text, rows[17], "hidden from the contacts list until they message you again", hspan2, *theme::LABEL_COLOUR, 400);
+                        }
                     }
                 }
             }
@@ -4763,23 +6250,14 @@ impl FluorApp for PhotonApp {
             if let Some(ci) = self.active_contact {
                 if ci < self.contacts.len() {
                     let ru = ctx.viewport.ru;
-                    // Build/refresh the contact's scaled-avatar cache at the CONVERSATION-HEADER diameter BEFORE the immutable borrow below. The header renders the avatar bigger than the contact-list rows, but it has no rebuild of its own — it used to draw whatever `avatar_scaled` happened to hold (built at the small row diameter) while telling draw_avatar the buffer was header-sized → it sampled past the smaller buffer → "index out of bounds: len 2028 (26²·3) but index 2307" panic on conversation-open. Rebuilding here at the header diameter keeps the cache and the claimed scaled_diameter in lockstep.
+                    // Build/refresh the contact's scaled-avatar cache at the CONVERSATION-HEADER diameter BEFORE the immutable borrow below. The header renders the avatar bigger than the contact-list rows; it used to draw whatever the single-slot cache happened to hold (built at the small row diameter) while telling draw_avatar the buffer was header-sized → it sampled past the smaller buffer → "index out of bounds: len 2028 (26²·3) but index 2307" panic on conversation-open. The cache is keyed by diameter now, so the draw below asks for exactly the size it paints.
                     {
                         let (_, _, header_r) =
                             ReadyLayout::compute(buf_w, buf_h, ru).avatar_center_radius();
                         let header_diam = (header_r * 2.0) as usize;
-                        if self.contacts[ci].avatar_pixels.is_some()
-                            && (self.contacts[ci].avatar_scaled.is_none()
-                                || self.contacts[ci].avatar_scaled_diameter != header_diam)
-                        {
-                            let base = self.contacts[ci].avatar_pixels.as_ref().unwrap();
-                            let scaled = crate::ui::avatar_render::update_avatar_scaled(
-                                base,
-                                crate::ui::avatar::AVATAR_SIZE,
-                                header_diam,
-                            );
-                            self.contacts[ci].avatar_scaled = Some(scaled);
-                            self.contacts[ci].avatar_scaled_diameter = header_diam;
+                        let contact = &mut self.contacts[ci];
+                        if let Some(base) = contact.avatar_pixels.as_ref() {
+                            contact.avatar_scaled.get_or_scale(base, crate::ui::avatar::AVATAR_SIZE, header_diam);
                         }
                     }
                     let contact = &self.contacts[ci];
@@ -4839,7 +6317,7 @@ impl FluorApp for PhotonApp {
                     let avatar_diam = (avatar_r * 2.0) as usize;
                     let avatar_cx = buf_w as f32 * 0.5;
                     let avatar_y = back_y + unit * 1.5 + avatar_r;
-                    if let Some(scaled) = contact.avatar_scaled.as_ref() {
+                    if let Some(scaled) = contact.avatar_scaled.get(avatar_diam) {
                         crate::ui::avatar_render::draw_avatar(
                             &mut canvas,
                             avatar_cx,
@@ -4953,16 +6431,75 @@ impl FluorApp for PhotonApp {
                             .iter()
                             .filter(|m| m.content != crate::types::CHAIN_PROBE_MARKER)
                             .collect();
-                        let n = visible.len();
-                        let content_h = n as f32 * line_h;
+                        // Image rows are a thumbnail tall: every row's height is known up front (a cached JPEG header read, no decode), so `content_h`, the clamp and the window below see exactly the heights the draw uses.
+                        if self.chat_thumbs_of != contact.handle_hash {
+                            self.chat_thumbs.clear();
+                            self.chat_thumbs_of = contact.handle_hash;
+                            self.chat_image_expanded = None;
+                        }
+                        let thumb_edge = unit * crate::ui::chat_image::THUMB_EDGE_UNITS;
+                        let heights: Vec<f32> = visible
+                            .iter()
+                            .map(|m| {
+                                let dims = m.image.as_deref().and_then(|jpeg| {
+                                    self.chat_thumbs
+                                        .entry(m.timestamp)
+                                        .or_insert_with(|| crate::ui::chat_image::ChatThumb::probe(jpeg))
+                                        .dims
+                                });
+                                crate::ui::chat_image::row_height(dims, line_h, thumb_edge)
+                            })
+                            .collect();
+                        let lifts = message_select::row_lifts(&heights);
+                        let content_h = lifts.first().zip(heights.first()).map_or(0.0, |(l, h)| l + h);
                         let view_h = (list_bottom - list_top).max(0.0);
                         let max_scroll = (content_h - view_h).max(0.0);
+                        // The find bar scrolls to hits anywhere in the history, so it needs every row's position, not just the drawn window's.
+                        self.msg_row_lifts.clear();
+                        if self.msg_find.is_some() {
+                            self.msg_row_lifts.extend(lifts.iter().copied().zip(heights.iter().copied()));
+                        }
                         let scroll = contact.message_scroll_offset.clamp(0.0, max_scroll);
-                        let mut y = list_bottom - msg_size + scroll;
-                        for msg in visible.iter().rev() {
-                            if y < list_top - line_h {
-                                break; // scrolled above the visible region
+                        self.msg_scroll_extent = max_scroll;
+                        self.msg_newest_h = heights.last().copied().unwrap_or(0.0);
+                        // Virtualized: only the rows intersecting the list band (plus overscan) are measured and drawn. Off-screen rows are positioned by `lifts` alone, so `content_h` / `max_scroll` above and the row indices below stay exact for the thousands of rows that are skipped.
+                        let newest_bottom = list_bottom - msg_size + line_h * 0.5 + scroll;
+                        let window = message_select::visible_range(&heights, &lifts, (list_top, list_bottom), newest_bottom, message_select::ROW_OVERSCAN);
+                        // Publish the row geometry drag-select resolves against. Caret edges cost one measure per char, so they're only taken while a selection is live on THIS conversation.
+                        self.msg_list_band = Some((list_top, list_bottom));
+                        self.msg_rows.clear();
+                        let selecting = self.msg_select_anchor.is_some()
+                            || self.msg_selection.is_some_and(|(sel_ci, _)| sel_ci == ci)
+                            || self.msg_find.as_ref().is_some_and(|f| !f.hits().is_empty());
+                        let unverified_peer = !is_self_contact && !contact.is_sibling && !contact.verified;
+                        // A tapped-open image owns the band in place of the history (the compose is front-to-back, so the rows simply aren't drawn under it). No rows are published, so the next press only closes it.
+                        let expanded = self
+                            .chat_image_expanded
+                            .and_then(|ts| visible.iter().find(|m| m.timestamp == ts))
+                            .and_then(|m| Some((m.timestamp, m.image.as_deref()?)));
+                        if let Some((ts, jpeg)) = expanded {
+                            let wake = self.thumb_waker();
+                            let thumb = self.chat_thumbs.entry(ts).or_insert_with(|| crate::ui::chat_image::ChatThumb::probe(jpeg));
+                            if let Some(dims) = thumb.dims {
+                                let (w, h) = crate::ui::chat_image::fit(dims, (buf_w as f32 - pad_x * 2.0).min(view_h));
+                                let (w, h) = (w as usize, h as usize);
+                                if let Some(px) = thumb.pixels(jpeg, w, h, wake) {
+                                    let x = (buf_w as f32 - w as f32) * 0.5;
+                                    let y = list_top + (view_h - h as f32) * 0.5;
+                                    crate::ui::chat_image::draw_image(&mut canvas, x, y, px, w, h, Some(list_clip));
+                                }
                             }
+                        }
+                        let window = if expanded.is_some() { 0..0 } else { window };
+                        // Every row as this reader sees it: sent mentions (party ids) become our names for them, with the spans a tap resolves. Rows without one are borrowed as stored.
+                        self.msg_ref_rects.clear();
+                        let shown: Vec<(std::borrow::Cow<str>, Vec<(usize, usize, usize)>)> =
+                            visible.iter().map(|m| mention::shown(&m.content, |p| Self::mention_name(&self.contacts, p))).collect();
+                        for (vi, msg) in visible.iter().enumerate().take(window.end).skip(window.start).rev() {
+                            let bottom = newest_bottom - lifts[vi];
+                            let top = bottom - heights[vi];
+                            // Text sits on the row's last line; an image row's thumbnail fills the space above it.
+                            let y = bottom - line_h * 0.5;
                             // Divider under this message (between it and the next-newer one).
                             paint::fill_rect(
                                 &mut canvas,
@@ -4975,27 +6512,233 @@ impl FluorApp for PhotonApp {
                                 None,
                             );
                             // Dim outgoing until delivered; incoming always full. Self-as-contact: every message is ours (there is no other party), so everything sits on the right in the neutral grey — their_colour is already the anchor in that case, and the loopback "incoming" copy renders like a delivered outgoing.
+                            // A given-up send (retransmits exhausted) reads in the error colour with its retry hint beside it; tapping the row re-queues it (see retry_failed_message).
                             let colour = if msg.is_outgoing {
                                 if msg.delivered {
                                     our_colour
+                                } else if msg.failed {
+                                    *theme::ERROR_TEXT_COLOUR
                                 } else {
                                     dim_colour(our_colour)
                                 }
                             } else {
                                 their_colour
                             };
-                            if msg.is_outgoing || is_self_contact {
-                                ctx.text.draw_text_right(&mut canvas, &msg.content, buf_w as f32 - pad_x, y, &TextStyle::new(msg_size, colour).weight(500), Some(list_clip), None);
+                            let right_aligned = msg.is_outgoing || is_self_contact;
+                            // Unverified friend: their words render a weight lighter, a quiet standing reminder that the safety number hasn't been compared (see the contact panel's Verify page).
+                            let weight = if !right_aligned && unverified_peer { 400 } else { 500 };
+                            // Inline *bold* / _italic_ / `code`: drawn run by run with the markers hidden (they stay in the stored text). Plain text keeps the single-call draw below; only formatted rows pay for run measurement — per char only while a selection needs the stops.
+                            let (text, refs) = (shown[vi].0.as_ref(), &shown[vi].1);
+                            let content_chars: Vec<char> = text.chars().collect();
+                            let runs = markup::runs(&content_chars);
+                            let marked_edges = (!markup::is_plain(&runs))
+                                .then(|| markup_edges(ctx.text, &content_chars, &runs, msg_size, colour, weight, selecting || !refs.is_empty()));
+                            let marked_w = marked_edges.as_ref().and_then(|e| e.last().copied());
+                            let thumb_size = msg
+                                .image
+                                .as_ref()
+                                .and_then(|_| self.chat_thumbs.get(&msg.timestamp)?.dims)
+                                .map(|d| crate::ui::chat_image::fit(d, thumb_edge));
+                            // A voice row's drawn width: triangle, gap, waveform, gap, length label — plus, where memos can't play, the note saying so.
+                            let voice_w = msg.voice.as_ref().filter(|_| thumb_size.is_none()).map(|memo| {
+                                let label = ctx.text.measure_text(&memo.duration_label(), &TextStyle::new(msg_size * 0.7, colour).weight(weight));
+                                let note = if crate::platform::voice::can_play() { 0.0 } else { msg_size * 0.5 + ctx.text.measure_text(VOICE_UNSUPPORTED_NOTE, &TextStyle::new(msg_size * 0.6, colour)) };
+                                msg_size * (0.7 + 0.5 + VOICE_WAVE_UNITS + 0.5) + label + note
+                            });
+                            if let Some((tw, th)) = thumb_size {
+                                let x = if right_aligned { buf_w as f32 - pad_x - tw } else { pad_x };
+                                let (w, h) = (tw as usize, th as usize);
+                                let jpeg = msg.image.as_deref().unwrap_or_default();
+                                let wake = self.thumb_waker();
+                                if let Some(px) = self.chat_thumbs.get_mut(&msg.timestamp).and_then(|t| t.pixels(jpeg, w, h, wake)) {
+                                    crate::ui::chat_image::draw_image(&mut canvas, x, top + line_h * 0.25, px, w, h, Some(list_clip));
+                                }
+                                if msg.is_outgoing && msg.failed {
+                                    ctx.text.draw_text_right(&mut canvas, "not delivered — tap to retry", x - msg_size, y, &TextStyle::new(msg_size * 0.6, dim_colour(colour)), Some(list_clip), None);
+                                }
+                            } else if let (Some(memo), Some(vw)) = (msg.voice.as_ref(), voice_w) {
+                                // Voice message: play triangle, waveform bars, length — drawn instead of the caption.
+                                let left = if right_aligned { buf_w as f32 - pad_x - vw } else { pad_x };
+                                let tri = msg_size * 0.7;
+                                let playable = crate::platform::voice::can_play();
+                                // The triangle as a stack of one-pixel columns narrowing to its point — dimmed where there's nothing to play it with.
+                                let tri_colour = if playable { colour } else { dim_colour(colour) };
+                                for col in 0..tri.max(1.0) as usize {
+                                    let half = tri * 0.5 * (1.0 - col as f32 / tri);
+                                    paint::fill_rect(&mut canvas, (left + col as f32) as isize, (y - half) as isize, 1, (half * 2.0).max(1.0) as isize, tri_colour, Some(list_clip), None);
+                                }
+                                let wave_left = left + tri + msg_size * 0.5;
+                                let pitch = msg_size * VOICE_WAVE_UNITS / crate::types::WAVEFORM_BARS as f32;
+                                for (i, &level) in memo.waveform.iter().enumerate() {
+                                    // A silent bar still shows as a hairline so the strip reads as one waveform.
+                                    let h = (msg_size * level as f32 / 255.0).max(ru);
+                                    paint::fill_rect(
+                                        &mut canvas,
+                                        (wave_left + pitch * i as f32) as isize,
+                                        (y - h * 0.5) as isize,
+                                        (pitch * 0.6).max(1.0) as isize,
+                                        h.max(1.0) as isize,
+                                        colour,
+                                        Some(list_clip),
+                                        None,
+                                    );
+                                }
+                                let label_style = TextStyle::new(msg_size * 0.7, colour).weight(weight);
+                                let label_left = wave_left + msg_size * VOICE_WAVE_UNITS + msg_size * 0.5;
+                                ctx.text.draw_text_left(&mut canvas, &memo.duration_label(), label_left, y, &label_style, Some(list_clip), None);
+                                if !playable {
+                                    let note_left = label_left + ctx.text.measure_text(&memo.duration_label(), &label_style) + msg_size * 0.5;
+                                    ctx.text.draw_text_left(&mut canvas, VOICE_UNSUPPORTED_NOTE, note_left, y, &TextStyle::new(msg_size * 0.6, dim_colour(colour)), Some(list_clip), None);
+                                }
+                                if msg.is_outgoing && msg.failed {
+                                    ctx.text.draw_text_right(&mut canvas, "not delivered — tap to retry", left - msg_size, y, &TextStyle::new(msg_size * 0.6, dim_colour(colour)), Some(list_clip), None);
+                                }
+                            } else if let (Some(edges), Some(w)) = (marked_edges.as_ref(), marked_w) {
+                                let left = if right_aligned { buf_w as f32 - pad_x - w } else { pad_x };
+                                draw_markup(&mut canvas, ctx.text, &content_chars, &runs, edges, left, y, msg_size, colour, weight, Some(list_clip));
+                                if msg.is_outgoing && msg.failed {
+                                    ctx.text.draw_text_right(&mut canvas, "not delivered — tap to retry", left - msg_size, y, &TextStyle::new(msg_size * 0.6, dim_colour(colour)), Some(list_clip), None);
+                                }
+                            } else if right_aligned {
+                                let style = TextStyle::new(msg_size, colour).weight(weight);
+                                ctx.text.draw_text_right(&mut canvas, text, buf_w as f32 - pad_x, y, &style, Some(list_clip), None);
+                                if msg.is_outgoing && msg.failed {
+                                    let hint_right = buf_w as f32 - pad_x - ctx.text.measure_text(text, &style) - msg_size;
+                                    ctx.text.draw_text_right(&mut canvas, "not delivered — tap to retry", hint_right, y, &TextStyle::new(msg_size * 0.6, dim_colour(colour)), Some(list_clip), None);
+                                }
                             } else {
-                                ctx.text.draw_text_left(&mut canvas, &msg.content, pad_x, y, &TextStyle::new(msg_size, colour).weight(500), Some(list_clip), None);
+                                ctx.text.draw_text_left(&mut canvas, text, pad_x, y, &TextStyle::new(msg_size, colour).weight(weight), Some(list_clip), None);
+                            }
+                            // Contact mentions in a text row: a hairline under each, and its rect published — a tap opens that conversation. Rows under the band edges only publish the part still on screen.
+                            if thumb_size.is_none() && voice_w.is_none() && !refs.is_empty() {
+                                let plain_style = TextStyle::new(msg_size, colour).weight(weight);
+                                let w = marked_w.unwrap_or_else(|| ctx.text.measure_text(text, &plain_style));
+                                let left = if right_aligned { buf_w as f32 - pad_x - w } else { pad_x };
+                                let mut stop = |k: usize| ctx.text.measure_text(&content_chars[..k].iter().collect::<String>(), &plain_style);
+                                for &(from, to, rci) in refs {
+                                    let (x0, x1) = match marked_edges.as_ref() {
+                                        Some(e) => (left + e[from], left + e[to]),
+                                        None => (left + stop(from), left + stop(to)),
+                                    };
+                                    paint::fill_rect(&mut canvas, x0 as isize, (y + msg_size * 0.45) as isize, (x1 - x0) as isize, ru.max(1.0) as isize, colour, Some(list_clip), None);
+                                    let (y0, y1) = ((y - line_h * 0.5).max(list_top), (y + line_h * 0.5).min(list_bottom));
+                                    if y1 > y0 {
+                                        self.msg_ref_rects.push((rci, (x0, y0, x1, y1)));
+                                    }
+                                }
+                            }
+                            // File message: a chip under the caption, and its size + save hint on the open side (a failed send's retry hint has that spot instead). Painted after the text, so the under-blend lands the chip beneath the glyphs.
+                            if let (Some(file), None) = (msg.attachment.as_ref(), thumb_size) {
+                                let w = marked_w.unwrap_or_else(|| ctx.text.measure_text(text, &TextStyle::new(msg_size, colour).weight(weight)));
+                                let left = if right_aligned { buf_w as f32 - pad_x - w } else { pad_x };
+                                if !(msg.is_outgoing && msg.failed) {
+                                    let hint = format!("{} \u{00B7} tap to save", file.size_label());
+                                    let hint_style = TextStyle::new(msg_size * 0.6, dim_colour(colour));
+                                    if right_aligned {
+                                        ctx.text.draw_text_right(&mut canvas, &hint, left - msg_size, y, &hint_style, Some(list_clip), None);
+                                    } else {
+                                        ctx.text.draw_text_left(&mut canvas, &hint, left + w + msg_size, y, &hint_style, Some(list_clip), None);
+                                    }
+                                }
+                                let pad = msg_size * 0.3;
+                                paint::fill_rect(
+                                    &mut canvas,
+                                    (left - pad) as isize,
+                                    (y - msg_size * 0.7) as isize,
+                                    (w + pad * 2.0) as isize,
+                                    (msg_size * 1.4) as isize,
+                                    *theme::ATTACHMENT_CHIP_COLOUR,
+                                    Some(list_clip),
+                                    None,
+                                );
+                            }
+                            let mut edges = Vec::new();
+                            if let (true, Some(tw)) = (selecting, thumb_size.map(|(tw, _)| tw).or(voice_w)) {
+                                // The caption's caret stops spread across the thumbnail (or the voice strip), so a drag over the picture selects (and copies) its caption.
+                                let left = if right_aligned { buf_w as f32 - pad_x - tw } else { pad_x };
+                                let chars = content_chars.len().max(1);
+                                edges.extend((0..=chars).map(|k| left + tw * k as f32 / chars as f32));
+                            } else if let (true, Some(marked), Some(w)) = (selecting, marked_edges.as_ref(), marked_w) {
+                                let left = if right_aligned { buf_w as f32 - pad_x - w } else { pad_x };
+                                edges.extend(marked.iter().map(|e| left + e));
+                            } else if selecting {
+                                let style = TextStyle::new(msg_size, colour).weight(weight);
+                                let left = if right_aligned {
+                                    buf_w as f32 - pad_x - ctx.text.measure_text(text, &style)
+                                } else {
+                                    pad_x
+                                };
+                                edges.push(left);
+                                let mut prefix = String::new();
+                                for &ch in &content_chars {
+                                    prefix.push(ch);
+                                    edges.push(left + ctx.text.measure_text(&prefix, &style));
+                                }
+                            }
+                            // Rows are laid out newest-first; insert at the front so the published list stays oldest-first (top to bottom) like the visible indices.
+                            self.msg_rows.insert(
+                                0,
+                                message_select::RowGeom { msg: vi, top, bottom, edges },
+                            );
+                        }
+                        // Live drag: re-resolve the span against THIS frame's rows, then paint each selected row's band. Painted after the text, so the under-blend lands it beneath the glyphs.
+                        if let Some(anchor) = self.msg_select_anchor {
+                            let texts: Vec<&str> = shown.iter().map(|(t, _)| t.as_ref()).collect();
+                            self.msg_selection = message_select::unit_range(&self.msg_rows, &texts, anchor, self.msg_select_head, self.msg_select_unit)
+                                .map(|r| (ci, r));
+                        }
+                        // Find-bar hits in the drawn window, on the selection's band: the current one brighter, painted first so it wins wherever another band overlaps it.
+                        if let Some(find) = self.msg_find.as_ref() {
+                            if let Some(hit) = find.current() {
+                                paint_span_band(&mut canvas, &self.msg_rows, hit, *theme::FIND_CURRENT_COLOUR, Some(list_clip));
                             }
-                            y -= line_h;
+                            for &hit in find.hits().iter().filter(|((m, _), _)| window.contains(m)) {
+                                paint_span_band(&mut canvas, &self.msg_rows, hit, *theme::SELECTION_COLOUR, Some(list_clip));
+                            }
+                        }
+                        if let Some((_, range)) = self.msg_selection.filter(|(sel_ci, _)| *sel_ci == ci) {
+                            paint_span_band(&mut canvas, &self.msg_rows, range, *theme::SELECTION_COLOUR, Some(list_clip));
+                        }
+
+                        // "New messages ↓": messages landed while the user reads further back (they no longer yank the view down). A chip centred at the foot of the list band; a press on it jumps to the newest.
+                        self.msg_jump_rect = None;
+                        if expanded.is_none() && message_select::show_jump_to_latest(scroll, self.msg_newest_h, contact.unread_count) {
+                            let label = format!("{} new message{} \u{2193}", contact.unread_count, if contact.unread_count == 1 { "" } else { "s" });
+                            let style = TextStyle::new(msg_size, *theme::CONTACT_NAME_COLOUR);
+                            let w = ctx.text.measure_text(&label, &style) + pad_x * 2.0;
+                            let (x0, y1) = ((buf_w as f32 - w) * 0.5, list_bottom - line_h * 0.25);
+                            let y0 = y1 - line_h;
+                            paint::fill_rect(
+                                &mut canvas,
+                                x0 as isize,
+                                y0 as isize,
+                                w as isize,
+                                line_h as isize,
+                                *theme::SELECTION_COLOUR,
+                                Some(list_clip),
+                                None,
+                            );
+                            ctx.text.draw_text_center(&mut canvas, &label, buf_w as f32 * 0.5, (y0 + y1) * 0.5, &style, None, None);
+                            self.msg_jump_rect = Some((x0, y0, x0 + w, y1));
                         }
-                        let _ = n;
 
                         // ── Compose box (pinned bottom) ────────────────────────────
                         // Hidden until the chain-weave probe seals BOTH directions (chain_woven: their probe seen + our ACK-advanced) — Complete alone only proves the ceremony, not the ratchet, and a message typed into an unproven chain can desync it. The status line above reads "testing · weaving the chain" for exactly this window. Self-contacts are exempt (loopback, no peer to weave with, probe deliberately skipped).
-                        if is_self_contact || contact.chain_woven {
+                        if let Some(find) = self.msg_find.as_ref() {
+                            // Find bar in the compose box's place: the query box, a hint while it's empty and unfocused, the hit count at its right end once something's typed.
+                            let find_cy = buf_h as f32 - compose_margin - compose_h * 0.5;
+                            let query_empty = self.find_textbox.as_ref().is_none_or(|t| t.chars.is_empty());
+                            let find_focused = self.find_textbox.as_ref().is_some_and(|t| Some(t.hit_id()) == self.focused);
+                            if query_empty && !find_focused {
+                                ctx.text.draw_text_left(&mut canvas, "find in conversation", pad_x * 1.2, find_cy, &TextStyle::new(msg_size, *theme::LABEL_COLOUR), None, None);
+                            } else if !query_empty {
+                                ctx.text.draw_text_right(&mut canvas, &find.label(), buf_w as f32 - pad_x * 1.2, find_cy, &TextStyle::new(msg_size * 0.8, *theme::LABEL_COLOUR), None, None);
+                            }
+                            if let Some(tb) = self.find_textbox.as_mut() {
+                                let id = tb.hit_id();
+                                tb.render_content_into(&mut canvas, 0., 0., ctx.text, None, None, Some(&mut chrome.hit_test_map), id);
+                            }
+                        } else if is_self_contact || contact.chain_woven {
                             let compose_empty = self
                                 .message_textbox
                                 .as_ref()
@@ -5007,7 +6750,11 @@ impl FluorApp for PhotonApp {
                                 .map(|t| Some(t.hit_id()) == self.focused)
                                 .unwrap_or(false);
                             let compose_cy = buf_h as f32 - compose_margin - compose_h * 0.5;
-                            if compose_empty && !compose_focused {
+                            if let Some((since, _)) = self.voice_recording {
+                                let secs = since.elapsed().as_secs();
+                                let hint = format!("recording {}:{:02} — release to send", secs / 60, secs % 60);
+                                ctx.text.draw_text_left(&mut canvas, &hint, pad_x * 1.2, compose_cy, &TextStyle::new(msg_size, *theme::ERROR_TEXT_COLOUR), None, None);
+                            } else if compose_empty && !compose_focused {
                                 ctx.text.draw_text_left(&mut canvas, "message", pad_x * 1.2, compose_cy, &TextStyle::new(msg_size, *theme::LABEL_COLOUR), None, None);
                             }
                             // Send button COLOUR first (its under() blit lands on the noise), then the arrowhead over the pill (source-over). The textbox draws after — it sits over the button and clobbers the button's hit stamp with its own id — so we re-stamp the button's TRUE pill silhouette (fill + stroke, which also covers the arrowhead) AFTER the textbox, as the last writer. That's the whole click + hover region: shape-accurate, not a bbox rectangle.
@@ -5022,13 +6769,39 @@ impl FluorApp for PhotonApp {
                                     Some(&mut chrome.hit_test_map),
                                     id,
                                 );
-                                draw_up_arrowhead(
-                                    &mut canvas,
-                                    btn.center_x,
-                                    btn.center_y,
-                                    btn.height * 0.5,
-                                    *theme::SEND_ARROW_COLOUR,
-                                );
+                                // Recording: a record dot in the arrow's place.
+                                if self.voice_recording.is_some() {
+                                    let r = btn.height * 0.2;
+                                    paint::draw_circle(&mut canvas, btn.center_x, btn.center_y, r, *theme::ERROR_TEXT_COLOUR, None);
+                                } else {
+                                    draw_up_arrowhead(
+                                        &mut canvas,
+                                        btn.center_x,
+                                        btn.center_y,
+                                        btn.height * 0.5,
+                                        *theme::SEND_ARROW_COLOUR,
+                                    );
+                                }
+                            }
+                            // Spell-check squiggles — painted BEFORE the textbox so the under-blend seats them over its pill. The box only answers x → caret stop, so each word's ends are found by bisecting that; a word scrolled out of the box collapses to nothing. The word holding the caret is still being typed and stays clean until the caret leaves it.
+                            if self.spell_check {
+                                if let Some(tb) = self.message_textbox.as_ref() {
+                                    let (left, right) = (unit, buf_w as f32 - unit);
+                                    let index_at = |x: f32| tb.cursor_index_from_x(x);
+                                    for (from, to) in spell::misspelled(&tb.chars) {
+                                        if (from..=to).contains(&tb.cursor) {
+                                            continue;
+                                        }
+                                        // Stops flip at glyph middles: pad the first..last middles out by half a glyph to reach the word's edges.
+                                        let a = spell::stop_x(index_at, from + 1, left, right);
+                                        let b = spell::stop_x(index_at, to, left, right);
+                                        let half = (b - a) / (to - from - 1) as f32 * 0.5;
+                                        let (x0, x1) = ((a - half).max(left), (b + half).min(right));
+                                        if x1 > x0 {
+                                            draw_squiggle(&mut canvas, x0, x1, compose_cy + tb.font_size * 0.6, tb.font_size / 8.0, *theme::ERROR_TEXT_COLOUR);
+                                        }
+                                    }
+                                }
                             }
                             if let Some(tb) = self.message_textbox.as_mut() {
                                 let id = tb.hit_id();
@@ -5048,6 +6821,60 @@ impl FluorApp for PhotonApp {
                                 btn.stamp_hit_into(&mut chrome.hit_test_map, buf_w, buf_h, btn.hit_id());
                             }
                         } // end chain-woven compose gate
+
+                        // Mention picker — a column of names rising off the compose box's top-left, best match nearest the caret's line. Rows are published for the press that picks one.
+                        self.mention_rects.clear();
+                        if self.mention.is_open() {
+                            let style = TextStyle::new(msg_size, *theme::CONTACT_NAME_COLOUR);
+                            let names: Vec<(usize, String)> = self
+                                .mention
+                                .matches
+                                .iter()
+                                .filter_map(|&mi| self.contacts.get(mi).map(|c| (mi, c.display_name())))
+                                .collect();
+                            let row_w = names
+                                .iter()
+                                .map(|(_, n)| ctx.text.measure_text(n, &style))
+                                .fold(0.0f32, f32::max)
+                                + pad_x * 2.0;
+                            let mut y1 = buf_h as f32 - compose_margin - compose_h;
+                            for (row, (mi, name)) in names.iter().enumerate() {
+                                let (x0, y0) = (unit, y1 - line_h);
+                                let fill = if row == self.mention.selected { *theme::SELECTION_COLOUR } else { *theme::ATTACHMENT_CHIP_COLOUR };
+                                paint::fill_rect(&mut canvas, x0 as isize, y0 as isize, row_w as isize, line_h as isize, fill, None, None);
+                                ctx.text.draw_text_left(&mut canvas, name, x0 + pad_x, (y0 + y1) * 0.5, &style, None, None);
+                                self.mention_rects.push((*mi, (x0, y0, x0 + row_w, y1)));
+                                y1 = y0;
+                            }
+                        }
+
+                        // Message menu — painted last so it sits over the history AND the compose box it may flip down onto. Its rects are published for the press that picks an item.
+                        self.msg_menu_rects.clear();
+                        if let Some(menu) = self.msg_menu.as_ref().filter(|m| m.contact == ci) {
+                            let item_h = line_h;
+                            let style = TextStyle::new(msg_size, *theme::CONTACT_NAME_COLOUR);
+                            let item_w = menu
+                                .actions
+                                .iter()
+                                .map(|a| ctx.text.measure_text(a.label(), &style))
+                                .fold(0.0f32, f32::max)
+                                + pad_x * 2.0;
+                            let rects = context_menu::item_rects(menu, item_w, item_h, buf_w as f32, buf_h as f32);
+                            for (&(x0, y0, x1, y1), action) in rects.iter().zip(&menu.actions) {
+                                paint::fill_rect(
+                                    &mut canvas,
+                                    x0 as isize,
+                                    y0 as isize,
+                                    (x1 - x0) as isize,
+                                    (y1 - y0) as isize,
+                                    *theme::ATTACHMENT_CHIP_COLOUR,
+                                    None,
+                                    None,
+                                );
+                                ctx.text.draw_text_left(&mut canvas, action.label(), x0 + pad_x, (y0 + y1) * 0.5, &style, None, None);
+                            }
+                            self.msg_menu_rects = rects;
+                        }
                     } // end CLUTCH-Complete gate (message list + compose box)
                 }
             }
@@ -5160,6 +6987,52 @@ impl FluorApp for PhotonApp {
             ctx.text.draw_text_center(&mut canvas, "tap the orb to cancel", cx, y + u * 0.22, &TextStyle::new(u * 0.4, *theme::STATUS_TEXT_COLOUR).font("Oxanium"), None, None);
         }
 
+        // ── Avatar crop screen: the new image fitted in a square with the framing circle over it (outside dimmed); −/+ zoom, Cancel, Use. ──
+        if let (AppState::AvatarCrop, Some(framing)) = (&self.state, self.avatar_framing.as_mut()) {
+            let mut canvas = Canvas::new(target, buf_w, buf_h, ctx.damage);
+            let cx = buf_w as f32 * 0.5;
+            let u = ReadyLayout::compute(buf_w, buf_h, ctx.viewport.ru).unit_height;
+            let title_y = buf_h as f32 * 0.06 + u;
+            ctx.text.draw_text_center(&mut canvas, "Frame your avatar", cx, title_y, &TextStyle::new(u * 0.85, *theme::STATUS_TEXT_COLOUR).weight(600).font("Oxanium"), None, None);
+            // The square box under the title, leaving room below for the hint and two pill rows.
+            let pill_h = u * 1.1;
+            let top = title_y + u;
+            let side = (buf_w as f32 * 0.86).min(buf_h as f32 - top - u * 0.9 - pill_h * 2.0 - u * 1.5).max(u * 4.0);
+            let view = avatar_crop::View::fit(framing.dims.0, framing.dims.1, (cx - side * 0.5, top), side);
+            framing.view = Some(view);
+            let (w, h) = framing.dims;
+            let (sw, sh) = (((w as f32 * view.scale).round() as usize).max(1), ((h as f32 * view.scale).round() as usize).max(1));
+            if !matches!(&framing.scaled, Some((cw, ch, _)) if *cw == sw && *ch == sh) {
+                let rgb = image::imageops::resize(&framing.preview, sw as u32, sh as u32, image::imageops::FilterType::Triangle);
+                framing.scaled = Some((sw, sh, rgb.into_raw()));
+            }
+            if let Some((_, _, rgb)) = framing.scaled.as_ref() {
+                let mut shown = rgb.clone();
+                let (ccx, ccy) = view.to_screen((framing.circle.cx, framing.circle.cy));
+                shade_outside_circle(&mut shown, sw, sh, (ccx - view.origin.0, ccy - view.origin.1), framing.circle.r * view.scale, ctx.viewport.ru);
+                crate::ui::chat_image::draw_image(&mut canvas, view.origin.0, view.origin.1, &shown, sw, sh, None);
+                restamp_hit_rect(
+                    &mut chrome.hit_test_map, buf_w, buf_h,
+                    view.origin.0 as isize, view.origin.1 as isize,
+                    (view.origin.0 + sw as f32) as isize, (view.origin.1 + sh as f32) as isize,
+                    self.avatar_crop_hit_base.wrapping_add(4),
+                );
+            }
+            let mut y = top + side + u * 0.5;
+            ctx.text.draw_text_center(&mut canvas, "drag to move \u{b7} scroll or \u{2212} / + to zoom", cx, y, &TextStyle::new(u * 0.4, fluor::theme::HINT_COLOUR).font("Oxanium"), None, None);
+            y += u * 0.5;
+            let gap = u * 0.4;
+            let pills: [[(&str, HitId); 2]; 2] = [[("\u{2212}", 0), ("+", 1)], [("Cancel", 2), ("Use", 3)]];
+            for (row, pw) in pills.iter().zip([u * 2.5, u * 5.0]) {
+                for (i, &(label, slot)) in row.iter().enumerate() {
+                    let x = cx + if i == 0 { -gap * 0.5 - pw } else { gap * 0.5 };
+                    let rect = fluor::region::Region::new(x, y, pw, pill_h);
+                    draw_stub_pill(&mut canvas, ctx.text, &mut chrome.hit_test_map, buf_w, buf_h, rect, label, self.avatar_crop_hit_base.wrapping_add(slot), ctx.pressed_hit);
+                }
+                y += pill_h + gap;
+            }
+        }
+
         // Settings panel (STUB) — nav rail + selected page body. Controls render but wire nothing (a checkbox may flip its own visual state; every button / dropdown / slider is inert).
         if let AppState::Settings(page) = self.state {
             let layout = SettingsLayout::compute(&ctx.viewport);
@@ -5346,6 +7219,9 @@ impl FluorApp for PhotonApp {
                                     .unwrap_or_else(|| "—".to_string());
                                 ctx.text.draw_text_left(&mut canvas, &fp, r.x + hspan2 * 0.3, r.center_y(), &TextStyle::new(hspan2, *theme::LABEL_COLOUR).font("Oxanium"), Some(content_clip), None);
                             }
+                            YouRow::LinkPill => {
+                                draw_stub_pill(&mut canvas, ctx.text, &mut chrome.hit_test_map, buf_w, buf_h, r.center_h(pillf(0.5)), "Copy contact link", btn_base.wrapping_add(3), ctx.pressed_hit);
+                            }
                             YouRow::SavePill => {
                                 draw_stub_pill(&mut canvas, ctx.text, &mut chrome.hit_test_map, buf_w, buf_h, r.center_h(pillf(0.5)), "Update", btn_base.wrapping_add(0), ctx.pressed_hit);
                             }
@@ -5353,6 +7229,10 @@ impl FluorApp for PhotonApp {
                             YouRow::AvatarPill => {
                                 draw_stub_pill(&mut canvas, ctx.text, &mut chrome.hit_test_map, buf_w, buf_h, r.center_h(pillf(0.5)), "Change avatar…", btn_base.wrapping_add(1), ctx.pressed_hit);
                             }
+                            YouRow::RemoveAvatarPill => {
+                                let label = if self.you_avatar_remove_armed { "Remove avatar — tap again to confirm" } else { "Remove avatar" };
+                                draw_stub_pill(&mut canvas, ctx.text, &mut chrome.hit_test_map, buf_w, buf_h, r.center_h(pillf(0.5)), label, btn_base.wrapping_add(4), ctx.pressed_hit);
+                            }
                         }
                     }
                 }
@@ -5464,6 +7344,9 @@ impl FluorApp for PhotonApp {
                         sl.render_content_into(&mut canvas, Some(&mut chrome.hit_test_map), sl.hit_id());
                     }
                     settings_line(&mut canvas, ctx.text, rows[6], "Colour calibration (Android panel)", hspan2, *theme::LABEL_COLOUR, 400);
+                    if let Some(cb) = self.settings_spell_check.as_mut() {
+                        cb.render_content_into(&mut canvas, ctx.text, None, Some(&mut chrome.hit_test_map));
+                    }
                 }
                 SettingsPage::Notifications => {
                     let rows = layout.content_scrolled(8, settings_content_scroll).split_v([1.0; 8]);
@@ -5478,6 +7361,9 @@ impl FluorApp for PhotonApp {
                     if let Some(cb) = self.settings_background_check.as_mut() {
                         cb.render_content_into(&mut canvas, ctx.text, None, Some(&mut chrome.hit_test_map));
                     }
+                    if let Some(cb) = self.settings_dnd_check.as_mut() {
+                        cb.render_content_into(&mut canvas, ctx.text, None, Some(&mut chrome.hit_test_map));
+                    }
                 }
                 SettingsPage::Updates => {
                     // Rows (blanks between the pills for vertical breathing room): 0 title · 1 current version · 2 blank · 3 release pill · 4 blank · 5 dev pill · 6 blank · 7 status.
@@ -5788,14 +7674,22 @@ impl PhotonApp {
     pub fn advance_protocol(&mut self, now: Instant) -> bool {
         let mut needs_redraw = false;
 
+        // Owed FGTW re-announce from `force_reconnect`: re-publish our address + re-fetch peers now rather than on the next attest echo. Taken even when there's no session yet — a pre-attest force has nothing to announce.
+        if self.sweeps.take_fgtw_refresh() {
+            if let (Some(hq), Some(session)) = (self.handle_query.as_ref(), self.session.clone()) {
+                hq.query_resume(session);
+            }
+        }
+
         // Recurring background presence sweep — re-ping every contact so online/offline rings stay live. The interval tapers with idle time (5s active → 1min idle → 15min deep-idle) so an untouched window isn't hammering the network. Runs on Ready AND in a Conversation — CRITICAL: presence is symmetric only if both sides keep pinging, and the person you most need a live status for is the one you're actively chatting with. Gating this to Ready meant opening a conversation stopped your pings, so your view of that contact went stale — and if both people opened the chat with each other, NEITHER pinged and both showed offline (observed: the peer on Ready saw the other online, while the one in the conversation saw the first offline). `wake_at()` schedules the next sweep so this fires even while otherwise idle.
         if matches!(self.state, AppState::Ready | AppState::Conversation) {
             let interval = self.presence_ping_interval(now);
             let due = self
-                .last_presence_ping
+                .sweeps
+                .presence_ping
                 .is_none_or(|last| now.duration_since(last) >= interval);
             if due {
-                self.last_presence_ping = Some(now);
+                self.sweeps.presence_ping = Some(now);
                 self.ping_contacts();
             }
         }
@@ -5804,16 +7698,32 @@ impl PhotonApp {
         const FLEET_REFOLD_INTERVAL: std::time::Duration = std::time::Duration::from_secs(45);
         if matches!(self.state, AppState::Ready | AppState::Conversation | AppState::Settings(_)) {
             let due = self
-                .last_fleet_refold
+                .sweeps
+                .fleet_refold
                 .is_none_or(|last| now.duration_since(last) >= FLEET_REFOLD_INTERVAL);
             if due {
                 if let Some(our_hp) = self.handle_query.as_ref().and_then(|hq| hq.get_handle_proof()) {
-                    self.last_fleet_refold = Some(now);
+                    self.sweeps.fleet_refold = Some(now);
                     self.spawn_contact_fleet_refresh(vec![our_hp]);
                 }
             }
         }
 
+        // Message-history retention: hourly, prune each conversation to its policy (its own, else the settings default). Hourly is plenty — a "30 days" bound overshooting by an hour is invisible, and the sweep reads every conversation.
+        const RETENTION_SWEEP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3_600);
+        if self.storage.is_some() && self.retention_rx.is_none() && self.sweeps.retention.is_none_or(|last| now.duration_since(last) >= RETENTION_SWEEP_INTERVAL) {
+            self.sweeps.retention = Some(now);
+            self.spawn_retention_sweep();
+        }
+        if self.drain_retention_sweep() > 0 {
+            needs_redraw = true;
+        }
+
+        // Batched contact upload: one push for however many changes landed before the list went quiet.
+        if self.cloud_sync.take_due(now) {
+            self.spawn_cloud_contacts_sync();
+        }
+
         // Stalled-address re-fetch — the deadlock breaker for flaky-fgtw address discovery.
         // A contact whose address fetch failed sits with `ip = None`: its CLUTCH offer can't
         // send (send needs an address), name/avatar never arrive (they ride the pong, which
@@ -5835,7 +7745,8 @@ impl PhotonApp {
                     && Some(c.handle_hash) != our_pid
             });
             let due = self
-                .last_stalled_refetch
+                .sweeps
+                .stalled_refetch
                 .is_none_or(|last| now.duration_since(last) >= STALLED_ADDR_REFETCH);
             // Harvest every tick while blocked: a record for a stalled contact may have landed in
             // the shared peer store — from our own fgtw fetch OR from a phonebook-gossip response.
@@ -5870,7 +7781,7 @@ impl PhotonApp {
             // Every 15s while blocked: pulse our own fgtw (may catch a working window) AND ask every
             // reachable peer for its phonebook — so a friend we CAN'T reach is learned from one we can.
             if blocked && due {
-                self.last_stalled_refetch = Some(now);
+                self.sweeps.stalled_refetch = Some(now);
                 if let (Some(hq), Some(session)) =
                     (self.handle_query.as_ref(), self.session.clone())
                 {
@@ -5928,6 +7839,12 @@ impl PhotonApp {
                 drained.push(result);
             }
             while let Some(online) = hq.try_recv_online() {
+                // Online edge = possibly a new network (wifi → cell, a new router): the NAT in front of us may have changed with it.
+                if online && !self.online {
+                    if let Some(checker) = self.status_checker.as_ref() {
+                        checker.probe_nat();
+                    }
+                }
                 self.online = online;
                 if let Some(chrome) = self.chrome.as_mut() {
                     chrome.set_orb_tint(orb_tint_for(online));
@@ -6060,11 +7977,65 @@ impl PhotonApp {
             needs_redraw = true;
         }
 
-        // Desktop resident-mode toggle: the OS autostart artifact IS the stored setting (platform::autostart — nothing in the vault to desync), and the live flag follows it immediately, so unchecking makes the very next close a real quit. A write failure reverts the box and says why.
-        #[cfg(not(target_os = "android"))]
-        {
-            let bg_toggle = self
-                .settings_background_check
+        // Mention picker follows the focused compose draft: a caret inside an `@…` span opens it, anything else closes it. Names are only gathered while a mention is actually being typed.
+        if matches!(self.state, AppState::Conversation) {
+            let draft = self
+                .message_textbox
+                .as_ref()
+                .filter(|t| Some(t.hit_id()) == self.focused)
+                .map(|t| (t.chars.clone(), t.cursor))
+                .unwrap_or_default();
+            let names = if mention::trigger(&draft.0, draft.1).is_some() { self.mention_names() } else { Vec::new() };
+            if self.mention.update(&draft.0, draft.1, &names) {
+                needs_redraw = true;
+            }
+        }
+
+        // The find bar follows its query box and the history: a new query re-runs and scrolls to the newest hit; a message landing re-runs it in place.
+        if matches!(self.state, AppState::Conversation) {
+            let query: Option<String> = self.msg_find.as_ref().and(self.find_textbox.as_ref()).map(|t| t.chars.iter().collect());
+            if let (Some(query), Some(ci)) = (query, self.active_contact.filter(|&ci| ci < self.contacts.len())) {
+                let shown = self.shown_history(ci);
+                let texts: Vec<&str> = shown.iter().map(|t| t.as_ref()).collect();
+                if self.msg_find.as_mut().is_some_and(|f| f.update(&texts, &query)) {
+                    self.reveal_find_hit();
+                    self.scene_dirty = true;
+                    needs_redraw = true;
+                }
+            }
+        }
+
+        // Spell-check is a local display knob (settings.vsf, not the linked fleet settings): flip the live flag and persist it.
+        let spell_toggle = self
+            .settings_spell_check
+            .as_mut()
+            .map(|cb| (cb.take_toggle(), cb.is_checked()));
+        if let Some((true, checked)) = spell_toggle {
+            self.spell_check = checked;
+            let mut settings = crate::storage::settings::Settings::load_or_create();
+            settings.spell_check = checked;
+            settings.save();
+            needs_redraw = true;
+        }
+
+        // Do-not-disturb, same local-knob treatment. Switching it off clears the held backlog off the Ready strip.
+        let dnd_toggle = self
+            .settings_dnd_check
+            .as_mut()
+            .map(|cb| (cb.take_toggle(), cb.is_checked()));
+        if let Some((true, checked)) = dnd_toggle {
+            self.dnd.set(checked);
+            let mut settings = crate::storage::settings::Settings::load_or_create();
+            settings.dnd = checked;
+            settings.save();
+            needs_redraw = true;
+        }
+
+        // Desktop resident-mode toggle: the OS autostart artifact IS the stored setting (platform::autostart — nothing in the vault to desync), and the live flag follows it immediately, so unchecking makes the very next close a real quit. A write failure reverts the box and says why.
+        #[cfg(not(target_os = "android"))]
+        {
+            let bg_toggle = self
+                .settings_background_check
                 .as_mut()
                 .map(|cb| (cb.take_toggle(), cb.is_checked()));
             if let Some((true, checked)) = bg_toggle {
@@ -6161,11 +8132,7 @@ impl PhotonApp {
                     self.pending_fleet_key = fleet_key;
                     self.add_join_handle = None;
                     // Attest with the roots the join thread already derived — no handle re-entry, no second ~1s proof, and no route thru submit_handle's permanence interstitial (this claims nothing new; the fleet exists and we were just bound into it).
-                    if let Some(hq) = self.handle_query.as_ref() {
-                        hq.query_first_attest_with_roots(session);
-                        self.state = AppState::Launch(LaunchState::Attesting);
-                        self.change_focus(None);
-                    }
+                    self.fire_attest_query_with_roots(session);
                 }
                 JoinUpdate::Failed(e) => {
                     // The ceremony is dead — take the words DOWN with it. Leaving them up strands the screen on a corpse: the user keeps waiting on words no thread is polling for. Back to handle entry with the error visible; re-submitting starts a fresh ceremony.
@@ -6360,6 +8327,7 @@ impl PhotonApp {
                         self.you_fields_loaded = false;
                         self.publish_profile_name();
                         self.publish_avatar_pin();
+                        self.adopt_avatar_removal();
                         // A sibling may have changed the avatar (profile.avatar_ts bump rides the same merge) — newest-wins sync pulls the fresh copy; a no-change sync is one cheap wall read.
                         self.spawn_avatar_sync();
                         crate::log("SETTINGS: adopted fleet changes");
@@ -6466,6 +8434,10 @@ impl PhotonApp {
             tb.set_rect(compose_cx, compose_cy, compose_w, compose_h);
             tb.set_font_size(font_size, ctx.text);
         }
+        if let Some(tb) = self.find_textbox.as_mut() {
+            tb.set_rect(compose_cx, compose_cy, compose_w, compose_h);
+            tb.set_font_size(font_size, ctx.text);
+        }
         if let Some(btn) = self.message_send_btn.as_mut() {
             let send_size = compose_h * 7.0 / 8.0;
             let send_inset = compose_h / 16.0;
@@ -6484,7 +8456,7 @@ impl PhotonApp {
             let ctrl_h = (layout.unit * 1.00).max(14.0);
             match page {
                 SettingsPage::Appearance => {
-                    // Rows: [0]=title [1]=Theme label [2]=Theme dropdown [3]=Party colours [4]=Zoom label [5]=Zoom slider [6]=Calibration.
+                    // Rows: [0]=title [1]=Theme label [2]=Theme dropdown [3]=Party colours [4]=Zoom label [5]=Zoom slider [6]=Calibration [7]=Spell-check.
                     let rows = layout.content_scrolled(8, settings_content_scroll).split_v([1.0; 8]);
                     if let Some(dd) = self.settings_theme_dropdown.as_mut() {
                         let r = rows[2].center_h(0.7);
@@ -6495,6 +8467,11 @@ impl PhotonApp {
                         let r = rows[5].center_h(0.8);
                         sl.set_rect(r.center_x(), r.center_y(), r.w, ctrl_h);
                     }
+                    if let Some(cb) = self.settings_spell_check.as_mut() {
+                        let r = rows[7];
+                        cb.set_rect(r.x + r.w * 0.45, r.center_y(), r.w * 0.9, ctrl_h);
+                        cb.set_font_size(ctrl_font);
+                    }
                 }
                 SettingsPage::Recovery => {
                     let rows = layout.content_scrolled(8, settings_content_scroll).split_v([1.0; 8]);
@@ -6521,6 +8498,11 @@ impl PhotonApp {
                         cb.set_rect(r.x + r.w * 0.45, r.center_y(), r.w * 0.9, ctrl_h);
                         cb.set_font_size(ctrl_font);
                     }
+                    if let Some(cb) = self.settings_dnd_check.as_mut() {
+                        let r = rows[7];
+                        cb.set_rect(r.x + r.w * 0.45, r.center_y(), r.w * 0.9, ctrl_h);
+                        cb.set_font_size(ctrl_font);
+                    }
                 }
                 SettingsPage::Updates => {
                     let rows = layout.content_scrolled(8, settings_content_scroll).split_v([1.0; 8]);
@@ -6593,6 +8575,13 @@ impl PhotonApp {
         if handle.is_empty() {
             return;
         }
+        // A contact link typed or IME-pasted into the box (Android has no paste chord) — add it directly, no search.
+        if self.apply_contact_link(&handle) {
+            if let Some(tb) = self.contacts_textbox.as_mut() {
+                tb.clear();
+            }
+            return;
+        }
 
         let typed_pid = crate::crypto::clutch::identity_party_id(&crate::types::Handle::to_identity_seed(&handle));
         if self.contacts.iter().any(|c| c.handle_hash == typed_pid) {
@@ -6720,6 +8709,13 @@ impl PhotonApp {
             "v" => {
                 if let Ok(mut clip) = arboard::Clipboard::new() {
                     if let Ok(s) = clip.get_text() {
+                        // A contact link pasted into the search box adds the contact directly instead of landing as search text.
+                        if on_contacts && self.apply_contact_link(&s) {
+                            return EventResponse::Handled;
+                        }
+                        let Some(tb) = (if on_launch { self.textbox.as_mut() } else { self.contacts_textbox.as_mut() }) else {
+                            return EventResponse::Pass;
+                        };
                         // Words entry accepts only letters and space — strip everything else from the paste (newlines/tabs become nothing; the camelCase/space tokenizer handles the rest).
                         let s = if matches!(self.state, AppState::AddDevice) {
                             s.chars().filter(|c| c.is_ascii_alphabetic() || *c == ' ').collect()
@@ -6747,8 +8743,10 @@ impl PhotonApp {
             AppState::Launch(LaunchState::Error(_))
                 | AppState::Launch(LaunchState::Confirm)
                 | AppState::Launch(LaunchState::KnownHandle)
+                | AppState::Launch(LaunchState::Retrying { .. })
         ) {
             self.state = AppState::Launch(LaunchState::Fresh);
+            self.attest_retry.reset();
             self.probed_session = None;
             self.probed_handle = None;
             if let Some(btn) = self.attest_btn.as_mut() {
@@ -7428,6 +9426,9 @@ impl PhotonApp {
         if adopted > 0 {
             crate::logf!("FLEET: adopted {} newer roster entr(ies) from siblings", adopted);
         }
+        if added + removed + adopted > 0 {
+            self.cloud_sync.touch(Instant::now());
+        }
         if added == 0 {
             return;
         }
@@ -7785,6 +9786,84 @@ impl PhotonApp {
         }
     }
 
+    /// "Remove avatar" (You page): back to the placeholder everywhere. The local half runs now ([`Self::forget_avatar`]); the wall slot under the current pin is deleted off-thread, so friends' next fetch finds nothing and siblings adopt the removal from the fleet-synced `profile.avatar_removed` stamp instead of re-uploading their copy.
+    fn remove_avatar(&mut self) {
+        let Some(identity_seed) = self.session.as_ref().map(|s| s.identity_seed) else {
+            return;
+        };
+        let Some(pin) = self.forget_avatar() else {
+            return;
+        };
+        let Some(kp) = self.device_keypair.clone() else {
+            crate::log("AVATAR: removed locally — no device key to delete the wall slot with (orphan blob remains)");
+            return;
+        };
+        std::thread::spawn(move || {
+            let sk = ed25519_dalek::SigningKey::from_bytes(kp.secret.as_bytes());
+            match crate::ui::avatar::delete_avatar_blocking(&sk, &identity_seed, &pin) {
+                Ok(()) => crate::log("AVATAR: wall slot deleted (avatar removed)"),
+                Err(e) => crate::logf!("AVATAR: wall slot delete failed (orphan blob remains): {}", e),
+            }
+        });
+    }
+
+    /// The on-device half of an avatar removal: evict the vault copy, drop every in-memory rendition (the placeholder draws whenever `device_avatar_pixels` is `None`), and stamp `profile.avatar_removed` for the fleet. Returns the pin whose wall slot must die — `None` when there's no session/vault to act on or no pin was ever minted (nothing on the wall). The pin itself stays: it's a bearer credential friends already hold, and the next avatar set rotates it anyway.
+    fn forget_avatar(&mut self) -> Option<[u8; 64]> {
+        let identity_seed = self.session.as_ref()?.identity_seed;
+        let storage = self.storage.clone()?;
+        if let Err(e) = storage.delete_addr(&crate::storage::vault_key("avatar", &identity_seed)) {
+            crate::logf!("AVATAR: vault evict failed: {}", e);
+        }
+        let _ = crate::ui::avatar::delete_avatar_animation_from_seed(&identity_seed, &storage);
+        self.device_avatar_pixels = None;
+        self.device_avatar_anim = None;
+        self.device_avatar_scaled = None;
+        self.device_avatar_scaled_diameter = 0;
+        self.avatar_set_rx = None; // an in-flight pick must not resurrect what was just removed
+        // One batched write: the removal stamp + the avatar_ts ding that wakes siblings into adopting it.
+        if self.ensure_fleet_settings() {
+            let now = vsf::eagle_time_oscillations();
+            let fs = self.fleet_settings.as_mut().unwrap();
+            fs.set("profile.avatar_removed", now.to_le_bytes().to_vec(), now);
+            fs.set("profile.avatar_ts", now.to_le_bytes().to_vec(), now);
+            self.persist_and_push_settings();
+        }
+        self.scene_dirty = true;
+        crate::log("AVATAR: removed — placeholder restored");
+        self.fleet_settings
+            .as_ref()
+            .and_then(|fs| fs.effective("profile.avatar_pin"))
+            .and_then(|v| <[u8; 64]>::try_from(v).ok())
+    }
+
+    /// When the fleet last removed our avatar (`profile.avatar_removed`, eagle oscillations LE), or `None` if it's been set since. An empty value is the "set since" tombstone.
+    fn avatar_removed_at(&self) -> Option<i64> {
+        let v = self.fleet_settings.as_ref()?.effective("profile.avatar_removed")?;
+        Some(i64::from_le_bytes(v.try_into().ok()?))
+    }
+
+    /// Sibling side of a removal: a merged `profile.avatar_removed` newer than our vault copy means another device removed the avatar — drop ours too rather than letting the next sync re-upload it.
+    fn adopt_avatar_removal(&mut self) {
+        let (Some(removed), Some(identity_seed), Some(storage)) =
+            (self.avatar_removed_at(), self.session.as_ref().map(|s| s.identity_seed), self.storage.clone())
+        else {
+            return;
+        };
+        let Some(local_ts) = crate::ui::avatar::get_local_avatar_timestamp_from_seed(&identity_seed, &storage) else {
+            return;
+        };
+        if local_ts < removed {
+            let _ = storage.delete_addr(&crate::storage::vault_key("avatar", &identity_seed));
+            let _ = crate::ui::avatar::delete_avatar_animation_from_seed(&identity_seed, &storage);
+            self.device_avatar_pixels = None;
+            self.device_avatar_anim = None;
+            self.device_avatar_scaled = None;
+            self.device_avatar_scaled_diameter = 0;
+            self.scene_dirty = true;
+            crate::log("AVATAR: a sibling removed the avatar — dropped our copy");
+        }
+    }
+
     /// Rotate the avatar bearer pin WITHOUT a new image — the removal-heal follow-up (braid.md §14.2). The pin is a bearer credential (AES key ‖ wall lookup) held at rest by friends and the whole fleet, and it otherwise rotates only on an avatar CHANGE — so a departed device would keep fetching + decrypting the avatar forever. Mirrors `set_avatar_from_file`'s rotate-on-set half: mint + set + publish + stamp-bump on the UI thread (the stamp makes siblings refetch, since the old wall slot is about to die), then re-upload the vault-cached avatar under the new pin and delete the old slot off-thread. Skips — pin left standing — when there's no pin at rest (nothing to revoke) or no vault copy to re-upload (deleting the wall blob without a replacement would blank the avatar fleet-wide; the next avatar set/sync closes it). Same accepted ordering as set: the new pin is announced before the upload lands, so an upload failure leaves a dangling pin that heals on the next set.
     fn rotate_avatar_pin(&mut self) {
         let Some(identity_seed) = self.session.as_ref().map(|s| s.identity_seed) else {
@@ -7864,19 +9943,317 @@ impl PhotonApp {
         if let Some(cb) = self.settings_autoupdate_check.as_mut() {
             cb.set_checked(auto);
         }
-        // Restore this device's persisted zoom (display.zoom, f32 LE bytes — binary at rest). Handed to the host as a one-shot absolute request; applies exactly like a user zoom.
+        // Restore this device's persisted zoom (display.zoom, f32 LE bytes — binary at rest), held to the release zoom range so a stray stored value can't open an unusable window. Handed to the host as a one-shot absolute request; applies exactly like a user zoom.
         if let Some(ru) = self
             .fleet_settings
             .as_ref()
             .and_then(|fs| fs.effective("display.zoom"))
-            .filter(|v| v.len() == 4)
-            .map(|v| f32::from_le_bytes([v[0], v[1], v[2], v[3]]))
-            .filter(|ru| ru.is_finite() && *ru > 0.0)
+            .and_then(crate::storage::fleet_settings::decode_zoom)
         {
             self.pending_zoom_restore = Some(ru);
         }
     }
 
+    /// The contacts list in DISPLAY order: search-filtered (case-insensitive substring on the handle; empty filter = all), siblings dropped, archived or not per `show_archived`, pinned first and unread floated ([`contact_order`]). The ONE place display order exists — the row loop draws from it AND stamps each row's hit id with the TRUE contact index it holds (so taps resolve with no knowledge of the permutation), and keyboard navigation walks the same order.
+    fn contact_display_order(&self) -> Vec<usize> {
+        let filter: String = self
+            .contacts_textbox
+            .as_ref()
+            .map(|t| t.chars.iter().collect::<String>().to_lowercase())
+            .unwrap_or_default();
+        let matching: Vec<ListEntry> = self
+            .contacts
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| {
+                // Fleet siblings are infrastructure, not conversations — never listed (device management gets its own page later).
+                !c.is_sibling
+                    && (filter.is_empty()
+                        || c.display_name().to_lowercase().contains(&filter))
+            })
+            .map(|(ci, c)| ListEntry { ci, pinned: c.pinned, unread: c.unread_count > 0, archived: c.archived })
+            .collect();
+        // The main list or the archived filter; pinned lead, then unread float. Stable sort preserves vault order within each group (incl. the self contact's relative position).
+        contact_order(&matching, self.show_archived)
+    }
+
+    /// Open contact `ci`'s conversation — the shared landing for a row tap and keyboard Enter.
+    fn open_conversation(&mut self, ci: usize) {
+        crate::logf!("contact-open: opening conversation with '{}'", self.contacts[ci].display_name());
+        self.active_contact = Some(ci);
+        self.state = AppState::Conversation;
+        // A find bar belongs to the conversation it was opened on.
+        self.msg_find = None;
+        // Opening the conversation is the interaction that clears unread (ring + float drop away on the next contacts-list frame).
+        self.clear_unread(ci);
+        self.change_focus(None);
+        // Refresh this contact's presence on conversation-enter so the header reflects reality promptly.
+        self.ping_contact(ci);
+        // Fetch the peer's avatar (once/session) so the conversation header shows it instead of the grey placeholder. Cache-first, network on miss; off-thread. Keyed by the pin-set (hp + party id + avatar key) — no handle.
+        self.spawn_avatar_download(ci);
+    }
+
+    /// Ctrl+F: open the find bar over the open conversation with an empty query and give it the keyboard. Already open → just refocus it, query kept.
+    fn open_find(&mut self) {
+        if self.msg_find.is_none() {
+            self.msg_find = Some(find::Find::default());
+            if let Some(tb) = self.find_textbox.as_mut() {
+                tb.clear();
+            }
+        }
+        let id = self.find_textbox.as_ref().map(|t| t.hit_id());
+        self.change_focus(id);
+    }
+
+    /// Esc in the find bar: the hits stop painting and the compose box comes back, focused.
+    fn close_find(&mut self) {
+        self.msg_find = None;
+        self.msg_row_lifts.clear();
+        let id = self.message_textbox.as_ref().map(|t| t.hit_id());
+        self.change_focus(id);
+    }
+
+    /// Scroll the open conversation so the find bar's current hit sits mid-band, off the row positions the last frame published.
+    fn reveal_find_hit(&mut self) {
+        let Some(((msg, _), _)) = self.msg_find.as_ref().and_then(|f| f.current()) else {
+            return;
+        };
+        let Some(&(lift, height)) = self.msg_row_lifts.get(msg) else {
+            return;
+        };
+        let view_h = self.msg_list_band.map_or(0.0, |(top, bottom)| (bottom - top).max(0.0));
+        if let Some(contact) = self.active_contact.and_then(|ci| self.contacts.get_mut(ci)) {
+            contact.message_scroll_offset = find::reveal_offset(lift, height, view_h).min(self.msg_scroll_extent);
+        }
+        // The rows move out from under an open menu and every hit stamp — same as a keyboard page.
+        self.msg_menu = None;
+        if let Some(chrome) = self.chrome.as_mut() {
+            chrome.invalidate_chrome();
+        }
+    }
+
+    /// Ctrl+V onto the selected avatar: pull an image off the clipboard, wrap it as PNG, and hand it to the same crop screen + pipeline a dropped file runs (`frame_avatar`). Text or empty clipboards get a toast instead of a silent no-op.
+    #[cfg(not(any(target_os = "redox", target_os = "android")))]
+    fn paste_avatar_from_clipboard(&mut self) {
+        let img = arboard::Clipboard::new().and_then(|mut clip| clip.get_image());
+        let png = match img {
+            Ok(img) => crate::ui::avatar::rgba_to_png(img.width, img.height, &img.bytes),
+            Err(e) => Err(e.to_string()),
+        };
+        match png {
+            Ok(bytes) => {
+                crate::logf!("avatar paste: clipboard image → {} PNG bytes", bytes.len());
+                self.frame_avatar(bytes);
+            }
+            Err(e) => {
+                crate::logf!("avatar paste: no usable image on the clipboard ({})", e);
+                self.ready_toast = Some("No image on the clipboard \u{2014} copy an image, then paste".to_string());
+            }
+        }
+    }
+
+    /// Ctrl+C over a selected span of the launch status/error line. `false` = nothing selected (the chord falls thru to the focused textbox).
+    fn copy_status_selection(&mut self) -> bool {
+        let (Some(range), Some((text, _))) = (self.status_selection, self.status_row.as_ref()) else {
+            return false;
+        };
+        if !matches!(self.state, AppState::Launch(_)) {
+            return false;
+        }
+        let text = message_select::selected_text(&[text.as_str()], range);
+        if text.is_empty() {
+            return false;
+        }
+        let ok = self.copy_to_clipboard(&text);
+        crate::logf!("COPY: {} char(s) of launch status → clipboard (ok = {})", text.chars().count(), ok);
+        ok
+    }
+
+    /// The "new messages ↓" pill: scroll the open conversation to its newest message and clear the unread count that raised the pill.
+    fn jump_to_latest(&mut self) {
+        let Some(ci) = self.active_contact else {
+            return;
+        };
+        if let Some(contact) = self.contacts.get_mut(ci) {
+            contact.message_scroll_offset = 0.0;
+        }
+        self.clear_unread(ci);
+        self.msg_jump_rect = None;
+        self.msg_menu = None;
+        self.scene_dirty = true;
+        if let Some(chrome) = self.chrome.as_mut() {
+            chrome.invalidate_chrome();
+        }
+    }
+
+    /// Open the message menu for the history row under `(x, y)` on the open conversation, against the rows the last frame published. Drops any history selection (the menu acts on the whole message). `false` when the point is on no row or that row has nothing to offer — an open menu closes either way.
+    fn open_message_menu(&mut self, x: f32, y: f32) -> bool {
+        self.msg_selection = None;
+        self.scene_dirty = true;
+        let menu = self.active_contact.and_then(|ci| {
+            let contact = self.contacts.get(ci)?;
+            // Same probe filter as render, so the row indices line up.
+            let visible: Vec<&crate::types::ChatMessage> = contact
+                .messages
+                .iter()
+                .filter(|m| m.content != crate::types::CHAIN_PROBE_MARKER)
+                .collect();
+            context_menu::open_at(&self.msg_rows, &visible, ci, x, y)
+        });
+        if let Some(menu) = menu.as_ref() {
+            crate::logf!("MENU: message {} of contact {} → {:?}", menu.msg, menu.contact, menu.actions);
+        }
+        self.msg_menu = menu;
+        self.msg_menu.is_some()
+    }
+
+    /// A pick from the message menu on the `visible_idx`-th history row of contact `ci`. Every action is a path the history already had: Copy takes the whole message (the drag-select copy), View / Save / Play are the row's tap ([`Self::open_history_row`]), Retry its failed-send tap ([`Self::retry_failed_message`]). A no-op once the conversation has changed under the menu.
+    fn run_message_action(&mut self, ci: usize, visible_idx: usize, action: context_menu::MessageAction) {
+        if self.active_contact != Some(ci) || !matches!(self.state, AppState::Conversation) {
+            return;
+        }
+        match action {
+            context_menu::MessageAction::Copy => {
+                let text = self.shown_history(ci).into_iter().nth(visible_idx).map(|t| t.into_owned());
+                if let Some(text) = text.filter(|t| !t.is_empty()) {
+                    let ok = self.copy_to_clipboard(&text);
+                    crate::logf!("COPY: {} char(s) of one message → clipboard (ok = {})", text.chars().count(), ok);
+                }
+            }
+            context_menu::MessageAction::View | context_menu::MessageAction::Save | context_menu::MessageAction::Play => {
+                self.open_history_row(ci, visible_idx)
+            }
+            context_menu::MessageAction::Retry => self.retry_failed_message(ci, visible_idx),
+        }
+    }
+
+    /// Ctrl+C on a settled conversation-history selection: copy the selected span (one message per line) to the clipboard. Returns false — so the chord falls thru to the textbox path — when there's no selection on the open conversation.
+    fn copy_message_selection(&mut self) -> bool {
+        let Some((ci, range)) = self.msg_selection else {
+            return false;
+        };
+        if self.active_contact != Some(ci) || !matches!(self.state, AppState::Conversation) {
+            return false;
+        }
+        // Same probe filter as render, so the indices line up with the rows the drag resolved against.
+        let text = match self.contacts.get(ci) {
+            Some(_) => {
+                let shown = self.shown_history(ci);
+                let visible: Vec<&str> = shown.iter().map(|t| t.as_ref()).collect();
+                message_select::selected_text(&visible, range)
+            }
+            None => return false,
+        };
+        if text.is_empty() {
+            return false;
+        }
+        let ok = self.copy_to_clipboard(&text);
+        crate::logf!("COPY: {} char(s) of conversation history → clipboard (ok = {})", text.chars().count(), ok);
+        ok
+    }
+
+    /// Exit-time persistence: write the entries `storage::unflushed` says memory is ahead on — a save that failed mid-session, or a mutation that deferred its save. Every save elsewhere is immediate, so normally nothing is owed and exit touches no disk. In-flight PT transfers need no separate checkpoint: each one carries a chain message whose un-ACKed row and pending chain entry were saved when it was queued, and the next launch's retransmit re-sends it. An owed entry for a contact or friendship no longer in memory is skipped. Safe to call any number of times. Returns how many writes failed (each is logged, and stays owed).
+    fn flush_for_shutdown(&self) -> usize {
+        use crate::storage::unflushed::{self, Unflushed};
+        let Some(storage) = self.storage.as_ref() else {
+            return 0;
+        };
+        let contact = |pid: &[u8; 32]| self.contacts.iter().find(|c| !c.is_sibling && c.handle_hash == *pid);
+        let mut failed = 0;
+        for entry in unflushed::owed() {
+            let result = match entry {
+                Unflushed::Chains(fid) => match self.friendship_chains.iter().find(|(id, _)| id.as_bytes() == &fid) {
+                    Some((_, chains)) => crate::storage::friendship::save_friendship_chains(chains, storage),
+                    None => continue,
+                },
+                Unflushed::ContactState(pid) | Unflushed::Messages(pid) => match contact(&pid) {
+                    Some(c) if matches!(entry, Unflushed::ContactState(_)) => crate::storage::contacts::save_contact_state(c, storage),
+                    Some(c) => crate::storage::contacts::save_messages(c, storage),
+                    None => continue,
+                },
+                Unflushed::ContactList => {
+                    let index: Vec<crate::storage::contacts::ContactIdentity> = self
+                        .contacts
+                        .iter()
+                        .filter(|c| !c.is_sibling)
+                        .map(|c| crate::storage::contacts::ContactIdentity {
+                            handle_proof: c.handle_proof,
+                            party_id: c.handle_hash,
+                            name: c.petname.clone(),
+                            avatar_pin: c.avatar_pin,
+                        })
+                        .collect();
+                    crate::storage::contacts::save_contact_list(&index, storage)
+                }
+            };
+            if let Err(e) = result {
+                crate::logf!("EXIT: owed write failed: {}", e);
+                failed += 1;
+            }
+        }
+        failed
+    }
+
+    /// The real exit (not a resident hide): flush pending state, then close the relay pipe so fgtw.org sees a clean disconnect instead of a socket timeout. Runs once; a second close request after it is a no-op.
+    fn shutdown(&mut self) {
+        if self.shut_down {
+            return;
+        }
+        self.shut_down = true;
+        let failed = self.flush_for_shutdown();
+        crate::logf!("EXIT: state flushed ({} {} failed)", failed, if failed == 1 { "write" } else { "writes" });
+        if self.settle_cloud_batch_at_exit(crate::storage::cloud::sync_cloud_contacts, EXIT_UPLOAD_TIMEOUT) {
+            let mut settings = crate::storage::settings::Settings::load_or_create();
+            settings.cloud_sync_owed = true;
+            settings.save();
+            crate::log("EXIT: pending contact batch owed to the next launch");
+        }
+        if let Some(checker) = self.status_checker.as_ref() {
+            checker.close_pipe();
+        }
+    }
+
+    /// A contact batch still waiting out its quiet period at exit goes up now: `upload` runs on a worker and quit waits at most `timeout` for it. Returns `true` when the batch is still owed — the upload failed, ran out of time, or there's no session to upload with — and the caller leaves it to the next launch. `false` with nothing pending.
+    fn settle_cloud_batch_at_exit(&mut self, upload: CloudUpload, timeout: std::time::Duration) -> bool {
+        if !self.cloud_sync.take_pending() {
+            return false;
+        }
+        let Some((rows, seed, kp, hp)) = self.cloud_contacts_snapshot() else {
+            return true;
+        };
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(upload(&rows, &seed, &kp, &hp));
+        });
+        match rx.recv_timeout(timeout) {
+            Ok(Ok(())) => {
+                crate::log("EXIT: pending contact batch uploaded");
+                false
+            }
+            Ok(Err(e)) => {
+                crate::logf!("EXIT: contact batch upload failed: {}", e);
+                true
+            }
+            Err(_) => {
+                crate::logf!("EXIT: contact batch upload still running after {:?}", timeout);
+                true
+            }
+        }
+    }
+
+    /// Write the tracked window size + maximized flag into `settings.vsf`. No-op until `on_resize` has seen a size (Android never tracks one — the surface is always fullscreen).
+    fn save_window_geometry(&self) {
+        let Some((w, h)) = self.window_size else {
+            return;
+        };
+        let mut settings = crate::storage::settings::Settings::load_or_create();
+        settings.window_w = w;
+        settings.window_h = h;
+        settings.window_maximized = self.window_maximized;
+        settings.save();
+        crate::logf!("WINDOW: saved geometry {}x{} maximized={}", w, h, self.window_maximized);
+    }
+
     /// Persist the settled zoom as this DEVICE's `display.zoom` (docs/global-vault.md model: per-device value, so it's UNLINKED — zoom is monitor ergonomics, never fleet-global — but still mirrored thru the fleet's device maps like every device setting). f32 LE bytes: binary at rest.
     fn save_zoom_setting(&mut self, ru: f32) {
         if !self.ensure_fleet_settings() {
@@ -8658,7 +11035,87 @@ impl PhotonApp {
         });
     }
 
-    /// Textbox front-end for the open conversation: pull + trim the compose text, hand it to [`Self::send_chain_message`] for the active contact (bubble shown), then clear the box.
+    /// "Tap to retry" on the `visible_idx`-th (probe-filtered, oldest-first — the render's row order) message of contact `ci`. A no-op unless it's one of our failed sends. Still pending on its chain → its backoff is reset and the retransmit sweep sends it again (same ciphertext, same chain slot, so the receiver's dedup and ACK work unchanged). No longer pending (a chain reset dropped it) → the same text goes out as a fresh send.
+    fn retry_failed_message(&mut self, ci: usize, visible_idx: usize) {
+        let Some(contact) = self.contacts.get_mut(ci) else {
+            return;
+        };
+        let Some(pos) = contact
+            .messages
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.content != crate::types::CHAIN_PROBE_MARKER)
+            .nth(visible_idx)
+            .map(|(i, _)| i)
+        else {
+            return;
+        };
+        let msg = &mut contact.messages[pos];
+        if !(msg.is_outgoing && msg.failed) {
+            return;
+        }
+        msg.failed = false;
+        let (eagle_time, text, image, file, memo) =
+            (msg.timestamp, msg.content.clone(), msg.image.clone(), msg.attachment.clone(), msg.voice.clone());
+        let fid = contact.friendship_id;
+        self.scene_dirty = true;
+        let requeued = fid.is_some_and(|fid| {
+            self.friendship_chains
+                .iter_mut()
+                .find(|(id, _)| *id == fid)
+                .is_some_and(|(_, chains)| chains.retry_pending(eagle_time, vsf::eagle_time_oscillations()))
+        });
+        if requeued {
+            crate::logf!("CHAT: retry requested for msg eagle_time {} — re-queued", eagle_time);
+            return;
+        }
+        // The old row stays as the record of the attempt — conversation rows are keyed by eagle_time and never deleted, so a fresh send is a fresh row.
+        crate::logf!("CHAT: retry requested for msg eagle_time {} — no longer pending, sending afresh", eagle_time);
+        let sent = match (image, file, memo) {
+            (Some(jpeg), _, _) => self.send_image(ci, jpeg),
+            (None, Some(file), _) => self.send_attachment(ci, file),
+            (None, None, Some(memo)) => self.send_voice(ci, memo),
+            (None, None, None) => self.send_message(ci, &text),
+        };
+        if let Err(e) = sent {
+            crate::logf!("CHAT: retry not sent: {}", e);
+            if let Some(m) = self.contacts.get_mut(ci).and_then(|c| c.messages.iter_mut().find(|m| m.timestamp == eagle_time)) {
+                m.failed = true;
+            }
+        }
+    }
+
+    /// Tap on the `visible_idx`-th history row of contact `ci` (render's probe-filtered order): an image row opens over the history; a file row saves into the downloads folder (never overwriting — see [`crate::types::Attachment::save_into`]) and says where in the toast; a voice row plays. A failed send is left to its retry tap instead.
+    fn open_history_row(&mut self, ci: usize, visible_idx: usize) {
+        let tapped = self.contacts.get(ci).and_then(|c| {
+            c.messages.iter().filter(|m| m.content != crate::types::CHAIN_PROBE_MARKER).nth(visible_idx)
+        });
+        let Some(msg) = tapped.filter(|m| !(m.is_outgoing && m.failed)) else {
+            return;
+        };
+        if msg.image.is_some() {
+            self.chat_image_expanded = Some(msg.timestamp);
+            self.scene_dirty = true;
+        } else if let Some(file) = msg.attachment.clone() {
+            self.ready_toast = Some(match dirs::download_dir() {
+                Some(dir) => match file.save_into(&dir) {
+                    Ok(path) => format!("Saved {}", path.display()),
+                    Err(e) => format!("Couldn't save {}: {}", file.name, e),
+                },
+                None => "No downloads folder to save into on this device".to_string(),
+            });
+            self.scene_dirty = true;
+        } else if let Some(memo) = msg.voice.as_ref() {
+            if crate::platform::voice::can_play() {
+                crate::platform::voice::play(&memo.opus);
+            } else {
+                self.ready_toast = Some("Voice memos play on Android only \u{2014} desktop can't record or play them".to_string());
+                self.scene_dirty = true;
+            }
+        }
+    }
+
+    /// Textbox front-end for the open conversation: pull + trim the compose text, hand it to [`Self::send_message`] for the active contact, then clear the box once it went out.
     fn submit_message(&mut self) {
         let Some(ci) = self.active_contact else {
             return;
@@ -8668,24 +11125,182 @@ impl PhotonApp {
             Some(tb) => tb.chars.iter().collect(),
             None => return,
         };
+        // The one rewrite: each `@name` of a contact goes out as their party id — our name for them means nothing on the other side.
+        let text = if text.contains('@') { mention::encode(&text.chars().collect::<Vec<_>>(), &self.mention_parties()) } else { text };
         if text.is_empty() {
             // Empty send = liveness probe. Optimistically mark the peer offline and ping them; a returning pong flips is_online back true (check_status_updates), so an empty send confirms whether they're actually reachable right now instead of doing nothing.
             self.contacts[ci].is_online = false;
             self.ping_contact(ci);
             return;
         }
-        self.send_chain_message(ci, &text, false);
+        // Keep the text in the box when nothing was sent, so a send with no chain or address yet isn't silently lost.
+        if let Err(e) = self.send_message(ci, &text) {
+            crate::logf!("CHAT: not sent: {}", e);
+            return;
+        }
         if let Some(tb) = self.message_textbox.as_mut() {
             tb.clear();
         }
+        // A sent message isn't an edit to undo — Ctrl+Z must not resurrect it into the box.
+        self.text_undo = None;
         // Tell the Android host to restart IME input — a predictive keyboard still holds the just-sent text as a composing buffer and would re-materialise it on the next keystroke without this.
         self.pending_input_reset = true;
     }
 
-    /// Encrypt + send + persist one chat message to `contact_idx` over the friendship chain, appending an outgoing bubble only when `!suppress_bubble`. Returns `true` if the message was dispatched to the network (so callers like the chain-weave probe only latch `probe_sent` on an actual send, and retry next cycle if the contact had no address yet). This is the reusable core factored out of the old open-contact send: it works for ANY contact index (not just `active_contact`), so the hidden chain-weave probe can ride the exact same ratchet path with its UI suppressed. Chain math (`prepare_send`, salt/advance) is untouched — the probe is a normal message whose only difference is a reserved marker content and a hidden bubble.
-    fn send_chain_message(&mut self, contact_idx: usize, text: &str, suppress_bubble: bool) -> bool {
-        use vsf::schema::section::FieldValue;
+    /// The one programmatic send: chain-encrypt `text` for `contact_idx`, persist the pending entry, hand it to PT, and append + persist the outgoing bubble. The compose box, tap-to-retry and the RPC automation surface all come thru here. `Err` = nothing was sent or stored.
+    pub fn send_message(&mut self, contact_idx: usize, text: &str) -> Result<(), SendError> {
+        if text.is_empty() {
+            return Err(SendError::Empty);
+        }
+        self.send_chain_message(contact_idx, text, None, None, None, false)
+    }
+
+    /// Send an image message (a [`crate::ui::chat_image::prepare_chat_image`] JPEG) to `contact_idx` — the same chain send as text, with [`crate::types::IMAGE_PLACEHOLDER`] as the caption and the JPEG riding alongside it. PT shards it like any large payload.
+    pub fn send_image(&mut self, contact_idx: usize, jpeg: Vec<u8>) -> Result<(), SendError> {
+        if jpeg.is_empty() {
+            return Err(SendError::Empty);
+        }
+        self.send_chain_message(contact_idx, crate::types::IMAGE_PLACEHOLDER, Some(jpeg), None, None, false)
+    }
+
+    /// Send a file attachment to `contact_idx` — the same chain send as text, with the file's caption as the text and its name/size/hash + bytes riding alongside (see [`crate::types::Attachment`]). PT shards it like any large payload.
+    pub fn send_attachment(&mut self, contact_idx: usize, file: crate::types::Attachment) -> Result<(), SendError> {
+        let caption = file.caption();
+        self.send_chain_message(contact_idx, &caption, None, Some(file), None, false)
+    }
+
+    /// Send a voice memo to `contact_idx` — the same chain send as text, with the memo's caption as the text and its length/waveform + Opus clip riding alongside (see [`crate::types::VoiceMemo`]). PT shards it like any large payload.
+    pub fn send_voice(&mut self, contact_idx: usize, memo: crate::types::VoiceMemo) -> Result<(), SendError> {
+        let caption = memo.caption();
+        self.send_chain_message(contact_idx, &caption, None, None, Some(memo), false)
+    }
 
+    /// Desktop drop onto an open conversation: size-check the file up front (the hint is instant, and a huge file is never read), then read + [`crate::ui::chat_image::prepare_drop`] on a low-priority worker. The result lands in `chat_drop_rx` keyed by the recipient's party id, so switching conversations mid-encode still sends to whoever was open at drop time.
+    fn drop_file_into_conversation(&mut self, path: &std::path::Path) {
+        let Some(party) = self.active_contact.and_then(|ci| self.contacts.get(ci)).map(|c| c.handle_hash) else {
+            return;
+        };
+        match std::fs::metadata(path) {
+            Ok(m) if m.len() > crate::ui::chat_image::MAX_DROP_BYTES => {
+                self.ready_toast = Some(format!("That file is too big to send — up to {} MB.", crate::ui::chat_image::MAX_DROP_BYTES >> 20));
+                self.scene_dirty = true;
+                return;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                crate::logf!("CHAT: file drop: stat failed: {}", e);
+                return;
+            }
+        }
+        if self.chat_drop_tx.is_none() {
+            let (tx, rx) = std::sync::mpsc::channel();
+            self.chat_drop_tx = Some(tx);
+            self.chat_drop_rx = Some(rx);
+        }
+        let tx = self.chat_drop_tx.clone().unwrap();
+        let path = path.to_path_buf();
+        #[cfg(not(target_os = "android"))]
+        let proxy = self.event_proxy.clone();
+        std::thread::spawn(move || {
+            #[cfg(not(target_os = "redox"))]
+            let _ = thread_priority::set_current_thread_priority(thread_priority::ThreadPriority::Min);
+            let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            let result = std::fs::read(&path)
+                .map_err(|e| format!("Couldn't read that file: {}", e))
+                .and_then(|bytes| crate::ui::chat_image::prepare_drop(&name, bytes));
+            let _ = tx.send((party, result));
+            #[cfg(not(target_os = "android"))]
+            if let Some(p) = proxy.as_ref() {
+                let _ = p.send(crate::ui::PhotonEvent::NetworkUpdate);
+            }
+        });
+    }
+
+    /// Send every file the drop worker has finished (tick). A rejection surfaces its hint as the toast; a send failure says why, like the compose box.
+    fn drain_dropped_files(&mut self) {
+        use crate::ui::chat_image::Dropped;
+        let Some(rx) = self.chat_drop_rx.as_ref() else {
+            return;
+        };
+        let done: Vec<([u8; 32], Result<Dropped, String>)> = rx.try_iter().collect();
+        for (party, result) in done {
+            let Some(ci) = self.contacts.iter().position(|c| c.handle_hash == party) else {
+                continue; // the contact went away mid-encode
+            };
+            let sent = result.and_then(|dropped| match dropped {
+                Dropped::Image(jpeg) => self.send_image(ci, jpeg).map_err(|e| format!("Image not sent: {}", e)),
+                Dropped::File(file) => self.send_attachment(ci, file).map_err(|e| format!("File not sent: {}", e)),
+            });
+            match sent {
+                Ok(()) => crate::log("CHAT: sent a dropped file"),
+                Err(hint) => self.ready_toast = Some(hint),
+            }
+            self.scene_dirty = true;
+        }
+    }
+
+    /// Press on the send button: with an empty compose box in an open conversation on a platform that records, start a voice memo. `false` = not a recording press (the button keeps its normal send).
+    fn start_voice_recording(&mut self) -> bool {
+        let compose_empty = self.message_textbox.as_ref().is_none_or(|t| t.chars.is_empty());
+        let party = self.active_contact.and_then(|ci| self.contacts.get(ci)).map(|c| c.handle_hash);
+        let (Some(party), true, true, true) =
+            (party, compose_empty, matches!(self.state, AppState::Conversation), crate::platform::voice::can_record())
+        else {
+            return false;
+        };
+        crate::platform::voice::start_recording();
+        self.voice_recording = Some((Instant::now(), party));
+        self.scene_dirty = true;
+        true
+    }
+
+    /// End a hold-to-record: `keep` stops it and waits for the clip (sent by [`Self::drain_voice_memo`]); otherwise the recording is thrown away.
+    fn finish_voice_recording(&mut self, keep: bool) {
+        let Some((_, party)) = self.voice_recording.take() else {
+            return;
+        };
+        if keep {
+            crate::platform::voice::stop_recording();
+            self.voice_pending_for = Some(party);
+        } else {
+            crate::platform::voice::cancel_recording();
+        }
+        self.scene_dirty = true;
+    }
+
+    /// Tick: repaint the running record timer (and stop at the memo cap as if released), then send a finished clip to whoever was open when it was recorded.
+    fn drain_voice_memo(&mut self) {
+        if let Some((since, _)) = self.voice_recording {
+            self.scene_dirty = true;
+            if since.elapsed() >= Duration::from_millis(crate::types::MAX_VOICE_MS as u64) {
+                self.finish_voice_recording(true);
+            }
+        }
+        let Some((opus, duration_ms, levels)) = crate::platform::voice::take_recorded() else {
+            return;
+        };
+        let Some(ci) = self.voice_pending_for.take().and_then(|party| self.contacts.iter().position(|c| c.handle_hash == party)) else {
+            return; // nobody asked for it, or the contact went away mid-record
+        };
+        let sent = crate::types::VoiceMemo::from_recording(opus, duration_ms, &levels)
+            .and_then(|memo| self.send_voice(ci, memo).map_err(|e| format!("Voice memo not sent: {}", e)));
+        match sent {
+            Ok(()) => crate::log("CHAT: sent a voice memo"),
+            Err(hint) => self.ready_toast = Some(hint),
+        }
+        self.scene_dirty = true;
+    }
+
+    /// Encrypt + send + persist one chat message to `contact_idx` over the friendship chain, appending an outgoing bubble only when `!suppress_bubble`. `Ok` once the message was dispatched to the network (so callers like the chain-weave probe only latch `probe_sent` on an actual send, and retry next cycle if the contact had no address yet). This is the reusable core factored out of the old open-contact send: it works for ANY contact index (not just `active_contact`), so the hidden chain-weave probe can ride the exact same ratchet path with its UI suppressed. Chain math (`prepare_send`, salt/advance) is untouched — the probe is a normal message whose only difference is a reserved marker content and a hidden bubble.
+    fn send_chain_message(
+        &mut self,
+        contact_idx: usize,
+        text: &str,
+        image: Option<Vec<u8>>,
+        attachment: Option<crate::types::Attachment>,
+        voice: Option<crate::types::VoiceMemo>,
+        suppress_bubble: bool,
+    ) -> Result<(), SendError> {
         let ci = contact_idx;
         let text = text.to_string();
 
@@ -8694,11 +11309,14 @@ impl PhotonApp {
             == self.contacts.get(ci).map(|c| c.handle_hash);
         if is_self {
             let Some(contact) = self.contacts.get_mut(ci) else {
-                return false;
+                return Err(SendError::NoSuchContact);
             };
             let mut msg =
                 ChatMessage::new_with_timestamp(text, true, vsf::eagle_time_oscillations());
             msg.delivered = true;
+            msg.image = image;
+            msg.attachment = attachment;
+            msg.voice = voice;
             contact.insert_message_sorted(msg.clone());
             contact.message_scroll_offset = 0.0;
             if let Some(storage) = self.storage.as_ref() {
@@ -8708,25 +11326,25 @@ impl PhotonApp {
             }
             // Live fleet propagation: a note typed here appears on our other devices now, not at the next sweep.
             self.push_rows_to_siblings(ci, std::slice::from_ref(&msg), None);
-            return true;
+            return Ok(());
         }
 
         // Contact must be CLUTCH-Complete with a friendship chain.
         let (friendship_id, recipient_pubkey, addr_pair, our_handle_hash, msg_relay_to) = {
             let Some(contact) = self.contacts.get(ci) else {
-                return false;
+                return Err(SendError::NoSuchContact);
             };
             if contact.clutch_state != crate::types::ClutchState::Complete {
                 crate::log("CHAT: cannot send — CLUTCH not complete");
-                return false;
+                return Err(SendError::ClutchIncomplete);
             }
             let Some(fid) = contact.friendship_id else {
                 crate::log("CHAT: cannot send — no friendship chain");
-                return false;
+                return Err(SendError::NoChain);
             };
             // Party id per contact: identity seed for friends, device-derived pid for fleet siblings — the chain index in prepare_send must match what from_clutch was keyed with.
             let Some(our_pid) = self.our_party_id(contact) else {
-                return false;
+                return Err(SendError::NotParticipant);
             };
             // No direct path → also relay this message over the pipe.
             let relay_to = if contact.validated_path.is_none() {
@@ -8734,51 +11352,19 @@ impl PhotonApp {
             } else {
                 Vec::new()
             };
-            (fid, contact.public_identity.key, contact.race_addrs(), our_pid, relay_to)
+            (fid, contact.public_identity.key, contact.race_addrs_behind(self.our_reflexive.map(|a| a.ip()), crate::network::udp::get_local_ip()), our_pid, relay_to)
         };
         let Some((peer_addr, alt_addr)) = addr_pair else {
             crate::log("CHAT: cannot send — no known address for contact");
-            return false;
+            return Err(SendError::NoAddress);
         };
 
         let eagle_time = vsf::eagle_time_oscillations();
 
-        // The braid: choose up to TWO distinct prior PEER messages to weave into this chain step. Eligible = incoming messages (is_outgoing == false) in the last ≤256 of this conversation — any stored incoming row is one the receive path already ACKed, so the sender knows the peer holds it (both-held → identical strands → lockstep). The weave ingredient is the message's x-text (`content`), recoverable identically on both sides from the message DB. Each chosen message's eagle_time goes on the wire so the receiver resolves the SAME content. 0 eligible → weave nothing (anchor). 1 → single strand. ≥2 → two distinct (a true braid). Pick with gen_range (bounded, bias-free) — NEVER modulo. Strands are sorted by eagle_time so both peers frame derive_fresh_link identically regardless of pick order.
-        let (woven_strands, woven_times): (Vec<Vec<u8>>, Vec<i64>) = {
-            let mut chosen: Vec<(i64, Vec<u8>)> = Vec::new();
-            if let Some(contact) = self.contacts.get(ci) {
-                let window: Vec<&crate::types::ChatMessage> = contact
-                    .messages
-                    .iter()
-                    .rev()
-                    // Probe rows are excluded from weave eligibility: they persist locally for re-ACK durability, but the PEER stores no outgoing row for its probe, so a woven probe ref would be unresolvable on their side — a guaranteed strand miss and chain fork.
-                    .filter(|m| !m.is_outgoing && m.content != crate::types::CHAIN_PROBE_MARKER)
-                    .take(256)
-                    .collect();
-                use rand::Rng;
-                let mut rng = rand::thread_rng();
-                if window.len() == 1 {
-                    let m = window[0];
-                    chosen.push((m.timestamp, m.content.as_bytes().to_vec()));
-                } else if window.len() >= 2 {
-                    let i = rng.gen_range(0..window.len());
-                    let mut j = rng.gen_range(0..window.len() - 1);
-                    if j >= i {
-                        j += 1; // map [0, len-1) → [0, len)\{i} so j is distinct from i, uniformly
-                    }
-                    for &idx in &[i, j] {
-                        let m = window[idx];
-                        chosen.push((m.timestamp, m.content.as_bytes().to_vec()));
-                    }
-                }
-            }
-            chosen.sort_by_key(|(t, _)| *t);
-            let times = chosen.iter().map(|(t, _)| *t).collect();
-            let strands = chosen.into_iter().map(|(_, c)| c).collect();
-            (strands, times)
-        };
+        // The braid: up to TWO distinct prior PEER messages woven into this chain step; each one's eagle_time goes on the wire so the receiver resolves the SAME content.
+        let woven = self.contacts.get(ci).map(|c| crate::types::pick_woven(&c.messages)).unwrap_or_default();
 
-        // Build the message VSF the receiver parses: (message: x{text}, hp{incorporated_hp}, e6{woven_time}…, hR{pad}), field order shuffled to enforce type-marker (not positional) parsing. The e6 values name the woven peer messages (0, 1, or 2). The receive path reads them back via VsfField::parse.
+        // Build + encrypt the message VSF the receiver parses. The image (v'j'), file and voice memo ride next to the caption — never chain-key material, only the x-text salts.
         let (ciphertext, prev_msg_hp, conversation_token) = {
             let Some((_, chains)) = self
                 .friendship_chains
@@ -8786,41 +11372,24 @@ impl PhotonApp {
                 .find(|(id, _)| *id == friendship_id)
             else {
                 crate::log("CHAT: friendship chains missing for open contact");
-                return false;
+                return Err(SendError::NoChain);
             };
-            let incorporated_hp = chains
-                .last_incorporated_hp()
-                .map(|h| *h)
-                .unwrap_or([0u8; 32]);
-            let mut values = vec![
-                vsf::VsfType::x(text.clone()),
-                vsf::VsfType::hp(incorporated_hp.to_vec()),
-            ];
-            // The braid: name each woven peer message by its eagle_time (e6). 0, 1, or 2 of these.
-            for &t in &woven_times {
-                values.push(vsf::VsfType::e(vsf::EtType::e6(t)));
-            }
-            // Short random pad (median ~53B) for traffic-analysis resistance.
-            let pad_len = rand::random::<u8>()
-                .min(rand::random::<u8>())
-                .min(rand::random::<u8>()) as usize;
-            if pad_len > 0 {
-                let pad: Vec<u8> = (0..pad_len).map(|_| rand::random()).collect();
-                values.push(vsf::VsfType::hR(pad));
-            }
-            use rand::seq::SliceRandom;
-            values.shuffle(&mut rand::thread_rng());
-            let payload = FieldValue::new("message", values).flatten();
-
-            // Chain ingredient = the bare x-text only (the hp/hR pad are siblings of x in the field, not part of it, and are never chain-key material). The full `payload` is what's encrypted onto the wire; `text` is what salts/advances the chain.
-            let salt_text = text.clone().into_bytes();
-
+            let mut extra = Vec::new();
+            if let Some(jpeg) = image.as_ref() {
+                extra.push(vsf::VsfType::v(b'j', jpeg.clone()));
+            }
+            if let Some(file) = attachment.as_ref() {
+                extra.extend(file.wire_values());
+            }
+            if let Some(memo) = voice.as_ref() {
+                extra.extend(memo.wire_values());
+            }
             let conv_token = chains.conversation_token;
-            match chains.prepare_send(&our_handle_hash, payload, salt_text, eagle_time, woven_strands) {
-                Some((ct, prev, _msg_hp, _ph)) => (ct, prev, conv_token),
+            match chains.seal_chat(&our_handle_hash, &text, woven, extra, eagle_time) {
+                Some((ct, prev)) => (ct, prev, conv_token),
                 None => {
                     crate::log("CHAT: prepare_send failed (not a participant)");
-                    return false;
+                    return Err(SendError::NotParticipant);
                 }
             }
         };
@@ -8856,7 +11425,10 @@ impl PhotonApp {
 
         // Append the outgoing bubble (delivered=false until the ACK lands) and persist — unless this is a suppressed send (the hidden chain-weave probe: it must ride the chain but show no UI).
         if !suppress_bubble && self.contacts.get(ci).is_some() {
-            let msg = ChatMessage::new_with_timestamp(text, true, eagle_time);
+            let mut msg = ChatMessage::new_with_timestamp(text, true, eagle_time);
+            msg.image = image;
+            msg.attachment = attachment;
+            msg.voice = voice;
             if let Some(contact) = self.contacts.get_mut(ci) {
                 contact.insert_message_sorted(msg.clone());
                 contact.message_scroll_offset = 0.0;
@@ -8869,7 +11441,7 @@ impl PhotonApp {
             // Live fleet propagation: our own outgoing message exists ONLY on this device until a sibling hears about it — push it now so the conversation follows the user across their devices.
             self.push_rows_to_siblings(ci, std::slice::from_ref(&msg), None);
         }
-        true
+        Ok(())
     }
 
     /// Just after a contact's CLUTCH reaches `Complete`, fire the one hidden chain-weave probe: a normal chat message with the reserved [`CHAIN_PROBE_MARKER`] content, sent once (guarded by `probe_sent`) with its UI bubble suppressed. When it lands the peer advances+ACKs the chain like any message, which is what proves the ratchet works end-to-end without the user seeing a decoy message. No-op if the contact isn't Complete, has no friendship chain yet, or already probed. Skips self-contacts (no peer to answer). Consolidates the transition-site logic so every `= ClutchState::Complete` path only needs one call.
@@ -8889,7 +11461,7 @@ impl PhotonApp {
         }
         crate::log("CHAIN-PROBE: sending hidden chain-weave probe");
         // Latch `probe_sent` only on an actual dispatch — if the contact had no address yet the send is a no-op and we retry on the next Complete transition / re-arm cycle rather than stalling.
-        if self.send_chain_message(contact_idx, crate::types::CHAIN_PROBE_MARKER, true) {
+        if self.send_chain_message(contact_idx, crate::types::CHAIN_PROBE_MARKER, None, None, None, true).is_ok() {
             if let Some(c) = self.contacts.get_mut(contact_idx) {
                 c.probe_sent = true;
             }
@@ -9012,18 +11584,43 @@ impl PhotonApp {
             hq.probe(handle);
             self.state = AppState::Launch(LaunchState::Attesting);
             self.change_focus(None);
+            self.attest_request = Some(AttestRequest::Probe);
+            self.attest_retry.reset();
         }
     }
 
     /// Fire an attest with caller-supplied roots (the probe already derived them), skipping the permanence interstitial and the second proof. First-attest persistence semantics.
     fn fire_attest_query_with_roots(&mut self, session: tohu::SessionIdentity) {
         if let Some(hq) = self.handle_query.as_ref() {
-            hq.query_first_attest_with_roots(session);
+            hq.query_first_attest_with_roots(session.clone());
             self.state = AppState::Launch(LaunchState::Attesting);
             self.change_focus(None);
+            self.attest_request = Some(AttestRequest::WithRoots(session));
+            self.attest_retry.reset();
         }
     }
 
+    /// Re-send the last launch-screen request after a transient failure (`LaunchState::Retrying` → `Attesting`). The retry budget is NOT reset — that's `on_query_result`'s call when this round comes back.
+    fn refire_attest(&mut self) {
+        let (Some(hq), Some(request)) = (self.handle_query.as_ref(), self.attest_request.clone()) else {
+            self.state = AppState::Launch(LaunchState::Fresh);
+            return;
+        };
+        crate::log("attest: retrying after a transient failure");
+        match request {
+            AttestRequest::Probe => {
+                let handle: String = self.textbox.as_ref().map(|tb| tb.chars.iter().collect()).unwrap_or_default();
+                if handle.is_empty() {
+                    self.state = AppState::Launch(LaunchState::Fresh);
+                    return;
+                }
+                hq.probe(handle);
+            }
+            AttestRequest::WithRoots(session) => hq.query_first_attest_with_roots(session),
+        }
+        self.state = AppState::Launch(LaunchState::Attesting);
+    }
+
     // (fire_attest_query — the string-based attest that BYPASSED the permanence interstitial — is deliberately gone: every launch-screen claim now flows probe → Confirm → roots-verified fire, so no path can attest a string the user didn't just confirm.)
 
     /// Handle a [`QueryResult`] arriving from HandleQuery's background worker. On success, stashes the proof, loads the device avatar + contacts, and transitions to the Ready screen; on rejection/error, drops to `LaunchState::Error` and refocuses the handle field.
@@ -9038,6 +11635,7 @@ impl PhotonApp {
                 if in_app {
                     return;
                 }
+                self.attest_retry.reset();
                 match outcome {
                     ProbeOutcome::Fresh => {
                         // Genuine fresh claim — NOW show the permanence warning, stashing the probed roots (and the canonical handle they belong to) so the confirm press claims without re-deriving the proof.
@@ -9070,9 +11668,17 @@ impl PhotonApp {
                 }
             }
             QueryResult::Success(data) => {
+                self.attest_retry.reset();
+                self.attest_request = None;
                 if let Some(hq) = self.handle_query.as_ref() {
                     hq.set_handle_proof(data.handle_proof);
                 }
+                // The launch box still holds what was just attested — keep it (memory only) for "copy my handle". Empty on a silent resume.
+                self.our_handle = self
+                    .textbox
+                    .as_ref()
+                    .map(|tb| crate::types::Handle::canonical(&tb.chars.iter().collect::<String>()))
+                    .filter(|h| !h.is_empty());
                 // Sync our own avatar with FGTW now that the handle_proof is set — newest-wins, so a copy this identity set on another device propagates here, and ours propagates out, without either clobbering a fresher one. (Was a blind one-way upload, which could overwrite a newer server copy with a stale local one.)
                 self.spawn_avatar_sync();
                 // Live fleet propagation: subscribe to hub events for this identity (idempotent across resumes/re-attests in one run).
@@ -9098,6 +11704,7 @@ impl PhotonApp {
                 if let Some(vsf_rgb) = &data.avatar_pixels {
                     self.device_avatar_pixels =
                         Some(crate::ui::colour_convert::vsf_rgb_to_bt2020(vsf_rgb));
+                    self.device_avatar_anim = None;
                     self.device_avatar_scaled = None;
                     self.device_avatar_scaled_diameter = 0;
                 }
@@ -9105,11 +11712,7 @@ impl PhotonApp {
                 if let Some(session) = &self.session {
                     if let Some(kp) = &self.device_keypair {
                         let device_secret = *kp.secret.as_bytes();
-                        match crate::storage::FlatStorage::open_shared(
-                            crate::storage::APP,
-                            session.vault_seed,
-                            device_secret,
-                        ) {
+                        match crate::storage::compact::open_shared(session.vault_seed, device_secret) {
                             Ok(s) => self.storage = Some(s),
                             Err(e) => {
                                 crate::logf!("STORAGE: init failed: {}", e);
@@ -9158,8 +11761,10 @@ impl PhotonApp {
                 }
                 // Merge the friendship chains the worker loaded from disk into the live map. Without this, resumed contacts have no chains in self.friendship_chains and sending fails with "friendship chains missing" — even though storage loaded them fine. Only add ids we don't already hold; an in-session chain (built at ceremony completion) is fresher than a disk copy, so never clobber it.
                 let mut merged_chains = 0usize;
-                for (fid, chains) in data.friendships {
+                let now_osc = vsf::eagle_time_oscillations();
+                for (fid, mut chains) in data.friendships {
                     if !self.friendship_chains.iter().any(|(id, _)| *id == fid) {
+                        chains.requeue_after_restart(now_osc);
                         self.friendship_chains.push((fid, chains));
                         merged_chains += 1;
                     }
@@ -9168,6 +11773,14 @@ impl PhotonApp {
                     crate::logf!("UI: merged {} friendship chain(s) from disk (total: {})", merged_chains, self.friendship_chains.len());
                     self.update_sync_records();
                 }
+                // The upload the last exit left owed: the cloud merge is in `contacts` now, so batching it here can't overwrite rows only the blob had.
+                if std::mem::take(&mut self.cloud_sync_owed) {
+                    let mut settings = crate::storage::settings::Settings::load_or_create();
+                    settings.cloud_sync_owed = false;
+                    settings.save();
+                    self.cloud_sync.touch(Instant::now());
+                    crate::log("Cloud: contact upload owed from the last exit — batched");
+                }
                 // Refresh existing contacts' WAN + LAN addresses from the FGTW peer list. FGTW reports both a public and a same-LAN address per device; pulling the LAN address in lets the offer/KEM send race the LAN path against the WAN path right away, instead of waiting for LAN multicast (which routers often drop) or a pong. This is what unblocks a same-router peer whose stored WAN IPv6 says "No route to host" — the case where m never received an offer. Retain the echo so a sibling contact created LATER (by the async fleet fold below) can be addressed from the same rows.
                 self.last_peers = data.peers.clone();
                 self.refresh_contact_addrs_from_peers(&data.peers);
@@ -9182,10 +11795,12 @@ impl PhotonApp {
                             crate::logf!("SIBLING: loaded {} sibling(s) from local vault on attest", siblings.len());
                             let fids: Vec<crate::types::FriendshipId> =
                                 siblings.iter().filter_map(|c| c.friendship_id).collect();
-                            for (fid, chains) in
+                            let now_osc = vsf::eagle_time_oscillations();
+                            for (fid, mut chains) in
                                 crate::storage::friendship::load_all_friendships(&fids, &storage)
                             {
                                 if !self.friendship_chains.iter().any(|(id, _)| *id == fid) {
+                                    chains.requeue_after_restart(now_osc);
                                     self.friendship_chains.push((fid, chains));
                                 }
                             }
@@ -9212,6 +11827,8 @@ impl PhotonApp {
                 // AlreadyAttested is now sent ONLY on a CHAIN-PROVEN takeover: the worker fold-verified a fleet chain whose genesis identity is not ours (handle_query.rs verdict). This is the genuine takeover case, so clearing the contested roots is correct — an indeterminate result (fold/parse/transport error) arrives as QueryResult::Error below, which does NOT clear the session. Clear so the next launch can't auto-resume into the same rejection, and bail to the attest screen (even from an optimistic Ready).
                 tohu::clear_session();
                 self.session = None;
+                self.our_handle = None;
+                self.text_undo = None; // drafts typed under this identity don't survive it
                 self.state = AppState::Launch(LaunchState::Error(msg));
                 self.refocus_handle_select_all();
             }
@@ -9227,8 +11844,20 @@ impl PhotonApp {
                     self.add_join_handle = None;
                     self.submit_join_step(None);
                 } else {
-                    self.state = AppState::Launch(LaunchState::Error(e));
-                    self.refocus_handle_select_all();
+                    // A transient failure re-fires the same request on a backoff (`LaunchState::Retrying`); a rejection, or an exhausted retry budget, surfaces the error for the user.
+                    use super::attest_retry::{Attempt, Step};
+                    let step = match self.attest_request {
+                        Some(_) => self.attest_retry.on_result(Attempt::Error(&e), Instant::now(), crate::jitter_dur),
+                        None => Step::Fail(e),
+                    };
+                    if let Step::Retry { attempt, of, .. } = &step {
+                        crate::logf!("attest: transient failure — retry {}/{} scheduled", attempt, of);
+                    }
+                    let failed = matches!(step, Step::Fail(_));
+                    self.state = step.state();
+                    if failed {
+                        self.refocus_handle_select_all();
+                    }
                 }
             }
         }
@@ -9297,6 +11926,7 @@ impl PhotonApp {
                 }
                 crate::logf!("search-result: added contact '{}' (total: {})", crate::fp(&contact.handle_proof).as_str(), self.contacts.len() + 1);
                 self.contacts.push(contact);
+                self.cloud_sync.touch(Instant::now());
                 // Register the new contact (and its fleet, once refreshed) so the checker answers pings/offers from any of its devices, and kick CLUTCH keypair generation so the contact becomes offer-ready when it comes online.
                 self.reseed_contact_pubkeys();
                 self.spawn_contact_fleet_refresh(vec![their_handle_proof]);
@@ -9523,6 +12153,10 @@ impl PhotonApp {
             }
             _ => return,
         };
+        // Removed fleet-wide: the empty wall slot is the goal state, not a gap for our copy to fill.
+        if self.avatar_removed_at().is_some() {
+            return;
+        }
         let secret = kp.secret.clone();
         let identity_seed = session.identity_seed;
         let tx = self.avatar_dl_tx.clone();
@@ -9596,7 +12230,343 @@ impl PhotonApp {
                     }
                 }
             }
-        }
+        }
+    }
+
+    /// Our own contact card — the pins a friend needs to verify or add us without a lookup. `None` before attestation (no session / device key yet).
+    fn our_contact_card(&self) -> Option<crate::types::ContactCard> {
+        let (session, kp) = (self.session.as_ref()?, self.device_keypair.as_ref()?);
+        Some(crate::types::ContactCard {
+            handle_proof: session.handle_proof,
+            party_id: crate::crypto::clutch::identity_party_id(&session.identity_seed),
+            device_pubkey: crate::types::DevicePubkey::from_bytes(*kp.public.as_bytes()),
+        })
+    }
+
+    /// Build (or keep) the Verify page's QR of our card. Re-encoded only when the identity changes.
+    fn ensure_verify_qr(&mut self) {
+        let Some(card) = self.our_contact_card() else {
+            self.verify_qr = None;
+            return;
+        };
+        if self.verify_qr.as_ref().is_some_and(|(pid, _)| *pid == card.party_id) {
+            return;
+        }
+        self.verify_qr = card
+            .to_text()
+            .and_then(|t| crate::ui::qr::qr_matrix(t.as_bytes()))
+            .map(|m| (card.party_id, m));
+    }
+
+    /// The no-lookup add: a contact built straight from a [`crate::types::ContactCard`]'s pins, then everything a search add does — ceremony claim, keygen, fleet refresh, persist, roster push. Returns the new contact's index. Callers have already ruled out self and an existing contact.
+    fn add_contact_from_card(&mut self, card: &crate::types::ContactCard, verified: bool) -> usize {
+        let mut contact = card.to_contact();
+        contact.verified = verified;
+        // §4.2 one-ceremony claim, as for a search add: the adding device owns this friendship's CLUTCH.
+        contact.ceremony_owner = self.device_keypair.as_ref().map(|kp| *kp.public.as_bytes());
+        contact.clutch_keygen_in_progress = true; // race guard, set BEFORE the spawn
+        let (contact_id, their_hh, their_hp) = (contact.id.clone(), contact.handle_hash, contact.handle_proof);
+        crate::logf!("CARD: added contact '{}' without a lookup (verified={}, total: {})", crate::fp(&their_hp).as_str(), verified, self.contacts.len() + 1);
+        self.contacts.push(contact);
+        self.cloud_sync.touch(Instant::now());
+        self.reseed_contact_pubkeys();
+        self.spawn_contact_fleet_refresh(vec![their_hp]);
+        let our_hh = self
+            .session
+            .as_ref()
+            .map(|s| crate::crypto::clutch::identity_party_id(&s.identity_seed))
+            .unwrap_or([0u8; 32]);
+        self.spawn_clutch_keygen(contact_id, our_hh, their_hh);
+        if let (Some(storage), Some(c)) = (self.storage.as_ref(), self.contacts.last()) {
+            if let Err(e) = crate::storage::contacts::save_contact(c, storage) {
+                crate::logf!("CARD: failed to save contact: {}", e);
+            }
+        }
+        self.spawn_roster_push();
+        self.contacts.len() - 1
+    }
+
+    /// A scanned verification QR: the card either confirms a contact we already hold (same party id AND a device their fleet trusts → verified), or names someone new, who is added already verified — an in-person scan IS the out-of-band check. Anything else is reported, never acted on.
+    fn apply_scanned_card(&mut self, text: &[u8]) {
+        let Some(card) = std::str::from_utf8(text).ok().and_then(crate::types::ContactCard::from_text) else {
+            crate::log("QR: scanned code is not a photon contact card");
+            self.ready_toast = Some("That code isn\u{2019}t a Photon contact card.".to_string());
+            return;
+        };
+        let our_pid = self.session.as_ref().map(|s| crate::crypto::clutch::identity_party_id(&s.identity_seed));
+        if our_pid.is_none() || our_pid == Some(card.party_id) {
+            self.ready_toast = Some("That\u{2019}s your own code.".to_string());
+            return;
+        }
+        match self.contacts.iter().position(|c| !c.is_sibling && c.handle_hash == card.party_id) {
+            Some(ci) if card.matches(&self.contacts[ci]) => {
+                let contact = &mut self.contacts[ci];
+                contact.verified = true;
+                crate::logf!("QR: scanned card verifies {}", crate::fp(&contact.handle_hash));
+                if let Some(storage) = self.storage.as_ref() {
+                    if let Err(e) = crate::storage::contacts::save_contact_state(contact, storage) {
+                        crate::logf!("STORAGE: Failed to save verification: {}", e);
+                    }
+                }
+                self.ready_toast = Some(format!("Verified {} \u{2713}", contact.display_name_or_pending()));
+            }
+            Some(ci) => {
+                // Right identity, unknown device: a fleet fold excluded it (or hasn't seen it yet). Don't verify on a device we don't trust for them.
+                crate::logf!("QR: card names {} but from device {} outside their folded fleet — not verified", crate::fp(&card.party_id), crate::fp(card.device_pubkey.as_bytes()));
+                self.ready_toast = Some(format!("\u{26a0} That code is from a device {} hasn\u{2019}t added \u{2014} not verified", self.contacts[ci].display_name_or_pending()));
+            }
+            None => {
+                self.add_contact_from_card(&card, true);
+                self.ready_toast = Some("Added and verified from their code.".to_string());
+            }
+        }
+    }
+
+    /// "Copy contact link": our card as a `photon://add/…` link on the clipboard. It carries pins only — never the handle — so it's safe to post anywhere; whoever opens it still has to compare safety numbers to verify.
+    fn copy_contact_link(&mut self) {
+        let Some(link) = self.our_contact_card().and_then(|c| c.to_link()) else {
+            self.ready_toast = Some("No identity yet \u{2014} attest first".to_string());
+            return;
+        };
+        let ok = self.copy_to_clipboard(&link);
+        crate::logf!("LINK: contact link → clipboard (ok = {})", ok);
+        self.ready_toast = Some(if ok { "Contact link copied" } else { "Couldn\u{2019}t reach the clipboard" }.to_string());
+    }
+
+    /// Ready's "copy my handle": our handle (when this run knows it) above our contact link, so a friend can either search for us or click straight thru.
+    fn copy_profile_share(&mut self) {
+        let Some(text) = self.our_contact_card().and_then(|c| c.share_text(self.our_handle.as_deref())) else {
+            self.ready_toast = Some("No identity yet \u{2014} attest first".to_string());
+            return;
+        };
+        let ok = self.copy_to_clipboard(&text);
+        crate::logf!("LINK: profile share → clipboard (handle included = {}, ok = {})", self.our_handle.is_some(), ok);
+        self.ready_toast = Some(match (ok, self.our_handle.is_some()) {
+            (false, _) => "Couldn\u{2019}t reach the clipboard",
+            (true, true) => "Handle + contact link copied",
+            (true, false) => "Contact link copied",
+        }.to_string());
+    }
+
+    /// A pasted or clicked `photon://add/…` link: offers to add the identity it names without an FGTW lookup. A link can arrive without the user doing anything here (any local process can launch the scheme handler), so it only arms [`Self::pending_link`] — the add happens when the user presses the prompt. Unlike a scanned QR the link rode an untrusted channel, so the new contact starts UNverified. Returns false when `text` isn't a contact link at all (the caller treats it as ordinary text).
+    fn apply_contact_link(&mut self, text: &str) -> bool {
+        let Some(card) = crate::types::ContactCard::from_link(text) else {
+            if text.trim_start().get(..9).is_some_and(|s| s.eq_ignore_ascii_case("photon://")) {
+                crate::log("LINK: photon link did not parse as a contact card");
+                self.ready_toast = Some("That link isn\u{2019}t a valid contact link".to_string());
+                return true;
+            }
+            return false;
+        };
+        let our_pid = self.session.as_ref().map(|s| crate::crypto::clutch::identity_party_id(&s.identity_seed));
+        if our_pid == Some(card.party_id) {
+            self.ready_toast = Some("That\u{2019}s your own link".to_string());
+        } else if self.contacts.iter().any(|c| !c.is_sibling && c.handle_hash == card.party_id) {
+            self.ready_toast = Some("Already in your contacts".to_string());
+        } else {
+            crate::logf!("LINK: contact link for {} awaiting confirmation", crate::fp(&card.party_id));
+            self.clear_toast();
+            self.pending_link = Some(card);
+        }
+        self.scene_dirty = true;
+        true
+    }
+
+    /// Enforce message retention on every friend conversation (siblings carry no conversation of their own). Un-ACKed outgoing messages always survive — see [`crate::types::Retention`]. The vault reads and deletes run on a worker — a sweep reads every conversation, far too much for a frame; [`Self::drain_retention_sweep`] brings memory in line when it's done.
+    fn spawn_retention_sweep(&mut self) {
+        let Some(storage) = self.storage.clone() else {
+            return;
+        };
+        let default = crate::storage::settings::Settings::load_or_create().retention();
+        let jobs: Vec<([u8; 32], crate::types::Retention)> = self
+            .contacts
+            .iter()
+            .filter(|c| !c.is_sibling)
+            .map(|c| (c.handle_hash, c.retention.unwrap_or(default)))
+            .filter(|(_, policy)| !policy.is_forever())
+            .collect();
+        if jobs.is_empty() {
+            return;
+        }
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.retention_rx = Some(rx);
+        let wake = self.event_proxy.clone();
+        std::thread::spawn(move || {
+            let now = vsf::eagle_time_oscillations();
+            let mut pruned = Vec::new();
+            for (party_id, policy) in jobs {
+                match crate::storage::contacts::prune_conversation(&party_id, policy, now, &storage) {
+                    Ok(doomed) if !doomed.is_empty() => pruned.push((party_id, doomed)),
+                    Ok(_) => {}
+                    Err(e) => crate::logf!("STORAGE: retention sweep failed for {}: {}", crate::fp(&party_id), e),
+                }
+            }
+            // The pruned rows' blocks stay in the vault file until it's compacted.
+            if !pruned.is_empty() {
+                crate::storage::compact::owe();
+            }
+            let _ = tx.send(pruned);
+            if let Some(w) = wake {
+                let _ = w.send(crate::ui::PhotonEvent::NetworkUpdate);
+            }
+        });
+    }
+
+    /// Apply a finished retention sweep to memory: drop each conversation's pruned messages and raise its history floor. Returns how many messages went; 0 while the sweep is still running.
+    fn drain_retention_sweep(&mut self) -> usize {
+        let pruned = match self.retention_rx.as_ref().map(|rx| rx.try_recv()) {
+            Some(Ok(pruned)) => pruned,
+            // The worker died without reporting: let the next interval try again.
+            Some(Err(std::sync::mpsc::TryRecvError::Disconnected)) => {
+                self.retention_rx = None;
+                return 0;
+            }
+            _ => return 0,
+        };
+        self.retention_rx = None;
+        let mut dropped = 0;
+        for (party_id, doomed) in pruned {
+            if let Some(contact) = self.contacts.iter_mut().find(|c| !c.is_sibling && c.handle_hash == party_id) {
+                crate::storage::contacts::drop_pruned(contact, &doomed);
+                dropped += doomed.len();
+            }
+        }
+        dropped
+    }
+
+    /// Manage page "Keep" pill: step the active conversation's retention thru [`RETENTION_PRESETS`] and persist it. The sweep is NOT forced — tapping past a tight preset on the way to another must not delete anything; the hourly sweep applies whatever the user settled on.
+    fn cycle_active_contact_retention(&mut self) {
+        let Some(ci) = self.active_contact.filter(|&ci| ci < self.contacts.len()) else {
+            return;
+        };
+        let contact = &mut self.contacts[ci];
+        let at = RETENTION_PRESETS.iter().position(|p| *p == contact.retention).unwrap_or(0);
+        contact.retention = RETENTION_PRESETS[(at + 1) % RETENTION_PRESETS.len()];
+        crate::logf!("RETENTION: {} → {}", crate::fp(&contact.handle_hash), retention_label(contact.retention));
+        if let Some(storage) = self.storage.as_ref() {
+            if let Err(e) = crate::storage::contacts::save_contact_state(contact, storage) {
+                crate::logf!("STORAGE: Failed to save retention: {}", e);
+            }
+        }
+    }
+
+    /// Manage page clear: zero and delete this device's copy of the active conversation (`storage::contacts::wipe_messages`), persist the raised history floor, and owe the compaction that takes the old blocks out of the vault file. `reset` also throws away the friendship's chains and re-runs the ceremony as the owning device, exactly like a fresh add — the peer takes it as a reset peer's re-key. Notes-to-self has no chain, so it only ever clears.
+    fn wipe_active_conversation(&mut self, reset: bool) {
+        let Some(ci) = self.active_contact.filter(|&ci| ci < self.contacts.len()) else {
+            return;
+        };
+        let Some(storage) = self.storage.clone() else {
+            return;
+        };
+        let our_hh = self.session.as_ref().map(|s| crate::crypto::clutch::identity_party_id(&s.identity_seed));
+        let reset = reset && !self.contacts[ci].is_sibling && Some(self.contacts[ci].handle_hash) != our_hh;
+        let contact = &mut self.contacts[ci];
+        match crate::storage::contacts::wipe_messages(contact, &storage, reset) {
+            Ok(n) => {
+                crate::logf!("WIPE: cleared {} row(s) of history with {}", n, crate::fp(&contact.handle_hash));
+                // The old rows' blocks are still in the vault file; the next launch compacts them away before opening it.
+                crate::storage::compact::owe();
+            }
+            Err(e) => {
+                crate::logf!("WIPE: clear failed: {}", e);
+                self.ready_toast = Some("Couldn\u{2019}t clear the history \u{2014} try again".to_string());
+                return;
+            }
+        }
+        if reset {
+            if let Some(fid) = contact.friendship_id.take() {
+                self.friendship_chains.retain(|(id, _)| *id != fid);
+            }
+            // The Complete-rekey teardown: round fields via discard_clutch_round, chain state here (discard deliberately leaves it).
+            contact.discard_clutch_round();
+            contact.chain_woven = false;
+            contact.ceremony_owner = self.device_keypair.as_ref().map(|kp| *kp.public.as_bytes());
+            contact.roster_updated = vsf::eagle_time_oscillations(); // the owner claim changed — let it win the roster merge
+            contact.clutch_keygen_in_progress = true; // race guard, set BEFORE the spawn
+        }
+        if let Err(e) = crate::storage::contacts::save_contact_state(contact, &storage) {
+            crate::logf!("STORAGE: Failed to save cleared contact: {}", e);
+        }
+        if reset {
+            let (contact_id, their_hh) = (contact.id.clone(), contact.handle_hash);
+            self.spawn_clutch_keygen(contact_id, our_hh.unwrap_or([0u8; 32]), their_hh);
+            self.spawn_roster_push();
+        }
+        self.ready_toast = Some(if reset { "History cleared \u{2014} re-securing the conversation" } else { "History cleared" }.to_string());
+    }
+
+    /// Flip the active contact's safety-number verification (the Verify page pill) and persist it at once. Friends only: self and fleet siblings share our own identity, so there is nothing to compare.
+    fn toggle_active_contact_verified(&mut self) {
+        let Some(contact) = self.active_contact.and_then(|ci| self.contacts.get_mut(ci)) else {
+            return;
+        };
+        let our_hh = self.session.as_ref().map(|s| crate::crypto::clutch::identity_party_id(&s.identity_seed));
+        if contact.is_sibling || our_hh == Some(contact.handle_hash) {
+            return;
+        }
+        contact.verified = !contact.verified;
+        crate::logf!("VERIFY: {} safety number marked {}", crate::fp(&contact.handle_hash), if contact.verified { "verified" } else { "unverified" });
+        if let Some(storage) = self.storage.as_ref() {
+            if let Err(e) = crate::storage::contacts::save_contact_state(contact, storage) {
+                crate::logf!("STORAGE: Failed to save verification: {}", e);
+            }
+        }
+    }
+
+    /// Manage page's mute pill: flip the active friend's `muted` and persist it. Notes-to-self and siblings never alert, so there's nothing to mute.
+    fn toggle_active_contact_muted(&mut self) {
+        let Some(contact) = self.active_contact.and_then(|ci| self.contacts.get_mut(ci)) else {
+            return;
+        };
+        let our_hh = self.session.as_ref().map(|s| crate::crypto::clutch::identity_party_id(&s.identity_seed));
+        if contact.is_sibling || our_hh == Some(contact.handle_hash) {
+            return;
+        }
+        contact.muted = !contact.muted;
+        crate::logf!("MUTE: {} {}", crate::fp(&contact.handle_hash), if contact.muted { "muted" } else { "unmuted" });
+        if let Some(storage) = self.storage.as_ref() {
+            if let Err(e) = crate::storage::contacts::save_contact(contact, storage) {
+                crate::logf!("STORAGE: Failed to save mute: {}", e);
+            }
+        }
+    }
+
+    /// Manage page's pin pill: flip the active contact's `pinned`, persist it, and batch a cloud contacts upload so the pin follows the identity to a fresh device.
+    fn toggle_active_contact_pinned(&mut self) {
+        let Some(contact) = self.active_contact.and_then(|ci| self.contacts.get_mut(ci)) else {
+            return;
+        };
+        if contact.is_sibling {
+            return;
+        }
+        contact.pinned = !contact.pinned;
+        contact.roster_updated = vsf::eagle_time_oscillations(); // the pin is a synced field — its edit must outrank the cloud's older row
+        crate::logf!("PIN: {} {}", crate::fp(&contact.handle_hash), if contact.pinned { "pinned" } else { "unpinned" });
+        if let Some(storage) = self.storage.as_ref() {
+            if let Err(e) = crate::storage::contacts::save_contact(contact, storage) {
+                crate::logf!("STORAGE: Failed to save pin: {}", e);
+            }
+        }
+        self.cloud_sync.touch(Instant::now());
+    }
+
+    /// The cloud blob's rows plus the keys to upload them: `None` before a session exists.
+    fn cloud_contacts_snapshot(&self) -> Option<(Vec<crate::storage::cloud::CloudContact>, [u8; 32], crate::network::fgtw::Keypair, [u8; 32])> {
+        let (session, kp) = (self.session.as_ref()?, self.device_keypair.clone()?);
+        let rows = self.contacts.iter().filter(|c| !c.is_sibling).map(Into::into).collect();
+        Some((rows, session.identity_seed, kp, session.handle_proof))
+    }
+
+    /// Push the contact list to the cloud blob off-thread — what a due `cloud_sync` batch does.
+    fn spawn_cloud_contacts_sync(&self) {
+        let Some((rows, seed, kp, hp)) = self.cloud_contacts_snapshot() else {
+            return;
+        };
+        std::thread::spawn(move || {
+            if let Err(e) = crate::storage::cloud::sync_cloud_contacts(&rows, &seed, &kp, &hp) {
+                crate::logf!("Cloud: contact sync failed: {}", e);
+            }
+        });
     }
 
     /// half of the avatar feature — the self avatar loads from the local vault; peers fetch by handle.
@@ -9637,6 +12607,7 @@ impl PhotonApp {
             // `owner: None` = our OWN avatar recovered from FGTW (the local vault was cleared). Install it as the device avatar and invalidate the scaled cache so the Ready screen repaints it.
             let Some(owner_hp) = result.owner else {
                 self.device_avatar_pixels = Some(display);
+                self.device_avatar_anim = None;
                 self.device_avatar_scaled = None;
                 self.device_avatar_scaled_diameter = 0;
                 crate::log("Avatar: recovered own avatar from FGTW after local clear");
@@ -9648,13 +12619,44 @@ impl PhotonApp {
                 .find(|c| !c.is_sibling && c.handle_proof == owner_hp)
             {
                 contact.avatar_pixels = Some(display);
-                contact.avatar_scaled = None; // force rebuild at the current diameter on next render
-                contact.avatar_scaled_diameter = 0;
+                contact.avatar_scaled.clear(); // force rebuild at the current diameters on next render
                 crate::logf!("Avatar: installed peer avatar for {}", crate::fp(&contact.handle_proof));
             }
         }
     }
 
+    /// Apply the address pushes the peer-update link delivered since the last tick — the same refresh a directory fetch runs, so a moved contact is re-punched at once.
+    #[cfg(not(target_os = "android"))]
+    fn drain_peer_updates(&mut self) {
+        let Some(client) = self.peer_updates.as_ref() else {
+            return;
+        };
+        let peers: Vec<crate::network::fgtw::PeerRecord> = std::iter::from_fn(|| client.try_recv()).filter_map(|u| u.to_peer_record()).collect();
+        if !peers.is_empty() {
+            self.refresh_contact_addrs_from_peers(&peers);
+        }
+    }
+
+    /// The peer-update link came up or went down. Logged on each change; and because FGTW doesn't replay pushes, coming back after an outage re-fetches the directory so an address that moved meanwhile isn't missed.
+    fn on_peer_update_link(&mut self, link: crate::network::peer_updates::LinkState) {
+        use crate::network::peer_updates::LinkState;
+        let was = self.peer_link.replace(link);
+        match link {
+            LinkState::Connected => {
+                crate::log("PEER-UPDATES: live address pushes connected");
+                if matches!(was, Some(LinkState::Reconnecting { .. })) {
+                    if let (Some(hq), Some(session)) = (self.handle_query.as_ref(), self.session.clone()) {
+                        hq.query_resume(session);
+                    }
+                }
+            }
+            LinkState::Reconnecting { attempt, retry_in } if !matches!(was, Some(LinkState::Reconnecting { .. })) => {
+                crate::logf!("PEER-UPDATES: link down — addresses fall back to directory fetches until it's back (retry {} in {}s)", attempt, retry_in.as_secs());
+            }
+            LinkState::Reconnecting { .. } => {}
+        }
+    }
+
     /// Drain the nunc-time clock verdict. A consensus offset beyond ±`CLOCK_OFF_THRESHOLD_SECS` raises the amber "clock off" banner (`clock_off`); within threshold clears it. An `Unavailable` result (we couldn't reach consensus) is NOT an anomaly — we leave the banner as-is rather than claiming the clock is fine. This is warn-only: the system clock is never corrected.
     fn drain_clock_check(&mut self) {
         /// How far off (seconds) the system clock must be before we warn. 30s — well past ordinary NTP jitter and nunc's own confidence half-width, so the banner means a real problem.
@@ -10519,7 +13521,7 @@ impl PhotonApp {
                     (None, _) => None,
                 };
                 if let Some(their_proof) = their_early_proof {
-                    if their_proof == result.eggs_proof {
+                    if crate::crypto::ct::eq(&their_proof, &result.eggs_proof) {
                         // SUCCESS! Both parties computed same eggs
                         crate::logf!("CLUTCH: Early proof verified with {}! ✓ proof={}...", contact_handle, hex::encode(&result.eggs_proof[..8]));
                         contact.clutch_state = ClutchState::Complete;
@@ -10907,6 +13909,7 @@ impl PhotonApp {
         }
         // Local removal, mirroring the tombstone-receive path, plus chain cleanup.
         let gone = self.contacts.remove(ci);
+        self.cloud_sync.touch(Instant::now());
         if let Some(storage) = self.storage.as_ref() {
             if let Err(e) = crate::storage::contacts::delete_contact(&gone.handle_hash, storage) {
                 crate::logf!("BOOT: contact state delete failed: {}", e);
@@ -10996,13 +13999,18 @@ impl PhotonApp {
         changed
     }
 
-    /// Current presence-sweep interval, chosen by how long since the user last interacted. Active (5s) while engaged → idle (1min) → deep-idle (15min). `now` is the tick's clock. Jittered to 50–100% of the tier so a roomful of devices doesn't ping their contacts in lockstep (a synchronised presence sweep is a self-inflicted DDoS). Presence timing is soft, so the fuzziness is free.
+    /// Current presence-sweep interval, chosen by how long since the user last interacted. Active (`ping_base`, 5s by default) while engaged → idle (1min) → deep-idle (15min). `now` is the tick's clock. Jittered to 50–100% of the tier so a roomful of devices doesn't ping their contacts in lockstep (a synchronised presence sweep is a self-inflicted DDoS). Presence timing is soft, so the fuzziness is free.
     fn presence_ping_interval(&self, now: Instant) -> std::time::Duration {
+        crate::jitter_dur(self.presence_ping_tier(now))
+    }
+
+    /// The un-jittered tier behind [`presence_ping_interval`](Self::presence_ping_interval) — also the base each offline contact's ping backoff doubles from.
+    fn presence_ping_tier(&self, now: Instant) -> std::time::Duration {
         let idle = self
             .last_interaction
             .map_or(std::time::Duration::ZERO, |last| now.duration_since(last));
         let mut tier = if idle < PRESENCE_IDLE_NEAR {
-            PRESENCE_PING_ACTIVE
+            self.ping_base
         } else if idle < PRESENCE_IDLE_FAR {
             PRESENCE_PING_IDLE
         } else {
@@ -11016,7 +14024,18 @@ impl PhotonApp {
         {
             tier = VALIDATED_PATH_KEEPALIVE;
         }
-        crate::jitter_dur(tier)
+        tier
+    }
+
+    /// Reconnect now: owe an FGTW re-announce, make every network sweep due (contacts re-ping on the next tick), bounce the relay pipe WebSocket (a half-open socket left over from a network change reads as alive until TCP gives up; a dead one skips its reconnect backoff) and re-probe the NAT. `why` is for the log.
+    pub fn force_reconnect(&mut self, why: &str) {
+        crate::logf!("NET: forced reconnect ({})", why);
+        self.sweeps.force();
+        if let Some(checker) = self.status_checker.as_ref() {
+            checker.reconnect_pipe();
+            checker.probe_nat();
+        }
+        self.scene_dirty = true;
     }
 
     /// Ping all contacts that have IP addresses (call periodically)
@@ -11090,8 +14109,15 @@ impl PhotonApp {
         let Some(checker) = self.status_checker.as_ref() else {
             return;
         };
+        let now = Instant::now();
+        let base = self.presence_ping_tier(now);
         let mut pinged = 0;
+        let our_lan_v4 = crate::network::udp::get_local_ip();
         for contact in &self.contacts {
+            // Long-offline contacts are pinged on a doubling, per-contact jittered backoff rather than every sweep (see PingBackoff). Keyed by the device we ping — fleet siblings all carry our own handle_proof, so keying on it would let one sibling's streak gate every other's.
+            if !self.sweeps.ping_backoff.due(contact.public_identity.as_bytes(), now) {
+                continue;
+            }
             // Ping the LAN address AND the public address (when both are known) rather than preferring LAN and never falling back. Two devices that once shared a LAN have a stored `local_ip`; the moment one moves to a different network (e.g. phone → cellular) that LAN address is stale and unreachable, but the public address in the registry is correct — pinging only LAN strands them offline forever. Each ping is tracked by a unique provenance hash and a single pong clears the whole per-contact failure counter (see status.rs StatusPong handler), so the unreachable address simply times out harmlessly while the reachable one keeps the contact online. On-LAN the LAN ping wins (no router hairpin / AP isolation); off-LAN the public ping wins.
             let lan_addr = match (contact.local_ip, contact.local_port) {
                 (Some(ip), Some(port)) => {
@@ -11101,10 +14127,10 @@ impl PhotonApp {
             };
             // Punch candidates, fired alongside the first ping (stale paths were cleared above):
             // - validated → keepalive: probe just the validated remote to keep its NAT mapping warm; its ack refreshes liveness so the path never expires while the contact stays reachable.
-            // - unvalidated → (re)punch: probe all the peer's addresses, best-first, so the first to round-trip wins.
+            // - unvalidated → (re)punch: probe all the peer's addresses, best-first, so the first to round-trip wins. A contact behind our own NAT (same public IPv4) has its LAN address first in line.
             let mut punch: Vec<std::net::SocketAddr> = match contact.validated_path {
                 Some((remote, _)) => vec![remote],
-                None => crate::network::traverse::gather::gather_peer_candidates(contact)
+                None => crate::network::traverse::gather::gather_peer_candidates_behind(contact, self.our_reflexive.map(|a| a.ip()), our_lan_v4)
                     .sorted()
                     .into_iter()
                     .map(|c| c.addr)
@@ -11176,6 +14202,7 @@ impl PhotonApp {
             }
             if sent {
                 pinged += 1;
+                self.sweeps.ping_backoff.pinged(contact.public_identity.as_bytes(), contact.is_online, base, PRESENCE_PING_DEEP, now);
             }
             // PER-DEVICE presence: every OTHER fleet device with a discovered endpoint gets its own ping at ITS OWN address(es), tracked by ITS pubkey — so each device answers for itself and the identity ring is "any device up". (The contact-level pings above already cover the active/first-met device; skip its endpoint to avoid a doubled ping.)
             for ep in &contact.device_endpoints {
@@ -11410,6 +14437,57 @@ impl PhotonApp {
         }
     }
 
+    /// Send what a `HeadlessProfile` queued (`storage::contacts::load_outbox`) thru the normal compose path, oldest first, the first tick a contact is CLUTCH-Complete with its chain loaded. Read once per contact per session; a failed send keeps it and everything after it (order matters to the chain braid) and retries after a pause instead of rereading the vault every tick.
+    fn drain_outboxes(&mut self) {
+        const RETRY: std::time::Duration = std::time::Duration::from_secs(30);
+        let Some(storage) = self.storage.clone() else {
+            return;
+        };
+        let now = Instant::now();
+        let ready: Vec<usize> = (0..self.contacts.len())
+            .filter(|&ci| {
+                let c = &self.contacts[ci];
+                let due = match self.outbox_drain.get(&c.handle_hash) {
+                    None => true,
+                    Some(None) => false,
+                    Some(Some(at)) => now >= *at,
+                };
+                due && c.clutch_state == crate::types::ClutchState::Complete
+                    && c.friendship_id.is_some_and(|fid| self.friendship_chains.iter().any(|(id, _)| *id == fid))
+            })
+            .collect();
+        for ci in ready {
+            let pid = self.contacts[ci].handle_hash;
+            let queued = match crate::storage::contacts::load_outbox(&pid, &storage) {
+                Ok(queued) => queued,
+                Err(e) => {
+                    crate::logf!("OUTBOX: can't read the outbox for {}: {}", crate::fp(&self.contacts[ci].handle_proof), e);
+                    self.outbox_drain.insert(pid, None);
+                    continue;
+                }
+            };
+            if queued.is_empty() {
+                self.outbox_drain.insert(pid, None);
+                continue;
+            }
+            let mut left = Vec::new();
+            for (queued_at, text) in queued {
+                if left.is_empty() {
+                    match self.send_chain_message(ci, &text, None, None, None, false) {
+                        Ok(()) => continue,
+                        Err(e) => crate::logf!("OUTBOX: send to {} failed ({}) — retrying in {}s", crate::fp(&self.contacts[ci].handle_proof), e, RETRY.as_secs()),
+                    }
+                }
+                left.push((queued_at, text));
+            }
+            if let Err(e) = crate::storage::contacts::save_outbox(&pid, &left, &storage) {
+                crate::logf!("OUTBOX: failed to save the outbox for {}: {}", crate::fp(&self.contacts[ci].handle_proof), e);
+            }
+            self.outbox_drain.insert(pid, (!left.is_empty()).then_some(now + RETRY));
+            self.scene_dirty = true;
+        }
+    }
+
     /// Reliability sweep (every tick): resend any unacked outgoing message whose backoff deadline has passed, with exponential backoff, until an ACK clears it or it exhausts its attempts. This is the per-message retry the protocol was missing — without it, a single dropped message OR a single dropped ACK desyncs the chain permanently (the sender advances on ACK, so a lost ACK freezes its chain while the receiver's has moved on → every later message decrypts as garbage). Resending is safe: the receiver dedupes by eagle_time and its ACK is deterministic, so a redelivered message just yields a free re-ACK. Uses the same LAN-preferring `race_addrs()` as the live send.
     fn retransmit_due_messages(&mut self) {
         let now_osc = vsf::eagle_time_oscillations();
@@ -11418,16 +14496,19 @@ impl PhotonApp {
         // OUR own LAN v4 (if any) decides whether a peer's private-v4 address is a same-subnet fast path or a
         // foreign black hole. Computed ONCE for the whole sweep — it's a syscall.
         let our_lan_v4 = crate::network::udp::get_local_ip();
+        let our_public = self.our_reflexive.map(|a| a.ip());
         let routes: Vec<(crate::types::FriendshipId, std::net::SocketAddr, Option<std::net::SocketAddr>, [u8; 32], Vec<[u8; 32]>)> = self
             .contacts
             .iter()
             .filter_map(|c| {
                 let fid = c.friendship_id?;
-                let (mut primary, mut alt) = c.race_addrs()?;
-                // Drop a FOREIGN peer LAN — a peer's private address on a subnet that isn't ours, which PT retransmits into a black hole forever. If the primary is foreign, promote a reachable alt into its place; if both are foreign, this route has NO direct target and survives ONLY on the relay fan-out below (relay_to). A same-subnet peer LAN is kept — that's a real fast path.
-                use crate::network::traverse::gather::is_foreign_peer_lan;
-                if is_foreign_peer_lan(&primary, our_lan_v4) {
-                    match alt.take().filter(|a| !is_foreign_peer_lan(a, our_lan_v4)) {
+                let (mut primary, mut alt) = c.race_addrs_behind(our_public, our_lan_v4)?;
+                // Drop a FOREIGN peer LAN — a peer's private address on a subnet that isn't ours, which PT retransmits into a black hole forever. If the primary is foreign, promote a reachable alt into its place; if both are foreign, this route has NO direct target and survives ONLY on the relay fan-out below (relay_to). A same-subnet peer LAN is kept — that's a real fast path. So is any peer LAN behind our own NAT (same public IPv4 AND their LAN beacon heard here, or a LAN address on our /24): the /24 guess is only a stand-in for "same site", and a beacon that crossed our LAN proves it (a /16 office LAN, a mesh with several subnets). The public IP alone proves nothing behind a carrier-grade NAT.
+                use crate::network::traverse::gather::{is_foreign_peer_lan, same_nat};
+                let behind_our_nat = same_nat(c, our_public, our_lan_v4);
+                let foreign = |a: &std::net::SocketAddr| !behind_our_nat && is_foreign_peer_lan(a, our_lan_v4);
+                if foreign(&primary) {
+                    match alt.take().filter(|a| !foreign(a)) {
                         Some(reachable) => primary = reachable,
                         None => {
                             // No reachable direct address at all. Keep the route only if the relay can carry it.
@@ -11440,7 +14521,7 @@ impl PhotonApp {
                             return Some((fid, crate::network::status::RELAY_ADDR, None, *c.public_identity.as_bytes(), relay_to));
                         }
                     }
-                } else if alt.map_or(false, |a| is_foreign_peer_lan(&a, our_lan_v4)) {
+                } else if alt.map_or(false, |a| foreign(&a)) {
                     alt = None; // primary reachable, but drop a foreign alt so PT doesn't race a black hole
                 }
                 // No direct path → carry the peer's relay device list so the retransmit also rides the pipe.
@@ -11457,6 +14538,7 @@ impl PhotonApp {
         };
 
         let mut undelivered_fids: Vec<crate::types::FriendshipId> = Vec::new();
+        let mut gave_up: Vec<(crate::types::FriendshipId, i64)> = Vec::new();
         for (fid, peer_addr, alt_addr, recipient_pubkey, relay_to) in routes {
             let Some((_, chains)) = self.friendship_chains.iter_mut().find(|(id, _)| *id == fid)
             else {
@@ -11480,6 +14562,7 @@ impl PhotonApp {
                 });
                 if exhausted {
                     crate::logf!("CHAT: retransmit GAVE UP on msg eagle_time {} after {} attempts (undelivered)", eagle_time, attempts);
+                    gave_up.push((fid, eagle_time));
                 } else {
                     crate::logf!("CHAT: retransmit msg eagle_time {} (attempt {})", eagle_time, attempts);
                 }
@@ -11489,6 +14572,15 @@ impl PhotonApp {
             }
         }
 
+        // Surface the give-ups: the bubble turns into "not delivered — tap to retry" (a late ACK still clears it).
+        for (fid, eagle_time) in gave_up {
+            if let Some(c) = self.contacts.iter_mut().find(|c| c.friendship_id == Some(fid)) {
+                if c.mark_failed(eagle_time) {
+                    self.scene_dirty = true;
+                }
+            }
+        }
+
         // The doorbell cascade (docs/reachability-doorbell.md): a due retransmit IS "I have something for this peer and direct isn't landing". If we also haven't heard ANY signed traffic from them past the dozed threshold, their process likely isn't scheduled — ring the bell once. Double-debounced: `last_ring` here, the per-target guard on the worker. Under-ringing is the design bias: a brief packet-loss blip on a live conversation never wakes anyone (their pongs/acks keep last_heard fresh).
         const DOZED_THRESHOLD: std::time::Duration = std::time::Duration::from_secs(90);
         const RE_RING_MIN: std::time::Duration = std::time::Duration::from_secs(300);
@@ -11516,9 +14608,7 @@ impl PhotonApp {
         let our_pid =
             crate::crypto::clutch::identity_party_id(&self.session.as_ref()?.identity_seed);
         self.contacts.iter().position(|c| {
-            !c.is_sibling
-                && crate::crypto::clutch::derive_conversation_token(&[our_pid, c.handle_hash])
-                    == *token
+            !c.is_sibling && crate::crypto::ct::eq(&crate::crypto::clutch::derive_conversation_token(&[our_pid, c.handle_hash]), token)
         })
     }
 
@@ -11984,7 +15074,7 @@ impl PhotonApp {
         if let Some(ip) = addr {
             let punch: Vec<std::net::SocketAddr> = match contact.validated_path {
                 Some((remote, _)) => vec![remote], // keepalive the validated path
-                None => crate::network::traverse::gather::gather_peer_candidates(contact)
+                None => crate::network::traverse::gather::gather_peer_candidates_behind(contact, self.our_reflexive.map(|a| a.ip()), crate::network::udp::get_local_ip())
                     .sorted()
                     .into_iter()
                     .map(|c| c.addr)
@@ -12009,18 +15099,45 @@ impl PhotonApp {
         // Peer avatars: install any completed downloads, then kick a fetch (once/session/handle) for any contact still without one. Cache-first + dedup'd by avatar_dl_started, so this is cheap to run every tick — it spawns at most one thread per peer per session.
         self.drain_avatar_downloads();
 
+        self.drain_dropped_files();
+        self.drain_voice_memo();
+        #[cfg(not(target_os = "android"))]
+        self.drain_peer_updates();
+
         // Our OWN just-picked avatar, arriving from the off-thread set pipeline (decode ran there too): install + repaint, then drop the channel — one avatar per pick.
         if let Some(rx) = self.avatar_set_rx.as_ref() {
-            if let Ok(px) = rx.try_recv() {
-                self.device_avatar_pixels = Some(px);
-                self.device_avatar_scaled = None;
-                self.device_avatar_scaled_diameter = 0;
+            if let Ok(anim) = rx.try_recv() {
+                self.install_device_avatar(anim);
                 self.scene_dirty = true;
                 self.avatar_set_rx = None;
                 crate::log("avatar picker: display pixels installed");
             }
         }
 
+        // A new avatar image decoded for framing: open the crop screen over Ready. Navigated away meanwhile → set it unframed, as before the crop screen.
+        if let Some(rx) = self.avatar_framing_rx.as_ref() {
+            if let Ok(decoded) = rx.try_recv() {
+                self.avatar_framing_rx = None;
+                match decoded {
+                    Ok(framing) if matches!(self.state, AppState::Ready) => {
+                        self.change_focus(None);
+                        self.avatar_framing = Some(framing);
+                        self.state = AppState::AvatarCrop;
+                        if let Some(chrome) = self.chrome.as_mut() {
+                            chrome.invalidate_bg();
+                            chrome.invalidate_chrome();
+                        }
+                    }
+                    Ok(framing) => self.set_avatar_from_file(framing.bytes, None),
+                    Err(e) => {
+                        crate::logf!("avatar picker: decode failed: {}", e);
+                        self.ready_toast = Some("That image couldn't be read \u{2014} try a JPEG, PNG or WebP".to_string());
+                    }
+                }
+                self.scene_dirty = true;
+            }
+        }
+
         // Clock sanity: drain any completed nunc verdict, then (if the wall clock has grossly jumped since the last baseline) spawn a fresh re-check. Both are cheap — the jump check is two clock reads and a subtraction; a re-check only spawns on an actual jump.
         self.drain_clock_check();
         // Surface any fleet-inbox alerts pulled since the last tick (bind attempts on our devices).
@@ -12174,6 +15291,8 @@ impl PhotonApp {
                     sync_records,
                     display_name,
                     avatar_pin,
+                    away,
+                    rtt,
                 } => {
                     // Stall recovery (runs EVERY ping that carries sync records, not just the offline→online edge): each record is the peer's contiguous tip (last_received_osc = "I have everything in order up to here"). Re-arm any pending message of ours that's newer than that tip AND has exhausted its retransmit attempts — so a gap-filler the sender already gave up on gets resent, and a receiver stuck behind a permanently-lost message un-sticks. collect_due_retransmits (the tick path) then actually sends the revived messages.
                     let now_osc = vsf::eagle_time_oscillations();
@@ -12181,7 +15300,7 @@ impl PhotonApp {
                         if let Some((_, chains)) = self
                             .friendship_chains
                             .iter_mut()
-                            .find(|(_, c)| c.conversation_token == record.conversation_token)
+                            .find(|(_, c)| crate::crypto::ct::eq(&c.conversation_token, &record.conversation_token))
                         {
                             let n = chains.rearm_pending_after(record.last_received_osc, now_osc);
                             if n > 0 {
@@ -12286,6 +15405,13 @@ impl PhotonApp {
                                     changed = true;
                                 }
                             }
+                            // Away presence off the pong (a chat clears it; pings and timeouts leave it). Offline drops it below — away only means anything on an online row.
+                            if let Some(a) = away {
+                                if contact.away != a {
+                                    contact.away = a;
+                                    changed = true;
+                                }
+                            }
                             // Per-device liveness: this pong/timeout is about the pinged DEVICE. The contact-level ring shows the IDENTITY reachable = any device online.
                             {
                                 let ep = contact.endpoint_mut(&peer_pubkey.key);
@@ -12296,12 +15422,25 @@ impl PhotonApp {
                             // Reachability clock: only the POSITIVE report counts (a TIMEOUT arrives thru this same arm with is_online=false — silence is exactly what the clock measures).
                             if is_online {
                                 contact.last_heard = Some(std::time::Instant::now());
+                                // Persisted "last seen" for the offline row — saved by the post-drain sweep.
+                                contact.update_last_seen(now_osc);
+                            }
+                            if let Some(d) = rtt {
+                                let before = contact.rtt.quality();
+                                contact.rtt.record(d);
+                                if contact.rtt.quality() != before {
+                                    changed = true;
+                                }
                             }
                             let identity_online = is_online || contact.any_device_online();
                             // True only on the offline→online EDGE, not every online ping/chat. Retransmit-of-pending (below) keys off this — without the edge gate it re-fired on every received chat (now that a chat marks the sender online), resending all pending messages in a storm.
                             let came_online = identity_online && !contact.is_online;
                             if contact.is_online != identity_online {
                                 contact.is_online = identity_online;
+                                if !identity_online {
+                                    contact.rtt.reset();
+                                    contact.away = false;
+                                }
                                 changed = true;
                                 crate::logf!("Status: {} is now {} (device {} {})", crate::fp(&contact.handle_proof), if identity_online { "ONLINE" } else { "offline" }, hex::encode(&peer_pubkey.key[..4]), if is_online { "up" } else { "down" });
                             }
@@ -12440,7 +15579,7 @@ impl PhotonApp {
                                         sync_records
                                             .iter()
                                             .find(|r| {
-                                                r.conversation_token == chains.conversation_token
+                                                crate::crypto::ct::eq(&r.conversation_token, &chains.conversation_token)
                                             })
                                             .map(|r| r.last_received_osc)
                                     } else {
@@ -12484,7 +15623,7 @@ impl PhotonApp {
                     let chains_result = self
                         .friendship_chains
                         .iter_mut()
-                        .find(|(_, c)| c.conversation_token == conversation_token);
+                        .find(|(_, c)| crate::crypto::ct::eq(&c.conversation_token, &conversation_token));
 
                     let mut need_sync_records_update = false;
                     // Contact index to seal the chain-weave for AFTER the `chains` borrow ends.
@@ -12599,15 +15738,15 @@ impl PhotonApp {
                             }
                         };
 
+                        // Every buffer from here holding plaintext or pad is Zeroizing — wiped when this iteration drops it, not left in freed memory (the same discipline as the CLUTCH keys).
                         // Get sender's last plaintext for salt derivation
-                        let their_last_plaintext =
-                            chains.last_plaintext(&from_handle_hash).to_vec();
+                        let their_last_plaintext = zeroize::Zeroizing::new(chains.last_plaintext(&from_handle_hash).to_vec());
 
                         // Derive salt from their previous plaintext
-                        let salt = derive_salt(&their_last_plaintext, &sender_chain);
+                        let salt = zeroize::Zeroizing::new(derive_salt(&their_last_plaintext, &sender_chain));
 
                         // Generate scratch pad
-                        let scratch = generate_scratch(&sender_chain, &salt);
+                        let scratch = zeroize::Zeroizing::new(generate_scratch(&sender_chain, &salt));
 
                         // Convert eagle time for decryption
                         let eagle_time = vsf::EagleTime::from_oscillations(timestamp);
@@ -12616,20 +15755,21 @@ impl PhotonApp {
                         crate::logf!("CHAIN DECRYPT: sender_handle_hash={}..., key={}..., salt={}..., eagle_time={}, ciphertext_len={}", hex::encode(&from_handle_hash[..4]), hex::encode(&sender_chain.current_key()[..4]), hex::encode(&salt[..4]), timestamp, ciphertext.len());
 
                         // Decrypt using sender's chain
-                        let plaintext = decrypt_layers(
+                        let plaintext = zeroize::Zeroizing::new(decrypt_layers(
                             &ciphertext,
                             &sender_chain,
                             CURRENT_KEY_INDEX,
                             &scratch,
                             &eagle_time,
-                        );
+                        ));
 
-                        // DEBUG: Log raw decrypted bytes
-                        crate::logf!("CHAIN DECRYPT: raw plaintext bytes = {}", format!("{:?}", &plaintext));
+                        // DEBUG: size only — the bytes themselves are the message and would outlive the wipe in the log
+                        crate::logf!("CHAIN DECRYPT: {} plaintext bytes", plaintext.len());
 
                         // Parse VSF field: (d{message}:x{text},hp{inc_hp},hR{pad}) Uses VsfField::parse() per AGENT.md
                         let mut ptr = 0usize;
                         let mut message_text = String::new();
+                        let mut image: Option<Vec<u8>> = None;
                         let mut incorporated_hp = [0u8; 32];
                         // The braid: eagle_times naming the prior peer (=our outgoing) messages this step weaves. 0, 1, or 2.
                         let mut woven_times: Vec<i64> = Vec::new();
@@ -12675,6 +15815,12 @@ impl PhotonApp {
                                     _ => {}
                                 },
                                 vsf::VsfType::hR(_) => {} // Random padding - ignore
+                                // Image message JPEG; an oversized one is dropped (the caption row still lands).
+                                vsf::VsfType::v(b'j', jpeg) if jpeg.len() <= crate::ui::chat_image::MAX_IMAGE_BYTES => image = Some(jpeg.clone()),
+                                // File attachment parts — checked + assembled together below.
+                                vsf::VsfType::d(_) | vsf::VsfType::u5(_) | vsf::VsfType::hb(_) | vsf::VsfType::v(b'f', _) => {}
+                                // Voice memo parts — likewise assembled below.
+                                vsf::VsfType::u6(_) | vsf::VsfType::v(b'w', _) | vsf::VsfType::v(b'o', _) => {}
                                 other => {
                                     crate::logf!("CHAT: Unexpected type in message: {}", format!("{:?}", other));
                                 }
@@ -12685,6 +15831,10 @@ impl PhotonApp {
                             crate::log("CHAT: No message text found in payload");
                             continue;
                         }
+                        // A file whose bytes don't match its declared size + hash is dropped; the caption row still lands.
+                        let attachment = crate::types::Attachment::from_wire(&field.values);
+                        // Likewise a memo missing its clip or past the length cap.
+                        let voice = crate::types::VoiceMemo::from_wire(&field.values);
 
                         // Hidden chain-weave probe: a reserved-marker message that proves the ratchet works but must show NO chat bubble. Everything else on the receive path (chain advance, set_last_plaintext, mark_received, ACK send) still runs so the sender's chain advances and dedup works — only the UI is suppressed.
                         let is_chain_probe = message_text == crate::types::CHAIN_PROBE_MARKER;
@@ -12770,6 +15920,9 @@ impl PhotonApp {
                             need_sync_records_update = true;
                         }
 
+                        // The notification's text as we'd render it: mentions in our names, not party ids.
+                        let notify_text = mention::shown(&message_text, |p| Self::mention_name(&self.contacts, p)).0.into_owned();
+
                         // Add message to contact's message list and persist — UNLESS this is the hidden chain-weave probe, which advances/ACKs the chain but must never surface a bubble or chime. For the probe we flip `their_probe_seen` (their TX / our RX proven), PERSIST a hidden row, and try to seal the chain.
                         if is_chain_probe {
                             if let Some(contact) = self.contacts.get_mut(contact_idx) {
@@ -12794,15 +15947,23 @@ impl PhotonApp {
                             // Any real received message means the chain is demonstrably working end-to-end in at least the RX direction — belt-and-suspenders toward woven.
                             contact.their_probe_seen = true;
                             // Use actual eagle_time and sorted insert for correct chronological order
-                            let msg = ChatMessage::new_with_timestamp(
+                            let mut msg = ChatMessage::new_with_timestamp(
                                 message_text,
                                 false,     // is_outgoing = false (received)
                                 timestamp, // Use message's actual eagle_time, not current time
                             )
                             // Persist the ACK hash so a later duplicate (our ACK was lost) can be re-ACKed from storage — keeps the sender's chain from stalling.
                             .with_ack_hash(plaintext_hash);
+                            msg.image = image;
+                            msg.attachment = attachment;
+                            msg.voice = voice;
+                            // Reading further back in this very conversation: the arrival must not yank the view away from what the user is reading — it counts as unread instead and the "new messages ↓" pill offers the jump.
+                            let reading_back = self.active_contact == Some(contact_idx)
+                                && message_select::away_from_latest(contact.message_scroll_offset, self.msg_newest_h);
                             contact.insert_message_sorted(msg.clone());
-                            contact.message_scroll_offset = 0.0; // Scroll to show new message
+                            if !reading_back {
+                                contact.message_scroll_offset = 0.0; // Scroll to show new message
+                            }
                             changed = true;
 
                             // Persist messages for UI
@@ -12816,13 +15977,26 @@ impl PhotonApp {
 
                             // Unread gate: is the user plausibly looking at THIS conversation right now? "Looking" = this contact's conversation (or its contact-scoped panel) is the active view AND, on desktop, the window is visible + focused. Event-shown, interaction-cleared doctrine: the counter only ever moves on a message landing or the user opening the conversation — no timers anywhere.
                             let conversation_open = matches!(self.state, AppState::Conversation | AppState::ContactPanel(_))
-                                && self.active_contact == Some(contact_idx);
+                                && self.active_contact == Some(contact_idx)
+                                && !reading_back;
                             #[cfg(not(target_os = "android"))]
                             let looking = conversation_open && crate::platform::desktop_notify::window_attended();
                             // Android v1: conversation-open alone — the Activity's foreground truth lives Kotlin-side (PhotonActivity.inForeground, which already suppresses the system notification); the unread gate adopts that signal if it ever grows a JNI mirror.
                             #[cfg(target_os = "android")]
                             let looking = conversation_open;
-                            if !contact.is_sibling && !looking {
+                            // What it sets off past storage (alerts::decide): unread when nobody's looking, notification + chime unless do-not-disturb holds them back.
+                            let arrival = alerts::decide(looking, contact.is_sibling, contact.muted, self.dnd.is_on());
+                            self.dnd.record(arrival);
+                            // A message from an archived conversation brings it back to the main list.
+                            if arrival.unarchive && contact.archived {
+                                contact.archived = false;
+                                if let Some(storage) = self.storage.as_ref() {
+                                    if let Err(e) = crate::storage::contacts::save_contact_state(contact, storage) {
+                                        crate::logf!("STORAGE: Failed to save unarchive: {}", e);
+                                    }
+                                }
+                            }
+                            if arrival.unread {
                                 // A real friend message landed while nobody was looking — bump the persistent unread counter (contacts-list inner ring + float-to-top; cleared at conversation-open).
                                 contact.unread_count += 1;
                                 if let Some(storage) = self.storage.as_ref() {
@@ -12833,12 +16007,12 @@ impl PhotonApp {
                             }
 
                             // System notification, POST-DECRYPT: real sender display name + message text BY DESIGN — hiding content on the lock screen is the OS's job, and the pre-decrypt RX worker no longer notifies at all (it over-dinged on probes and sibling fleet-sync frames it couldn't tell apart). Same friend-message gate as the chirp below; the notify fns themselves gate on window-hidden/unfocused (desktop) or Activity-foreground (Kotlin) and dedup on msg_hp, so an unconditional call here can't double-ding.
-                            if !contact.is_sibling {
+                            if arrival.notify {
                                 let sender_name = contact.display_name();
                                 #[cfg(target_os = "android")]
-                                crate::platform::jni_android::notify_new_message(&msg_hp, contact.public_identity.as_bytes(), &sender_name, &msg.content);
+                                crate::platform::jni_android::notify_new_message(&msg_hp, contact.public_identity.as_bytes(), &sender_name, &notify_text);
                                 #[cfg(not(target_os = "android"))]
-                                crate::platform::desktop_notify::notify_new_message(&msg_hp, &sender_name, &msg.content);
+                                crate::platform::desktop_notify::notify_new_message(&msg_hp, &sender_name, &notify_text);
                             }
 
                             // Live fleet propagation: the friend only delivered this to the device in hand — our other devices hear it from us (pushed after the `chains` borrow ends, below).
@@ -12847,7 +16021,7 @@ impl PhotonApp {
                             // Per-contact notification chime: the sender's relationship digest → deterministic modal bell (chirp crate) — the SAME digest that colours their handle and messages, so ears and eyes agree. The handle TEXT never touches the session store by design; the pre-PoW hashes are the canonical identity material. Synthesis (~a second of f64 modal math) + playback run on a detached thread so the receive loop never blocks; desktop-only (Android gets platform notifications).
                             // Only ding for a real human message from a friend: a chain-weave probe (hidden ceremony frame) and a sibling/fleet-sync frame (our own devices propagating a conversation) both arrive as ChatMessages, and neither is something a person sent us — so neither should ring. Interim gate ahead of the full unnotified-flag + focus-claim design; that lands with the sync-testing work.
                             #[cfg(not(any(target_os = "redox", target_os = "android")))]
-                            if !is_chain_probe && !self.contacts[contact_idx].is_sibling {
+                            if !is_chain_probe && arrival.notify {
                                 let digest = relationship_digest(&from_handle_hash, &our_handle_hash);
                                 std::thread::spawn(move || {
                                     chirp::Chirp::from_hash(digest).play_blocking().unwrap_or_else(|e| crate::logf!("CHIME: {}", e));
@@ -12921,7 +16095,7 @@ impl PhotonApp {
                     let chains_result = self
                         .friendship_chains
                         .iter_mut()
-                        .find(|(_, c)| c.conversation_token == conversation_token);
+                        .find(|(_, c)| crate::crypto::ct::eq(&c.conversation_token, &conversation_token));
 
                     // Contact index to seal AFTER the `chains` borrow ends (seal needs &mut self).
                     let mut ack_sealed_idx: Option<usize> = None;
@@ -13049,6 +16223,7 @@ impl PhotonApp {
                                     // Match by eagle_time (exact i64 match)
                                     if msg.timestamp == acked_eagle_time {
                                         msg.delivered = true;
+                                        msg.failed = false;
                                         delivered_row = Some(msg.clone());
                                         found_msg = true;
                                         changed = true;
@@ -13093,6 +16268,16 @@ impl PhotonApp {
                     // TODO: Track completion for full CLUTCH flow
                 }
 
+                // App datagrams are for an embedding application — photon itself sends none; hand them to its carrier (network::app_datagrams).
+                StatusUpdate::AppDatagramReceived { sender_pubkey, payload, sender_addr } => {
+                    crate::logf!("PT: App datagram ({} bytes) from {} at {}", payload.len(), crate::fp(&sender_pubkey), sender_addr);
+                    crate::network::app_datagrams::on_received(crate::network::pt::AppDatagram { from: sender_pubkey, peer_addr: sender_addr, payload });
+                }
+                StatusUpdate::AppDatagramDelivered { id } => {
+                    crate::logf!("PT: App datagram {} delivered", crate::fp(&id));
+                    crate::network::app_datagrams::on_delivered(id);
+                }
+
                 // Full CLUTCH offer received (~548KB with all 8 pubkeys) Payload is already parsed and signature verified by status.rs
                 StatusUpdate::ClutchOfferReceived {
                     conversation_token,
@@ -13135,8 +16320,7 @@ impl PhotonApp {
                             } else {
                                 our_handle_hash
                             };
-                            (derive_conversation_token(&[our, c.handle_hash])
-                                == conversation_token)
+                            crate::crypto::ct::eq(&derive_conversation_token(&[our, c.handle_hash]), &conversation_token)
                                 .then_some((c.handle_hash, our))
                         }) {
                         Some(pair) => pair,
@@ -13727,8 +16911,7 @@ impl PhotonApp {
                             } else {
                                 our_handle_hash
                             };
-                            (derive_conversation_token(&[our, c.handle_hash])
-                                == conversation_token)
+                            crate::crypto::ct::eq(&derive_conversation_token(&[our, c.handle_hash]), &conversation_token)
                                 .then_some((c.handle_hash, our))
                         }) {
                         Some(pair) => pair,
@@ -13930,8 +17113,7 @@ impl PhotonApp {
                             } else {
                                 our_handle_hash
                             };
-                            (derive_conversation_token(&[our, c.handle_hash])
-                                == conversation_token)
+                            crate::crypto::ct::eq(&derive_conversation_token(&[our, c.handle_hash]), &conversation_token)
                                 .then_some((c.handle_hash, our))
                         }) {
                         Some(pair) => pair,
@@ -14005,7 +17187,7 @@ impl PhotonApp {
                                 ClutchState::AwaitingProof => {
                                     // We have our proof - verify theirs matches
                                     if let Some(our_proof) = contact.clutch_our_eggs_proof {
-                                        if payload.eggs_proof == our_proof {
+                                        if crate::crypto::ct::eq(&payload.eggs_proof, &our_proof) {
                                             // SUCCESS! Both parties computed same eggs
                                             crate::logf!("CLUTCH: Proof verified with {}! ✓ proof={}...", crate::fp(&contact.handle_proof), hex::encode(&our_proof[..8]));
                                             contact.clutch_state = ClutchState::Complete;
@@ -14106,19 +17288,23 @@ impl PhotonApp {
                 }
 
                 // LAN peer discovered via broadcast (NAT hairpinning workaround)
-                StatusUpdate::LanPeerDiscovered {
-                    handle_proof,
-                    local_ip,
-                    port,
-                } => {
-                    // Find contact by handle_proof and store their LAN IP + port. Siblings AND the self-contact are skipped — an own-hp broadcast carries only (hp, port) with no device disambiguation, so it can't say WHICH of our devices it came from; sibling addresses flow via FGTW peer rows + pong source addresses instead.
+                StatusUpdate::LanPeerDiscovered { id, local_ip, port } => {
+                    // Open the blinded beacon against each contact's handle_proof + known fleet; a match names the device, and nobody but a contact can make one. Siblings AND the self-contact are skipped — that's also what discards our own looped-back beacon — and sibling addresses flow via FGTW peer rows + pong source addresses instead.
                     for (idx, contact) in self.contacts.iter_mut().enumerate() {
-                        if !contact.is_sibling
-                            && contact.handle_hash != our_handle_hash
-                            && contact.handle_proof == handle_proof
-                        {
+                        if contact.is_sibling || contact.handle_hash == our_handle_hash {
+                            continue;
+                        }
+                        if let Some(device) = id.sender(&contact.handle_proof, &contact.answerable_pubkeys()) {
+                            // Directly reachable on our LAN — record it in the phonebook too, so a handle lookup finds the device with FGTW out of the picture.
+                            if let Some(store) = self.peer_store.as_ref() {
+                                let addr = std::net::SocketAddr::new(std::net::IpAddr::V4(local_ip), port);
+                                if store.lock().unwrap().note_lan_peer(contact.handle_proof, crate::types::DevicePubkey::from_bytes(device), addr) {
+                                    crate::logf!("LAN: {} device {} added to peer store at {}", crate::fp(&contact.handle_proof), hex::encode(&device[..4]), addr);
+                                }
+                            }
                             let old_local = contact.local_ip;
                             let old_port = contact.local_port;
+                            contact.lan_beacon_heard = true;
                             contact.local_ip = Some(local_ip);
                             contact.local_port = Some(port);
                             if old_local != Some(local_ip) || old_port != Some(port) {
@@ -14216,8 +17402,7 @@ impl PhotonApp {
                                         crate::ui::colour_convert::vsf_rgb_to_bt2020(&vsf_rgb);
                                     let contact = &mut self.contacts[idx];
                                     contact.avatar_pixels = Some(display);
-                                    contact.avatar_scaled = None;
-                                    contact.avatar_scaled_diameter = 0;
+                                    contact.avatar_scaled.clear();
                                     changed = true;
                                     crate::log("Avatar: installed mutual peer's avatar (P2P)");
                                 }
@@ -14265,7 +17450,7 @@ impl PhotonApp {
                         let key_and_other = self
                             .friendship_chains
                             .iter()
-                            .find(|(_, c)| c.conversation_token == conversation_token)
+                            .find(|(_, c)| crate::crypto::ct::eq(&c.conversation_token, &conversation_token))
                             .and_then(|(_, c)| {
                                 let key = c.history_key().copied()?;
                                 let other = c
@@ -14413,7 +17598,7 @@ impl PhotonApp {
                         let our_pid = crate::crypto::clutch::sibling_party_id(kp.public.as_bytes());
                         crate::crypto::clutch::derive_conversation_token(&[our_pid, self.contacts[idx].handle_hash])
                     });
-                    if expected_token != Some(conversation_token) {
+                    if !expected_token.is_some_and(|t| crate::crypto::ct::eq(&t, &conversation_token)) {
                         crate::log("CHAIN-RESET: token mismatch — dropped");
                         continue;
                     }
@@ -14450,7 +17635,7 @@ impl PhotonApp {
                         let key_and_other = self
                             .friendship_chains
                             .iter()
-                            .find(|(_, c)| c.conversation_token == conversation_token)
+                            .find(|(_, c)| crate::crypto::ct::eq(&c.conversation_token, &conversation_token))
                             .and_then(|(_, c)| {
                                 let key = c.history_key().copied()?;
                                 let other = c
@@ -14486,6 +17671,10 @@ impl PhotonApp {
                                         if row.content == crate::types::CHAIN_PROBE_MARKER {
                                             continue;
                                         }
+                                        // Below the history floor = cleared or pruned here on purpose; a friend or sibling still holding it mustn't undo that.
+                                        if row.timestamp <= contact.history_floor {
+                                            continue;
+                                        }
                                         let (is_outgoing, delivered, recovered) = if from_sibling {
                                             (row.sender_outgoing, row.delivered, false)
                                         } else {
@@ -14513,6 +17702,10 @@ impl PhotonApp {
                                             delivered,
                                             ack_hash: None,
                                             recovered,
+                                            failed: false,
+                                            image: None,
+                                            attachment: None,
+                                            voice: None,
                                         };
                                         contact.insert_message_sorted(msg.clone());
                                         fresh.push(msg);
@@ -14609,10 +17802,10 @@ impl PhotonApp {
                                 };
                                 c.is_mutual()
                                     && c.knows_device(&sender_pubkey.key)
-                                    && crate::crypto::clutch::derive_conversation_token(&[
-                                        our,
-                                        c.handle_hash,
-                                    ]) == conversation_token
+                                    && crate::crypto::ct::eq(
+                                        &crate::crypto::clutch::derive_conversation_token(&[our, c.handle_hash]),
+                                        &conversation_token,
+                                    )
                             });
                             let Some(idx) = cidx else {
                                 crate::log("BLIND: put/get REJECTED (unknown token or unauthorized device)");
@@ -14925,7 +18118,32 @@ impl PhotonApp {
                     if self.our_reflexive != Some(addr) {
                         self.our_reflexive = Some(addr);
                         crate::logf!("TRAVERSE: our reflexive address = {}", addr);
+                        if let Some(checker) = self.status_checker.as_ref() {
+                            checker.probe_nat();
+                        }
+                    }
+                }
+
+                StatusUpdate::NatClassified { nat } => {
+                    // Diagnosis for the "everything goes via relay" case: only a changed verdict is news, and only Symmetric is worth a toast.
+                    if self.nat_type != Some(nat) {
+                        self.nat_type = Some(nat);
+                        if let Some(warning) = nat.warning() {
+                            self.ready_toast = Some(format!("\u{26a0} {warning}"));
+                        }
+                    }
+                }
+
+                StatusUpdate::TransferHeld { peer_addr, stream_id, total_size } => {
+                    // Over the `auto_download_mib` limit: queue it for the hint-slot prompt. Entries the checker has already expired go first, and the queue never outgrows the checker's.
+                    use crate::network::pt::PTManager;
+                    crate::logf!("PT: {}-byte transfer from {} (stream '{}') held for acceptance", total_size, peer_addr, stream_id as char);
+                    self.held_transfers.retain(|(.., at)| at.elapsed() <= PTManager::HOLD_TIMEOUT);
+                    if self.held_transfers.len() >= PTManager::MAX_PENDING_SPECS {
+                        self.held_transfers.remove(0);
                     }
+                    self.held_transfers.push((peer_addr, stream_id, total_size, std::time::Instant::now()));
+                    self.scene_dirty = true;
                 }
 
                 StatusUpdate::PathValidated { peer_pubkey, remote } => {
@@ -15025,7 +18243,8 @@ impl PhotonApp {
         // Retransmit pending messages to contacts that just came online Use last_received_ef6 from pong to only retransmit messages they don't have
         for (fid, peer_addr, alt_addr, handle, recipient_pubkey, last_received_ef6) in retransmit_requests {
             if let Some((_, chains)) = self.friendship_chains.iter().find(|(id, _)| *id == fid) {
-                let pending = chains.pending_messages();
+                // Chain order, not insertion order: the receiver's verify_chain_link needs each predecessor before its successor.
+                let pending = chains.pending_in_chain_order();
                 if !pending.is_empty() {
                     // Filter to only messages newer than what peer has received
                     let to_retransmit: Vec<_> = pending
@@ -15076,6 +18295,9 @@ impl PhotonApp {
         // Reliability: per-message retransmit with exponential backoff. The came-online loop above only fires on the offline→online EDGE, so a message (or its ACK) dropped while the peer was already online would otherwise never be resent — the exact desync seen live (msg 1 ACKed, msg 2 garbage because the sender's chain never advanced on a lost ACK). This sweep runs every tick and resends any unacked pending whose backoff deadline has passed, until an ACK clears it or it exhausts its attempts.
         self.retransmit_due_messages();
 
+        // Messages a headless run queued while the app was closed go out the first tick each contact's chain is ready.
+        self.drain_outboxes();
+
         // History recovery: fire the next backfill page request for any contact mid-recovery (newest-first cursor; urgent jumps the trickle interval; in-flight expiry re-requests lost pages).
         self.drive_history_recovery();
 
@@ -15096,13 +18318,14 @@ impl PhotonApp {
             self.update_sync_records();
         }
 
-        // Persist any published-name adoptions from this drain (deferred: saving inside the loop would fight the contacts borrow). Only the per-contact STATE entry carries published_name — the index row's name is the petname.
-        if self.contacts.iter().any(|c| c.published_name_dirty) {
+        // Persist any published-name adoptions and last-seen moves from this drain (deferred: saving inside the loop would fight the contacts borrow). Only the per-contact STATE entry carries published_name / last_seen — the index row's name is the petname.
+        if self.contacts.iter().any(|c| c.published_name_dirty || c.last_seen_dirty) {
             if let Some(storage) = self.storage.as_ref() {
-                for contact in self.contacts.iter_mut().filter(|c| c.published_name_dirty) {
+                for contact in self.contacts.iter_mut().filter(|c| c.published_name_dirty || c.last_seen_dirty) {
                     contact.published_name_dirty = false;
+                    contact.last_seen_dirty = false;
                     if let Err(e) = crate::storage::contacts::save_contact_state(contact, storage) {
-                        crate::logf!("CONTACT: published-name persist failed: {}", e);
+                        crate::logf!("CONTACT: state persist failed: {}", e);
                     }
                 }
             }
@@ -15158,6 +18381,9 @@ impl PhotonApp {
             self.message_textbox
                 .as_mut()
                 .map(|t| (TextboxRole::MessageCompose, t)),
+            self.find_textbox
+                .as_mut()
+                .map(|t| (TextboxRole::MessageFind, t)),
             self.settings_note_textbox
                 .as_mut()
                 .map(|t| (TextboxRole::SettingsNote, t)),
@@ -15232,6 +18458,145 @@ impl PhotonApp {
             .map(|(_, t)| t)
     }
 
+    /// Text + caret of textbox `id` for its undo history. `None` when `id` isn't a textbox.
+    fn textbox_snapshot(&mut self, id: HitId) -> Option<text_undo::Snapshot> {
+        let tb = self.textbox_by_hit_mut(id)?;
+        Some(text_undo::Snapshot { chars: tb.chars.clone(), cursor: tb.cursor })
+    }
+
+    /// After an edit path ran on textbox `id` (`before` = its state going in): a changed text is recorded as one undo step of `kind`; an unchanged one (a caret move, a copy) only ends the typing run. A box other than the one the history belongs to starts a fresh history. The launch handle field keeps none: the handle derives the identity, so no earlier spelling of it may linger in memory to be undone back into view.
+    fn record_textbox_edit(&mut self, id: HitId, before: Option<text_undo::Snapshot>, kind: text_undo::EditKind) {
+        if self.textbox.as_ref().is_some_and(|t| t.hit_id() == id) {
+            self.text_undo = None;
+            return;
+        }
+        let Some(before) = before else {
+            return;
+        };
+        let changed = self.textbox_by_hit_mut(id).is_some_and(|tb| tb.chars != before.chars);
+        if self.text_undo.as_ref().map(|(owner, _)| *owner) != Some(id) {
+            if !changed {
+                return;
+            }
+            self.text_undo = Some((id, text_undo::UndoStack::default()));
+        }
+        if let Some((_, stack)) = self.text_undo.as_mut() {
+            if changed {
+                stack.record(before, kind);
+            } else {
+                stack.break_run();
+            }
+        }
+    }
+
+    /// Ctrl+Z (`redo` false) / Ctrl+Y, Ctrl+Shift+Z (`redo` true) on textbox `id`: swap its text and caret for the neighbouring snapshot. Returns true if the box changed. Never the launch handle field — it records no history (see [`Self::record_textbox_edit`]).
+    fn step_textbox_history(&mut self, id: HitId, redo: bool, text: &mut fluor::text::TextRenderer) -> bool {
+        let Some(current) = self.textbox_snapshot(id) else {
+            return false;
+        };
+        let Some((_, stack)) = self.text_undo.as_mut().filter(|(owner, _)| *owner == id) else {
+            return false;
+        };
+        let Some(target) = (if redo { stack.redo(current) } else { stack.undo(current) }) else {
+            return false;
+        };
+        let Some(tb) = self.textbox_by_hit_mut(id) else {
+            return false;
+        };
+        tb.clear();
+        tb.insert_str(&target.chars.iter().collect::<String>(), text);
+        // insert_str leaves the caret after the inserted text — put it back where the snapshot had it.
+        tb.cursor = target.cursor.min(tb.chars.len());
+        true
+    }
+
+    /// Mention candidates: every listed contact (fleet siblings aren't conversations) as (index, the name it renders as).
+    fn mention_names(&self) -> Vec<(usize, String)> {
+        self.contacts
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !c.is_sibling)
+            .map(|(i, c)| (i, c.display_name()))
+            .collect()
+    }
+
+    /// What a sent `@name` can resolve to: the same contacts as [`Self::mention_names`], keyed by party id.
+    fn mention_parties(&self) -> Vec<([u8; 32], String)> {
+        self.contacts.iter().filter(|c| !c.is_sibling).map(|c| (c.handle_hash, c.display_name())).collect()
+    }
+
+    /// The contact (index, our name for them) a mention's party id names, for [`mention::shown`].
+    fn mention_name(contacts: &[crate::types::Contact], party: &[u8; 32]) -> Option<(usize, String)> {
+        contacts.iter().position(|c| !c.is_sibling && c.handle_hash == *party).map(|i| (i, contacts[i].display_name()))
+    }
+
+    /// Contact `ci`'s history rows as the reader sees them ([`mention::shown`]), probe rows left out so the indices line up with the drawn rows — what selection, find and copy work on.
+    fn shown_history(&self, ci: usize) -> Vec<std::borrow::Cow<'_, str>> {
+        let Some(contact) = self.contacts.get(ci) else {
+            return Vec::new();
+        };
+        contact
+            .messages
+            .iter()
+            .filter(|m| m.content != crate::types::CHAIN_PROBE_MARKER)
+            .map(|m| mention::shown(&m.content, |p| Self::mention_name(&self.contacts, p)).0)
+            .collect()
+    }
+
+    /// Complete the open mention with contact `ci`'s name: the `@query` span becomes `@name `, caret after it, as one undo step.
+    fn complete_mention(&mut self, ci: usize, text: &mut fluor::text::TextRenderer) {
+        let (Some(trigger), Some(name)) = (self.mention.trigger.clone(), self.contacts.get(ci).map(|c| c.display_name())) else {
+            return;
+        };
+        let Some(id) = self.message_textbox.as_ref().map(|t| t.hit_id()) else {
+            return;
+        };
+        let before = self.textbox_snapshot(id);
+        let Some(tb) = self.message_textbox.as_mut() else {
+            return;
+        };
+        let (chars, caret) = mention::complete(&tb.chars, tb.cursor, &trigger, &name);
+        tb.clear();
+        tb.insert_str(&chars.iter().collect::<String>(), text);
+        tb.cursor = caret.min(tb.chars.len());
+        self.record_textbox_edit(id, before, text_undo::EditKind::Other);
+        self.mention = mention::Picker::default();
+    }
+
+    /// Ctrl+←/→/Backspace/Delete on textbox `id` (see [`text_words`]): move the caret to the neighbouring word boundary, or delete up to it — a live selection is deleted instead, like a plain Backspace. A deletion is one undo step and, on the launch handle, an edit that tears down a stale Error/Confirm. Returns true if anything moved.
+    fn textbox_word_key(&mut self, id: HitId, key: NamedKey, text: &mut fluor::text::TextRenderer) -> bool {
+        let before = self.textbox_snapshot(id);
+        let Some(tb) = self.textbox_by_hit_mut(id) else {
+            return false;
+        };
+        let at = tb.cursor.min(tb.chars.len());
+        match key {
+            NamedKey::ArrowLeft => tb.cursor = text_words::prev_boundary(&tb.chars, at),
+            NamedKey::ArrowRight => tb.cursor = text_words::next_boundary(&tb.chars, at),
+            NamedKey::Backspace | NamedKey::Delete if tb.selected_text().is_some() => tb.delete_selection(text),
+            NamedKey::Backspace | NamedKey::Delete => {
+                let (from, to) = if key == NamedKey::Backspace {
+                    (text_words::prev_boundary(&tb.chars, at), at)
+                } else {
+                    (at, text_words::next_boundary(&tb.chars, at))
+                };
+                // The widget deletes one char back at a time: park the caret at the span's end and backspace across it.
+                tb.cursor = to;
+                for _ in from..to {
+                    tb.backspace(text);
+                }
+            }
+            _ => return false,
+        }
+        let moved = before.as_ref().map(|b| (b.chars.as_slice(), b.cursor)) != Some((tb.chars.as_slice(), tb.cursor));
+        let edited = before.as_ref().is_some_and(|b| b.chars != tb.chars);
+        self.record_textbox_edit(id, before, text_undo::EditKind::Other);
+        if edited && self.textbox.as_ref().is_some_and(|t| t.hit_id() == id) {
+            self.clear_launch_error();
+        }
+        moved
+    }
+
     /// Pointer press over hit `id`: if it's a textbox, focus it, place the caret under the pointer, and grab the text for the pan (drag = the text follows the finger — see `drag_pan_text`). Multi-tap streak: double → select word, triple → select paragraph (the whole single-line box). The tap interval comes from the OS (Android's ViewConfiguration double-tap timeout via JNI, X11 XSettings on Linux, 400 ms default). Returns true if a textbox was engaged (caller consumes the press so it can't start a window drag). Works for ANY textbox on ANY screen — the uniform pointer model, every platform.
     fn textbox_press(&mut self, id: HitId, x: Coord) -> bool {
         // A busy-frozen box (`!is_enabled`) takes no pointer input — treat it as "not a textbox" so the press falls through to the normal consume, matching the pre-rework behaviour.
@@ -15327,7 +18692,10 @@ impl PhotonApp {
             }
         };
         #[cfg(not(target_os = "android"))]
-        {
+        if let Some(root) = crate::storage::data_dir_override() {
+            // Overridden root: everything lives there, and the system dirs belong to some other instance.
+            wipe_dir(Some(root), &mut count);
+        } else {
             wipe_dir(dirs::config_dir(), &mut count);
             wipe_dir(dirs::data_dir(), &mut count);
         }
@@ -15344,24 +18712,29 @@ impl PhotonApp {
         count
     }
 
-    /// Fully clean this device for a new owner / a fresh identity: nuke the on-disk vault (all `.vsf` — contacts, chains, keypairs, the cached fleet key) AND clear the tohu session (identity_seed + vault_seed + handle_proof), drop all in-memory state, and drop back to the attest screen. The device KEY is fingerprint-derived (not stored) so it survives — but with no identity bound and an empty vault the device is a blank slate: a new owner types their handle to attest fresh, or JOINs another fleet. This is `[]n` + `[]u` combined, exposed as a real (non-dev-chord) action for the Security page + the removed-device "start fresh" path; the `-1` broadcast signal tells the Android host to drop its sticky session too.
-    fn clean_device_for_reuse(&mut self) {
-        let count = Self::dev_wipe_vault_files("clean");
+    /// Drop every identity-flavoured RAM slot and land on `Launch(Fresh)` — the in-memory half of a wipe (`clean_device_for_reuse` deletes the vault files around it).
+    fn forget_identity(&mut self) {
         tohu::clear_session();
         self.session = None;
+        self.our_handle = None;
+        self.text_undo = None; // drafts typed under this identity don't survive it
         self.private_s = crate::crypto::blind::PrivateS::None; // zeroized on overwrite
         self.contacts.clear();
         self.friendship_chains.clear();
+        self.outbox_drain.clear();
         if let Ok(mut pks) = self.contact_pubkeys.lock() {
             pks.clear();
         }
         self.storage = None; // next attest re-opens a fresh vault
-        // EVERY identity-flavoured RAM slot dies here (observed: one identity's avatar surfaced under a different identity after a wipe — the in-place reset only cleared what it knew about, and the settings cache kept feeding the OLD identity's avatar pin + name into the new session's pongs and wall sync). Desktop re-execs below anyway; Android's in-place reset is exactly this list, so the list must be COMPLETE.
+        // EVERY identity-flavoured RAM slot dies here (observed: one identity's avatar surfaced under a different identity after a wipe — the in-place reset only cleared what it knew about, and the settings cache kept feeding the OLD identity's avatar pin + name into the new session's pongs and wall sync). Desktop re-execs after a wipe anyway; Android's in-place reset is exactly this list, so the list must be COMPLETE.
         self.fleet_settings = None; // the big one: cached profile.avatar_pin / profile.name of the OLD identity
         self.device_avatar_pixels = None;
+        self.device_avatar_anim = None;
         self.device_avatar_scaled = None;
         self.device_avatar_scaled_diameter = 0;
         self.avatar_set_rx = None; // an in-flight avatar pick must not install under the next identity
+        self.avatar_framing_rx = None;
+        self.avatar_framing = None;
         self.pending_fleet_key = None;
         self.probed_session = None;
         self.probed_handle = None;
@@ -15373,6 +18746,43 @@ impl PhotonApp {
         self.pending_broadcast_signal = -1; // Android: drop the sticky session broadcast
         self.state = AppState::Launch(LaunchState::Fresh);
         self.refocus_handle_select_all();
+    }
+
+    /// The network half of "Remove & shred": refuse on the fleet's last member, else publish this device's self-signed departure. `Ok` = the departure landed and the caller may wipe; `Err` carries the toast, and NOTHING was touched — a wipe without a landed departure would leave the fleet forever listing a device whose keys are gone.
+    /// The avatar's wall slot is deliberately left alone: it's fleet-wide, and deleting it here would blank the avatar on every surviving sibling. The survivors' removal heal (`rotate_avatar_pin`) re-uploads under a fresh pin and deletes the old slot, which is what actually revokes this device's copy of the pin.
+    fn retire_device(&mut self, exit: &mut dyn crate::network::fgtw::fleet::Departure) -> Result<(), &'static str> {
+        let (Some(hp), Some(kp)) = (self.session.as_ref().map(|s| s.handle_proof), self.device_keypair.clone()) else {
+            crate::log("SECURITY: no session/keypair to depart with — NOT wiping");
+            return Err("No signed-in identity to remove — use Shred instead.");
+        };
+        // LAST-MEMBER GATE (identity never dies — supersedes lifecycle D3's LastRites): the fleet's final member cannot sign out, full stop. There is no terminal op — an identity always lives somewhere (the worker refuses zero-member folds too, so this isn't just UI courtesy). Want out of this hardware? Add another device first, then retire this one. A fetch failure counts as last (fail toward refusal, never past it).
+        let last = match exit.member_count(&hp) {
+            Ok(n) => n <= 1,
+            Err(e) => {
+                crate::logf!("SECURITY: member count fetch failed ({}) — treating as last device", e);
+                true
+            }
+        };
+        if last {
+            crate::log("SECURITY: last member — sign-out refused (an identity must live somewhere)");
+            return Err("This is your identity's last device — it can't sign out. Add another device first, then retire this one.");
+        }
+        match exit.depart(&kp, &hp) {
+            Ok(()) => {
+                crate::log("SECURITY: departed the fleet chain (self-signed remove) — wiping");
+                Ok(())
+            }
+            Err(e) => {
+                crate::logf!("SECURITY: fleet departure failed ({}) — NOT wiping", e);
+                Err("Couldn't sign out of the fleet — nothing wiped. Check connection and retry.")
+            }
+        }
+    }
+
+    /// Fully clean this device for a new owner / a fresh identity: nuke the on-disk vault (all `.vsf` — contacts, chains, keypairs, the cached fleet key) AND clear the tohu session (identity_seed + vault_seed + handle_proof), drop all in-memory state, and drop back to the attest screen. The device KEY is fingerprint-derived (not stored) so it survives — but with no identity bound and an empty vault the device is a blank slate: a new owner types their handle to attest fresh, or JOINs another fleet. This is `[]n` + `[]u` combined, exposed as a real (non-dev-chord) action for the Security page + the removed-device "start fresh" path; the `-1` broadcast signal tells the Android host to drop its sticky session too.
+    fn clean_device_for_reuse(&mut self) {
+        let count = Self::dev_wipe_vault_files("clean");
+        self.forget_identity();
         crate::storage::device_binding::clear();
         crate::logf!("CLEAN: wiped {} vault file(s) + cleared session — device is a blank slate, ready to attest fresh or join another fleet", count);
         // Full process restart (desktop): the in-place reset leaves launch half-initialized (the wordmark went missing after a shred) and a wiped process still holds freed secrets in reachable memory anyway — exec into a pristine self via the same machinery a self-update uses. Android can't exec; its in-place reset stands.
@@ -15477,6 +18887,12 @@ impl PhotonApp {
                 paint::DEBUG_SHOW_FADE.store(!cur, Ordering::Relaxed);
                 eprintln!("[]d screen-decay = {}", !cur);
             }
+            'v' => {
+                // Cycle the runtime log threshold one step more verbose (Error → Warn → Info → Debug → Trace → back to Error), so the per-packet PT trace can be switched on for just the stretch being debugged and off again without a rebuild.
+                let next = crate::log_threshold().more_verbose();
+                crate::set_log_threshold(next);
+                eprintln!("[]v log-threshold = {:?}", next);
+            }
             'b' => {
                 let cur = paint::DEBUG_SHOW_OPAQUE_SCAN.load(Ordering::Relaxed);
                 paint::DEBUG_SHOW_OPAQUE_SCAN.store(!cur, Ordering::Relaxed);
@@ -15502,6 +18918,8 @@ impl PhotonApp {
                 // De-attest — clear the tohu session (identity_seed/vault_seed/handle_proof) and drop back to the attest screen, leaving the vault on disk intact. The identity is deterministic from the handle, so re-typing it re-derives the same roots. Mirror of []n: []u forgets WHO you are, []n forgets WHAT you've stored. Only fires in development builds.
                 tohu::clear_session();
                 self.session = None;
+                self.our_handle = None;
+                self.text_undo = None; // drafts typed under this identity don't survive it
                 self.private_s = crate::crypto::blind::PrivateS::None; // zeroized on overwrite — no identity, no S
                 self.pending_broadcast_signal = -1; // Android: drop the sticky session broadcast.
                 self.state = AppState::Launch(LaunchState::Fresh);
@@ -15647,12 +19065,36 @@ fn settings_page_rows(page: SettingsPage) -> usize {
     }
 }
 
+/// The Manage page "Keep" pill's cycle: follow the settings default, then explicit per-conversation policies from loosest to tightest.
+const RETENTION_PRESETS: [Option<crate::types::Retention>; 6] = [
+    None,
+    Some(crate::types::Retention { keep_last: 0, keep_days: 0 }),
+    Some(crate::types::Retention { keep_last: 1 << 10, keep_days: 0 }),
+    Some(crate::types::Retention { keep_last: 0, keep_days: 90 }),
+    Some(crate::types::Retention { keep_last: 0, keep_days: 30 }),
+    Some(crate::types::Retention { keep_last: 0, keep_days: 7 }),
+];
+
+/// Short human form of a conversation's retention for the Manage pill.
+fn retention_label(r: Option<crate::types::Retention>) -> String {
+    match r {
+        None => "default".to_string(),
+        Some(r) if r.is_forever() => "forever".to_string(),
+        Some(r) => match (r.keep_last, r.keep_days) {
+            (n, 0) => format!("last {n}"),
+            (0, d) => format!("{d} days"),
+            (n, d) => format!("last {n}, {d} days"),
+        },
+    }
+}
+
 /// Natural row count per contact-panel page — the scroll-extent input, mirroring [`settings_page_rows`]. About's first rows are consumed by the avatar block (drawn at row height ×N, not text).
 fn contact_page_rows(page: ContactPage) -> usize {
     match page {
         ContactPage::About => 12,
+        ContactPage::Verify => 18,
         ContactPage::Stats => 9,
-        ContactPage::Manage => 6,
+        ContactPage::Manage => 18,
     }
 }
 
@@ -15730,6 +19172,12 @@ fn contact_status_line(
 }
 
 
+/// The away indicator: a small moon-violet disc over an avatar's lower-right rim. Paint it before the avatar (under-blend: first paint wins).
+fn draw_away_badge(canvas: &mut Canvas, cx: f32, cy: f32, avatar_r: f32, clip: Option<fluor::paint::Clip>) {
+    let r = (avatar_r * 0.28).max(2.0);
+    paint::draw_circle(canvas, cx + avatar_r * 0.7, cy + avatar_r * 0.7, r, *theme::AWAY_BADGE_COLOUR, clip);
+}
+
 /// Presence-ring tier (user spec, VSF-authored in theme.rs): cyan = direct in the same room (LAN), green = direct across the WAN, amber = relay-only, grey = offline. LAN = the validated direct path is a private / link-local / ULA address; a same-site GLOBAL v6 path (e.g. two phones on one home /64) still reads green — refining that needs a same-prefix check against our own addresses, later.
 fn ring_tier_colour(c: &crate::types::Contact) -> u32 {
     if !c.is_online {
@@ -15885,6 +19333,24 @@ fn draw_stub_pill_filled(
     }
 }
 
+/// The crop screen's framing overlay, baked into the `w × h` RGB preview before it's drawn: everything outside the circle (centre `(cx, cy)`, radius `r`, image-local pixels) dimmed to 40 %, and a light ring `ru` px wide on its edge.
+fn shade_outside_circle(rgb: &mut [u8], w: usize, h: usize, (cx, cy): (f32, f32), r: f32, ru: f32) {
+    let ring = ru.max(1.0);
+    for y in 0..h {
+        let dy = y as f32 + 0.5 - cy;
+        for x in 0..w {
+            let dx = x as f32 + 0.5 - cx;
+            let d = (dx * dx + dy * dy).sqrt();
+            let px = &mut rgb[(y * w + x) * 3..][..3];
+            if (d - r).abs() < ring {
+                px.fill(0xE0);
+            } else if d > r {
+                px.iter_mut().for_each(|c| *c = (*c as u16 * 2 / 5) as u8);
+            }
+        }
+    }
+}
+
 /// Stamp `hit_id` over every pixel in `[x0, x1) × [y0, y1)` of `hit_map`. Used to reclaim hit-test coverage for a widget that paints visually on top of another but whose hit stamps were overwritten by the under-blend partner's later stamping pass (the contacts-page plus button overlaid inside the textbox). Bbox over-stamp — corners outside the pill silhouette claim a few extra pixels, which dispatches those clicks to the button. Acceptable UX since the area is tiny and inside the pill anyway.
 fn restamp_hit_rect(
     hit_map: &mut [HitId],
@@ -15907,3 +19373,458 @@ fn restamp_hit_rect(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The programmatic send path end to end, minus the socket: a message sent thru `send_message` lands as an outgoing bubble, is persisted (conversation row + pending chain entry), and reaches PT as a `MessageRequest`.
+    #[test]
+    fn send_message_appends_persists_and_hands_to_pt() {
+        use crate::types::{ClutchState, Contact, DevicePubkey, HandleText};
+
+        let device_secret = [0x5Au8; 32];
+        let vault_seed = *ihi::handle_to_hash("send-message-api-test").as_bytes();
+        let app_name = crate::storage::APP;
+        let storage =
+            Arc::new(crate::storage::FlatStorage::new(app_name, vault_seed, device_secret).unwrap());
+
+        let mut app = PhotonApp::new();
+        let identity_seed = [0x51u8; 32];
+        app.session = Some(tohu::SessionIdentity {
+            identity_seed,
+            vault_seed,
+            handle_proof: [0x52; 32],
+        });
+        let our_pid = crate::crypto::clutch::identity_party_id(&identity_seed);
+
+        let peer_addr: std::net::SocketAddr = "192.0.2.7:4383".parse().unwrap();
+        let mut contact = Contact::new(
+            HandleText::new("send-api-peer"),
+            [0x53; 32],
+            DevicePubkey::from_bytes([0x54; 32]),
+        );
+        let eggs: Vec<[u8; 32]> = (0..8).map(|i| [i as u8; 32]).collect();
+        let chains = FriendshipChains::from_clutch(&[our_pid, contact.handle_hash], &eggs);
+        let fid = *chains.id();
+        contact.clutch_state = ClutchState::Complete;
+        contact.friendship_id = Some(fid);
+        contact.validated_path = Some((peer_addr, Instant::now()));
+        app.contacts.push(contact);
+        app.friendship_chains.push((fid, chains));
+        app.storage = Some(storage.clone());
+        let (checker, pt_rx) = crate::network::status::StatusChecker::detached();
+        app.status_checker = Some(checker);
+
+        assert_eq!(app.send_message(0, ""), Err(SendError::Empty));
+        assert_eq!(app.send_message(7, "hello"), Err(SendError::NoSuchContact));
+        assert!(app.contacts[0].messages.is_empty());
+
+        app.send_message(0, "hello").unwrap();
+
+        // Appended as an undelivered outgoing bubble.
+        let msg = app.contacts[0].messages.last().unwrap().clone();
+        assert_eq!(msg.content, "hello");
+        assert!(msg.is_outgoing && !msg.delivered);
+
+        // Handed to PT with the same eagle_time the bubble is keyed by.
+        let request = pt_rx.try_recv().unwrap();
+        assert_eq!(request.peer_addr, peer_addr);
+        assert_eq!(request.eagle_time, msg.timestamp);
+
+        // Persisted: the conversation row and the pending chain entry both come back from disk.
+        let mut reloaded = Contact::new(
+            HandleText::new("send-api-peer"),
+            [0x53; 32],
+            DevicePubkey::from_bytes([0x54; 32]),
+        );
+        crate::storage::contacts::load_messages(&mut reloaded, &storage).unwrap();
+        assert_eq!(reloaded.messages.len(), 1);
+        assert_eq!(reloaded.messages[0].content, "hello");
+        let chains = crate::storage::friendship::load_friendship_chains(&fid, &storage).unwrap();
+        assert_eq!(chains.pending_messages().len(), 1);
+        assert_eq!(chains.pending_messages()[0].eagle_time, msg.timestamp);
+
+        // Clean up the on-disk vault so reruns start fresh.
+        if let Ok([primary, shadow]) = kete::vault_ring_paths(app_name, &vault_seed, &device_secret) {
+            let _ = std::fs::remove_file(primary);
+            let _ = std::fs::remove_file(shadow);
+        }
+    }
+
+    /// The exit flush writes the owed entries (a failed or deferred save) — chains, contact state, conversation rows, the contact-list index — and nothing else; once written they're settled, so a second run (close requested twice) writes nothing.
+    #[test]
+    fn shutdown_flush_persists_dirty_state_idempotently() {
+        use crate::storage::unflushed::{self, Unflushed};
+        use crate::storage::contacts::{load_contact_list, load_contact_state, load_messages};
+        use crate::types::{ChatMessage, Contact, DevicePubkey, HandleText};
+
+        let device_secret = [0x7Cu8; 32];
+        let vault_seed = *ihi::handle_to_hash("shutdown-flush-test").as_bytes();
+        let app_name = crate::storage::APP;
+        let storage = Arc::new(crate::storage::FlatStorage::new(app_name, vault_seed, device_secret).unwrap());
+
+        let mut app = PhotonApp::new();
+        let identity_seed = [0x71u8; 32];
+        let our_pid = crate::crypto::clutch::identity_party_id(&identity_seed);
+        let mut contact = Contact::new(HandleText::new("shutdown-peer"), [0x73; 32], DevicePubkey::from_bytes([0x74; 32]));
+        let eggs: Vec<[u8; 32]> = (0..8).map(|i| [i as u8 ^ 0x70; 32]).collect();
+        let mut chains = FriendshipChains::from_clutch(&[our_pid, contact.handle_hash], &eggs);
+        let fid = *chains.id();
+        chains.set_history_key(Some([0x75; 32]));
+        contact.friendship_id = Some(fid);
+        contact.verified = true;
+        contact.messages.push(ChatMessage::new_with_timestamp("unsaved".to_string(), true, 1_000));
+        app.contacts.push(contact);
+        app.friendship_chains.push((fid, chains));
+        app.storage = Some(storage.clone());
+        let (checker, _pt_rx) = crate::network::status::StatusChecker::detached();
+        app.status_checker = Some(checker);
+
+        // Nothing was saved by the (skipped) mutation paths; they owe these entries.
+        assert!(load_contact_list(&storage).unwrap_or_default().is_empty());
+        let pid = app.contacts[0].handle_hash;
+        let owed = [Unflushed::ContactList, Unflushed::ContactState(pid), Unflushed::Messages(pid), Unflushed::Chains(*fid.as_bytes())];
+        owed.iter().for_each(|e| unflushed::mark(*e));
+
+        let on_disk = || {
+            let index = load_contact_list(&storage).unwrap();
+            let state = load_contact_state(&index[0], &storage).unwrap();
+            let mut rows = Contact::new(HandleText::new("shutdown-peer"), [0x73; 32], DevicePubkey::from_bytes([0x74; 32]));
+            load_messages(&mut rows, &storage).unwrap();
+            let chains = crate::storage::friendship::load_friendship_chains(&fid, &storage).unwrap();
+            (index.len(), state.verified, rows.messages.iter().map(|m| m.content.clone()).collect::<Vec<_>>(), chains.history_key().copied())
+        };
+
+        assert_eq!(app.flush_for_shutdown(), 0);
+        let first = on_disk();
+        assert_eq!(first, (1, true, vec!["unsaved".to_string()], Some([0x75; 32])));
+        assert!(owed.iter().all(|e| !unflushed::owed().contains(e)), "written entries are settled");
+        // A change nobody marked isn't owed, so the flush leaves it alone.
+        app.contacts[0].messages.push(ChatMessage::new_with_timestamp("not owed".to_string(), true, 2_000));
+        assert_eq!(app.flush_for_shutdown(), 0);
+        assert_eq!(on_disk(), first);
+
+        // The exit hook runs the flush once and latches.
+        app.shutdown();
+        assert!(app.shut_down);
+        app.shutdown();
+        assert_eq!(on_disk(), first);
+
+        if let Ok([primary, shadow]) = kete::vault_ring_paths(app_name, &vault_seed, &device_secret) {
+            let _ = std::fs::remove_file(primary);
+            let _ = std::fs::remove_file(shadow);
+        }
+    }
+
+    /// A contact batch pending at exit goes up before quit within a bounded wait: a landed upload settles it; a refused or hung one (or no session to upload with) leaves it owed, and the hung one doesn't hold quit past the timeout.
+    #[test]
+    fn exit_uploads_a_pending_contact_batch_within_a_bounded_wait() {
+        use crate::network::fgtw::Keypair;
+        use crate::storage::cloud::{CloudContact, CloudError};
+        use crate::types::{Contact, DevicePubkey, HandleText};
+        use std::time::{Duration, Instant};
+
+        fn landed(rows: &[CloudContact], _: &[u8; 32], _: &Keypair, _: &[u8; 32]) -> Result<(), CloudError> {
+            assert_eq!(rows.len(), 1, "the upload carries the list as it is at exit");
+            Ok(())
+        }
+        fn refused(_: &[CloudContact], _: &[u8; 32], _: &Keypair, _: &[u8; 32]) -> Result<(), CloudError> {
+            Err(CloudError::Network("offline".to_string()))
+        }
+        fn hung(_: &[CloudContact], _: &[u8; 32], _: &Keypair, _: &[u8; 32]) -> Result<(), CloudError> {
+            std::thread::sleep(Duration::from_secs(30));
+            Ok(())
+        }
+
+        let mut app = PhotonApp::new();
+        app.session = Some(tohu::SessionIdentity { identity_seed: [0x81; 32], vault_seed: [0x81; 32], handle_proof: [0x82; 32] });
+        app.device_keypair = Some(Keypair::from_seed(&[0x83; 32]));
+        app.contacts.push(Contact::new(HandleText::new("exit-peer"), [0x84; 32], DevicePubkey::from_bytes([0x85; 32])));
+
+        // Nothing pending: no upload runs, so even a failing one owes nothing.
+        assert!(!app.settle_cloud_batch_at_exit(refused, EXIT_UPLOAD_TIMEOUT));
+
+        app.cloud_sync.touch(Instant::now());
+        assert!(!app.settle_cloud_batch_at_exit(landed, EXIT_UPLOAD_TIMEOUT));
+        assert_eq!(app.cloud_sync.due_at(), None, "an uploaded batch is settled");
+
+        app.cloud_sync.touch(Instant::now());
+        assert!(app.settle_cloud_batch_at_exit(refused, EXIT_UPLOAD_TIMEOUT));
+
+        app.cloud_sync.touch(Instant::now());
+        let started = Instant::now();
+        assert!(app.settle_cloud_batch_at_exit(hung, Duration::from_millis(100)));
+        assert!(started.elapsed() < Duration::from_secs(5), "quit waited out a hung upload");
+
+        app.cloud_sync.touch(Instant::now());
+        app.session = None;
+        assert!(app.settle_cloud_batch_at_exit(landed, EXIT_UPLOAD_TIMEOUT));
+    }
+
+    /// An image dropped on an open conversation is re-encoded off-thread, then lands as an outgoing image bubble, persists with its JPEG, and reaches PT as one (sharded) message whose ciphertext carries the image.
+    #[test]
+    fn dropped_image_in_conversation_sends_over_pt() {
+        use crate::types::{ClutchState, Contact, DevicePubkey, HandleText};
+
+        let device_secret = [0x6Au8; 32];
+        let vault_seed = *ihi::handle_to_hash("drop-image-test").as_bytes();
+        let app_name = crate::storage::APP;
+        let storage = Arc::new(crate::storage::FlatStorage::new(app_name, vault_seed, device_secret).unwrap());
+
+        let mut app = PhotonApp::new();
+        let identity_seed = [0x6Bu8; 32];
+        app.session = Some(tohu::SessionIdentity { identity_seed, vault_seed, handle_proof: [0x6C; 32] });
+        let our_pid = crate::crypto::clutch::identity_party_id(&identity_seed);
+        let peer_addr: std::net::SocketAddr = "192.0.2.9:4383".parse().unwrap();
+        let mut contact = Contact::new(HandleText::new("drop-image-peer"), [0x6D; 32], DevicePubkey::from_bytes([0x6E; 32]));
+        let eggs: Vec<[u8; 32]> = (0..8).map(|i| [i as u8; 32]).collect();
+        let chains = FriendshipChains::from_clutch(&[our_pid, contact.handle_hash], &eggs);
+        let fid = *chains.id();
+        contact.clutch_state = ClutchState::Complete;
+        contact.friendship_id = Some(fid);
+        contact.validated_path = Some((peer_addr, Instant::now()));
+        app.contacts.push(contact);
+        app.friendship_chains.push((fid, chains));
+        app.storage = Some(storage.clone());
+        let (checker, pt_rx) = crate::network::status::StatusChecker::detached();
+        app.status_checker = Some(checker);
+        app.state = AppState::Conversation;
+        app.active_contact = Some(0);
+
+        let img = image::RgbImage::from_fn(640, 480, |x, y| image::Rgb([x as u8, y as u8, (x + y) as u8]));
+        let path = std::env::temp_dir().join(format!("photon-drop-test-{}.png", std::process::id()));
+        img.save(&path).unwrap();
+        app.drop_file_into_conversation(&path);
+        for _ in 0..500 {
+            app.drain_dropped_files();
+            if !app.contacts[0].messages.is_empty() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        let _ = std::fs::remove_file(&path);
+
+        let msg = app.contacts[0].messages.last().unwrap().clone();
+        assert_eq!(msg.content, crate::types::IMAGE_PLACEHOLDER);
+        assert!(msg.is_outgoing && !msg.delivered);
+        let jpeg = msg.image.clone().expect("image bubble carries its JPEG");
+        assert!(image::load_from_memory(&jpeg).is_ok());
+
+        // One PT message, big enough to be carrying the image (it shards past SINGLE_PACKET_MAX).
+        let request = pt_rx.try_recv().unwrap();
+        assert_eq!(request.eagle_time, msg.timestamp);
+        assert!(request.ciphertext.len() > jpeg.len());
+
+        let mut reloaded = Contact::new(HandleText::new("drop-image-peer"), [0x6D; 32], DevicePubkey::from_bytes([0x6E; 32]));
+        crate::storage::contacts::load_messages(&mut reloaded, &storage).unwrap();
+        assert_eq!(reloaded.messages[0].image.as_deref(), Some(jpeg.as_slice()));
+
+        if let Ok([primary, shadow]) = kete::vault_ring_paths(app_name, &vault_seed, &device_secret) {
+            let _ = std::fs::remove_file(primary);
+            let _ = std::fs::remove_file(shadow);
+        }
+    }
+
+    /// A non-image dropped on an open conversation goes out as a file attachment: the bubble carries the exact bytes + name, they persist with the row, and PT gets one message carrying them.
+    #[test]
+    fn dropped_file_sends_as_attachment() {
+        use crate::types::{ClutchState, Contact, DevicePubkey, HandleText};
+
+        let device_secret = [0x7Au8; 32];
+        let vault_seed = *ihi::handle_to_hash("drop-file-test").as_bytes();
+        let app_name = crate::storage::APP;
+        let storage = Arc::new(crate::storage::FlatStorage::new(app_name, vault_seed, device_secret).unwrap());
+
+        let mut app = PhotonApp::new();
+        let identity_seed = [0x7Bu8; 32];
+        app.session = Some(tohu::SessionIdentity { identity_seed, vault_seed, handle_proof: [0x7C; 32] });
+        let our_pid = crate::crypto::clutch::identity_party_id(&identity_seed);
+        let peer_addr: std::net::SocketAddr = "192.0.2.10:4383".parse().unwrap();
+        let mut contact = Contact::new(HandleText::new("drop-file-peer"), [0x7D; 32], DevicePubkey::from_bytes([0x7E; 32]));
+        let eggs: Vec<[u8; 32]> = (0..8).map(|i| [i as u8; 32]).collect();
+        let chains = FriendshipChains::from_clutch(&[our_pid, contact.handle_hash], &eggs);
+        let fid = *chains.id();
+        contact.clutch_state = ClutchState::Complete;
+        contact.friendship_id = Some(fid);
+        contact.validated_path = Some((peer_addr, Instant::now()));
+        app.contacts.push(contact);
+        app.friendship_chains.push((fid, chains));
+        app.storage = Some(storage.clone());
+        let (checker, pt_rx) = crate::network::status::StatusChecker::detached();
+        app.status_checker = Some(checker);
+        app.state = AppState::Conversation;
+        app.active_contact = Some(0);
+
+        let blob: Vec<u8> = (0..5000u32).map(|i| (i * 31 % 256) as u8).collect();
+        let path = std::env::temp_dir().join(format!("photon-drop-file-{}.bin", std::process::id()));
+        std::fs::write(&path, &blob).unwrap();
+        app.drop_file_into_conversation(&path);
+        for _ in 0..500 {
+            app.drain_dropped_files();
+            if !app.contacts[0].messages.is_empty() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        let _ = std::fs::remove_file(&path);
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+
+        let msg = app.contacts[0].messages.last().unwrap().clone();
+        let file = msg.attachment.clone().expect("file bubble carries its attachment");
+        assert_eq!((file.name.as_str(), file.bytes.as_slice()), (name.as_str(), blob.as_slice()));
+        assert_eq!(msg.content, file.caption());
+        assert!(msg.image.is_none());
+
+        let request = pt_rx.try_recv().unwrap();
+        assert_eq!(request.eagle_time, msg.timestamp);
+        assert!(request.ciphertext.len() > blob.len());
+
+        let mut reloaded = Contact::new(HandleText::new("drop-file-peer"), [0x7D; 32], DevicePubkey::from_bytes([0x7E; 32]));
+        crate::storage::contacts::load_messages(&mut reloaded, &storage).unwrap();
+        assert_eq!(reloaded.messages[0].attachment.as_ref(), Some(&file));
+
+        if let Ok([primary, shadow]) = kete::vault_ring_paths(app_name, &vault_seed, &device_secret) {
+            let _ = std::fs::remove_file(primary);
+            let _ = std::fs::remove_file(shadow);
+        }
+    }
+
+    /// An archived conversation comes back when the friend writes — muted or not — thru the real receive path: one side sends on the chain, the other's `check_status_updates` decrypts and stores it, and the arrival unarchives the chat (a muted one without the unread ring).
+    #[test]
+    fn archived_chat_resurfaces_when_a_message_arrives_even_muted() {
+        use crate::network::status::{StatusChecker, StatusUpdate};
+        use crate::types::{ClutchState, Contact, DevicePubkey, Handle, HandleText};
+
+        let eggs: Vec<[u8; 32]> = (0..8).map(|i| [i as u8 ^ 0x30; 32]).collect();
+        let peer_addr: std::net::SocketAddr = "192.0.2.9:4383".parse().unwrap();
+        // One side of the friendship: a session for `ours` and a Complete contact for `theirs`, on chains both sides derive alike.
+        let side = |ours: &str, theirs: &str, key: u8| {
+            let mut app = PhotonApp::new();
+            let identity_seed = Handle::to_identity_seed(ours);
+            app.session = Some(tohu::SessionIdentity { identity_seed, vault_seed: identity_seed, handle_proof: [key; 32] });
+            let mut contact = Contact::new(HandleText::new(theirs), [!key; 32], DevicePubkey::from_bytes([!key; 32]));
+            let our_pid = crate::crypto::clutch::identity_party_id(&identity_seed);
+            let chains = FriendshipChains::from_clutch(&[our_pid, contact.handle_hash], &eggs);
+            contact.clutch_state = ClutchState::Complete;
+            contact.friendship_id = Some(*chains.id());
+            contact.validated_path = Some((peer_addr, Instant::now()));
+            app.friendship_chains.push((*chains.id(), chains));
+            app.contacts.push(contact);
+            app
+        };
+        let mut sender = side("archive-test-sender", "archive-test-reader", 0xA1);
+        let mut reader = side("archive-test-reader", "archive-test-sender", 0xB2);
+        let (checker, pt_rx) = StatusChecker::detached();
+        sender.status_checker = Some(checker);
+        let (checker, _reader_pt_rx, status_tx) = StatusChecker::detached_with_status();
+        reader.status_checker = Some(checker);
+        reader.contacts[0].archived = true;
+        reader.contacts[0].muted = true;
+
+        sender.send_message(0, "back again").unwrap();
+        let sent = pt_rx.try_recv().unwrap();
+        status_tx
+            .send(StatusUpdate::ChatMessage {
+                conversation_token: sent.conversation_token,
+                prev_msg_hp: sent.prev_msg_hp,
+                ciphertext: sent.ciphertext,
+                timestamp: sent.eagle_time,
+                sender_addr: peer_addr,
+            })
+            .unwrap();
+        reader.check_status_updates();
+
+        let chat = &reader.contacts[0];
+        assert_eq!(chat.messages.last().map(|m| m.content.as_str()), Some("back again"));
+        assert!(!chat.archived, "a muted chat still comes back");
+        assert_eq!(chat.unread_count, 0, "quietly — muting drops the unread ring");
+    }
+
+    /// Removing the avatar evicts the vault copy and every in-memory rendition, stamps the removal for siblings, and hands back the live pin so the wall slot under it is the one deleted.
+    #[test]
+    fn remove_avatar_clears_vault_and_pixels() {
+        let device_secret = [0x71u8; 32];
+        let vault_seed = *ihi::handle_to_hash("remove-avatar-test").as_bytes();
+        let app_name = crate::storage::APP;
+        let storage = Arc::new(crate::storage::FlatStorage::new(app_name, vault_seed, device_secret).unwrap());
+        let identity_seed = [0x72u8; 32];
+        let addr = crate::storage::vault_key("avatar", &identity_seed);
+        storage.write_addr(&addr, b"cached avatar vsf").unwrap();
+
+        let mut app = PhotonApp::new();
+        app.session = Some(tohu::SessionIdentity { identity_seed, vault_seed, handle_proof: [0x73; 32] });
+        app.storage = Some(storage.clone());
+        app.device_keypair = Some(crate::network::fgtw::Keypair::from_seed(&device_secret));
+        let pin = app.ensure_avatar_pin().unwrap();
+        app.device_avatar_pixels = Some(vec![0xFF; 4]);
+        app.device_avatar_scaled = Some(vec![0xFF; 4]);
+        app.device_avatar_scaled_diameter = 64;
+
+        assert_eq!(app.forget_avatar(), Some(pin));
+        assert!(storage.read_addr(&addr).unwrap().is_none());
+        assert!(app.device_avatar_pixels.is_none() && app.device_avatar_scaled.is_none());
+        assert_eq!(app.device_avatar_scaled_diameter, 0);
+        assert!(app.avatar_removed_at().is_some());
+
+        if let Ok([primary, shadow]) = kete::vault_ring_paths(app_name, &vault_seed, &device_secret) {
+            let _ = std::fs::remove_file(primary);
+            let _ = std::fs::remove_file(shadow);
+        }
+    }
+
+    /// Remove & shred's network half, minus the network: the last member is refused with nothing published or cleared, a live sibling lets the signed departure go out, and the RAM clear that follows leaves no identity behind and lands on `Launch(Fresh)`.
+    #[test]
+    fn retire_departs_then_forgets_identity() {
+        use crate::network::fgtw::{fleet::Departure, Keypair};
+        use crate::types::{Contact, DevicePubkey, HandleText};
+
+        struct Recorder {
+            members: usize,
+            departed: Vec<([u8; 32], [u8; 32])>,
+        }
+        impl Departure for Recorder {
+            fn member_count(&mut self, _: &[u8; 32]) -> Result<usize, String> {
+                Ok(self.members)
+            }
+            fn depart(&mut self, device_key: &Keypair, handle_proof: &[u8; 32]) -> Result<(), String> {
+                self.departed.push((device_key.public.to_bytes(), *handle_proof));
+                Ok(())
+            }
+        }
+
+        let mut app = PhotonApp::new();
+        let kp = Keypair::from_seed(&[0x61; 32]);
+        let hp = [0x62; 32];
+        app.session = Some(tohu::SessionIdentity { identity_seed: [0x63; 32], vault_seed: [0x64; 32], handle_proof: hp });
+        app.device_keypair = Some(kp.clone());
+        app.contacts.push(Contact::new(HandleText::new("retire-peer"), [0x65; 32], DevicePubkey::from_bytes([0x66; 32])));
+        app.active_contact = Some(0);
+        app.device_avatar_pixels = Some(vec![0xFF; 4]);
+        app.text_undo = Some((app.hit_counter.wrapping_add(1), super::text_undo::UndoStack::default()));
+
+        // The last member can't sign out: nothing departs, nothing is forgotten.
+        let mut last = Recorder { members: 1, departed: Vec::new() };
+        assert!(app.retire_device(&mut last).is_err());
+        assert!(last.departed.is_empty());
+        assert!(app.session.is_some());
+
+        let mut fleet = Recorder { members: 2, departed: Vec::new() };
+        app.retire_device(&mut fleet).unwrap();
+        assert_eq!(fleet.departed, vec![(kp.public.to_bytes(), hp)]);
+
+        app.forget_identity();
+        assert!(app.session.is_none());
+        assert!(app.storage.is_none());
+        assert!(app.contacts.is_empty() && app.friendship_chains.is_empty());
+        assert!(app.active_contact.is_none());
+        assert!(app.fleet_settings.is_none());
+        assert!(app.device_avatar_pixels.is_none() && app.device_avatar_scaled.is_none());
+        assert!(app.text_undo.is_none(), "no draft history outlives the identity");
+        assert!(matches!(app.state, AppState::Launch(LaunchState::Fresh)));
+
+        // With no identity left there's nothing to depart with — a second press can't publish anything.
+        assert!(app.retire_device(&mut fleet).is_err());
+        assert_eq!(fleet.departed.len(), 1);
+    }
+}
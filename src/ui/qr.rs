@@ -0,0 +1,66 @@
+//! Verification QR — encode a [`crate::types::ContactCard`] to a module matrix and paint it into a fluor `Canvas`.
+//!
+//! Error-correction level M over the card's text form ([`crate::types::ContactCard::to_text`], ~180 chars of base64url), which lands around version 9 — small enough to scan off a phone screen at arm's length, with 15% damage tolerance for glare. Modules snap to whole pixels so the grid never aliases into a blur a camera can't threshold.
+
+use fluor::canvas::Canvas;
+use fluor::coord::Coord;
+use fluor::paint::{self, Clip};
+
+/// Modules of light border the QR spec requires on every side.
+const QUIET_ZONE: usize = 4;
+
+/// A square module grid, row-major, `true` = dark.
+pub struct QrMatrix {
+    pub width: usize,
+    pub dark: Vec<bool>,
+}
+
+/// Encode `payload` as a QR module grid. `None` only if the payload outgrows the largest QR version (a card never does).
+pub fn qr_matrix(payload: &[u8]) -> Option<QrMatrix> {
+    let code = qrcode::QrCode::with_error_correction_level(payload, qrcode::EcLevel::M).ok()?;
+    let width = code.width();
+    let dark = code.to_colors().into_iter().map(|c| c == qrcode::Color::Dark).collect();
+    Some(QrMatrix { width, dark })
+}
+
+/// Paint `m` centred on (cx, cy) within a `size`-pixel square, quiet zone included. The module pitch rounds DOWN to whole pixels (never below 1), so the drawn code may sit a little inside `size`.
+pub fn draw_qr(canvas: &mut Canvas, cx: Coord, cy: Coord, size: Coord, m: &QrMatrix, clip: Option<Clip>) {
+    let modules = m.width + QUIET_ZONE * 2;
+    let pitch = ((size / modules as Coord).floor() as isize).max(1);
+    let total = pitch * modules as isize;
+    let x0 = (cx - total as Coord * 0.5).round() as isize;
+    let y0 = (cy - total as Coord * 0.5).round() as isize;
+    paint::fill_rect(canvas, x0, y0, total, total, *super::theme::QR_LIGHT, clip, None);
+    let origin = QUIET_ZONE as isize * pitch;
+    for (i, _) in m.dark.iter().enumerate().filter(|(_, &d)| d) {
+        let (mx, my) = ((i % m.width) as isize, (i / m.width) as isize);
+        paint::fill_rect(
+            canvas,
+            x0 + origin + mx * pitch,
+            y0 + origin + my * pitch,
+            pitch,
+            pitch,
+            *super::theme::QR_DARK,
+            clip,
+            None,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn card_payload_fits_a_small_qr() {
+        let card = crate::types::ContactCard {
+            handle_proof: [0x5a; 32],
+            party_id: [0x11; 32],
+            device_pubkey: crate::types::DevicePubkey::from_bytes([0x77; 32]),
+        };
+        let m = qr_matrix(card.to_text().unwrap().as_bytes()).unwrap();
+        assert_eq!(m.dark.len(), m.width * m.width);
+        // Version 10 is 57 modules — anything past it gets hard to scan off a phone screen.
+        assert!(m.width <= 57, "card needs a {}-module QR", m.width);
+    }
+}
@@ -30,7 +30,7 @@ const IDX_SEPARATOR: usize = 9;
 pub struct ReadyLayout {
     /// Square region the avatar circle is inscribed in. Width = block width; height = avatar slice height. Circle diameter = the smaller dim (= height in normal aspect ratios).
     pub avatar: PixelRect,
-    /// Handle text slot (optional, settings-gated — off by default for security).
+    /// Handle slot — holds the "copy my handle" share label (the handle itself is never drawn: it derives the identity seed).
     pub handle: PixelRect,
     /// Hint text slot — the avatar update prompt (drag/drop on desktop, tap-to-pick on Android).
     pub hint: PixelRect,
@@ -119,6 +119,15 @@ impl ReadyLayout {
         }
     }
 
+    /// Carry a pixel scroll offset across a change of layout unit (a DPI hop between monitors, a zoom step, a resize). Everything scrollable lays out in units, so the offset that showed a given row at `from_unit` shows the same row at `offset · to_unit / from_unit`. A zero `from_unit` (nothing laid out yet) keeps the offset as-is.
+    pub fn rescale_offset(offset: f32, from_unit: f32, to_unit: f32) -> f32 {
+        if from_unit > 0. {
+            offset * to_unit / from_unit
+        } else {
+            offset
+        }
+    }
+
     /// Avatar circle center + radius, derived from the avatar slot. Circle is inscribed in the smaller dimension of the slot (= height for normal aspect ratios).
     pub fn avatar_center_radius(&self) -> (f32, f32, f32) {
         let w = (self.avatar.x1 - self.avatar.x0) as f32;
@@ -129,3 +138,23 @@ impl ReadyLayout {
         (cx, cy, radius)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale_factor_change_scales_units_and_carries_scroll() {
+        // The same logical 270×540 window on a 1× monitor, then dragged onto a 2× one: the host hands us twice the physical pixels.
+        let low = ReadyLayout::compute(270, 540, 1.);
+        let high = ReadyLayout::compute(540, 1080, 1.);
+        assert!((high.unit_height - 2. * low.unit_height).abs() < 1e-3);
+        assert_eq!(high.row_height, (high.unit_height * 1.5) as usize);
+
+        // A list scrolled 3 rows down stays 3 rows down.
+        let offset = 3. * low.row_height as f32;
+        let carried = ReadyLayout::rescale_offset(offset, low.unit_height, high.unit_height);
+        assert!((carried - 2. * offset).abs() < 1e-3);
+        assert_eq!(ReadyLayout::rescale_offset(offset, 0., high.unit_height), offset);
+    }
+}
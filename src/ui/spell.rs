@@ -0,0 +1,175 @@
+//! Spell-check for the compose box — the maths half. PhotonApp hands the draft's chars to [`misspelled`] and draws a red squiggle under each returned range while compositing; nothing here ever touches what gets sent.
+//!
+//! The word list is bundled (`assets/dictionary/en.txt`, one lowercase word per line) and parsed once on first use. Only plain words are judged: a whitespace chunk holding a handle (`@`), a link (`/`, `:`), a digit, `_`, `#` or a non-ASCII letter is left alone, as are ALL-CAPS acronyms, single letters and Capitalised words mid-sentence (names). A word the list lacks is still accepted when a common suffix strips it back to one the list has (`checked`, `making`, `running`, `parties`), so the list holds stems, not every inflection.
+
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+/// Drafts shorter than this (in chars) aren't checked — a quick "ok see u" reads fine and underlining it is noise. Long-form messages are where a typo slips through.
+pub const MIN_CHARS: usize = 1 << 4;
+
+static WORDS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    include_str!("../../assets/dictionary/en.txt")
+        .lines()
+        .map(str::trim)
+        .filter(|w| !w.is_empty())
+        .collect()
+});
+
+/// Contraction tails stripped before lookup — `don't` checks `do`, `we'll` checks `we`.
+const CONTRACTIONS: [&str; 7] = ["n't", "'s", "'re", "'ve", "'ll", "'d", "'m"];
+
+/// Inflection suffixes tried when the bare word misses, longest first.
+const SUFFIXES: [&str; 7] = ["ing", "est", "ed", "er", "ly", "es", "s"];
+
+fn known(word: &str) -> bool {
+    WORDS.contains(word)
+}
+
+/// `word` (lowercase, apostrophes as `'`) is in the list outright or via a contraction or suffix strip.
+fn in_dictionary(word: &str) -> bool {
+    if known(word) {
+        return true;
+    }
+    for tail in CONTRACTIONS {
+        if let Some(stem) = word.strip_suffix(tail) {
+            // "can't" / "won't" keep an irregular stem.
+            if known(stem) || (tail == "n't" && matches!(stem, "ca" | "wo" | "sha")) {
+                return true;
+            }
+        }
+    }
+    if let Some(stem) = word.strip_suffix("ies").or_else(|| word.strip_suffix("ied")) {
+        if known(&format!("{stem}y")) {
+            return true;
+        }
+    }
+    for suffix in SUFFIXES {
+        let Some(stem) = word.strip_suffix(suffix) else {
+            continue;
+        };
+        if stem.len() < 2 {
+            continue;
+        }
+        // make → making, like → liked; run → running.
+        if known(stem) || known(&format!("{stem}e")) {
+            return true;
+        }
+        let b = stem.as_bytes();
+        if b.len() >= 3 && b[b.len() - 1] == b[b.len() - 2] && known(&stem[..stem.len() - 1]) {
+            return true;
+        }
+    }
+    false
+}
+
+fn is_apostrophe(c: char) -> bool {
+    c == '\'' || c == '\u{2019}'
+}
+
+/// A whitespace chunk holding any of these is a handle, link, tag, number or code — never a misspelling.
+fn skips_chunk(c: char) -> bool {
+    matches!(c, '@' | '/' | ':' | '_' | '#' | '\\' | '=') || c.is_ascii_digit() || (c.is_alphabetic() && !c.is_ascii())
+}
+
+/// Char ranges `(from, to)` of the words in `chars` the dictionary doesn't know, in order. Drafts under [`MIN_CHARS`] return nothing.
+pub fn misspelled(chars: &[char]) -> Vec<(usize, usize)> {
+    let mut out = Vec::new();
+    if chars.len() < MIN_CHARS {
+        return out;
+    }
+    // A sentence starts at the top of the draft and after `.`/`!`/`?`; a Capitalised word anywhere else is taken for a name.
+    let mut sentence_start = true;
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i].is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let chunk_from = i;
+        while i < chars.len() && !chars[i].is_whitespace() {
+            i += 1;
+        }
+        let chunk = &chars[chunk_from..i];
+        let starts_sentence = sentence_start;
+        sentence_start = chunk.last().is_some_and(|c| matches!(c, '.' | '!' | '?'));
+        if chunk.iter().any(|&c| skips_chunk(c)) {
+            continue;
+        }
+        // The word is the letter run inside the chunk's leading/trailing punctuation, apostrophes kept only between letters.
+        let Some(lead) = chunk.iter().position(|c| c.is_ascii_alphabetic()) else {
+            continue;
+        };
+        let Some(tail) = chunk.iter().rposition(|c| c.is_ascii_alphabetic()) else {
+            continue;
+        };
+        let word = &chunk[lead..=tail];
+        if word.len() < 2 || word.iter().any(|&c| !c.is_ascii_alphabetic() && !is_apostrophe(c)) {
+            continue;
+        }
+        if word.iter().filter(|c| c.is_ascii_alphabetic()).all(|c| c.is_ascii_uppercase()) {
+            continue;
+        }
+        if !starts_sentence && word[0].is_ascii_uppercase() {
+            continue;
+        }
+        let lower: String = word
+            .iter()
+            .map(|&c| if is_apostrophe(c) { '\'' } else { c.to_ascii_lowercase() })
+            .collect();
+        if !in_dictionary(&lower) {
+            let from = chunk_from + lead;
+            out.push((from, from + word.len()));
+        }
+    }
+    out
+}
+
+/// Screen x of caret stop `k` in a box whose only geometry query is x → nearest caret stop (`Textbox::cursor_index_from_x`): the smallest x in `[lo, hi]` that maps to `k` or beyond, found by bisection to within a pixel. That x sits at the middle of glyph `k - 1` (nearest-stop rounding flips there), so callers shift by half a glyph to reach the edge.
+pub fn stop_x(index_at: impl Fn(f32) -> usize, k: usize, mut lo: f32, mut hi: f32) -> f32 {
+    while hi - lo > 0.5 {
+        let mid = (lo + hi) * 0.5;
+        if index_at(mid) >= k {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    hi
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(s: &str) -> Vec<char> {
+        s.chars().collect()
+    }
+
+    fn words(s: &str) -> Vec<String> {
+        let t = chars(s);
+        misspelled(&t).iter().map(|&(a, b)| t[a..b].iter().collect()).collect()
+    }
+
+    #[test]
+    fn flags_unknown_words_with_their_char_ranges() {
+        let t = chars("I definately think we shoud meet, don't you?");
+        assert_eq!(misspelled(&t), vec![(2, 12), (22, 27)]);
+        // Inflections, contractions and curly apostrophes resolve to listed stems.
+        assert!(words("She was running late and making plans, parties can’t wait.").is_empty());
+        // Handles, links, numbers, acronyms and mid-sentence names are never judged.
+        assert!(words("ask @nick about https://x.io/qq at 10pm, the NASA thing with Priya").is_empty());
+        // A sentence-initial capital is still checked.
+        assert_eq!(words("Teh meeting moved to tomorrow."), vec!["Teh"]);
+        // Short drafts aren't checked at all.
+        assert!(misspelled(&chars("teh")).is_empty());
+    }
+
+    #[test]
+    fn stop_x_finds_the_flip_point() {
+        // Monospace 10 px glyphs from x = 100, nearest-stop rounding: stop k flips in at the middle of glyph k - 1.
+        let index_at = |x: f32| (((x - 100.) / 10.).round().max(0.)) as usize;
+        let x = stop_x(index_at, 3, 0., 400.);
+        assert!((x - 125.).abs() <= 0.5, "{x}");
+    }
+}
@@ -20,6 +20,9 @@ pub enum AppState {
     /// Existing (attested) device adding another device to the fleet: a words-entry screen — the NEW device displays its pairing words, the user types them here, and a match lights the Bind affordance (orb tap). Entered by tapping the orb on Ready.
     AddDevice,
 
+    /// Framing a new avatar before it's set: the picked / dropped / pasted image fitted on screen with a draggable, zoomable circle over it. Use encodes the circle's square; Cancel (or Esc) drops the image. Returns to Ready either way.
+    AvatarCrop,
+
     /// Settings / About / Help panel — the orb's real destination on Ready. Carries the currently-selected page so the render + layout know which page body to draw. STUB: every page + control renders, but no behaviour is wired.
     Settings(SettingsPage),
 
@@ -94,6 +97,8 @@ impl SettingsPage {
 pub enum ContactPage {
     /// Avatar + everything they've shared with us + identity/trust detail (pinned genesis, their fleet, the loud superseded/ended states).
     About,
+    /// The safety number both sides compare out of band, plus the mark-verified toggle.
+    Verify,
     /// The conversation in numbers — counts, span, delivery, history/chain/connection state. These rows should CONVERGE across our fleet devices; divergence is a sync bug made visible.
     Stats,
     /// Actions on the relationship: Boot (remove + fleet-wide tombstone; ostracism, not erasure). Petname edit and friends land here later.
@@ -102,18 +107,68 @@ pub enum ContactPage {
 
 impl ContactPage {
     /// All pages in rail order.
-    pub const ALL: [ContactPage; 3] = [ContactPage::About, ContactPage::Stats, ContactPage::Manage];
+    pub const ALL: [ContactPage; 4] = [ContactPage::About, ContactPage::Verify, ContactPage::Stats, ContactPage::Manage];
 
     /// Human-readable rail label for this page.
     pub fn label(self) -> &'static str {
         match self {
             ContactPage::About => "About",
+            ContactPage::Verify => "Verify",
             ContactPage::Stats => "Between you",
             ContactPage::Manage => "Manage",
         }
     }
 }
 
+/// A keyboard step on the contacts list (Ready screen).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContactNavKey {
+    Up,
+    Down,
+    Enter,
+}
+
+/// What a [`ContactNavKey`] does to the contacts list: move the keyboard focus to a contact, open one's conversation, or nothing (empty list / Enter with no focus).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContactNavOutcome {
+    Focus(usize),
+    Open(usize),
+    None,
+}
+
+/// What the contacts-list order reads off one listed contact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListEntry {
+    /// Contact index.
+    pub ci: usize,
+    pub pinned: bool,
+    pub unread: bool,
+    pub archived: bool,
+}
+
+/// The contacts list's rows: the default list shows every unarchived contact, the "archived" filter only the archived ones. Pinned contacts lead, then unread conversations float above the rest. Stable — within each group the caller's (vault) order holds, so an unpinned, read list never reshuffles.
+pub fn contact_order(entries: &[ListEntry], show_archived: bool) -> Vec<usize> {
+    let mut sorted: Vec<ListEntry> = entries.iter().copied().filter(|e| e.archived == show_archived).collect();
+    sorted.sort_by_key(|e| (!e.pinned, !e.unread));
+    sorted.into_iter().map(|e| e.ci).collect()
+}
+
+/// Keyboard navigation over the contacts list. `order` is the DISPLAY order (contact indices as the rows are drawn — filtered, pinned first, unread floated); `focused` is the keyboard-focused contact index, if any. Focus is tracked by contact index rather than row position so an unread float reordering the list under it keeps the same person focused; a focused contact that's no longer displayed (filtered out) restarts from the nearest end. Up/Down stop at the ends — no wrap, so holding the key can't spin past the person you were heading for.
+pub fn contact_nav(order: &[usize], focused: Option<usize>, key: ContactNavKey) -> ContactNavOutcome {
+    if order.is_empty() {
+        return ContactNavOutcome::None;
+    }
+    let pos = focused.and_then(|ci| order.iter().position(|&o| o == ci));
+    match (key, pos) {
+        (ContactNavKey::Enter, Some(p)) => ContactNavOutcome::Open(order[p]),
+        (ContactNavKey::Enter, None) => ContactNavOutcome::None,
+        (ContactNavKey::Down, None) => ContactNavOutcome::Focus(order[0]),
+        (ContactNavKey::Up, None) => ContactNavOutcome::Focus(order[order.len() - 1]),
+        (ContactNavKey::Down, Some(p)) => ContactNavOutcome::Focus(order[(p + 1).min(order.len() - 1)]),
+        (ContactNavKey::Up, Some(p)) => ContactNavOutcome::Focus(order[p.saturating_sub(1)]),
+    }
+}
+
 /// Sub-states for the launch screen
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum LaunchState {
@@ -129,6 +184,9 @@ pub enum LaunchState {
     /// Computing handle_proof + announcing to FGTW Show loading spinner, no button
     Attesting,
 
+    /// A transient network failure; the same request re-fires on a backoff (`ui::attest_retry`). `attempt` of `of` retries. Editing the handle cancels back to `Fresh`.
+    Retrying { attempt: u8, of: u8 },
+
     /// Attestation failed - show error message, no button User can edit textbox to return to Fresh
     Error(String),
 }
@@ -141,7 +199,7 @@ impl LaunchState {
 
     /// Check if we're waiting for a network response
     pub fn is_loading(&self) -> bool {
-        matches!(self, LaunchState::Attesting)
+        matches!(self, LaunchState::Attesting | LaunchState::Retrying { .. })
     }
 }
 
@@ -161,3 +219,53 @@ pub enum SearchResult {
     NotFound,
     Error(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arrows_walk_display_order_and_enter_opens_focused_contact() {
+        // Display order floats contact 3 (unread) above 0 and 1.
+        let order = [3, 0, 1];
+        // First Down lands on the top row; two more walk to the bottom and stop there.
+        let mut focus = None;
+        for expect in [3, 0, 1, 1] {
+            match contact_nav(&order, focus, ContactNavKey::Down) {
+                ContactNavOutcome::Focus(ci) => focus = Some(ci),
+                other => panic!("unexpected {other:?}"),
+            }
+            assert_eq!(focus, Some(expect));
+        }
+        assert_eq!(contact_nav(&order, focus, ContactNavKey::Up), ContactNavOutcome::Focus(0));
+        // Enter opens the focused CONTACT index, not its row position.
+        assert_eq!(contact_nav(&order, Some(0), ContactNavKey::Enter), ContactNavOutcome::Open(0));
+        assert_eq!(contact_nav(&order, None, ContactNavKey::Enter), ContactNavOutcome::None);
+        // A filtered-out focus restarts from the end the key points at; an empty list does nothing.
+        assert_eq!(contact_nav(&order, Some(9), ContactNavKey::Up), ContactNavOutcome::Focus(1));
+        assert_eq!(contact_nav(&[], Some(0), ContactNavKey::Down), ContactNavOutcome::None);
+    }
+
+    #[test]
+    fn pinned_contacts_lead_whatever_their_insertion_order() {
+        let entry = |ci, pinned, unread| ListEntry { ci, pinned, unread, archived: false };
+        // Pinned 4 and 1 were added last and in between; unread 2 floats only past the unpinned.
+        let entries = [entry(0, false, false), entry(1, true, false), entry(2, false, true), entry(3, false, false), entry(4, true, true)];
+        assert_eq!(contact_order(&entries, false), vec![4, 1, 2, 0, 3]);
+        // Nothing pinned or unread: vault order untouched.
+        let plain: Vec<ListEntry> = (0..4).map(|ci| entry(ci, false, false)).collect();
+        assert_eq!(contact_order(&plain, false), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn archived_contacts_list_apart_from_the_main_list() {
+        let entry = |ci, unread, archived| ListEntry { ci, pinned: false, unread, archived };
+        let entries = [entry(0, false, false), entry(1, false, true), entry(2, false, false)];
+        assert_eq!(contact_order(&entries, false), vec![0, 2]);
+        assert_eq!(contact_order(&entries, true), vec![1]);
+        // Unarchived by an arrival (the receive path, see photon_app's tests), it's back and floats up on its unread.
+        let back = [entry(0, false, false), entry(1, true, false), entry(2, false, false)];
+        assert_eq!(contact_order(&back, false), vec![1, 0, 2]);
+        assert!(contact_order(&back, true).is_empty());
+    }
+}
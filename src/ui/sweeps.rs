@@ -0,0 +1,265 @@
+//! Background network sweep clocks — when `advance_protocol` last ran each periodic job — grouped so a forced reconnect (the Ctrl+Shift+R shortcut, a resume from sleep) can make every one of them due on the very next tick instead of waiting out a cadence that was scheduled against stale state.
+//! [`PingBackoff`] spaces the presence pings per contact: online contacts ride every sweep, long-offline ones back off.
+//! [`ResumeDetector`] is the resume half: it spots the tick that follows a suspend, so the app reconnects on wake instead of showing every contact stale until the next poll.
+//! [`AwayClock`] turns input idle time into the "away" presence our pongs carry.
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime};
+
+#[derive(Debug, Default)]
+pub struct SweepClocks {
+    /// Last presence ping sweep (`ping_contacts`). `None` until the first sweep. Paired with `last_interaction` to drive the tiered cadence (see `presence_ping_interval`): `tick()` re-pings when due and `wake_at()` schedules the next due sweep so presence refreshes even while idle. Without this, contacts only flipped online when you opened their conversation.
+    pub presence_ping: Option<Instant>,
+    /// Last time an already-running device re-folded its OWN fleet chain to catch a device add/remove it may have missed. The hub `fleet` event is the fast path but best-effort (a dropped WebSocket = a missed add), so this periodic re-fold is the reliable doorbell: without it, an existing device never learns a newly-added sibling until relaunch — it wouldn't answer the new device's presence pings (→ shows it offline) and its Fleet list would stay stale. `None` until the first poll.
+    pub fleet_refold: Option<Instant>,
+    /// Last time we pulsed a background resume to re-fetch a stalled contact's address. Address discovery (`contact.ip`) only refreshes on attest echo / roster / search — there is no periodic re-fetch — so a contact whose initial fetch failed (flaky cellular fgtw) is stuck with no address: its CLUTCH offer can't send, name/avatar (which ride the pong) never arrive, and it loops keygen forever. While any contact is blocked this way we pulse a lightweight background resume on a fast cadence; one success learns the address and fire-on-learn punches + the offer sends. `None` until the first pulse. (Stopgap for the peer-gossip fix, TICKETS T0.)
+    pub stalled_refetch: Option<Instant>,
+    /// Last message-retention sweep (`spawn_retention_sweep`). Not a network job, but scheduled the same way; `force` leaves it alone — a reconnect doesn't make old history any older. `None` until the first sweep.
+    pub retention: Option<Instant>,
+    /// Per-contact presence backoff, consulted by `ping_contacts` for each contact on every sweep.
+    pub ping_backoff: PingBackoff,
+    /// A forced FGTW re-announce (`query_resume`) is owed — set by [`force`](Self::force), taken once by the tick that performs it.
+    fgtw_refresh: bool,
+}
+
+impl SweepClocks {
+    /// Make every sweep due now and owe one FGTW re-announce.
+    pub fn force(&mut self) {
+        self.presence_ping = None;
+        self.fleet_refold = None;
+        self.stalled_refetch = None;
+        self.ping_backoff.clear();
+        self.fgtw_refresh = true;
+    }
+
+    /// `true` once after a [`force`](Self::force) — the caller re-announces to FGTW.
+    pub fn take_fgtw_refresh(&mut self) -> bool {
+        std::mem::take(&mut self.fgtw_refresh)
+    }
+}
+
+/// Per-contact presence-ping schedule. The sweep itself runs on the tiered cadence; this decides who in it actually gets pinged. An online contact is due every sweep, so its ring stays on the base cadence. Each consecutive sweep that finds a contact offline doubles its interval (from the sweep's base, up to `ceiling`), jittered per contact — a list full of long-gone contacts stops costing a burst of pings (plus punches and relay copies) every five seconds, and the ones that are due drift apart instead of coming due together.
+#[derive(Debug, Default)]
+pub struct PingBackoff {
+    /// Pinged device pubkey (`Contact::public_identity`) → (next due, consecutive offline pings). Not the handle_proof: every fleet sibling carries ours, so they'd share one schedule.
+    next: HashMap<[u8; 32], (Instant, u8)>,
+}
+
+impl PingBackoff {
+    /// Whether `who` should be pinged by the sweep running at `now`. Never-pinged = due.
+    pub fn due(&self, who: &[u8; 32], now: Instant) -> bool {
+        self.next.get(who).is_none_or(|(at, _)| now >= *at)
+    }
+
+    /// Record that `who` was just pinged while `online` (or not), with the sweep on `base`. Returns the wait before it's next due — zero for an online contact (next sweep).
+    pub fn pinged(&mut self, who: &[u8; 32], online: bool, base: Duration, ceiling: Duration, now: Instant) -> Duration {
+        if online {
+            self.next.remove(who);
+            return Duration::ZERO;
+        }
+        let streak = self.next.get(who).map_or(0, |(_, n)| n.saturating_add(1));
+        // Shift capped at 10: 1024× even a 1s base is past the 15 min deep tier, and an unbounded shift would overflow the u32.
+        let every = base.saturating_mul(1 << streak.min(10)).min(ceiling.max(base));
+        let wait = crate::jitter_dur(every);
+        self.next.insert(*who, (now + wait, streak));
+        wait
+    }
+
+    /// Forget every backoff — all contacts due on the next sweep.
+    pub fn clear(&mut self) {
+        self.next.clear();
+    }
+}
+
+/// Spots the first tick after the machine slept. Two witnesses, because platforms disagree on whether the monotonic clock runs thru a suspend:
+/// - the wall clock advanced further between ticks than the monotonic clock did (Linux `CLOCK_MONOTONIC`, macOS uptime — frozen while suspended, the wall clock isn't);
+/// - the tick came far later than `wake_at` asked for (Windows, where `Instant` keeps counting thru sleep but nothing ran to tick).
+///
+/// Either gap past `threshold` is a resume. A wall clock stepped *backwards* (NTP) reads as no gap rather than a huge one.
+#[derive(Debug)]
+pub struct ResumeDetector {
+    threshold: Duration,
+    last: Option<(Instant, SystemTime)>,
+    /// The wake `wake_at` last scheduled — a `Cell` because `wake_at` takes `&self`.
+    expected: Cell<Option<Instant>>,
+}
+
+impl ResumeDetector {
+    /// `threshold` zero disables detection.
+    pub fn new(threshold: Duration) -> Self {
+        Self { threshold, last: None, expected: Cell::new(None) }
+    }
+
+    /// Built from `settings.vsf`'s `resume_gap_s`.
+    pub fn from_settings(s: &crate::storage::settings::Settings) -> Self {
+        Self::new(Duration::from_secs(u64::from(s.resume_gap_s)))
+    }
+
+    /// Note the wake the host was just asked for (`None` = sleep until input, no lateness to measure).
+    pub fn expect_wake(&self, at: Option<Instant>) {
+        self.expected.set(at);
+    }
+
+    /// Feed one tick's clocks. `Some(gap)` = this tick follows a suspend of roughly `gap`.
+    pub fn observe(&mut self, now: Instant, wall: SystemTime) -> Option<Duration> {
+        let previous = self.last.replace((now, wall));
+        let expected = self.expected.take();
+        let (then, then_wall) = previous?;
+        let mono_gap = now.saturating_duration_since(then);
+        let unseen = wall.duration_since(then_wall).unwrap_or_default().saturating_sub(mono_gap);
+        let late = expected.map_or(Duration::ZERO, |at| now.saturating_duration_since(at));
+        let gap = unseen.max(late);
+        (!self.threshold.is_zero() && gap >= self.threshold).then_some(gap)
+    }
+}
+
+/// Away presence from input idle time: no keyboard or pointer input for `after` = away, and the next input = back. Fed the same `last_interaction` stamp the sweep cadence reads, which every window event refreshes.
+#[derive(Debug)]
+pub struct AwayClock {
+    after: Duration,
+    away: bool,
+}
+
+impl AwayClock {
+    /// `after` zero = never away.
+    pub fn new(after: Duration) -> Self {
+        Self { after, away: false }
+    }
+
+    /// Built from `settings.vsf`'s `away_after_min`.
+    pub fn from_settings(s: &crate::storage::settings::Settings) -> Self {
+        Self::new(Duration::from_secs(u64::from(s.away_after_min) * 60))
+    }
+
+    pub fn is_away(&self) -> bool {
+        self.away
+    }
+
+    /// When the user goes away if nothing else happens — for `wake_at`, so the flip lands without waiting for an unrelated tick. `None` while already away, with no input seen yet, or when off.
+    pub fn deadline(&self, last_input: Option<Instant>) -> Option<Instant> {
+        (!self.away && !self.after.is_zero()).then(|| last_input.map(|t| t + self.after)).flatten()
+    }
+
+    /// Feed one tick. `Some(away)` when the state just flipped. No input seen yet (`None`) counts as active — a launch isn't idleness.
+    pub fn observe(&mut self, last_input: Option<Instant>, now: Instant) -> Option<bool> {
+        let away = !self.after.is_zero() && last_input.is_some_and(|t| now.saturating_duration_since(t) >= self.after);
+        (away != self.away).then(|| {
+            self.away = away;
+            away
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn force_makes_every_sweep_due_and_owes_one_refresh() {
+        let t = Instant::now();
+        let mut s = SweepClocks { presence_ping: Some(t), fleet_refold: Some(t), stalled_refetch: Some(t), ..Default::default() };
+        assert!(!s.take_fgtw_refresh(), "nothing owed until forced");
+
+        s.force();
+        // `None` = due on the next tick, however far the idle cadence had backed off.
+        let due = |last: Option<Instant>, every: Duration| last.is_none_or(|l| t.duration_since(l) >= every);
+        assert!(due(s.presence_ping, Duration::from_secs(15 * 60)));
+        assert!(due(s.fleet_refold, Duration::from_secs(45)));
+        assert!(due(s.stalled_refetch, Duration::from_secs(15)));
+        assert!(s.take_fgtw_refresh());
+        assert!(!s.take_fgtw_refresh(), "the re-announce fires once per force");
+    }
+
+    #[test]
+    fn offline_contacts_back_off_while_online_ones_ride_every_sweep() {
+        let base = Duration::from_secs(5);
+        let ceiling = Duration::from_secs(15 * 60);
+        let (friend, gone) = ([1u8; 32], [2u8; 32]);
+        let mut b = PingBackoff::default();
+        let t0 = Instant::now();
+
+        // An hour of sweeps on the base cadence: note when each contact actually got pinged.
+        let (mut friend_pings, mut gone_pings) = (Vec::new(), Vec::new());
+        for i in 0..720u64 {
+            let now = t0 + base * i as u32;
+            if b.due(&friend, now) {
+                assert_eq!(b.pinged(&friend, true, base, ceiling, now), Duration::ZERO);
+                friend_pings.push(now);
+            }
+            if b.due(&gone, now) {
+                b.pinged(&gone, false, base, ceiling, now);
+                gone_pings.push(now);
+            }
+        }
+        assert_eq!(friend_pings.len(), 720, "online stays on the base cadence");
+
+        let gaps: Vec<Duration> = gone_pings.windows(2).map(|w| w[1] - w[0]).collect();
+        // Doubling beats the 50–100% jitter, so gaps never shrink while climbing — and they do climb, to the ceiling's range.
+        assert!(gaps.windows(2).take(4).all(|w| w[1] >= w[0]), "{gaps:?}");
+        assert!(gaps.iter().all(|g| *g <= ceiling + base), "capped: {gaps:?}");
+        assert!(*gaps.last().unwrap() >= ceiling / 2, "{gaps:?}");
+        assert!(gone_pings.len() < 20);
+
+        // Coming back online resets it; a forced reconnect makes everyone due.
+        let now = t0 + Duration::from_secs(3600);
+        b.pinged(&gone, true, base, ceiling, now);
+        assert!(b.due(&gone, now));
+        b.pinged(&gone, false, base, ceiling, now);
+        b.clear();
+        assert!(b.due(&gone, now));
+    }
+
+    #[test]
+    fn resume_is_a_gap_the_clocks_cannot_explain() {
+        let secs = Duration::from_secs;
+        let mut d = ResumeDetector::new(secs(30));
+        let (t0, w0) = (Instant::now(), SystemTime::UNIX_EPOCH + secs(1_700_000_000));
+        assert_eq!(d.observe(t0, w0), None, "first tick has nothing to compare");
+
+        // Ordinary idle: both clocks advance together, even by a long while.
+        assert_eq!(d.observe(t0 + secs(900), w0 + secs(900)), None);
+
+        // Linux/macOS suspend: the monotonic clock stood still for 10 minutes the wall clock saw.
+        assert_eq!(d.observe(t0 + secs(901), w0 + secs(1501)), Some(secs(600)));
+
+        // Windows sleep: both clocks ran, but the tick came 2 min after the wake it asked for.
+        d.expect_wake(Some(t0 + secs(906)));
+        assert_eq!(d.observe(t0 + secs(1026), w0 + secs(1626)), Some(secs(120)));
+        // A merely late-ish tick (scheduler jitter) is not a resume; nor is an NTP step backwards.
+        d.expect_wake(Some(t0 + secs(1030)));
+        assert_eq!(d.observe(t0 + secs(1035), w0 + secs(1635)), None);
+        assert_eq!(d.observe(t0 + secs(1036), w0), None);
+
+        // Threshold zero = off.
+        let mut off = ResumeDetector::new(Duration::ZERO);
+        off.observe(t0, w0);
+        assert_eq!(off.observe(t0 + secs(1), w0 + secs(86_400)), None);
+    }
+
+    #[test]
+    fn away_after_idle_and_back_on_input() {
+        let min = Duration::from_secs(60);
+        let mut c = AwayClock::new(min * 5);
+        let t0 = Instant::now();
+        assert_eq!(c.observe(None, t0 + min * 60), None, "no input yet is not idleness");
+
+        let input = Some(t0);
+        assert_eq!(c.deadline(input), Some(t0 + min * 5));
+        assert_eq!(c.observe(input, t0 + min * 4), None);
+        // Crossing the threshold flips once; staying idle doesn't re-report.
+        assert_eq!(c.observe(input, t0 + min * 5), Some(true));
+        assert!(c.is_away());
+        assert_eq!(c.observe(input, t0 + min * 30), None);
+        assert_eq!(c.deadline(input), None);
+        // Any input is back at once.
+        let input = Some(t0 + min * 31);
+        assert_eq!(c.observe(input, t0 + min * 31), Some(false));
+        assert_eq!(c.observe(input, t0 + min * 35), None);
+
+        // Zero = off.
+        let mut off = AwayClock::new(Duration::ZERO);
+        assert_eq!(off.observe(Some(t0), t0 + min * 600), None);
+        assert_eq!(off.deadline(Some(t0)), None);
+    }
+}
@@ -0,0 +1,137 @@
+//! Undo / redo for photon's textboxes (Ctrl+Z, Ctrl+Y / Ctrl+Shift+Z) — the maths half. fluor's `Textbox` edits in place with no history, so PhotonApp snapshots the box (its chars + caret) around every edit that changes the text and hands the BEFORE state to [`UndoStack::record`]; an undo swaps the box back to the newest snapshot and keeps the state it replaced for redo.
+//!
+//! A run of typed characters undoes as one step (the way every editor treats a burst of typing); anything else — a deletion, a paste, a cut, a newline — is its own step. Moving the caret without editing ends the run, so typing, clicking elsewhere and typing again undoes in two steps.
+//!
+//! The launch handle field keeps no history, and the history is dropped whenever the identity is (lock, de-attest, wipe) — what was typed under one identity never undoes back into view under the next.
+
+use std::collections::VecDeque;
+
+/// Undo steps kept per box — enough for a long compose, bounded so a pathological session can't grow it forever. The oldest step falls off first.
+pub const DEPTH: usize = 1 << 6;
+
+/// The text and caret of a box at one moment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    pub chars: Vec<char>,
+    /// Caret as a char index (0 = before the first char).
+    pub cursor: usize,
+}
+
+/// What kind of edit produced a step — only consecutive [`EditKind::Typing`] coalesces.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditKind {
+    /// One plain character typed at the caret.
+    Typing,
+    /// Everything else that changed the text: a deletion, a cut, a paste, a newline.
+    Other,
+}
+
+#[derive(Debug, Default)]
+pub struct UndoStack {
+    undo: VecDeque<Snapshot>,
+    redo: Vec<Snapshot>,
+    /// The last recorded edit was typing and nothing has broken the run since — the next typed char joins its step.
+    typing_run: bool,
+}
+
+impl UndoStack {
+    /// An edit just changed the box; `before` is its state going in. Any new edit discards the redo branch.
+    pub fn record(&mut self, before: Snapshot, kind: EditKind) {
+        self.redo.clear();
+        let joins_run = kind == EditKind::Typing && self.typing_run && !self.undo.is_empty();
+        self.typing_run = kind == EditKind::Typing;
+        if joins_run {
+            return;
+        }
+        if self.undo.len() == DEPTH {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(before);
+    }
+
+    /// The caret moved (or focus left) without an edit — the next typed char starts a new step.
+    pub fn break_run(&mut self) {
+        self.typing_run = false;
+    }
+
+    /// Step back: the state to restore, given the box's `current` state (kept for [`Self::redo`]). `None` when there is nothing to undo.
+    pub fn undo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let prev = self.undo.pop_back()?;
+        self.redo.push(current);
+        self.typing_run = false;
+        Some(prev)
+    }
+
+    /// Step forward again after an undo. `None` when there is nothing to redo (including after any fresh edit).
+    pub fn redo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let next = self.redo.pop()?;
+        self.undo.push_back(current);
+        self.typing_run = false;
+        Some(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snap(s: &str, cursor: usize) -> Snapshot {
+        Snapshot { chars: s.chars().collect(), cursor }
+    }
+
+    #[test]
+    fn typing_run_undoes_in_one_step_and_restores_the_caret() {
+        let mut stack = UndoStack::default();
+        stack.record(snap("hi ", 3), EditKind::Typing);
+        stack.record(snap("hi t", 4), EditKind::Typing);
+        stack.record(snap("hi th", 5), EditKind::Typing);
+        assert_eq!(stack.undo(snap("hi the", 6)), Some(snap("hi ", 3)));
+        assert_eq!(stack.undo(snap("hi ", 3)), None);
+
+        // A caret move between two bursts splits them.
+        let mut stack = UndoStack::default();
+        stack.record(snap("", 0), EditKind::Typing);
+        stack.break_run();
+        stack.record(snap("a", 0), EditKind::Typing);
+        assert_eq!(stack.undo(snap("ba", 1)), Some(snap("a", 0)));
+        assert_eq!(stack.undo(snap("a", 0)), Some(snap("", 0)));
+    }
+
+    #[test]
+    fn range_deletion_undoes_back_to_the_text_and_caret() {
+        let mut stack = UndoStack::default();
+        // Select-all + delete: one step, caret back where it was before the delete.
+        stack.record(snap("draft message", 5), EditKind::Other);
+        assert_eq!(stack.undo(snap("", 0)), Some(snap("draft message", 5)));
+    }
+
+    #[test]
+    fn redo_after_undo_and_a_fresh_edit_drops_it() {
+        let mut stack = UndoStack::default();
+        stack.record(snap("abc", 3), EditKind::Other);
+        let back = stack.undo(snap("a", 1)).unwrap();
+        assert_eq!(back, snap("abc", 3));
+        assert_eq!(stack.redo(back), Some(snap("a", 1)));
+        // Redo re-arms undo.
+        assert_eq!(stack.undo(snap("a", 1)), Some(snap("abc", 3)));
+
+        stack.record(snap("abc", 3), EditKind::Typing);
+        assert_eq!(stack.redo(snap("abcd", 4)), None);
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut stack = UndoStack::default();
+        for i in 0..DEPTH + 5 {
+            stack.record(snap("x", i), EditKind::Other);
+        }
+        let mut steps = 0;
+        let mut cur = snap("x", 0);
+        while let Some(prev) = stack.undo(cur.clone()) {
+            cur = prev;
+            steps += 1;
+        }
+        assert_eq!(steps, DEPTH);
+        assert_eq!(cur.cursor, 5);
+    }
+}
@@ -0,0 +1,128 @@
+//! Word boundaries over a textbox's chars — what Ctrl+←/→ jump to and Ctrl+Backspace/Delete eat (Option on macOS). fluor's `Textbox` only steps one char at a time, so PhotonApp finds the boundary here and moves the caret / deletes up to it.
+//!
+//! A "word" is a run of one [`Class`]: letters and digits in any script (plus `_`, so `snake_case` is one word) are one class, other visible characters (punctuation, symbols, emoji) another, whitespace a third. A jump first crosses any whitespace, then the whole run it lands in — so `foo, bar` steps `foo` → `,` → `bar`, and a run of spaces never costs an extra press.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Class {
+    Space,
+    Word,
+    Punct,
+}
+
+fn class(c: char) -> Class {
+    if c.is_whitespace() {
+        Class::Space
+    } else if c.is_alphanumeric() || c == '_' {
+        Class::Word
+    } else {
+        Class::Punct
+    }
+}
+
+/// Caret stop a word-left jump from `cursor` lands on: back over whitespace, then to the start of the run before it. 0 at the start of the text.
+pub fn prev_boundary(chars: &[char], cursor: usize) -> usize {
+    let mut i = cursor.min(chars.len());
+    while i > 0 && class(chars[i - 1]) == Class::Space {
+        i -= 1;
+    }
+    if i == 0 {
+        return 0;
+    }
+    let run = class(chars[i - 1]);
+    while i > 0 && class(chars[i - 1]) == run {
+        i -= 1;
+    }
+    i
+}
+
+/// Caret stop a word-right jump from `cursor` lands on: forward over whitespace, then to the end of the run after it. `chars.len()` at the end of the text.
+pub fn next_boundary(chars: &[char], cursor: usize) -> usize {
+    let mut i = cursor.min(chars.len());
+    while i < chars.len() && class(chars[i]) == Class::Space {
+        i += 1;
+    }
+    if i == chars.len() {
+        return i;
+    }
+    let run = class(chars[i]);
+    while i < chars.len() && class(chars[i]) == run {
+        i += 1;
+    }
+    i
+}
+
+/// The run a double-click on char `at` selects, as `(from, to)` caret stops — the word, punctuation run or space run holding it. `at` past the end means the last char (a click right of a line's last glyph still means its last word). Empty text → `(0, 0)`.
+pub fn word_at(chars: &[char], at: usize) -> (usize, usize) {
+    let Some(last) = chars.len().checked_sub(1) else {
+        return (0, 0);
+    };
+    let at = at.min(last);
+    let run = class(chars[at]);
+    let mut from = at;
+    while from > 0 && class(chars[from - 1]) == run {
+        from -= 1;
+    }
+    let mut to = at + 1;
+    while to < chars.len() && class(chars[to]) == run {
+        to += 1;
+    }
+    (from, to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(s: &str) -> Vec<char> {
+        s.chars().collect()
+    }
+
+    #[test]
+    fn jumps_stop_at_punctuation_and_cross_space_runs() {
+        let t = chars("hello,   wörld_2 !!");
+        // From the end: "!!" → "wörld_2" → "," → "hello".
+        assert_eq!(prev_boundary(&t, t.len()), 17);
+        assert_eq!(prev_boundary(&t, 17), 9);
+        assert_eq!(prev_boundary(&t, 9), 5);
+        assert_eq!(prev_boundary(&t, 5), 0);
+        assert_eq!(prev_boundary(&t, 0), 0);
+        // And back: the space run after "," is crossed in the same press as the next word.
+        assert_eq!(next_boundary(&t, 0), 5);
+        assert_eq!(next_boundary(&t, 5), 6);
+        assert_eq!(next_boundary(&t, 6), 16);
+        assert_eq!(next_boundary(&t, 16), t.len());
+        assert_eq!(next_boundary(&t, t.len()), t.len());
+    }
+
+    #[test]
+    fn word_delete_spans() {
+        // Ctrl+Backspace eats from the previous boundary to the caret; Ctrl+Delete from the caret to the next.
+        let t = chars("send it   now");
+        let at = t.len();
+        let from = prev_boundary(&t, at);
+        assert_eq!(t[from..at].iter().collect::<String>(), "now");
+        let after: String = t[..from].iter().chain(&t[at..]).collect();
+        assert_eq!(after, "send it   ");
+        let t = chars(after.as_str());
+        assert_eq!(t[prev_boundary(&t, t.len())..].iter().collect::<String>(), "it   ");
+
+        let t = chars("a...b c");
+        assert_eq!(t[1..next_boundary(&t, 1)].iter().collect::<String>(), "...");
+        assert_eq!(t[5..next_boundary(&t, 5)].iter().collect::<String>(), " c");
+        // A caret past the end (stale) clamps instead of panicking.
+        assert_eq!(next_boundary(&t, 99), t.len());
+        assert_eq!(prev_boundary(&t, 99), 6);
+    }
+
+    #[test]
+    fn word_under_a_char() {
+        let t = chars("hi, wörld  x");
+        assert_eq!(word_at(&t, 0), (0, 2));
+        assert_eq!(word_at(&t, 1), (0, 2));
+        assert_eq!(word_at(&t, 2), (2, 3));
+        assert_eq!(word_at(&t, 6), (4, 9));
+        assert_eq!(word_at(&t, 10), (9, 11));
+        assert_eq!(word_at(&t, 99), (11, 12));
+        assert_eq!(word_at(&[], 0), (0, 0));
+    }
+}
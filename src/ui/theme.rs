@@ -47,6 +47,9 @@ pub const VERSION_COLOUR: u32 = 0x20_00_00_00;
 /// Colour for the zoom-percentage watermark at the top of the screen: pure white, α = 64 = 1/4 opacity (twice [`VERSION_COLOUR`]'s 1/8). Painted before the background noise so it reads as a faint top-centre indicator of the current `ru` zoom factor.
 pub const ZOOM_COLOUR: u32 = 0x40_00_00_00;
 
+/// Colour for the perf overlay readout (Ctrl+Shift+F), top-left: pure white, α = 128 = 1/2 opacity — twice the zoom watermark, since it's read for numbers rather than glanced at.
+pub const PERF_COLOUR: u32 = 0x80_00_00_00;
+
 /// Contact name text on the Ready list — near-white.
 pub static CONTACT_NAME_COLOUR: LazyLock<u32> = LazyLock::new(|| c(0x00_F0_F0_F0));
 /// Hairline separating the user section from the contact list — pure white at 1/4 opacity (α=64), the same translucent treatment as the hints + zoom watermark.
@@ -59,6 +62,8 @@ pub static RING_ONLINE_COLOUR: LazyLock<u32> = LazyLock::new(|| c(0x00_00_FF_00)
 pub static RING_OFFLINE_COLOUR: LazyLock<u32> = LazyLock::new(|| c(0x00_28_28_28));
 /// 0xFFB000 amber — the long-standing 0xB0FF00 lime was this value with its bytes swapped, never a deliberate lime.
 pub static RING_RELAY_COLOUR: LazyLock<u32> = LazyLock::new(|| c(0x00_FF_B0_00));
+/// Away badge on an online contact's avatar (idle past their threshold) — a pale moon-violet, clear of every ring tier so "away" never reads as a connection state.
+pub static AWAY_BADGE_COLOUR: LazyLock<u32> = LazyLock::new(|| c(0x00_A0_98_FF));
 pub static SEARCH_RELAY_COLOUR: LazyLock<u32> = LazyLock::new(|| c(0x00_FF_B0_00));
 /// Add-friend result text + the in-flight hourglass: green on success, red on not-found/error.
 pub static SEARCH_FOUND_COLOUR: LazyLock<u32> = LazyLock::new(|| c(0x00_40_E0_40));
@@ -95,6 +100,12 @@ pub static BG_BASE_WARNING: LazyLock<u32> =
     LazyLock::new(|| fluor::theme::fmt(to_display(0x00_30_10_00)));
 /// Thin white rule between conversation messages.
 pub static DIVIDER_COLOUR: LazyLock<u32> = LazyLock::new(|| c(0x00_FF_FF_FF));
+/// Band painted under drag-selected conversation text — a muted blue that keeps both the grey anchor and every relationship colour legible on top.
+pub static SELECTION_COLOUR: LazyLock<u32> = LazyLock::new(|| c(0x00_24_38_70));
+/// Band under the find bar's current hit — the selection blue stepped up, so the one Enter landed on stands out from the other hits.
+pub static FIND_CURRENT_COLOUR: LazyLock<u32> = LazyLock::new(|| c(0x00_3C_5C_B0));
+/// Chip painted under a file message's caption — a quiet dark grey that reads as "a thing you can tap" without competing with the text.
+pub static ATTACHMENT_CHIP_COLOUR: LazyLock<u32> = LazyLock::new(|| c(0x00_30_30_30));
 /// Dim grey for the compose-box placeholder text.
 pub static LABEL_COLOUR: LazyLock<u32> = LazyLock::new(|| c(0x00_80_80_80));
 
@@ -106,3 +117,7 @@ pub static POSTURE_OFF_COLOUR: LazyLock<u32> = LazyLock::new(|| c(0x00_40_40_40)
 
 /// Status-message colour for the "Attesting…" indicator that occupies the error slot while a handle query is in flight. Pure visible white, fully opaque — same slot as [`ERROR_TEXT_COLOUR`] but white instead of red so the user reads it as "neutral status" rather than "something went wrong".
 pub static STATUS_TEXT_COLOUR: LazyLock<u32> = LazyLock::new(|| c(0x00_FF_FF_FF));
+
+/// Verification-QR modules: near-black on a white quiet zone. Scanners want dark-on-light with real contrast, so the code ignores the dark theme — this is the one surface painted light.
+pub static QR_DARK: LazyLock<u32> = LazyLock::new(|| c(0x00_00_00_00));
+pub static QR_LIGHT: LazyLock<u32> = LazyLock::new(|| c(0x00_FF_FF_FF));
@@ -0,0 +1,157 @@
+//! Whole-profile backup and seed rotation against real vaults, and the scripting socket's backup pair. Its own test binary (its own process) so every vault it creates sits under a temp `PHOTON_DATA_DIR` — the override is process-global.
+
+use photon_messenger::headless::HeadlessProfile;
+use photon_messenger::platform::rpc::{dispatch, parse_request};
+use photon_messenger::storage::backup::{export_all, import_all, rotate_encryption, ImportReport};
+use photon_messenger::storage::contacts::{load_all_contacts, load_conversation, save_contact, save_messages};
+use photon_messenger::storage::{self, vault_key, FlatStorage};
+use photon_messenger::types::{Attachment, ChatMessage, Contact, DevicePubkey, HandleText, VoiceMemo, IMAGE_PLACEHOLDER};
+use serde_json::json;
+use std::sync::OnceLock;
+
+/// One temp root for every test in this binary, set before any of them touches storage.
+fn data_root() -> &'static std::path::Path {
+    static ROOT: OnceLock<std::path::PathBuf> = OnceLock::new();
+    ROOT.get_or_init(|| {
+        let root = std::env::temp_dir().join(format!("photon-backup-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        storage::set_data_dir(&root);
+        root
+    })
+}
+
+fn message(content: &str, timestamp: i64, is_outgoing: bool) -> ChatMessage {
+    ChatMessage { content: content.into(), timestamp, is_outgoing, delivered: is_outgoing, ack_hash: None, recovered: false, failed: false, image: None, attachment: None, voice: None }
+}
+
+#[test]
+fn export_then_import_into_empty_vault_reproduces_the_profile() {
+    let root = data_root();
+    let seed = *ihi::handle_to_hash("me-backup-test").as_bytes();
+    let (old_device, new_device) = ([41u8; 32], [42u8; 32]);
+
+    let mut bob = Contact::new(HandleText::new("bob"), [3u8; 32], DevicePubkey::from_bytes([4u8; 32]));
+    bob.petname = "Bob".to_string();
+    bob.messages = vec![
+        message("hi", 100, true),
+        ChatMessage { ack_hash: Some([7; 32]), recovered: true, ..message("yo", 200, false) },
+        ChatMessage { image: Some(vec![0xff, 0xd8, 0xff, 0xe0]), ..message(IMAGE_PLACEHOLDER, 300, true) },
+        ChatMessage { attachment: Some(Attachment { name: "notes.txt".into(), bytes: b"line one".to_vec() }), ..message("notes", 400, false) },
+        ChatMessage { voice: Some(VoiceMemo { duration_ms: 3_200, waveform: vec![10, 200, 90], opus: b"OggS-clip".to_vec() }), ..message("[voice 0:03]", 500, true) },
+    ];
+    let archive = {
+        let old = FlatStorage::new(storage::APP, seed, old_device).unwrap();
+        save_contact(&bob, &old).unwrap();
+        save_messages(&bob, &old).unwrap();
+        old.write_addr(&vault_key("avatar", &seed), b"our avatar blob").unwrap();
+        export_all(&old, "correct horse").unwrap()
+    };
+    let [old_primary, _] = kete::vault_ring_paths(storage::APP, &seed, &old_device).unwrap();
+    assert!(old_primary.starts_with(root), "vault at {}", old_primary.display());
+
+    // Salted per archive: the same profile and passphrase never seal to the same bytes twice.
+    assert_eq!(&archive[..4], b"pbk\x01");
+    let again = export_all(&FlatStorage::new(storage::APP, seed, old_device).unwrap(), "correct horse").unwrap();
+    assert_ne!(&again[4..20], &archive[4..20]);
+
+    let new = FlatStorage::new(storage::APP, seed, new_device).unwrap();
+    assert!(import_all(&new, &archive, "wrong horse").is_err());
+    assert!(import_all(&new, &archive[4..], "correct horse").is_err());
+    let report = import_all(&new, &archive, "correct horse").unwrap();
+    assert_eq!((report.contacts, report.messages), (1, 5));
+
+    let contacts = load_all_contacts(&new);
+    assert_eq!(contacts.len(), 1);
+    assert_eq!(contacts[0].handle_hash, bob.handle_hash);
+    assert_eq!(contacts[0].petname, "Bob");
+    assert_eq!(contacts[0].public_identity.key, bob.public_identity.key);
+    let msgs = load_conversation(&bob.handle_hash, &new).unwrap();
+    assert_eq!(msgs.len(), 5);
+    assert_eq!((msgs[0].content.as_str(), msgs[1].content.as_str()), ("hi", "yo"));
+    assert_eq!(msgs[1].ack_hash, Some([7; 32]));
+    assert!(msgs[1].recovered && msgs[0].delivered);
+    assert_eq!(msgs[0].image, None);
+    assert_eq!(msgs[2].image.as_deref(), Some(&[0xff, 0xd8, 0xff, 0xe0][..]), "the picture travels, not just its placeholder");
+    assert_eq!(msgs[0].attachment, None);
+    let file = msgs[3].attachment.as_ref().expect("the file travels with its caption");
+    assert_eq!((file.name.as_str(), &file.bytes[..]), ("notes.txt", &b"line one"[..]));
+    assert_eq!(msgs[0].voice, None);
+    assert_eq!(msgs[4].voice, bob.messages[4].voice, "the memo travels: clip, length and waveform");
+    assert_eq!(new.read_addr(&vault_key("avatar", &seed)).unwrap().as_deref(), Some(&b"our avatar blob"[..]));
+
+    // A second import merges to nothing: no duplicate contact, no duplicate rows.
+    assert_eq!(import_all(&new, &archive, "correct horse").unwrap(), ImportReport::default());
+    assert_eq!(load_all_contacts(&new).len(), 1);
+}
+
+#[test]
+fn rotate_encryption_moves_every_record_to_the_new_seed_only() {
+    data_root();
+    let old_seed = *ihi::handle_to_hash("me-rotate-old").as_bytes();
+    let new_seed = *ihi::handle_to_hash("me-rotate-new").as_bytes();
+    let device = [43u8; 32];
+
+    let mut bob = Contact::new(HandleText::new("bob"), [5u8; 32], DevicePubkey::from_bytes([6u8; 32]));
+    bob.messages = vec![ChatMessage { image: Some(vec![0xff, 0xd8, 0xff]), ..message("hi", 100, true) }];
+    {
+        let old = FlatStorage::new(storage::APP, old_seed, device).unwrap();
+        save_contact(&bob, &old).unwrap();
+        save_messages(&bob, &old).unwrap();
+        old.write_addr(&vault_key("settings", &old_seed), b"settings blob").unwrap();
+        old.write_addr(&vault_key("fleet_key", &old_seed), &[9u8; 32]).unwrap();
+        old.write_addr(&vault_key("avatar", &old_seed), b"our avatar blob").unwrap();
+        // A record no domain list names — rotation must carry it anyway.
+        old.write_addr(&vault_key("some_future_domain", &[12u8; 32]), b"unlisted").unwrap();
+    }
+
+    let moved = rotate_encryption(old_seed, new_seed, device).unwrap();
+    assert!(moved >= 5, "moved {}", moved);
+
+    // Everything reads back under the new seed, vault-scoped entries re-addressed to it.
+    let new = FlatStorage::new(storage::APP, new_seed, device).unwrap();
+    let contacts = load_all_contacts(&new);
+    assert_eq!(contacts.len(), 1);
+    assert_eq!(contacts[0].handle_hash, bob.handle_hash);
+    let msgs = load_conversation(&bob.handle_hash, &new).unwrap();
+    assert_eq!(msgs.len(), 1);
+    assert_eq!(msgs[0].image.as_deref(), Some(&[0xff, 0xd8, 0xff][..]), "media columns travel with the row");
+    assert_eq!(new.read_addr(&vault_key("some_future_domain", &[12u8; 32])).unwrap().as_deref(), Some(&b"unlisted"[..]));
+    assert!(new.read_addr(&vault_key("settings", &old_seed)).unwrap().is_none(), "seed-scoped entries are moved, not duplicated");
+    assert_eq!(new.read_addr(&vault_key("settings", &new_seed)).unwrap().as_deref(), Some(&b"settings blob"[..]));
+    assert_eq!(new.read_addr(&vault_key("fleet_key", &new_seed)).unwrap().as_deref(), Some(&[9u8; 32][..]));
+    assert_eq!(new.read_addr(&vault_key("avatar", &old_seed)).unwrap().as_deref(), Some(&b"our avatar blob"[..]));
+    drop(new);
+
+    // The old seed's vault is gone: opening it yields a fresh, empty one.
+    let [old_primary, _] = kete::vault_ring_paths(storage::APP, &old_seed, &device).unwrap();
+    assert!(!old_primary.exists());
+    let reopened = FlatStorage::new(storage::APP, old_seed, device).unwrap();
+    assert!(load_all_contacts(&reopened).is_empty());
+    assert!(reopened.read_addr(&vault_key("settings", &old_seed)).unwrap().is_none());
+}
+
+/// The scripting socket's backup pair end to end: export a profile to a file, import that file into the same identity on another device.
+#[test]
+fn backup_pair_over_the_scripting_socket() {
+    let root = data_root();
+    let seed = *ihi::handle_to_hash("me-backup-rpc-test").as_bytes();
+    let token = "0123abcd";
+    let path = root.join("profile.pbk");
+    let request = |method: &str| {
+        let line = json!({"id": method, "token": token, "method": method, "params": {"path": path.to_str().unwrap(), "passphrase": "pw"}}).to_string();
+        parse_request(&line, token).unwrap()
+    };
+
+    let mut old = HeadlessProfile::open(seed, [49u8; 32]).unwrap();
+    old.add_contact("bob", [7u8; 32], DevicePubkey::from_bytes([8u8; 32])).unwrap();
+    let (id, export) = request("export_backup");
+    let exported = dispatch(&mut old, id, &export);
+    assert_eq!(exported["result"]["bytes"].as_u64(), Some(std::fs::metadata(&path).unwrap().len()));
+    drop(old);
+
+    let mut new = HeadlessProfile::open(seed, [50u8; 32]).unwrap();
+    assert!(new.contacts().is_empty());
+    let (id, import) = request("import_backup");
+    assert_eq!(dispatch(&mut new, id, &import)["result"]["contacts"], json!(1));
+    assert_eq!(new.contact("bob").unwrap().handle_proof, [7u8; 32], "the imported contact is live in the profile, not just on disk");
+}
@@ -0,0 +1,176 @@
+//! Vault compaction against real ring files. Its own test binary (its own process) so the whole run can sit under a temp `PHOTON_DATA_DIR` — the override is process-global, and these tests create, wipe and rebuild vaults.
+
+use photon_messenger::storage::contacts::{load_all_contacts, load_conversation, save_contact, save_messages, wipe_messages};
+use photon_messenger::storage::settings::Settings;
+use photon_messenger::storage::{self, compact, vault_key, FlatStorage};
+use photon_messenger::types::{ChatMessage, Contact, DevicePubkey, HandleText};
+use std::sync::OnceLock;
+
+/// One temp root for every test in this binary, set before any of them touches storage.
+fn data_root() {
+    static ROOT: OnceLock<std::path::PathBuf> = OnceLock::new();
+    ROOT.get_or_init(|| {
+        let root = std::env::temp_dir().join(format!("photon-compact-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        storage::set_data_dir(&root);
+        root
+    });
+}
+
+fn ring(seed: &[u8; 32], device: &[u8; 32]) -> [std::path::PathBuf; 2] {
+    kete::vault_ring_paths(storage::APP, seed, device).unwrap()
+}
+
+fn on_disk_size(seed: &[u8; 32], device: &[u8; 32]) -> u64 {
+    ring(seed, device).iter().filter_map(|p| std::fs::metadata(p).ok()).map(|m| m.len()).sum()
+}
+
+fn remove_all(seed: &[u8; 32], device: &[u8; 32]) {
+    for p in ring(seed, device).iter().chain(compact::journal_path(seed, device).ok().as_ref()) {
+        let _ = std::fs::remove_file(p);
+    }
+}
+
+/// Write a record big enough to show up in the file size, then delete it — dead weight only compaction gives back.
+fn leave_dead_bulk(storage: &FlatStorage) {
+    let gone = vault_key("chains", &[9u8; 32]);
+    storage.write_addr(&gone, &vec![0x5a; 1 << 18]).unwrap();
+    storage.delete_addr(&gone).unwrap();
+}
+
+#[test]
+fn compact_shrinks_the_vault_and_keeps_live_records() {
+    data_root();
+    let seed = *ihi::handle_to_hash("me-compact-test").as_bytes();
+    let device = [44u8; 32];
+    remove_all(&seed, &device);
+
+    let mut bob = Contact::new(HandleText::new("bob"), [7u8; 32], DevicePubkey::from_bytes([8u8; 32]));
+    bob.messages = vec![ChatMessage { content: "keep me".into(), timestamp: 100, is_outgoing: true, delivered: true, ack_hash: None, recovered: false, failed: false, image: None, attachment: None, voice: None }];
+    {
+        let storage = FlatStorage::new(storage::APP, seed, device).unwrap();
+        save_contact(&bob, &storage).unwrap();
+        save_messages(&bob, &storage).unwrap();
+        storage.write_addr(&vault_key("settings", &seed), b"live settings").unwrap();
+        // Bulk that gets deleted (a removed friend's chains) is dead weight compaction drops.
+        leave_dead_bulk(&storage);
+        // Live records no contact-index walk reaches — a domain compaction has never heard of, an avatar nobody references — are still live, and must come through untouched.
+        storage.write_addr(&vault_key("some_future_domain", &[10u8; 32]), b"unknown but live").unwrap();
+        storage.write_addr(&vault_key("avatar", &[11u8; 32]), b"unreferenced avatar").unwrap();
+    }
+    let before = on_disk_size(&seed, &device);
+
+    let reclaimed = compact::compact(seed, device).unwrap();
+    assert!(reclaimed > 0);
+    assert_eq!(on_disk_size(&seed, &device), before - reclaimed);
+    assert!(!compact::journal_path(&seed, &device).unwrap().exists());
+    assert!(ring(&seed, &device)[0].starts_with(storage::data_dir_override().unwrap()), "the test vault stays under the temp root");
+
+    let storage = FlatStorage::new(storage::APP, seed, device).unwrap();
+    assert_eq!(load_all_contacts(&storage).len(), 1);
+    assert_eq!(load_conversation(&bob.handle_hash, &storage).unwrap()[0].content, "keep me");
+    assert_eq!(storage.read_addr(&vault_key("settings", &seed)).unwrap().as_deref(), Some(&b"live settings"[..]));
+    assert_eq!(storage.read_addr(&vault_key("some_future_domain", &[10u8; 32])).unwrap().as_deref(), Some(&b"unknown but live"[..]));
+    assert_eq!(storage.read_addr(&vault_key("avatar", &[11u8; 32])).unwrap().as_deref(), Some(&b"unreferenced avatar"[..]));
+    assert!(storage.read_addr(&vault_key("chains", &[9u8; 32])).unwrap().is_none());
+    drop(storage);
+    remove_all(&seed, &device);
+}
+
+#[test]
+fn interrupted_compaction_is_finished_from_its_journal() {
+    data_root();
+    let seed = *ihi::handle_to_hash("me-compact-crash-test").as_bytes();
+    let device = [45u8; 32];
+    remove_all(&seed, &device);
+
+    {
+        let storage = FlatStorage::new(storage::APP, seed, device).unwrap();
+        storage.write_addr(&vault_key("settings", &seed), b"survives").unwrap();
+    }
+    // Simulate a crash right after the journal landed and the old ring was wiped.
+    compact::seal_journal(seed, device).unwrap();
+    for p in &ring(&seed, &device) {
+        let _ = std::fs::remove_file(p);
+    }
+
+    assert!(compact::finish_interrupted(&seed, &device).unwrap());
+    assert!(!compact::journal_path(&seed, &device).unwrap().exists());
+    let storage = FlatStorage::new(storage::APP, seed, device).unwrap();
+    assert_eq!(storage.read_addr(&vault_key("settings", &seed)).unwrap().as_deref(), Some(&b"survives"[..]));
+    drop(storage);
+    remove_all(&seed, &device);
+}
+
+#[test]
+fn an_unfinishable_journal_keeps_the_vault_closed() {
+    data_root();
+    let seed = *ihi::handle_to_hash("me-compact-bad-journal-test").as_bytes();
+    let device = [46u8; 32];
+    remove_all(&seed, &device);
+
+    let journal = compact::journal_path(&seed, &device).unwrap();
+    std::fs::create_dir_all(journal.parent().unwrap()).unwrap();
+    storage::write_file(&journal, b"not a sealed journal", "test journal").unwrap();
+    assert!(compact::open_shared(seed, device).is_err());
+    // Left in place: it may be the only copy of the profile.
+    assert!(journal.exists());
+    remove_all(&seed, &device);
+}
+
+/// The only test in this binary whose `open_shared` succeeds — the owed pass runs on the process's FIRST open.
+#[test]
+fn an_owed_compaction_runs_at_the_first_open() {
+    data_root();
+    let seed = *ihi::handle_to_hash("me-compact-owed-test").as_bytes();
+    let device = [47u8; 32];
+    remove_all(&seed, &device);
+
+    {
+        let storage = FlatStorage::new(storage::APP, seed, device).unwrap();
+        storage.write_addr(&vault_key("settings", &seed), b"kept").unwrap();
+        leave_dead_bulk(&storage);
+    }
+    let before = on_disk_size(&seed, &device);
+    compact::owe();
+    assert!(Settings::load_or_create().compact_owed);
+
+    let storage = compact::open_shared(seed, device).unwrap();
+    assert!(on_disk_size(&seed, &device) < before, "the dead bulk was reclaimed before the engine opened");
+    assert!(!Settings::load_or_create().compact_owed, "paid debts are cleared");
+    assert_eq!(storage.read_addr(&vault_key("settings", &seed)).unwrap().as_deref(), Some(&b"kept"[..]));
+    drop(storage);
+    remove_all(&seed, &device);
+}
+
+/// A cleared conversation is gone from the files themselves once the owed compaction has run — not just unlinked from the index.
+#[test]
+fn a_wiped_conversation_leaves_the_ring_files_at_compaction() {
+    data_root();
+    let seed = *ihi::handle_to_hash("me-compact-wipe-test").as_bytes();
+    let device = [48u8; 32];
+    remove_all(&seed, &device);
+
+    let marker = "wipe-me-";
+    let content = marker.repeat((1 << 18) / marker.len());
+    let mut gale = Contact::new(HandleText::new("gale"), [0x92; 32], DevicePubkey::from_bytes([0x32; 32]));
+    gale.messages = vec![ChatMessage { content: content.clone(), timestamp: 100, is_outgoing: true, delivered: true, ack_hash: None, recovered: false, failed: false, image: Some(vec![0xd8; 1 << 16]), attachment: None, voice: None }];
+    {
+        let storage = FlatStorage::new(storage::APP, seed, device).unwrap();
+        save_contact(&gale, &storage).unwrap();
+        save_messages(&gale, &storage).unwrap();
+        assert_eq!(wipe_messages(&mut gale, &storage, false).unwrap(), 1);
+    }
+
+    let reclaimed = compact::compact(seed, device).unwrap();
+    assert!(reclaimed >= content.len() as u64, "only {} bytes reclaimed — the wiped row's blocks are still on disk", reclaimed);
+    for p in &ring(&seed, &device) {
+        let bytes = std::fs::read(p).unwrap_or_default();
+        assert!(!bytes.windows(marker.len()).any(|w| w == marker.as_bytes()), "wiped text in {}", p.display());
+    }
+    let storage = FlatStorage::new(storage::APP, seed, device).unwrap();
+    assert!(load_conversation(&gale.handle_hash, &storage).unwrap().is_empty());
+    assert_eq!(load_all_contacts(&storage).len(), 1, "the contact itself stays");
+    drop(storage);
+    remove_all(&seed, &device);
+}
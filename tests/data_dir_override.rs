@@ -0,0 +1,37 @@
+//! `PHOTON_DATA_DIR` / `storage::set_data_dir` relocate the whole data root. Lives in its own test binary (its own process) because the override is process-global — set inside the library's unit tests it would move every other test's vault mid-run.
+
+use photon_messenger::storage::{self, contacts, FlatStorage};
+use photon_messenger::types::{Contact, DevicePubkey, HandleText};
+
+#[test]
+fn contact_save_load_lands_under_the_override() {
+    let root = std::env::temp_dir().join(format!("photon-data-dir-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    storage::set_data_dir(&root);
+    assert_eq!(storage::data_dir_override().as_deref(), Some(root.as_path()));
+    assert_eq!(storage::photon_config_dir().unwrap(), root);
+
+    let vault_seed = [0x61u8; 32];
+    let device_secret = [0x62u8; 32];
+    let store = FlatStorage::new(storage::APP, vault_seed, device_secret).unwrap();
+
+    let mut contact = Contact::new(
+        HandleText::new("data-dir-peer"),
+        [0x63; 32],
+        DevicePubkey::from_bytes([0x64; 32]),
+    );
+    contact.petname = "portable".to_string();
+    contacts::save_contact(&contact, &store).unwrap();
+
+    let loaded = contacts::load_all_contacts(&store);
+    assert!(loaded
+        .iter()
+        .any(|c| c.handle_hash == contact.handle_hash && c.petname == "portable"));
+
+    // The vault file itself sits under the overridden root, not the system config dir.
+    let [primary, _shadow] = kete::vault_ring_paths(storage::APP, &vault_seed, &device_secret).unwrap();
+    assert!(primary.starts_with(&root), "vault at {}", primary.display());
+    assert!(primary.exists());
+
+    let _ = std::fs::remove_dir_all(&root);
+}
@@ -0,0 +1,154 @@
+//! A headless profile end to end — attest, add a friend, send — against a stub FGTW and a stub wire. Its own test binary (its own process) so the vaults it creates sit under a temp `PHOTON_DATA_DIR`; the override is process-global.
+
+use photon_messenger::headless::{Fgtw, HeadlessProfile, Wire};
+use photon_messenger::network::fgtw::{Keypair, PeerRecord};
+use photon_messenger::network::status::MessageRequest;
+use photon_messenger::storage::{self, contacts, friendship};
+use photon_messenger::types::{ClutchState, DevicePubkey, FriendshipChains, Handle};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// One temp root for every test in this binary, set before any of them touches storage.
+fn data_root() -> &'static std::path::Path {
+    static ROOT: OnceLock<std::path::PathBuf> = OnceLock::new();
+    ROOT.get_or_init(|| {
+        let root = std::env::temp_dir().join(format!("photon-headless-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        storage::set_data_dir(&root);
+        root
+    })
+}
+
+/// FGTW as a table: who's attested where. Records every attest it's asked for.
+#[derive(Default)]
+struct StubFgtw {
+    directory: HashMap<[u8; 32], PeerRecord>,
+    attested: Vec<[u8; 32]>,
+}
+
+impl StubFgtw {
+    fn register(&mut self, handle: &str, device: [u8; 32], ip: &str) {
+        let handle_proof = Handle::username_to_handle_proof(handle);
+        self.directory.insert(
+            handle_proof,
+            PeerRecord {
+                handle_proof,
+                device_pubkey: DevicePubkey::from_bytes(device),
+                ip: ip.parse().unwrap(),
+                local_ip: None,
+                last_seen: vsf::eagle_time_oscillations(),
+                signature: [0u8; 64],
+            },
+        );
+    }
+}
+
+impl Fgtw for StubFgtw {
+    fn attest(&mut self, session: &tohu::SessionIdentity, _device: &Keypair) -> Result<(), String> {
+        self.attested.push(session.handle_proof);
+        Ok(())
+    }
+
+    fn lookup(&mut self, handle_proof: &[u8; 32]) -> Result<Option<PeerRecord>, String> {
+        Ok(self.directory.get(handle_proof).cloned())
+    }
+}
+
+/// The wire as a list of what was put on it.
+#[derive(Default)]
+struct StubWire(Vec<MessageRequest>);
+
+impl Wire for StubWire {
+    fn send(&mut self, request: MessageRequest) {
+        self.0.push(request);
+    }
+}
+
+#[test]
+fn attest_add_a_friend_and_send_without_a_window() {
+    let root = data_root();
+    let device = Keypair::from_seed(&[0x47; 32]);
+    let bob_device = [0x48u8; 32];
+    let mut fgtw = StubFgtw::default();
+    fgtw.register("headless-bob", bob_device, "192.0.2.9:4383");
+    let mut wire = StubWire::default();
+
+    // Attest, then add bob by handle: he lands at the device and address FGTW has for him.
+    let mut profile = HeadlessProfile::attest("headless-me", &device, &mut fgtw).unwrap();
+    assert_eq!(fgtw.attested, [Handle::username_to_handle_proof("headless-me")]);
+    assert!(profile.add_friend("headless-nobody", &mut fgtw).is_err());
+    let bob = profile.add_friend("headless-bob", &mut fgtw).unwrap().clone();
+    assert_eq!(bob.public_identity.key, bob_device);
+    assert_eq!(bob.ip, Some("192.0.2.9:4383".parse().unwrap()));
+    let [primary, _] = kete::vault_ring_paths(storage::APP, profile.storage().vault_seed(), device.secret.as_bytes()).unwrap();
+    assert!(primary.starts_with(root), "vault at {}", primary.display());
+
+    // Before CLUTCH has finished there's no chain to seal on: the send waits in the outbox.
+    profile.queue_message("headless-bob", "hi bob", 100).unwrap();
+    assert_eq!(profile.flush(&mut wire).unwrap(), 0);
+    assert!(wire.0.is_empty());
+    assert_eq!(profile.pending("headless-bob").unwrap().len(), 1);
+
+    // The ceremony finishes (the app's job): the friendship's chains land in the vault and bob goes Complete.
+    let our_pid = photon_messenger::crypto::clutch::identity_party_id(&contacts::derive_identity_seed("headless-me"));
+    let eggs: Vec<[u8; 32]> = (0..8).map(|i| [i as u8; 32]).collect();
+    let chains = FriendshipChains::from_clutch(&[our_pid, bob.handle_hash], &eggs);
+    let fid = *chains.id();
+    friendship::save_friendship_chains(&chains, profile.storage()).unwrap();
+    let mut done = bob.clone();
+    done.clutch_state = ClutchState::Complete;
+    done.friendship_id = Some(fid);
+    contacts::save_contact(&done, profile.storage()).unwrap();
+    drop(profile);
+
+    // Next run: the queued message goes out on the wire, sealed on the chain, and is in the conversation.
+    let mut profile = HeadlessProfile::attest("headless-me", &device, &mut fgtw).unwrap();
+    assert_eq!(profile.flush(&mut wire).unwrap(), 1);
+    assert!(profile.pending("headless-bob").unwrap().is_empty());
+    let sent = profile.messages("headless-bob").unwrap();
+    assert_eq!(sent.len(), 1);
+    assert!(sent[0].is_outgoing && !sent[0].delivered && sent[0].content == "hi bob");
+
+    let request = &wire.0[0];
+    assert_eq!(request.peer_addr, bob.ip.unwrap());
+    assert_eq!(request.recipient_pubkey, bob_device);
+    assert_eq!(request.eagle_time, sent[0].timestamp);
+    assert_eq!(request.conversation_token, chains.conversation_token);
+    assert!(!request.ciphertext.is_empty() && !request.ciphertext.windows(6).any(|w| w == b"hi bob"));
+    // Saved before the send: the chain holds it pending its ACK.
+    let saved = friendship::load_friendship_chains(&fid, profile.storage()).unwrap();
+    assert_eq!(saved.pending_messages().len(), 1);
+    assert_eq!(saved.pending_messages()[0].eagle_time, request.eagle_time);
+
+    // Nothing owed, nothing more sent.
+    assert_eq!(profile.flush(&mut wire).unwrap(), 0);
+    assert_eq!(wire.0.len(), 1);
+}
+
+#[test]
+fn contact_and_queued_message_round_trip_without_a_window() {
+    data_root();
+    let seed = *ihi::handle_to_hash("me-headless-test").as_bytes();
+    let device = [47u8; 32];
+
+    {
+        let mut profile = HeadlessProfile::open(seed, device).unwrap();
+        assert!(profile.contacts().is_empty());
+        profile.add_contact("bob", [7u8; 32], DevicePubkey::from_bytes([8u8; 32])).unwrap();
+        assert!(profile.queue_message("carol", "nobody home", 100).is_err());
+        profile.queue_message("bob", "hi bob", 100).unwrap();
+        profile.queue_message("bob", "second", 200).unwrap();
+    }
+
+    // A fresh open sees what the GUI would: the contact, and both sends owed in its outbox — not yet in the conversation.
+    let mut profile = HeadlessProfile::open(seed, device).unwrap();
+    assert_eq!(profile.contacts().len(), 1);
+    assert_eq!(profile.contact("bob").unwrap().handle_proof, [7u8; 32]);
+    let pending: Vec<String> = profile.pending("bob").unwrap().into_iter().map(|m| m.content).collect();
+    assert_eq!(pending, ["hi bob", "second"]);
+    assert!(profile.messages("bob").unwrap().is_empty());
+    let owed = contacts::load_outbox(&profile.contact("bob").unwrap().handle_hash, profile.storage()).unwrap();
+    assert_eq!(owed, [(100, "hi bob".to_string()), (200, "second".to_string())]);
+    // Opened without a session, it has nothing to seal as: it only queues.
+    assert_eq!(profile.flush(&mut StubWire::default()).unwrap(), 0);
+}
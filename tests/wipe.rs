@@ -0,0 +1,62 @@
+//! Decrypted buffers are zero before their memory goes back to the allocator. Its own test binary because the check needs a `#[global_allocator]` that watches frees — installing one is process-wide, and the library's own test binary shouldn't run every other test thru it.
+
+use photon_messenger::crypto::chain::{decrypt_layers, derive_salt, encrypt_layers, generate_scratch, Chain, ACTIVE_LINKS, CURRENT_KEY_INDEX, LINK_SIZE};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Forwards to System, but for the one block [`wiped_on_drop`] watches it records whether the block was all zeros as it was freed — read inside dealloc, while the memory is still ours.
+struct WipeCheck;
+
+thread_local! {
+    static WATCH: Cell<usize> = const { Cell::new(0) };
+    static WIPED: Cell<Option<bool>> = const { Cell::new(None) };
+}
+
+unsafe impl GlobalAlloc for WipeCheck {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let _ = WATCH.try_with(|watch| {
+            if watch.get() == ptr as usize {
+                watch.set(0);
+                let block = std::slice::from_raw_parts(ptr, layout.size());
+                let _ = WIPED.try_with(|wiped| wiped.set(Some(block.iter().all(|&b| b == 0))));
+            }
+        });
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOC: WipeCheck = WipeCheck;
+
+/// Drop `owner` (which holds the heap block at `ptr`) and report whether that block was zero when freed.
+fn wiped_on_drop<T>(ptr: *const u8, owner: T) -> bool {
+    WATCH.with(|watch| watch.set(ptr as usize));
+    WIPED.with(|wiped| wiped.set(None));
+    drop(owner);
+    WIPED.with(|wiped| wiped.get()).expect("watched block freed")
+}
+
+#[test]
+fn decrypted_buffers_are_wiped_on_drop() {
+    let bytes: Vec<u8> = (0..ACTIVE_LINKS * LINK_SIZE).map(|i| (i % 256) as u8).collect();
+    let chain = Chain::from_bytes(&bytes).unwrap();
+    let message = b"meet at the usual place";
+    let eagle_time = vsf::EagleTime::from_oscillations(vsf::eagle_time_oscillations());
+    let salt = zeroize::Zeroizing::new(derive_salt(b"", &chain));
+    let scratch = zeroize::Zeroizing::new(generate_scratch(&chain, &salt));
+    let ciphertext = encrypt_layers(message, &chain, &scratch, &eagle_time);
+
+    // A bare Vec leaves the message in freed memory — the control, so the check can fail.
+    let bare = decrypt_layers(&ciphertext, &chain, CURRENT_KEY_INDEX, &scratch, &eagle_time);
+    assert!(!wiped_on_drop(bare.as_ptr(), bare));
+
+    // The receive path's guards: plaintext and scratch pad are zero before their blocks are freed.
+    let plaintext = zeroize::Zeroizing::new(decrypt_layers(&ciphertext, &chain, CURRENT_KEY_INDEX, &scratch, &eagle_time));
+    assert_eq!(&plaintext[..], message);
+    assert!(wiped_on_drop(plaintext.as_ptr(), plaintext));
+    assert!(wiped_on_drop(scratch.as_ptr(), scratch));
+}